@@ -0,0 +1,1027 @@
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use rauncher_core::games::{parse_age, parse_size};
+use rauncher_core::legendary;
+use rauncher_core::providers::PROVIDER_IDS;
+
+const RUNNER_KINDS: [&str; 2] = ["proton", "wine"];
+
+const REPORT_FORMATS: [&str; 2] = ["json", "csv"];
+
+const PLATFORMS: [&str; 2] = ["windows", "mac"];
+
+/// Columns `list --columns` can show
+pub const LIST_COLUMNS: [&str; 6] = ["id", "title", "version", "store", "path", "size"];
+
+const LIST_FORMATS: [&str; 3] = ["csv", "markdown", "json"];
+
+#[derive(Parser)]
+#[command(name = "rauncher")]
+#[command(author, version, about = "Epic Games launcher for Linux written in Rust - GUI-first application", long_about = None)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
+    /// Enable verbose logging
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Write a transcript of this run, shown by `rauncher last-run`
+    #[arg(long, global = true)]
+    pub transcript: bool,
+
+    /// Print install and update progress on stdout as newline-delimited JSON events
+    #[arg(long, global = true)]
+    pub progress_json: bool,
+
+    /// Download on a metered or roaming connection as on any other, whatever `[metered]` says
+    #[arg(long, global = true)]
+    pub allow_metered: bool,
+
+    /// Run the command on another machine's `rauncher serve`, given as host[:port] or a URL
+    #[arg(long, global = true, value_name = "HOST")]
+    pub host: Option<String>,
+
+    /// File holding the token of the `--host` API (defaults to RAUNCHER_SERVE_TOKEN)
+    #[arg(long, global = true, value_name = "PATH", requires = "host")]
+    pub host_token_file: Option<PathBuf>,
+
+    /// Open the GUI fullscreen in Big Picture mode, for TVs and handhelds
+    #[cfg(feature = "gui")]
+    #[arg(long)]
+    pub fullscreen: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Authenticate with Epic Games Store or another store
+    Auth {
+        /// Store to log in to
+        #[arg(long, default_value = "epic", value_parser = PossibleValuesParser::new(PROVIDER_IDS))]
+        provider: String,
+
+        /// Authorization code (or the URL or page text containing it) from the store's login
+        /// page, or the API key for stores that use one
+        #[arg(long, conflicts_with_all = ["logout", "switch", "profiles"])]
+        code: Option<String>,
+
+        /// Log in to Epic through its website instead of a device code, for accounts whose
+        /// two-factor or captcha checks break the device login
+        #[arg(long, conflicts_with_all = ["code", "logout", "switch", "profiles", "status"])]
+        web: bool,
+
+        /// Logout instead of login
+        #[arg(short, long)]
+        logout: bool,
+
+        /// Switch to another stored profile without logging in again
+        #[arg(short, long, value_name = "NAME", conflicts_with = "logout")]
+        switch: Option<String>,
+
+        /// Log in as a new profile, keeping the current one stored
+        #[arg(short, long, value_name = "NAME", conflicts_with_all = ["logout", "switch"])]
+        profile: Option<String>,
+
+        /// List stored profiles
+        #[arg(long, conflicts_with_all = ["logout", "switch", "profile"])]
+        profiles: bool,
+
+        /// Show the current session: account, token expiry and granted scopes
+        #[arg(long, conflicts_with_all = ["code", "logout", "switch", "profile", "profiles"])]
+        status: bool,
+    },
+
+    /// List games in your library
+    List {
+        /// Show installed games only
+        #[arg(short, long)]
+        installed: bool,
+
+        /// Only list games from this store (defaults to every store you're logged in to)
+        #[arg(long, value_parser = PossibleValuesParser::new(PROVIDER_IDS))]
+        provider: Option<String>,
+
+        /// List the library as of the last refresh without contacting the stores
+        #[arg(long)]
+        offline: bool,
+
+        /// Only list games in this category
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Only list favorite games
+        #[arg(long)]
+        favorites: bool,
+
+        /// List the hidden games instead
+        #[arg(long)]
+        hidden: bool,
+
+        /// Only list games whose title or app name contains this, ignoring case
+        #[arg(long, value_name = "TEXT")]
+        filter: Option<String>,
+
+        /// Only list Epic games with a build for this platform
+        #[arg(long, value_parser = PossibleValuesParser::new(PLATFORMS))]
+        platform: Option<String>,
+
+        /// Only list installed games with an update available
+        #[arg(long, conflicts_with = "not_installed")]
+        updates_available: bool,
+
+        /// Only list games that aren't installed
+        #[arg(long, conflicts_with = "installed")]
+        not_installed: bool,
+
+        /// Columns to show, e.g. `id,title,size`, in a table instead of the usual listing
+        #[arg(
+            long,
+            value_delimiter = ',',
+            value_parser = PossibleValuesParser::new(LIST_COLUMNS)
+        )]
+        columns: Vec<String>,
+
+        /// Print every field of the listed games on stdout as CSV, a Markdown table or JSON,
+        /// e.g. for an inventory or a spreadsheet
+        #[arg(
+            long,
+            conflicts_with = "columns",
+            value_parser = PossibleValuesParser::new(LIST_FORMATS)
+        )]
+        format: Option<String>,
+    },
+
+    /// Install a game
+    Install {
+        /// App name of the game to install, or a provider:app_name address; picked from the
+        /// library when left out
+        app_name: Option<String>,
+
+        /// Install a specific build instead of the latest one (pins the game)
+        #[arg(long, value_name = "BUILD_ID")]
+        version: Option<String>,
+
+        /// Store to install the game from (defaults to Epic unless the address names one)
+        #[arg(long, value_parser = PossibleValuesParser::new(PROVIDER_IDS))]
+        provider: Option<String>,
+
+        /// Only show what would be downloaded and how much space it takes
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Shut the computer down once the install is over, after a minute to cancel
+        #[arg(long, conflicts_with_all = ["suspend_after", "dry_run"])]
+        shutdown_after: bool,
+
+        /// Suspend the computer once the install is over, after a minute to cancel
+        #[arg(long, conflicts_with = "dry_run")]
+        suspend_after: bool,
+
+        /// Run a shell command once the install is over; RAUNCHER_FAILED says whether it failed
+        #[arg(long, value_name = "CMD", conflicts_with = "dry_run")]
+        exec_after: Option<String>,
+    },
+
+    /// List the builds available for a game
+    Versions {
+        /// App name of the game
+        app_name: String,
+    },
+
+    /// Pin an installed game to its current build
+    Pin {
+        /// App name of the game to pin
+        app_name: String,
+    },
+
+    /// Allow a pinned game to be updated again
+    Unpin {
+        /// App name of the game to unpin
+        app_name: String,
+    },
+
+    /// Mark a game as a favorite
+    Favorite {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Remove a game from the favorites
+    Unfavorite {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Leave a game out of library listings, e.g. a giveaway you'll never play
+    Hide {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// List a hidden game again
+    Unhide {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Organize games into categories
+    Category {
+        #[command(subcommand)]
+        command: CategoryCommands,
+    },
+
+    /// Give games short names, taken by every command in place of the app name
+    Alias {
+        #[command(subcommand)]
+        command: AliasCommands,
+    },
+
+    /// Launch a game
+    Launch {
+        /// App name of the game to launch, or a provider:app_name address; picked from the
+        /// installed games when left out
+        app_name: Option<String>,
+
+        /// Executable to run instead of the game's own, relative to its install directory
+        #[arg(long, value_name = "PATH")]
+        exe: Option<PathBuf>,
+
+        /// Working directory for the game, relative to its install directory
+        #[arg(long, value_name = "PATH")]
+        cwd: Option<PathBuf>,
+
+        /// Make sure nothing contacts the stores, e.g. on a plane
+        #[arg(long)]
+        offline: bool,
+
+        /// Stay until the game exits, show its output with --verbose, run its post-exit command
+        /// and exit with the game's exit code, e.g. for Steam shortcuts
+        #[arg(long)]
+        wait: bool,
+
+        /// Launch even if the game is already running
+        #[arg(long)]
+        force: bool,
+
+        /// Arguments for the game, after `--`; added to the ones configured for it
+        #[arg(last = true, value_name = "GAME_ARGS")]
+        args: Vec<String>,
+    },
+
+    /// Stop a running game that rauncher launched, and everything it started
+    Stop {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Uninstall a game, or every game matching the filters
+    Uninstall {
+        /// App name of the game to uninstall, or a provider:app_name address
+        #[arg(required_unless_present_any = ["not_played_since", "larger_than"])]
+        app_name: Option<String>,
+
+        /// Also remove the game's Wine prefix and shader cache (saves are backed up first)
+        #[arg(long)]
+        purge: bool,
+
+        /// Uninstall games not played for this long, e.g. 6mo, 8w or 90d
+        #[arg(long, value_name = "AGE", value_parser = parse_age, conflicts_with = "app_name")]
+        not_played_since: Option<chrono::Duration>,
+
+        /// Uninstall games taking more disk space than this, e.g. 50GB
+        #[arg(long, value_name = "SIZE", value_parser = parse_size, conflicts_with = "app_name")]
+        larger_than: Option<u64>,
+
+        /// Delete the game's files for good instead of moving them to the trash
+        #[arg(long)]
+        permanent: bool,
+
+        /// Don't ask for confirmation before uninstalling
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Check an installed game's files against its manifest
+    Verify {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+
+        /// Write the mismatched, missing and extra files to this file, or to stdout with -
+        #[arg(long, value_name = "FILE")]
+        report: Option<PathBuf>,
+
+        /// Report format (defaults to the report file's extension, then JSON)
+        #[arg(long, requires = "report", value_parser = PossibleValuesParser::new(REPORT_FORMATS))]
+        format: Option<String>,
+
+        /// Move files that aren't part of the game, such as leftover mods, to the trash
+        #[arg(long)]
+        delete_extra: bool,
+
+        /// Delete the extra files for good instead of moving them to the trash
+        #[arg(long, requires = "delete_extra")]
+        permanent: bool,
+
+        /// Don't ask for confirmation before deleting extra files
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Protect mods and tweaked files from installs, updates and verify
+    Mods {
+        #[command(subcommand)]
+        command: ModsCommands,
+    },
+
+    /// List the raw entitlements of the Epic account, to diagnose games missing from the library
+    Entitlements {
+        /// Only show entitlements whose name, namespace or catalog item id contains this
+        filter: Option<String>,
+
+        /// Print the entitlements as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show information about a game
+    Info {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Show status and configuration
+    Status,
+
+    /// Check connectivity, login, config, libraries, Wine and disk space, and suggest fixes
+    Doctor,
+
+    /// Check for game updates
+    Update {
+        /// App name of the game to check/update
+        #[arg(required_unless_present = "all")]
+        app_name: Option<String>,
+
+        /// Update all installed games that aren't pinned
+        #[arg(short, long, conflicts_with = "app_name")]
+        all: bool,
+
+        /// Only check for updates, don't install them
+        #[arg(short, long)]
+        check_only: bool,
+
+        /// Only show what the update would download and how much space it takes
+        #[arg(long, conflicts_with = "check_only")]
+        dry_run: bool,
+
+        /// Shut the computer down once the updates are over, after a minute to cancel
+        #[arg(long, conflicts_with_all = ["suspend_after", "check_only", "dry_run"])]
+        shutdown_after: bool,
+
+        /// Suspend the computer once the updates are over, after a minute to cancel
+        #[arg(long, conflicts_with_all = ["check_only", "dry_run"])]
+        suspend_after: bool,
+
+        /// Run a shell command once the updates are over; RAUNCHER_FAILED holds how many
+        /// failed
+        #[arg(long, value_name = "CMD", conflicts_with_all = ["check_only", "dry_run"])]
+        exec_after: Option<String>,
+    },
+
+    /// Manage cloud saves
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    CloudSave {
+        #[command(subcommand)]
+        command: Option<CloudSaveCommands>,
+
+        /// App name of the game
+        #[arg(required = true)]
+        app_name: Option<String>,
+
+        /// Download cloud saves
+        #[arg(short, long)]
+        download: bool,
+
+        /// Upload local saves to cloud
+        #[arg(short, long)]
+        upload: bool,
+
+        /// Snapshot to download, as named by 'cloud-save list' (defaults to the newest)
+        #[arg(short, long, value_name = "ID", requires = "download")]
+        snapshot: Option<String>,
+    },
+
+    /// Back up a game's saves to a local archive
+    Backup {
+        /// App name of the game
+        app_name: String,
+
+        /// List existing backups instead of creating one
+        #[arg(short, long)]
+        list: bool,
+    },
+
+    /// Restore a game's saves from a local backup
+    Restore {
+        /// App name of the game
+        app_name: String,
+
+        /// Backup to restore, as numbered by 'backup --list' (defaults to the newest)
+        #[arg(short, long, value_name = "N")]
+        snapshot: Option<usize>,
+    },
+
+    /// Look for the folder a game keeps its saves in, for games the store doesn't say it for
+    DetectSaves {
+        /// App name of the game
+        app_name: String,
+
+        /// Use a suggestion, as numbered in the list, for cloud saves and backups. Without N
+        /// the first is used, if it's named after the game.
+        #[arg(long, value_name = "N", num_args = 0..=1)]
+        apply: Option<Option<usize>>,
+    },
+
+    /// Import games installed by the official Epic Games Launcher
+    ImportEgl {
+        /// EGL Manifests directory (defaults to the one recorded in the registry)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+
+        /// Only show what would be imported
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Keep installed games in step with the official Epic Games Launcher, both ways: installs,
+    /// updates and uninstalls in either show up in the other
+    EglSync {
+        /// EGL Manifests directory (defaults to the one recorded in the registry)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+
+        /// Only show what would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Take over the games, Epic login and per-game settings of Legendary or Heroic
+    Migrate {
+        /// Launcher to migrate from
+        #[arg(long, value_parser = PossibleValuesParser::new(legendary::SOURCES))]
+        from: String,
+
+        /// The launcher's configuration directory (defaults to where it keeps it)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+
+        /// Only show what would be migrated
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Record rauncher's installed Epic games for Legendary, so it and Heroic can run them from
+    /// where they are
+    Export {
+        /// Format to export in
+        #[arg(long, value_parser = PossibleValuesParser::new(legendary::EXPORT_FORMATS))]
+        format: String,
+
+        /// Configuration directory to write to (defaults to Legendary's; for Heroic, its
+        /// legendaryConfig/legendary directory)
+        #[arg(short, long)]
+        dir: Option<PathBuf>,
+    },
+
+    /// Manage owned Unreal Engine marketplace assets (opt-in)
+    Assets {
+        #[command(subcommand)]
+        command: AssetCommands,
+    },
+
+    /// Show rauncher's log, or the output of a game's last launch
+    Logs {
+        /// Keep printing new lines as they are written
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Show the output of this game's last launch instead
+        #[arg(short, long, value_name = "APP")]
+        game: Option<String>,
+
+        /// Number of lines to show from the end
+        #[arg(short = 'n', long, default_value_t = 100)]
+        lines: usize,
+    },
+
+    /// Show past installs, updates, repairs and uninstalls, newest first
+    History {
+        /// Only show this game's, by app name or provider:app_name address
+        app_name: Option<String>,
+
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+
+        /// Print the history as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Summarize the library, play time and downloads
+    Stats {
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the transcript of the last run: steps taken, files touched, endpoints called
+    LastRun,
+
+    /// Write RSS and iCalendar feeds of Epic's free games and pending updates
+    Feed {
+        /// Country whose free game promotions to list (two-letter code)
+        #[arg(long, default_value = "US")]
+        country: String,
+    },
+
+    /// Browse the Epic Games Store, with prices for your account's country. Games are bought
+    /// on the website: --open shows the first result there.
+    Store {
+        /// Words in the game's title
+        keywords: Vec<String>,
+
+        /// Only games of this genre (Action, Adventure, Casual, Indie, Platformer, Puzzle,
+        /// Racing, RPG, Shooter, Simulation, Sports or Strategy)
+        #[arg(long)]
+        genre: Option<String>,
+
+        /// Only free games, along with the ones given away right now
+        #[arg(long)]
+        free: bool,
+
+        /// Page of results
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        page: u32,
+
+        /// Open the first result's store page in the browser, to buy it
+        #[arg(long)]
+        open: bool,
+    },
+
+    /// Keep a wishlist of store games and check their prices; the daemon checks it on every
+    /// run and notifies sales and giveaways
+    Wishlist {
+        #[command(subcommand)]
+        command: WishlistCommands,
+    },
+
+    /// Show the disk space used by each game, its prefix and shader cache, per library root
+    #[command(group(clap::ArgGroup::new("clean").multiple(true)))]
+    DiskUsage {
+        /// Move every game's shader cache to the trash; they are rebuilt as the games run
+        #[arg(long, group = "clean")]
+        clean_shader_cache: bool,
+
+        /// Delete what's cleaned for good instead of moving it to the trash
+        #[arg(long, requires = "clean")]
+        permanent: bool,
+
+        /// Move install directories left behind by downloads that never finished to the trash
+        #[arg(long, group = "clean")]
+        clean_partial: bool,
+    },
+
+    /// Set up and fix a game's Wine prefix, using the game's runner
+    Prefix {
+        #[command(subcommand)]
+        command: PrefixCommands,
+    },
+
+    /// Manage the prebuilt DXVK/VKD3D shader caches of games run through Wine or Proton
+    ShaderCache {
+        #[command(subcommand)]
+        command: ShaderCacheCommands,
+    },
+
+    /// Manage the Wine and Proton builds (GE-Proton, Wine-GE) games can be run with
+    Runners {
+        #[command(subcommand)]
+        command: RunnerCommands,
+    },
+
+    /// Find game folders rauncher has no record of, and adopt or delete them
+    Orphans {
+        /// Record every folder identified as an owned game as installed, files left in place
+        #[arg(long)]
+        adopt: bool,
+
+        /// Move the folders that aren't adopted to the trash
+        #[arg(long)]
+        delete: bool,
+
+        /// Delete the folders for good instead of moving them to the trash
+        #[arg(long, requires = "delete")]
+        permanent: bool,
+
+        /// Don't ask for confirmation before deleting
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Read or change settings in config.toml by dotted key
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Run unattended: keep installed games updated, refresh the feeds and answer health checks
+    Daemon {
+        /// Serve a health endpoint (GET /health), Prometheus metrics (GET /metrics) and the
+        /// feeds on this address, e.g. 0.0.0.0:9178
+        #[arg(long, value_name = "ADDR")]
+        listen: Option<SocketAddr>,
+
+        /// Minutes between update runs
+        #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Country whose free game promotions go into the feeds (two-letter code)
+        #[arg(long, default_value = "US")]
+        country: String,
+    },
+
+    /// Serve an HTTP API to list, install, update and launch games from other devices
+    Serve {
+        /// Address to listen on; use 0.0.0.0:9178 to accept other devices on the network
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:9178")]
+        listen: SocketAddr,
+
+        /// File holding the token clients must send; RAUNCHER_SERVE_TOKEN, or one generated
+        /// and kept in the data directory, when not given
+        #[arg(long, value_name = "PATH")]
+        token_file: Option<PathBuf>,
+    },
+
+    /// Show and reorder the download queue of `rauncher serve`, on this machine or --host
+    Queue {
+        #[command(subcommand)]
+        command: QueueCommands,
+    },
+
+    /// Launch the GUI
+    #[cfg(feature = "gui")]
+    Gui {
+        /// Open fullscreen in Big Picture mode, for TVs and handhelds
+        #[arg(long)]
+        fullscreen: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Print a setting, or the whole configuration without a key
+    Get {
+        /// Dotted key, e.g. games.Fortnite.env
+        key: Option<String>,
+    },
+
+    /// Change a setting, e.g. `config set games.Fortnite.env.DXVK_HUD fps`
+    Set {
+        /// Dotted key; per-game settings live under games.<app_name>
+        key: String,
+
+        /// New value; read as a number or boolean where the setting is one
+        value: String,
+    },
+
+    /// Return a setting to its default, or remove a per-game entry
+    Unset {
+        /// Dotted key
+        key: String,
+    },
+
+    /// Print the path of the configuration file
+    Path,
+}
+
+#[derive(Subcommand)]
+pub enum PrefixCommands {
+    /// Create and initialize the game's prefix
+    Create {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Run a program inside the prefix, e.g. `prefix run Fortnite -- regedit`
+    Run {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+
+        /// Program and its arguments, after `--`
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+
+    /// Open Wine's configuration dialog for the prefix
+    Winecfg {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Apply winetricks verbs to the prefix, e.g. vcrun2019 d3dcompiler_47
+    Winetricks {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+
+        /// Verbs to install
+        #[arg(required = true)]
+        verbs: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RunnerCommands {
+    /// List the installed runners, or the releases available for download
+    List {
+        /// List the releases published on GitHub instead
+        #[arg(long)]
+        available: bool,
+
+        /// Which builds to list with --available
+        #[arg(
+            long,
+            default_value = "proton",
+            value_parser = PossibleValuesParser::new(RUNNER_KINDS)
+        )]
+        kind: String,
+    },
+
+    /// Download a release into the runners directory
+    Install {
+        /// Release tag, e.g. GE-Proton9-20 (defaults to the newest release)
+        version: Option<String>,
+
+        /// Which builds the release is one of
+        #[arg(
+            long,
+            default_value = "proton",
+            value_parser = PossibleValuesParser::new(RUNNER_KINDS)
+        )]
+        kind: String,
+    },
+
+    /// Move an installed runner to the trash
+    Remove {
+        /// Runner name, as shown by `runners list`
+        name: String,
+
+        /// Delete the runner for good instead of moving it to the trash
+        #[arg(long)]
+        permanent: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ShaderCacheCommands {
+    /// Download a game's shader cache from the configured source if it changed
+    /// (see shader_cache_url in the configuration)
+    Update {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AssetCommands {
+    /// List owned Unreal Engine assets
+    List,
+
+    /// Download an owned Unreal Engine asset
+    Download {
+        /// App name of the asset
+        app_name: String,
+
+        /// Directory to download the asset into
+        #[arg(short, long)]
+        dir: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CategoryCommands {
+    /// List the categories in use
+    List,
+
+    /// Put a game in a category, creating the category if needed
+    Add {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+
+        /// Name of the category
+        category: String,
+    },
+
+    /// Take a game out of a category
+    Remove {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+
+        /// Name of the category
+        category: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AliasCommands {
+    /// List the aliases and the games they stand for
+    List,
+
+    /// Give a game an alias, e.g. `alias add bl3 Catnip`
+    Add {
+        /// The alias
+        alias: String,
+
+        /// App name of the game, a provider:app_name address, or its title
+        app_name: String,
+    },
+
+    /// Drop an alias
+    Remove {
+        /// The alias
+        alias: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WishlistCommands {
+    /// List the wishlisted games with the price seen at the last check
+    List,
+
+    /// Wishlist the store game titled so, or the most relevant match
+    Add {
+        /// Words in the game's title
+        #[arg(required = true)]
+        keywords: Vec<String>,
+    },
+
+    /// Take a game off the wishlist
+    Remove {
+        /// Title of the game
+        #[arg(required = true)]
+        title: Vec<String>,
+    },
+
+    /// Look up current prices, showing the sales and giveaways not seen before
+    Check,
+}
+
+#[derive(Subcommand)]
+pub enum QueueCommands {
+    /// List the running and waiting installs, in queue order
+    List,
+
+    /// Move a queued or running install to the front with high priority; with the
+    /// `priority` download order, a running install of lower priority makes way for it
+    Prioritize {
+        /// App name of the game
+        app_name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CloudSaveCommands {
+    /// List a game's cloud save snapshots with their upload times and sizes
+    List {
+        /// App name of the game
+        app_name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ModsCommands {
+    /// List a game's protected files and folders
+    List {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+    },
+
+    /// Keep a file or folder as it is through installs and updates, and out of verify
+    Protect {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+
+        /// Path relative to the install directory, or an absolute path inside it
+        path: PathBuf,
+    },
+
+    /// Stop protecting a file or folder
+    Unprotect {
+        /// App name of the game, or a provider:app_name address
+        app_name: String,
+
+        /// Path as it was protected
+        path: PathBuf,
+    },
+}
+
+impl Commands {
+    /// The game the command is about, to read aliases and titles in one place
+    pub fn app_name_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Commands::Versions { app_name }
+            | Commands::Pin { app_name }
+            | Commands::Unpin { app_name }
+            | Commands::Favorite { app_name }
+            | Commands::Unfavorite { app_name }
+            | Commands::Hide { app_name }
+            | Commands::Unhide { app_name }
+            | Commands::Verify { app_name, .. }
+            | Commands::Info { app_name }
+            | Commands::Stop { app_name }
+            | Commands::Backup { app_name, .. }
+            | Commands::Restore { app_name, .. }
+            | Commands::DetectSaves { app_name, .. } => Some(app_name),
+            Commands::Install { app_name, .. }
+            | Commands::Launch { app_name, .. }
+            | Commands::Uninstall { app_name, .. }
+            | Commands::Update { app_name, .. } => app_name.as_mut(),
+            Commands::CloudSave {
+                command: Some(CloudSaveCommands::List { app_name }),
+                ..
+            } => Some(app_name),
+            Commands::CloudSave { app_name, .. } => app_name.as_mut(),
+            Commands::Logs { game, .. } => game.as_mut(),
+            Commands::Queue {
+                command: QueueCommands::Prioritize { app_name },
+            } => Some(app_name),
+            Commands::Category { command } => match command {
+                CategoryCommands::Add { app_name, .. }
+                | CategoryCommands::Remove { app_name, .. } => Some(app_name),
+                CategoryCommands::List => None,
+            },
+            Commands::Mods { command } => match command {
+                ModsCommands::List { app_name }
+                | ModsCommands::Protect { app_name, .. }
+                | ModsCommands::Unprotect { app_name, .. } => Some(app_name),
+            },
+            Commands::Prefix { command } => match command {
+                PrefixCommands::Create { app_name }
+                | PrefixCommands::Run { app_name, .. }
+                | PrefixCommands::Winecfg { app_name }
+                | PrefixCommands::Winetricks { app_name, .. } => Some(app_name),
+            },
+            Commands::ShaderCache { command } => match command {
+                ShaderCacheCommands::Update { app_name } => Some(app_name),
+            },
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uninstall_args(args: &[&str]) -> (Option<String>, bool, bool) {
+        let cli = Cli::try_parse_from([&["rauncher", "uninstall"], args].concat()).unwrap();
+        match cli.command {
+            Some(Commands::Uninstall {
+                app_name,
+                permanent,
+                yes,
+                ..
+            }) => (app_name, permanent, yes),
+            _ => panic!("not an uninstall"),
+        }
+    }
+
+    #[test]
+    fn test_uninstall_flags() {
+        assert_eq!(
+            uninstall_args(&["Fortnite"]),
+            (Some("Fortnite".to_string()), false, false)
+        );
+        assert_eq!(
+            uninstall_args(&["Fortnite", "--yes", "--permanent"]),
+            (Some("Fortnite".to_string()), true, true)
+        );
+        assert!(uninstall_args(&["Fortnite", "-y"]).2);
+        // As "Add/Remove Programs" runs it
+        assert!(uninstall_args(&["Fortnite", "--yes"]).2);
+
+        assert!(Cli::try_parse_from(["rauncher", "uninstall", "Fortnite", "--trash"]).is_err());
+        assert!(Cli::try_parse_from(["rauncher", "uninstall", "--yes"]).is_err());
+    }
+
+    #[test]
+    fn test_permanent_needs_something_to_clean() {
+        let parse =
+            |args: &[&str]| Cli::try_parse_from([&["rauncher", "disk-usage"], args].concat());
+        assert!(parse(&["--clean-partial", "--permanent"]).is_ok());
+        assert!(parse(&["--clean-shader-cache", "--clean-partial"]).is_ok());
+        assert!(parse(&["--permanent"]).is_err());
+    }
+}
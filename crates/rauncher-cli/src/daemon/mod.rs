@@ -0,0 +1,329 @@
+//! Unattended mode for servers and containers: periodic update runs, feed refreshes and
+//! wishlist checks, plus health and metrics endpoints and webhooks.
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rauncher_core::notify::webhook::{WebhookEvent, Webhooks};
+use rauncher_core::notify::Notification;
+use rauncher_core::{
+    api::BuildInfo,
+    auth::AuthManager,
+    config::Config,
+    feeds,
+    games::{GameManager, InstalledGame},
+    metrics, tr, Result,
+};
+
+pub struct DaemonOptions {
+    /// Where to serve the health endpoint, if anywhere
+    pub listen: Option<SocketAddr>,
+    /// Time between update runs
+    pub interval: Duration,
+    /// Country whose free game promotions go into the feeds
+    pub country: String,
+}
+
+/// What the health endpoint reports
+#[derive(Default)]
+struct Health {
+    last_run: Option<DateTime<Utc>>,
+    last_run_failures: usize,
+}
+
+type SharedHealth = Arc<Mutex<Health>>;
+
+/// Run until SIGINT/SIGTERM, updating installed games every `interval`
+pub async fn run(config: Config, auth: AuthManager, options: DaemonOptions) -> Result<()> {
+    let mut manager = GameManager::new(config, auth)?;
+    manager.set_notifications(true);
+    let health = SharedHealth::default();
+    // Updates already announced, so each one is only notified once
+    let mut notified = HashSet::new();
+    let webhooks = Webhooks::new(manager.config().webhooks.clone());
+    // Giveaways already in the library, so only the ones claimed since are sent; `None`
+    // until the first run has seen them
+    let mut claimed = None;
+
+    if let Some(addr) = options.listen {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        log::info!(
+            "{}",
+            tr!("daemon-listening", url = format!("http://{}/health", addr))
+        );
+        log::info!(
+            "{}",
+            tr!(
+                "daemon-feeds-served",
+                rss = format!("http://{}/{}", addr, feeds::RSS_FILE),
+                ical = format!("http://{}/{}", addr, feeds::ICAL_FILE)
+            )
+        );
+        log::info!(
+            "{}",
+            tr!(
+                "daemon-metrics-served",
+                url = format!("http://{}/metrics", addr)
+            )
+        );
+
+        let app = Router::new()
+            .route("/health", get(health_handler))
+            .route("/metrics", get(metrics_handler))
+            .route(&format!("/{}", feeds::RSS_FILE), get(rss_handler))
+            .route(&format!("/{}", feeds::ICAL_FILE), get(ical_handler))
+            .with_state(Arc::clone(&health));
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                log::error!("{}", tr!("daemon-http-stopped", error = e.to_string()));
+            }
+        });
+    }
+
+    log::info!(
+        "{}",
+        tr!("daemon-started", minutes = options.interval.as_secs() / 60)
+    );
+
+    let mut ticker = tokio::time::interval(options.interval);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let mut failures = update_run(&mut manager, &webhooks).await;
+                // Checked after the run so whatever it just installed is no longer pending
+                let pending = manager.pending_updates().await;
+                notify_pending_updates(&manager, &pending, &mut notified);
+                let written = match pending {
+                    Ok(pending) => manager.write_feeds(&options.country, &pending).await,
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = written {
+                    log::warn!("{}", tr!("daemon-feeds-failed", error = e.to_string()));
+                    failures += 1;
+                }
+                if let Err(e) = manager.check_wishlist().await {
+                    log::warn!("{}", tr!("daemon-wishlist-failed", error = e.to_string()));
+                    failures += 1;
+                }
+                if !webhooks.is_empty() {
+                    send_claimed_giveaways(&manager, &webhooks, &mut claimed).await;
+                }
+
+                let mut health = health.lock().unwrap();
+                health.last_run = Some(Utc::now());
+                health.last_run_failures = failures;
+            }
+            _ = &mut shutdown => {
+                log::info!("{}", tr!("daemon-stopping"));
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Refresh logins and update every unpinned game, returning how many steps failed
+async fn update_run(manager: &mut GameManager, webhooks: &Webhooks) -> usize {
+    let mut failures = 0;
+
+    for (provider, result) in manager.refresh_tokens().await {
+        if let Err(e) = result {
+            log::warn!(
+                "{}",
+                tr!(
+                    "daemon-refresh-failed",
+                    store = provider,
+                    error = e.to_string()
+                )
+            );
+            failures += 1;
+        }
+    }
+
+    // Builds before the run, to tell which games it updated
+    let versions: HashMap<String, String> = manager
+        .list_installed()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|game| (game.app_name, game.app_version))
+        .collect();
+
+    match manager.update_all().await {
+        Ok(results) => {
+            let installed = manager.list_installed().unwrap_or_default();
+            for (app_name, result) in results {
+                let game = installed.iter().find(|game| game.app_name == app_name);
+                let title = game.map_or_else(|| app_name.clone(), |game| game.app_title.clone());
+                match result {
+                    Ok(()) => {
+                        let (Some(game), Some(from_version)) = (game, versions.get(&app_name))
+                        else {
+                            continue;
+                        };
+                        if *from_version != game.app_version {
+                            let event = WebhookEvent::UpdateInstalled {
+                                app_name,
+                                title,
+                                from_version: from_version.clone(),
+                                version: game.app_version.clone(),
+                            };
+                            webhooks.send(&event).await;
+                        }
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "{}",
+                            tr!(
+                                "update-game-failed",
+                                app = app_name.as_str(),
+                                error = e.to_string()
+                            )
+                        );
+                        failures += 1;
+                        let event = WebhookEvent::DownloadFailed {
+                            app_name,
+                            title,
+                            error: e.to_string(),
+                        };
+                        webhooks.send(&event).await;
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            log::error!("{}", tr!("daemon-update-run-failed", error = e.to_string()));
+            failures += 1;
+            let event = WebhookEvent::UpdateRunFailed {
+                error: e.to_string(),
+            };
+            webhooks.send(&event).await;
+        }
+    }
+
+    failures
+}
+
+/// Notify about updates the run left behind (for pinned games, say) that weren't seen before
+fn notify_pending_updates(
+    manager: &GameManager,
+    pending: &Result<Vec<(InstalledGame, BuildInfo)>>,
+    notified: &mut HashSet<(String, String)>,
+) {
+    let pending = match pending {
+        Ok(pending) => pending,
+        Err(e) => {
+            log::debug!("Could not list pending updates: {}", e);
+            return;
+        }
+    };
+
+    for (game, build) in pending {
+        if notified.insert((game.id(), build.version.clone())) {
+            manager.notify(Notification::UpdateAvailable {
+                title: game.app_title.clone(),
+                version: build.version.clone(),
+            });
+        }
+    }
+}
+
+/// Send the giveaways that showed up in the library since the last run
+async fn send_claimed_giveaways(
+    manager: &GameManager,
+    webhooks: &Webhooks,
+    claimed: &mut Option<HashSet<String>>,
+) {
+    let giveaways = match manager.claimed_giveaways().await {
+        Ok(giveaways) => giveaways,
+        Err(e) => {
+            log::debug!("Could not check for claimed giveaways: {}", e);
+            return;
+        }
+    };
+
+    let seen = claimed.get_or_insert_with(|| {
+        giveaways
+            .iter()
+            .map(|giveaway| giveaway.title.clone())
+            .collect()
+    });
+    for giveaway in giveaways {
+        if seen.insert(giveaway.title.clone()) {
+            let event = WebhookEvent::FreeGameClaimed {
+                title: giveaway.title,
+                url: giveaway.url,
+            };
+            webhooks.send(&event).await;
+        }
+    }
+}
+
+async fn health_handler(State(health): State<SharedHealth>) -> Json<serde_json::Value> {
+    let health = health.lock().unwrap();
+
+    Json(serde_json::json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+        "last_run": health.last_run.map(|t| t.to_rfc3339()),
+        "last_run_failures": health.last_run_failures,
+    }))
+}
+
+async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::render(),
+    )
+}
+
+async fn rss_handler() -> impl IntoResponse {
+    serve_feed(feeds::RSS_FILE, "application/rss+xml")
+}
+
+async fn ical_handler() -> impl IntoResponse {
+    serve_feed(feeds::ICAL_FILE, "text/calendar")
+}
+
+/// Serve a feed file written by the last run; 404 until the first run finishes
+fn serve_feed(name: &str, content_type: &'static str) -> axum::response::Response {
+    let contents = feeds::feeds_dir().and_then(|dir| Ok(std::fs::read(dir.join(name))?));
+
+    match contents {
+        Ok(contents) => ([(header::CONTENT_TYPE, content_type)], contents).into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Resolves on Ctrl-C, or on SIGTERM as sent by `docker stop`
+pub(crate) async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(e) => {
+                log::warn!("{}", tr!("daemon-no-sigterm", error = e.to_string()));
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -0,0 +1,410 @@
+//! Rows of `rauncher list`: library or installed games, narrowed down by the listing's filters
+//! and shown as the usual listing, a table of chosen columns, or exported with every field.
+
+use serde_json::json;
+use std::collections::HashSet;
+
+use rauncher_core::api::Game;
+use rauncher_core::csv::csv_field;
+use rauncher_core::games::{format_size, InstalledGame};
+use rauncher_core::{providers, tr};
+
+/// A game as `list` shows it, with its install when it has one
+pub struct ListRow {
+    pub app_name: String,
+    pub title: String,
+    pub version: String,
+    pub provider: String,
+    pub installed: Option<InstalledGame>,
+}
+
+impl ListRow {
+    pub fn from_installed(game: InstalledGame) -> Self {
+        Self {
+            app_name: game.app_name.clone(),
+            title: game.app_title.clone(),
+            version: game.app_version.clone(),
+            provider: game.provider.clone(),
+            installed: Some(game),
+        }
+    }
+
+    /// A library game, with its install found in `installed`
+    pub fn from_game(game: Game, installed: &[InstalledGame]) -> Self {
+        let id = game.id();
+        Self {
+            installed: installed.iter().find(|g| g.id() == id).cloned(),
+            app_name: game.app_name,
+            title: game.app_title,
+            version: game.app_version,
+            provider: game.provider,
+        }
+    }
+
+    pub fn id(&self) -> String {
+        providers::qualified_id(&self.provider, &self.app_name)
+    }
+
+    /// Whether the title or app name contains `text`, ignoring case
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        self.title.to_lowercase().contains(&text) || self.app_name.to_lowercase().contains(&text)
+    }
+
+    /// The row's value in `column`, one of `LIST_COLUMNS`; `-` when it has none
+    fn cell(&self, column: &str) -> String {
+        let installed = self.installed.as_ref();
+        let value = match column {
+            "id" => Some(self.id()),
+            "title" => Some(self.title.clone()),
+            "version" => Some(self.version.clone()),
+            "store" => Some(self.provider.clone()),
+            "path" => installed.map(|game| game.install_path.display().to_string()),
+            "size" => installed.map(|game| format_size(game.disk_usage())),
+            _ => None,
+        };
+        value.unwrap_or_else(|| "-".to_string())
+    }
+}
+
+/// What the listing's filters keep. The games with updates and those for a platform are
+/// looked up beforehand, since that takes the stores.
+#[derive(Default)]
+pub struct RowFilter {
+    pub provider: Option<String>,
+    /// Text the title or app name contains
+    pub text: Option<String>,
+    pub not_installed: bool,
+    /// Ids of the games with an update waiting, with `--updates-available`
+    pub updates: Option<HashSet<String>>,
+    /// App names of the Epic games for the platform, with `--platform`
+    pub platform_apps: Option<HashSet<String>>,
+}
+
+impl RowFilter {
+    pub fn keeps(&self, row: &ListRow) -> bool {
+        self.provider.as_ref().is_none_or(|p| &row.provider == p)
+            && self.text.as_deref().is_none_or(|text| row.matches(text))
+            && !(self.not_installed && row.installed.is_some())
+            && self
+                .updates
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&row.id()))
+            && self
+                .platform_apps
+                .as_ref()
+                .is_none_or(|apps| row.provider == providers::EPIC && apps.contains(&row.app_name))
+    }
+}
+
+/// Fields of an export, in order
+const FIELDS: [&str; 8] = [
+    "id",
+    "app_name",
+    "title",
+    "store",
+    "version",
+    "installed_version",
+    "install_path",
+    "install_size",
+];
+
+/// A row's value for each of `FIELDS`, with its install size in bytes
+struct Record {
+    values: [Option<String>; FIELDS.len()],
+    size: Option<u64>,
+}
+
+impl Record {
+    fn new(row: &ListRow) -> Self {
+        let installed = row.installed.as_ref();
+        // Measured once, as it can mean walking the whole install
+        let size = installed.map(|game| game.disk_usage());
+        Self {
+            values: [
+                Some(row.id()),
+                Some(row.app_name.clone()),
+                Some(row.title.clone()),
+                Some(row.provider.clone()),
+                Some(row.version.clone()),
+                installed.map(|game| game.app_version.clone()),
+                installed.map(|game| game.install_path.display().to_string()),
+                size.map(|size| size.to_string()),
+            ],
+            size,
+        }
+    }
+}
+
+/// `rows` with every field, in `format`: `csv`, `markdown` or `json`. Sizes are in bytes,
+/// except in Markdown, meant for reading; fields of games that aren't installed are empty.
+pub fn export(rows: &[ListRow], format: &str) -> String {
+    let records: Vec<Record> = rows.iter().map(Record::new).collect();
+
+    match format {
+        "json" => {
+            let games: Vec<serde_json::Value> = records
+                .iter()
+                .map(|record| {
+                    let game = FIELDS
+                        .iter()
+                        .zip(&record.values)
+                        .map(|(field, value)| {
+                            // A number, where the other formats have text
+                            let value = if *field == "install_size" {
+                                json!(record.size)
+                            } else {
+                                json!(value)
+                            };
+                            (field.to_string(), value)
+                        })
+                        .collect();
+                    serde_json::Value::Object(game)
+                })
+                .collect();
+            let mut out = serde_json::to_string_pretty(&games).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        "markdown" => {
+            let mut out = format!("| {} |\n", FIELDS.join(" | "));
+            out.push_str(&format!("|{}\n", "---|".repeat(FIELDS.len())));
+            for record in &records {
+                let cells: Vec<String> = FIELDS
+                    .iter()
+                    .zip(&record.values)
+                    .map(|(field, value)| {
+                        if *field == "install_size" {
+                            record.size.map(format_size).unwrap_or_default()
+                        } else {
+                            value.as_deref().unwrap_or_default().replace('|', "\\|")
+                        }
+                    })
+                    .collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+            out
+        }
+        _ => {
+            let mut out = FIELDS.join(",");
+            out.push('\n');
+            for record in &records {
+                let cells: Vec<String> = record
+                    .values
+                    .iter()
+                    .map(|value| csv_field(value.as_deref().unwrap_or_default()))
+                    .collect();
+                out.push_str(&cells.join(","));
+                out.push('\n');
+            }
+            out
+        }
+    }
+}
+
+/// Log `rows` as a table of `columns`, each as wide as its longest value
+pub fn log_table(rows: &[ListRow], columns: &[String]) {
+    for line in table(rows, columns) {
+        log::info!("{}", line);
+    }
+}
+
+/// The lines of `log_table`, headers first
+fn table(rows: &[ListRow], columns: &[String]) -> Vec<String> {
+    let headers: Vec<String> = columns
+        .iter()
+        .map(|column| tr!("list-column", column = column.as_str()))
+        .collect();
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| columns.iter().map(|column| row.cell(column)).collect())
+        .collect();
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let line = |values: &[String]| {
+        let padded: Vec<String> = values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    std::iter::once(line(&headers))
+        .chain(cells.iter().map(|row| line(row)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(app_name: &str, title: &str, size: u64) -> InstalledGame {
+        serde_json::from_value(json!({
+            "app_name": app_name,
+            "app_title": title,
+            "app_version": "1.1",
+            "install_path": format!("/games/{}", app_name),
+            "executable": "Game.exe",
+            "install_size": size,
+        }))
+        .unwrap()
+    }
+
+    fn library(app_name: &str, title: &str, provider: &str) -> ListRow {
+        ListRow {
+            app_name: app_name.to_string(),
+            title: title.to_string(),
+            version: "2.0".to_string(),
+            provider: provider.to_string(),
+            installed: None,
+        }
+    }
+
+    fn rows() -> Vec<ListRow> {
+        vec![
+            ListRow::from_installed(installed("Kiwi", "Kiwi, \"Deluxe\" | Edition", 2048)),
+            library("1207658924", "Pyre", "gog"),
+        ]
+    }
+
+    #[test]
+    fn test_filters() {
+        let rows = [
+            ListRow::from_installed(installed("Kiwi", "Kiwi Quest", 2048)),
+            library("Fortnite", "Fortnite", "epic"),
+            library("1207658924", "Pyre", "gog"),
+        ];
+        let kept = |filter: &RowFilter| -> Vec<String> {
+            rows.iter()
+                .filter(|row| filter.keeps(row))
+                .map(ListRow::id)
+                .collect()
+        };
+
+        assert_eq!(kept(&RowFilter::default()).len(), 3);
+        let text = |text: &str| RowFilter {
+            text: Some(text.to_string()),
+            ..RowFilter::default()
+        };
+        assert_eq!(kept(&text("QUEST")), vec!["epic:Kiwi"]);
+        assert_eq!(kept(&text("120765")), vec!["gog:1207658924"]);
+        assert!(kept(&text("celeste")).is_empty());
+
+        let by_store = RowFilter {
+            provider: Some("gog".to_string()),
+            ..RowFilter::default()
+        };
+        assert_eq!(kept(&by_store), vec!["gog:1207658924"]);
+
+        let not_installed = RowFilter {
+            not_installed: true,
+            ..RowFilter::default()
+        };
+        assert_eq!(
+            kept(&not_installed),
+            vec!["epic:Fortnite", "gog:1207658924"]
+        );
+
+        let updates = RowFilter {
+            updates: Some(HashSet::from(["epic:Kiwi".to_string()])),
+            ..RowFilter::default()
+        };
+        assert_eq!(kept(&updates), vec!["epic:Kiwi"]);
+
+        // Only Epic games are known by platform
+        let platform = RowFilter {
+            platform_apps: Some(HashSet::from([
+                "Fortnite".to_string(),
+                "1207658924".to_string(),
+            ])),
+            ..RowFilter::default()
+        };
+        assert_eq!(kept(&platform), vec!["epic:Fortnite"]);
+
+        let combined = RowFilter {
+            text: Some("fort".to_string()),
+            not_installed: true,
+            ..platform
+        };
+        assert_eq!(kept(&combined), vec!["epic:Fortnite"]);
+    }
+
+    #[test]
+    fn test_table_columns() {
+        let rows = rows();
+        assert_eq!(rows[1].cell("size"), "-");
+        assert_eq!(rows[1].cell("path"), "-");
+        assert_eq!(rows[0].cell("path"), "/games/Kiwi");
+        assert_eq!(rows[0].cell("rating"), "-");
+
+        // Columns come in the order given, each as wide as its longest value
+        let columns = ["size".to_string(), "store".to_string(), "id".to_string()];
+        let lines = table(&rows, &columns);
+        assert_eq!(lines.len(), 3);
+        let size = format_size(2048);
+        let width = size
+            .len()
+            .max(tr!("list-column", column = "size").chars().count());
+        assert_eq!(
+            lines[1],
+            format!("{:<width$}  epic   epic:Kiwi", size, width = width)
+        );
+        assert_eq!(
+            lines[2],
+            format!("{:<width$}  gog    gog:1207658924", "-", width = width)
+        );
+    }
+
+    #[test]
+    fn test_export_csv_quotes_fields() {
+        let csv = export(&rows(), "csv");
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], FIELDS.join(","));
+        assert_eq!(
+            lines[1],
+            "epic:Kiwi,Kiwi,\"Kiwi, \"\"Deluxe\"\" | Edition\",epic,1.1,1.1,/games/Kiwi,2048"
+        );
+        assert_eq!(lines[2], "gog:1207658924,1207658924,Pyre,gog,2.0,,,");
+    }
+
+    #[test]
+    fn test_export_markdown_escapes_pipes() {
+        let markdown = export(&rows(), "markdown");
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[0], format!("| {} |", FIELDS.join(" | ")));
+        assert_eq!(lines[1].matches("---|").count(), FIELDS.len());
+        assert!(lines[2].contains("| Kiwi, \"Deluxe\" \\| Edition |"));
+        assert!(lines[2].ends_with(&format!("| {} |", format_size(2048))));
+        assert!(lines[3].ends_with("| 2.0 |  |  |  |"));
+    }
+
+    #[test]
+    fn test_export_json_shape() {
+        let games: serde_json::Value = serde_json::from_str(&export(&rows(), "json")).unwrap();
+        let games = games.as_array().unwrap();
+        assert_eq!(games.len(), 2);
+        let fields: Vec<&str> = games[0]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut expected = FIELDS.to_vec();
+        expected.sort();
+        assert_eq!(fields, expected);
+        assert_eq!(games[0]["install_size"], json!(2048));
+        assert_eq!(games[0]["installed_version"], json!("1.1"));
+        assert_eq!(games[1]["id"], json!("gog:1207658924"));
+        assert_eq!(games[1]["install_size"], serde_json::Value::Null);
+        assert_eq!(games[1]["install_path"], serde_json::Value::Null);
+    }
+}
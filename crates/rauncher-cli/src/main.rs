@@ -0,0 +1,3290 @@
+mod cli;
+mod daemon;
+mod list;
+mod prompt;
+mod remote;
+mod serve;
+
+use clap::Parser;
+use cli::{
+    AliasCommands, AssetCommands, CategoryCommands, Cli, CloudSaveCommands, Commands,
+    ConfigCommands, ModsCommands, PrefixCommands, RunnerCommands, ShaderCacheCommands,
+    WishlistCommands,
+};
+use list::{ListRow, RowFilter};
+use prompt::{choose, confirm, interactive, pick, TerminalPrompter};
+use rauncher_core::{
+    api::{StorePage, StoreQuery},
+    auth::{AuthManager, AuthToken},
+    collections::{self, Collections},
+    config::Config,
+    downloads::TransferStats,
+    games::{
+        self, format_duration, format_size, Check, FileStatus, GameManager, InstallPlan,
+        InstallProgress, LaunchOptions, OrphanedInstall, Severity, ShaderCacheUpdate,
+        UninstallFilter, UninstallOptions, VerifyReport,
+    },
+    history::{self, HistoryAction, HistoryEntry},
+    logs, network,
+    power::{self, AfterDownloads, PowerAction},
+    profiling,
+    providers::{self, LoginFlow},
+    runners::{self, RunnerKind},
+    sandbox,
+    stats::LibraryStats,
+    storage::{self, Removal, StorageReport},
+    tr,
+    wishlist::{Wishlist, WishlistEvent},
+    Result,
+};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Time between progress lines while downloading
+const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    profiling::mark_process_start();
+
+    let cli = Cli::parse();
+
+    // Load configuration, then start logging since log files follow the configured data dir
+    let phase = profiling::phase("config and logging");
+    let config = match Config::load() {
+        // The doctor reports a broken config itself rather than stopping at it
+        Err(_) if matches!(cli.command, Some(Commands::Doctor)) => Config::default(),
+        config => config?,
+    };
+    let log_level = if cli.verbose { "debug" } else { "info" };
+    // Reading a transcript shouldn't replace it with its own
+    let transcript =
+        (cli.transcript || config.transcripts) && !matches!(cli.command, Some(Commands::LastRun));
+    logs::init(&config, log_level, transcript);
+    drop(phase);
+    log::debug!("Configuration loaded");
+    rauncher_core::i18n::init(config.language.as_deref());
+    network::set_allowed(cli.allow_metered);
+
+    let result = run(cli, config).await;
+    if result.is_err() {
+        log::info!("{}", tr!("doctor-hint"));
+    }
+    logs::transcript::finish();
+    result
+}
+
+async fn run(mut cli: Cli, config: Config) -> Result<()> {
+    if let Some(host) = cli.host.take() {
+        let token = match cli.host_token_file.take() {
+            Some(path) => Some(std::fs::read_to_string(path)?.trim().to_string()),
+            None => std::env::var("RAUNCHER_SERVE_TOKEN").ok(),
+        };
+        let (Some(command), Some(token)) = (cli.command, token) else {
+            log::error!("{}", tr!("remote-needs-command"));
+            std::process::exit(1);
+        };
+        return remote::run(&host, token, command, cli.progress_json).await;
+    }
+
+    if let Some(name) = cli.command.as_mut().and_then(Commands::app_name_mut) {
+        *name = games::resolve_name(&config, name)?;
+        if let Some(choice) = did_you_mean(&config, name) {
+            *name = choice;
+        }
+    }
+
+    // Initialize auth manager
+    let mut auth = {
+        let _phase = profiling::phase("auth load");
+        AuthManager::new()?
+    };
+    profiling::milestone("startup complete");
+
+    match cli.command {
+        None => {
+            // Launch GUI when no command is provided
+            #[cfg(feature = "gui")]
+            run_gui(cli.fullscreen);
+
+            // CLI-only builds have nothing to open, so show what they can do instead
+            #[cfg(not(feature = "gui"))]
+            {
+                use clap::CommandFactory;
+                Cli::command().print_help()?;
+            }
+        }
+
+        Some(command) => match command {
+            Commands::Auth {
+                provider,
+                code,
+                web,
+                logout,
+                switch,
+                profile,
+                profiles,
+                status,
+            } => {
+                if provider != providers::EPIC {
+                    let store = providers::create(&provider)?;
+
+                    if status {
+                        match AuthToken::load_for(&provider)? {
+                            Some(token) => print_session(
+                                store.name(),
+                                None,
+                                &token.account_id,
+                                token.expires_at,
+                                &[],
+                            ),
+                            None => log::info!(
+                                "{}",
+                                tr!("auth-status-not-logged-in", store = store.name())
+                            ),
+                        }
+                    } else if switch.is_some() || profile.is_some() || profiles {
+                        log::error!("{}", tr!("auth-profiles-epic-only"));
+                        std::process::exit(1);
+                    } else if web {
+                        log::error!("{}", tr!("auth-web-epic-only"));
+                        std::process::exit(1);
+                    } else if logout {
+                        AuthToken::delete_for(&provider)?;
+                        log::info!("{}", tr!("auth-logged-out-store", store = store.name()));
+                    } else if let Some(code) = code {
+                        match store.exchange_code(&code).await {
+                            Ok(token) => {
+                                token.save_for(&provider)?;
+                                log::info!("{}", tr!("auth-success-store", store = store.name()));
+                                log::info!(
+                                    "{}",
+                                    tr!("auth-hint-list-store", provider = provider.as_str())
+                                );
+                            }
+                            Err(e) => {
+                                log::error!("{}", tr!("auth-failed", error = e.to_string()));
+                                std::process::exit(1);
+                            }
+                        }
+                    } else {
+                        log::info!("{}", tr!("auth-title", store = store.name()));
+
+                        match store.login_flow()? {
+                            LoginFlow::AuthorizationCode { url } => {
+                                log::info!("{}", tr!("auth-log-in-browser"));
+                                log::info!("{}", tr!("auth-open-url", url = url.as_str()));
+
+                                if let Err(e) = webbrowser::open(&url) {
+                                    log::debug!("Could not open a browser: {}", e);
+                                }
+
+                                log::info!("{}", tr!("auth-copy-code"));
+                                log::info!("  rauncher auth --provider {} --code <CODE>", provider);
+                            }
+                            LoginFlow::ApiKey { url } => {
+                                log::info!("{}", tr!("auth-create-api-key", url = url.as_str()));
+                                log::info!("{}", tr!("auth-then-run"));
+                                log::info!(
+                                    "  rauncher auth --provider {} --code <API_KEY>",
+                                    provider
+                                );
+                            }
+                            LoginFlow::DeviceCode => {}
+                        }
+                    }
+                } else if web || code.is_some() {
+                    use rauncher_core::api::EpicClient;
+                    use rauncher_core::auth::WebLogin;
+
+                    log::info!("{}", tr!("auth-epic-title"));
+
+                    let client = EpicClient::with_settings(&config.epic)?;
+                    let code = match code {
+                        Some(code) => Ok(code),
+                        None => {
+                            let login = WebLogin::bind(&client)?;
+                            let url = login.url();
+                            log::info!("{}", tr!("auth-web-open", url = url.as_str()));
+                            log::info!("{}", tr!("auth-web-steps"));
+
+                            if let Err(e) = webbrowser::open(&url) {
+                                log::debug!("Could not open a browser: {}", e);
+                            }
+
+                            log::info!("{}", tr!("auth-web-waiting"));
+                            login.wait_for_code().await
+                        }
+                    };
+
+                    let token = match code {
+                        Ok(code) => client.exchange_code(&code).await,
+                        Err(e) => Err(e),
+                    };
+                    match token {
+                        Ok(token) => {
+                            match &profile {
+                                Some(name) => auth.set_token_for_profile(name, token)?,
+                                None => auth.set_token(token)?,
+                            }
+
+                            log::info!("{}", tr!("auth-epic-success"));
+                            log::info!("{}", tr!("auth-next-steps"));
+                            log::info!("{}", tr!("auth-hint-list"));
+                            log::info!("{}", tr!("auth-hint-install"));
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("auth-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                } else if status {
+                    let store = providers::display_name(providers::EPIC);
+                    // An expired access token is refreshed by the session check below
+                    if !auth.has_session() {
+                        log::info!("{}", tr!("auth-status-not-logged-in", store = store));
+                        return Ok(());
+                    }
+
+                    let profile = auth.active_profile();
+                    let manager = GameManager::new(config, auth)?;
+                    match manager.session_info().await {
+                        Ok(session) => {
+                            print_session(
+                                store,
+                                session.display_name.as_deref(),
+                                &session.account_id,
+                                session.expires_at,
+                                &session.scopes,
+                            );
+                            if let Some(profile) = profile {
+                                log::info!("{}", tr!("auth-status-profile", value = profile));
+                            }
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("auth-status-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                } else if profiles {
+                    let active = auth.active_profile();
+                    let stored = AuthManager::list_profiles()?;
+
+                    if stored.is_empty() {
+                        log::info!("{}", tr!("profiles-none"));
+                    } else {
+                        heading(tr!("profiles-title"));
+                        for name in stored {
+                            let marker = if active.as_deref() == Some(name.as_str()) {
+                                "*"
+                            } else {
+                                " "
+                            };
+                            log::info!("{} {}", marker, name);
+                        }
+                    }
+                } else if let Some(name) = switch {
+                    match auth.switch_profile(&name) {
+                        Ok(()) => log::info!("{}", tr!("profile-switched", name = name.as_str())),
+                        Err(e) => {
+                            log::error!("{}", tr!("profile-switch-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                } else if logout {
+                    auth.logout()?;
+                    log::info!("{}", tr!("auth-logged-out"));
+                } else {
+                    use rauncher_core::api::EpicClient;
+
+                    log::info!("{}", tr!("auth-epic-title"));
+
+                    let client = EpicClient::with_settings(&config.epic)?;
+
+                    log::info!("{}", tr!("auth-starting"));
+
+                    match client.authenticate().await {
+                        Ok((user_code, verification_url, token)) => {
+                            log::info!("{}", tr!("auth-use-browser"));
+                            log::info!("{}", tr!("auth-open-url", url = verification_url));
+                            log::info!("{}", tr!("auth-enter-code", code = user_code));
+                            log::info!("{}", tr!("auth-waiting"));
+
+                            // Save the token, keeping any previous account stored
+                            match &profile {
+                                Some(name) => auth.set_token_for_profile(name, token)?,
+                                None => auth.set_token(token)?,
+                            }
+
+                            log::info!("{}", tr!("auth-epic-success"));
+                            log::info!("{}", tr!("auth-next-steps"));
+                            log::info!("{}", tr!("auth-hint-list"));
+                            log::info!("{}", tr!("auth-hint-install"));
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("auth-failed", error = e.to_string()));
+                            log::error!("{}", tr!("auth-retry"));
+                            log::error!("{}", tr!("auth-check-connection"));
+                            log::error!("{}", tr!("auth-check-epic-status"));
+                            log::error!("{}", tr!("auth-try-web"));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+
+            Commands::List {
+                installed,
+                provider,
+                offline,
+                category,
+                favorites,
+                hidden,
+                filter,
+                platform,
+                updates_available,
+                not_installed,
+                columns,
+                format,
+            } => {
+                let collections = Collections::load()?;
+                let shown = collections::Filter {
+                    category,
+                    favorites,
+                    hidden,
+                };
+                let installed_only = installed || updates_available;
+                let filtered =
+                    filter.is_some() || platform.is_some() || updates_available || not_installed;
+
+                if !installed_only
+                    && provider.as_deref() == Some(providers::EPIC)
+                    && !auth.is_authenticated()
+                {
+                    log::error!("{}", tr!("not-authenticated"));
+                    std::process::exit(1);
+                }
+
+                let mut manager = GameManager::new(config, auth)?;
+                manager.set_offline(offline);
+                let installed_games = manager.list_installed()?;
+                let mut rows: Vec<ListRow> = if installed_only {
+                    installed_games
+                        .into_iter()
+                        .map(ListRow::from_installed)
+                        .collect()
+                } else {
+                    let result = match provider.as_deref() {
+                        Some(provider) => manager.list_provider_library(provider).await,
+                        None => manager.list_merged_library().await,
+                    };
+                    match result {
+                        Ok(games) => games
+                            .into_iter()
+                            .map(|game| ListRow::from_game(game, &installed_games))
+                            .collect(),
+                        Err(e) => {
+                            log::error!("{}", tr!("library-fetch-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                };
+                if !installed_only && manager.is_offline() {
+                    log::warn!("{}", tr!("library-offline"));
+                }
+
+                let updates = if updates_available {
+                    let pending = manager.pending_updates().await?;
+                    Some(pending.into_iter().map(|(game, _)| game.id()).collect())
+                } else {
+                    None
+                };
+                let platform_apps = match platform {
+                    Some(platform) => {
+                        let platform = if platform == "mac" { "Mac" } else { "Windows" };
+                        match manager.epic_games_for_platform(platform).await {
+                            Ok(apps) => Some(apps),
+                            Err(e) => {
+                                log::error!(
+                                    "{}",
+                                    tr!("list-platform-failed", error = e.to_string())
+                                );
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                    None => None,
+                };
+                let row_filter = RowFilter {
+                    provider,
+                    text: filter,
+                    not_installed,
+                    updates,
+                    platform_apps,
+                };
+                rows.retain(|row| row_filter.keeps(row) && collections.shows(&row.id(), &shown));
+
+                if let Some(format) = format {
+                    print!("{}", list::export(&rows, &format));
+                } else if rows.is_empty() {
+                    log::info!(
+                        "{}",
+                        if filtered {
+                            tr!("list-none-match")
+                        } else if installed_only {
+                            tr!("list-no-installed")
+                        } else {
+                            tr!("library-empty")
+                        }
+                    );
+                } else if !columns.is_empty() {
+                    list::log_table(&rows, &columns);
+                } else if installed_only {
+                    heading(tr!("list-installed-title"));
+                    for row in rows {
+                        log::info!("  {} - {} (v{})", row.id(), row.title, row.version);
+                        let Some(game) = row.installed else {
+                            continue;
+                        };
+                        log::info!(
+                            "    {}",
+                            tr!("list-path", path = game.install_path.display().to_string())
+                        );
+                        if game.is_unavailable() {
+                            log::info!("    {}", tr!("list-unavailable"));
+                        }
+                    }
+                } else {
+                    heading(tr!("library-title"));
+                    for row in rows {
+                        log::info!("  {} - {} (v{})", row.id(), row.title, row.version);
+                    }
+                }
+            }
+
+            Commands::Install {
+                app_name,
+                version,
+                provider,
+                dry_run,
+                shutdown_after,
+                suspend_after,
+                exec_after,
+            } => {
+                let after = after_downloads(shutdown_after, suspend_after, exec_after);
+                let mut manager = GameManager::new(config, auth)?;
+                let app_name = match app_name {
+                    Some(app_name) => app_name,
+                    None => pick_game(&manager, false, provider.as_deref()).await?,
+                };
+                let (address_provider, app_name) = providers::parse_address(&app_name);
+                let provider = match (address_provider, provider.as_deref()) {
+                    (Some(a), Some(p)) if a != p => {
+                        log::error!(
+                            "{}",
+                            tr!("install-provider-conflict", address = a, provider = p)
+                        );
+                        std::process::exit(1);
+                    }
+                    (Some(p), _) | (None, Some(p)) => p,
+                    (None, None) => providers::EPIC,
+                };
+
+                if provider == providers::EPIC && !manager.auth().is_authenticated() {
+                    log::error!("{}", tr!("not-authenticated"));
+                    std::process::exit(1);
+                }
+
+                if provider != providers::EPIC && version.is_some() {
+                    log::error!("{}", tr!("install-version-epic-only"));
+                    std::process::exit(1);
+                }
+
+                if dry_run {
+                    match manager
+                        .plan_install(provider, app_name, version.as_deref())
+                        .await
+                    {
+                        Ok(plan) => log_plan(&plan),
+                        Err(e) => {
+                            log::error!(
+                                "{}",
+                                tr!("plan-failed", app = app_name, error = e.to_string())
+                            );
+                            std::process::exit(1);
+                        }
+                    }
+                    return Ok(());
+                }
+
+                // Without a terminal, or with JSON progress, nobody can answer, so the config's
+                // answers (or the safe defaults) are given instead
+                if interactive() && !cli.progress_json {
+                    manager.set_prompter(Arc::new(TerminalPrompter));
+                }
+                let progress = report_install_progress(&mut manager, cli.progress_json);
+                log::info!("{}", tr!("install-start", app = app_name));
+
+                let result = if provider == providers::EPIC {
+                    manager
+                        .install_game_version(app_name, version.as_deref())
+                        .await
+                } else {
+                    manager.install_provider_game(provider, app_name).await
+                };
+                drop(manager);
+                let _ = progress.await;
+
+                match &result {
+                    Ok(()) => log::info!("{}", tr!("install-done")),
+                    Err(e) => log::error!("{}", tr!("install-failed", error = e.to_string())),
+                }
+                finish_downloads(&after, usize::from(result.is_err())).await;
+            }
+
+            Commands::Versions { app_name } => {
+                if !auth.is_authenticated() {
+                    log::error!("{}", tr!("not-authenticated"));
+                    std::process::exit(1);
+                }
+
+                let manager = GameManager::new(config, auth)?;
+
+                match manager.list_builds(&app_name).await {
+                    Ok(builds) => {
+                        heading(tr!("versions-title", app = app_name.as_str()));
+                        for build in builds {
+                            log::info!("  {} ({})", build.build_version, build.label);
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("versions-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Pin { app_name } => {
+                let manager = GameManager::new(config, auth)?;
+
+                match manager.set_pinned(&app_name, true) {
+                    Ok(()) => log::info!("{}", tr!("pin-done", app = app_name.as_str())),
+                    Err(e) => {
+                        log::error!("{}", tr!("pin-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Unpin { app_name } => {
+                let manager = GameManager::new(config, auth)?;
+
+                match manager.set_pinned(&app_name, false) {
+                    Ok(()) => log::info!("{}", tr!("unpin-done", app = app_name.as_str())),
+                    Err(e) => {
+                        log::error!("{}", tr!("unpin-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Favorite { app_name } => {
+                let mut collections = Collections::load()?;
+                collections.set_favorite(&app_name, true);
+                collections.save()?;
+                log::info!("{}", tr!("favorite-done", app = app_name.as_str()));
+            }
+
+            Commands::Unfavorite { app_name } => {
+                let mut collections = Collections::load()?;
+                collections.set_favorite(&app_name, false);
+                collections.save()?;
+                log::info!("{}", tr!("unfavorite-done", app = app_name.as_str()));
+            }
+
+            Commands::Hide { app_name } => {
+                let mut collections = Collections::load()?;
+                collections.set_hidden(&app_name, true);
+                collections.save()?;
+                log::info!("{}", tr!("hide-done", app = app_name.as_str()));
+            }
+
+            Commands::Unhide { app_name } => {
+                let mut collections = Collections::load()?;
+                collections.set_hidden(&app_name, false);
+                collections.save()?;
+                log::info!("{}", tr!("unhide-done", app = app_name.as_str()));
+            }
+
+            Commands::Category { command } => {
+                let mut collections = Collections::load()?;
+
+                match command {
+                    CategoryCommands::List => {
+                        let categories = collections.categories();
+                        if categories.is_empty() {
+                            log::info!("{}", tr!("category-none"));
+                        } else {
+                            heading(tr!("category-title"));
+                            for category in categories {
+                                log::info!("  {}", category);
+                            }
+                        }
+                    }
+                    CategoryCommands::Add { app_name, category } => {
+                        if category.trim().is_empty() {
+                            log::error!("{}", tr!("category-empty"));
+                            std::process::exit(1);
+                        }
+                        if collections.add_category(&app_name, &category) {
+                            collections.save()?;
+                        }
+                        log::info!(
+                            "{}",
+                            tr!(
+                                "category-added",
+                                app = app_name.as_str(),
+                                category = category.trim()
+                            )
+                        );
+                    }
+                    CategoryCommands::Remove { app_name, category } => {
+                        if collections.remove_category(&app_name, &category) {
+                            collections.save()?;
+                            log::info!(
+                                "{}",
+                                tr!(
+                                    "category-removed",
+                                    app = app_name.as_str(),
+                                    category = category.as_str()
+                                )
+                            );
+                        } else {
+                            log::info!(
+                                "{}",
+                                tr!(
+                                    "category-not-in",
+                                    app = app_name.as_str(),
+                                    category = category.as_str()
+                                )
+                            );
+                        }
+                    }
+                }
+            }
+
+            Commands::Alias { command } => {
+                let mut config = config;
+
+                match command {
+                    AliasCommands::List if config.aliases.is_empty() => {
+                        log::info!("{}", tr!("alias-none"))
+                    }
+                    AliasCommands::List => {
+                        heading(tr!("alias-title"));
+                        for (alias, app_name) in &config.aliases {
+                            log::info!("  {} → {}", alias, app_name);
+                        }
+                    }
+                    AliasCommands::Add { alias, app_name } => {
+                        match games::add_alias(&mut config, &alias, &app_name) {
+                            Ok(target) => {
+                                config.save()?;
+                                log::info!(
+                                    "{}",
+                                    tr!("alias-added", alias = alias.trim(), app = target.as_str())
+                                );
+                            }
+                            Err(e) => {
+                                log::error!("{}", tr!("alias-failed", error = e.to_string()));
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                    AliasCommands::Remove { alias } => {
+                        if games::remove_alias(&mut config, &alias) {
+                            config.save()?;
+                            log::info!("{}", tr!("alias-removed", alias = alias.as_str()));
+                        } else {
+                            log::info!("{}", tr!("alias-unknown", alias = alias.as_str()));
+                        }
+                    }
+                }
+            }
+
+            Commands::Launch {
+                app_name,
+                exe,
+                cwd,
+                offline,
+                wait,
+                force,
+                args,
+            } => {
+                let mut manager = GameManager::new(config, auth)?;
+                manager.set_offline(offline);
+                let app_name = match app_name {
+                    Some(app_name) => app_name,
+                    None => pick_game(&manager, true, None).await?,
+                };
+                let options = LaunchOptions {
+                    args,
+                    executable: exe,
+                    working_dir: cwd,
+                    stream_output: wait,
+                    force,
+                };
+
+                let running = match manager.launch_game_with_options(&app_name, &options) {
+                    Ok(running) => running,
+                    Err(rauncher_core::Error::AlreadyRunning(title)) => {
+                        log::error!("{}", tr!("launch-already-running", app = title));
+                        log::info!("{}", tr!("launch-already-running-hint", app = app_name));
+                        std::process::exit(1);
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("launch-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                log::info!("{}", tr!("launch-done"));
+                if !wait {
+                    // The game keeps running once rauncher exits
+                    return Ok(());
+                }
+
+                let game = running.game.clone();
+                let pid = running.pid();
+                let mut waiting = tokio::task::spawn_blocking(move || running.wait_for_exit());
+                // The game leads a process group of its own, so Ctrl-C and SIGTERM only reach
+                // rauncher; the game is stopped with it instead of being left running
+                let exit = tokio::select! {
+                    exit = &mut waiting => exit,
+                    _ = daemon::shutdown_signal() => {
+                        log::info!("{}", tr!("launch-stopping", app = game.app_title.as_str()));
+                        if let Err(e) = manager.stop_instance(&game.app_name, pid).await {
+                            log::error!("{}", tr!("stop-failed", error = e.to_string()));
+                        }
+                        waiting.await
+                    }
+                }
+                .map_err(|e| rauncher_core::Error::Other(e.to_string()))??;
+                manager.after_exit(&game, &exit).await;
+
+                match (exit.code, exit.signal) {
+                    (Some(0), _) => {}
+                    (Some(code), _) => std::process::exit(code),
+                    // Like a shell reports a process a signal killed
+                    (None, Some(signal)) => {
+                        log::warn!(
+                            "{}",
+                            tr!(
+                                "launch-killed",
+                                app = game.app_title.as_str(),
+                                signal = signal
+                            )
+                        );
+                        std::process::exit(128 + signal)
+                    }
+                    (None, None) => std::process::exit(1),
+                }
+            }
+
+            Commands::Stop { app_name } => {
+                let manager = GameManager::new(config, auth)?;
+
+                match manager.stop_game(&app_name).await {
+                    Ok(true) => log::info!("{}", tr!("stop-done", app = app_name.as_str())),
+                    Ok(false) => {
+                        log::info!("{}", tr!("stop-not-running", app = app_name.as_str()))
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("stop-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Uninstall {
+                app_name,
+                purge,
+                not_played_since,
+                larger_than,
+                permanent,
+                yes,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                let options = UninstallOptions {
+                    purge,
+                    removal: removal(permanent),
+                };
+
+                let Some(app_name) = app_name else {
+                    let filter = UninstallFilter {
+                        not_played_since,
+                        larger_than,
+                    };
+                    uninstall_matching(&manager, &filter, &options, yes)?;
+                    return Ok(());
+                };
+
+                let game = match games::InstalledGame::resolve(&manager.config(), &app_name) {
+                    Ok(game) => game,
+                    Err(e) => {
+                        log::error!("{}", tr!("uninstall-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                heading(tr!("uninstall-game-title", title = game.app_title.as_str()));
+                log::info!(
+                    "{}",
+                    tr!(
+                        "info-install-path",
+                        value = game.install_path.display().to_string()
+                    )
+                );
+                log::info!(
+                    "{}",
+                    tr!("info-install-size", value = format_size(game.disk_usage()))
+                );
+                if !yes && !confirm(tr!("uninstall-confirm", count = 1)) {
+                    log::info!("{}", tr!("uninstall-aborted"));
+                    return Ok(());
+                }
+
+                match manager.uninstall_game_with_options(&app_name, &options) {
+                    Ok(()) => log::info!("{}", tr!("uninstall-done", trash = yes_no(!permanent))),
+                    Err(e) => {
+                        log::error!("{}", tr!("uninstall-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Verify {
+                app_name,
+                report,
+                format,
+                delete_extra,
+                permanent,
+                yes,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                let mut reporting = manager.clone();
+                let progress = report_install_progress(&mut reporting, cli.progress_json);
+                log::info!("{}", tr!("verify-start", app = app_name.as_str()));
+                let result = reporting.verify_game(&app_name).await;
+                drop(reporting);
+                let _ = progress.await;
+                let result = match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        log::error!("{}", tr!("verify-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                let delete_extra = delete_extra.then(|| removal(permanent));
+                handle_verify_report(&manager, &result, report, format, delete_extra, yes)?;
+            }
+
+            Commands::Mods { command } => {
+                let manager = GameManager::new(config, auth)?;
+                let result = match command {
+                    ModsCommands::List { app_name } => {
+                        manager.protected_paths(&app_name).map(|paths| {
+                            if paths.is_empty() {
+                                log::info!("{}", tr!("mods-none", app = app_name.as_str()));
+                            } else {
+                                heading(tr!("mods-title", app = app_name.as_str()));
+                                for path in paths {
+                                    log::info!("  {}", path);
+                                }
+                            }
+                        })
+                    }
+                    ModsCommands::Protect { app_name, path } => {
+                        manager.protect_path(&app_name, &path).map(|(path, added)| {
+                            if added {
+                                log::info!("{}", tr!("mods-protected", path = path));
+                            } else {
+                                log::info!("{}", tr!("mods-already", path = path));
+                            }
+                        })
+                    }
+                    ModsCommands::Unprotect { app_name, path } => {
+                        manager.unprotect_path(&app_name, &path).map(|removed| {
+                            let path = path.display().to_string();
+                            if removed {
+                                log::info!("{}", tr!("mods-unprotected", path = path));
+                            } else {
+                                log::info!("{}", tr!("mods-not-protected", path = path));
+                            }
+                        })
+                    }
+                };
+                if let Err(e) = result {
+                    log::error!("{}", tr!("mods-failed", error = e.to_string()));
+                    std::process::exit(1);
+                }
+            }
+
+            Commands::Entitlements { filter, json } => {
+                let manager = GameManager::new(config, auth)?;
+                let entitlements = match manager.entitlements().await {
+                    Ok(entitlements) => entitlements,
+                    Err(e) => {
+                        log::error!("{}", tr!("entitlements-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+
+                let needle = filter.as_deref().map(str::to_lowercase);
+                let entitlements: Vec<_> = entitlements
+                    .into_iter()
+                    .filter(|e| {
+                        needle.as_deref().is_none_or(|needle| {
+                            [&e.entitlement_name, &e.namespace, &e.catalog_item_id]
+                                .iter()
+                                .any(|field| field.to_lowercase().contains(needle))
+                        })
+                    })
+                    .collect();
+
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&entitlements)?);
+                } else if entitlements.is_empty() {
+                    log::info!("{}", tr!("entitlements-none"));
+                } else {
+                    heading(tr!("entitlements-title", count = entitlements.len()));
+                    for e in &entitlements {
+                        let granted = e
+                            .grant_date
+                            .map(|date| date.format("%Y-%m-%d").to_string())
+                            .unwrap_or_else(|| "-".to_string());
+                        log::info!(
+                            "  {}",
+                            tr!(
+                                "entitlements-entry",
+                                name = e.entitlement_name.as_str(),
+                                namespace = e.namespace.as_str(),
+                                item = e.catalog_item_id.as_str(),
+                                kind = e.entitlement_type.as_str(),
+                                granted = granted,
+                                active = yes_no(e.active)
+                            )
+                        );
+                    }
+                }
+            }
+
+            Commands::Info { app_name } => {
+                let manager = GameManager::new(config, auth)?;
+
+                match manager
+                    .list_installed()?
+                    .iter()
+                    .find(|g| g.app_name == app_name || g.id() == app_name)
+                {
+                    Some(game) => {
+                        heading(tr!("info-title"));
+                        log::info!("{}", tr!("info-name", value = game.app_name.as_str()));
+                        log::info!("{}", tr!("info-store", value = game.provider.as_str()));
+                        log::info!(
+                            "{}",
+                            tr!("info-game-title", value = game.app_title.as_str())
+                        );
+                        log::info!("{}", tr!("info-version", value = game.app_version.as_str()));
+                        log::info!(
+                            "{}",
+                            tr!(
+                                "info-install-path",
+                                value = game.install_path.display().to_string()
+                            )
+                        );
+                        log::info!(
+                            "{}",
+                            tr!("info-executable", value = game.executable.as_str())
+                        );
+                        log::info!(
+                            "{}",
+                            tr!("info-install-size", value = format_size(game.disk_usage()))
+                        );
+                        if game.download_size > 0 {
+                            log::info!(
+                                "{}",
+                                tr!(
+                                    "info-download-size",
+                                    value = format_size(game.download_size)
+                                )
+                            );
+                        }
+                        match manager.save_dir(&game.app_name) {
+                            Ok(Some(path)) => log::info!(
+                                "{}",
+                                tr!("info-save-path", value = path.display().to_string())
+                            ),
+                            Ok(None) => {}
+                            Err(e) => log::info!(
+                                "{}",
+                                tr!("info-save-path-unresolved", error = e.to_string())
+                            ),
+                        }
+                    }
+                    None => {
+                        log::error!("{}", tr!("game-not-found", app = app_name.as_str()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Status => {
+                heading(tr!("status-title"));
+                log::info!(
+                    "{}",
+                    tr!("status-version", version = env!("CARGO_PKG_VERSION"))
+                );
+                log::info!(
+                    "{}",
+                    tr!(
+                        "status-authenticated",
+                        value = yes_no(auth.is_authenticated())
+                    )
+                );
+                for id in providers::PROVIDER_IDS
+                    .iter()
+                    .filter(|id| **id != providers::EPIC)
+                {
+                    let logged_in = matches!(AuthToken::load_for(id), Ok(Some(_)));
+                    log::info!(
+                        "{}",
+                        tr!(
+                            "status-authenticated-store",
+                            store = *id,
+                            value = yes_no(logged_in)
+                        )
+                    );
+                }
+                log::info!("{}", tr!("status-configuration"));
+                log::info!(
+                    "  {}",
+                    tr!(
+                        "status-install-dir",
+                        path = config.install_dir.display().to_string()
+                    )
+                );
+                log::info!(
+                    "  {}",
+                    tr!("status-log-level", level = config.log_level.as_str())
+                );
+                log::info!(
+                    "  {}",
+                    tr!(
+                        "status-language",
+                        language = rauncher_core::i18n::language()
+                    )
+                );
+
+                if let Ok(config_path) = Config::config_path() {
+                    log::info!(
+                        "{}",
+                        tr!(
+                            "status-config-path",
+                            path = config_path.display().to_string()
+                        )
+                    );
+                }
+
+                if let Ok(data_dir) = Config::data_dir() {
+                    log::info!(
+                        "{}",
+                        tr!("status-data-dir", path = data_dir.display().to_string())
+                    );
+                }
+            }
+
+            Commands::Doctor => {
+                let manager = GameManager::new(config, auth)?;
+                heading(tr!("doctor-title"));
+
+                let checks = manager.doctor().await;
+                let mut fixes = Vec::new();
+                for check in &checks {
+                    let (line, fix) = describe_check(check);
+                    match check.severity() {
+                        Severity::Ok => log::info!("  ✓ {}", line),
+                        Severity::Warning => log::warn!("  ! {}", line),
+                        Severity::Error => log::error!("  ✗ {}", line),
+                    }
+                    // Several services down share one fix; give it once
+                    if let Some(fix) = fix.filter(|fix| !fixes.contains(fix)) {
+                        log::info!("    → {}", fix);
+                        fixes.push(fix);
+                    }
+                }
+
+                let count = |severity| checks.iter().filter(|c| c.severity() == severity).count();
+                let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+                if errors + warnings == 0 {
+                    log::info!("{}", tr!("doctor-all-good"));
+                } else {
+                    log::info!(
+                        "{}",
+                        tr!("doctor-summary", errors = errors, warnings = warnings)
+                    );
+                }
+                if errors > 0 {
+                    std::process::exit(1);
+                }
+            }
+
+            Commands::Update {
+                app_name,
+                all,
+                check_only,
+                dry_run,
+                shutdown_after,
+                suspend_after,
+                exec_after,
+            } => {
+                let after = after_downloads(shutdown_after, suspend_after, exec_after);
+                if !auth.is_authenticated() {
+                    log::error!("{}", tr!("not-authenticated"));
+                    std::process::exit(1);
+                }
+
+                let mut manager = GameManager::new(config, auth)?;
+
+                if dry_run {
+                    let addresses = match app_name {
+                        Some(app_name) => vec![app_name],
+                        None => manager
+                            .list_installed()?
+                            .iter()
+                            .filter(|game| !game.pinned)
+                            .map(|game| game.id())
+                            .collect(),
+                    };
+                    let mut failed = false;
+                    for address in addresses {
+                        match manager.plan_update(&address).await {
+                            Ok(plan) => log_plan(&plan),
+                            Err(e) => {
+                                failed = true;
+                                log::error!(
+                                    "{}",
+                                    tr!(
+                                        "plan-failed",
+                                        app = address.as_str(),
+                                        error = e.to_string()
+                                    )
+                                );
+                            }
+                        }
+                    }
+                    if failed {
+                        std::process::exit(1);
+                    }
+                    return Ok(());
+                }
+
+                let progress = report_install_progress(&mut manager, cli.progress_json);
+
+                if all && check_only {
+                    for (game, result) in manager.check_all_for_updates().await? {
+                        let id = game.id();
+                        match result {
+                            Ok(Some(build)) if game.pinned => log::info!(
+                                "  {}",
+                                tr!(
+                                    "update-line-pinned",
+                                    id = id,
+                                    version = build.version,
+                                    current = game.app_version.as_str()
+                                )
+                            ),
+                            Ok(Some(build)) => log::info!(
+                                "  {}",
+                                tr!("update-line-available", id = id, version = build.version)
+                            ),
+                            Ok(None) => log::info!("  {}", tr!("update-line-current", id = id)),
+                            Err(e) => log::error!("  {}: {}", id, e),
+                        }
+                    }
+
+                    return Ok(());
+                }
+
+                if all {
+                    let outcome = manager.update_all().await;
+                    drop(manager);
+                    let _ = progress.await;
+                    let failed = power::failures(&outcome);
+
+                    match &outcome {
+                        Ok(results) => {
+                            for (app_name, result) in results {
+                                if let Err(e) = result {
+                                    log::error!(
+                                        "{}",
+                                        tr!(
+                                            "update-game-failed",
+                                            app = app_name.as_str(),
+                                            error = e.to_string()
+                                        )
+                                    );
+                                }
+                            }
+
+                            log::info!(
+                                "{}",
+                                tr!("update-processed", count = results.len(), failed = failed)
+                            );
+                        }
+                        Err(e) => log::error!("{}", tr!("update-failed", error = e.to_string())),
+                    }
+                    finish_downloads(&after, failed).await;
+                    return Ok(());
+                }
+
+                // clap guarantees an app name when --all isn't given
+                let app_name = app_name.unwrap_or_default();
+
+                if check_only {
+                    log::info!("{}", tr!("update-checking", app = app_name.as_str()));
+                    match manager.check_for_updates(&app_name).await {
+                        Ok(Some(build)) => {
+                            let version = build.version.as_str();
+                            match build.date {
+                                Some(date) => log::info!(
+                                    "{}",
+                                    tr!(
+                                        "update-available-dated",
+                                        version = version,
+                                        date = date.format("%Y-%m-%d").to_string()
+                                    )
+                                ),
+                                None => {
+                                    log::info!("{}", tr!("update-available", version = version))
+                                }
+                            }
+                            if let Some(notes) = &build.release_notes {
+                                heading(tr!("update-release-notes"));
+                                for line in notes.lines() {
+                                    log::info!("  {}", line);
+                                }
+                            }
+                        }
+                        Ok(None) => {
+                            log::info!("{}", tr!("update-up-to-date"));
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("update-check-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                } else {
+                    let result = manager.update_game(&app_name).await;
+                    drop(manager);
+                    let _ = progress.await;
+                    match &result {
+                        Ok(()) => log::info!("{}", tr!("update-done")),
+                        Err(e) => log::error!("{}", tr!("update-failed", error = e.to_string())),
+                    }
+                    finish_downloads(&after, usize::from(result.is_err())).await;
+                }
+            }
+
+            Commands::CloudSave {
+                command,
+                app_name,
+                download,
+                upload,
+                snapshot,
+            } => {
+                if !auth.is_authenticated() {
+                    log::error!("{}", tr!("not-authenticated"));
+                    std::process::exit(1);
+                }
+
+                let mut manager = GameManager::new(config, auth)?;
+                if interactive() {
+                    manager.set_prompter(Arc::new(TerminalPrompter));
+                }
+
+                if let Some(CloudSaveCommands::List { app_name }) = command {
+                    match manager.list_cloud_saves(&app_name).await {
+                        Ok(saves) if saves.is_empty() => {
+                            log::info!("{}", tr!("cloud-list-none", app = app_name.as_str()))
+                        }
+                        Ok(saves) => {
+                            heading(tr!("cloud-list-title", app = app_name.as_str()));
+                            for save in saves {
+                                log::info!(
+                                    "  {}",
+                                    tr!(
+                                        "cloud-list-entry",
+                                        id = save.id,
+                                        date = save
+                                            .uploaded_at
+                                            .format("%Y-%m-%d %H:%M:%S")
+                                            .to_string(),
+                                        size = save.size
+                                    )
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("cloud-list-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                    return Ok(());
+                }
+
+                // clap guarantees an app name without a subcommand
+                let app_name = app_name.unwrap_or_default();
+                if !download && !upload {
+                    log::error!("{}", tr!("cloud-specify-direction"));
+                    std::process::exit(1);
+                }
+
+                if download {
+                    match manager
+                        .download_cloud_saves(&app_name, snapshot.as_deref())
+                        .await
+                    {
+                        Ok(()) => {}
+                        Err(e) => {
+                            log::error!("{}", tr!("cloud-download-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+
+                if upload {
+                    match manager.upload_cloud_saves(&app_name).await {
+                        Ok(()) => {}
+                        Err(e) => {
+                            log::error!("{}", tr!("cloud-upload-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+
+            Commands::Backup { app_name, list } => {
+                let manager = GameManager::new(config, auth)?;
+
+                if list {
+                    match manager.list_save_backups(&app_name) {
+                        Ok(snapshots) if snapshots.is_empty() => {
+                            log::info!("{}", tr!("backup-none", app = app_name.as_str()))
+                        }
+                        Ok(snapshots) => {
+                            heading(tr!("backup-title", app = app_name.as_str()));
+                            for (idx, snapshot) in snapshots.iter().enumerate() {
+                                log::info!(
+                                    "  {}",
+                                    tr!(
+                                        "backup-entry",
+                                        index = idx + 1,
+                                        date = snapshot
+                                            .created_at
+                                            .format("%Y-%m-%d %H:%M:%S")
+                                            .to_string(),
+                                        size = snapshot.size
+                                    )
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("backup-list-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                } else {
+                    match manager.backup_saves(&app_name) {
+                        Ok(Some(path)) => {
+                            log::info!("{}", tr!("backup-done", path = path.display().to_string()))
+                        }
+                        Ok(None) => {
+                            log::info!("{}", tr!("backup-no-saves", app = app_name.as_str()));
+                            log::info!("{}", tr!("backup-detect-hint", app = app_name.as_str()));
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("backup-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+
+            Commands::Restore { app_name, snapshot } => {
+                let manager = GameManager::new(config, auth)?;
+
+                match manager.restore_saves(&app_name, snapshot) {
+                    Ok(count) => log::info!("{}", tr!("restore-done", count = count)),
+                    Err(e) => {
+                        log::error!("{}", tr!("restore-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::DetectSaves { app_name, apply } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let suggestions = match manager.detect_save_dirs(&app_name) {
+                    Ok(suggestions) => suggestions,
+                    Err(e) => {
+                        log::error!("{}", tr!("detect-saves-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                if suggestions.is_empty() {
+                    log::info!("{}", tr!("detect-saves-none", app = app_name.as_str()));
+                    return Ok(());
+                }
+
+                match apply {
+                    // Only found by its files changing, the first folder may well be another
+                    // program's; leave the pick to the user
+                    Some(None) if !suggestions[0].name_match => {
+                        log::error!(
+                            "{}",
+                            tr!(
+                                "detect-saves-unsure",
+                                path = suggestions[0].path.display().to_string(),
+                                app = app_name.as_str()
+                            )
+                        );
+                        std::process::exit(1);
+                    }
+                    Some(index) => {
+                        let index = index.unwrap_or(1);
+                        let Some(chosen) = index.checked_sub(1).and_then(|i| suggestions.get(i))
+                        else {
+                            log::error!(
+                                "{}",
+                                tr!(
+                                    "detect-saves-invalid",
+                                    index = index,
+                                    count = suggestions.len()
+                                )
+                            );
+                            std::process::exit(1);
+                        };
+                        manager.set_save_path(&app_name, &chosen.path)?;
+                        log::info!(
+                            "{}",
+                            tr!(
+                                "detect-saves-applied",
+                                app = app_name.as_str(),
+                                path = chosen.path.display().to_string()
+                            )
+                        );
+                    }
+                    None => {
+                        heading(tr!("detect-saves-title", app = app_name.as_str()));
+                        for (idx, suggestion) in suggestions.iter().enumerate() {
+                            let mut reasons = Vec::new();
+                            if suggestion.name_match {
+                                reasons.push(tr!("detect-saves-named"));
+                            }
+                            if suggestion.changed_files > 0 {
+                                reasons.push(tr!(
+                                    "detect-saves-changed",
+                                    count = suggestion.changed_files
+                                ));
+                            }
+                            log::info!(
+                                "  {} ({})",
+                                tr!(
+                                    "detect-saves-entry",
+                                    index = idx + 1,
+                                    path = suggestion.path.display().to_string()
+                                ),
+                                reasons.join(", ")
+                            );
+                        }
+                        log::info!(
+                            "{}",
+                            tr!("detect-saves-apply-hint", app = app_name.as_str())
+                        );
+                    }
+                }
+            }
+
+            Commands::ImportEgl { dir, dry_run } => {
+                let manager = GameManager::new(config, auth)?;
+
+                match manager.import_egl_games(dir.as_deref(), dry_run) {
+                    Ok(games) if games.is_empty() => log::info!("{}", tr!("import-none")),
+                    Ok(games) => {
+                        for game in &games {
+                            log::info!(
+                                "  {}",
+                                tr!(
+                                    "import-entry",
+                                    dry_run = yes_no(dry_run),
+                                    id = game.id(),
+                                    title = game.app_title.as_str(),
+                                    path = game.install_path.display().to_string()
+                                )
+                            );
+                        }
+                        log::info!(
+                            "{}",
+                            tr!(
+                                "import-summary",
+                                dry_run = yes_no(dry_run),
+                                count = games.len()
+                            )
+                        );
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("import-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::EglSync { dir, dry_run } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let report = match manager.egl_sync(dir.as_deref(), dry_run) {
+                    Ok(report) => report,
+                    Err(e) => {
+                        log::error!("{}", tr!("egl-sync-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                let dry = yes_no(dry_run);
+
+                for game in &report.imported {
+                    log::info!(
+                        "  {}",
+                        tr!(
+                            "import-entry",
+                            dry_run = dry,
+                            id = game.id(),
+                            title = game.app_title.as_str(),
+                            path = game.install_path.display().to_string()
+                        )
+                    );
+                }
+                for game in &report.exported {
+                    log::info!(
+                        "  {}",
+                        tr!("egl-sync-exported", dry_run = dry, id = game.id())
+                    );
+                }
+                for (app, version) in &report.updated {
+                    log::info!(
+                        "  {}",
+                        tr!(
+                            "egl-sync-updated",
+                            dry_run = dry,
+                            app = app.as_str(),
+                            version = version.as_str()
+                        )
+                    );
+                }
+                for app in &report.forgotten {
+                    log::info!(
+                        "  {}",
+                        tr!("egl-sync-forgotten", dry_run = dry, app = app.as_str())
+                    );
+                }
+                for app in &report.removed {
+                    log::info!(
+                        "  {}",
+                        tr!("egl-sync-removed", dry_run = dry, app = app.as_str())
+                    );
+                }
+                if report.is_empty() {
+                    log::info!("{}", tr!("egl-sync-none"));
+                }
+            }
+
+            Commands::Migrate { from, dir, dry_run } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let report = match manager.migrate(&from, dir.as_deref(), dry_run) {
+                    Ok(report) => report,
+                    Err(e) => {
+                        log::error!("{}", tr!("migrate-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                let dry = yes_no(dry_run);
+
+                if let Some(account) = &report.account {
+                    log::info!(
+                        "{}",
+                        tr!("migrate-session", dry_run = dry, account = account.as_str())
+                    );
+                    log::warn!("{}", tr!("migrate-session-note", launcher = from.as_str()));
+                }
+                for game in &report.games {
+                    log::info!(
+                        "  {}",
+                        tr!(
+                            "import-entry",
+                            dry_run = dry,
+                            id = game.id(),
+                            title = game.app_title.as_str(),
+                            path = game.install_path.display().to_string()
+                        )
+                    );
+                }
+                for app in &report.settings {
+                    log::info!(
+                        "  {}",
+                        tr!("migrate-settings", dry_run = dry, app = app.as_str())
+                    );
+                }
+                for (app, prefix) in &report.prefixes {
+                    log::info!(
+                        "  {}",
+                        tr!(
+                            "migrate-prefix",
+                            dry_run = dry,
+                            app = app.as_str(),
+                            path = prefix.display().to_string()
+                        )
+                    );
+                }
+                if !report.aliases.is_empty() {
+                    log::info!(
+                        "{}",
+                        tr!(
+                            "migrate-aliases",
+                            dry_run = dry,
+                            count = report.aliases.len()
+                        )
+                    );
+                }
+
+                if report.account.is_none() && report.games.is_empty() && report.aliases.is_empty()
+                {
+                    log::info!("{}", tr!("migrate-none"));
+                } else {
+                    log::info!(
+                        "{}",
+                        tr!("import-summary", dry_run = dry, count = report.games.len())
+                    );
+                }
+            }
+
+            Commands::Export { format, dir } => {
+                let manager = GameManager::new(config, auth)?;
+
+                match manager.export(&format, dir.as_deref()) {
+                    Ok(games) if games.is_empty() => log::info!("{}", tr!("export-none")),
+                    Ok(games) => {
+                        for game in &games {
+                            log::info!(
+                                "  {}",
+                                tr!(
+                                    "export-entry",
+                                    id = game.id(),
+                                    title = game.app_title.as_str()
+                                )
+                            );
+                        }
+                        log::info!("{}", tr!("export-summary", count = games.len()));
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("export-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Assets { command } => {
+                if !auth.is_authenticated() {
+                    log::error!("{}", tr!("not-authenticated"));
+                    std::process::exit(1);
+                }
+
+                let manager = GameManager::new(config, auth)?;
+
+                match command {
+                    AssetCommands::List => match manager.list_ue_assets().await {
+                        Ok(assets) if assets.is_empty() => {
+                            log::info!("{}", tr!("assets-none"))
+                        }
+                        Ok(assets) => {
+                            heading(tr!("assets-title"));
+                            for asset in assets {
+                                log::info!("  {}", asset.app_name);
+                            }
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("assets-list-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    },
+                    AssetCommands::Download { app_name, dir } => {
+                        match manager.download_ue_asset(&app_name, &dir).await {
+                            Ok(path) => log::info!(
+                                "{}",
+                                tr!("assets-downloaded", path = path.display().to_string())
+                            ),
+                            Err(e) => {
+                                log::error!(
+                                    "{}",
+                                    tr!("assets-download-failed", error = e.to_string())
+                                );
+                                std::process::exit(1);
+                            }
+                        }
+                    }
+                }
+            }
+
+            Commands::Logs {
+                follow,
+                game,
+                lines,
+            } => {
+                let latest = match &game {
+                    Some(address) => {
+                        let (_, app_name) = providers::parse_address(address);
+                        logs::latest_game_log(app_name)?
+                    }
+                    None => logs::latest_log()?,
+                };
+
+                let Some(path) = latest else {
+                    log::info!("{}", tr!("logs-none"));
+                    return Ok(());
+                };
+
+                print_log(&path, lines, follow, game.is_none())?;
+            }
+
+            Commands::History {
+                app_name,
+                limit,
+                json,
+            } => {
+                // Uninstalled games have a history too, so names aren't checked against the
+                // installed ones
+                let app_name = app_name
+                    .map(|name| games::resolve_name(&config, &name))
+                    .transpose()?;
+                let mut entries = history::load(app_name.as_deref())?;
+                entries.truncate(limit);
+
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&entries)?);
+                } else if entries.is_empty() {
+                    log::info!("{}", tr!("history-none"));
+                } else {
+                    heading(tr!("history-title"));
+                    for entry in &entries {
+                        print_history_entry(entry);
+                    }
+                }
+            }
+
+            Commands::Stats { json } => {
+                let manager = GameManager::new(config, auth)?;
+                let stats = manager.stats().await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&stats)?);
+                } else {
+                    print_stats(&stats);
+                }
+            }
+
+            Commands::LastRun => {
+                let Some(path) = logs::transcript::latest()? else {
+                    log::info!("{}", tr!("last-run-none"));
+                    return Ok(());
+                };
+
+                print!("{}", std::fs::read_to_string(path)?);
+            }
+
+            Commands::Store {
+                keywords,
+                genre,
+                free,
+                page,
+                open,
+            } => {
+                let query = StoreQuery {
+                    keywords: keywords.join(" "),
+                    genre,
+                    free,
+                    page: page as usize - 1,
+                };
+                let manager = GameManager::new(config, auth)?;
+                let page = match manager.browse_store(&query).await {
+                    Ok(page) => page,
+                    Err(e) => {
+                        log::error!("{}", tr!("store-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                print_store_page(&query, &page);
+
+                if open {
+                    let Some(offer) = page.offers.first() else {
+                        return Ok(());
+                    };
+                    if let Some(url) = &offer.url {
+                        log::info!(
+                            "{}",
+                            tr!(
+                                "store-opening",
+                                title = offer.title.as_str(),
+                                url = url.as_str()
+                            )
+                        );
+                        if let Err(e) = webbrowser::open(url) {
+                            log::debug!("Could not open a browser: {}", e);
+                        }
+                    }
+                }
+            }
+
+            Commands::Wishlist { command } => match command {
+                WishlistCommands::List => {
+                    let wishlist = Wishlist::load()?;
+                    if wishlist.is_empty() {
+                        log::info!("{}", tr!("wishlist-empty"));
+                        return Ok(());
+                    }
+
+                    heading(tr!("wishlist-title"));
+                    for item in wishlist.items() {
+                        let title = item.title.as_str();
+                        let line = if item.owned {
+                            tr!("wishlist-item-owned", title = title)
+                        } else if item.giveaway {
+                            tr!("store-offer-free", title = title)
+                        } else {
+                            match &item.price {
+                                Some(price) if price.free => tr!("store-offer-free", title = title),
+                                Some(price) if price.discount > 0 => tr!(
+                                    "store-offer-discounted",
+                                    title = title,
+                                    price = price.current.as_str(),
+                                    discount = price.discount,
+                                    original = price.original.as_str()
+                                ),
+                                Some(price) => tr!(
+                                    "store-offer",
+                                    title = title,
+                                    price = price.current.as_str()
+                                ),
+                                None => tr!("store-offer-no-price", title = title),
+                            }
+                        };
+                        log::info!("  {}", line);
+                    }
+                }
+                WishlistCommands::Add { keywords } => {
+                    let manager = GameManager::new(config, auth)?;
+                    match manager.wishlist_add(&keywords.join(" ")).await {
+                        Ok((offer, true)) => {
+                            log::info!("{}", tr!("wishlist-added", title = offer.title.as_str()));
+                        }
+                        Ok((offer, false)) => {
+                            log::info!("{}", tr!("wishlist-already", title = offer.title.as_str()));
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("wishlist-add-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                WishlistCommands::Remove { title } => {
+                    let title = title.join(" ");
+                    let mut wishlist = Wishlist::load()?;
+                    match wishlist.remove(&title) {
+                        Some(item) => {
+                            wishlist.save()?;
+                            log::info!("{}", tr!("wishlist-removed", title = item.title.as_str()));
+                        }
+                        None => {
+                            log::error!("{}", tr!("wishlist-not-found", title = title.as_str()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                WishlistCommands::Check => {
+                    let manager = GameManager::new(config, auth)?;
+                    let events = match manager.check_wishlist().await {
+                        Ok(events) => events,
+                        Err(e) => {
+                            log::error!("{}", tr!("wishlist-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    };
+
+                    if events.is_empty() {
+                        log::info!("{}", tr!("wishlist-no-news"));
+                    }
+                    for event in events {
+                        let line = match event {
+                            WishlistEvent::OnSale {
+                                title,
+                                discount,
+                                price,
+                            } => tr!(
+                                "wishlist-on-sale",
+                                title = title.as_str(),
+                                discount = discount,
+                                price = price.as_str()
+                            ),
+                            WishlistEvent::Free { title } => {
+                                tr!("wishlist-free", title = title.as_str())
+                            }
+                        };
+                        log::info!("{}", line);
+                    }
+                }
+            },
+
+            Commands::Feed { country } => {
+                let manager = GameManager::new(config, auth)?;
+
+                let written = match manager.pending_updates().await {
+                    Ok(pending) => manager.write_feeds(&country, &pending).await,
+                    Err(e) => Err(e),
+                };
+                match written {
+                    Ok((rss, ical)) => {
+                        log::info!(
+                            "{}",
+                            tr!("feed-rss-written", path = rss.display().to_string())
+                        );
+                        log::info!(
+                            "{}",
+                            tr!("feed-ical-written", path = ical.display().to_string())
+                        );
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("feed-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Config { command } => {
+                let mut config = config;
+                match command {
+                    ConfigCommands::Get { key: None } => {
+                        let contents = toml::to_string_pretty(&config)
+                            .map_err(|e| rauncher_core::Error::Config(e.to_string()))?;
+                        print!("{}", contents);
+                    }
+                    ConfigCommands::Get { key: Some(key) } => match config.get_value(&key)? {
+                        Some(toml::Value::String(value)) => println!("{}", value),
+                        Some(toml::Value::Table(table)) => print!("{}", table),
+                        Some(value) => println!("{}", value),
+                        None => {
+                            log::error!("{}", tr!("config-not-set", key = key.as_str()));
+                            std::process::exit(1);
+                        }
+                    },
+                    ConfigCommands::Set { key, value } => {
+                        if let Err(e) = config.set_value(&key, &value) {
+                            log::error!("{}", tr!("config-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                        config.save()?;
+                        log::info!(
+                            "{}",
+                            tr!("config-set", key = key.as_str(), value = value.as_str())
+                        );
+                        if key == "install_dir" {
+                            if let Some(command) = sandbox::missing_permission(&config.install_dir)
+                            {
+                                log::warn!(
+                                    "{}",
+                                    tr!("config-flatpak-permission", command = command)
+                                );
+                            }
+                        }
+                    }
+                    ConfigCommands::Unset { key } => match config.unset_value(&key) {
+                        Ok(true) => {
+                            config.save()?;
+                            log::info!("{}", tr!("config-unset", key = key.as_str()));
+                        }
+                        Ok(false) => log::info!("{}", tr!("config-not-set", key = key.as_str())),
+                        Err(e) => {
+                            log::error!("{}", tr!("config-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    },
+                    ConfigCommands::Path => println!("{}", Config::config_path()?.display()),
+                }
+            }
+
+            Commands::DiskUsage {
+                clean_shader_cache,
+                permanent,
+                clean_partial,
+            } => {
+                if clean_shader_cache {
+                    let freed = storage::clean_shader_caches(removal(permanent))?;
+                    log::info!(
+                        "{}",
+                        tr!(
+                            "disk-usage-cleaned-shaders",
+                            size = format_size(freed),
+                            trash = yes_no(!permanent)
+                        )
+                    );
+                }
+                if clean_partial {
+                    let freed = storage::clean_partial_downloads(&config, &[], removal(permanent))?;
+                    log::info!(
+                        "{}",
+                        tr!(
+                            "disk-usage-cleaned-partial",
+                            size = format_size(freed),
+                            trash = yes_no(!permanent)
+                        )
+                    );
+                }
+
+                let report = match StorageReport::collect(&config) {
+                    Ok(report) => report,
+                    Err(e) => {
+                        log::error!("{}", tr!("disk-usage-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                print_storage_report(&report);
+            }
+
+            Commands::ShaderCache {
+                command: ShaderCacheCommands::Update { app_name },
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                match manager.update_shader_cache(&app_name).await {
+                    Ok(ShaderCacheUpdate::Installed { bytes }) => log::info!(
+                        "{}",
+                        tr!(
+                            "shader-cache-installed",
+                            app = app_name.as_str(),
+                            size = format_size(bytes)
+                        )
+                    ),
+                    Ok(ShaderCacheUpdate::UpToDate) => {
+                        log::info!(
+                            "{}",
+                            tr!("shader-cache-up-to-date", app = app_name.as_str())
+                        )
+                    }
+                    Ok(ShaderCacheUpdate::Unavailable) => {
+                        log::info!(
+                            "{}",
+                            tr!("shader-cache-unavailable", app = app_name.as_str())
+                        )
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("shader-cache-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Prefix { command } => {
+                let manager = GameManager::new(config, auth)?;
+                let command = match command {
+                    PrefixCommands::Create { app_name } => {
+                        match manager.create_prefix(&app_name) {
+                            Ok((prefix, true)) => log::info!(
+                                "{}",
+                                tr!("prefix-created", path = prefix.display().to_string())
+                            ),
+                            Ok((prefix, false)) => log::info!(
+                                "{}",
+                                tr!("prefix-exists", path = prefix.display().to_string())
+                            ),
+                            Err(e) => {
+                                log::error!("{}", tr!("prefix-failed", error = e.to_string()));
+                                std::process::exit(1);
+                            }
+                        }
+                        return Ok(());
+                    }
+                    PrefixCommands::Run { app_name, command } => {
+                        let args: Vec<OsString> = command.into_iter().map(OsString::from).collect();
+                        manager.prefix_command(&app_name, &args)
+                    }
+                    PrefixCommands::Winecfg { app_name } => {
+                        manager.prefix_command(&app_name, &[OsString::from("winecfg")])
+                    }
+                    PrefixCommands::Winetricks { app_name, verbs } => {
+                        manager.winetricks_command(&app_name, &verbs)
+                    }
+                };
+
+                let status = command.and_then(|command| {
+                    log::debug!("Running {:?} {:?}", command.program, command.args);
+                    Ok(command.to_command().status()?)
+                });
+                match status {
+                    Ok(status) if status.success() => {}
+                    Ok(status) => {
+                        log::error!("{}", tr!("prefix-exit", status = status.to_string()));
+                        std::process::exit(status.code().unwrap_or(1));
+                    }
+                    Err(e) => {
+                        log::error!("{}", tr!("prefix-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            Commands::Runners { command } => match command {
+                RunnerCommands::List {
+                    available: false, ..
+                } => print_runners(&runners::installed()?),
+                RunnerCommands::List {
+                    available: true,
+                    kind,
+                } => {
+                    let kind = runner_kind(&kind)?;
+                    let releases = match runners::available(kind).await {
+                        Ok(releases) => releases,
+                        Err(e) => {
+                            log::error!("{}", tr!("runners-list-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    };
+                    let installed = runners::installed()?;
+
+                    heading(tr!("runners-available-title", kind = kind.as_str()));
+                    for release in releases {
+                        let is_installed = installed
+                            .iter()
+                            .any(|r| r.kind == kind && r.version == release.version);
+                        log::info!(
+                            "  {}",
+                            tr!(
+                                "runners-available-line",
+                                version = release.version.as_str(),
+                                size = format_size(release.size),
+                                date = release
+                                    .published_at
+                                    .map(|date| date.format("%Y-%m-%d").to_string())
+                                    .unwrap_or_default(),
+                                installed = yes_no(is_installed)
+                            )
+                        );
+                    }
+                }
+                RunnerCommands::Install { version, kind } => {
+                    let kind = runner_kind(&kind)?;
+                    let installed = async {
+                        let release = runners::release(kind, version.as_deref()).await?;
+                        log::info!(
+                            "{}",
+                            tr!(
+                                "runners-downloading",
+                                version = release.version.as_str(),
+                                size = format_size(release.size)
+                            )
+                        );
+                        runners::install(&release).await
+                    }
+                    .await;
+
+                    match installed {
+                        Ok(runner) => {
+                            log::info!("{}", tr!("runners-installed", name = runner.name.as_str()));
+                            log::info!("{}", tr!("runners-use-hint", name = runner.name.as_str()));
+                        }
+                        Err(e) => {
+                            log::error!("{}", tr!("runners-install-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                RunnerCommands::Remove { name, permanent } => {
+                    match runners::remove(&config, &name, removal(permanent)) {
+                        Ok(()) => log::info!(
+                            "{}",
+                            tr!(
+                                "runners-removed",
+                                name = name.as_str(),
+                                trash = yes_no(!permanent)
+                            )
+                        ),
+                        Err(e) => {
+                            log::error!("{}", tr!("runners-remove-failed", error = e.to_string()));
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            },
+
+            Commands::Orphans {
+                adopt,
+                delete,
+                permanent,
+                yes,
+            } => {
+                let manager = GameManager::new(config, auth)?;
+                let orphans = match manager.find_orphans().await {
+                    Ok(orphans) => orphans,
+                    Err(e) => {
+                        log::error!("{}", tr!("orphans-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                let delete = delete.then(|| removal(permanent));
+                handle_orphans(&manager, orphans, adopt, delete, yes)?;
+            }
+
+            Commands::Daemon {
+                listen,
+                interval,
+                country,
+            } => {
+                let options = daemon::DaemonOptions {
+                    listen,
+                    interval: std::time::Duration::from_secs(interval * 60),
+                    country,
+                };
+
+                if let Err(e) = daemon::run(config, auth, options).await {
+                    log::error!("{}", tr!("daemon-failed", error = e.to_string()));
+                    std::process::exit(1);
+                }
+            }
+
+            Commands::Queue { command } => {
+                // The queue lives in the local `rauncher serve`, reached like a remote one at
+                // the address it recorded
+                let Some((address, token)) = serve::running()? else {
+                    log::error!("{}", tr!("queue-no-serve"));
+                    std::process::exit(1);
+                };
+                let command = Commands::Queue { command };
+                remote::run(&address.to_string(), token, command, cli.progress_json).await?;
+            }
+
+            Commands::Serve { listen, token_file } => {
+                let token = match token_file {
+                    Some(path) => Some(std::fs::read_to_string(path)?),
+                    None => std::env::var("RAUNCHER_SERVE_TOKEN").ok(),
+                };
+                if let Err(e) = serve::run(config, auth, listen, token).await {
+                    log::error!("{}", tr!("serve-failed", error = e.to_string()));
+                    std::process::exit(1);
+                }
+            }
+
+            #[cfg(feature = "gui")]
+            Commands::Gui { fullscreen } => run_gui(fullscreen || cli.fullscreen),
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "gui")]
+fn run_gui(fullscreen: bool) {
+    if let Err(e) = rauncher_gui::run(rauncher_gui::GuiOptions { fullscreen }) {
+        log::error!("{}", tr!("gui-failed", error = e.to_string()));
+        std::process::exit(1);
+    }
+}
+
+/// List what `verify` found, write the report and delete extra files as asked, exiting with
+/// an error while game files are still missing or damaged
+fn handle_verify_report(
+    manager: &GameManager,
+    result: &VerifyReport,
+    report: Option<PathBuf>,
+    format: Option<String>,
+    delete_extra: Option<Removal>,
+    yes: bool,
+) -> Result<()> {
+    for file in &result.problems {
+        log::info!(
+            "  {}",
+            tr!(
+                "verify-entry",
+                status = file.status.as_str(),
+                path = file.path.as_str()
+            )
+        );
+    }
+    let (mismatched, missing, extra) = (
+        result.count(FileStatus::Mismatched),
+        result.count(FileStatus::Missing),
+        result.count(FileStatus::Extra),
+    );
+    if result.is_clean() {
+        log::info!("{}", tr!("verify-clean", count = result.files_checked));
+    } else {
+        log::info!(
+            "{}",
+            tr!(
+                "verify-summary",
+                count = result.files_checked,
+                mismatched = mismatched,
+                missing = missing,
+                extra = extra
+            )
+        );
+    }
+
+    if let Some(path) = report {
+        let csv = match format.as_deref() {
+            Some(format) => format == "csv",
+            None => path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("csv")),
+        };
+        let contents = if csv {
+            result.to_csv()
+        } else {
+            result.to_json()?
+        };
+        if path == Path::new("-") {
+            print!("{}", contents);
+        } else {
+            std::fs::write(&path, contents)?;
+            log::info!(
+                "{}",
+                tr!("verify-report-written", path = path.display().to_string())
+            );
+        }
+    }
+
+    if let Some(removal) = delete_extra.filter(|_| extra > 0) {
+        let trash = yes_no(removal == Removal::Trash);
+        if !yes && !confirm(tr!("verify-delete-confirm", count = extra, trash = trash)) {
+            log::info!("{}", tr!("verify-delete-aborted"));
+        } else {
+            match manager.delete_extra_files(result, removal) {
+                Ok(freed) => log::info!(
+                    "{}",
+                    tr!(
+                        "verify-deleted",
+                        count = extra,
+                        size = format_size(freed),
+                        trash = trash
+                    )
+                ),
+                Err(e) => {
+                    log::error!("{}", tr!("verify-delete-failed", error = e.to_string()));
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    if mismatched + missing > 0 {
+        log::info!("{}", tr!("verify-hint"));
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Uninstall every game matching `filter` after listing them and asking for confirmation
+fn uninstall_matching(
+    manager: &GameManager,
+    filter: &UninstallFilter,
+    options: &UninstallOptions,
+    yes: bool,
+) -> Result<()> {
+    let candidates = manager.uninstall_candidates(filter)?;
+    if candidates.is_empty() {
+        log::info!("{}", tr!("uninstall-matching-none"));
+        return Ok(());
+    }
+
+    let total: u64 = candidates.iter().map(|(_, size)| size).sum();
+    heading(tr!("uninstall-matching-title"));
+    for (game, size) in &candidates {
+        let played = game
+            .last_played
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| tr!("uninstall-never-played"));
+        log::info!(
+            "  {}",
+            tr!(
+                "uninstall-matching-entry",
+                id = game.id(),
+                title = game.app_title.as_str(),
+                size = format_size(*size),
+                played = played
+            )
+        );
+    }
+    log::info!(
+        "{}",
+        tr!(
+            "uninstall-matching-total",
+            count = candidates.len(),
+            size = format_size(total)
+        )
+    );
+
+    if !yes && !confirm(tr!("uninstall-confirm", count = candidates.len())) {
+        log::info!("{}", tr!("uninstall-aborted"));
+        return Ok(());
+    }
+
+    let mut freed = 0;
+    let mut removed = 0;
+    let mut failed = false;
+    for (game, size) in candidates {
+        match manager.uninstall_game_with_options(&game.id(), options) {
+            Ok(()) => {
+                log::info!("{}", tr!("uninstall-game-done", id = game.id()));
+                freed += size;
+                removed += 1;
+            }
+            Err(e) => {
+                log::error!(
+                    "{}",
+                    tr!(
+                        "uninstall-game-failed",
+                        id = game.id(),
+                        error = e.to_string()
+                    )
+                );
+                failed = true;
+            }
+        }
+    }
+
+    log::info!(
+        "{}",
+        tr!(
+            "uninstall-bulk-done",
+            count = removed,
+            size = format_size(freed),
+            trash = yes_no(options.removal == Removal::Trash)
+        )
+    );
+    if failed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Print the last `lines` lines of a log file, then with `follow` keep printing what gets
+/// appended; rauncher's own log moves on to the next file when it rotates
+fn print_log(path: &Path, lines: usize, follow: bool, rotates: bool) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let bytes = std::fs::read(path)?;
+    let contents = String::from_utf8_lossy(&bytes);
+    let all: Vec<_> = contents.lines().collect();
+    for line in &all[all.len().saturating_sub(lines)..] {
+        println!("{}", line);
+    }
+
+    if !follow {
+        return Ok(());
+    }
+
+    let mut path = path.to_path_buf();
+    let mut file = std::fs::File::open(&path)?;
+    file.seek(SeekFrom::Start(bytes.len() as u64))?;
+
+    loop {
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended)?;
+        print!("{}", String::from_utf8_lossy(&appended));
+        std::io::stdout().flush()?;
+
+        if appended.is_empty() {
+            if rotates {
+                if let Some(newer) = logs::latest_log()?.filter(|newest| *newest != path) {
+                    file = std::fs::File::open(&newer)?;
+                    path = newer;
+                    continue;
+                }
+            }
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+    }
+}
+
+/// Log a title underlined to its own width
+/// Print who a store session belongs to, when its token expires and what it may do
+fn print_session(
+    store: &str,
+    display_name: Option<&str>,
+    account_id: &str,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    scopes: &[String],
+) {
+    heading(tr!("auth-status-title", store = store));
+    if let Some(name) = display_name {
+        log::info!("{}", tr!("auth-status-display-name", value = name));
+    }
+    log::info!("{}", tr!("auth-status-account-id", value = account_id));
+
+    let time = expires_at.format("%Y-%m-%d %H:%M UTC").to_string();
+    let remaining = expires_at - chrono::Utc::now();
+    if remaining > chrono::Duration::zero() {
+        log::info!(
+            "{}",
+            tr!(
+                "auth-status-expires",
+                time = time,
+                minutes = remaining.num_minutes()
+            )
+        );
+    } else {
+        log::info!("{}", tr!("auth-status-expired", time = time));
+    }
+
+    if !scopes.is_empty() {
+        log::info!("{}", tr!("auth-status-scopes", value = scopes.join(", ")));
+    }
+}
+
+fn handle_orphans(
+    manager: &GameManager,
+    orphans: Vec<OrphanedInstall>,
+    adopt: bool,
+    delete: Option<Removal>,
+    yes: bool,
+) -> Result<()> {
+    if orphans.is_empty() {
+        log::info!("{}", tr!("orphans-none"));
+        return Ok(());
+    }
+
+    heading(tr!("orphans-title"));
+    for orphan in &orphans {
+        let path = orphan.path.display().to_string();
+        let line = match &orphan.matched {
+            Some(matched) => tr!(
+                "orphans-identified",
+                path = path,
+                size = format_size(orphan.size),
+                title = matched.app_title.as_str(),
+                id = providers::qualified_id(&matched.provider, &matched.app_name),
+                version = matched.app_version.as_str(),
+                found = matched.files_found,
+                total = matched.files_total
+            ),
+            None => tr!(
+                "orphans-unidentified",
+                path = path,
+                size = format_size(orphan.size)
+            ),
+        };
+        log::info!("  {}", line);
+    }
+
+    if !adopt && delete.is_none() {
+        log::info!("{}", tr!("orphans-hint"));
+        return Ok(());
+    }
+
+    let mut failed = false;
+    let mut remaining = Vec::new();
+    for orphan in orphans {
+        if !adopt || orphan.matched.is_none() {
+            remaining.push(orphan);
+            continue;
+        }
+
+        let path = orphan.path.display().to_string();
+        match manager.adopt_orphan(&orphan) {
+            Ok(game) => log::info!("{}", tr!("orphans-adopted", path = path, id = game.id())),
+            Err(e) => {
+                log::error!(
+                    "{}",
+                    tr!("orphans-adopt-failed", path = path, error = e.to_string())
+                );
+                failed = true;
+            }
+        }
+    }
+
+    if let Some(removal) = delete.filter(|_| !remaining.is_empty()) {
+        let trash = yes_no(removal == Removal::Trash);
+        let question = tr!("orphans-confirm", count = remaining.len(), trash = trash);
+        if !yes && !confirm(question) {
+            log::info!("{}", tr!("orphans-aborted"));
+        } else {
+            for orphan in &remaining {
+                let path = orphan.path.display().to_string();
+                match orphan.delete(removal) {
+                    Ok(freed) => log::info!(
+                        "{}",
+                        tr!(
+                            "orphans-deleted",
+                            path = path,
+                            size = format_size(freed),
+                            trash = trash
+                        )
+                    ),
+                    Err(e) => {
+                        log::error!(
+                            "{}",
+                            tr!("orphans-delete-failed", path = path, error = e.to_string())
+                        );
+                        failed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn print_storage_report(report: &StorageReport) {
+    heading(tr!("disk-usage-title"));
+
+    for root in &report.roots {
+        let path = root.path.display().to_string();
+        let line = match (root.free, root.capacity) {
+            (Some(free), Some(capacity)) => tr!(
+                "disk-usage-root",
+                path = path,
+                free = format_size(free),
+                capacity = format_size(capacity)
+            ),
+            _ => tr!("disk-usage-root-unknown", path = path),
+        };
+        log::info!("");
+        log::info!("{}", line);
+
+        if root.games.is_empty() {
+            log::info!("  {}", tr!("disk-usage-root-empty"));
+        }
+        for game in &root.games {
+            log::info!(
+                "  {}",
+                tr!(
+                    "disk-usage-game",
+                    title = game.app_title.as_str(),
+                    app = game.app_name.as_str(),
+                    total = format_size(game.total()),
+                    install = format_size(game.install),
+                    prefix = format_size(game.prefix),
+                    shaders = format_size(game.shader_cache)
+                )
+            );
+        }
+    }
+
+    let partial_size: u64 = report.partial_downloads.iter().map(|p| p.size).sum();
+    log::info!("");
+    log::info!(
+        "{}",
+        tr!(
+            "disk-usage-shader-cache",
+            size = format_size(report.shader_cache)
+        )
+    );
+    log::info!(
+        "{}",
+        tr!(
+            "disk-usage-partial",
+            count = report.partial_downloads.len(),
+            size = format_size(partial_size)
+        )
+    );
+    for partial in &report.partial_downloads {
+        log::info!(
+            "  {} ({})",
+            partial.path.display(),
+            format_size(partial.size)
+        );
+    }
+
+    if report.reclaimable() > 0 {
+        log::info!(
+            "{}",
+            tr!(
+                "disk-usage-clean-hint",
+                size = format_size(report.reclaimable())
+            )
+        );
+    }
+}
+
+fn print_runners(installed: &[runners::Runner]) {
+    if installed.is_empty() {
+        log::info!("{}", tr!("runners-none"));
+        return;
+    }
+
+    heading(tr!("runners-title"));
+    for runner in installed {
+        log::info!(
+            "  {}",
+            tr!(
+                "runners-line",
+                name = runner.name.as_str(),
+                kind = runner.kind.as_str(),
+                version = runner.version.as_str()
+            )
+        );
+    }
+}
+
+/// Let the user pick a game, from the installed ones or the library, by typing any part of its
+/// title. Only works on a terminal; leaving the picker quits.
+async fn pick_game(
+    manager: &GameManager,
+    installed: bool,
+    provider: Option<&str>,
+) -> Result<String> {
+    if !interactive() {
+        log::error!("{}", tr!("pick-needs-terminal"));
+        std::process::exit(1);
+    }
+
+    let collections = Collections::load()?;
+    let installed_games = manager.list_installed()?;
+    let mut games: Vec<(String, String)> = if installed {
+        installed_games
+            .iter()
+            .map(|game| (game.id(), game.app_title.clone()))
+            .collect()
+    } else {
+        let library = match provider {
+            Some(provider) => manager.list_provider_library(provider).await?,
+            None => manager.list_merged_library().await?,
+        };
+        library
+            .into_iter()
+            .filter(|game| !installed_games.iter().any(|g| g.id() == game.id()))
+            .map(|game| (game.id(), game.app_title))
+            .collect()
+    };
+    games.retain(|(id, _)| collections.shows(id, &collections::Filter::default()));
+    games.sort_by_key(|(_, title)| title.to_lowercase());
+
+    if games.is_empty() {
+        if installed {
+            log::info!("{}", tr!("list-no-installed"));
+        } else {
+            log::info!("{}", tr!("pick-nothing-to-install"));
+        }
+        std::process::exit(0);
+    }
+
+    let labels: Vec<String> = games
+        .iter()
+        .map(|(id, title)| format!("{} ({})", title, id))
+        .collect();
+    let prompt = if installed {
+        tr!("pick-launch")
+    } else {
+        tr!("pick-install")
+    };
+    match pick(&prompt, &labels) {
+        Some(index) => Ok(games.swap_remove(index).0),
+        None => std::process::exit(0),
+    }
+}
+
+/// When `name` matches no game, offer the games it may have been meant for: picked from on a
+/// terminal, listed otherwise. The command goes on with `name` when none is picked, since the
+/// store may still know it.
+fn did_you_mean(config: &Config, name: &str) -> Option<String> {
+    let mut suggestions = games::suggest_names(config, name);
+    if suggestions.is_empty() {
+        return None;
+    }
+    let labels: Vec<String> = suggestions
+        .iter()
+        .map(|(address, title)| format!("{} ({})", title, address))
+        .collect();
+
+    if interactive() {
+        let index = choose(tr!("did-you-mean-choose", name = name), &labels)?;
+        Some(suggestions.swap_remove(index).0)
+    } else {
+        log::warn!(
+            "{}",
+            tr!("did-you-mean", name = name, games = labels.join(", "))
+        );
+        None
+    }
+}
+
+fn runner_kind(kind: &str) -> Result<RunnerKind> {
+    kind.parse().map_err(rauncher_core::Error::Other)
+}
+
+fn print_store_page(query: &StoreQuery, page: &StorePage) {
+    heading(tr!(
+        "store-title",
+        page = query.page + 1,
+        pages = page.pages(),
+        country = page.country.as_str()
+    ));
+    if page.stale {
+        log::info!("{}", tr!("store-stale"));
+    }
+
+    for game in &page.giveaways {
+        log::info!("  {}", tr!("store-giveaway", title = game.title.as_str()));
+        if let Some(url) = &game.url {
+            log::info!("    {}", url);
+        }
+    }
+
+    if page.offers.is_empty() {
+        log::info!("{}", tr!("store-empty"));
+    }
+    for offer in &page.offers {
+        let title = offer.title.as_str();
+        let line = match &offer.price {
+            Some(price) if price.free => tr!("store-offer-free", title = title),
+            Some(price) if price.discount > 0 => tr!(
+                "store-offer-discounted",
+                title = title,
+                price = price.current.as_str(),
+                discount = price.discount,
+                original = price.original.as_str()
+            ),
+            Some(price) => tr!("store-offer", title = title, price = price.current.as_str()),
+            None => tr!("store-offer-no-price", title = title),
+        };
+        log::info!("  {}", line);
+        if let Some(url) = &offer.url {
+            log::info!("    {}", url);
+        }
+    }
+}
+
+/// Log how downloads and verifications are going every few seconds from the manager's progress
+/// events, or with `json`, print every event on stdout for wrappers. The returned task ends
+/// once the manager is dropped and the last event is out.
+fn report_install_progress(manager: &mut GameManager, json: bool) -> tokio::task::JoinHandle<()> {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    manager.set_progress(sender);
+
+    tokio::spawn(async move {
+        let mut logged: Option<Instant> = None;
+        // Files and bytes a verification will check
+        let mut verifying = (0, 0);
+        while let Some(event) = receiver.recv().await {
+            if json {
+                print_json_line(&event);
+                continue;
+            }
+
+            let stats = match event {
+                InstallProgress::Transfer { stats, .. } => stats,
+                InstallProgress::VerifyStarted { files, bytes, .. } => {
+                    verifying = (files, bytes);
+                    continue;
+                }
+                InstallProgress::FileVerified {
+                    index,
+                    filename,
+                    status,
+                    bytes_done,
+                    ..
+                } => {
+                    log::debug!("{}: {}", filename, status.as_str());
+                    if logged.is_some_and(|at| at.elapsed() < PROGRESS_LOG_INTERVAL) {
+                        continue;
+                    }
+                    logged = Some(Instant::now());
+
+                    let (files, bytes) = verifying;
+                    let percent = if bytes > 0 {
+                        (bytes_done as f64 / bytes as f64 * 100.0).round() as u32
+                    } else {
+                        100
+                    };
+                    log::info!(
+                        "{}",
+                        tr!(
+                            "verify-progress",
+                            percent = percent,
+                            checked = index,
+                            files = files,
+                            done = format_size(bytes_done),
+                            total = format_size(bytes)
+                        )
+                    );
+                    continue;
+                }
+                _ => continue,
+            };
+            if logged.is_some_and(|at| at.elapsed() < PROGRESS_LOG_INTERVAL) {
+                continue;
+            }
+            logged = Some(Instant::now());
+            log::info!("{}", transfer_line(&stats));
+        }
+    })
+}
+
+/// The actions asked for with `--shutdown-after`, `--suspend-after` and `--exec-after`
+fn after_downloads(shutdown: bool, suspend: bool, exec: Option<String>) -> AfterDownloads {
+    let power = match (shutdown, suspend) {
+        (true, _) => Some(PowerAction::Shutdown),
+        (_, true) => Some(PowerAction::Suspend),
+        _ => None,
+    };
+    AfterDownloads { exec, power }
+}
+
+/// Take the after-download actions now that the downloads are over, then exit with an error
+/// if `failed` of them did.
+///
+/// Every install or update that got as far as downloading ends here, whether it succeeded,
+/// failed, or `update --all` failed as a whole. Dry runs, `--check-only` and commands refused
+/// before downloading (bad arguments, not logged in) exit without the actions.
+async fn finish_downloads(after: &AfterDownloads, failed: usize) {
+    if let Err(e) = after.run_command(failed).await {
+        log::error!("{}", e);
+    }
+
+    if let Some(action) = after.power {
+        let action_name = match action {
+            PowerAction::Shutdown => "shutdown",
+            PowerAction::Suspend => "suspend",
+        };
+        log::info!(
+            "{}",
+            tr!(
+                "after-power-countdown",
+                action = action_name,
+                seconds = power::POWER_DELAY.as_secs()
+            )
+        );
+        tokio::time::sleep(power::POWER_DELAY).await;
+        if let Err(e) = power::power_off(action).await {
+            log::error!("{}", e);
+        }
+    }
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// How far a download got, as the progress lines of installs and updates show it
+fn transfer_line(stats: &TransferStats) -> String {
+    let percent = (stats.fraction() * 100.0).round() as u32;
+    let done = format_size(stats.bytes_done);
+    let total = format_size(stats.bytes_total);
+    let speed = format_size(stats.speed as u64);
+    match stats.eta {
+        Some(eta) => tr!(
+            "install-progress-eta",
+            percent = percent,
+            done = done,
+            total = total,
+            speed = speed,
+            eta = format_duration(eta)
+        ),
+        None => tr!(
+            "install-progress",
+            percent = percent,
+            done = done,
+            total = total,
+            speed = speed
+        ),
+    }
+}
+
+/// One `--progress-json` event, flushed right away so wrappers see it as it happens
+fn print_json_line(event: &InstallProgress) {
+    use std::io::Write;
+
+    match serde_json::to_string(event) {
+        Ok(line) => {
+            let mut stdout = std::io::stdout().lock();
+            if let Err(e) = writeln!(stdout, "{}", line).and_then(|()| stdout.flush()) {
+                log::debug!("Could not print progress event: {}", e);
+            }
+        }
+        Err(e) => log::debug!("Could not serialize progress event: {}", e),
+    }
+}
+
+/// A `doctor` finding, and how to fix it when it's a problem
+fn describe_check(check: &Check) -> (String, Option<String>) {
+    match check {
+        Check::Reachable { service } => (tr!("doctor-reachable", service = service.as_str()), None),
+        Check::Unreachable { service, error } => (
+            tr!(
+                "doctor-unreachable",
+                service = service.as_str(),
+                error = error.as_str()
+            ),
+            Some(tr!("doctor-fix-unreachable")),
+        ),
+        Check::SessionValid {
+            account,
+            expires_at,
+        } => (
+            tr!(
+                "doctor-session-valid",
+                account = account.as_str(),
+                expires = expires_at.format("%Y-%m-%d %H:%M UTC").to_string()
+            ),
+            None,
+        ),
+        Check::SessionExpired { expired_at } => (
+            tr!(
+                "doctor-session-expired",
+                expired = expired_at.format("%Y-%m-%d %H:%M UTC").to_string()
+            ),
+            None,
+        ),
+        Check::NotLoggedIn => (tr!("doctor-not-logged-in"), Some(tr!("doctor-fix-login"))),
+        Check::SessionRejected { error } => (
+            tr!("doctor-session-rejected", error = error.as_str()),
+            Some(tr!("doctor-fix-login")),
+        ),
+        Check::ConfigValid { path } => (
+            tr!("doctor-config-valid", path = path.display().to_string()),
+            None,
+        ),
+        Check::ConfigInvalid { path, error } => (
+            tr!(
+                "doctor-config-invalid",
+                path = path.display().to_string(),
+                error = error.as_str()
+            ),
+            Some(tr!("doctor-fix-config")),
+        ),
+        Check::LibraryWritable { path } => (
+            tr!("doctor-library-writable", path = path.display().to_string()),
+            None,
+        ),
+        Check::LibraryNotWritable { path, error } => (
+            tr!(
+                "doctor-library-not-writable",
+                path = path.display().to_string(),
+                error = error.as_str()
+            ),
+            Some(tr!("doctor-fix-library")),
+        ),
+        Check::DriveNotMounted { path } => (
+            tr!(
+                "doctor-drive-not-mounted",
+                path = path.display().to_string()
+            ),
+            Some(tr!("doctor-fix-drive")),
+        ),
+        Check::RunnerFound { runner } => {
+            (tr!("doctor-runner-found", runner = runner.as_str()), None)
+        }
+        Check::NoRunner => (tr!("doctor-no-runner"), Some(tr!("doctor-fix-runner"))),
+        Check::RunnerMissing { app_name, runner } => (
+            tr!(
+                "doctor-runner-missing",
+                app = app_name.as_str(),
+                runner = runner.as_str()
+            ),
+            Some(tr!("doctor-fix-runner-missing", app = app_name.as_str())),
+        ),
+        Check::FreeSpace { path, free } => (
+            tr!(
+                "doctor-free-space",
+                size = format_size(*free),
+                path = path.display().to_string()
+            ),
+            None,
+        ),
+        Check::LowSpace { path, free } => (
+            tr!(
+                "doctor-low-space",
+                size = format_size(*free),
+                path = path.display().to_string()
+            ),
+            Some(tr!("doctor-fix-space")),
+        ),
+    }
+}
+
+/// Print what an install or update would do, for `--dry-run`
+fn log_plan(plan: &InstallPlan) {
+    heading(tr!(
+        "plan-title",
+        app = plan.app_name.as_str(),
+        provider = plan.provider.as_str()
+    ));
+    let version = match &plan.current_version {
+        Some(current) => tr!(
+            "plan-version-update",
+            current = current.as_str(),
+            version = plan.version.as_str()
+        ),
+        None => tr!("plan-version", version = plan.version.as_str()),
+    };
+    log::info!("{}", version);
+    log::info!(
+        "{}",
+        tr!("plan-path", path = plan.install_path.display().to_string())
+    );
+    log::info!(
+        "{}",
+        tr!(
+            "plan-download",
+            changed = plan.changed_files.len(),
+            files = plan.files,
+            size = format_size(plan.download_bytes),
+            unpacked = format_size(plan.write_bytes)
+        )
+    );
+    for file in &plan.changed_files {
+        log::debug!("  {}", file);
+    }
+    log::info!(
+        "{}",
+        tr!(
+            "plan-disk-usage",
+            current = format_size(plan.current_disk_usage),
+            size = format_size(plan.disk_usage)
+        )
+    );
+    if let Some(free) = plan.free_space {
+        log::info!("{}", tr!("plan-free-space", size = format_size(free)));
+    }
+    if !plan.fits() {
+        log::warn!(
+            "{}",
+            tr!("plan-no-space", size = format_size(plan.space_needed()))
+        );
+    }
+    log::info!("{}", tr!("plan-nothing-written"));
+}
+
+fn print_stats(stats: &LibraryStats) {
+    let seconds = |seconds: u64| format_duration(Duration::from_secs(seconds));
+
+    heading(tr!("stats-title"));
+    let owned = match stats.owned_games {
+        Some(count) => count.to_string(),
+        None => tr!("stats-unknown"),
+    };
+    log::info!("{}", tr!("stats-owned", count = owned));
+    log::info!(
+        "{}",
+        tr!(
+            "stats-installed",
+            count = stats.installed_games,
+            size = format_size(stats.installed_bytes)
+        )
+    );
+    let playtime = seconds(stats.total_playtime_seconds);
+    log::info!("{}", tr!("stats-playtime", time = playtime));
+
+    if !stats.most_played.is_empty() {
+        log::info!("");
+        log::info!("{}", tr!("stats-most-played"));
+        for (rank, game) in stats.most_played.iter().enumerate() {
+            log::info!(
+                "  {}",
+                tr!(
+                    "stats-most-played-entry",
+                    rank = rank + 1,
+                    title = game.title.as_str(),
+                    time = seconds(game.seconds),
+                    sessions = game.sessions
+                )
+            );
+        }
+    }
+
+    if !stats.downloads.is_empty() {
+        log::info!("");
+        log::info!("{}", tr!("stats-downloads"));
+        for month in &stats.downloads {
+            log::info!(
+                "  {}",
+                tr!(
+                    "stats-downloads-entry",
+                    month = month.month.as_str(),
+                    size = format_size(month.bytes),
+                    total = format_size(month.cumulative_bytes)
+                )
+            );
+        }
+    }
+}
+
+fn print_history_entry(entry: &HistoryEntry) {
+    let action = match entry.action {
+        HistoryAction::Install => tr!("history-install"),
+        HistoryAction::Update => tr!("history-update"),
+        HistoryAction::Repair => tr!("history-repair"),
+        HistoryAction::Uninstall => tr!("history-uninstall"),
+    };
+    let version = match (&entry.from_version, &entry.version) {
+        (Some(from), Some(to)) if from != to => format!("{} → {}", from, to),
+        (_, Some(version)) | (Some(version), None) => version.clone(),
+        (None, None) => String::new(),
+    };
+    let duration = entry.duration().to_std().unwrap_or_default();
+
+    log::info!(
+        "  {}",
+        tr!(
+            "history-entry",
+            date = entry
+                .started_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            action = action,
+            game = entry.id(),
+            version = version,
+            duration = format_duration(duration),
+            size = format_size(entry.bytes),
+            downloaded = yes_no(entry.bytes > 0)
+        )
+    );
+    if let Some(error) = &entry.error {
+        log::info!("    {}", tr!("history-failed", error = error.as_str()));
+    }
+}
+
+fn heading(title: String) {
+    log::info!("{}", title);
+    log::info!("{}", "=".repeat(title.chars().count()));
+}
+
+/// How `--permanent` says files are gotten rid of
+fn removal(permanent: bool) -> Removal {
+    if permanent {
+        Removal::Permanent
+    } else {
+        Removal::Trash
+    }
+}
+
+/// Selector value for the `yes-no` and dry-run variants of messages
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
@@ -0,0 +1,84 @@
+//! Questions from the core asked on the terminal. Questions go to stderr, so stdout stays
+//! clean for `--progress-json` and piped output.
+
+use std::io::{IsTerminal, Write};
+
+use rauncher_core::prompt::{Prompter, Question, QuestionKind};
+use rauncher_core::tr;
+
+/// Reads answers from stdin; no input, or input that isn't an answer, picks the default
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+    fn ask(&self, question: &Question) -> &'static str {
+        if question.kind == QuestionKind::Confirm {
+            let answer = read_answer(&format!("{} {}", question.text, tr!("prompt-yes-no")));
+            let yes = !answer.is_empty()
+                && (answer.starts_with('y') || answer.starts_with(&tr!("prompt-yes")));
+            return if yes { "yes" } else { "no" };
+        }
+
+        let choices = question.choices();
+        eprintln!("{}", question.text);
+        for (number, (_, label)) in choices.iter().enumerate() {
+            eprintln!("  {}) {}", number + 1, label);
+        }
+
+        let answer = read_answer(&tr!("prompt-choice", default = 1));
+        answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| choices.get(number.checked_sub(1)?))
+            .map(|(id, _)| *id)
+            .unwrap_or_else(|| question.default_answer())
+    }
+}
+
+/// Ask a [y/N] question on the terminal; anything but a yes, including no input, is a no
+pub fn confirm(question: String) -> bool {
+    TerminalPrompter.ask(&Question::new(QuestionKind::Confirm, question)) == "yes"
+}
+
+/// Ask which of `options` to take; no input, or input that isn't one of their numbers, takes
+/// none of them
+pub fn choose(question: String, options: &[String]) -> Option<usize> {
+    eprintln!("{}", question);
+    for (number, option) in options.iter().enumerate() {
+        eprintln!("  {}) {}", number + 1, option);
+    }
+
+    read_answer(&tr!("prompt-choice-optional"))
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .filter(|index| *index < options.len())
+}
+
+/// Pick one of `items`, narrowed down by typing any part of it. Escape picks none.
+pub fn pick(prompt: &str, items: &[String]) -> Option<usize> {
+    dialoguer::FuzzySelect::new()
+        .with_prompt(prompt)
+        .items(items)
+        .default(0)
+        .interact_opt()
+        .ok()
+        .flatten()
+}
+
+/// Whether someone is at the terminal to answer questions
+pub fn interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// Print `prompt` and read a trimmed, lowercased line; empty when stdin is closed
+fn read_answer(prompt: &str) -> String {
+    eprint!("{} ", prompt);
+    let _ = std::io::stderr().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return String::new();
+    }
+
+    answer.trim().to_lowercase()
+}
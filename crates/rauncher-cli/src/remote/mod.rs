@@ -0,0 +1,527 @@
+//! Client of the API `rauncher serve` answers, for commands run on another machine with
+//! `--host`: listing games, installs and updates followed to the end, launches and the
+//! download queue.
+
+use reqwest::{Method, RequestBuilder, Response, Url};
+use serde_json::Value;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use rauncher_core::api::Game;
+use rauncher_core::downloads::TransferStats;
+use rauncher_core::games::InstalledGame;
+use rauncher_core::{providers, tr, Error, Result};
+
+use crate::cli::{Commands, QueueCommands};
+use crate::{heading, transfer_line, PROGRESS_LOG_INTERVAL};
+
+/// Port `rauncher serve` listens on by default, used when `--host` names none
+const DEFAULT_PORT: u16 = 9178;
+
+pub struct Remote {
+    /// Scheme, host, port and any proxy path, e.g. `http://gamingpc:9178/`
+    base: Url,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl Remote {
+    /// `host` is `host[:port]`, or a URL when the API is behind an HTTPS proxy
+    pub fn new(host: &str, token: String) -> Result<Self> {
+        let base = if host.contains("://") {
+            host.to_string()
+        } else if let Ok(IpAddr::V6(ip)) = host.parse() {
+            format!("http://[{}]:{}", ip, DEFAULT_PORT)
+        } else if host.contains(':') {
+            format!("http://{}", host)
+        } else {
+            format!("http://{}:{}", host, DEFAULT_PORT)
+        };
+        let invalid =
+            |error: String| Error::Other(tr!("remote-invalid-host", host = host, error = error));
+        let base = Url::parse(&base).map_err(|e| invalid(e.to_string()))?;
+        if base.cannot_be_a_base() || base.host_str().is_none() {
+            return Err(invalid(tr!("remote-no-host")));
+        }
+
+        Ok(Self {
+            base,
+            token,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// URL of the API endpoint at `segments`, each percent-encoded as one path segment
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base.clone();
+        url.path_segments_mut()
+            .expect("checked to be a base in new")
+            .pop_if_empty()
+            .push("api")
+            .extend(segments);
+        url
+    }
+
+    fn request(&self, method: Method, segments: &[&str]) -> RequestBuilder {
+        self.client
+            .request(method, self.url(segments))
+            .bearer_auth(&self.token)
+    }
+
+    /// Send a request, turning error answers into errors carrying the server's message
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let message = response
+            .json::<Value>()
+            .await
+            .ok()
+            .and_then(|body| body["error"].as_str().map(str::to_string))
+            .unwrap_or_else(|| status.to_string());
+        Err(Error::Other(message))
+    }
+
+    pub async fn installed(&self) -> Result<Vec<InstalledGame>> {
+        let response = self.send(self.request(Method::GET, &["installed"])).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn library(&self) -> Result<Vec<Game>> {
+        let response = self.send(self.request(Method::GET, &["library"])).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn launch(&self, app_name: &str) -> Result<()> {
+        let request = self.request(Method::POST, &["games", app_name, "launch"]);
+        self.send(request).await?;
+        Ok(())
+    }
+
+    pub async fn queue(&self) -> Result<Vec<Value>> {
+        let response = self.send(self.request(Method::GET, &["queue"])).await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn prioritize(&self, app_name: &str) -> Result<()> {
+        let request = self.request(Method::POST, &["queue", app_name, "prioritize"]);
+        self.send(request).await?;
+        Ok(())
+    }
+
+    /// Start an install or update (`action`) of `app_name` and follow it until it ends,
+    /// logging its progress, or printing it as JSON lines with `json`
+    pub async fn follow(&self, action: &str, app_name: &str, json: bool) -> Result<()> {
+        // Subscribed before starting, so no event is missed
+        let mut events = self.send(self.request(Method::GET, &["events"])).await?;
+        let request = self.request(Method::POST, &["games", app_name, action]);
+        self.send(request).await?;
+
+        // Progress events name the game without its store prefix
+        let bare_name = providers::parse_address(app_name).1;
+        let mut buffer = Vec::new();
+        let mut logged: Option<Instant> = None;
+        while let Some(chunk) = events.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            for data in take_events(&mut buffer) {
+                let event: Value = serde_json::from_str(&data)?;
+                let name = event["app_name"].as_str();
+                if name != Some(app_name) && name != Some(bare_name) {
+                    continue;
+                }
+
+                match event["event"].as_str() {
+                    Some("succeeded") if event["action"] == action => return Ok(()),
+                    Some("failed") if event["action"] == action => {
+                        let error = event["error"].as_str().unwrap_or_default();
+                        return Err(Error::Other(error.to_string()));
+                    }
+                    _ if json => println!("{}", data),
+                    Some("transfer") => {
+                        if logged.is_some_and(|at| at.elapsed() < PROGRESS_LOG_INTERVAL) {
+                            continue;
+                        }
+                        logged = Some(Instant::now());
+                        log::info!("{}", transfer_line(&transfer_stats(&event)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Err(Error::Other(tr!("remote-disconnected")))
+    }
+}
+
+/// Remove the complete server-sent events from the start of `buffer`, returning the data of
+/// each. Comments and keep-alives carry none; an event split over several `data:` lines has
+/// them joined with newlines. What's left is the start of an event still arriving.
+fn take_events(buffer: &mut Vec<u8>) -> Vec<String> {
+    let mut events = Vec::new();
+    while let Some(end) = buffer.windows(2).position(|pair| pair == b"\n\n") {
+        let block: Vec<u8> = buffer.drain(..end + 2).collect();
+        let block = String::from_utf8_lossy(&block);
+        let data: Vec<&str> = block
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|data| data.strip_prefix(' ').unwrap_or(data))
+            .collect();
+        if !data.is_empty() {
+            events.push(data.join("\n"));
+        }
+    }
+    events
+}
+
+/// The download statistics of a `transfer` event
+fn transfer_stats(event: &Value) -> TransferStats {
+    TransferStats {
+        bytes_done: event["bytes_done"].as_u64().unwrap_or_default(),
+        bytes_total: event["bytes_total"].as_u64().unwrap_or_default(),
+        speed: event["speed"].as_f64().unwrap_or_default(),
+        average_speed: event["average_speed"].as_f64().unwrap_or_default(),
+        eta: event["eta"].as_u64().map(Duration::from_secs),
+    }
+}
+
+/// Run `command` on the machine at `host` instead of this one. Only commands the API has an
+/// equivalent for are accepted.
+pub async fn run(host: &str, token: String, command: Commands, progress_json: bool) -> Result<()> {
+    let remote = Remote::new(host, token)?;
+
+    match command {
+        Commands::List {
+            installed,
+            provider,
+            offline: false,
+            category: None,
+            favorites: false,
+            hidden: false,
+            filter: None,
+            platform: None,
+            updates_available: false,
+            not_installed: false,
+            columns,
+            format: None,
+        } if columns.is_empty() => {
+            let matches =
+                |game_provider: &str| provider.as_deref().is_none_or(|p| p == game_provider);
+            if installed {
+                let games: Vec<_> = match remote.installed().await {
+                    Ok(games) => games
+                        .into_iter()
+                        .filter(|game| matches(&game.provider))
+                        .collect(),
+                    Err(e) => {
+                        log::error!("{}", tr!("library-fetch-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                if games.is_empty() {
+                    log::info!("{}", tr!("list-no-installed"));
+                    return Ok(());
+                }
+                heading(tr!("list-installed-title"));
+                for game in games {
+                    log::info!(
+                        "  {} - {} (v{})",
+                        game.id(),
+                        game.app_title,
+                        game.app_version
+                    );
+                    log::info!(
+                        "    {}",
+                        tr!("list-path", path = game.install_path.display().to_string())
+                    );
+                }
+            } else {
+                let games: Vec<_> = match remote.library().await {
+                    Ok(games) => games
+                        .into_iter()
+                        .filter(|game| matches(&game.provider))
+                        .collect(),
+                    Err(e) => {
+                        log::error!("{}", tr!("library-fetch-failed", error = e.to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                if games.is_empty() {
+                    log::info!("{}", tr!("library-empty"));
+                    return Ok(());
+                }
+                heading(tr!("library-title"));
+                for game in games {
+                    log::info!(
+                        "  {} - {} (v{})",
+                        game.id(),
+                        game.app_title,
+                        game.app_version
+                    );
+                }
+            }
+        }
+
+        Commands::Install {
+            app_name: Some(app_name),
+            version: None,
+            provider,
+            dry_run: false,
+            shutdown_after: false,
+            suspend_after: false,
+            exec_after: None,
+        } => {
+            let address = match (providers::parse_address(&app_name), provider) {
+                ((None, name), Some(provider)) => providers::qualified_id(&provider, name),
+                _ => app_name,
+            };
+            log::info!("{}", tr!("install-start", app = address.as_str()));
+            match remote.follow("install", &address, progress_json).await {
+                Ok(()) => log::info!("{}", tr!("install-done")),
+                Err(e) => {
+                    log::error!("{}", tr!("install-failed", error = e.to_string()));
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Update {
+            app_name: Some(app_name),
+            all: false,
+            check_only: false,
+            dry_run: false,
+            shutdown_after: false,
+            suspend_after: false,
+            exec_after: None,
+        } => match remote.follow("update", &app_name, progress_json).await {
+            Ok(()) => log::info!("{}", tr!("update-done")),
+            Err(e) => {
+                log::error!("{}", tr!("update-failed", error = e.to_string()));
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Launch {
+            app_name: Some(app_name),
+            exe: None,
+            cwd: None,
+            offline: false,
+            wait: false,
+            force: false,
+            args,
+        } if args.is_empty() => match remote.launch(&app_name).await {
+            Ok(()) => log::info!("{}", tr!("launch-done")),
+            Err(e) => {
+                log::error!("{}", tr!("launch-failed", error = e.to_string()));
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Queue {
+            command: QueueCommands::List,
+        } => {
+            let jobs = match remote.queue().await {
+                Ok(jobs) => jobs,
+                Err(e) => {
+                    log::error!("{}", tr!("queue-fetch-failed", error = e.to_string()));
+                    std::process::exit(1);
+                }
+            };
+            if jobs.is_empty() {
+                log::info!("{}", tr!("queue-empty"));
+                return Ok(());
+            }
+            heading(tr!("queue-title"));
+            for job in jobs {
+                // Failed jobs are `{"failed": error}`, the other states plain names
+                let state = match &job["state"] {
+                    Value::String(state) => state.as_str(),
+                    _ => "failed",
+                };
+                log::info!(
+                    "  {}",
+                    tr!(
+                        "queue-job",
+                        app = job["address"].as_str().unwrap_or_default(),
+                        state = state,
+                        priority = job["priority"].as_str().unwrap_or_default()
+                    )
+                );
+            }
+        }
+
+        Commands::Queue {
+            command: QueueCommands::Prioritize { app_name },
+        } => match remote.prioritize(&app_name).await {
+            Ok(()) => log::info!("{}", tr!("queue-prioritized", app = app_name.as_str())),
+            Err(e) => {
+                log::error!(
+                    "{}",
+                    tr!(
+                        "queue-prioritize-failed",
+                        app = app_name.as_str(),
+                        error = e.to_string()
+                    )
+                );
+                std::process::exit(1);
+            }
+        },
+
+        _ => {
+            log::error!("{}", tr!("remote-unsupported"));
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::{Path, State};
+    use axum::routing::{get, post};
+    use axum::Router;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    type Started = Arc<Mutex<Vec<(String, String)>>>;
+
+    /// Serve an API whose event stream sends `chunks` and then closes, returning its address
+    /// and the games and actions started on it
+    async fn serve_events(chunks: &'static [&'static str]) -> (SocketAddr, Started) {
+        let started = Started::default();
+        let app = Router::new()
+            .route(
+                "/api/events",
+                get(move || async move {
+                    let chunks = chunks.iter().map(|chunk| Ok::<_, std::io::Error>(*chunk));
+                    Body::from_stream(futures_util::stream::iter(chunks))
+                }),
+            )
+            .route(
+                "/api/games/:app/:action",
+                post(|State(started): State<Started>, Path(start): Path<(String, String)>| async move {
+                    started.lock().unwrap().push(start);
+                }),
+            )
+            .with_state(started.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (addr, started)
+    }
+
+    #[test]
+    fn test_host_parsing() {
+        let base = |host| Remote::new(host, String::new()).unwrap().base.to_string();
+
+        assert_eq!(base("gamingpc"), "http://gamingpc:9178/");
+        assert_eq!(base("gamingpc:8080"), "http://gamingpc:8080/");
+        assert_eq!(base("192.168.1.20"), "http://192.168.1.20:9178/");
+        assert_eq!(base("::1"), "http://[::1]:9178/");
+        assert_eq!(base("[::1]:8080"), "http://[::1]:8080/");
+        assert_eq!(
+            base("https://games.example.com"),
+            "https://games.example.com/"
+        );
+        assert_eq!(
+            base("https://example.com/rauncher/"),
+            "https://example.com/rauncher/"
+        );
+
+        assert!(Remote::new("gamingpc:port", String::new()).is_err());
+        assert!(Remote::new("gaming pc", String::new()).is_err());
+        assert!(Remote::new("http://", String::new()).is_err());
+    }
+
+    #[test]
+    fn test_urls_encode_app_names() {
+        let remote = Remote::new("gamingpc", String::new()).unwrap();
+        assert_eq!(
+            remote.url(&["games", "gog:1207658924", "install"]).as_str(),
+            "http://gamingpc:9178/api/games/gog:1207658924/install"
+        );
+        assert_eq!(
+            remote
+                .url(&["games", "Kiwi Quest/../x?y#z", "launch"])
+                .as_str(),
+            "http://gamingpc:9178/api/games/Kiwi%20Quest%2F..%2Fx%3Fy%23z/launch"
+        );
+
+        // Behind a proxy, with or without the trailing slash
+        for host in [
+            "https://example.com/rauncher",
+            "https://example.com/rauncher/",
+        ] {
+            let remote = Remote::new(host, String::new()).unwrap();
+            assert_eq!(
+                remote.url(&["queue"]).as_str(),
+                "https://example.com/rauncher/api/queue"
+            );
+        }
+    }
+
+    #[test]
+    fn test_take_events() {
+        let mut buffer =
+            b": keep-alive\n\ndata: {\"a\":1}\n\ndata:one\ndata: two\n\ndata: {\"b\"".to_vec();
+        assert_eq!(take_events(&mut buffer), ["{\"a\":1}", "one\ntwo"]);
+        assert_eq!(buffer, b"data: {\"b\"");
+
+        buffer.extend_from_slice(b":2}\n\n");
+        assert_eq!(take_events(&mut buffer), ["{\"b\":2}"]);
+        assert!(buffer.is_empty());
+
+        // A character split between two chunks
+        let event = "data: \"Città\"\n\n".as_bytes();
+        let split = event.len() - 4;
+        let mut buffer = event[..split].to_vec();
+        assert!(take_events(&mut buffer).is_empty());
+        buffer.extend_from_slice(&event[split..]);
+        assert_eq!(take_events(&mut buffer), ["\"Città\""]);
+    }
+
+    #[tokio::test]
+    async fn test_follow_until_the_action_ends() {
+        // Events of other games and actions, split anywhere, come before the end of this one
+        let (addr, started) = serve_events(&[
+            ": keep-alive\n\n",
+            "data: {\"event\":\"succeeded\",\"app_name\":\"Other\",\"action\":\"install\"}\n\n",
+            "data: {\"event\":\"succeeded\",\"app_name\":\"Kiwi Quest\",\"action\":\"upd",
+            "ate\"}\n\ndata: {\"event\":\"transfer\",\"app_name\":\"Kiwi Quest\",\"bytes_done\":1}\n\n",
+            "data: {\"event\":\"succeeded\",\"app_name\":\"Kiwi Quest\",\"action\":\"install\"}\n\n",
+        ])
+        .await;
+        let remote = Remote::new(&addr.to_string(), String::new()).unwrap();
+
+        remote
+            .follow("install", "gog:Kiwi Quest", false)
+            .await
+            .unwrap();
+        assert_eq!(
+            *started.lock().unwrap(),
+            [("gog:Kiwi Quest".to_string(), "install".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_follow_reports_failures() {
+        let (addr, _) = serve_events(&[
+            "data: {\"event\":\"failed\",\"app_name\":\"Kiwi\",\"action\":\"update\",\"error\":\"Disk full\"}\n\n",
+        ])
+        .await;
+        let remote = Remote::new(&addr.to_string(), String::new()).unwrap();
+        let error = remote.follow("update", "Kiwi", false).await.unwrap_err();
+        assert_eq!(error.to_string(), "Disk full");
+
+        // A stream that ends first
+        let (addr, _) = serve_events(&[": keep-alive\n\n"]).await;
+        let remote = Remote::new(&addr.to_string(), String::new()).unwrap();
+        assert!(remote.follow("update", "Kiwi", false).await.is_err());
+    }
+}
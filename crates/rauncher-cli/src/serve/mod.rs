@@ -0,0 +1,626 @@
+//! HTTP API for managing the launcher from another device: list the library and the installed
+//! games, start installs, updates and launches, and follow their progress as server-sent
+//! events. Installs wait their turn in a download queue, which `/api/queue` shows and reorders.
+//! Every request must carry the token as `Authorization: Bearer <token>`.
+
+use axum::extract::{Path, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::Stream;
+use rand::RngCore;
+use serde_json::json;
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fs;
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path as FsPath;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use rauncher_core::downloads::{DownloadQueue, JobState};
+use rauncher_core::games::{GameManager, LaunchOptions};
+use rauncher_core::{auth::AuthManager, config::Config, providers, tr, Error, Result};
+
+/// Where the generated token is kept, in the data directory
+const TOKEN_FILE: &str = "serve_token";
+
+/// Where a running API records its address and token for `rauncher queue`, in the data
+/// directory
+const RECORD_FILE: &str = "serve.json";
+
+/// Events kept for subscribers that fall behind; older ones are dropped
+const EVENT_BUFFER: usize = 256;
+
+/// How often finished downloads are looked for, to report their outcome
+const QUEUE_POLL: Duration = Duration::from_millis(500);
+
+/// An install, update or launch started by a request
+#[derive(Debug, Clone, Copy)]
+enum Action {
+    Install,
+    Update,
+    Launch,
+}
+
+impl Action {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Install => "install",
+            Self::Update => "update",
+            Self::Launch => "launch",
+        }
+    }
+}
+
+struct ApiState {
+    manager: GameManager,
+    token: String,
+    events: broadcast::Sender<serde_json::Value>,
+    /// Installs, run one at a time
+    downloads: DownloadQueue,
+    /// Games with an update running, which can't start another
+    busy: Mutex<HashSet<String>>,
+}
+
+impl ApiState {
+    /// Send an event to the event stream, next to the install progress
+    fn publish(&self, event: serde_json::Value) {
+        // Nobody subscribed is fine; events are only informational
+        let _ = self.events.send(event);
+    }
+}
+
+type SharedState = Arc<ApiState>;
+
+/// Serve the API on `listen` until Ctrl-C or SIGTERM
+pub async fn run(
+    config: Config,
+    auth: AuthManager,
+    listen: SocketAddr,
+    token: Option<String>,
+) -> Result<()> {
+    // An empty token would let anyone in with a bare `Bearer ` header
+    let token = match token.as_deref().map(str::trim) {
+        Some(token) if !token.is_empty() => token.to_string(),
+        Some(_) => {
+            log::warn!("{}", tr!("serve-token-empty"));
+            stored_token()?
+        }
+        None => stored_token()?,
+    };
+
+    let downloads = DownloadQueue::new();
+    downloads.set_order(config.download_order);
+    let mut manager = GameManager::new(config, auth)?;
+    let (events, _) = broadcast::channel(EVENT_BUFFER);
+    let (progress, mut progress_events) = tokio::sync::mpsc::unbounded_channel();
+    manager.set_progress(progress);
+    let forward = events.clone();
+    tokio::spawn(async move {
+        while let Some(event) = progress_events.recv().await {
+            if let Ok(event) = serde_json::to_value(event) {
+                let _ = forward.send(event);
+            }
+        }
+    });
+
+    let worker_manager = manager.clone();
+    downloads.spawn_worker(move || Ok(worker_manager.clone()));
+
+    let state = Arc::new(ApiState {
+        manager,
+        token,
+        events,
+        downloads,
+        busy: Mutex::default(),
+    });
+    tokio::spawn(report_finished(Arc::clone(&state)));
+
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    let listen = listener.local_addr()?;
+    log::info!(
+        "{}",
+        tr!("serve-listening", url = format!("http://{}/api", listen))
+    );
+    let record = Config::data_dir()?.join(RECORD_FILE);
+    if let Err(e) = write_record(&record, listen, &state.token) {
+        log::warn!("{}", tr!("serve-record-failed", error = e.to_string()));
+    }
+
+    let served = axum::serve(listener, router(state))
+        .with_graceful_shutdown(crate::daemon::shutdown_signal())
+        .await;
+    // Left alone if another API has replaced it since
+    if read_record(&record).is_some_and(|(address, _)| address == local_address(listen)) {
+        let _ = fs::remove_file(&record);
+    }
+    served?;
+    log::info!("{}", tr!("serve-stopped"));
+    Ok(())
+}
+
+/// The address and token of the API running on this machine, from the record it keeps while
+/// it runs
+pub fn running() -> Result<Option<(SocketAddr, String)>> {
+    Ok(read_record(&Config::data_dir()?.join(RECORD_FILE)))
+}
+
+fn read_record(path: &FsPath) -> Option<(SocketAddr, String)> {
+    let record: serde_json::Value = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+    let address = record["address"].as_str()?.parse().ok()?;
+    let token = record["token"].as_str()?.to_string();
+    Some((address, token))
+}
+
+/// Record where the API listening on `listen` is reached from this machine, with its token.
+/// Only the user can read it, like the token file.
+fn write_record(path: &FsPath, listen: SocketAddr, token: &str) -> Result<()> {
+    let record = json!({
+        "address": local_address(listen).to_string(),
+        "token": token,
+    });
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Replaced rather than rewritten, so its permissions are set from the start
+    let _ = fs::remove_file(path);
+    write_private(path, &serde_json::to_vec(&record)?)
+}
+
+/// Where this machine reaches an API listening on `listen`: loopback when it listens on every
+/// address
+fn local_address(listen: SocketAddr) -> SocketAddr {
+    match listen.ip() {
+        IpAddr::V4(ip) if ip.is_unspecified() => (Ipv4Addr::LOCALHOST, listen.port()).into(),
+        IpAddr::V6(ip) if ip.is_unspecified() => (Ipv6Addr::LOCALHOST, listen.port()).into(),
+        _ => listen,
+    }
+}
+
+/// Create `path` with `contents`, readable only by the user from the start rather than
+/// chmodded once written
+fn write_private(path: &FsPath, contents: &[u8]) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents)?;
+    Ok(())
+}
+
+/// Every route of the API, behind the token check
+fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/api/library", get(library_handler))
+        .route("/api/installed", get(installed_handler))
+        .route("/api/games/:app_name/install", post(install_handler))
+        .route("/api/games/:app_name/update", post(update_handler))
+        .route("/api/games/:app_name/launch", post(launch_handler))
+        .route("/api/queue", get(queue_handler))
+        .route("/api/queue/:app_name/prioritize", post(prioritize_handler))
+        .route("/api/events", get(events_handler))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            require_token,
+        ))
+        .with_state(state)
+}
+
+/// The token kept in the data directory, generated the first time
+fn stored_token() -> Result<String> {
+    token_in(&Config::data_dir()?.join(TOKEN_FILE))
+}
+
+fn token_in(path: &FsPath) -> Result<String> {
+    if let Ok(token) = fs::read_to_string(path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            return Ok(token.to_string());
+        }
+        // An empty file left by an interrupted run, replaced below
+        fs::remove_file(path)?;
+    }
+
+    let mut bytes = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    write_private(path, token.as_bytes())?;
+
+    log::info!(
+        "{}",
+        tr!("serve-token-created", path = path.display().to_string())
+    );
+    Ok(token)
+}
+
+/// Compare in constant time, so response times don't give the token away. An empty token
+/// never matches.
+fn token_matches(given: &str, token: &str) -> bool {
+    !token.is_empty()
+        && given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn require_token(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| token_matches(given, &state.token));
+
+    if !authorized {
+        return error_response(StatusCode::UNAUTHORIZED, "Missing or wrong token");
+    }
+    next.run(request).await
+}
+
+fn error_response(status: StatusCode, error: &str) -> Response {
+    (status, Json(json!({ "error": error }))).into_response()
+}
+
+/// A core error, answered with the status closest to its cause
+struct ApiError(Error);
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self.0 {
+            Error::GameNotFound(_) => StatusCode::NOT_FOUND,
+            Error::AlreadyRunning(_) => StatusCode::CONFLICT,
+            Error::NotAuthenticated | Error::ReauthRequired(_) => StatusCode::FORBIDDEN,
+            Error::Offline | Error::Api(_) | Error::Http(_) => StatusCode::BAD_GATEWAY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        error_response(status, &self.0.to_string())
+    }
+}
+
+type ApiResult<T> = std::result::Result<T, ApiError>;
+
+async fn library_handler(State(state): State<SharedState>) -> ApiResult<Response> {
+    let games = state.manager.list_merged_library().await?;
+    Ok(Json(games).into_response())
+}
+
+async fn installed_handler(State(state): State<SharedState>) -> ApiResult<Response> {
+    let games = state.manager.list_installed()?;
+    Ok(Json(games).into_response())
+}
+
+async fn install_handler(
+    State(state): State<SharedState>,
+    Path(app_name): Path<String>,
+) -> Response {
+    start(state, Action::Install, app_name)
+}
+
+async fn update_handler(
+    State(state): State<SharedState>,
+    Path(app_name): Path<String>,
+) -> Response {
+    start(state, Action::Update, app_name)
+}
+
+fn accepted(action: Action, app_name: &str) -> Response {
+    (
+        StatusCode::ACCEPTED,
+        Json(json!({ "action": action.name(), "app_name": app_name })),
+    )
+        .into_response()
+}
+
+/// Start an install or update in the background; its progress and outcome go to the event
+/// stream
+fn start(state: SharedState, action: Action, app_name: String) -> Response {
+    if let Action::Install = action {
+        if state.downloads.contains(&app_name) {
+            return error_response(
+                StatusCode::CONFLICT,
+                &format!("{} is already queued", app_name),
+            );
+        }
+        state.downloads.enqueue(&app_name);
+        return accepted(action, &app_name);
+    }
+
+    if !state.busy.lock().unwrap().insert(app_name.clone()) {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("{} is already being installed or updated", app_name),
+        );
+    }
+
+    let task_state = Arc::clone(&state);
+    let task_app = app_name.clone();
+    // Installs hold futures that aren't `Send`, so each one gets a thread of its own
+    let runtime = tokio::runtime::Handle::current();
+    tokio::task::spawn_blocking(move || {
+        let state = task_state;
+        let result = runtime.block_on(state.manager.update_game(&task_app));
+        state.busy.lock().unwrap().remove(&task_app);
+        state.publish(match result {
+            Ok(()) => json!({
+                "event": "succeeded",
+                "action": action.name(),
+                "app_name": task_app,
+            }),
+            Err(e) => json!({
+                "event": "failed",
+                "action": action.name(),
+                "app_name": task_app,
+                "error": e.to_string(),
+            }),
+        });
+    });
+
+    accepted(action, &app_name)
+}
+
+/// Send the outcome of every install the queue finishes to the event stream, then drop it
+/// from the queue
+async fn report_finished(state: SharedState) {
+    let mut interval = tokio::time::interval(QUEUE_POLL);
+    loop {
+        interval.tick().await;
+        for job in state.downloads.take_finished() {
+            let mut event = json!({
+                "event": "succeeded",
+                "action": Action::Install.name(),
+                "app_name": job.address,
+            });
+            let error = match job.state {
+                JobState::Completed => None,
+                JobState::Failed(error) => Some(error),
+                _ => Some("Download cancelled".to_string()),
+            };
+            if let Some(error) = error {
+                event["event"] = json!("failed");
+                event["error"] = json!(error);
+            }
+            state.publish(event);
+        }
+        state.downloads.clear_finished();
+    }
+}
+
+/// The running and waiting installs, in queue order
+async fn queue_handler(State(state): State<SharedState>) -> Response {
+    Json(state.downloads.jobs()).into_response()
+}
+
+/// Boost a queued or running install; `app_name` may leave out the store prefix
+async fn prioritize_handler(
+    State(state): State<SharedState>,
+    Path(app_name): Path<String>,
+) -> Response {
+    let id = state.downloads.find(&app_name).or_else(|| {
+        state.downloads.jobs().into_iter().find_map(|job| {
+            let queued =
+                !job.state.is_finished() && providers::parse_address(&job.address).1 == app_name;
+            queued.then_some(job.id)
+        })
+    });
+    let Some(id) = id else {
+        return error_response(StatusCode::NOT_FOUND, &format!("{} isn't queued", app_name));
+    };
+
+    state.downloads.prioritize(id);
+    (
+        StatusCode::OK,
+        Json(json!({ "action": "prioritize", "app_name": app_name })),
+    )
+        .into_response()
+}
+
+/// Launch a game and keep waiting for it in the background, so its play time is recorded and
+/// its exit goes to the event stream
+async fn launch_handler(
+    State(state): State<SharedState>,
+    Path(app_name): Path<String>,
+) -> ApiResult<Response> {
+    let running = state
+        .manager
+        .launch_game_with_options(&app_name, &LaunchOptions::default())?;
+    let game = running.game.clone();
+    state.publish(json!({
+        "event": "succeeded",
+        "action": Action::Launch.name(),
+        "app_name": game.app_name,
+    }));
+
+    tokio::spawn(async move {
+        let exited = tokio::task::spawn_blocking(move || running.wait_for_exit()).await;
+        let (code, signal) = match exited {
+            Ok(Ok(exit)) => {
+                state.manager.after_exit(&game, &exit).await;
+                (exit.code, exit.signal)
+            }
+            Ok(Err(e)) => {
+                log::warn!("Lost track of {}: {}", game.app_name, e);
+                (None, None)
+            }
+            Err(e) => {
+                log::warn!("Lost track of {}: {}", game.app_name, e);
+                (None, None)
+            }
+        };
+        state.publish(json!({
+            "event": "exited",
+            "app_name": game.app_name,
+            "code": code,
+            "signal": signal,
+        }));
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({ "action": Action::Launch.name(), "app_name": app_name })),
+    )
+        .into_response())
+}
+
+/// Install progress and the outcome of requests, as they happen
+async fn events_handler(
+    State(state): State<SharedState>,
+) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+    let receiver = state.events.subscribe();
+    let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    let name = event["event"].as_str().unwrap_or("message").to_string();
+                    let sse = Event::default().event(name).data(event.to_string());
+                    return Some((Ok(sse), receiver));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    log::debug!("Event subscriber fell behind by {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve the API with `token` on a free local port, returning its address and the install
+    /// directory to keep alive while it runs
+    async fn serve_api(token: &str) -> (SocketAddr, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            install_dir: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        let manager = GameManager::new(config, AuthManager::new().unwrap()).unwrap();
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        let state = Arc::new(ApiState {
+            manager,
+            token: token.to_string(),
+            events,
+            downloads: DownloadQueue::new(),
+            busy: Mutex::default(),
+        });
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(state)).await });
+        (addr, dir)
+    }
+
+    #[tokio::test]
+    async fn test_requests_need_the_token() {
+        let (addr, _dir) = serve_api("s3cret").await;
+        let url = format!("http://{}/api/queue", addr);
+        let client = reqwest::Client::new();
+
+        let missing = client.get(&url).send().await.unwrap();
+        assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+        let body: serde_json::Value = missing.json().await.unwrap();
+        assert_eq!(body["error"], "Missing or wrong token");
+
+        for wrong in ["Bearer s3cre", "Bearer s3cret!", "Bearer S3CRET", "s3cret"] {
+            let response = client
+                .get(&url)
+                .header("Authorization", wrong)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", wrong);
+        }
+
+        let response = client.get(&url).bearer_auth("s3cret").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let jobs: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(jobs, json!([]));
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("abc123", "abc123"));
+        assert!(!token_matches("abc124", "abc123"));
+        assert!(!token_matches("abc12", "abc123"));
+        assert!(!token_matches("", "abc123"));
+        assert!(!token_matches("", ""));
+    }
+
+    #[tokio::test]
+    async fn test_empty_token_lets_nobody_in() {
+        let (addr, _dir) = serve_api("").await;
+        let response = reqwest::Client::new()
+            .get(format!("http://{}/api/queue", addr))
+            .header("Authorization", "Bearer ")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_token_is_generated_once_and_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data").join(TOKEN_FILE);
+
+        let token = token_in(&path).unwrap();
+        assert_eq!(token.len(), 48);
+        assert_eq!(token_in(&path).unwrap(), token);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // An empty file is replaced with a new token
+        fs::write(&path, "").unwrap();
+        assert_ne!(token_in(&path).unwrap(), "");
+    }
+
+    #[test]
+    fn test_record_points_at_the_running_api() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data").join(RECORD_FILE);
+        assert_eq!(read_record(&path), None);
+
+        write_record(&path, "0.0.0.0:8080".parse().unwrap(), "s3cret").unwrap();
+        let local = "127.0.0.1:8080".parse().unwrap();
+        assert_eq!(read_record(&path), Some((local, "s3cret".to_string())));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // A newer API replaces it
+        write_record(&path, "[::]:9000".parse().unwrap(), "other").unwrap();
+        let local = "[::1]:9000".parse().unwrap();
+        assert_eq!(read_record(&path), Some((local, "other".to_string())));
+
+        let lan = "192.168.1.20:9178".parse().unwrap();
+        assert_eq!(local_address(lan), lan);
+    }
+}
@@ -0,0 +1,660 @@
+# Messages printed by the `rauncher` command line
+
+not-authenticated = Error: Not authenticated. Run 'rauncher auth' first.
+yes-no = { $value ->
+    [yes] Yes
+   *[no] No
+}
+# Answer accepted by [y/N] prompts, besides "y"
+prompt-yes = y
+# Appended to yes/no questions asked on the terminal
+prompt-yes-no = [y/N]
+# Asked after listing the numbered answers to a question
+prompt-choice = Choice [{ $default }]:
+# Asked after listing numbered options, none of which has to be taken
+prompt-choice-optional = Choice (Enter for none):
+
+## auth
+
+auth-title = { $store } Authentication
+auth-epic-title = Epic Games Store Authentication
+auth-profiles-epic-only = Profiles are only supported for the Epic Games Store
+auth-web-epic-only = --web is only for the Epic Games Store; other stores already log in through the browser
+auth-logged-out = Successfully logged out
+auth-logged-out-store = Successfully logged out of { $store }
+auth-success-store = ✓ Successfully authenticated with { $store }!
+auth-epic-success = ✓ Successfully authenticated with Epic Games Store!
+auth-failed = Authentication failed: { $error }
+auth-log-in-browser = Please log in using your web browser:
+auth-use-browser = Please authenticate using your web browser:
+auth-open-url = Open this URL: { $url }
+auth-enter-code = Enter this code: { $code }
+auth-copy-code = Then copy the code from the page you land on and run:
+auth-create-api-key = Create an API key at: { $url }
+auth-then-run = Then run:
+auth-starting = Starting authentication process...
+auth-waiting = Waiting for authentication...
+auth-next-steps = You can now:
+auth-hint-list = List your games: rauncher list
+auth-hint-list-store = List your games: rauncher list --provider { $provider }
+auth-hint-install = Install a game: rauncher install <app_name>
+auth-retry = Please try again. If the problem persists, check:
+auth-check-connection = Your internet connection
+auth-check-epic-status = Epic Games services status
+auth-try-web = If your account uses two-factor authentication or a captcha, try: rauncher auth --web
+auth-web-open = Log in through this page in your browser: { $url }
+auth-web-steps = It links to the Epic Games login, which handles two-factor and captcha checks; paste the text Epic shows afterwards back into the page
+auth-web-waiting = Waiting for the login to finish (Ctrl+C to cancel)...
+profiles-none = No stored profiles
+profiles-title = Profiles:
+profile-switched = ✓ Switched to profile { $name }
+profile-switch-failed = Failed to switch profile: { $error }
+auth-status-title = { $store } session
+auth-status-not-logged-in = Not logged in to { $store }
+auth-status-failed = Could not check the session: { $error }
+auth-status-display-name = Display name: { $value }
+auth-status-account-id = Account ID: { $value }
+auth-status-expires = Token expires: { $time } (in { $minutes ->
+    [one] 1 minute
+   *[other] { $minutes } minutes
+})
+auth-status-expired = Token expired: { $time }; it is refreshed on the next command that needs it
+auth-status-scopes = Scopes: { $value }
+auth-status-profile = Profile: { $value }
+
+## entitlements
+
+entitlements-failed = Failed to fetch entitlements: { $error }
+entitlements-none = No entitlements found
+entitlements-title = Entitlements ({ $count }):
+entitlements-entry = { $name } ({ $namespace }/{ $item }): { $kind }, granted { $granted }{ $active ->
+    [yes] {""}
+   *[no] , inactive
+}
+
+## list
+
+list-no-installed = No games installed
+list-installed-title = Installed Games:
+list-path = Path: { $path }
+list-unavailable = Unavailable (drive not mounted)
+library-title = Library:
+library-empty = No games in library (or authentication required)
+list-none-match = No games match the filters
+list-platform-failed = Failed to look up the platforms of Epic games: { $error }
+list-column = { $column ->
+    [id] ID
+    [title] Title
+    [version] Version
+    [store] Store
+    [path] Path
+   *[size] Size
+}
+library-fetch-failed = Failed to fetch library: { $error }
+library-offline = Offline: showing the library as of the last refresh
+
+## install, versions, pin, launch, uninstall, info
+
+install-provider-conflict = { $address } conflicts with --provider { $provider }
+install-version-epic-only = The --version option is only supported for Epic Games Store games
+install-start = Installing game: { $app }
+install-done = Game installed successfully!
+install-progress = { $percent }% ({ $done } of { $total }) at { $speed }/s
+install-progress-eta = { $percent }% ({ $done } of { $total }) at { $speed }/s, { $eta } left
+install-failed = Failed to install game: { $error }
+versions-title = Available builds for { $app }:
+versions-failed = Failed to fetch builds: { $error }
+pin-done = ✓ Pinned { $app }
+pin-failed = Failed to pin game: { $error }
+unpin-done = ✓ Unpinned { $app }
+unpin-failed = Failed to unpin game: { $error }
+launch-done = Game launched successfully!
+launch-stopping = Stopping { $app }...
+launch-killed = { $app } was killed by signal { $signal }
+launch-failed = Failed to launch game: { $error }
+launch-already-running = { $app } is already running; a second instance could corrupt its saves
+launch-already-running-hint = Run 'rauncher stop { $app }' first, or launch anyway with --force
+stop-done = ✓ Stopped { $app }
+stop-not-running = { $app } isn't running
+stop-failed = Failed to stop the game: { $error }
+uninstall-done = { $trash ->
+    [yes] Game uninstalled, its files are in the trash
+   *[no] Game uninstalled successfully!
+}
+uninstall-failed = Failed to uninstall game: { $error }
+uninstall-matching-none = No installed games match the filters
+uninstall-matching-title = Games to uninstall:
+uninstall-matching-entry = { $id } - { $title }: { $size }, last played { $played }
+uninstall-never-played = never
+uninstall-matching-total = { $count ->
+    [one] 1 game
+   *[other] { $count } games
+}, { $size } in total
+uninstall-confirm = Uninstall { $count ->
+    [one] this game
+   *[other] these { $count } games
+}?
+uninstall-game-title = Game to uninstall: { $title }
+uninstall-aborted = Nothing was uninstalled
+uninstall-game-done = ✓ Uninstalled { $id }
+uninstall-game-failed = Failed to uninstall { $id }: { $error }
+uninstall-bulk-done = ✓ Uninstalled { $count ->
+    [one] 1 game
+   *[other] { $count } games
+}, { $trash ->
+    [yes] moving { $size } to the trash
+   *[no] freeing { $size }
+}
+info-title = Game Information:
+info-name = Name: { $value }
+info-store = Store: { $value }
+info-game-title = Title: { $value }
+info-version = Version: { $value }
+info-install-path = Install Path: { $value }
+info-executable = Executable: { $value }
+info-install-size = Install size: { $value }
+info-download-size = Download size: { $value }
+info-save-path = Save path: { $value }
+info-save-path-unresolved = Save path: unknown ({ $error })
+game-not-found = Game not found: { $app }
+
+## verify
+
+verify-start = Verifying { $app }...
+verify-failed = Failed to verify game: { $error }
+verify-progress = { $percent }% ({ $checked } of { $files } files, { $done } of { $total })
+verify-entry = { $status ->
+    [mismatched] Damaged
+    [missing] Missing
+   *[extra] Extra
+}: { $path }
+verify-clean = ✓ All { $count } files match the manifest
+verify-summary = Checked { $count } files: { $mismatched } damaged, { $missing } missing, { $extra } extra
+verify-report-written = ✓ Report written to { $path }
+verify-delete-confirm = { $trash ->
+    [yes] Move { $count ->
+        [one] 1 extra file
+       *[other] { $count } extra files
+    } to the trash
+   *[no] Delete { $count ->
+        [one] 1 extra file
+       *[other] { $count } extra files
+    }
+}?
+verify-delete-aborted = Nothing was deleted
+verify-deleted = ✓ { $trash ->
+    [yes] Moved { $count ->
+        [one] 1 extra file
+       *[other] { $count } extra files
+    } ({ $size }) to the trash
+   *[no] Deleted { $count ->
+        [one] 1 extra file
+       *[other] { $count } extra files
+    }, freeing { $size }
+}
+verify-delete-failed = Failed to delete extra files: { $error }
+verify-hint = Reinstall the game to restore the damaged and missing files
+
+## mods
+
+mods-title = Protected files of { $app }
+mods-none = { $app } has no protected files
+mods-protected = ✓ Protected { $path }
+mods-already = { $path } is already protected
+mods-unprotected = ✓ { $path } is no longer protected
+mods-not-protected = { $path } wasn't protected
+mods-failed = Failed to change protected files: { $error }
+
+## did you mean, pickers
+
+did-you-mean = No game is called { $name }; did you mean { $games }?
+did-you-mean-choose = No game is called { $name }. Did you mean:
+pick-install = Game to install (type to search)
+pick-launch = Game to launch (type to search)
+pick-needs-terminal = Name the game; it can only be picked from a list on a terminal
+pick-nothing-to-install = Every game in the library is installed already
+
+## favorite, hide, category, alias
+
+favorite-done = ★ Added { $app } to the favorites
+unfavorite-done = Removed { $app } from the favorites
+hide-done = { $app } is hidden from listings (see it with 'rauncher list --hidden')
+unhide-done = { $app } is listed again
+category-title = Categories:
+category-none = No categories yet (add one with 'rauncher category add <app> <category>')
+category-empty = The category name can't be empty
+category-added = ✓ { $app } is in { $category }
+category-removed = ✓ Took { $app } out of { $category }
+category-not-in = { $app } isn't in { $category }
+alias-title = Aliases:
+alias-none = No aliases yet (add one with 'rauncher alias add <alias> <app>')
+alias-added = ✓ { $alias } now stands for { $app }
+alias-removed = ✓ Removed the alias { $alias }
+alias-unknown = There's no alias { $alias }
+alias-failed = Couldn't add the alias: { $error }
+
+## status
+
+status-title = R Games Launcher Status
+status-version = Version: { $version }
+status-authenticated = Authenticated: { yes-no }
+status-authenticated-store = Authenticated ({ $store }): { yes-no }
+status-configuration = Configuration:
+status-install-dir = Install Directory: { $path }
+status-log-level = Log Level: { $level }
+status-language = Language: { $language }
+status-config-path = Config Path: { $path }
+status-data-dir = Data Directory: { $path }
+
+## update
+
+update-line-pinned = { $id }: { $version } available (pinned to { $current })
+update-line-available = { $id }: update available ({ $version })
+update-line-current = { $id }: up to date
+update-game-failed = Failed to update { $app }: { $error }
+update-processed = ✓ Processed { $count ->
+    [one] 1 game
+   *[other] { $count } games
+}, { $failed } failed
+update-checking = Checking for updates for { $app }...
+update-available = ✓ Update available: version { $version }
+update-available-dated = ✓ Update available: version { $version }, released { $date }
+update-release-notes = Release notes
+update-up-to-date = ✓ Game is up to date
+update-check-failed = Failed to check for updates: { $error }
+update-done = ✓ Update complete!
+update-failed = Failed to update game: { $error }
+
+## after downloads
+
+after-power-countdown = { $action ->
+    [shutdown] Shutting the computer down
+   *[suspend] Suspending the computer
+} in { $seconds } seconds; press Ctrl+C to cancel
+
+## saves
+
+cloud-specify-direction = Error: Specify --download or --upload
+cloud-download-failed = Failed to download cloud saves: { $error }
+cloud-upload-failed = Failed to upload cloud saves: { $error }
+cloud-list-none = No cloud saves for { $app }
+cloud-list-title = Cloud saves for { $app }, newest first:
+cloud-list-entry = { $id }  { $date } UTC ({ $size } bytes)
+cloud-list-failed = Failed to list cloud saves: { $error }
+backup-none = No backups for { $app }
+backup-title = Backups for { $app }:
+backup-entry = #{ $index } { $date } ({ $size } bytes)
+backup-list-failed = Failed to list backups: { $error }
+backup-done = ✓ Saves backed up to { $path }
+backup-no-saves = No saves found for { $app }
+backup-failed = Failed to back up saves: { $error }
+restore-done = ✓ Restored { $count ->
+    [one] 1 save file
+   *[other] { $count } save files
+}
+restore-failed = Failed to restore saves: { $error }
+backup-detect-hint = Run 'rauncher detect-saves { $app }' after playing to find where the game saves
+detect-saves-none = No likely save folders found for { $app }
+detect-saves-title = Likely save folders for { $app }:
+detect-saves-entry = #{ $index } { $path }
+detect-saves-named = named after the game
+detect-saves-changed = { $count ->
+    [one] 1 file changed since it was last played
+   *[other] { $count } files changed since it was last played
+}
+detect-saves-apply-hint = Run 'rauncher detect-saves { $app } --apply [N]' to use one for cloud saves and backups
+detect-saves-applied = ✓ { $app } saves in { $path }
+detect-saves-unsure = { $path } isn't named after the game, it only had files change; run 'rauncher detect-saves { $app }' to check the list, then '--apply N' to pick one
+detect-saves-invalid = No suggestion #{ $index } ({ $count } found)
+detect-saves-failed = Failed to look for save folders: { $error }
+
+## install and update --dry-run
+
+plan-title = Plan for { $app } ({ $provider })
+plan-version = Version: { $version }
+plan-version-update = Version: { $current } → { $version }
+plan-path = Install path: { $path }
+plan-download = Download: { $changed } of { $files } files ({ $size }, { $unpacked } unpacked)
+plan-disk-usage = Disk usage: { $current } → { $size }
+plan-free-space = Free space: { $size }
+plan-no-space = Not enough free space: { $size } more is needed
+plan-nothing-written = Dry run: nothing was downloaded or written
+plan-failed = Could not plan { $app }: { $error }
+
+## doctor
+
+doctor-title = Diagnostics
+doctor-reachable = Epic { $service } service reachable
+doctor-unreachable = Can't reach Epic's { $service } service: { $error }
+doctor-fix-unreachable = Check your internet connection, proxy and firewall; installed games still start with `rauncher launch --offline`
+doctor-session-valid = Logged in as { $account } (session valid until { $expires })
+doctor-session-expired = Session expired at { $expired }; it is refreshed on the next command that needs it
+doctor-not-logged-in = Not logged in to Epic
+doctor-session-rejected = Epic didn't accept the session: { $error }
+doctor-fix-login = Log in with `rauncher auth`
+doctor-config-valid = Config is valid ({ $path })
+doctor-config-invalid = Config { $path } is invalid: { $error }
+doctor-fix-config = Correct the value with `rauncher config set`, or go back to its default with `rauncher config unset`
+doctor-library-writable = Library { $path } is writable
+doctor-library-not-writable = Can't write to library { $path }: { $error }
+doctor-fix-library = Fix the folder's permissions, or choose another with `rauncher config set install_dir <path>`
+doctor-drive-not-mounted = Library { $path } is on a drive that isn't mounted
+doctor-fix-drive = Plug in or mount the drive; its games show up again once it's back
+doctor-runner-found = Wine/Proton available for Windows games ({ $runner })
+doctor-no-runner = No Wine or Proton found for Windows games
+doctor-fix-runner = Install Proton with `rauncher runners install`, or Wine from your distribution
+doctor-runner-missing = { $app } is set to run with { $runner }, which isn't there
+doctor-fix-runner-missing = Install it with `rauncher runners install`, or drop the setting with `rauncher config unset games.{ $app }.runner`
+doctor-free-space = { $size } free in { $path }
+doctor-low-space = Only { $size } free in { $path }
+doctor-fix-space = See what takes the space with `rauncher disk-usage`, and clean up with `--clean-shader-cache` or `--clean-partial`
+doctor-all-good = ✓ Everything looks fine
+doctor-summary = { $errors ->
+    [one] 1 problem
+   *[other] { $errors } problems
+}, { $warnings ->
+    [one] 1 warning
+   *[other] { $warnings } warnings
+}
+doctor-hint = Run `rauncher doctor` to look for common problems
+
+## import-egl
+
+import-none = No new games to import
+import-entry = { $dry_run ->
+    [yes] Would import
+   *[no] Imported
+} { $id } - { $title } ({ $path })
+import-summary = ✓ { $dry_run ->
+    [yes] Would import
+   *[no] Imported
+} { $count ->
+    [one] 1 game
+   *[other] { $count } games
+}
+import-failed = Failed to import games: { $error }
+
+## egl-sync
+
+egl-sync-exported = { $dry_run ->
+    [yes] Would add
+   *[no] Added
+} { $id } to the Epic Games Launcher
+egl-sync-updated = { $dry_run ->
+    [yes] Would record
+   *[no] Recorded
+} the update of { $app } to { $version } on the other side
+egl-sync-forgotten = { $dry_run ->
+    [yes] Would forget
+   *[no] Forgot
+} { $app }: it was uninstalled in the Epic Games Launcher
+egl-sync-removed = { $dry_run ->
+    [yes] Would remove
+   *[no] Removed
+} { $app } from the Epic Games Launcher: it was uninstalled here
+egl-sync-none = Already in sync with the Epic Games Launcher
+egl-sync-failed = Sync with the Epic Games Launcher failed: { $error }
+
+## migrate
+
+migrate-session = { $dry_run ->
+    [yes] Would take over
+   *[no] Took over
+} the Epic login of account { $account }
+migrate-session-note = { $launcher } will have to log in again once rauncher renews the session
+migrate-settings = { $dry_run ->
+    [yes] Would take over
+   *[no] Took over
+} the launch settings of { $app }
+migrate-prefix = { $dry_run ->
+    [yes] Would reuse
+   *[no] Reusing
+} the Wine prefix of { $app } ({ $path })
+migrate-aliases = { $dry_run ->
+    [yes] Would take over
+   *[no] Took over
+} { $count ->
+    [one] 1 alias
+   *[other] { $count } aliases
+}
+migrate-none = Nothing new to migrate
+migrate-failed = Migration failed: { $error }
+
+## export
+
+export-entry = Exported { $id } - { $title }
+export-summary = ✓ Exported { $count ->
+    [one] 1 game
+   *[other] { $count } games
+}
+export-none = No installed Epic games to export
+export-failed = Export failed: { $error }
+
+## assets
+
+assets-none = No Unreal Engine assets in library
+assets-title = Unreal Engine Assets:
+assets-list-failed = Failed to list assets: { $error }
+assets-downloaded = ✓ Asset downloaded to { $path }
+assets-download-failed = Failed to download asset: { $error }
+
+## logs
+
+logs-none = No logs yet
+
+## history
+
+history-none = No installs, updates or uninstalls recorded yet
+history-title = History:
+history-install = install
+history-update = update
+history-repair = repair
+history-uninstall = uninstall
+history-entry = { $date }  { $action } { $game } { $version }, { $duration }{ $downloaded ->
+    [yes] , { $size } downloaded
+   *[no] {""}
+}
+history-failed = ✗ Failed: { $error }
+
+## stats
+
+stats-title = Statistics
+stats-unknown = unknown (offline or logged out)
+stats-owned = Games owned: { $count }
+stats-installed = Games installed: { $count } ({ $size })
+stats-playtime = Total play time: { $time }
+stats-most-played = Most played:
+stats-most-played-entry = { $rank }. { $title }: { $time } in { $sessions ->
+    [one] 1 session
+   *[other] { $sessions } sessions
+}
+stats-downloads = Downloaded per month:
+stats-downloads-entry = { $month }: { $size } ({ $total } in all)
+
+## last-run
+
+last-run-none = No transcripts yet; run a command with --transcript or set transcripts = true in the config
+
+## store
+
+store-title = Store (page { $page } of { $pages }, prices for { $country })
+store-stale = Epic couldn't be reached; showing the last results
+store-empty = No games match
+store-giveaway = Free right now: { $title }
+store-offer = { $title } - { $price }
+store-offer-discounted = { $title } - { $price } (-{ $discount }%, was { $original })
+store-offer-free = { $title } - Free
+store-offer-no-price = { $title } - no price yet
+store-opening = Opening { $title } in the browser: { $url }
+store-failed = Failed to browse the store: { $error }
+
+## wishlist
+
+wishlist-title = Wishlist
+wishlist-empty = The wishlist is empty; add games with `rauncher wishlist add <title>`
+wishlist-item-owned = { $title } - owned
+wishlist-added = Added { $title } to the wishlist
+wishlist-already = { $title } is already on the wishlist
+wishlist-add-failed = Failed to add to the wishlist: { $error }
+wishlist-removed = Removed { $title } from the wishlist
+wishlist-not-found = { $title } is not on the wishlist
+wishlist-no-news = No new sales or giveaways on the wishlist
+wishlist-on-sale = On sale: { $title } is { $discount }% off, { $price }
+wishlist-free = Free to claim: { $title }
+wishlist-failed = Failed to check the wishlist: { $error }
+
+## feed
+
+feed-rss-written = ✓ RSS feed written to { $path }
+feed-ical-written = ✓ iCalendar feed written to { $path }
+feed-failed = Failed to write feeds: { $error }
+
+## disk-usage
+
+disk-usage-title = Disk usage
+disk-usage-root = { $path } ({ $free } free of { $capacity })
+disk-usage-root-unknown = { $path } (free space unknown)
+disk-usage-root-empty = No games installed here
+disk-usage-game = { $title } ({ $app }): { $total } (game { $install }, prefix { $prefix }, shader cache { $shaders })
+disk-usage-shader-cache = Shader caches: { $size }
+disk-usage-partial = Partial downloads: { $count } ({ $size })
+disk-usage-clean-hint = Run with --clean-shader-cache or --clean-partial to free { $size }
+disk-usage-cleaned-shaders = { $trash ->
+    [yes] ✓ Moved the shader caches to the trash ({ $size }); empty it to free the space
+   *[no] ✓ Removed the shader caches, freeing { $size }
+}
+disk-usage-cleaned-partial = { $trash ->
+    [yes] ✓ Moved the partial downloads to the trash ({ $size }); empty it to free the space
+   *[no] ✓ Removed the partial downloads, freeing { $size }
+}
+disk-usage-failed = Failed to measure disk usage: { $error }
+
+## shader-cache
+
+shader-cache-installed = ✓ Installed the shader cache for { $app } ({ $size })
+shader-cache-up-to-date = The shader cache for { $app } is up to date
+shader-cache-unavailable = No shader cache is published for { $app } yet
+shader-cache-failed = Failed to update the shader cache: { $error }
+
+## runners
+
+runners-title = Installed runners
+runners-none = No runners installed. Download one with `rauncher runners install`
+runners-line = { $name } ({ $kind }, { $version })
+runners-available-title = Available { $kind } releases
+runners-available-line = { $version } ({ $size }, { $date }){ $installed ->
+    [yes] {" "}- installed
+   *[no] {""}
+}
+runners-list-failed = Failed to list runner releases: { $error }
+runners-downloading = Downloading { $version } ({ $size })...
+runners-installed = ✓ Installed runner { $name }
+runners-use-hint = Select it for a game with `rauncher config set games.<app_name>.runner { $name }`
+runners-install-failed = Failed to install the runner: { $error }
+runners-removed = { $trash ->
+    [yes] ✓ Moved runner { $name } to the trash
+   *[no] ✓ Removed runner { $name }
+}
+runners-remove-failed = Failed to remove the runner: { $error }
+
+## prefix
+
+prefix-created = ✓ Created the prefix in { $path }
+prefix-exists = The prefix already exists in { $path }
+prefix-exit = The command failed ({ $status })
+prefix-failed = Failed to run in the prefix: { $error }
+
+## orphans
+
+orphans-failed = Failed to look for orphaned folders: { $error }
+orphans-none = No orphaned game folders found
+orphans-title = Orphaned game folders:
+orphans-identified = { $path } ({ $size }): { $title } ({ $id }) { $version }, { $found }/{ $total } files match
+orphans-unidentified = { $path } ({ $size }): not an owned game
+orphans-hint = Run with --adopt to record identified games as installed, or --delete to remove the folders
+orphans-adopted = ✓ Adopted { $path } as { $id }
+orphans-adopt-failed = Failed to adopt { $path }: { $error }
+orphans-confirm = { $trash ->
+    [yes] Move { $count ->
+        [one] this folder
+       *[other] these { $count } folders
+    } to the trash?
+   *[no] Delete { $count ->
+        [one] this folder
+       *[other] these { $count } folders
+    } for good?
+}
+orphans-aborted = Nothing was deleted
+orphans-deleted = { $trash ->
+    [yes] ✓ Moved { $path } to the trash ({ $size })
+   *[no] ✓ Deleted { $path }, freeing { $size }
+}
+orphans-delete-failed = Failed to delete { $path }: { $error }
+
+## config
+
+config-set = ✓ Set { $key } to { $value }
+config-unset = ✓ Reset { $key } to its default
+config-not-set = { $key } is not set
+config-failed = Could not change the configuration: { $error }
+config-flatpak-permission = rauncher runs as a Flatpak that can't reach this directory yet; allow it with: { $command }
+
+## daemon
+
+daemon-failed = Daemon failed: { $error }
+daemon-listening = Health endpoint listening on { $url }
+daemon-feeds-served = Feeds served at { $rss } and { $ical }
+daemon-metrics-served = Prometheus metrics served at { $url }
+daemon-started = Daemon started; updating games every { $minutes ->
+    [one] minute
+   *[other] { $minutes } minutes
+}
+daemon-stopping = Shutting down
+daemon-http-stopped = Health endpoint stopped: { $error }
+daemon-no-sigterm = Cannot listen for SIGTERM: { $error }
+daemon-refresh-failed = Could not refresh the { $store } login: { $error }
+daemon-update-run-failed = Update run failed: { $error }
+daemon-feeds-failed = Failed to refresh the feeds: { $error }
+daemon-wishlist-failed = Failed to check the wishlist: { $error }
+
+## serve
+
+serve-listening = API listening on { $url }
+serve-token-created = Generated an API token in { $path }
+serve-token-empty = RAUNCHER_SERVE_TOKEN or --token-file is empty; using the stored token instead
+serve-stopped = API stopped
+serve-failed = API server failed: { $error }
+serve-record-failed = Could not record the API's address for rauncher queue: { $error }
+
+## remote
+
+remote-needs-command = --host needs a command to run, and a token from --host-token-file or RAUNCHER_SERVE_TOKEN
+remote-unsupported = This command can't run with --host; use list, install, update, launch or queue without local-only options
+remote-disconnected = The connection to the remote rauncher closed before the operation finished
+remote-invalid-host = Invalid --host { $host }: { $error }
+remote-no-host = it names no host
+
+## queue
+
+queue-title = Download queue
+queue-empty = No installs are queued.
+queue-no-serve = No rauncher serve is running on this machine; start one, or pass --host to reach another
+queue-job = { $app } - { $state ->
+    [queued] queued
+    [active] downloading
+    [paused] paused
+    [completed] completed
+    [cancelled] cancelled
+   *[failed] failed
+}, { $priority ->
+    [high] high
+    [low] low
+   *[normal] normal
+} priority
+queue-prioritized = { $app } moved to the front of the queue with high priority
+queue-fetch-failed = Failed to read the download queue: { $error }
+queue-prioritize-failed = Failed to prioritize { $app }: { $error }
+
+## gui
+
+gui-failed = Failed to run GUI: { $error }
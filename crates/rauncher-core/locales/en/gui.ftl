@@ -0,0 +1,271 @@
+# Labels and status messages of the desktop GUI
+# Status bar messages starting with ✓ are shown in green, those starting with ✗ in red
+
+## header
+
+gui-title = R Games Launcher
+gui-logout = Logout
+gui-account = Account
+gui-add-account = Add account...
+gui-notifications = Notifications
+gui-notify-install-finished = Finished installs
+gui-notify-update-available = Available updates
+gui-notify-cloud-sync-failed = Failed cloud save syncs
+gui-notify-wishlist = Wishlist sales and giveaways
+gui-theme = Theme
+gui-theme-failed = ✗ Failed to load theme: { $error }
+gui-unavailable-title = Rauncher could not start
+gui-unavailable-hint = Fix the configuration in { $path } and start Rauncher again.
+gui-appearance = Appearance
+gui-ui-scale = UI scale
+gui-ui-scale-auto = Automatic ({ $percent }%)
+
+## login
+
+gui-store-banner = EPIC GAMES STORE
+gui-sign-in-subtitle = Sign in to your account
+gui-sign-in = Sign In with Epic Games
+gui-sign-in-hint = Click the button above to authenticate with Epic Games
+gui-sign-in-code-hint = You'll receive a code to enter in your browser
+gui-sign-in-web = Sign in through the website instead
+gui-sign-in-web-hint = For accounts with two-factor authentication or captchas that the code login can't handle
+gui-auth-web-steps = Log in on the page opened in your browser, then paste the text Epic shows into it:
+gui-auth-initializing = Initializing authentication...
+gui-auth-in-progress = ⏳ Authentication in Progress
+gui-auth-complete-in-browser = Please complete authentication in your browser:
+gui-auth-url = URL:
+gui-auth-code = Code:
+gui-open-in-browser = 🌐 Open in Browser
+gui-auth-waiting = Waiting for you to complete authentication... (attempt { $attempt }/{ $max })
+gui-auth-start-failed = ✗ Failed to start authentication: { $error }
+gui-auth-save-failed = ✗ Error saving token: { $error }
+gui-auth-success = ✓ Successfully authenticated with Epic Games!
+gui-auth-timed-out = ✗ Authentication timed out. Please try again.
+gui-auth-failed = ✗ Authentication failed: { $error }
+gui-auth-cancelled = Authentication cancelled
+
+## library
+
+gui-library = Library
+gui-search-hint = Search games...
+gui-filter-all = All Games
+gui-filter-installed = Installed
+gui-all-stores = All stores
+gui-no-games = No games found
+gui-library-empty = Your library is empty or not yet loaded
+gui-play = ▶ Play
+gui-stop = ■ Stop
+gui-uninstall = Uninstall
+gui-settings = Settings
+gui-installing = ⏳ Installing...
+gui-drive-not-mounted = Drive not mounted
+gui-update-available = Update available
+gui-update-available-hint = Version { $version } is available; run 'rauncher update' to install it
+gui-update-released = Released { $date }
+gui-drive-not-mounted-hint = The game is on a removable or network drive that isn't connected; it can be played again once the drive is back
+gui-get = Get
+gui-filter-favorites = ★ Favorites
+gui-filter-hidden = Hidden
+gui-favorite = Add to favorites
+gui-unfavorite = Remove from favorites
+gui-game-menu = More
+gui-hide = Hide game
+gui-unhide = Show game
+gui-categories = Categories
+gui-new-category = New category
+gui-add-category = Add
+
+## game settings
+
+gui-settings-title = { $app } Settings
+gui-launch-wrappers = Launch Wrappers
+gui-gamemode = Run with GameMode
+gui-mangohud = Show MangoHud overlay
+gui-gamescope = Run in gamescope
+gui-gamescope-options = gamescope options:
+gui-sandbox = Sandbox:
+gui-sandbox-none = None
+gui-sandbox-network = Allow network access in the sandbox
+gui-launch-args = Launch arguments:
+gui-launch-executable = Executable:
+gui-launch-executable-hint = The game's own, or e.g. mods/loader.exe
+gui-launch-working-dir = Working directory:
+gui-launch-working-dir-hint = The install directory
+gui-environment = Environment Variables
+gui-env-add = ➕ Add variable
+gui-compatibility = Compatibility
+gui-runner = Runner:
+gui-runner-none = None (Wine/Proton path below)
+gui-runner-hint = Download GE-Proton or Wine-GE with `rauncher runners install`
+gui-wine = Wine/Proton:
+gui-wine-hint = wine from PATH
+gui-shader-cache-url = Shader cache URL:
+gui-shader-cache-url-hint = The global shader_cache_url
+gui-save = Save
+gui-cancel = Cancel
+
+## status bar
+
+gui-clear = Clear
+gui-error = ✗ Error: { $error }
+gui-profile-switched = ✓ Switched to profile { $name }
+gui-library-loading = Loading library...
+gui-library-loaded = ✓ Library loaded successfully
+gui-library-loaded-offline = Offline: showing your library as of the last refresh
+gui-offline = ⚠ Offline
+gui-offline-hint = The stores could not be reached. Installed games can still be launched; installs and updates need a connection.
+gui-library-failed = ✗ Failed to load library: { $error }
+gui-install-started = Installation started for { $app }...
+gui-install-done = ✓ Installation completed for { $app }
+gui-install-failed = ✗ Installation failed for { $app }: { $error }
+gui-launched = ✓ Launched { $app }
+gui-launch-failed = ✗ Failed to launch { $app }: { $error }
+gui-stopped = Stopped { $app }
+gui-stop-failed = ✗ Failed to stop { $app }: { $error }
+gui-settings-saved = ✓ Saved settings for { $app }
+gui-settings-save-failed = ✗ Failed to save settings: { $error }
+gui-collections-save-failed = ✗ Failed to save your categories and favorites: { $error }
+gui-uninstalled = { $trash ->
+    [yes] ✓ Uninstalled { $app }; its files are in the trash
+   *[no] ✓ Uninstalled { $app }
+}
+gui-uninstall-failed = ✗ Failed to uninstall { $app }: { $error }
+gui-game-crashed = ✗ { $app } crashed
+gui-view-report = View report
+gui-install-cancelled = Installation cancelled for { $app }
+gui-launching = Launching { $app }...
+gui-stopping = Stopping { $app }...
+gui-uninstall-started = Uninstalling { $app }...
+gui-toast-dismiss = Dismiss
+
+## questions
+
+gui-question = Question
+
+## crash report
+
+gui-crash-title = { $app } crashed
+gui-crash-output = Game output
+gui-crash-saved = Full report: { $path }
+gui-close = Close
+
+## downloads
+
+gui-downloads = Downloads
+gui-downloads-empty = Nothing queued. Games you install show up here.
+gui-clear-finished = Clear finished
+gui-pause = Pause
+gui-resume = Resume
+gui-move-up = Move up
+gui-move-down = Move down
+gui-drag-to-reorder = Drag to reorder
+gui-prioritize = Download next, with high priority
+gui-download-queued = Queued
+gui-download-active = Downloading
+gui-download-paused = Paused
+gui-download-completed = ✓ Completed
+gui-download-failed = ✗ Failed: { $error }
+gui-download-cancelled = Cancelled
+gui-download-progress = { $files_done }/{ $files_total } files, { $done } of { $total }, { $speed }/s
+gui-download-eta = { $eta } left
+gui-metered-paused = Metered connection: downloads wait for another one
+gui-metered-limited = Metered connection: downloads are limited to { $speed }/s
+gui-metered-allowed = Metered connection: downloading anyway
+gui-metered-allow = Download anyway
+gui-download-size = { $size } to download
+gui-download-order = Order
+gui-order-manual = As listed
+gui-order-smallest-first = Smallest first
+gui-order-priority = By priority
+gui-priority-low = Low priority
+gui-priority-normal = Normal priority
+gui-priority-high = High priority
+
+## storage
+
+gui-storage = Storage
+gui-storage-loading = Measuring disk usage...
+gui-storage-failed = Failed to measure disk usage: { $error }
+gui-install-dir = Install directory: { $path }
+gui-install-dir-change = 📁 Change...
+gui-install-dir-saved = ✓ New games will be installed in { $path }
+gui-flatpak-permission = rauncher's Flatpak sandbox can't reach { $path }. Allow it with `{ $command }`, restart rauncher and pick it again
+gui-flatpak-portal-path = That folder isn't shared with rauncher's Flatpak sandbox. Allow it with `{ $command }`, restart rauncher and pick it again
+gui-storage-root = { $free } free of { $capacity }
+gui-storage-root-unknown = Free space unknown
+gui-storage-root-empty = No games installed here
+gui-storage-game-detail = game { $install } · prefix { $prefix } · shader cache { $shaders }
+gui-storage-game-download = downloaded { $size }
+gui-storage-cleanup = Cleanup
+gui-storage-shader-cache = Shader caches: { $size }
+gui-storage-partial = Partial downloads: { $count } ({ $size })
+gui-clean-shader-cache = Clear shader caches
+gui-clean-partial = Remove partial downloads
+gui-storage-freed = ✓ Moved { $size } to the trash; empty it to free the space
+gui-refresh = Refresh
+gui-history = History
+gui-history-empty = No installs, updates or uninstalls yet
+gui-history-search = Filter by game
+gui-history-install = Installed
+gui-history-update = Updated
+gui-history-repair = Repaired
+gui-history-uninstall = Uninstalled
+gui-history-downloaded = { $size } downloaded
+gui-history-failed = ✗ Failed: { $error }
+gui-stats = Stats
+gui-stats-owned = Games owned
+gui-stats-installed = Games installed
+gui-stats-installed-size = Installed size
+gui-stats-playtime = Play time
+gui-stats-most-played = Most played
+gui-stats-no-sessions = No play time recorded yet. It counts games launched from here or with launch --wait.
+gui-stats-played = { $time } in { $sessions ->
+    [one] 1 session
+   *[other] { $sessions } sessions
+}
+gui-stats-downloads = Downloads over time
+gui-stats-downloads-total = { $size } downloaded in all
+gui-stats-no-downloads = Nothing downloaded yet
+gui-orphans = Orphaned folders
+gui-orphans-hint = Game folders rauncher has no record of, e.g. after losing its data or copying a game in
+gui-orphans-scan = Scan
+gui-orphans-none = No orphaned folders found
+gui-orphans-failed = Failed to look for orphaned folders: { $error }
+gui-orphan-identified = { $title } { $version }, { $found }/{ $total } files match
+gui-orphan-unidentified = Not an owned game
+gui-adopt = Adopt
+gui-delete = Delete
+gui-orphan-adopted = ✓ Adopted { $path } as { $app }
+
+## store
+
+gui-store = Store
+gui-store-search = Search the store...
+gui-store-all-genres = All genres
+gui-store-free = Free games
+gui-store-loading = Loading the store...
+gui-store-failed = ✗ Failed to load the store: { $error }
+gui-store-prices = Prices for { $country }
+gui-store-stale = Epic couldn't be reached; these are the last results
+gui-store-page = Page { $page } of { $pages }
+gui-store-previous = ◀ Previous
+gui-store-next = Next ▶
+gui-store-giveaways = Free right now
+gui-store-giveaway-until = Free until { $date }
+gui-store-claim = Claim in browser
+gui-store-empty = No games match
+gui-store-open = Open in browser to purchase
+gui-store-owned = ✓ Owned
+gui-store-wishlist = ♡ Wishlist
+gui-store-wishlisted = ♥ Wishlisted
+gui-store-no-price = No price yet
+gui-store-price-free = Free
+
+## big picture
+
+gui-big-picture = Big Picture: fullscreen mode for TVs and handhelds
+gui-big-picture-exit = Hold for the full interface
+gui-big-picture-empty = No installed games yet
+gui-big-picture-hint = Hold a game for its settings · Hold ⚙ for the full interface
+gui-last-played = Last played { $date }
+gui-never-played = Not played yet
@@ -0,0 +1,19 @@
+# Desktop notifications shown by the GUI and the daemon
+
+notify-install-finished = Installation finished
+notify-install-finished-body = { $title } is ready to play
+notify-update-available = Update available
+notify-update-available-body = { $title } can be updated to { $version }
+notify-cloud-sync-failed = Cloud save sync failed
+notify-cloud-sync-failed-body = { $title }: { $error }
+notify-wishlist-sale = Wishlisted game on sale
+notify-wishlist-sale-body = { $title } is { $discount }% off: { $price }
+notify-wishlist-free = Wishlisted game free
+notify-wishlist-free-body = { $title } is free to claim this week
+
+# Messages sent to webhooks in Discord's format
+
+webhook-update-installed = { $title } was updated from { $from } to { $version }
+webhook-download-failed = Could not update { $title }: { $error }
+webhook-update-run-failed = The update run failed: { $error }
+webhook-free-game-claimed = { $title } was claimed for free
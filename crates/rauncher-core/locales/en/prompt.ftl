@@ -0,0 +1,10 @@
+# Questions asked half-way through an operation, by the CLI on the terminal and the GUI in a dialog
+
+prompt-save-conflict = The cloud save { $file } of { $title } differs from your local save. Which one do you want to keep?
+prompt-overwrite-install = { $path } already contains files. Install { $title } over them?
+prompt-answer-keep-local = Keep local save
+prompt-answer-use-cloud = Use cloud save
+prompt-answer-cancel = Cancel
+prompt-answer-overwrite = Overwrite
+prompt-answer-no = No
+prompt-answer-yes = Yes
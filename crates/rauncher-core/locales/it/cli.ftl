@@ -0,0 +1,660 @@
+# Messaggi stampati dalla riga di comando `rauncher`
+
+not-authenticated = Errore: non autenticato. Esegui prima 'rauncher auth'.
+yes-no = { $value ->
+    [yes] Sì
+   *[no] No
+}
+# Risposta accettata dalle domande [s/N], oltre a "y"
+prompt-yes = s
+# Aggiunto alle domande sì/no poste nel terminale
+prompt-yes-no = [s/N]
+# Chiesto dopo aver elencato le risposte numerate a una domanda
+prompt-choice = Scelta [{ $default }]:
+# Chiesto dopo aver elencato opzioni numerate, nessuna delle quali va scelta per forza
+prompt-choice-optional = Scelta (Invio per nessuna):
+
+## auth
+
+auth-title = Autenticazione { $store }
+auth-epic-title = Autenticazione Epic Games Store
+auth-profiles-epic-only = I profili sono supportati solo per l'Epic Games Store
+auth-web-epic-only = --web vale solo per l'Epic Games Store; gli altri store accedono già tramite il browser
+auth-logged-out = Disconnessione completata
+auth-logged-out-store = Disconnessione da { $store } completata
+auth-success-store = ✓ Autenticazione con { $store } riuscita!
+auth-epic-success = ✓ Autenticazione con l'Epic Games Store riuscita!
+auth-failed = Autenticazione non riuscita: { $error }
+auth-log-in-browser = Accedi dal tuo browser web:
+auth-use-browser = Autenticati dal tuo browser web:
+auth-open-url = Apri questo URL: { $url }
+auth-enter-code = Inserisci questo codice: { $code }
+auth-copy-code = Poi copia il codice dalla pagina su cui arrivi ed esegui:
+auth-create-api-key = Crea una chiave API su: { $url }
+auth-then-run = Poi esegui:
+auth-starting = Avvio dell'autenticazione...
+auth-waiting = In attesa dell'autenticazione...
+auth-next-steps = Ora puoi:
+auth-hint-list = Elencare i tuoi giochi: rauncher list
+auth-hint-list-store = Elenca i tuoi giochi: rauncher list --provider { $provider }
+auth-hint-install = Installare un gioco: rauncher install <app_name>
+auth-retry = Riprova. Se il problema persiste, controlla:
+auth-check-connection = La tua connessione a internet
+auth-check-epic-status = Lo stato dei servizi Epic Games
+auth-try-web = Se il tuo account usa l'autenticazione a due fattori o un captcha, prova: rauncher auth --web
+auth-web-open = Accedi tramite questa pagina nel browser: { $url }
+auth-web-steps = Rimanda all'accesso di Epic Games, che gestisce i controlli a due fattori e i captcha; incolla poi nella pagina il testo che Epic mostra
+auth-web-waiting = In attesa che l'accesso termini (Ctrl+C per annullare)...
+profiles-none = Nessun profilo salvato
+profiles-title = Profili:
+profile-switched = ✓ Passato al profilo { $name }
+profile-switch-failed = Impossibile cambiare profilo: { $error }
+auth-status-title = Sessione { $store }
+auth-status-not-logged-in = Accesso a { $store } non effettuato
+auth-status-failed = Impossibile verificare la sessione: { $error }
+auth-status-display-name = Nome visualizzato: { $value }
+auth-status-account-id = ID account: { $value }
+auth-status-expires = Il token scade: { $time } (tra { $minutes ->
+    [one] 1 minuto
+   *[other] { $minutes } minuti
+})
+auth-status-expired = Token scaduto: { $time }; verrà rinnovato dal prossimo comando che ne ha bisogno
+auth-status-scopes = Ambiti: { $value }
+auth-status-profile = Profilo: { $value }
+
+## entitlements
+
+entitlements-failed = Impossibile recuperare i diritti: { $error }
+entitlements-none = Nessun diritto trovato
+entitlements-title = Diritti ({ $count }):
+entitlements-entry = { $name } ({ $namespace }/{ $item }): { $kind }, concesso il { $granted }{ $active ->
+    [yes] {""}
+   *[no] , inattivo
+}
+
+## list
+
+list-no-installed = Nessun gioco installato
+list-installed-title = Giochi installati:
+list-path = Percorso: { $path }
+list-unavailable = Non disponibile (unità non montata)
+library-title = Libreria:
+library-empty = Nessun gioco in libreria (o è necessaria l'autenticazione)
+list-none-match = Nessun gioco corrisponde ai filtri
+list-platform-failed = Impossibile trovare le piattaforme dei giochi Epic: { $error }
+list-column = { $column ->
+    [id] ID
+    [title] Titolo
+    [version] Versione
+    [store] Store
+    [path] Percorso
+   *[size] Dimensione
+}
+library-fetch-failed = Impossibile caricare la libreria: { $error }
+library-offline = Offline: la libreria mostrata è quella dell'ultimo aggiornamento
+
+## install, versions, pin, launch, uninstall, info
+
+install-provider-conflict = { $address } è in conflitto con --provider { $provider }
+install-version-epic-only = L'opzione --version è supportata solo per i giochi dell'Epic Games Store
+install-start = Installazione del gioco: { $app }
+install-done = Gioco installato!
+install-progress = { $percent }% ({ $done } di { $total }) a { $speed }/s
+install-progress-eta = { $percent }% ({ $done } di { $total }) a { $speed }/s, { $eta } rimanenti
+install-failed = Installazione non riuscita: { $error }
+versions-title = Build disponibili per { $app }:
+versions-failed = Impossibile recuperare le build: { $error }
+pin-done = ✓ { $app } bloccato sulla build attuale
+pin-failed = Impossibile bloccare il gioco: { $error }
+unpin-done = ✓ { $app } sbloccato
+unpin-failed = Impossibile sbloccare il gioco: { $error }
+launch-done = Gioco avviato!
+launch-stopping = Arresto di { $app } in corso...
+launch-killed = { $app } è stato terminato dal segnale { $signal }
+launch-failed = Impossibile avviare il gioco: { $error }
+launch-already-running = { $app } è già in esecuzione; una seconda istanza potrebbe corrompere i salvataggi
+launch-already-running-hint = Esegui prima 'rauncher stop { $app }', o avvialo comunque con --force
+stop-done = ✓ { $app } fermato
+stop-not-running = { $app } non è in esecuzione
+stop-failed = Impossibile fermare il gioco: { $error }
+uninstall-done = { $trash ->
+    [yes] Gioco disinstallato, i suoi file sono nel cestino
+   *[no] Gioco disinstallato!
+}
+uninstall-failed = Disinstallazione non riuscita: { $error }
+uninstall-matching-none = Nessun gioco installato corrisponde ai filtri
+uninstall-matching-title = Giochi da disinstallare:
+uninstall-matching-entry = { $id } - { $title }: { $size }, ultima partita { $played }
+uninstall-never-played = mai
+uninstall-matching-total = { $count ->
+    [one] 1 gioco
+   *[other] { $count } giochi
+}, { $size } in totale
+uninstall-confirm = Disinstallare { $count ->
+    [one] questo gioco
+   *[other] questi { $count } giochi
+}?
+uninstall-game-title = Gioco da disinstallare: { $title }
+uninstall-aborted = Nessun gioco è stato disinstallato
+uninstall-game-done = ✓ { $id } disinstallato
+uninstall-game-failed = Impossibile disinstallare { $id }: { $error }
+uninstall-bulk-done = ✓ { $count ->
+    [one] 1 gioco disinstallato
+   *[other] { $count } giochi disinstallati
+}, { $trash ->
+    [yes] { $size } spostati nel cestino
+   *[no] liberati { $size }
+}
+info-title = Informazioni sul gioco:
+info-name = Nome: { $value }
+info-store = Store: { $value }
+info-game-title = Titolo: { $value }
+info-version = Versione: { $value }
+info-install-path = Percorso di installazione: { $value }
+info-executable = Eseguibile: { $value }
+info-install-size = Dimensione installata: { $value }
+info-download-size = Dimensione del download: { $value }
+info-save-path = Percorso dei salvataggi: { $value }
+info-save-path-unresolved = Percorso dei salvataggi: sconosciuto ({ $error })
+game-not-found = Gioco non trovato: { $app }
+
+## verify
+
+verify-start = Verifica di { $app }...
+verify-failed = Impossibile verificare il gioco: { $error }
+verify-progress = { $percent }% ({ $checked } di { $files } file, { $done } di { $total })
+verify-entry = { $status ->
+    [mismatched] Danneggiato
+    [missing] Mancante
+   *[extra] In più
+}: { $path }
+verify-clean = ✓ Tutti i { $count } file corrispondono al manifest
+verify-summary = Controllati { $count } file: { $mismatched } danneggiati, { $missing } mancanti, { $extra } in più
+verify-report-written = ✓ Rapporto scritto in { $path }
+verify-delete-confirm = { $trash ->
+    [yes] Spostare nel cestino
+   *[no] Eliminare
+} { $count ->
+    [one] 1 file in più
+   *[other] { $count } file in più
+}?
+verify-delete-aborted = Non è stato eliminato nulla
+verify-deleted = ✓ { $trash ->
+    [yes] { $count ->
+        [one] Spostato nel cestino 1 file in più
+       *[other] Spostati nel cestino { $count } file in più
+    } ({ $size })
+   *[no] { $count ->
+        [one] Eliminato 1 file in più
+       *[other] Eliminati { $count } file in più
+    }, liberando { $size }
+}
+verify-delete-failed = Impossibile eliminare i file in più: { $error }
+verify-hint = Reinstalla il gioco per ripristinare i file danneggiati e mancanti
+
+## mods
+
+mods-title = File protetti di { $app }
+mods-none = { $app } non ha file protetti
+mods-protected = ✓ { $path } protetto
+mods-already = { $path } è già protetto
+mods-unprotected = ✓ { $path } non è più protetto
+mods-not-protected = { $path } non era protetto
+mods-failed = Impossibile modificare i file protetti: { $error }
+
+## did you mean, pickers
+
+did-you-mean = Nessun gioco si chiama { $name }; forse intendevi { $games }?
+did-you-mean-choose = Nessun gioco si chiama { $name }. Forse intendevi:
+pick-install = Gioco da installare (scrivi per cercare)
+pick-launch = Gioco da avviare (scrivi per cercare)
+pick-needs-terminal = Indica il gioco; si può sceglierlo da un elenco solo in un terminale
+pick-nothing-to-install = Tutti i giochi della libreria sono già installati
+
+## favorite, hide, category, alias
+
+favorite-done = ★ { $app } aggiunto ai preferiti
+unfavorite-done = { $app } rimosso dai preferiti
+hide-done = { $app } è nascosto dagli elenchi (lo mostra 'rauncher list --hidden')
+unhide-done = { $app } è di nuovo negli elenchi
+category-title = Categorie:
+category-none = Ancora nessuna categoria (aggiungine una con 'rauncher category add <app> <categoria>')
+category-empty = Il nome della categoria non può essere vuoto
+category-added = ✓ { $app } è in { $category }
+category-removed = ✓ { $app } tolto da { $category }
+category-not-in = { $app } non è in { $category }
+alias-title = Alias:
+alias-none = Ancora nessun alias (aggiungine uno con 'rauncher alias add <alias> <app>')
+alias-added = ✓ { $alias } ora indica { $app }
+alias-removed = ✓ Rimosso l'alias { $alias }
+alias-unknown = Non esiste l'alias { $alias }
+alias-failed = Impossibile aggiungere l'alias: { $error }
+
+## status
+
+status-title = Stato di R Games Launcher
+status-version = Versione: { $version }
+status-authenticated = Autenticato: { yes-no }
+status-authenticated-store = Autenticato ({ $store }): { yes-no }
+status-configuration = Configurazione:
+status-install-dir = Cartella di installazione: { $path }
+status-log-level = Livello di log: { $level }
+status-language = Lingua: { $language }
+status-config-path = File di configurazione: { $path }
+status-data-dir = Cartella dei dati: { $path }
+
+## update
+
+update-line-pinned = { $id }: disponibile { $version } (bloccato su { $current })
+update-line-available = { $id }: aggiornamento disponibile ({ $version })
+update-line-current = { $id }: aggiornato
+update-game-failed = Impossibile aggiornare { $app }: { $error }
+update-processed = ✓ { $count ->
+    [one] 1 gioco elaborato
+   *[other] { $count } giochi elaborati
+}, { $failed } non riusciti
+update-checking = Ricerca di aggiornamenti per { $app }...
+update-available = ✓ Aggiornamento disponibile: versione { $version }
+update-available-dated = ✓ Aggiornamento disponibile: versione { $version }, pubblicata il { $date }
+update-release-notes = Note di rilascio
+update-up-to-date = ✓ Il gioco è aggiornato
+update-check-failed = Impossibile cercare aggiornamenti: { $error }
+update-done = ✓ Aggiornamento completato!
+update-failed = Aggiornamento non riuscito: { $error }
+
+## after downloads
+
+after-power-countdown = { $action ->
+    [shutdown] Spegnimento del computer
+   *[suspend] Sospensione del computer
+} tra { $seconds } secondi; premi Ctrl+C per annullare
+
+## saves
+
+cloud-specify-direction = Errore: specifica --download o --upload
+cloud-download-failed = Impossibile scaricare i salvataggi cloud: { $error }
+cloud-upload-failed = Impossibile caricare i salvataggi cloud: { $error }
+cloud-list-none = Nessun salvataggio cloud per { $app }
+cloud-list-title = Salvataggi cloud di { $app }, dal più recente:
+cloud-list-entry = { $id }  { $date } UTC ({ $size } byte)
+cloud-list-failed = Impossibile elencare i salvataggi cloud: { $error }
+backup-none = Nessun backup per { $app }
+backup-title = Backup di { $app }:
+backup-entry = #{ $index } { $date } ({ $size } byte)
+backup-list-failed = Impossibile elencare i backup: { $error }
+backup-done = ✓ Salvataggi copiati in { $path }
+backup-no-saves = Nessun salvataggio trovato per { $app }
+backup-failed = Backup dei salvataggi non riuscito: { $error }
+restore-done = ✓ { $count ->
+    [one] Ripristinato 1 file di salvataggio
+   *[other] Ripristinati { $count } file di salvataggio
+}
+restore-failed = Ripristino dei salvataggi non riuscito: { $error }
+backup-detect-hint = Esegui 'rauncher detect-saves { $app }' dopo aver giocato per trovare dove il gioco salva
+detect-saves-none = Nessuna probabile cartella dei salvataggi trovata per { $app }
+detect-saves-title = Probabili cartelle dei salvataggi di { $app }:
+detect-saves-entry = #{ $index } { $path }
+detect-saves-named = ha il nome del gioco
+detect-saves-changed = { $count ->
+    [one] 1 file modificato dall'ultima partita
+   *[other] { $count } file modificati dall'ultima partita
+}
+detect-saves-apply-hint = Esegui 'rauncher detect-saves { $app } --apply [N]' per usarne una per i salvataggi nel cloud e i backup
+detect-saves-applied = ✓ { $app } salva in { $path }
+detect-saves-unsure = { $path } non ha il nome del gioco, vi sono solo cambiati dei file; esegui 'rauncher detect-saves { $app }' per controllare l'elenco, poi '--apply N' per sceglierne una
+detect-saves-invalid = Nessun suggerimento #{ $index } ({ $count } trovati)
+detect-saves-failed = Impossibile cercare le cartelle dei salvataggi: { $error }
+
+## install and update --dry-run
+
+plan-title = Piano per { $app } ({ $provider })
+plan-version = Versione: { $version }
+plan-version-update = Versione: { $current } → { $version }
+plan-path = Percorso di installazione: { $path }
+plan-download = Download: { $changed } di { $files } file ({ $size }, { $unpacked } decompressi)
+plan-disk-usage = Spazio su disco: { $current } → { $size }
+plan-free-space = Spazio libero: { $size }
+plan-no-space = Spazio libero insufficiente: servono altri { $size }
+plan-nothing-written = Prova: non è stato scaricato né scritto nulla
+plan-failed = Impossibile pianificare { $app }: { $error }
+
+## doctor
+
+doctor-title = Diagnostica
+doctor-reachable = Servizio { $service } di Epic raggiungibile
+doctor-unreachable = Impossibile raggiungere il servizio { $service } di Epic: { $error }
+doctor-fix-unreachable = Controlla la connessione a internet, il proxy e il firewall; i giochi installati si avviano comunque con `rauncher launch --offline`
+doctor-session-valid = Accesso effettuato come { $account } (sessione valida fino a { $expires })
+doctor-session-expired = Sessione scaduta il { $expired }; viene rinnovata al prossimo comando che ne ha bisogno
+doctor-not-logged-in = Accesso a Epic non effettuato
+doctor-session-rejected = Epic non ha accettato la sessione: { $error }
+doctor-fix-login = Accedi con `rauncher auth`
+doctor-config-valid = Configurazione valida ({ $path })
+doctor-config-invalid = La configurazione { $path } non è valida: { $error }
+doctor-fix-config = Correggi il valore con `rauncher config set`, o torna al predefinito con `rauncher config unset`
+doctor-library-writable = Libreria { $path } scrivibile
+doctor-library-not-writable = Impossibile scrivere nella libreria { $path }: { $error }
+doctor-fix-library = Correggi i permessi della cartella, o scegline un'altra con `rauncher config set install_dir <percorso>`
+doctor-drive-not-mounted = La libreria { $path } è su un'unità non montata
+doctor-fix-drive = Collega o monta l'unità; i suoi giochi ricompaiono appena torna disponibile
+doctor-runner-found = Wine/Proton disponibile per i giochi Windows ({ $runner })
+doctor-no-runner = Nessun Wine o Proton trovato per i giochi Windows
+doctor-fix-runner = Installa Proton con `rauncher runners install`, o Wine dalla tua distribuzione
+doctor-runner-missing = { $app } è impostato per usare { $runner }, che non c'è
+doctor-fix-runner-missing = Installalo con `rauncher runners install`, o rimuovi l'impostazione con `rauncher config unset games.{ $app }.runner`
+doctor-free-space = { $size } liberi in { $path }
+doctor-low-space = Solo { $size } liberi in { $path }
+doctor-fix-space = Guarda cosa occupa spazio con `rauncher disk-usage`, e libera spazio con `--clean-shader-cache` o `--clean-partial`
+doctor-all-good = ✓ Sembra tutto a posto
+doctor-summary = { $errors ->
+    [one] 1 problema
+   *[other] { $errors } problemi
+}, { $warnings ->
+    [one] 1 avviso
+   *[other] { $warnings } avvisi
+}
+doctor-hint = Esegui `rauncher doctor` per cercare i problemi più comuni
+
+## import-egl
+
+import-none = Nessun nuovo gioco da importare
+import-entry = { $dry_run ->
+    [yes] Da importare:
+   *[no] Importato:
+} { $id } - { $title } ({ $path })
+import-summary = ✓ { $dry_run ->
+    [yes] { $count ->
+        [one] 1 gioco da importare
+       *[other] { $count } giochi da importare
+    }
+   *[no] { $count ->
+        [one] 1 gioco importato
+       *[other] { $count } giochi importati
+    }
+}
+import-failed = Importazione non riuscita: { $error }
+
+## egl-sync
+
+egl-sync-exported = { $dry_run ->
+    [yes] Verrebbe aggiunto
+   *[no] Aggiunto
+} { $id } all'Epic Games Launcher
+egl-sync-updated = { $dry_run ->
+    [yes] Verrebbe registrato
+   *[no] Registrato
+} l'aggiornamento di { $app } a { $version } dall'altra parte
+egl-sync-forgotten = { $dry_run ->
+    [yes] Verrebbe dimenticato
+   *[no] Dimenticato
+} { $app }: è stato disinstallato nell'Epic Games Launcher
+egl-sync-removed = { $dry_run ->
+    [yes] Verrebbe rimosso
+   *[no] Rimosso
+} { $app } dall'Epic Games Launcher: è stato disinstallato qui
+egl-sync-none = Già sincronizzato con l'Epic Games Launcher
+egl-sync-failed = Sincronizzazione con l'Epic Games Launcher non riuscita: { $error }
+
+## migrate
+
+migrate-session = { $dry_run ->
+    [yes] Verrebbe ripreso
+   *[no] Ripreso
+} l'accesso a Epic dell'account { $account }
+migrate-session-note = { $launcher } dovrà accedere di nuovo quando rauncher rinnoverà la sessione
+migrate-settings = { $dry_run ->
+    [yes] Verrebbero riprese
+   *[no] Riprese
+} le impostazioni di avvio di { $app }
+migrate-prefix = { $dry_run ->
+    [yes] Verrebbe riusato
+   *[no] Riuso di
+} il prefisso Wine di { $app } ({ $path })
+migrate-aliases = { $dry_run ->
+    [yes] Verrebbero ripresi
+   *[no] Ripresi
+} { $count ->
+    [one] 1 alias
+   *[other] { $count } alias
+}
+migrate-none = Niente di nuovo da migrare
+migrate-failed = Migrazione non riuscita: { $error }
+
+## export
+
+export-entry = Esportato { $id } - { $title }
+export-summary = ✓ { $count ->
+    [one] Esportato 1 gioco
+   *[other] Esportati { $count } giochi
+}
+export-none = Nessun gioco Epic installato da esportare
+export-failed = Esportazione non riuscita: { $error }
+
+## assets
+
+assets-none = Nessun asset di Unreal Engine in libreria
+assets-title = Asset di Unreal Engine:
+assets-list-failed = Impossibile elencare gli asset: { $error }
+assets-downloaded = ✓ Asset scaricato in { $path }
+assets-download-failed = Download dell'asset non riuscito: { $error }
+
+## logs
+
+logs-none = Ancora nessun log
+
+## history
+
+history-none = Nessuna installazione, aggiornamento o disinstallazione registrati
+history-title = Cronologia:
+history-install = installazione
+history-update = aggiornamento
+history-repair = riparazione
+history-uninstall = disinstallazione
+history-entry = { $date }  { $action } { $game } { $version }, { $duration }{ $downloaded ->
+    [yes] , { $size } scaricati
+   *[no] {""}
+}
+history-failed = ✗ Non riuscito: { $error }
+
+## stats
+
+stats-title = Statistiche
+stats-unknown = sconosciuto (offline o senza accesso)
+stats-owned = Giochi posseduti: { $count }
+stats-installed = Giochi installati: { $count } ({ $size })
+stats-playtime = Tempo di gioco totale: { $time }
+stats-most-played = Più giocati:
+stats-most-played-entry = { $rank }. { $title }: { $time } in { $sessions ->
+    [one] 1 sessione
+   *[other] { $sessions } sessioni
+}
+stats-downloads = Scaricati per mese:
+stats-downloads-entry = { $month }: { $size } ({ $total } in totale)
+
+## last-run
+
+last-run-none = Nessuna trascrizione; esegui un comando con --transcript o imposta transcripts = true nella configurazione
+
+## store
+
+store-title = Negozio (pagina { $page } di { $pages }, prezzi per { $country })
+store-stale = Epic non è raggiungibile; mostro gli ultimi risultati
+store-empty = Nessun gioco corrisponde
+store-giveaway = Gratis ora: { $title }
+store-offer = { $title } - { $price }
+store-offer-discounted = { $title } - { $price } (-{ $discount }%, prima { $original })
+store-offer-free = { $title } - Gratis
+store-offer-no-price = { $title } - prezzo non ancora disponibile
+store-opening = Apro { $title } nel browser: { $url }
+store-failed = Impossibile consultare il negozio: { $error }
+
+## wishlist
+
+wishlist-title = Lista dei desideri
+wishlist-empty = La lista dei desideri è vuota; aggiungi giochi con `rauncher wishlist add <titolo>`
+wishlist-item-owned = { $title } - già posseduto
+wishlist-added = { $title } aggiunto alla lista dei desideri
+wishlist-already = { $title } è già nella lista dei desideri
+wishlist-add-failed = Impossibile aggiungere alla lista dei desideri: { $error }
+wishlist-removed = { $title } rimosso dalla lista dei desideri
+wishlist-not-found = { $title } non è nella lista dei desideri
+wishlist-no-news = Nessun nuovo saldo o regalo nella lista dei desideri
+wishlist-on-sale = In saldo: { $title } è scontato del { $discount }%, { $price }
+wishlist-free = Gratis da riscattare: { $title }
+wishlist-failed = Impossibile controllare la lista dei desideri: { $error }
+
+## feed
+
+feed-rss-written = ✓ Feed RSS scritto in { $path }
+feed-ical-written = ✓ Calendario iCalendar scritto in { $path }
+feed-failed = Impossibile scrivere i feed: { $error }
+
+## disk-usage
+
+disk-usage-title = Spazio su disco
+disk-usage-root = { $path } ({ $free } liberi su { $capacity })
+disk-usage-root-unknown = { $path } (spazio libero sconosciuto)
+disk-usage-root-empty = Nessun gioco installato qui
+disk-usage-game = { $title } ({ $app }): { $total } (gioco { $install }, prefisso { $prefix }, cache shader { $shaders })
+disk-usage-shader-cache = Cache degli shader: { $size }
+disk-usage-partial = Download parziali: { $count } ({ $size })
+disk-usage-clean-hint = Esegui con --clean-shader-cache o --clean-partial per liberare { $size }
+disk-usage-cleaned-shaders = { $trash ->
+    [yes] ✓ Cache degli shader spostate nel cestino ({ $size }); svuotalo per liberare lo spazio
+   *[no] ✓ Cache degli shader rimosse, liberati { $size }
+}
+disk-usage-cleaned-partial = { $trash ->
+    [yes] ✓ Download parziali spostati nel cestino ({ $size }); svuotalo per liberare lo spazio
+   *[no] ✓ Download parziali rimossi, liberati { $size }
+}
+disk-usage-failed = Impossibile misurare lo spazio su disco: { $error }
+
+## shader-cache
+
+shader-cache-installed = ✓ Cache degli shader di { $app } installata ({ $size })
+shader-cache-up-to-date = La cache degli shader di { $app } è aggiornata
+shader-cache-unavailable = Non è ancora pubblicata una cache degli shader per { $app }
+shader-cache-failed = Impossibile aggiornare la cache degli shader: { $error }
+
+## runners
+
+runners-title = Runner installati
+runners-none = Nessun runner installato. Scaricane uno con `rauncher runners install`
+runners-line = { $name } ({ $kind }, { $version })
+runners-available-title = Release { $kind } disponibili
+runners-available-line = { $version } ({ $size }, { $date }){ $installed ->
+    [yes] {" "}- installata
+   *[no] {""}
+}
+runners-list-failed = Impossibile elencare le release dei runner: { $error }
+runners-downloading = Download di { $version } ({ $size })...
+runners-installed = ✓ Runner { $name } installato
+runners-use-hint = Selezionalo per un gioco con `rauncher config set games.<app_name>.runner { $name }`
+runners-install-failed = Impossibile installare il runner: { $error }
+runners-removed = { $trash ->
+    [yes] ✓ Runner { $name } spostato nel cestino
+   *[no] ✓ Runner { $name } rimosso
+}
+runners-remove-failed = Impossibile rimuovere il runner: { $error }
+
+## prefix
+
+prefix-created = ✓ Prefisso creato in { $path }
+prefix-exists = Il prefisso esiste già in { $path }
+prefix-exit = Il comando non è riuscito ({ $status })
+prefix-failed = Impossibile eseguire nel prefisso: { $error }
+
+## orphans
+
+orphans-failed = Impossibile cercare le cartelle orfane: { $error }
+orphans-none = Nessuna cartella di gioco orfana trovata
+orphans-title = Cartelle di gioco orfane:
+orphans-identified = { $path } ({ $size }): { $title } ({ $id }) { $version }, { $found }/{ $total } file corrispondono
+orphans-unidentified = { $path } ({ $size }): non è un gioco posseduto
+orphans-hint = Esegui con --adopt per registrare come installati i giochi riconosciuti, o con --delete per rimuovere le cartelle
+orphans-adopted = ✓ { $path } adottata come { $id }
+orphans-adopt-failed = Impossibile adottare { $path }: { $error }
+orphans-confirm = { $trash ->
+    [yes] Spostare nel cestino { $count ->
+        [one] questa cartella
+       *[other] queste { $count } cartelle
+    }?
+   *[no] Eliminare definitivamente { $count ->
+        [one] questa cartella
+       *[other] queste { $count } cartelle
+    }?
+}
+orphans-aborted = Nessuna cartella è stata eliminata
+orphans-deleted = { $trash ->
+    [yes] ✓ { $path } spostata nel cestino ({ $size })
+   *[no] ✓ { $path } eliminata, liberati { $size }
+}
+orphans-delete-failed = Impossibile eliminare { $path }: { $error }
+
+## config
+
+config-set = ✓ { $key } impostato a { $value }
+config-unset = ✓ { $key } riportato al valore predefinito
+config-not-set = { $key } non è impostato
+config-failed = Impossibile modificare la configurazione: { $error }
+config-flatpak-permission = rauncher è un Flatpak che non raggiunge ancora questa cartella; consenti l'accesso con: { $command }
+
+## daemon
+
+daemon-failed = Il demone si è interrotto: { $error }
+daemon-listening = Endpoint di stato in ascolto su { $url }
+daemon-feeds-served = Feed disponibili su { $rss } e { $ical }
+daemon-metrics-served = Metriche Prometheus servite su { $url }
+daemon-started = Demone avviato; aggiornamento dei giochi { $minutes ->
+    [one] ogni minuto
+   *[other] ogni { $minutes } minuti
+}
+daemon-stopping = Arresto in corso
+daemon-http-stopped = L'endpoint di stato si è fermato: { $error }
+daemon-no-sigterm = Impossibile ricevere SIGTERM: { $error }
+daemon-refresh-failed = Impossibile rinnovare l'accesso a { $store }: { $error }
+daemon-update-run-failed = Ciclo di aggiornamento non riuscito: { $error }
+daemon-feeds-failed = Impossibile aggiornare i feed: { $error }
+daemon-wishlist-failed = Impossibile controllare la lista dei desideri: { $error }
+
+## serve
+
+serve-listening = API in ascolto su { $url }
+serve-token-created = Token dell'API generato in { $path }
+serve-token-empty = RAUNCHER_SERVE_TOKEN o --token-file è vuoto; uso il token salvato
+serve-stopped = API arrestata
+serve-failed = Errore del server API: { $error }
+serve-record-failed = Impossibile registrare l'indirizzo dell'API per rauncher queue: { $error }
+
+## remote
+
+remote-needs-command = --host richiede un comando da eseguire e un token da --host-token-file o RAUNCHER_SERVE_TOKEN
+remote-unsupported = Questo comando non può essere eseguito con --host; usa list, install, update, launch o queue senza opzioni solo locali
+remote-disconnected = La connessione al rauncher remoto si è chiusa prima della fine dell'operazione
+remote-invalid-host = --host { $host } non valido: { $error }
+remote-no-host = non indica alcun host
+
+## queue
+
+queue-title = Coda dei download
+queue-empty = Nessuna installazione in coda.
+queue-no-serve = Nessun rauncher serve è in esecuzione su questa macchina; avviane uno o usa --host per raggiungerne un altro
+queue-job = { $app } - { $state ->
+    [queued] in coda
+    [active] download in corso
+    [paused] in pausa
+    [completed] completato
+    [cancelled] annullato
+   *[failed] non riuscito
+}, priorità { $priority ->
+    [high] alta
+    [low] bassa
+   *[normal] normale
+}
+queue-prioritized = { $app } spostato in cima alla coda con priorità alta
+queue-fetch-failed = Impossibile leggere la coda dei download: { $error }
+queue-prioritize-failed = Impossibile dare priorità a { $app }: { $error }
+
+## gui
+
+gui-failed = Impossibile avviare la GUI: { $error }
@@ -0,0 +1,271 @@
+# Etichette e messaggi di stato della GUI
+# I messaggi della barra di stato che iniziano con ✓ sono mostrati in verde, quelli con ✗ in rosso
+
+## header
+
+gui-title = R Games Launcher
+gui-logout = Esci
+gui-account = Account
+gui-add-account = Aggiungi account...
+gui-notifications = Notifiche
+gui-notify-install-finished = Installazioni completate
+gui-notify-update-available = Aggiornamenti disponibili
+gui-notify-cloud-sync-failed = Sincronizzazioni cloud non riuscite
+gui-notify-wishlist = Saldi e regali della lista dei desideri
+gui-theme = Tema
+gui-theme-failed = ✗ Impossibile caricare il tema: { $error }
+gui-unavailable-title = Impossibile avviare Rauncher
+gui-unavailable-hint = Correggi la configurazione in { $path } e riavvia Rauncher.
+gui-appearance = Aspetto
+gui-ui-scale = Scala dell'interfaccia
+gui-ui-scale-auto = Automatica ({ $percent }%)
+
+## login
+
+gui-store-banner = EPIC GAMES STORE
+gui-sign-in-subtitle = Accedi al tuo account
+gui-sign-in = Accedi con Epic Games
+gui-sign-in-hint = Fai clic sul pulsante qui sopra per autenticarti con Epic Games
+gui-sign-in-code-hint = Riceverai un codice da inserire nel browser
+gui-sign-in-web = Accedi invece tramite il sito
+gui-sign-in-web-hint = Per account con autenticazione a due fattori o captcha che l'accesso con codice non gestisce
+gui-auth-web-steps = Accedi dalla pagina aperta nel browser, poi incollaci il testo che Epic mostra:
+gui-auth-initializing = Avvio dell'autenticazione...
+gui-auth-in-progress = ⏳ Autenticazione in corso
+gui-auth-complete-in-browser = Completa l'autenticazione nel tuo browser:
+gui-auth-url = URL:
+gui-auth-code = Codice:
+gui-open-in-browser = 🌐 Apri nel browser
+gui-auth-waiting = In attesa che completi l'autenticazione... (tentativo { $attempt }/{ $max })
+gui-auth-start-failed = ✗ Impossibile avviare l'autenticazione: { $error }
+gui-auth-save-failed = ✗ Errore durante il salvataggio del token: { $error }
+gui-auth-success = ✓ Autenticazione con Epic Games riuscita!
+gui-auth-timed-out = ✗ Tempo scaduto per l'autenticazione. Riprova.
+gui-auth-failed = ✗ Autenticazione non riuscita: { $error }
+gui-auth-cancelled = Autenticazione annullata
+
+## library
+
+gui-library = Libreria
+gui-search-hint = Cerca giochi...
+gui-filter-all = Tutti i giochi
+gui-filter-installed = Installati
+gui-all-stores = Tutti gli store
+gui-no-games = Nessun gioco trovato
+gui-library-empty = La tua libreria è vuota o non ancora caricata
+gui-play = ▶ Gioca
+gui-stop = ■ Ferma
+gui-uninstall = Disinstalla
+gui-settings = Impostazioni
+gui-installing = ⏳ Installazione...
+gui-drive-not-mounted = Unità non montata
+gui-update-available = Aggiornamento disponibile
+gui-update-available-hint = È disponibile la versione { $version }; esegui 'rauncher update' per installarla
+gui-update-released = Pubblicata il { $date }
+gui-drive-not-mounted-hint = Il gioco è su un'unità rimovibile o di rete non collegata; si potrà giocare di nuovo appena l'unità torna disponibile
+gui-get = Ottieni
+gui-filter-favorites = ★ Preferiti
+gui-filter-hidden = Nascosti
+gui-favorite = Aggiungi ai preferiti
+gui-unfavorite = Rimuovi dai preferiti
+gui-game-menu = Altro
+gui-hide = Nascondi gioco
+gui-unhide = Mostra gioco
+gui-categories = Categorie
+gui-new-category = Nuova categoria
+gui-add-category = Aggiungi
+
+## game settings
+
+gui-settings-title = Impostazioni di { $app }
+gui-launch-wrappers = Wrapper di avvio
+gui-gamemode = Avvia con GameMode
+gui-mangohud = Mostra l'overlay di MangoHud
+gui-gamescope = Avvia in gamescope
+gui-gamescope-options = Opzioni di gamescope:
+gui-sandbox = Sandbox:
+gui-sandbox-none = Nessuna
+gui-sandbox-network = Consenti l'accesso alla rete nella sandbox
+gui-launch-args = Argomenti di avvio:
+gui-launch-executable = Eseguibile:
+gui-launch-executable-hint = Quello del gioco, o ad es. mods/loader.exe
+gui-launch-working-dir = Cartella di lavoro:
+gui-launch-working-dir-hint = La cartella di installazione
+gui-environment = Variabili d'ambiente
+gui-env-add = ➕ Aggiungi variabile
+gui-compatibility = Compatibilità
+gui-runner = Runner:
+gui-runner-none = Nessuno (percorso Wine/Proton sotto)
+gui-runner-hint = Scarica GE-Proton o Wine-GE con `rauncher runners install`
+gui-wine = Wine/Proton:
+gui-wine-hint = wine dal PATH
+gui-shader-cache-url = URL cache shader:
+gui-shader-cache-url-hint = Lo shader_cache_url globale
+gui-save = Salva
+gui-cancel = Annulla
+
+## status bar
+
+gui-clear = Cancella
+gui-error = ✗ Errore: { $error }
+gui-profile-switched = ✓ Passato al profilo { $name }
+gui-library-loading = Caricamento della libreria...
+gui-library-loaded = ✓ Libreria caricata
+gui-library-loaded-offline = Offline: la libreria mostrata è quella dell'ultimo aggiornamento
+gui-offline = ⚠ Offline
+gui-offline-hint = Gli store non sono raggiungibili. I giochi installati si possono avviare; installazioni e aggiornamenti richiedono una connessione.
+gui-library-failed = ✗ Impossibile caricare la libreria: { $error }
+gui-install-started = Installazione avviata per { $app }...
+gui-install-done = ✓ Installazione completata per { $app }
+gui-install-failed = ✗ Installazione fallita per { $app }: { $error }
+gui-launched = ✓ { $app } avviato
+gui-launch-failed = ✗ Impossibile avviare { $app }: { $error }
+gui-stopped = { $app } fermato
+gui-stop-failed = ✗ Impossibile fermare { $app }: { $error }
+gui-settings-saved = ✓ Impostazioni di { $app } salvate
+gui-settings-save-failed = ✗ Impossibile salvare le impostazioni: { $error }
+gui-collections-save-failed = ✗ Impossibile salvare categorie e preferiti: { $error }
+gui-uninstalled = { $trash ->
+    [yes] ✓ { $app } disinstallato; i suoi file sono nel cestino
+   *[no] ✓ { $app } disinstallato
+}
+gui-uninstall-failed = ✗ Impossibile disinstallare { $app }: { $error }
+gui-game-crashed = ✗ { $app } si è chiuso in modo anomalo
+gui-view-report = Vedi rapporto
+gui-install-cancelled = Installazione annullata per { $app }
+gui-launching = Avvio di { $app }...
+gui-stopping = Arresto di { $app }...
+gui-uninstall-started = Disinstallazione di { $app }...
+gui-toast-dismiss = Chiudi
+
+## questions
+
+gui-question = Domanda
+
+## crash report
+
+gui-crash-title = { $app } si è chiuso in modo anomalo
+gui-crash-output = Output del gioco
+gui-crash-saved = Rapporto completo: { $path }
+gui-close = Chiudi
+
+## downloads
+
+gui-downloads = Download
+gui-downloads-empty = Nessun download in coda. I giochi che installi compaiono qui.
+gui-clear-finished = Rimuovi completati
+gui-pause = Pausa
+gui-resume = Riprendi
+gui-move-up = Sposta su
+gui-move-down = Sposta giù
+gui-drag-to-reorder = Trascina per riordinare
+gui-prioritize = Scarica per primo, con priorità alta
+gui-download-queued = In coda
+gui-download-active = Download in corso
+gui-download-paused = In pausa
+gui-download-completed = ✓ Completato
+gui-download-failed = ✗ Non riuscito: { $error }
+gui-download-cancelled = Annullato
+gui-download-progress = { $files_done }/{ $files_total } file, { $done } di { $total }, { $speed }/s
+gui-download-eta = { $eta } rimanenti
+gui-metered-paused = Connessione a consumo: i download attendono un'altra connessione
+gui-metered-limited = Connessione a consumo: i download sono limitati a { $speed }/s
+gui-metered-allowed = Connessione a consumo: download comunque in corso
+gui-metered-allow = Scarica comunque
+gui-download-size = { $size } da scaricare
+gui-download-order = Ordine
+gui-order-manual = Come in elenco
+gui-order-smallest-first = Prima i più piccoli
+gui-order-priority = Per priorità
+gui-priority-low = Priorità bassa
+gui-priority-normal = Priorità normale
+gui-priority-high = Priorità alta
+
+## storage
+
+gui-storage = Spazio
+gui-storage-loading = Misurazione dello spazio su disco...
+gui-storage-failed = Impossibile misurare lo spazio su disco: { $error }
+gui-install-dir = Cartella di installazione: { $path }
+gui-install-dir-change = 📁 Cambia...
+gui-install-dir-saved = ✓ I nuovi giochi verranno installati in { $path }
+gui-flatpak-permission = Il sandbox Flatpak di rauncher non raggiunge { $path }. Consenti l'accesso con `{ $command }`, riavvia rauncher e sceglila di nuovo
+gui-flatpak-portal-path = Quella cartella non è condivisa con il sandbox Flatpak di rauncher. Consenti l'accesso con `{ $command }`, riavvia rauncher e sceglila di nuovo
+gui-storage-root = { $free } liberi su { $capacity }
+gui-storage-root-unknown = Spazio libero sconosciuto
+gui-storage-root-empty = Nessun gioco installato qui
+gui-storage-game-detail = gioco { $install } · prefisso { $prefix } · cache shader { $shaders }
+gui-storage-game-download = scaricati { $size }
+gui-storage-cleanup = Pulizia
+gui-storage-shader-cache = Cache degli shader: { $size }
+gui-storage-partial = Download parziali: { $count } ({ $size })
+gui-clean-shader-cache = Svuota le cache degli shader
+gui-clean-partial = Rimuovi i download parziali
+gui-storage-freed = ✓ { $size } spostati nel cestino; svuotalo per liberare lo spazio
+gui-refresh = Aggiorna
+gui-history = Cronologia
+gui-history-empty = Ancora nessuna installazione, aggiornamento o disinstallazione
+gui-history-search = Filtra per gioco
+gui-history-install = Installato
+gui-history-update = Aggiornato
+gui-history-repair = Riparato
+gui-history-uninstall = Disinstallato
+gui-history-downloaded = { $size } scaricati
+gui-history-failed = ✗ Non riuscito: { $error }
+gui-stats = Statistiche
+gui-stats-owned = Giochi posseduti
+gui-stats-installed = Giochi installati
+gui-stats-installed-size = Spazio occupato
+gui-stats-playtime = Tempo di gioco
+gui-stats-most-played = Più giocati
+gui-stats-no-sessions = Nessun tempo di gioco registrato. Conta i giochi avviati da qui o con launch --wait.
+gui-stats-played = { $time } in { $sessions ->
+    [one] 1 sessione
+   *[other] { $sessions } sessioni
+}
+gui-stats-downloads = Download nel tempo
+gui-stats-downloads-total = { $size } scaricati in totale
+gui-stats-no-downloads = Ancora niente scaricato
+gui-orphans = Cartelle orfane
+gui-orphans-hint = Cartelle di gioco di cui rauncher non ha traccia, ad esempio dopo averne perso i dati o copiato un gioco a mano
+gui-orphans-scan = Cerca
+gui-orphans-none = Nessuna cartella orfana trovata
+gui-orphans-failed = Impossibile cercare le cartelle orfane: { $error }
+gui-orphan-identified = { $title } { $version }, { $found }/{ $total } file corrispondono
+gui-orphan-unidentified = Non è un gioco posseduto
+gui-adopt = Adotta
+gui-delete = Elimina
+gui-orphan-adopted = ✓ { $path } adottata come { $app }
+
+## store
+
+gui-store = Negozio
+gui-store-search = Cerca nel negozio...
+gui-store-all-genres = Tutti i generi
+gui-store-free = Giochi gratuiti
+gui-store-loading = Caricamento del negozio...
+gui-store-failed = ✗ Impossibile caricare il negozio: { $error }
+gui-store-prices = Prezzi per { $country }
+gui-store-stale = Epic non è raggiungibile; questi sono gli ultimi risultati
+gui-store-page = Pagina { $page } di { $pages }
+gui-store-previous = ◀ Precedente
+gui-store-next = Successiva ▶
+gui-store-giveaways = Gratis ora
+gui-store-giveaway-until = Gratis fino al { $date }
+gui-store-claim = Riscatta nel browser
+gui-store-empty = Nessun gioco corrisponde
+gui-store-open = Apri nel browser per acquistare
+gui-store-owned = ✓ Posseduto
+gui-store-wishlist = ♡ Lista dei desideri
+gui-store-wishlisted = ♥ Nella lista dei desideri
+gui-store-no-price = Prezzo non ancora disponibile
+gui-store-price-free = Gratis
+
+## big picture
+
+gui-big-picture = Big Picture: modalità a schermo intero per TV e dispositivi portatili
+gui-big-picture-exit = Tieni premuto per l'interfaccia completa
+gui-big-picture-empty = Ancora nessun gioco installato
+gui-big-picture-hint = Tieni premuto un gioco per le sue impostazioni · Tieni premuto ⚙ per l'interfaccia completa
+gui-last-played = Ultima partita il { $date }
+gui-never-played = Mai giocato
@@ -0,0 +1,19 @@
+# Notifiche desktop mostrate dalla GUI e dal demone
+
+notify-install-finished = Installazione completata
+notify-install-finished-body = { $title } è pronto per giocare
+notify-update-available = Aggiornamento disponibile
+notify-update-available-body = { $title } può essere aggiornato alla versione { $version }
+notify-cloud-sync-failed = Sincronizzazione dei salvataggi cloud non riuscita
+notify-cloud-sync-failed-body = { $title }: { $error }
+notify-wishlist-sale = Gioco della lista dei desideri in saldo
+notify-wishlist-sale-body = { $title } è scontato del { $discount }%: { $price }
+notify-wishlist-free = Gioco della lista dei desideri gratis
+notify-wishlist-free-body = { $title } si può riscattare gratis questa settimana
+
+# Messaggi inviati ai webhook nel formato di Discord
+
+webhook-update-installed = { $title } è stato aggiornato dalla versione { $from } alla { $version }
+webhook-download-failed = Impossibile aggiornare { $title }: { $error }
+webhook-update-run-failed = Aggiornamento automatico non riuscito: { $error }
+webhook-free-game-claimed = { $title } è stato riscattato gratis
@@ -0,0 +1,10 @@
+# Domande poste durante un'operazione, dalla CLI nel terminale e dalla GUI in una finestra
+
+prompt-save-conflict = Il salvataggio cloud { $file } di { $title } è diverso dal salvataggio locale. Quale vuoi tenere?
+prompt-overwrite-install = { $path } contiene già dei file. Installare { $title } sovrascrivendoli?
+prompt-answer-keep-local = Tieni il salvataggio locale
+prompt-answer-use-cloud = Usa il salvataggio cloud
+prompt-answer-cancel = Annulla
+prompt-answer-overwrite = Sovrascrivi
+prompt-answer-no = No
+prompt-answer-yes = Sì
@@ -0,0 +1,154 @@
+//! Library metadata kept between runs, so a refresh only asks the catalog about games whose
+//! build changed since the last sync. Epic's asset list is the index: it is fetched on every
+//! refresh and compared against the build each cached entry was resolved for.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::Result;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct LibraryCache {
+    /// Catalog details by app name
+    entries: BTreeMap<String, CachedEntry>,
+    synced_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedEntry {
+    /// Build the entry was resolved for
+    pub(crate) build_version: String,
+    pub(crate) title: String,
+}
+
+impl LibraryCache {
+    /// The cache of an account; a missing or unreadable cache starts empty
+    pub(crate) fn load(account_id: &str) -> Self {
+        match Self::path(account_id) {
+            Ok(path) => Self::load_from(&path),
+            Err(e) => {
+                log::debug!("No library cache location: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return Self::default(),
+        };
+
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            log::debug!("Ignoring unreadable library cache {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    pub(crate) fn save(&mut self, account_id: &str) -> Result<()> {
+        self.save_to(&Self::path(account_id)?)
+    }
+
+    fn save_to(&mut self, path: &Path) -> Result<()> {
+        self.synced_at = Some(Utc::now());
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        crate::logs::transcript::touched("wrote", path);
+
+        Ok(())
+    }
+
+    fn path(account_id: &str) -> Result<PathBuf> {
+        Ok(Config::data_dir()?
+            .join("cache")
+            .join(format!("library_{}.json", account_id)))
+    }
+
+    /// The cached details of a game, if they were resolved for its current build. With the
+    /// build unknown any cached details are taken as current.
+    pub(crate) fn current(
+        &self,
+        app_name: &str,
+        build_version: Option<&str>,
+    ) -> Option<&CachedEntry> {
+        self.entries
+            .get(app_name)
+            .filter(|entry| build_version.is_none_or(|build| entry.build_version == build))
+    }
+
+    pub(crate) fn insert(&mut self, app_name: &str, build_version: &str, title: &str) {
+        self.entries.insert(
+            app_name.to_string(),
+            CachedEntry {
+                build_version: build_version.to_string(),
+                title: title.to_string(),
+            },
+        );
+    }
+
+    /// Drop games that left the library
+    pub(crate) fn retain<'a>(&mut self, app_names: impl IntoIterator<Item = &'a str>) {
+        let owned: std::collections::HashSet<&str> = app_names.into_iter().collect();
+        self.entries
+            .retain(|app_name, _| owned.contains(app_name.as_str()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_are_stale_once_the_build_changes() {
+        let mut cache = LibraryCache::default();
+        cache.insert("Fortnite", "1.0", "Fortnite");
+        cache.insert("Kiwi", "2.0", "Hades II");
+
+        let title = |cache: &LibraryCache, app_name, build| {
+            cache
+                .current(app_name, build)
+                .map(|entry| entry.title.clone())
+        };
+
+        assert_eq!(
+            title(&cache, "Fortnite", Some("1.0")).as_deref(),
+            Some("Fortnite")
+        );
+        assert_eq!(title(&cache, "Fortnite", Some("1.1")), None);
+        assert_eq!(title(&cache, "Fortnite", None).as_deref(), Some("Fortnite"));
+        assert_eq!(title(&cache, "Celeste", None), None);
+
+        cache.retain(["Kiwi"]);
+        assert_eq!(title(&cache, "Fortnite", None), None);
+        assert_eq!(
+            title(&cache, "Kiwi", Some("2.0")).as_deref(),
+            Some("Hades II")
+        );
+    }
+
+    #[test]
+    fn test_cache_round_trips_and_tolerates_garbage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("library.json");
+
+        let mut cache = LibraryCache::default();
+        cache.insert("Kiwi", "2.0", "Hades II");
+        cache.save_to(&path).unwrap();
+
+        let loaded = LibraryCache::load_from(&path);
+        assert!(loaded.current("Kiwi", Some("2.0")).is_some());
+        assert!(loaded.synced_at.is_some());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(LibraryCache::load_from(&path)
+            .current("Kiwi", None)
+            .is_none());
+    }
+}
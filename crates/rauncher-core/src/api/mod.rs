@@ -0,0 +1,2527 @@
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::auth::AuthToken;
+use crate::config::EpicSettings;
+use crate::logs::transcript::RecordedSend;
+use crate::{Error, Result};
+
+mod library_cache;
+mod store;
+mod throttle;
+use library_cache::LibraryCache;
+pub(crate) use store::StoreCache;
+pub use store::{StoreOffer, StorePage, StorePrice, StoreQuery, STORE_GENRES, STORE_PAGE_SIZE};
+use throttle::{InFlight, LimitedSend, RateLimiter};
+
+// Request timeout configuration
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+// Epic Games Store API endpoints
+const OAUTH_TOKEN_URL: &str =
+    "https://account-public-service-prod.ol.epicgames.com/account/api/oauth/token";
+const DEVICE_AUTH_URL: &str =
+    "https://account-public-service-prod.ol.epicgames.com/account/api/oauth/deviceAuthorization";
+const LIBRARY_API_URL: &str =
+    "https://library-service.live.use1a.on.epicgames.com/library/api/public";
+const LAUNCHER_API_URL: &str =
+    "https://launcher-public-service-prod.ol.epicgames.com/launcher/api/public";
+const CATALOG_API_URL: &str =
+    "https://catalog-public-service-prod06.ol.epicgames.com/catalog/api/shared";
+const ACCOUNT_API_URL: &str =
+    "https://account-public-service-prod.ol.epicgames.com/account/api/public/account";
+const VERIFY_TOKEN_URL: &str =
+    "https://account-public-service-prod.ol.epicgames.com/account/api/oauth/verify";
+const ENTITLEMENT_API_URL: &str =
+    "https://entitlement-public-service-prod08.ol.epicgames.com/entitlement/api/account";
+const SAVESYNC_API_URL: &str = concat!(
+    "https://datastorage-public-service-liveegs.live.use1a.on.epicgames.com",
+    "/api/v1/access/egstore/savesync"
+);
+const FREE_GAMES_URL: &str =
+    "https://store-site-backend-static.ak.epicgames.com/freeGamesPromotions";
+const STORE_PRODUCT_URL: &str = "https://store.epicgames.com/p";
+const WEB_LOGIN_URL: &str = "https://www.epicgames.com/id/login";
+const WEB_LOGIN_REDIRECT_URL: &str = "https://www.epicgames.com/id/api/redirect";
+
+/// Epic services rauncher relies on, by name, checked by `rauncher doctor`
+pub(crate) const SERVICES: [(&str, &str); 4] = [
+    ("accounts", ACCOUNT_API_URL),
+    ("launcher", LAUNCHER_API_URL),
+    ("library", LIBRARY_API_URL),
+    ("catalog", CATALOG_API_URL),
+];
+
+// Library namespace Epic uses for Unreal Engine marketplace assets
+const UE_NAMESPACE: &str = "ue";
+
+// Asset labels that make up a game's build index, newest first
+const BUILD_LABELS: [&str; 2] = ["Live", "Archive"];
+
+// Epic error codes returned while the user hasn't finished the device login yet
+const PENDING_AUTH_CODES: [&str; 2] = [
+    "errors.com.epicgames.account.oauth.authorization_pending",
+    "errors.com.epicgames.account.oauth.slow_down",
+];
+
+// Epic error codes meaning the stored session can't be used anymore
+const REAUTH_CODES: [&str; 3] = [
+    "errors.com.epicgames.account.oauth.expired",
+    "errors.com.epicgames.account.auth_token.invalid_refresh_token",
+    "errors.com.epicgames.account.auth_token.unknown_oauth_session",
+];
+
+// Epic error codes for an access token turned down, which a refresh may fix
+const TOKEN_REJECTED_CODES: [&str; 2] = [
+    "errors.com.epicgames.common.oauth.invalid_token",
+    "errors.com.epicgames.common.authentication.token_verification_failed",
+];
+
+// Catalog requests in flight at once while resolving library titles
+const CATALOG_CONCURRENCY: usize = 16;
+
+// Entitlements fetched per request; the endpoint pages through larger accounts
+const ENTITLEMENTS_PAGE_SIZE: usize = 1000;
+
+// Epic Games launcher client credentials, used unless configured otherwise
+const CLIENT_ID: &str = "34a02cf8f4414e29b15921876da36f9a";
+const CLIENT_SECRET: &str = "daafbccc737745039dffe53d94fc76cf";
+const USER_AGENT: &str = "rauncher/0.1.0";
+const PLATFORM: &str = "Windows";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    pub app_name: String,
+    pub app_title: String,
+    pub app_version: String,
+    pub install_path: Option<String>,
+    /// Store the game is owned on
+    #[serde(default = "crate::providers::default_provider")]
+    pub provider: String,
+}
+
+impl Game {
+    /// Identifier that stays the same across listings and stores
+    pub fn id(&self) -> String {
+        crate::providers::qualified_id(&self.provider, &self.app_name)
+    }
+}
+
+/// An Unreal Engine marketplace asset owned by the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UeAsset {
+    pub app_name: String,
+    pub catalog_item_id: String,
+}
+
+/// Structured error body returned by Epic services
+#[derive(Debug, Deserialize)]
+struct EpicErrorResponse {
+    #[serde(rename = "errorCode")]
+    error_code: String,
+    #[serde(rename = "errorMessage", default)]
+    error_message: String,
+}
+
+impl EpicErrorResponse {
+    fn parse(body: &str) -> Option<Self> {
+        serde_json::from_str(body).ok()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OAuthTokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: i64,
+    account_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceAuthResponse {
+    pub verification_uri_complete: String,
+    pub user_code: String,
+    pub device_code: String,
+    pub expires_in: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LibraryResponse {
+    records: Vec<LibraryItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LibraryItem {
+    #[serde(rename = "appName")]
+    app_name: String,
+    namespace: String,
+    #[serde(rename = "catalogItemId")]
+    catalog_item_id: String,
+}
+
+/// The library records update checks look release details up in, fetched by the first check
+/// that finds an update and shared by the checks after it
+pub type ReleaseLookup = tokio::sync::OnceCell<LibraryRecords>;
+
+/// The account's raw library records
+#[derive(Debug, Clone)]
+pub struct LibraryRecords(Vec<LibraryItem>);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetResponse {
+    id: String,
+    #[serde(rename = "appName")]
+    app_name: String,
+    label_name: String,
+    #[serde(rename = "buildVersion", default)]
+    build_version: String,
+    #[serde(default)]
+    namespace: String,
+    #[serde(rename = "catalogItemId", default)]
+    catalog_item_id: String,
+    metadata: AssetMetadata,
+}
+
+/// Where the manifest of a game's latest build can be downloaded from
+#[derive(Debug, Deserialize)]
+struct ManifestLocations {
+    elements: Vec<ManifestElement>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestElement {
+    /// SHA-1 of the manifest file, in hex
+    #[serde(default)]
+    hash: String,
+    /// Mirrors of the same file
+    manifests: Vec<ManifestUri>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestUri {
+    uri: String,
+    /// Signing parameters some CDNs need in the query string
+    #[serde(default)]
+    query_params: Vec<QueryParam>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QueryParam {
+    name: String,
+    value: String,
+}
+
+/// Where the chunks of a game's build are on the CDN: next to its manifest, on any of the
+/// mirrors it was listed on
+struct ChunkSource {
+    /// Directories the manifest was listed in, with the query string the mirror needs
+    mirrors: Vec<(String, String)>,
+    /// Picks the chunk directory
+    manifest_version: u32,
+    /// Group and rolling hash of each chunk by GUID, which make up its file name
+    chunks: HashMap<String, (u8, u64)>,
+}
+
+impl ChunkSource {
+    fn new(manifest_urls: &[String], manifest: &crate::manifest::Manifest) -> Self {
+        let mirrors = manifest_urls
+            .iter()
+            .map(|url| {
+                let (path, query) = url.split_once('?').unwrap_or((url, ""));
+                let dir = path.rsplit_once('/').map_or(path, |(dir, _)| dir);
+                (dir.to_string(), query.to_string())
+            })
+            .collect();
+        let chunks = manifest
+            .chunks
+            .iter()
+            .map(|chunk| (chunk.guid.to_string(), (chunk.group, chunk.hash)))
+            .collect();
+        Self {
+            mirrors,
+            manifest_version: manifest.version,
+            chunks,
+        }
+    }
+
+    /// URLs of a chunk, one per mirror
+    fn urls(&self, guid: &str) -> Result<Vec<String>> {
+        let (group, hash) = self
+            .chunks
+            .get(guid)
+            .ok_or_else(|| Error::Api(format!("Chunk {} isn't in the manifest", guid)))?;
+        let path =
+            crate::manifest::chunk::path(self.manifest_version, *group, *hash, &guid.parse()?);
+        Ok(self
+            .mirrors
+            .iter()
+            .map(|(dir, query)| match query.as_str() {
+                "" => format!("{}/{}", dir, path),
+                query => format!("{}/{}?{}", dir, path, query),
+            })
+            .collect())
+    }
+}
+
+impl ManifestUri {
+    fn url(&self) -> String {
+        if self.query_params.is_empty() {
+            return self.uri.clone();
+        }
+        let query: Vec<String> = self
+            .query_params
+            .iter()
+            .map(|param| format!("{}={}", param.name, param.value))
+            .collect();
+        format!("{}?{}", self.uri, query.join("&"))
+    }
+}
+
+/// A single build of a game as listed in Epic's build index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildVersion {
+    pub build_version: String,
+    pub label: String,
+    pub asset_id: String,
+}
+
+/// A newer build of an installed game, with what the store says about it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    /// When the build was released, if the store says
+    pub date: Option<DateTime<Utc>>,
+    /// What changed, where the publisher wrote it down
+    pub release_notes: Option<String>,
+}
+
+impl BuildInfo {
+    /// A build known by its version only
+    pub fn version(version: String) -> Self {
+        Self {
+            version,
+            date: None,
+            release_notes: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetMetadata {
+    #[serde(rename = "applicationId")]
+    application_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(dead_code)]
+struct CatalogItem {
+    id: String,
+    title: String,
+    #[serde(rename = "currentVersion")]
+    current_version: Option<String>,
+    #[serde(rename = "countriesBlacklist", default)]
+    countries_blacklist: Vec<String>,
+    #[serde(rename = "countriesWhitelist", default)]
+    countries_whitelist: Vec<String>,
+    /// Releases of the item's apps, per platform
+    #[serde(rename = "releaseInfo", default)]
+    release_info: Vec<ReleaseInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseInfo {
+    app_id: String,
+    #[serde(default)]
+    platform: Vec<String>,
+    #[serde(default)]
+    date_added: Option<DateTime<Utc>>,
+    /// Rarely filled in; most publishers post patch notes elsewhere
+    #[serde(default)]
+    release_note: Option<String>,
+}
+
+impl CatalogItem {
+    /// Explain why the item can't be used from `country`, if it can't
+    fn region_restriction(&self, country: &str) -> Option<String> {
+        let blacklisted = self
+            .countries_blacklist
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(country));
+        let not_whitelisted = !self.countries_whitelist.is_empty()
+            && !self
+                .countries_whitelist
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(country));
+
+        (blacklisted || not_whitelisted).then(|| {
+            format!(
+                "{} cannot be downloaded from your account's country ({})",
+                self.title, country
+            )
+        })
+    }
+
+    /// The latest release of `app_name` on `platform`
+    fn latest_release(&self, app_name: &str, platform: &str) -> Option<&ReleaseInfo> {
+        self.release_info
+            .iter()
+            .filter(|release| release.app_id.eq_ignore_ascii_case(app_name))
+            .filter(|release| {
+                release.platform.is_empty()
+                    || release
+                        .platform
+                        .iter()
+                        .any(|p| p.eq_ignore_ascii_case(platform))
+            })
+            .max_by_key(|release| release.date_added)
+    }
+}
+
+/// What Epic knows about the session an access token belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub account_id: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default)]
+    pub client_id: String,
+    /// Permissions granted to the token
+    #[serde(default, rename = "scope")]
+    pub scopes: Vec<String>,
+}
+
+/// A raw record of something the account owns, as the entitlement service reports it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entitlement {
+    pub id: String,
+    pub entitlement_name: String,
+    pub namespace: String,
+    pub catalog_item_id: String,
+    #[serde(default)]
+    pub entitlement_type: String,
+    #[serde(default)]
+    pub grant_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountInfo {
+    #[serde(default)]
+    country: Option<String>,
+}
+
+/// A game Epic gives away, now or in an announced upcoming promotion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreeGame {
+    pub title: String,
+    /// Store page, when the promotion names one
+    pub url: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FreeGamesResponse {
+    data: FreeGamesData,
+}
+
+#[derive(Debug, Deserialize)]
+struct FreeGamesData {
+    #[serde(rename = "Catalog")]
+    catalog: FreeGamesCatalog,
+}
+
+#[derive(Debug, Deserialize)]
+struct FreeGamesCatalog {
+    #[serde(rename = "searchStore")]
+    search_store: FreeGamesSearch,
+}
+
+#[derive(Debug, Deserialize)]
+struct FreeGamesSearch {
+    elements: Vec<PromotedOffer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromotedOffer {
+    title: String,
+    #[serde(rename = "productSlug", default)]
+    product_slug: Option<String>,
+    #[serde(rename = "offerMappings", default)]
+    offer_mappings: Option<Vec<PageMapping>>,
+    #[serde(default)]
+    promotions: Option<Promotions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageMapping {
+    #[serde(rename = "pageSlug")]
+    page_slug: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Promotions {
+    #[serde(rename = "promotionalOffers", default)]
+    current: Vec<PromotionGroup>,
+    #[serde(rename = "upcomingPromotionalOffers", default)]
+    upcoming: Vec<PromotionGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromotionGroup {
+    #[serde(rename = "promotionalOffers", default)]
+    offers: Vec<Promotion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Promotion {
+    #[serde(rename = "startDate")]
+    start_date: DateTime<Utc>,
+    #[serde(rename = "endDate")]
+    end_date: DateTime<Utc>,
+    #[serde(rename = "discountSetting")]
+    discount: DiscountSetting,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscountSetting {
+    #[serde(rename = "discountPercentage")]
+    percentage: u32,
+}
+
+/// Store page of an offer, from its product slug or else its first page mapping
+fn store_url(product_slug: Option<&str>, mappings: Option<&[PageMapping]>) -> Option<String> {
+    let slug = product_slug
+        .filter(|slug| !slug.is_empty() && *slug != "[]")
+        .or_else(|| {
+            mappings
+                .and_then(|mappings| mappings.first())
+                .map(|mapping| mapping.page_slug.as_str())
+        })?;
+
+    // Slugs sometimes carry a "/home" suffix that the store URL doesn't use
+    let slug = slug.trim_end_matches("/home");
+    Some(format!("{}/{}", STORE_PRODUCT_URL, slug))
+}
+
+impl PromotedOffer {
+    fn store_url(&self) -> Option<String> {
+        store_url(self.product_slug.as_deref(), self.offer_mappings.as_deref())
+    }
+
+    /// Promotions that make the game free; a 0% "discount percentage" is Epic's way of saying
+    /// the price is zero
+    fn free_games(&self) -> Vec<FreeGame> {
+        let Some(promotions) = &self.promotions else {
+            return vec![];
+        };
+
+        promotions
+            .current
+            .iter()
+            .chain(&promotions.upcoming)
+            .flat_map(|group| &group.offers)
+            .filter(|offer| offer.discount.percentage == 0)
+            .map(|offer| FreeGame {
+                title: self.title.clone(),
+                url: self.store_url(),
+                starts_at: offer.start_date,
+                ends_at: offer.end_date,
+            })
+            .collect()
+    }
+}
+
+// Manifest structures for Epic Games manifest format
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameManifest {
+    #[serde(rename = "ManifestFileVersion")]
+    pub manifest_file_version: String,
+    #[serde(rename = "bIsFileData")]
+    pub is_file_data: bool,
+    #[serde(rename = "AppNameString")]
+    pub app_name: String,
+    #[serde(rename = "AppVersionString")]
+    pub app_version: String,
+    #[serde(rename = "LaunchExeString")]
+    pub launch_exe: String,
+    #[serde(rename = "LaunchCommand")]
+    pub launch_command: String,
+    #[serde(rename = "BuildSizeInt")]
+    pub build_size: u64,
+    #[serde(rename = "FileManifestList")]
+    pub file_list: Vec<FileManifest>,
+    #[serde(rename = "ChunkHashList")]
+    pub chunk_hash_list: std::collections::BTreeMap<String, String>,
+    #[serde(rename = "ChunkShaList")]
+    pub chunk_sha_list: std::collections::BTreeMap<String, Vec<u8>>,
+    #[serde(rename = "DataGroupList")]
+    pub data_group_list: std::collections::BTreeMap<String, Vec<String>>,
+    /// Size of each chunk as downloaded, compressed; chunks not listed are downloaded at the
+    /// size of the data they hold
+    #[serde(rename = "ChunkFilesizeList", default)]
+    pub chunk_filesize_list: std::collections::BTreeMap<String, u64>,
+    /// Free-form metadata, such as where the game keeps its saves
+    #[serde(rename = "CustomFields", default)]
+    pub custom_fields: std::collections::BTreeMap<String, String>,
+    /// Redistributables the game needs installed on Windows
+    #[serde(
+        rename = "Prerequisite",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub prerequisite: Option<Prerequisite>,
+}
+
+impl GameManifest {
+    /// Bytes downloaded for the whole build
+    pub fn download_size(&self) -> u64 {
+        self.download_size_of(&self.file_list)
+    }
+
+    /// Bytes downloaded for `files`, each chunk they're assembled from counted once
+    pub fn download_size_of<'a>(&self, files: impl IntoIterator<Item = &'a FileManifest>) -> u64 {
+        let mut seen = std::collections::HashSet::new();
+        let mut size = 0;
+        for part in files.into_iter().flat_map(|file| &file.file_chunk_parts) {
+            match self.chunk_filesize_list.get(&part.guid) {
+                Some(chunk_size) => {
+                    if seen.insert(&part.guid) {
+                        size += chunk_size;
+                    }
+                }
+                None => size += part.size,
+            }
+        }
+        size
+    }
+}
+
+/// An installer shipped with the game, e.g. the Visual C++ runtime, run once after install
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Prerequisite {
+    /// Identifies what it installs, so games sharing it don't run it again
+    #[serde(rename = "PrereqIds")]
+    pub ids: Vec<String>,
+    #[serde(rename = "PrereqName")]
+    pub name: String,
+    /// Installer, relative to the install directory
+    #[serde(rename = "PrereqPath")]
+    pub path: String,
+    #[serde(rename = "PrereqArgs")]
+    pub args: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileManifest {
+    #[serde(rename = "Filename")]
+    pub filename: String,
+    #[serde(rename = "FileHash")]
+    pub file_hash: Vec<u8>,
+    #[serde(rename = "FileChunkParts")]
+    pub file_chunk_parts: Vec<ChunkPart>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkPart {
+    #[serde(rename = "Guid")]
+    pub guid: String,
+    #[serde(rename = "Offset")]
+    pub offset: u64,
+    #[serde(rename = "Size")]
+    pub size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    pub total_bytes: u64,
+    pub downloaded_bytes: u64,
+    pub total_files: usize,
+    pub downloaded_files: usize,
+    pub current_file: String,
+}
+
+/// Clones share the connection pool, the request budget and the requests in flight
+#[derive(Clone)]
+pub struct EpicClient {
+    client: Client,
+    limiter: Arc<RateLimiter>,
+    /// Catalog lookups running, by namespace, item and country
+    catalog_requests: Arc<InFlight<Option<CatalogItem>>>,
+    identity: Arc<Identity>,
+    /// Where the chunks of each game's last downloaded manifest are, by app name
+    chunk_sources: Arc<Mutex<HashMap<String, Arc<ChunkSource>>>>,
+}
+
+/// The OAuth client and platform requests are made as
+struct Identity {
+    client_id: String,
+    client_secret: String,
+    platform: String,
+}
+
+impl EpicClient {
+    pub fn new() -> Result<Self> {
+        Self::with_settings(&EpicSettings::default())
+    }
+
+    /// A client presenting itself as configured, with Epic's launcher filling in the rest
+    pub fn with_settings(settings: &EpicSettings) -> Result<Self> {
+        settings.validate()?;
+        let (client_id, client_secret) = match (&settings.client_id, &settings.client_secret) {
+            (Some(id), Some(secret)) => (id.clone(), secret.clone()),
+            _ => (CLIENT_ID.to_string(), CLIENT_SECRET.to_string()),
+        };
+
+        let client = Client::builder()
+            .user_agent(settings.user_agent.as_deref().unwrap_or(USER_AGENT))
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            limiter: Arc::new(RateLimiter::new()),
+            catalog_requests: Arc::new(InFlight::new()),
+            chunk_sources: Arc::default(),
+            identity: Arc::new(Identity {
+                client_id,
+                client_secret,
+                platform: settings
+                    .platform
+                    .clone()
+                    .unwrap_or_else(|| PLATFORM.to_string()),
+            }),
+        })
+    }
+
+    /// Whether `url` can be reached at all; any HTTP response counts, errors included
+    pub(crate) async fn ping(&self, url: &str) -> Result<()> {
+        self.client.get(url).send_recorded().await?;
+        Ok(())
+    }
+
+    /// Request device authorization (Step 1 of OAuth device flow)
+    pub async fn request_device_auth(&self) -> Result<DeviceAuthResponse> {
+        log::info!("Requesting device authorization from Epic Games");
+
+        let device_auth_response = self
+            .client
+            .post(DEVICE_AUTH_URL)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .basic_auth(&self.identity.client_id, Some(&self.identity.client_secret))
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !device_auth_response.status().is_success() {
+            let status = device_auth_response.status();
+            let error_text = device_auth_response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to request device authorization",
+                Error::Auth,
+            ));
+        }
+
+        let device_auth: DeviceAuthResponse = device_auth_response.json().await?;
+
+        log::debug!(
+            "Device code received. Verification URL: {}",
+            device_auth.verification_uri_complete
+        );
+
+        Ok(device_auth)
+    }
+
+    /// Poll for token using device code (Step 2 of OAuth device flow)
+    pub async fn poll_for_token(&self, device_code: &str) -> Result<Option<AuthToken>> {
+        let response = self
+            .client
+            .post(OAUTH_TOKEN_URL)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .basic_auth(&self.identity.client_id, Some(&self.identity.client_secret))
+            .form(&[("grant_type", "device_code"), ("device_code", device_code)])
+            .send_limited(&self.limiter)
+            .await?;
+
+        if response.status().is_success() {
+            let oauth_response: OAuthTokenResponse = response.json().await?;
+
+            log::info!("Successfully authenticated with Epic Games");
+
+            let token = AuthToken {
+                access_token: oauth_response.access_token,
+                refresh_token: oauth_response.refresh_token,
+                expires_at: chrono::Utc::now()
+                    + chrono::Duration::seconds(oauth_response.expires_in),
+                account_id: oauth_response.account_id,
+            };
+
+            return Ok(Some(token));
+        }
+
+        // Check if we got an error that means we should continue polling
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        if status == 400 {
+            let code = EpicErrorResponse::parse(&error_text).map(|e| e.error_code);
+            if code
+                .as_deref()
+                .is_none_or(|c| PENDING_AUTH_CODES.contains(&c))
+            {
+                // This is expected while waiting for user to authenticate
+                log::debug!("Still waiting for user authentication...");
+                return Ok(None);
+            }
+        }
+
+        // Any other error should be reported
+        Err(error_from_response(
+            status,
+            &error_text,
+            "Authentication failed",
+            Error::Auth,
+        ))
+    }
+
+    /// Authenticate with Epic Games using device code flow (combined method for CLI)
+    pub async fn authenticate(&self) -> Result<(String, String, AuthToken)> {
+        // Step 1: Request device authorization
+        let device_auth = self.request_device_auth().await?;
+
+        let device_code = device_auth.device_code.clone();
+        let user_code = device_auth.user_code.clone();
+        let verification_url = device_auth.verification_uri_complete.clone();
+
+        // Step 2: Poll for token
+        // Poll every 5 seconds for up to 10 minutes
+        let max_attempts = 120; // 10 minutes
+        let poll_interval = Duration::from_secs(5);
+
+        for attempt in 0..max_attempts {
+            if attempt > 0 {
+                tokio::time::sleep(poll_interval).await;
+            }
+
+            log::debug!(
+                "Polling for token (attempt {}/{})",
+                attempt + 1,
+                max_attempts
+            );
+
+            if let Some(token) = self.poll_for_token(&device_code).await? {
+                return Ok((user_code, verification_url, token));
+            }
+        }
+
+        Err(Error::Auth(
+            "Authentication timed out. Please try again.".to_string(),
+        ))
+    }
+
+    /// Refresh an expired access token
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken> {
+        log::info!("Refreshing access token");
+
+        let response = self
+            .client
+            .post(OAUTH_TOKEN_URL)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .basic_auth(&self.identity.client_id, Some(&self.identity.client_secret))
+            .form(&[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+            ])
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to refresh token",
+                Error::Auth,
+            ));
+        }
+
+        let oauth_response: OAuthTokenResponse = response.json().await?;
+
+        log::info!("Successfully refreshed access token");
+
+        Ok(AuthToken {
+            access_token: oauth_response.access_token,
+            refresh_token: oauth_response.refresh_token,
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(oauth_response.expires_in),
+            account_id: oauth_response.account_id,
+        })
+    }
+
+    /// Epic's web login, which lands on a page showing an authorization code for this client.
+    /// Unlike the device flow it handles every two-factor and captcha challenge.
+    pub fn web_login_url(&self) -> String {
+        let mut redirect = reqwest::Url::parse(WEB_LOGIN_REDIRECT_URL).expect("valid URL");
+        redirect
+            .query_pairs_mut()
+            .append_pair("clientId", &self.identity.client_id)
+            .append_pair("responseType", "code");
+
+        let mut url = reqwest::Url::parse(WEB_LOGIN_URL).expect("valid URL");
+        url.query_pairs_mut()
+            .append_pair("redirectUrl", redirect.as_str());
+        url.into()
+    }
+
+    /// Exchange the authorization code from a web login for a token. Accepts anything
+    /// `authorization_code_from` understands, e.g. the whole redirect page.
+    pub async fn exchange_code(&self, code: &str) -> Result<AuthToken> {
+        let code = crate::auth::authorization_code_from(code)
+            .ok_or_else(|| Error::Auth("No authorization code found".to_string()))?;
+
+        let response = self
+            .client
+            .post(OAUTH_TOKEN_URL)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .basic_auth(&self.identity.client_id, Some(&self.identity.client_secret))
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code.as_str()),
+                ("token_type", "eg1"),
+            ])
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to exchange authorization code",
+                Error::Auth,
+            ));
+        }
+
+        let oauth_response: OAuthTokenResponse = response.json().await?;
+
+        Ok(AuthToken {
+            access_token: oauth_response.access_token,
+            refresh_token: oauth_response.refresh_token,
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(oauth_response.expires_in),
+            account_id: oauth_response.account_id,
+        })
+    }
+
+    /// Fetch the raw library records, games and marketplace assets alike
+    async fn get_library_items(&self, token: &AuthToken) -> Result<Vec<LibraryItem>> {
+        let library_url = format!("{}/users/{}/items", LIBRARY_API_URL, token.account_id);
+
+        let response = self
+            .client
+            .get(&library_url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to fetch library",
+                Error::Api,
+            ));
+        }
+
+        let library_response: LibraryResponse = response.json().await?;
+
+        log::debug!("Found {} items in library", library_response.records.len());
+
+        Ok(library_response.records)
+    }
+
+    /// Current and upcoming free game promotions for `country`, soonest first.
+    /// The promotions API is public, so no login is needed.
+    pub async fn get_free_games(&self, country: &str) -> Result<Vec<FreeGame>> {
+        let response = self
+            .client
+            .get(FREE_GAMES_URL)
+            .query(&[
+                ("locale", "en-US"),
+                ("country", country),
+                ("allowCountries", country),
+            ])
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to fetch free games",
+                Error::Api,
+            ));
+        }
+
+        let promotions: FreeGamesResponse = response.json().await?;
+        let mut games: Vec<FreeGame> = promotions
+            .data
+            .catalog
+            .search_store
+            .elements
+            .iter()
+            .flat_map(PromotedOffer::free_games)
+            .collect();
+        games.sort_by(|a, b| (a.starts_at, &a.title).cmp(&(b.starts_at, &b.title)));
+
+        Ok(games)
+    }
+
+    /// Get the user's game library
+    /// Get the games in the library. Titles come from the catalog and are cached per build,
+    /// so only games that are new or have a new build cost a catalog request.
+    pub async fn get_games(&self, token: &AuthToken) -> Result<Vec<Game>> {
+        log::info!("Fetching game library from Epic Games");
+
+        let items: Vec<LibraryItem> = self
+            .get_library_items(token)
+            .await?
+            .into_iter()
+            .filter(|i| !is_ue_asset(i))
+            .collect();
+
+        // The asset list is the index of current builds. Without it cached details are still
+        // better than none, so every cached entry counts as current.
+        let builds: Option<std::collections::HashMap<String, String>> =
+            match self.get_assets(token, BUILD_LABELS[0]).await {
+                Ok(assets) => Some(
+                    assets
+                        .into_iter()
+                        .map(|a| (a.app_name, a.build_version))
+                        .collect(),
+                ),
+                Err(e) => {
+                    log::warn!("Using cached library details, the asset list failed: {}", e);
+                    None
+                }
+            };
+
+        let mut cache = LibraryCache::load(&token.account_id);
+        let build_of = |item: &LibraryItem| {
+            builds
+                .as_ref()
+                .map(|builds| builds.get(&item.app_name).map_or("", String::as_str))
+        };
+
+        let stale: Vec<(LibraryItem, String)> = items
+            .iter()
+            .filter(|item| cache.current(&item.app_name, build_of(item)).is_none())
+            .map(|item| (item.clone(), build_of(item).unwrap_or_default().to_string()))
+            .collect();
+        let titles = self.get_catalog_titles(token, stale).await;
+        let refreshed = titles.len();
+        for (app_name, build_version, title) in titles {
+            cache.insert(&app_name, &build_version, &title);
+        }
+
+        let games: Vec<Game> = items
+            .iter()
+            .map(|item| {
+                let (title, version) = match cache.current(&item.app_name, build_of(item)) {
+                    Some(entry) => (entry.title.clone(), entry.build_version.clone()),
+                    None => (
+                        item.app_name.clone(),
+                        build_of(item).unwrap_or_default().to_string(),
+                    ),
+                };
+
+                Game {
+                    app_name: item.app_name.clone(),
+                    app_title: title,
+                    app_version: if version.is_empty() {
+                        "unknown".to_string()
+                    } else {
+                        version
+                    },
+                    install_path: None,
+                    provider: crate::providers::EPIC.to_string(),
+                }
+            })
+            .collect();
+
+        cache.retain(items.iter().map(|item| item.app_name.as_str()));
+        if let Err(e) = cache.save(&token.account_id) {
+            log::warn!("Failed to save the library cache: {}", e);
+        }
+
+        log::debug!(
+            "{} of {} library entries refreshed from the catalog",
+            refreshed,
+            items.len()
+        );
+        log::info!("Successfully fetched {} games from library", games.len());
+
+        Ok(games)
+    }
+
+    /// Look up the catalog titles of `(item, build)` pairs, up to `CATALOG_CONCURRENCY`
+    /// requests at a time, as `(app_name, build, title)`. Items whose lookup fails are left out.
+    async fn get_catalog_titles(
+        &self,
+        token: &AuthToken,
+        items: Vec<(LibraryItem, String)>,
+    ) -> Vec<(String, String, String)> {
+        let semaphore = Arc::new(Semaphore::new(CATALOG_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+
+        for (item, build_version) in items {
+            let client = self.clone();
+            let token = token.clone();
+            let semaphore = Arc::clone(&semaphore);
+            tasks.spawn(async move {
+                // Never closed, so the permit is always granted
+                let _permit = semaphore.acquire_owned().await.ok();
+                let catalog = client
+                    .get_catalog_item(&token, &item.namespace, &item.catalog_item_id, None)
+                    .await;
+                (item.app_name, build_version, catalog)
+            });
+        }
+
+        let mut titles = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((app_name, build_version, Ok(Some(catalog)))) => {
+                    titles.push((app_name, build_version, catalog.title))
+                }
+                Ok((app_name, _, Ok(None))) => {
+                    log::debug!("{} is missing from its catalog", app_name)
+                }
+                Ok((app_name, _, Err(e))) => {
+                    log::debug!("No catalog details for {}: {}", app_name, e)
+                }
+                Err(e) => log::warn!("Catalog lookup failed: {}", e),
+            }
+        }
+
+        titles
+    }
+
+    /// Fetch the builds Epic lists under `label` for the configured platform, one asset per
+    /// game
+    async fn get_assets(&self, token: &AuthToken, label: &str) -> Result<Vec<AssetResponse>> {
+        self.get_platform_assets(token, &self.identity.platform, label)
+            .await
+    }
+
+    /// App names of the library's games with a build for `platform`, e.g. "Windows" or "Mac"
+    pub async fn get_platform_apps(
+        &self,
+        token: &AuthToken,
+        platform: &str,
+    ) -> Result<std::collections::HashSet<String>> {
+        let assets = self
+            .get_platform_assets(token, platform, BUILD_LABELS[0])
+            .await?;
+        Ok(assets.into_iter().map(|asset| asset.app_name).collect())
+    }
+
+    async fn get_platform_assets(
+        &self,
+        token: &AuthToken,
+        platform: &str,
+        label: &str,
+    ) -> Result<Vec<AssetResponse>> {
+        let asset_url = format!("{}/assets/{}?label={}", LAUNCHER_API_URL, platform, label);
+
+        let response = self
+            .client
+            .get(&asset_url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to fetch assets",
+                Error::Api,
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Look up an item in a namespace's catalog; with a `country` the answer includes where
+    /// the item may be used
+    async fn get_catalog_item(
+        &self,
+        token: &AuthToken,
+        namespace: &str,
+        catalog_item_id: &str,
+        country: Option<&str>,
+    ) -> Result<Option<CatalogItem>> {
+        // The library refresh and the store can ask for the same item at the same time
+        let key = format!(
+            "{}/{}/{}",
+            namespace,
+            catalog_item_id,
+            country.unwrap_or_default()
+        );
+        let request = async {
+            let catalog_url = format!("{}/namespace/{}/bulk/items", CATALOG_API_URL, namespace);
+            let mut query = vec![("id", catalog_item_id)];
+            query.extend(country.map(|country| ("country", country)));
+
+            let response = self
+                .client
+                .get(&catalog_url)
+                .query(&query)
+                .header("Authorization", format!("Bearer {}", token.access_token))
+                .send_limited(&self.limiter)
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(error_from_response(
+                    status,
+                    &error_text,
+                    "Failed to fetch catalog item",
+                    Error::Api,
+                ));
+            }
+
+            let mut catalog: std::collections::HashMap<String, CatalogItem> =
+                response.json().await?;
+            Ok(catalog.remove(catalog_item_id))
+        };
+        self.catalog_requests.run(&key, request).await
+    }
+
+    /// Get the id of the asset behind a game's latest build
+    pub async fn get_game_manifest(&self, token: &AuthToken, app_name: &str) -> Result<String> {
+        Ok(self.find_asset(token, app_name).await?.id)
+    }
+
+    /// The asset of a game's latest build
+    async fn find_asset(&self, token: &AuthToken, app_name: &str) -> Result<AssetResponse> {
+        log::info!("Fetching manifest for game: {}", app_name);
+
+        let asset = self
+            .get_assets(token, BUILD_LABELS[0])
+            .await?
+            .into_iter()
+            .find(|a| a.app_name.eq_ignore_ascii_case(app_name))
+            .ok_or_else(|| Error::GameNotFound(app_name.to_string()))?;
+
+        log::info!("Found asset for {}: {}", app_name, asset.id);
+        Ok(asset)
+    }
+
+    /// Get the Unreal Engine marketplace assets in the user's library
+    pub async fn get_ue_assets(&self, token: &AuthToken) -> Result<Vec<UeAsset>> {
+        log::info!("Fetching Unreal Engine assets from Epic Games");
+
+        let assets: Vec<UeAsset> = self
+            .get_library_items(token)
+            .await?
+            .into_iter()
+            .filter(is_ue_asset)
+            .map(|item| UeAsset {
+                app_name: item.app_name,
+                catalog_item_id: item.catalog_item_id,
+            })
+            .collect();
+
+        log::info!("Successfully fetched {} Unreal Engine assets", assets.len());
+
+        Ok(assets)
+    }
+
+    /// Ask Epic about the session behind a token: account, display name, expiry and scopes
+    pub async fn verify_token(&self, token: &AuthToken) -> Result<SessionInfo> {
+        let response = self
+            .client
+            .get(VERIFY_TOKEN_URL)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to verify session",
+                Error::Auth,
+            ));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Every entitlement the account holds, in the order Epic lists them
+    pub async fn get_entitlements(&self, token: &AuthToken) -> Result<Vec<Entitlement>> {
+        let url = format!("{}/{}/entitlements", ENTITLEMENT_API_URL, token.account_id);
+        let mut entitlements = Vec::new();
+
+        loop {
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", token.access_token))
+                .query(&[
+                    ("start", entitlements.len()),
+                    ("count", ENTITLEMENTS_PAGE_SIZE),
+                ])
+                .send_limited(&self.limiter)
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(error_from_response(
+                    status,
+                    &error_text,
+                    "Failed to fetch entitlements",
+                    Error::Api,
+                ));
+            }
+
+            let page: Vec<Entitlement> = response.json().await?;
+            let last_page = page.len() < ENTITLEMENTS_PAGE_SIZE;
+            entitlements.extend(page);
+            if last_page {
+                break;
+            }
+        }
+
+        log::debug!("Found {} entitlements", entitlements.len());
+        Ok(entitlements)
+    }
+
+    /// Fail with `Error::RegionBlocked` when the account's country may not download a game.
+    /// Games that aren't in the library, or whose metadata can't be fetched, pass the check.
+    pub async fn check_region(&self, token: &AuthToken, app_name: &str) -> Result<()> {
+        let items = self.get_library_items(token).await?;
+        let Some(item) = items
+            .iter()
+            .find(|i| i.app_name.eq_ignore_ascii_case(app_name))
+        else {
+            return Ok(());
+        };
+
+        let Some(country) = self.account_country(token).await? else {
+            log::debug!("Account has no country set, skipping region check");
+            return Ok(());
+        };
+
+        let catalog = self
+            .get_catalog_item(
+                token,
+                &item.namespace,
+                &item.catalog_item_id,
+                Some(&country),
+            )
+            .await?;
+
+        match catalog.and_then(|c| c.region_restriction(&country)) {
+            Some(reason) => Err(Error::RegionBlocked(reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Country set on the account, which decides store prices and region locks
+    pub async fn account_country(&self, token: &AuthToken) -> Result<Option<String>> {
+        let account_url = format!("{}/{}", ACCOUNT_API_URL, token.account_id);
+        let account: AccountInfo = self
+            .client
+            .get(&account_url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send_limited(&self.limiter)
+            .await?
+            .json()
+            .await?;
+
+        Ok(account.country)
+    }
+
+    /// Get the builds Epic lists for a game, newest first
+    pub async fn get_build_index(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+    ) -> Result<Vec<BuildVersion>> {
+        log::info!("Fetching build index for game: {}", app_name);
+
+        let mut builds = Vec::new();
+
+        for label in BUILD_LABELS {
+            let asset_url = format!(
+                "{}/assets/{}?label={}",
+                LAUNCHER_API_URL, self.identity.platform, label
+            );
+
+            let response = self
+                .client
+                .get(&asset_url)
+                .header("Authorization", format!("Bearer {}", token.access_token))
+                .send_limited(&self.limiter)
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+
+                // Only the Live label is guaranteed to exist for every game
+                if label != BUILD_LABELS[0] {
+                    log::debug!("No {} builds available for {}: {}", label, app_name, status);
+                    continue;
+                }
+
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(error_from_response(
+                    status,
+                    &error_text,
+                    "Failed to fetch build index",
+                    Error::Api,
+                ));
+            }
+
+            let assets: Vec<AssetResponse> = response.json().await?;
+
+            for asset in assets
+                .into_iter()
+                .filter(|a| a.app_name.eq_ignore_ascii_case(app_name))
+                .filter(|a| !a.build_version.is_empty())
+            {
+                if !builds
+                    .iter()
+                    .any(|b: &BuildVersion| b.build_version == asset.build_version)
+                {
+                    builds.push(BuildVersion {
+                        build_version: asset.build_version,
+                        label: asset.label_name,
+                        asset_id: asset.id,
+                    });
+                }
+            }
+        }
+
+        if builds.is_empty() {
+            return Err(Error::GameNotFound(app_name.to_string()));
+        }
+
+        log::debug!("Found {} build(s) for {}", builds.len(), app_name);
+
+        Ok(builds)
+    }
+
+    /// Download and parse the manifest of a specific build, or of the latest one. Epic only
+    /// hands out the manifest of each label's latest build, so an older build can't be
+    /// installed yet.
+    pub async fn download_manifest_for_build(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        build_version: Option<&str>,
+    ) -> Result<GameManifest> {
+        let Some(requested) = build_version else {
+            return self.download_manifest(token, app_name).await;
+        };
+
+        let builds = self.get_build_index(token, app_name).await?;
+        let build = builds
+            .iter()
+            .find(|b| b.build_version == requested)
+            .ok_or_else(|| {
+                Error::Api(format!(
+                    "Build {} is not available for {} (run 'rauncher versions {}')",
+                    requested, app_name, app_name
+                ))
+            })?;
+
+        let manifest = self.download_manifest(token, app_name).await?;
+        if manifest.app_version != build.build_version {
+            return Err(Error::Api(format!(
+                "Pinning {} to build {} isn't supported yet: only its latest build, {}, can be \
+                 downloaded",
+                app_name, build.build_version, manifest.app_version
+            )));
+        }
+        log::info!("Using build {} ({})", build.build_version, build.label);
+        Ok(manifest)
+    }
+
+    /// Download and parse game manifest
+    pub async fn download_manifest(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+    ) -> Result<GameManifest> {
+        log::info!("Downloading manifest for game: {}", app_name);
+
+        let asset = self.find_asset(token, app_name).await?;
+        self.download_asset_manifest(token, &asset).await
+    }
+
+    /// Download and parse the manifest of the build behind `asset`, trying each mirror Epic
+    /// lists in turn
+    async fn download_asset_manifest(
+        &self,
+        token: &AuthToken,
+        asset: &AssetResponse,
+    ) -> Result<GameManifest> {
+        let url = format!(
+            "{}/assets/v2/platform/{}/namespace/{}/catalogItem/{}/app/{}/label/{}",
+            LAUNCHER_API_URL,
+            self.identity.platform,
+            asset.namespace,
+            asset.catalog_item_id,
+            asset.app_name,
+            asset.label_name
+        );
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send_limited(&self.limiter)
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to fetch the manifest location",
+                Error::Api,
+            ));
+        }
+
+        let locations: ManifestLocations = response.json().await?;
+        let element =
+            locations.elements.into_iter().next().ok_or_else(|| {
+                Error::Api(format!("Epic lists no manifest for {}", asset.app_name))
+            })?;
+        let urls: Vec<String> = element.manifests.iter().map(ManifestUri::url).collect();
+        self.manifest_from_mirrors(&asset.app_name, &urls, &element.hash)
+            .await
+    }
+
+    /// Download the manifest listed at `urls`, mirrors of the same file, trying each in turn.
+    /// Its chunks are then downloaded from next to it.
+    pub(crate) async fn manifest_from_mirrors(
+        &self,
+        app_name: &str,
+        urls: &[String],
+        hash: &str,
+    ) -> Result<GameManifest> {
+        let mut last_error = None;
+        for url in urls {
+            match self.fetch_manifest(url, hash).await {
+                Ok(manifest) => {
+                    let source = ChunkSource::new(urls, &manifest);
+                    self.chunk_sources
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(manifest.meta.app_name.clone(), Arc::new(source));
+                    return Ok(manifest.into());
+                }
+                Err(e) => {
+                    log::debug!("Manifest mirror {} failed: {}", url, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| Error::Api(format!("Epic lists no manifest for {}", app_name))))
+    }
+
+    /// Download the manifest file at `url` and parse it, checking it against the SHA-1 `hash`
+    /// (hex) when Epic gave one
+    async fn fetch_manifest(&self, url: &str, hash: &str) -> Result<crate::manifest::Manifest> {
+        let response = self.client.get(url).send_limited(&self.limiter).await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to download the manifest",
+                Error::Api,
+            ));
+        }
+
+        let data = response.bytes().await?;
+        if !hash.is_empty() {
+            use sha1::{Digest, Sha1};
+            let actual: String = Sha1::digest(&data)
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            if !actual.eq_ignore_ascii_case(hash) {
+                return Err(Error::Api(format!(
+                    "The downloaded manifest is damaged (SHA-1 {}, expected {})",
+                    actual, hash
+                )));
+            }
+        }
+        crate::manifest::parse(&data)
+    }
+
+    /// Download a chunk of `app_name`'s build as the CDN stores it, to be read with
+    /// [`crate::manifest::chunk::decode`]. The chunk is looked for next to the game's last
+    /// downloaded manifest, which is fetched first if there's none yet.
+    pub async fn download_chunk(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        chunk_guid: &str,
+    ) -> Result<Vec<u8>> {
+        log::debug!("Downloading chunk: {}", chunk_guid);
+
+        let known = self
+            .chunk_sources
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(app_name)
+            .cloned();
+        let source = match known {
+            Some(source) => source,
+            None => {
+                self.download_manifest(token, app_name).await?;
+                self.chunk_sources
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get(app_name)
+                    .cloned()
+                    .ok_or_else(|| {
+                        Error::Api(format!("No chunk location known for {}", app_name))
+                    })?
+            }
+        };
+
+        let mut last_error = None;
+        for url in source.urls(chunk_guid)? {
+            let response = match self.client.get(&url).send_limited(&self.limiter).await {
+                Ok(response) => response,
+                Err(e) => {
+                    last_error = Some(e.into());
+                    continue;
+                }
+            };
+            if response.status().is_success() {
+                return Ok(response.bytes().await?.to_vec());
+            }
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            log::debug!("Chunk mirror {} failed: {}", url, status);
+            last_error = Some(error_from_response(
+                status,
+                &error_text,
+                &format!("Failed to download chunk {}", chunk_guid),
+                Error::Api,
+            ));
+        }
+        Err(last_error
+            .unwrap_or_else(|| Error::Api(format!("No mirror lists chunk {}", chunk_guid))))
+    }
+
+    /// Check for game updates
+    pub async fn check_for_updates(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        current_version: &str,
+        releases: &ReleaseLookup,
+    ) -> Result<Option<BuildInfo>> {
+        log::info!("Checking for updates for {}", app_name);
+
+        // Get latest manifest
+        let manifest = self.download_manifest(token, app_name).await?;
+
+        if manifest.app_version != current_version {
+            log::info!(
+                "Update available: {} -> {}",
+                current_version,
+                manifest.app_version
+            );
+            let mut build = BuildInfo::version(manifest.app_version);
+            // The release date and notes are extras; the update is there without them
+            match self.latest_release(token, app_name, releases).await {
+                Ok(Some(release)) => {
+                    build.date = release.date_added;
+                    build.release_notes = release
+                        .release_note
+                        .filter(|notes| !notes.trim().is_empty());
+                }
+                Ok(None) => {}
+                Err(e) => log::debug!("Could not fetch release info for {}: {}", app_name, e),
+            }
+            Ok(Some(build))
+        } else {
+            log::info!("Game is up to date");
+            Ok(None)
+        }
+    }
+
+    /// The catalog's latest release of a game in the library, for its date and notes
+    async fn latest_release(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        releases: &ReleaseLookup,
+    ) -> Result<Option<ReleaseInfo>> {
+        let LibraryRecords(items) = releases
+            .get_or_try_init(|| async {
+                Ok::<_, Error>(LibraryRecords(self.get_library_items(token).await?))
+            })
+            .await?;
+        let Some(item) = items
+            .iter()
+            .find(|i| i.app_name.eq_ignore_ascii_case(app_name))
+        else {
+            return Ok(None);
+        };
+
+        let catalog = self
+            .get_catalog_item(token, &item.namespace, &item.catalog_item_id, None)
+            .await?;
+        Ok(catalog.and_then(|catalog| {
+            catalog
+                .latest_release(app_name, &self.identity.platform)
+                .cloned()
+        }))
+    }
+
+    /// A game's cloud save snapshots, newest first. Every upload stores a manifest of the save
+    /// files under `manifests/`, so each manifest is one snapshot.
+    pub async fn get_cloud_saves(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+    ) -> Result<Vec<CloudSave>> {
+        log::info!("Fetching cloud saves for {}", app_name);
+
+        let url = format!(
+            "{}/{}/{}/manifests/",
+            SAVESYNC_API_URL, token.account_id, app_name
+        );
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to fetch cloud saves",
+                Error::Api,
+            ));
+        }
+
+        let listing: SaveListing = response.json().await?;
+        let mut saves = Vec::new();
+        for (mut save, read_link) in listing.into_saves(app_name) {
+            // The listing has the size of the manifest; the snapshot is the files it lists
+            if let Some(read_link) = read_link {
+                save.size = self.save_manifest(&read_link).await?.build_size();
+            }
+            saves.push(save);
+        }
+        Ok(saves)
+    }
+
+    /// Download and parse the manifest of a cloud save snapshot
+    async fn save_manifest(&self, read_link: &str) -> Result<crate::manifest::Manifest> {
+        let response = self
+            .client
+            .get(read_link)
+            .send_limited(&self.limiter)
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to fetch a cloud save manifest",
+                Error::Api,
+            ));
+        }
+        crate::manifest::parse(&response.bytes().await?)
+    }
+
+    /// Download the files of a cloud save snapshot: its manifest, then the chunks it lists,
+    /// stored next to it
+    pub async fn download_cloud_save(
+        &self,
+        token: &AuthToken,
+        save: &CloudSave,
+    ) -> Result<Vec<SaveFile>> {
+        log::debug!("Downloading cloud save snapshot {}", save.id);
+
+        let url = format!(
+            "{}/{}/{}/",
+            SAVESYNC_API_URL, token.account_id, save.app_name
+        );
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token.access_token))
+            .send_limited(&self.limiter)
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to list cloud save files",
+                Error::Api,
+            ));
+        }
+        let listing: SaveListing = response.json().await?;
+        self.assemble_snapshot(&listing, &save.filename).await
+    }
+
+    /// Put together the files of the snapshot whose manifest is `manifest_name` in `listing`
+    async fn assemble_snapshot(
+        &self,
+        listing: &SaveListing,
+        manifest_name: &str,
+    ) -> Result<Vec<SaveFile>> {
+        let read_link = |suffix: &str| {
+            listing
+                .files
+                .iter()
+                .find(|(path, _)| path.ends_with(suffix))
+                .and_then(|(_, file)| file.read_link.as_deref())
+        };
+        let manifest_link = read_link(&format!("manifests/{}", manifest_name))
+            .ok_or_else(|| Error::Api(format!("Cloud save {} is gone", manifest_name)))?;
+        let manifest = self.save_manifest(manifest_link).await?;
+
+        let mut chunks = HashMap::new();
+        for chunk in &manifest.chunks {
+            let path = crate::manifest::chunk::path(
+                manifest.version,
+                chunk.group,
+                chunk.hash,
+                &chunk.guid,
+            );
+            let link = read_link(&format!("/{}", path)).ok_or_else(|| {
+                Error::Api(format!(
+                    "Cloud save {} is incomplete: chunk {} is missing",
+                    manifest_name, chunk.guid
+                ))
+            })?;
+            let response = self.client.get(link).send_limited(&self.limiter).await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(error_from_response(
+                    status,
+                    &error_text,
+                    "Failed to download a cloud save chunk",
+                    Error::Api,
+                ));
+            }
+            let data = crate::manifest::chunk::decode(&response.bytes().await?)?;
+            chunks.insert(chunk.guid, data);
+        }
+
+        manifest
+            .files
+            .iter()
+            .map(|file| {
+                let mut data = Vec::with_capacity(file.size() as usize);
+                for part in &file.parts {
+                    let start = part.offset as usize;
+                    let range = chunks
+                        .get(&part.guid)
+                        .and_then(|chunk| chunk.get(start..start + part.size as usize))
+                        .ok_or_else(|| {
+                            Error::Api(format!(
+                                "Cloud save {} is damaged: chunk {} is too short for {}",
+                                manifest_name, part.guid, file.filename
+                            ))
+                        })?;
+                    data.extend_from_slice(range);
+                }
+                Ok(SaveFile {
+                    path: file.filename.clone(),
+                    data,
+                })
+            })
+            .collect()
+    }
+
+    /// Upload a cloud save file. Not supported yet: Epic wants the saves split into chunks
+    /// and a manifest written for them.
+    pub async fn upload_cloud_save(
+        &self,
+        _token: &AuthToken,
+        app_name: &str,
+        save_data: &[u8],
+    ) -> Result<()> {
+        // TODO: Chunk the saves, write their manifest and upload both
+
+        log::debug!(
+            "Cloud save upload requested for {} ({} bytes)",
+            app_name,
+            save_data.len()
+        );
+        Err(Error::Api(
+            "Uploading Epic cloud saves is not implemented yet".to_string(),
+        ))
+    }
+}
+
+/// Turn a failed response into the most specific error we know for its Epic error code,
+/// falling back to `fallback` with the status and body for unknown failures
+fn error_from_response(
+    status: StatusCode,
+    body: &str,
+    context: &str,
+    fallback: fn(String) -> Error,
+) -> Error {
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        return fallback(format!(
+            "{}: Epic is limiting requests from this account; try again in a few minutes",
+            context
+        ));
+    }
+    let Some(epic_error) = EpicErrorResponse::parse(body) else {
+        if status == StatusCode::UNAUTHORIZED {
+            return Error::TokenRejected(format!("{}: {}", context, status));
+        }
+        return fallback(format!("{}: {} - {}", context, status, body));
+    };
+
+    let code = epic_error.error_code.as_str();
+    let message = if epic_error.error_message.is_empty() {
+        code.to_string()
+    } else {
+        epic_error.error_message.clone()
+    };
+
+    if REAUTH_CODES.contains(&code) {
+        Error::ReauthRequired(message)
+    } else if TOKEN_REJECTED_CODES.contains(&code) {
+        Error::TokenRejected(message)
+    } else if code.contains("captcha") {
+        Error::CaptchaRequired(message)
+    } else if code.contains("region") || code.contains("country") {
+        Error::RegionBlocked(message)
+    } else {
+        fallback(format!("{}: {} ({})", context, message, code))
+    }
+}
+
+fn is_ue_asset(item: &LibraryItem) -> bool {
+    item.namespace == UE_NAMESPACE
+}
+
+/// One snapshot of a game's cloud save
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudSave {
+    /// Name of the snapshot, which `cloud-save --snapshot` takes
+    pub id: String,
+    pub app_name: String,
+    pub filename: String,
+    /// Size of the files in the snapshot, once unpacked
+    pub size: u64,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// A file of a cloud save snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveFile {
+    /// Where the file goes, relative to the game's save folder
+    pub path: String,
+    pub data: Vec<u8>,
+}
+
+/// Files stored for an account, as the save sync service lists them, keyed by path
+#[derive(Debug, Deserialize)]
+struct SaveListing {
+    #[serde(default)]
+    files: std::collections::HashMap<String, StoredFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredFile {
+    last_modified: DateTime<Utc>,
+    #[serde(default)]
+    size: u64,
+    /// Signed URL the file is downloaded from
+    #[serde(default)]
+    read_link: Option<String>,
+}
+
+impl SaveListing {
+    /// The save manifests in the listing, as snapshots, newest first, each with where its
+    /// manifest is downloaded from. Sizes are the manifests' own until they're read.
+    fn into_saves(self, app_name: &str) -> Vec<(CloudSave, Option<String>)> {
+        let mut saves: Vec<(CloudSave, Option<String>)> = self
+            .files
+            .into_iter()
+            .filter_map(|(path, file)| {
+                let filename = path.rsplit('/').next()?.to_string();
+                let id = filename.strip_suffix(".manifest")?.to_string();
+                let save = CloudSave {
+                    id,
+                    app_name: app_name.to_string(),
+                    filename,
+                    size: file.size,
+                    uploaded_at: file.last_modified,
+                };
+                Some((save, file.read_link))
+            })
+            .collect();
+        saves.sort_by(|(a, _), (b, _)| b.uploaded_at.cmp(&a.uploaded_at).then(a.id.cmp(&b.id)));
+        saves
+    }
+}
+
+impl Default for EpicClient {
+    fn default() -> Self {
+        Self::new().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_responses;
+
+    #[test]
+    fn test_epic_client_creation() {
+        let client = EpicClient::new();
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_manifest_without_prerequisite_has_none() {
+        let without = serde_json::to_value(GameManifest::default()).unwrap();
+        assert!(without.get("Prerequisite").is_none());
+        let manifest: GameManifest = serde_json::from_value(without.clone()).unwrap();
+        assert_eq!(manifest.prerequisite, None);
+
+        let prerequisite = Prerequisite {
+            ids: vec!["vcredist".to_string()],
+            name: "Visual C++".to_string(),
+            path: "redist/vc_redist.x64.exe".to_string(),
+            args: "/quiet".to_string(),
+        };
+        let with = GameManifest {
+            prerequisite: Some(prerequisite.clone()),
+            ..GameManifest::default()
+        };
+        let manifest: GameManifest =
+            serde_json::from_value(serde_json::to_value(with).unwrap()).unwrap();
+        assert_eq!(manifest.prerequisite, Some(prerequisite));
+    }
+
+    #[test]
+    fn test_client_settings() {
+        let settings = EpicSettings {
+            client_id: Some("kiwi".to_string()),
+            client_secret: Some("secret".to_string()),
+            platform: Some("Mac".to_string()),
+            ..Default::default()
+        };
+        let client = EpicClient::with_settings(&settings).unwrap();
+        assert_eq!(client.identity.client_id, "kiwi");
+        assert_eq!(client.identity.platform, "Mac");
+        assert!(client.web_login_url().contains("kiwi"));
+
+        let default = EpicClient::new().unwrap();
+        assert_eq!(default.identity.client_id, CLIENT_ID);
+        assert_eq!(default.identity.platform, PLATFORM);
+
+        let without_secret = EpicSettings {
+            client_id: Some("kiwi".to_string()),
+            ..Default::default()
+        };
+        assert!(EpicClient::with_settings(&without_secret).is_err());
+    }
+
+    #[test]
+    fn test_web_login_url_redirects_to_code_page() {
+        let url = reqwest::Url::parse(&EpicClient::new().unwrap().web_login_url()).unwrap();
+        let (_, redirect) = url
+            .query_pairs()
+            .find(|(key, _)| key == "redirectUrl")
+            .unwrap();
+        let redirect = reqwest::Url::parse(&redirect).unwrap();
+
+        assert!(redirect.as_str().starts_with(WEB_LOGIN_REDIRECT_URL));
+        assert!(redirect
+            .query_pairs()
+            .any(|(key, value)| key == "clientId" && value == CLIENT_ID));
+    }
+
+    #[test]
+    fn test_game_serialization() {
+        let game = Game {
+            app_name: "test_app".to_string(),
+            app_title: "Test Game".to_string(),
+            app_version: "1.0.0".to_string(),
+            install_path: None,
+            provider: "epic".to_string(),
+        };
+        let serialized = serde_json::to_string(&game).unwrap();
+        let deserialized: Game = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(game.app_name, deserialized.app_name);
+    }
+
+    #[test]
+    fn test_game_id_defaults_to_epic() {
+        let game: Game = serde_json::from_str(
+            r#"{"app_name": "Fortnite", "app_title": "Fortnite", "app_version": "1", "install_path": null}"#,
+        )
+        .unwrap();
+        assert_eq!(game.id(), "epic:Fortnite");
+    }
+
+    #[test]
+    fn test_free_games_from_promotions() {
+        let json = r#"{"data": {"Catalog": {"searchStore": {"elements": [
+            {
+                "title": "Current Game",
+                "productSlug": "current-game/home",
+                "promotions": {
+                    "promotionalOffers": [{"promotionalOffers": [{
+                        "startDate": "2026-10-08T15:00:00.000Z",
+                        "endDate": "2026-10-15T15:00:00.000Z",
+                        "discountSetting": {"discountType": "PERCENTAGE", "discountPercentage": 0}
+                    }]}],
+                    "upcomingPromotionalOffers": []
+                }
+            },
+            {
+                "title": "Upcoming Game",
+                "productSlug": null,
+                "offerMappings": [{"pageSlug": "upcoming-game", "pageType": "productHome"}],
+                "promotions": {
+                    "promotionalOffers": [],
+                    "upcomingPromotionalOffers": [{"promotionalOffers": [{
+                        "startDate": "2026-10-15T15:00:00.000Z",
+                        "endDate": "2026-10-22T15:00:00.000Z",
+                        "discountSetting": {"discountType": "PERCENTAGE", "discountPercentage": 0}
+                    }]}]
+                }
+            },
+            {
+                "title": "Discounted Game",
+                "promotions": {
+                    "promotionalOffers": [{"promotionalOffers": [{
+                        "startDate": "2026-10-08T15:00:00.000Z",
+                        "endDate": "2026-10-15T15:00:00.000Z",
+                        "discountSetting": {"discountType": "PERCENTAGE", "discountPercentage": 50}
+                    }]}]
+                }
+            },
+            {"title": "Mystery Game", "promotions": null}
+        ]}}}}"#;
+
+        let response: FreeGamesResponse = serde_json::from_str(json).unwrap();
+        let games: Vec<FreeGame> = response
+            .data
+            .catalog
+            .search_store
+            .elements
+            .iter()
+            .flat_map(PromotedOffer::free_games)
+            .collect();
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].title, "Current Game");
+        assert_eq!(
+            games[0].url.as_deref(),
+            Some("https://store.epicgames.com/p/current-game")
+        );
+        assert_eq!(
+            games[1].url.as_deref(),
+            Some("https://store.epicgames.com/p/upcoming-game")
+        );
+        assert!(games[1].starts_at > games[0].starts_at);
+    }
+
+    #[test]
+    fn test_oauth_token_response_deserialization() {
+        let json = r#"{
+            "access_token": "test_access",
+            "refresh_token": "test_refresh",
+            "expires_in": 3600,
+            "account_id": "test_account"
+        }"#;
+        let response: OAuthTokenResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.access_token, "test_access");
+        assert_eq!(response.expires_in, 3600);
+    }
+
+    #[test]
+    fn test_library_response_deserialization() {
+        let json = r#"{
+            "records": [
+                {
+                    "appName": "Fortnite",
+                    "namespace": "fn",
+                    "catalogItemId": "4fe75bbc5a674f4f9b356b5c90567da5"
+                }
+            ]
+        }"#;
+        let response: LibraryResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.records.len(), 1);
+        assert_eq!(response.records[0].app_name, "Fortnite");
+    }
+
+    #[test]
+    fn test_session_and_entitlement_deserialization() {
+        let json = r#"{
+            "token": "eg1~abc",
+            "account_id": "test_account",
+            "client_id": "34a02cf8f4414e29b15921876da36f9a",
+            "display_name": "Tester",
+            "expires_at": "2026-10-15T20:00:00.000Z",
+            "scope": ["basic_profile", "openid"]
+        }"#;
+        let session: SessionInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(session.display_name.as_deref(), Some("Tester"));
+        assert_eq!(session.scopes, ["basic_profile", "openid"]);
+
+        let json = r#"[{
+            "id": "0a1b",
+            "entitlementName": "Fortnite_Free",
+            "namespace": "fn",
+            "catalogItemId": "4fe75bbc5a674f4f9b356b5c90567da5",
+            "entitlementType": "EXECUTABLE",
+            "grantDate": "2020-01-01T00:00:00.000Z",
+            "active": true,
+            "status": "ACTIVE",
+            "useCount": 0
+        }]"#;
+        let entitlements: Vec<Entitlement> = serde_json::from_str(json).unwrap();
+        assert_eq!(entitlements[0].entitlement_name, "Fortnite_Free");
+        assert!(entitlements[0].active);
+    }
+
+    #[test]
+    fn test_ue_assets_are_told_apart_from_games() {
+        let json = r#"{
+            "records": [
+                { "appName": "Fortnite", "namespace": "fn", "catalogItemId": "a" },
+                { "appName": "MarketplaceAsset", "namespace": "ue", "catalogItemId": "b" }
+            ]
+        }"#;
+        let response: LibraryResponse = serde_json::from_str(json).unwrap();
+        let assets: Vec<_> = response.records.iter().filter(|i| is_ue_asset(i)).collect();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].app_name, "MarketplaceAsset");
+    }
+
+    #[test]
+    fn test_error_from_response_maps_known_codes() {
+        let body = r#"{
+            "errorCode": "errors.com.epicgames.account.oauth.expired",
+            "errorMessage": "Sorry the token has expired"
+        }"#;
+        let err = error_from_response(StatusCode::UNAUTHORIZED, body, "ctx", Error::Api);
+        assert!(matches!(err, Error::ReauthRequired(_)));
+
+        let body = r#"{ "errorCode": "errors.com.epicgames.common.oauth.invalid_token" }"#;
+        let err = error_from_response(StatusCode::UNAUTHORIZED, body, "ctx", Error::Api);
+        assert!(matches!(err, Error::TokenRejected(_)));
+        let err = error_from_response(StatusCode::UNAUTHORIZED, "", "ctx", Error::Api);
+        assert!(matches!(err, Error::TokenRejected(_)));
+
+        let body = r#"{ "errorCode": "errors.com.epicgames.accountportal.captcha_invalid" }"#;
+        let err = error_from_response(StatusCode::BAD_REQUEST, body, "ctx", Error::Auth);
+        assert!(matches!(err, Error::CaptchaRequired(_)));
+
+        let body = r#"{ "errorCode": "errors.com.epicgames.purchase.region_locked" }"#;
+        let err = error_from_response(StatusCode::FORBIDDEN, body, "ctx", Error::Api);
+        assert!(matches!(err, Error::RegionBlocked(_)));
+    }
+
+    #[test]
+    fn test_error_from_response_falls_back() {
+        let err = error_from_response(StatusCode::BAD_GATEWAY, "oops", "Failed", Error::Api);
+        assert!(matches!(err, Error::Api(msg) if msg.contains("502") && msg.contains("oops")));
+
+        let body = r#"{ "errorCode": "errors.com.epicgames.unknown", "errorMessage": "Nope" }"#;
+        let err = error_from_response(StatusCode::BAD_REQUEST, body, "Failed", Error::Auth);
+        assert!(matches!(err, Error::Auth(msg) if msg.contains("Nope")));
+    }
+
+    #[test]
+    fn test_catalog_item_region_restriction() {
+        let json = r#"{
+            "id": "item",
+            "title": "Some Game",
+            "countriesBlacklist": ["DE", "RU"]
+        }"#;
+        let item: CatalogItem = serde_json::from_str(json).unwrap();
+        assert!(item.region_restriction("de").is_some());
+        assert!(item.region_restriction("US").is_none());
+
+        let json = r#"{ "id": "item", "title": "Regional", "countriesWhitelist": ["JP"] }"#;
+        let item: CatalogItem = serde_json::from_str(json).unwrap();
+        assert!(item.region_restriction("JP").is_none());
+        assert!(item.region_restriction("US").unwrap().contains("Regional"));
+    }
+
+    #[test]
+    fn test_catalog_item_latest_release() {
+        let json = r#"{
+            "id": "item",
+            "title": "Some Game",
+            "releaseInfo": [
+                {
+                    "appId": "Kiwi",
+                    "platform": ["Windows"],
+                    "dateAdded": "2024-01-10T12:00:00.000Z"
+                },
+                {
+                    "appId": "Kiwi",
+                    "platform": ["Windows", "Mac"],
+                    "dateAdded": "2024-03-02T08:30:00.000Z",
+                    "releaseNote": "Fixed the kiwis"
+                },
+                {
+                    "appId": "Kiwi",
+                    "platform": ["Mac"],
+                    "dateAdded": "2024-05-01T00:00:00.000Z"
+                },
+                { "appId": "KiwiDemo", "dateAdded": "2024-06-01T00:00:00.000Z" }
+            ]
+        }"#;
+        let item: CatalogItem = serde_json::from_str(json).unwrap();
+
+        let release = item.latest_release("kiwi", "Windows").unwrap();
+        assert_eq!(release.release_note.as_deref(), Some("Fixed the kiwis"));
+        assert_eq!(
+            release.date_added.unwrap().to_rfc3339(),
+            "2024-03-02T08:30:00+00:00"
+        );
+        assert_eq!(
+            item.latest_release("Kiwi", "Mac").unwrap().release_note,
+            None
+        );
+        assert!(item.latest_release("Banana", "Windows").is_none());
+    }
+
+    #[test]
+    fn test_save_listing_snapshots() {
+        let json = r#"{
+            "files": {
+                "acc/Kiwi/manifests/Kiwi_2024.01.10.manifest": {
+                    "readLink": "https://example.com/a",
+                    "lastModified": "2024-01-10T12:00:00.000Z",
+                    "size": 512
+                },
+                "acc/Kiwi/manifests/Kiwi_2024.03.02.manifest": {
+                    "lastModified": "2024-03-02T08:30:00.000Z",
+                    "size": 640
+                },
+                "acc/Kiwi/chunks/0/ABCD.chunk": {
+                    "lastModified": "2024-03-02T08:30:00.000Z",
+                    "size": 4096
+                }
+            }
+        }"#;
+        let listing: SaveListing = serde_json::from_str(json).unwrap();
+        let saves = listing.into_saves("Kiwi");
+
+        let ids: Vec<&str> = saves.iter().map(|(save, _)| save.id.as_str()).collect();
+        assert_eq!(ids, vec!["Kiwi_2024.03.02", "Kiwi_2024.01.10"]);
+        assert_eq!(saves[0].0.filename, "Kiwi_2024.03.02.manifest");
+        assert_eq!(saves[0].1, None);
+        assert_eq!(saves[1].0.app_name, "Kiwi");
+        assert_eq!(saves[1].1.as_deref(), Some("https://example.com/a"));
+
+        let empty: SaveListing = serde_json::from_str("{}").unwrap();
+        assert!(empty.into_saves("Kiwi").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_size_comes_from_its_manifest() {
+        let manifest = include_bytes!("../manifest/fixtures/kiwi.manifest").to_vec();
+        let url = serve_responses(vec![(200, manifest)]).await;
+
+        let client = EpicClient::new().unwrap();
+        let manifest = client.save_manifest(&url).await.unwrap();
+        assert_eq!(manifest.build_size(), 301_350);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_files_are_assembled_from_its_chunks() {
+        use crate::manifest::{chunk, ChunkInfo, ChunkPart, FileEntry, Guid, Manifest};
+        use sha1::{Digest, Sha1};
+
+        let guid = Guid([1, 2, 3, 4]);
+        let data = b"slot one|slot two".to_vec();
+        let file = |filename: &str, offset: u32, size: u32| FileEntry {
+            filename: filename.to_string(),
+            parts: vec![ChunkPart { guid, offset, size }],
+            ..FileEntry::default()
+        };
+        let snapshot = Manifest {
+            version: 18,
+            chunks: vec![ChunkInfo {
+                guid,
+                hash: 0xAB,
+                sha: Sha1::digest(&data).into(),
+                group: 7,
+                window_size: data.len() as u32,
+                file_size: 0,
+            }],
+            files: vec![file("Slot1.sav", 0, 8), file("Profiles/Slot2.sav", 9, 8)],
+            ..Manifest::default()
+        };
+        let chunk_path = chunk::path(18, 7, 0xAB, &guid);
+
+        let mut served = std::collections::HashMap::new();
+        served.insert("/manifest".to_string(), snapshot.to_binary());
+        served.insert("/chunk".to_string(), chunk::encode(&guid, 0xAB, &data));
+        let url = crate::test_support::serve_files(served).await;
+        let listing: SaveListing = serde_json::from_value(serde_json::json!({
+            "files": {
+                "acc/Kiwi/manifests/2024.01.01-00.00.00.manifest": {
+                    "lastModified": "2024-01-01T00:00:00Z",
+                    "readLink": format!("{}/manifest", url),
+                },
+                format!("acc/Kiwi/{}", chunk_path): {
+                    "lastModified": "2024-01-01T00:00:00Z",
+                    "readLink": format!("{}/chunk", url),
+                },
+            }
+        }))
+        .unwrap();
+
+        let client = EpicClient::new().unwrap();
+        let files = client
+            .assemble_snapshot(&listing, "2024.01.01-00.00.00.manifest")
+            .await
+            .unwrap();
+        assert_eq!(
+            files,
+            vec![
+                SaveFile {
+                    path: "Slot1.sav".to_string(),
+                    data: b"slot one".to_vec(),
+                },
+                SaveFile {
+                    path: "Profiles/Slot2.sav".to_string(),
+                    data: b"slot two".to_vec(),
+                },
+            ]
+        );
+
+        assert!(client
+            .assemble_snapshot(&listing, "2023.01.01-00.00.00.manifest")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cloud_save_upload_is_refused() {
+        let token = AuthToken {
+            access_token: "access".to_string(),
+            refresh_token: "refresh".to_string(),
+            expires_at: Utc::now(),
+            account_id: "acc".to_string(),
+        };
+        let client = EpicClient::new().unwrap();
+        assert!(client
+            .upload_cloud_save(&token, "Kiwi", b"save")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_manifest_download_checks_its_hash() {
+        let json = r#"{
+            "elements": [{
+                "appName": "Kiwi",
+                "labelName": "Live",
+                "buildVersion": "1.2.0-CL-1234+++Kiwi+Release",
+                "hash": "HASH",
+                "manifests": [
+                    { "uri": "https://cdn.example.com/Builds/kiwi.manifest",
+                      "queryParams": [{ "name": "f_token", "value": "exp=1~hmac=ab" }] },
+                    { "uri": "https://mirror.example.com/Builds/kiwi.manifest" }
+                ]
+            }]
+        }"#;
+        let locations: ManifestLocations = serde_json::from_str(json).unwrap();
+        let element = &locations.elements[0];
+        assert_eq!(
+            element.manifests[0].url(),
+            "https://cdn.example.com/Builds/kiwi.manifest?f_token=exp=1~hmac=ab"
+        );
+        assert_eq!(
+            element.manifests[1].url(),
+            "https://mirror.example.com/Builds/kiwi.manifest"
+        );
+
+        let data = include_bytes!("../manifest/fixtures/kiwi.manifest").to_vec();
+        let hash: String = {
+            use sha1::{Digest, Sha1};
+            Sha1::digest(&data)
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect()
+        };
+        let url = serve_responses(vec![(200, data.clone()), (200, data)]).await;
+        let client = EpicClient::new().unwrap();
+
+        let manifest = client.fetch_manifest(&url, &hash).await.unwrap();
+        assert_eq!(manifest.meta.build_version, "1.2.0-CL-1234+++Kiwi+Release");
+        let wrong = "0".repeat(40);
+        assert!(client.fetch_manifest(&url, &wrong).await.is_err());
+    }
+
+    #[test]
+    fn test_asset_response_build_version_optional() {
+        let json = r#"{
+            "id": "asset",
+            "appName": "Fortnite",
+            "label_name": "Live",
+            "metadata": { "applicationId": "app" }
+        }"#;
+        let asset: AssetResponse = serde_json::from_str(json).unwrap();
+        assert!(asset.build_version.is_empty());
+    }
+}
@@ -0,0 +1,520 @@
+//! Browsing the Epic Games Store catalog: searches by keyword, genre and price, with prices
+//! in the account's region. Purchases happen on the website; every offer carries its store
+//! page. Answers are cached per query, so paging back and forth or reopening the store
+//! doesn't hit Epic again, and the last answer is still there when Epic can't be reached.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::throttle::LimitedSend;
+use super::{error_from_response, store_url, EpicClient, FreeGame, PageMapping};
+use crate::config::Config;
+use crate::{Error, Result};
+
+const STORE_GRAPHQL_URL: &str = "https://graphql.epicgames.com/graphql";
+
+/// Offers per page of results
+pub const STORE_PAGE_SIZE: usize = 40;
+
+/// How long a cached answer is used without asking Epic again
+const CACHE_TTL_MINUTES: i64 = 60;
+
+/// Genres offered as filters, with the store tag each one searches for
+pub const STORE_GENRES: [(&str, &str); 12] = [
+    ("Action", "1216"),
+    ("Adventure", "1117"),
+    ("Casual", "1298"),
+    ("Indie", "1263"),
+    ("Platformer", "1370"),
+    ("Puzzle", "1218"),
+    ("Racing", "1212"),
+    ("RPG", "1367"),
+    ("Shooter", "1210"),
+    ("Simulation", "1393"),
+    ("Sports", "1283"),
+    ("Strategy", "1115"),
+];
+
+const SEARCH_QUERY: &str = r#"query searchStoreQuery($category: String, $count: Int,
+  $country: String!, $keywords: String, $locale: String, $sortBy: String, $sortDir: String,
+  $start: Int, $tag: String, $freeGame: Boolean) {
+  Catalog {
+    searchStore(allowCountries: $country, category: $category, count: $count,
+      country: $country, keywords: $keywords, locale: $locale, sortBy: $sortBy,
+      sortDir: $sortDir, start: $start, tag: $tag, freeGame: $freeGame) {
+      elements {
+        title id namespace description
+        seller { name }
+        productSlug
+        offerMappings { pageSlug }
+        keyImages { type url }
+        tags { id name groupName }
+        price(country: $country) {
+          totalPrice {
+            discountPrice originalPrice currencyCode
+            fmtPrice(locale: $locale) { originalPrice discountPrice }
+          }
+        }
+      }
+      paging { total }
+    }
+  }
+}"#;
+
+/// What to look for in the store
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoreQuery {
+    /// Words in the title, or anything when empty
+    pub keywords: String,
+    /// Name of one of `STORE_GENRES`
+    pub genre: Option<String>,
+    /// Only games that cost nothing, along with the current giveaways
+    pub free: bool,
+    /// Page of results, from 0
+    pub page: usize,
+}
+
+impl StoreQuery {
+    /// Store tag of the genre, failing on genres the store doesn't know
+    fn tag(&self) -> Result<Option<&'static str>> {
+        let Some(genre) = &self.genre else {
+            return Ok(None);
+        };
+
+        STORE_GENRES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(genre.trim()))
+            .map(|(_, tag)| Some(*tag))
+            .ok_or_else(|| {
+                let genres: Vec<&str> = STORE_GENRES.iter().map(|(name, _)| *name).collect();
+                Error::Other(format!(
+                    "Unknown genre: '{}'. Must be one of: {}",
+                    genre,
+                    genres.join(", ")
+                ))
+            })
+    }
+
+    /// File name of the cached answer
+    fn cache_key(&self, country: &str) -> String {
+        let keywords: String = self
+            .keywords
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        format!(
+            "{}_{}_{}_{}_{}",
+            country.to_lowercase(),
+            keywords,
+            self.genre.as_deref().unwrap_or("all").to_lowercase(),
+            if self.free { "free" } else { "any" },
+            self.page
+        )
+    }
+}
+
+/// A game for sale
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoreOffer {
+    pub title: String,
+    pub namespace: String,
+    pub id: String,
+    pub description: String,
+    pub seller: Option<String>,
+    /// Store page, where the game is bought
+    pub url: Option<String>,
+    /// Cover art
+    pub image: Option<String>,
+    pub genres: Vec<String>,
+    /// None when the store shows no price, e.g. for games not released yet
+    pub price: Option<StorePrice>,
+}
+
+/// An offer's price in the account's currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorePrice {
+    /// Price as the store shows it, e.g. "€19.99"
+    pub original: String,
+    /// Price after discounts
+    pub current: String,
+    pub currency: String,
+    /// Percentage taken off the original price
+    pub discount: u32,
+    pub free: bool,
+}
+
+/// One page of store results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorePage {
+    pub offers: Vec<StoreOffer>,
+    /// Offers matching the query over all pages
+    pub total: usize,
+    /// Games given away now, filled in for free searches
+    #[serde(default)]
+    pub giveaways: Vec<FreeGame>,
+    /// Country the prices are for
+    pub country: String,
+    pub fetched_at: DateTime<Utc>,
+    /// Served from the cache after Epic couldn't be reached
+    #[serde(skip)]
+    pub stale: bool,
+}
+
+impl StorePage {
+    pub fn pages(&self) -> usize {
+        self.total.div_ceil(STORE_PAGE_SIZE).max(1)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    data: SearchData,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchData {
+    #[serde(rename = "Catalog")]
+    catalog: SearchCatalog,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchCatalog {
+    #[serde(rename = "searchStore")]
+    search_store: SearchResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResults {
+    elements: Vec<SearchElement>,
+    paging: Paging,
+}
+
+#[derive(Debug, Deserialize)]
+struct Paging {
+    total: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchElement {
+    title: String,
+    id: String,
+    namespace: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    seller: Option<Seller>,
+    #[serde(rename = "productSlug", default)]
+    product_slug: Option<String>,
+    #[serde(rename = "offerMappings", default)]
+    offer_mappings: Option<Vec<PageMapping>>,
+    #[serde(rename = "keyImages", default)]
+    key_images: Vec<KeyImage>,
+    #[serde(default)]
+    tags: Vec<Tag>,
+    #[serde(default)]
+    price: Option<Price>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Seller {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyImage {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tag {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename = "groupName", default)]
+    group_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Price {
+    #[serde(rename = "totalPrice")]
+    total: TotalPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct TotalPrice {
+    #[serde(rename = "discountPrice")]
+    discount_price: u64,
+    #[serde(rename = "originalPrice")]
+    original_price: u64,
+    #[serde(rename = "currencyCode")]
+    currency_code: String,
+    #[serde(rename = "fmtPrice")]
+    formatted: FormattedPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct FormattedPrice {
+    #[serde(rename = "originalPrice")]
+    original_price: String,
+    #[serde(rename = "discountPrice")]
+    discount_price: String,
+}
+
+impl SearchElement {
+    fn into_offer(self) -> StoreOffer {
+        // Wide art fits the store's cards better than the tall box art
+        let image = ["OfferImageWide", "DieselStoreFrontWide", "Thumbnail"]
+            .iter()
+            .find_map(|kind| self.key_images.iter().find(|image| image.kind == *kind))
+            .map(|image| image.url.clone());
+
+        let genres = self
+            .tags
+            .iter()
+            .filter(|tag| tag.group_name.as_deref() == Some("genre"))
+            .filter_map(|tag| tag.name.clone())
+            .collect();
+
+        let price = self.price.map(|price| {
+            let total = price.total;
+            let discount = match total.original_price {
+                0 => 0,
+                original => {
+                    let off = original.saturating_sub(total.discount_price);
+                    (off * 100 / original) as u32
+                }
+            };
+            StorePrice {
+                original: total.formatted.original_price,
+                current: total.formatted.discount_price,
+                currency: total.currency_code,
+                discount,
+                free: total.discount_price == 0,
+            }
+        });
+
+        StoreOffer {
+            url: store_url(self.product_slug.as_deref(), self.offer_mappings.as_deref()),
+            title: self.title,
+            namespace: self.namespace,
+            id: self.id,
+            description: self.description.unwrap_or_default(),
+            seller: self.seller.map(|seller| seller.name),
+            image,
+            genres,
+            price,
+        }
+    }
+}
+
+impl EpicClient {
+    /// Search the store, with prices as offered in `country`. The catalog is public, so no
+    /// login is needed.
+    pub async fn search_store(&self, query: &StoreQuery, country: &str) -> Result<StorePage> {
+        let tag = query.tag()?;
+        let keywords = query.keywords.trim();
+        let variables = serde_json::json!({
+            "category": "games/edition/base|bundles/games|editors|software/edition/base",
+            "count": STORE_PAGE_SIZE,
+            "country": country,
+            "keywords": keywords,
+            "locale": "en-US",
+            "sortBy": if keywords.is_empty() { "releaseDate" } else { "relevancy" },
+            "sortDir": "DESC",
+            "start": query.page * STORE_PAGE_SIZE,
+            "tag": tag.unwrap_or_default(),
+            "freeGame": query.free.then_some(true),
+        });
+
+        let response = self
+            .client
+            .post(STORE_GRAPHQL_URL)
+            .json(&serde_json::json!({ "query": SEARCH_QUERY, "variables": variables }))
+            .send_limited(&self.limiter)
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(
+                status,
+                &error_text,
+                "Failed to search the store",
+                Error::Api,
+            ));
+        }
+
+        let results: SearchResponse = response.json().await?;
+        let results = results.data.catalog.search_store;
+        Ok(StorePage {
+            offers: results
+                .elements
+                .into_iter()
+                .map(SearchElement::into_offer)
+                .collect(),
+            total: results.paging.total,
+            giveaways: Vec::new(),
+            country: country.to_string(),
+            fetched_at: Utc::now(),
+            stale: false,
+        })
+    }
+}
+
+/// Store answers kept on disk, by query
+pub(crate) struct StoreCache {
+    dir: PathBuf,
+}
+
+impl StoreCache {
+    pub(crate) fn new() -> Result<Self> {
+        Ok(Self {
+            dir: Config::data_dir()?.join("cache").join("store"),
+        })
+    }
+
+    /// The cached answer to a query, fresh or not
+    pub(crate) fn get(&self, query: &StoreQuery, country: &str) -> Option<StorePage> {
+        let path = self.path(query, country);
+        let contents = fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&contents)
+            .inspect_err(|e| log::debug!("Ignoring unreadable store cache {:?}: {}", path, e))
+            .ok()
+    }
+
+    /// The cached answer to a query, if it's recent enough to use as is
+    pub(crate) fn fresh(&self, query: &StoreQuery, country: &str) -> Option<StorePage> {
+        self.get(query, country)
+            .filter(|page| Utc::now() - page.fetched_at < Duration::minutes(CACHE_TTL_MINUTES))
+    }
+
+    pub(crate) fn put(&self, query: &StoreQuery, page: &StorePage) -> Result<()> {
+        let path = self.path(query, &page.country);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string(page)?)?;
+        crate::logs::transcript::touched("wrote", &path);
+
+        Ok(())
+    }
+
+    /// Country remembered for an account
+    pub(crate) fn country(&self, account_id: &str) -> Option<String> {
+        let country = fs::read_to_string(self.country_path(account_id)).ok()?;
+        Some(country.trim().to_string()).filter(|country| !country.is_empty())
+    }
+
+    pub(crate) fn set_country(&self, account_id: &str, country: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.country_path(account_id), country)?;
+        Ok(())
+    }
+
+    fn country_path(&self, account_id: &str) -> PathBuf {
+        self.dir.join(format!("country_{}", account_id))
+    }
+
+    fn path(&self, query: &StoreQuery, country: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", query.cache_key(country)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_element_becomes_offer() {
+        let element: SearchElement = serde_json::from_value(serde_json::json!({
+            "title": "Hades",
+            "id": "offer1",
+            "namespace": "min",
+            "description": "Defy the god of the dead",
+            "seller": { "name": "Supergiant Games" },
+            "productSlug": "hades/home",
+            "keyImages": [
+                { "type": "Thumbnail", "url": "https://cdn/thumb.jpg" },
+                { "type": "OfferImageWide", "url": "https://cdn/wide.jpg" }
+            ],
+            "tags": [
+                { "id": "1216", "name": "Action", "groupName": "genre" },
+                { "id": "9547", "name": "Windows", "groupName": "platform" }
+            ],
+            "price": { "totalPrice": {
+                "discountPrice": 1249, "originalPrice": 2499, "currencyCode": "EUR",
+                "fmtPrice": { "originalPrice": "€24.99", "discountPrice": "€12.49" }
+            } }
+        }))
+        .unwrap();
+
+        let offer = element.into_offer();
+        assert_eq!(
+            offer.url.as_deref(),
+            Some("https://store.epicgames.com/p/hades")
+        );
+        assert_eq!(offer.image.as_deref(), Some("https://cdn/wide.jpg"));
+        assert_eq!(offer.genres, vec!["Action"]);
+        assert_eq!(offer.seller.as_deref(), Some("Supergiant Games"));
+        let price = offer.price.unwrap();
+        assert_eq!(price.current, "€12.49");
+        assert_eq!(price.discount, 50);
+        assert!(!price.free);
+    }
+
+    #[test]
+    fn test_cache_keeps_answers_per_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = StoreCache {
+            dir: dir.path().to_path_buf(),
+        };
+        let query = StoreQuery {
+            keywords: "Hollow Knight".to_string(),
+            genre: Some("Platformer".to_string()),
+            ..StoreQuery::default()
+        };
+        let mut page = StorePage {
+            offers: Vec::new(),
+            total: 81,
+            giveaways: Vec::new(),
+            country: "IT".to_string(),
+            fetched_at: Utc::now(),
+            stale: false,
+        };
+        assert_eq!(page.pages(), 3);
+
+        cache.put(&query, &page).unwrap();
+        assert!(cache.fresh(&query, "IT").is_some());
+        assert!(cache.get(&query, "US").is_none());
+        let next = StoreQuery {
+            page: 1,
+            ..query.clone()
+        };
+        assert!(cache.get(&next, "IT").is_none());
+
+        page.fetched_at = Utc::now() - Duration::minutes(CACHE_TTL_MINUTES + 1);
+        cache.put(&query, &page).unwrap();
+        assert!(cache.fresh(&query, "IT").is_none());
+        assert!(cache.get(&query, "IT").is_some());
+    }
+
+    #[test]
+    fn test_unknown_genre_is_rejected() {
+        let query = StoreQuery {
+            genre: Some("rpg".to_string()),
+            ..StoreQuery::default()
+        };
+        assert_eq!(query.tag().unwrap(), Some("1367"));
+
+        let query = StoreQuery {
+            genre: Some("Cooking".to_string()),
+            ..StoreQuery::default()
+        };
+        assert!(query.tag().is_err());
+    }
+}
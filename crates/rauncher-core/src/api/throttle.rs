@@ -0,0 +1,258 @@
+//! Keeping Epic's API calls within what an account may send. Requests draw from a shared
+//! budget that refills at a steady rate, a `429 Too Many Requests` pauses every request for
+//! as long as Epic asks, and the same catalog item asked for by several tasks at once is only
+//! requested once.
+
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::logs::transcript::RecordedSend;
+use crate::Result;
+
+/// Requests sent per second once the burst is spent
+const REQUESTS_PER_SECOND: f64 = 10.0;
+
+/// Requests sent at once after a quiet spell
+const BURST: f64 = 20.0;
+
+/// Tries of a request Epic answered with 429, the first one included
+const RATE_LIMITED_TRIES: u32 = 4;
+
+/// Longest pause honoured from a `Retry-After` header
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+/// Request budget shared by every clone of a client
+pub(super) struct RateLimiter {
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+    /// Set by a 429; nothing is sent before then
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(now: Instant) -> Self {
+        Self {
+            tokens: BURST,
+            refilled: now,
+            paused_until: None,
+        }
+    }
+
+    /// Take a request from the budget, or say how long until one is available
+    fn take(&mut self, now: Instant) -> std::result::Result<(), Duration> {
+        if let Some(until) = self.paused_until.filter(|until| *until > now) {
+            return Err(until - now);
+        }
+
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * REQUESTS_PER_SECOND).min(BURST);
+        self.refilled = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / REQUESTS_PER_SECOND,
+            ))
+        }
+    }
+
+    /// Send nothing for `delay`, then start again from an empty budget
+    fn pause(&mut self, now: Instant, delay: Duration) {
+        let until = now + delay;
+        self.paused_until = Some(self.paused_until.map_or(until, |paused| paused.max(until)));
+        self.tokens = 0.0;
+        self.refilled = until;
+    }
+}
+
+impl RateLimiter {
+    pub(super) fn new() -> Self {
+        Self {
+            bucket: Mutex::new(Bucket::new(Instant::now())),
+        }
+    }
+
+    /// Wait until a request may be sent
+    async fn acquire(&self) {
+        loop {
+            let taken = self
+                .bucket
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .take(Instant::now());
+            match taken {
+                Ok(()) => return,
+                Err(wait) => tokio::time::sleep(wait).await,
+            }
+        }
+    }
+
+    fn pause(&self, delay: Duration) {
+        self.bucket
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pause(Instant::now(), delay);
+    }
+}
+
+pub(super) trait LimitedSend {
+    /// Send within the budget of `limiter`, trying again after a 429 for as long as Epic
+    /// asks. The last answer is returned as it is when Epic keeps refusing.
+    async fn send_limited(self, limiter: &RateLimiter) -> reqwest::Result<Response>;
+}
+
+impl LimitedSend for RequestBuilder {
+    async fn send_limited(self, limiter: &RateLimiter) -> reqwest::Result<Response> {
+        let mut request = self;
+        for attempt in 1.. {
+            // Requests with a streamed body can't be sent twice
+            let retry = request.try_clone();
+            limiter.acquire().await;
+            let response = request.send_recorded().await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS || attempt == RATE_LIMITED_TRIES {
+                return Ok(response);
+            }
+            let Some(next) = retry else {
+                return Ok(response);
+            };
+
+            let delay = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after)
+                .unwrap_or(Duration::from_secs(1 << attempt));
+            log::warn!(
+                "Epic is limiting requests; waiting {}s before trying again",
+                delay.as_secs()
+            );
+            limiter.pause(delay);
+            request = next;
+        }
+        unreachable!("the last try returns")
+    }
+}
+
+/// The delay of a `Retry-After` header given in seconds, capped at `MAX_RETRY_AFTER`
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let seconds: u64 = value.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// Requests in flight by key: the same one asked for again while it runs isn't sent twice,
+/// the later callers get the answer of the first
+pub(super) struct InFlight<T> {
+    slots: Mutex<HashMap<String, Arc<tokio::sync::Mutex<Option<T>>>>>,
+}
+
+impl<T: Clone> InFlight<T> {
+    pub(super) fn new() -> Self {
+        Self {
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run `request` under `key`, unless a request with that key is running already. A
+    /// failed request isn't shared; the next caller waiting sends its own.
+    pub(super) async fn run(
+        &self,
+        key: &str,
+        request: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let slot = Arc::clone(
+            self.slots
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .entry(key.to_string())
+                .or_default(),
+        );
+
+        let result = {
+            let mut answer = slot.lock().await;
+            match answer.as_ref() {
+                Some(answer) => Ok(answer.clone()),
+                None => {
+                    let result = request.await;
+                    if let Ok(value) = &result {
+                        *answer = Some(value.clone());
+                    }
+                    result
+                }
+            }
+        };
+
+        drop(slot);
+        // Nobody else holds the slots left, so they're done with
+        self.slots
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, slot| Arc::strong_count(slot) > 1);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_bucket() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(start);
+        for _ in 0..BURST as usize {
+            assert!(bucket.take(start).is_ok());
+        }
+        let wait = bucket.take(start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(100));
+        assert!(bucket.take(start + wait).is_ok());
+
+        bucket.pause(start, Duration::from_secs(5));
+        assert_eq!(
+            bucket.take(start + Duration::from_secs(1)).unwrap_err(),
+            Duration::from_secs(4)
+        );
+        let resumed = start + Duration::from_secs(5);
+        assert!(bucket.take(resumed).is_err());
+        assert!(bucket.take(resumed + Duration::from_millis(100)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("3"), Some(Duration::from_secs(3)));
+        assert_eq!(parse_retry_after(" 600 "), Some(MAX_RETRY_AFTER));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2026 07:28:00 GMT"), None);
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_sends_once() {
+        let in_flight = InFlight::new();
+        let sent = AtomicUsize::new(0);
+        let request = || async {
+            sent.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok("Kiwi Quest".to_string())
+        };
+
+        let (a, b) = tokio::join!(
+            in_flight.run("kiwi", request()),
+            in_flight.run("kiwi", request())
+        );
+        assert_eq!(a.unwrap(), "Kiwi Quest");
+        assert_eq!(b.unwrap(), "Kiwi Quest");
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+        assert!(in_flight.slots.lock().unwrap().is_empty());
+
+        // Once done, the same key is sent again
+        in_flight.run("kiwi", request()).await.unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+    }
+}
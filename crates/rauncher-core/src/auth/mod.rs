@@ -0,0 +1,668 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::{Error, Result};
+
+mod web_login;
+pub use web_login::{authorization_code_from, WebLogin};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthToken {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_at: DateTime<Utc>,
+    pub account_id: String,
+}
+
+impl AuthToken {
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+
+    pub fn save(&self) -> Result<()> {
+        // TODO: Encrypt tokens at rest instead of storing as plain JSON
+        // TODO: Use OS keychain/credential manager for secure storage
+
+        self.save_to(&Self::auth_path()?)
+    }
+
+    fn save_to(&self, auth_path: &Path) -> Result<()> {
+        if let Some(parent) = auth_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(auth_path, &contents)?;
+        crate::logs::transcript::touched("wrote", auth_path);
+
+        // Set restrictive file permissions (0600) on Unix systems
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(auth_path)?.permissions();
+            perms.set_mode(0o600);
+            fs::set_permissions(auth_path, perms)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn load() -> Result<Option<Self>> {
+        // TODO: Decrypt tokens if encryption is implemented
+        // TODO: Handle migration from old token formats
+
+        match Self::load_from(&Self::auth_path()?)? {
+            Some(token) => Ok(Some(token)),
+            None => Self::from_env(crate::providers::EPIC),
+        }
+    }
+
+    /// Token provisioned through the environment, for logins that can't be interactive
+    /// (containers, CI). `RAUNCHER_<PROVIDER>_TOKEN` holds the contents of an `auth.json`,
+    /// `RAUNCHER_<PROVIDER>_TOKEN_FILE` the path of one, e.g. a Docker secret.
+    /// Only consulted when no token is stored, so refreshed tokens take over once saved.
+    fn from_env(provider: &str) -> Result<Option<Self>> {
+        let var = format!("RAUNCHER_{}_TOKEN", provider.to_uppercase());
+        let file_var = format!("{}_FILE", var);
+
+        let contents = match (std::env::var(&var), std::env::var_os(&file_var)) {
+            (Ok(contents), _) => contents,
+            (Err(_), Some(path)) => fs::read_to_string(&path).map_err(|e| {
+                Error::Auth(format!("Failed to read {} ({:?}): {}", file_var, path, e))
+            })?,
+            (Err(_), None) => return Ok(None),
+        };
+
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| Error::Auth(format!("Invalid token in {}: {}", var, e)))
+    }
+
+    fn load_from(auth_path: &Path) -> Result<Option<Self>> {
+        if !auth_path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(auth_path)?;
+        let token: AuthToken = serde_json::from_str(&contents)?;
+
+        Ok(Some(token))
+    }
+
+    pub fn delete() -> Result<()> {
+        let auth_path = Self::auth_path()?;
+
+        if auth_path.exists() {
+            fs::remove_file(&auth_path)?;
+        }
+
+        Ok(())
+    }
+
+    fn auth_path() -> Result<PathBuf> {
+        let data_dir = Config::data_dir()?;
+        Ok(data_dir.join("auth.json"))
+    }
+
+    /// Load the token of a store other than Epic, whose login lives in `AuthManager`
+    pub fn load_for(provider: &str) -> Result<Option<Self>> {
+        match Self::load_from(&Self::provider_auth_path(provider)?)? {
+            Some(token) => Ok(Some(token)),
+            None => Self::from_env(provider),
+        }
+    }
+
+    pub fn save_for(&self, provider: &str) -> Result<()> {
+        self.save_to(&Self::provider_auth_path(provider)?)
+    }
+
+    pub fn delete_for(provider: &str) -> Result<()> {
+        let auth_path = Self::provider_auth_path(provider)?;
+
+        if auth_path.exists() {
+            fs::remove_file(&auth_path)?;
+        }
+
+        Ok(())
+    }
+
+    fn provider_auth_path(provider: &str) -> Result<PathBuf> {
+        Ok(Config::data_dir()?
+            .join("providers")
+            .join(provider)
+            .join("auth.json"))
+    }
+}
+
+/// Name of the file recording which profile `auth.json` belongs to
+const ACTIVE_PROFILE_FILE: &str = "active_profile";
+
+fn profiles_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("profiles"))
+}
+
+fn profile_path(name: &str) -> Result<PathBuf> {
+    Ok(profiles_dir()?.join(format!("{}.json", name)))
+}
+
+fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !name.starts_with('.');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Auth(format!(
+            "Invalid profile name '{}': use letters, digits, '-', '_' or '.'",
+            name
+        )))
+    }
+}
+
+/// Holds the active account's token. Every account that has logged in is also kept
+/// as a named profile, so switching accounts doesn't require a new device login.
+#[derive(Clone)]
+pub struct AuthManager {
+    token: Option<AuthToken>,
+    profile: Option<String>,
+}
+
+impl AuthManager {
+    pub fn new() -> Result<Self> {
+        let token = AuthToken::load()?;
+        let active_path = Config::data_dir()?.join(ACTIVE_PROFILE_FILE);
+        let profile = fs::read_to_string(active_path)
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| validate_profile_name(name).is_ok());
+
+        Ok(Self { token, profile })
+    }
+
+    /// Name of the active profile, if an account is logged in
+    pub fn active_profile(&self) -> Option<String> {
+        self.token.as_ref().map(|t| self.profile_name(t))
+    }
+
+    /// Profiles default to the account id until they are named explicitly
+    fn profile_name(&self, token: &AuthToken) -> String {
+        self.profile
+            .clone()
+            .unwrap_or_else(|| token.account_id.clone())
+    }
+
+    /// List the names of all stored profiles
+    pub fn list_profiles() -> Result<Vec<String>> {
+        let dir = profiles_dir()?;
+
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut profiles = Vec::new();
+
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    profiles.push(name.to_string());
+                }
+            }
+        }
+
+        profiles.sort();
+        Ok(profiles)
+    }
+
+    /// Keep the active token under its profile so it can be switched back to later
+    fn stash_active(&self) -> Result<()> {
+        if let Some(token) = &self.token {
+            token.save_to(&profile_path(&self.profile_name(token))?)?;
+        }
+        Ok(())
+    }
+
+    fn write_active_profile(name: Option<&str>) -> Result<()> {
+        let active_path = Config::data_dir()?.join(ACTIVE_PROFILE_FILE);
+
+        match name {
+            Some(name) => {
+                if let Some(parent) = active_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(active_path, name)?;
+            }
+            None if active_path.exists() => fs::remove_file(active_path)?,
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Activate another stored profile, keeping the current one stored
+    pub fn switch_profile(&mut self, name: &str) -> Result<()> {
+        validate_profile_name(name)?;
+
+        if self.active_profile().as_deref() == Some(name) {
+            return Ok(());
+        }
+
+        let token = AuthToken::load_from(&profile_path(name)?)?.ok_or_else(|| {
+            Error::Auth(format!(
+                "No stored profile '{}'. Log in with 'rauncher auth --profile {}'",
+                name, name
+            ))
+        })?;
+
+        self.stash_active()?;
+        self.activate(name.to_string(), token)
+    }
+
+    /// Log out of the active account without forgetting it, so another account can log in
+    pub fn prepare_new_profile(&mut self) -> Result<()> {
+        self.stash_active()?;
+        AuthToken::delete()?;
+        Self::write_active_profile(None)?;
+        self.token = None;
+        self.profile = None;
+        Ok(())
+    }
+
+    /// Store a freshly obtained token as the named profile and activate it
+    pub fn set_token_for_profile(&mut self, name: &str, token: AuthToken) -> Result<()> {
+        validate_profile_name(name)?;
+        self.stash_active()?;
+        self.activate(name.to_string(), token)
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        if let Some(token) = &self.token {
+            !token.is_expired()
+        } else {
+            false
+        }
+    }
+
+    /// Whether an account is logged in, even when its access token expired and has to be
+    /// refreshed before it's used
+    pub fn has_session(&self) -> bool {
+        self.token
+            .as_ref()
+            .is_some_and(|token| !token.is_expired() || !token.refresh_token.is_empty())
+    }
+
+    /// When the access token expires, or expired, if an account is logged in
+    pub fn token_expires_at(&self) -> Option<DateTime<Utc>> {
+        self.token.as_ref().map(|token| token.expires_at)
+    }
+
+    pub fn get_token(&self) -> Result<&AuthToken> {
+        match &self.token {
+            Some(token) if !token.is_expired() => Ok(token),
+            _ => Err(Error::NotAuthenticated),
+        }
+    }
+
+    /// Check if token will expire soon (within 5 minutes)
+    pub fn token_needs_refresh(&self) -> bool {
+        if let Some(token) = &self.token {
+            let now = chrono::Utc::now();
+            let time_until_expiry = token.expires_at.signed_duration_since(now);
+            time_until_expiry.num_minutes() < 5
+        } else {
+            false
+        }
+    }
+
+    pub fn get_refresh_token(&self) -> Option<String> {
+        self.token.as_ref().map(|t| t.refresh_token.clone())
+    }
+
+    /// Store a token under the profile of its account: a refresh keeps the active
+    /// profile, while another account's token is kept apart instead of replacing it
+    pub fn set_token(&mut self, token: AuthToken) -> Result<()> {
+        let name = match &self.token {
+            Some(current) if current.account_id == token.account_id => self.profile_name(current),
+            _ => {
+                self.stash_active()?;
+                Self::profile_for_account(&token.account_id)?
+                    .unwrap_or_else(|| token.account_id.clone())
+            }
+        };
+        self.activate(name, token)
+    }
+
+    /// Name of the stored profile already holding a token for the account
+    fn profile_for_account(account_id: &str) -> Result<Option<String>> {
+        for name in Self::list_profiles()? {
+            if let Some(stored) = AuthToken::load_from(&profile_path(&name)?)? {
+                if stored.account_id == account_id {
+                    return Ok(Some(name));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn activate(&mut self, name: String, token: AuthToken) -> Result<()> {
+        token.save()?;
+        token.save_to(&profile_path(&name)?)?;
+        Self::write_active_profile(Some(&name))?;
+        self.profile = Some(name);
+        self.token = Some(token);
+        Ok(())
+    }
+
+    /// Log out of the active account and forget its stored profile
+    pub fn logout(&mut self) -> Result<()> {
+        if let Some(name) = self.active_profile() {
+            let path = profile_path(&name)?;
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        AuthToken::delete()?;
+        Self::write_active_profile(None)?;
+        self.token = None;
+        self.profile = None;
+        Ok(())
+    }
+
+    /// Garantisce che il token sia valido: se prossimo alla scadenza o scaduto,
+    /// prova a effettuare il refresh tramite l'implementazione di TokenRefresher.
+    /// Restituisce un riferimento al token valido in memoria.
+    pub async fn ensure_valid_token<T: TokenRefresher + ?Sized>(
+        &mut self,
+        refresher: &T,
+    ) -> Result<&AuthToken> {
+        // Se non abbiamo token → non autenticato
+        if self.token.is_none() {
+            return Err(Error::NotAuthenticated);
+        }
+
+        // Valutiamo se serve refresh senza mantenere un prestito lungo
+        let needs_refresh = self.token_needs_refresh()
+            || self.token.as_ref().map(|t| t.is_expired()).unwrap_or(true);
+
+        if !needs_refresh {
+            return self.get_token();
+        }
+
+        self.refresh_with(refresher).await
+    }
+
+    /// Refresh the token now, however long it has left: the store may turn down a token
+    /// before it expires, as during downloads that outlive it
+    pub async fn refresh_with<T: TokenRefresher + ?Sized>(
+        &mut self,
+        refresher: &T,
+    ) -> Result<&AuthToken> {
+        let refresh = self
+            .get_refresh_token()
+            .ok_or_else(|| Error::NotAuthenticated)?;
+
+        let new_token = refresher.refresh_token(&refresh).await?;
+        self.set_token(new_token)?;
+        self.get_token()
+    }
+}
+
+impl Default for AuthManager {
+    fn default() -> Self {
+        Self::new().unwrap_or(Self {
+            token: None,
+            profile: None,
+        })
+    }
+}
+
+/// Trait minimo per permettere all'AuthManager di effettuare il refresh del token
+/// senza dipendere direttamente da un tipo concreto del client API.
+#[async_trait]
+pub trait TokenRefresher: Send + Sync {
+    async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::isolate_data_dir;
+    use chrono::Duration;
+
+    struct MockRefresher;
+    #[async_trait]
+    impl TokenRefresher for MockRefresher {
+        async fn refresh_token(&self, _refresh_token: &str) -> Result<AuthToken> {
+            Ok(AuthToken {
+                access_token: "new_access".into(),
+                refresh_token: "new_refresh".into(),
+                expires_at: Utc::now() + Duration::hours(1),
+                account_id: "acc".into(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_auth_manager_not_authenticated_by_default() {
+        let manager = AuthManager {
+            token: None,
+            profile: None,
+        };
+        assert!(!manager.is_authenticated());
+    }
+
+    #[test]
+    fn test_expired_token_with_refresh_token_is_a_session() {
+        let mut manager = AuthManager {
+            token: Some(AuthToken {
+                access_token: "access".into(),
+                refresh_token: "refresh".into(),
+                expires_at: Utc::now() - Duration::hours(1),
+                account_id: "acc".into(),
+            }),
+            profile: None,
+        };
+        assert!(!manager.is_authenticated());
+        assert!(manager.has_session());
+
+        if let Some(token) = &mut manager.token {
+            token.refresh_token.clear();
+        }
+        assert!(!manager.has_session());
+    }
+
+    #[test]
+    fn test_auth_token_expiry() {
+        let expired_token = AuthToken {
+            access_token: "test".to_string(),
+            refresh_token: "test".to_string(),
+            expires_at: Utc::now() - chrono::Duration::hours(1),
+            account_id: "test".to_string(),
+        };
+        assert!(expired_token.is_expired());
+
+        let valid_token = AuthToken {
+            access_token: "test".to_string(),
+            refresh_token: "test".to_string(),
+            expires_at: Utc::now() + chrono::Duration::hours(1),
+            account_id: "test".to_string(),
+        };
+        assert!(!valid_token.is_expired());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_valid_token_no_refresh_needed() {
+        let token = AuthToken {
+            access_token: "a".into(),
+            refresh_token: "r".into(),
+            expires_at: Utc::now() + Duration::minutes(30),
+            account_id: "acc".into(),
+        };
+        let mut manager = AuthManager {
+            token: Some(token.clone()),
+            profile: None,
+        };
+        let got = manager.ensure_valid_token(&MockRefresher).await.unwrap();
+        assert_eq!(got.access_token, token.access_token);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_valid_token_does_refresh_on_expiring() {
+        isolate_data_dir();
+        let token = AuthToken {
+            access_token: "old".into(),
+            refresh_token: "refresh".into(),
+            expires_at: Utc::now() + Duration::minutes(1), // within 5 minutes threshold
+            account_id: "acc".into(),
+        };
+        let mut manager = AuthManager {
+            token: Some(token),
+            profile: None,
+        };
+        let got = manager.ensure_valid_token(&MockRefresher).await.unwrap();
+        assert_eq!(got.access_token, "new_access");
+        // and persisted
+        assert_eq!(manager.get_token().unwrap().access_token, "new_access");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_with_refreshes_valid_token() {
+        isolate_data_dir();
+        let token = AuthToken {
+            access_token: "rejected".into(),
+            refresh_token: "refresh".into(),
+            expires_at: Utc::now() + Duration::minutes(30),
+            account_id: "acc".into(),
+        };
+        let mut manager = AuthManager {
+            token: Some(token),
+            profile: None,
+        };
+        let got = manager.refresh_with(&MockRefresher).await.unwrap();
+        assert_eq!(got.access_token, "new_access");
+    }
+
+    #[test]
+    fn test_token_from_env() {
+        let token = AuthToken {
+            access_token: "env_access".into(),
+            refresh_token: "env_refresh".into(),
+            expires_at: Utc::now() + Duration::hours(1),
+            account_id: "acc".into(),
+        };
+        let dir = tempfile::TempDir::new().unwrap();
+        let secret = dir.path().join("token.json");
+        fs::write(&secret, serde_json::to_string(&token).unwrap()).unwrap();
+
+        assert!(AuthToken::from_env("envtest").unwrap().is_none());
+
+        std::env::set_var("RAUNCHER_ENVTEST_TOKEN_FILE", &secret);
+        let loaded = AuthToken::from_env("envtest").unwrap().unwrap();
+        assert_eq!(loaded.access_token, "env_access");
+
+        std::env::set_var("RAUNCHER_ENVTEST_TOKEN", "not json");
+        assert!(AuthToken::from_env("envtest").is_err());
+    }
+
+    #[test]
+    fn test_switch_profile_keeps_previous_account() {
+        isolate_data_dir();
+        let token_for = |account: &str| AuthToken {
+            access_token: format!("{}_access", account),
+            refresh_token: "refresh".into(),
+            expires_at: Utc::now() + Duration::hours(1),
+            account_id: account.into(),
+        };
+
+        let mut manager = AuthManager {
+            token: None,
+            profile: None,
+        };
+        manager
+            .set_token_for_profile("switch-main", token_for("main"))
+            .unwrap();
+        manager
+            .set_token_for_profile("switch-alt", token_for("alt"))
+            .unwrap();
+        assert_eq!(manager.active_profile().as_deref(), Some("switch-alt"));
+
+        manager.switch_profile("switch-main").unwrap();
+        assert_eq!(manager.get_token().unwrap().access_token, "main_access");
+
+        let profiles = AuthManager::list_profiles().unwrap();
+        assert!(profiles.contains(&"switch-main".to_string()));
+        assert!(profiles.contains(&"switch-alt".to_string()));
+
+        assert!(manager.switch_profile("switch-missing").is_err());
+        assert!(manager.switch_profile("../escape").is_err());
+    }
+
+    #[test]
+    fn test_set_token_keeps_accounts_in_their_own_profiles() {
+        isolate_data_dir();
+        let token_for = |account: &str, access: &str| AuthToken {
+            access_token: access.into(),
+            refresh_token: "refresh".into(),
+            expires_at: Utc::now() + Duration::hours(1),
+            account_id: account.into(),
+        };
+
+        let mut manager = AuthManager {
+            token: None,
+            profile: None,
+        };
+        manager
+            .set_token_for_profile("keep-work", token_for("keep-work-acc", "work_1"))
+            .unwrap();
+
+        // A refresh of the same account stays in the named profile
+        manager
+            .set_token(token_for("keep-work-acc", "work_2"))
+            .unwrap();
+        assert_eq!(manager.active_profile().as_deref(), Some("keep-work"));
+
+        // Another account gets a profile of its own and leaves the first one alone
+        manager
+            .set_token(token_for("keep-home-acc", "home_1"))
+            .unwrap();
+        assert_eq!(manager.active_profile().as_deref(), Some("keep-home-acc"));
+        let work = AuthToken::load_from(&profile_path("keep-work").unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(work.access_token, "work_2");
+
+        // Logging back into the first account finds its named profile again
+        manager
+            .set_token(token_for("keep-work-acc", "work_3"))
+            .unwrap();
+        assert_eq!(manager.active_profile().as_deref(), Some("keep-work"));
+        assert_eq!(manager.get_token().unwrap().access_token, "work_3");
+    }
+
+    #[test]
+    fn test_provider_tokens_are_stored_separately() {
+        isolate_data_dir();
+        let token = AuthToken {
+            access_token: "gog_access".into(),
+            refresh_token: "gog_refresh".into(),
+            expires_at: Utc::now() + Duration::hours(1),
+            account_id: "gog_user".into(),
+        };
+
+        token.save_for("test-store").unwrap();
+        let loaded = AuthToken::load_for("test-store").unwrap().unwrap();
+        assert_eq!(loaded.access_token, "gog_access");
+        assert!(AuthToken::load_for("other-store").unwrap().is_none());
+
+        AuthToken::delete_for("test-store").unwrap();
+        assert!(AuthToken::load_for("test-store").unwrap().is_none());
+    }
+}
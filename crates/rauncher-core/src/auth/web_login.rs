@@ -0,0 +1,402 @@
+//! Browser login for Epic accounts whose two-factor or captcha challenges break the device
+//! code flow. A listener on localhost serves a page that links to Epic's own login and takes
+//! the authorization code back, so the code never has to be copied into a terminal.
+
+use rand::RngCore;
+use std::net::{Ipv4Addr, TcpListener as StdTcpListener};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::api::EpicClient;
+use crate::{Error, Result};
+
+/// How long to wait for the browser before giving up, matching the device code flow
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Largest request the listener reads; the pasted redirect page is well under this
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+/// A local listener waiting for the authorization code from a browser login
+pub struct WebLogin {
+    listener: StdTcpListener,
+    port: u16,
+    /// Epic's login for the client the code will be exchanged with
+    login_url: String,
+    /// Random value embedded in the served form, so codes posted by other pages are refused
+    nonce: String,
+}
+
+impl WebLogin {
+    /// Listen on a free localhost port, for a login to be finished by `client`
+    pub fn bind(client: &EpicClient) -> Result<Self> {
+        let listener = StdTcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+        log::debug!("Browser login listening on port {}", port);
+
+        let mut bytes = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut bytes);
+
+        Ok(Self {
+            listener,
+            port,
+            login_url: client.web_login_url(),
+            nonce: bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+        })
+    }
+
+    /// The page to open in the browser
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}/", self.port)
+    }
+
+    /// Serve the login page until a code arrives, then return it
+    pub async fn wait_for_code(self) -> Result<String> {
+        let listener = TcpListener::from_std(self.listener)?;
+
+        tokio::time::timeout(LOGIN_TIMEOUT, serve(listener, &self.login_url, &self.nonce))
+            .await
+            .map_err(|_| Error::Auth("Timed out waiting for the browser login".to_string()))?
+    }
+}
+
+async fn serve(listener: TcpListener, login_url: &str, nonce: &str) -> Result<String> {
+    let port = listener.local_addr()?.port();
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        match handle(&mut stream, port, login_url, nonce).await {
+            Ok(Some(code)) => return Ok(code),
+            Ok(None) => {}
+            Err(e) => log::debug!("Browser login request failed: {}", e),
+        }
+    }
+}
+
+/// Answer one request, returning the authorization code if it carried one. Callbacks must
+/// carry the nonce of the served form: any page open in the browser can post to localhost,
+/// and would otherwise log rauncher in to an account of its choosing. Requests must also be
+/// addressed to the listener itself, so a page that rebinds its own domain to 127.0.0.1 can't
+/// read the form, nonce included.
+async fn handle(
+    stream: &mut TcpStream,
+    port: u16,
+    login_url: &str,
+    nonce: &str,
+) -> std::io::Result<Option<String>> {
+    let request = read_request(stream).await?;
+    let (path, query) = request
+        .target
+        .split_once('?')
+        .unwrap_or((request.target.as_str(), ""));
+
+    let (status, page, code) = match (request.method.as_str(), path) {
+        _ if !is_local_host(request.host.as_deref(), port) => {
+            log::warn!(
+                "Refused a browser login request for host {:?}",
+                request.host.as_deref().unwrap_or_default()
+            );
+            ("421 Misdirected Request", String::new(), None)
+        }
+        ("GET", "/") => ("200 OK", login_page(login_url, nonce, None), None),
+        ("GET", "/callback") | ("POST", "/callback") => {
+            let form = if request.method == "GET" {
+                query
+            } else {
+                request.body.as_str()
+            };
+            let submitted = form_value(form, "code");
+            if form_value(form, "state").as_deref() != Some(nonce) {
+                log::warn!("Refused a browser login callback that didn't come from its page");
+                (
+                    "403 Forbidden",
+                    login_page(
+                        login_url,
+                        nonce,
+                        Some("This page was out of date; paste the code again"),
+                    ),
+                    None,
+                )
+            } else {
+                match submitted.as_deref().and_then(authorization_code_from) {
+                    Some(code) => ("200 OK", DONE_PAGE.to_string(), Some(code)),
+                    None => (
+                        "400 Bad Request",
+                        login_page(
+                            login_url,
+                            nonce,
+                            Some("No authorization code found in what was pasted"),
+                        ),
+                        None,
+                    ),
+                }
+            }
+        }
+        _ => ("404 Not Found", login_page(login_url, nonce, None), None),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        page.len(),
+        page
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(code)
+}
+
+struct Request {
+    method: String,
+    target: String,
+    /// The `Host` header
+    host: Option<String>,
+    body: String,
+}
+
+/// Whether a `Host` header names this listener, as the browser was sent to it
+fn is_local_host(host: Option<&str>, port: u16) -> bool {
+    let Some((name, host_port)) = host.and_then(|host| host.trim().rsplit_once(':')) else {
+        return false;
+    };
+    host_port.parse() == Ok(port) && (name == "127.0.0.1" || name.eq_ignore_ascii_case("localhost"))
+}
+
+/// Read the request line, headers and a `Content-Length` body
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Request> {
+    let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if buf.len() > MAX_REQUEST_BYTES {
+            return Err(invalid("request too large"));
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(invalid("connection closed mid-request"));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default().to_string();
+
+    let headers: Vec<(&str, &str)> = lines.filter_map(|line| line.split_once(':')).collect();
+    let header = |wanted: &str| {
+        headers
+            .iter()
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(wanted))
+            .map(|(_, value)| value.trim())
+    };
+    let host = header("host").map(str::to_string);
+    let content_length = header("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    if content_length > MAX_REQUEST_BYTES {
+        return Err(invalid("request too large"));
+    }
+
+    let mut body = buf[header_end..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(Request {
+        method,
+        target,
+        host,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+/// Look up a field of a query string or urlencoded form body
+fn form_value(encoded: &str, key: &str) -> Option<String> {
+    let url = reqwest::Url::parse(&format!("http://localhost/?{}", encoded)).ok()?;
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Pick the authorization code out of what the user pasted: the JSON Epic's redirect page
+/// shows after logging in, a URL carrying `code=`, or the bare code
+pub fn authorization_code_from(input: &str) -> Option<String> {
+    let input = input.trim();
+
+    if input.starts_with('{') {
+        let page: serde_json::Value = serde_json::from_str(input).ok()?;
+        return page
+            .get("authorizationCode")
+            .and_then(|code| code.as_str())
+            .filter(|code| !code.is_empty())
+            .map(str::to_string);
+    }
+
+    if let Ok(url) = reqwest::Url::parse(input) {
+        return url
+            .query_pairs()
+            .find(|(key, _)| key == "code")
+            .map(|(_, value)| value.into_owned());
+    }
+
+    let is_code = !input.is_empty() && input.chars().all(|c| c.is_ascii_alphanumeric());
+    is_code.then(|| input.to_string())
+}
+
+fn login_page(login_url: &str, nonce: &str, error: Option<&str>) -> String {
+    let error = error
+        .map(|e| format!("<p class=\"error\">{}</p>", e))
+        .unwrap_or_default();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>rauncher login</title>{style}</head>
+<body>
+<h1>Log in to Epic Games</h1>
+<ol>
+<li><a href="{login}" target="_blank" rel="noopener">Open the Epic Games login</a> and sign in,
+completing any two-factor or captcha check it asks for.</li>
+<li>Epic then shows a short page of text containing <code>authorizationCode</code>.
+Copy all of it (or just the code) into the box below.</li>
+</ol>
+{error}
+<form method="post" action="/callback">
+<input type="hidden" name="state" value="{nonce}">
+<textarea name="code" rows="5" placeholder="Paste here" autofocus></textarea>
+<button type="submit">Log in</button>
+</form>
+</body>
+</html>
+"#,
+        style = STYLE,
+        login = login_url,
+        nonce = nonce,
+        error = error
+    )
+}
+
+const DONE_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>rauncher login</title></head>
+<body>
+<h1>Code received</h1>
+<p>rauncher is finishing the login; you can close this tab.</p>
+</body>
+</html>
+"#;
+
+const STYLE: &str = "<style>body{font-family:sans-serif;max-width:40em;margin:3em auto}\
+textarea{width:100%}.error{color:#c00}</style>";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorization_code_from_pasted_input() {
+        let redirect_page = r#"{"warning":"Do not share this code","redirectUrl":
+            "https://localhost/launcher/authorized?code=abc123","authorizationCode":"abc123",
+            "sid":null}"#;
+        assert_eq!(
+            authorization_code_from(redirect_page).as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            authorization_code_from("https://localhost/launcher/authorized?code=def456").as_deref(),
+            Some("def456")
+        );
+        assert_eq!(
+            authorization_code_from("  0a1b2c\n").as_deref(),
+            Some("0a1b2c")
+        );
+
+        assert_eq!(
+            authorization_code_from(r#"{"authorizationCode":null}"#),
+            None
+        );
+        assert_eq!(authorization_code_from("not a code"), None);
+        assert_eq!(authorization_code_from(""), None);
+    }
+
+    #[test]
+    fn test_form_value_decodes() {
+        assert_eq!(
+            form_value("code=%7B%22a%22%3A1%7D+x&other=2", "code").as_deref(),
+            Some("{\"a\":1} x")
+        );
+        assert_eq!(form_value("other=2", "code"), None);
+    }
+
+    #[tokio::test]
+    async fn test_callback_needs_the_nonce() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server =
+            tokio::spawn(async move { serve(listener, "https://epic.test/login", "n0nce").await });
+        let client = reqwest::Client::new();
+
+        let page = client.get(&url).send().await.unwrap().text().await.unwrap();
+        assert!(page.contains(r#"name="state" value="n0nce""#));
+
+        for form in ["code=forged", "code=forged&state=other"] {
+            let response = client
+                .post(format!("{}/callback", url))
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(form)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+        }
+
+        let response = client
+            .post(format!("{}/callback", url))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body("code=abc123&state=n0nce")
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(server.await.unwrap().unwrap(), "abc123");
+    }
+
+    #[test]
+    fn test_local_hosts() {
+        assert!(is_local_host(Some("127.0.0.1:8400"), 8400));
+        assert!(is_local_host(Some("LocalHost:8400"), 8400));
+        assert!(!is_local_host(Some("127.0.0.1:8401"), 8400));
+        assert!(!is_local_host(Some("127.0.0.1"), 8400));
+        assert!(!is_local_host(Some("rebound.example:8400"), 8400));
+        assert!(!is_local_host(None, 8400));
+    }
+
+    #[tokio::test]
+    async fn test_other_hosts_are_refused() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { serve(listener, "https://epic.test/login", "n0nce").await });
+
+        let response = reqwest::Client::new()
+            .get(format!("http://127.0.0.1:{}/", port))
+            .header("Host", format!("rebound.example:{}", port))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::MISDIRECTED_REQUEST);
+        assert!(!response.text().await.unwrap().contains("n0nce"));
+    }
+}
@@ -0,0 +1,217 @@
+//! How the user organizes their library: categories, favorite games and hidden ones (such as
+//! giveaway shovelware). Kept in `<data dir>/collections.json` and keyed by `provider:app_name`,
+//! so games that aren't installed can be organized too.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::providers;
+use crate::Result;
+
+const COLLECTIONS_FILE: &str = "collections.json";
+
+/// What the user set on one game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameTags {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub favorite: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+}
+
+impl GameTags {
+    pub fn has_category(&self, category: &str) -> bool {
+        self.categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(category.trim()))
+    }
+}
+
+/// Which games a listing shows
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// Only games in this category, matched case-insensitively
+    pub category: Option<String>,
+    /// Only favorites
+    pub favorites: bool,
+    /// Only hidden games; otherwise hidden games are left out
+    pub hidden: bool,
+}
+
+/// The tags of every game the user organized
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Collections {
+    #[serde(default)]
+    games: BTreeMap<String, GameTags>,
+}
+
+impl Collections {
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        crate::logs::transcript::touched("wrote", &path);
+
+        Ok(())
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join(COLLECTIONS_FILE))
+    }
+
+    /// Tags of a game, by address; bare app names are Epic games, as with installs
+    pub fn tags(&self, address: &str) -> GameTags {
+        self.games.get(&key(address)).cloned().unwrap_or_default()
+    }
+
+    /// Every category in use, sorted
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = Vec::new();
+        for category in self.games.values().flat_map(|tags| &tags.categories) {
+            if !categories.iter().any(|c| c.eq_ignore_ascii_case(category)) {
+                categories.push(category.clone());
+            }
+        }
+        categories.sort_by_key(|c| c.to_lowercase());
+        categories
+    }
+
+    /// Put a game in a category. Returns false if it already was in it.
+    pub fn add_category(&mut self, address: &str, category: &str) -> bool {
+        let category = category.trim();
+        // Reuse the spelling of a category that already exists
+        let category = self
+            .categories()
+            .into_iter()
+            .find(|c| c.eq_ignore_ascii_case(category))
+            .unwrap_or_else(|| category.to_string());
+
+        self.update(address, |tags| {
+            if tags.has_category(&category) {
+                return false;
+            }
+            tags.categories.push(category);
+            true
+        })
+    }
+
+    /// Take a game out of a category. Returns false if it wasn't in it.
+    pub fn remove_category(&mut self, address: &str, category: &str) -> bool {
+        self.update(address, |tags| {
+            let before = tags.categories.len();
+            tags.categories
+                .retain(|c| !c.eq_ignore_ascii_case(category.trim()));
+            tags.categories.len() != before
+        })
+    }
+
+    pub fn set_favorite(&mut self, address: &str, favorite: bool) {
+        self.update(address, |tags| tags.favorite = favorite);
+    }
+
+    pub fn set_hidden(&mut self, address: &str, hidden: bool) {
+        self.update(address, |tags| tags.hidden = hidden);
+    }
+
+    /// Whether a listing with `filter` shows the game
+    pub fn shows(&self, address: &str, filter: &Filter) -> bool {
+        let tags = self.tags(address);
+
+        tags.hidden == filter.hidden
+            && (!filter.favorites || tags.favorite)
+            && filter
+                .category
+                .as_deref()
+                .is_none_or(|category| tags.has_category(category))
+    }
+
+    /// Change a game's tags, forgetting the game once nothing is set on it
+    fn update<T>(&mut self, address: &str, change: impl FnOnce(&mut GameTags) -> T) -> T {
+        let key = key(address);
+        let tags = self.games.entry(key.clone()).or_default();
+        let result = change(tags);
+        if *tags == GameTags::default() {
+            self.games.remove(&key);
+        }
+        result
+    }
+}
+
+fn key(address: &str) -> String {
+    let (provider, app_name) = providers::parse_address(address);
+    providers::qualified_id(provider.unwrap_or(providers::EPIC), app_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories_are_case_insensitive() {
+        let mut collections = Collections::default();
+        assert!(collections.add_category("Fortnite", "Shooters"));
+        assert!(!collections.add_category("epic:Fortnite", "shooters"));
+        assert!(collections.add_category("gog:1207658924", " SHOOTERS "));
+        assert!(collections.add_category("gog:1207658924", "RPG"));
+
+        assert_eq!(collections.categories(), vec!["RPG", "Shooters"]);
+        assert_eq!(
+            collections.tags("gog:1207658924").categories,
+            vec!["Shooters", "RPG"]
+        );
+
+        assert!(collections.remove_category("Fortnite", "SHOOTERS"));
+        assert!(!collections.remove_category("Fortnite", "Shooters"));
+        assert!(!collections.games.contains_key("epic:Fortnite"));
+    }
+
+    #[test]
+    fn test_filter_leaves_out_hidden_games() {
+        let mut collections = Collections::default();
+        collections.set_favorite("epic:Kiwi", true);
+        collections.add_category("epic:Kiwi", "Roguelikes");
+        collections.set_hidden("epic:Shovelware", true);
+
+        let all = Filter::default();
+        assert!(collections.shows("epic:Kiwi", &all));
+        assert!(collections.shows("epic:Fortnite", &all));
+        assert!(!collections.shows("epic:Shovelware", &all));
+
+        let favorites = Filter {
+            favorites: true,
+            ..Filter::default()
+        };
+        assert!(collections.shows("epic:Kiwi", &favorites));
+        assert!(!collections.shows("epic:Fortnite", &favorites));
+
+        let category = Filter {
+            category: Some("roguelikes".to_string()),
+            ..Filter::default()
+        };
+        assert!(collections.shows("Kiwi", &category));
+        assert!(!collections.shows("epic:Fortnite", &category));
+
+        let hidden = Filter {
+            hidden: true,
+            ..Filter::default()
+        };
+        assert!(collections.shows("epic:Shovelware", &hidden));
+        assert!(!collections.shows("epic:Kiwi", &hidden));
+    }
+}
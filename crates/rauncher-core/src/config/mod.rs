@@ -0,0 +1,794 @@
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{Error, Result};
+
+/// Range accepted for `ui_scale`
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 4.0;
+
+// TODO: Add more configuration options:
+// - download_threads: Number of concurrent downloads
+// - bandwidth_limit: Optional download speed limit
+// - cdn_region: Preferred CDN region
+// - auto_update: Auto-update games in background
+// - proxy_settings: HTTP/SOCKS proxy configuration
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub install_dir: PathBuf,
+    pub log_level: String,
+    /// Format of the log files: "text" or "json" (one object per line)
+    pub log_format: String,
+    /// Write a transcript of every run to the logs directory, shown by `rauncher last-run`
+    pub transcripts: bool,
+    /// Language for CLI and GUI messages, e.g. "it"; the system locale when unset
+    pub language: Option<String>,
+    /// GUI theme: "dark", "light", "high-contrast", or the name of a TOML file in the
+    /// `themes` directory next to this file; dark when unset
+    pub theme: Option<String>,
+    /// GUI scale factor (physical pixels per point, e.g. 2.0 on a 4K screen); the system's
+    /// display scaling when unset
+    pub ui_scale: Option<f32>,
+    /// Opt in to downloading owned Unreal Engine marketplace assets
+    pub ue_assets_enabled: bool,
+    /// Number of save backups kept per game (0 keeps all of them)
+    pub backup_retention: usize,
+    /// Upload a game's cloud saves when it exits, if rauncher waits for it (`launch --wait`)
+    pub sync_saves_on_exit: bool,
+    /// Which events show a desktop notification
+    pub notifications: NotificationSettings,
+    /// URLs `rauncher daemon` POSTs events to, one `[[webhooks]]` table each
+    pub webhooks: Vec<WebhookSettings>,
+    /// Answers given when nobody can be asked (the daemon), by question kind,
+    /// e.g. `save_conflict = "use_cloud"`; unlisted questions get the safest answer
+    pub prompt_answers: BTreeMap<String, String>,
+    /// Where to download prebuilt shader caches from, with `{app_name}`, `{version}` and
+    /// `{provider}` filled in per game; no caches are downloaded when unset
+    pub shader_cache_url: Option<String>,
+    /// Size limit of the cache of downloaded chunks, reused by reinstalls and retried
+    /// updates; 0 turns the cache off
+    pub cache_size_mb: u64,
+    /// Reserve each file's disk space before downloading it; turn off on filesystems where
+    /// that is slow, and files are written sparse instead
+    pub preallocate: bool,
+    /// Where installs download and assemble their files before they're moved into the
+    /// install directory, e.g. a fast SSD in front of a library on a hard drive; files are
+    /// downloaded in place when unset
+    pub staging_dir: Option<PathBuf>,
+    /// Which queued download the GUI starts next: "manual" (queue order), "smallest_first"
+    /// or "priority"
+    pub download_order: QueueOrder,
+    /// What downloads do on a metered or roaming connection
+    pub metered: MeteredSettings,
+    /// Files `verify` hashes at once; 0 uses one per CPU core. Lower it on a hard drive,
+    /// where reading several files at a time makes the disk seek back and forth
+    pub verify_workers: usize,
+    /// How often the GUI reloads the library and checks installed games for updates, in
+    /// minutes; 0 only does it at login
+    pub library_refresh_minutes: u64,
+    /// Short names for games, e.g. `bl3 = "Catnip"`; every command takes them in place of
+    /// the app name
+    pub aliases: BTreeMap<String, String>,
+    /// What rauncher presents itself as to Epic; Epic's own launcher on Windows when unset
+    pub epic: EpicSettings,
+    /// Per-game settings, keyed by app name
+    pub games: BTreeMap<String, GameSettings>,
+}
+
+/// Desktop notifications sent by the GUI and the daemon, per event type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub install_finished: bool,
+    pub update_available: bool,
+    pub cloud_sync_failed: bool,
+    /// Wishlisted games going on sale or being given away
+    pub wishlist: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            install_finished: true,
+            update_available: true,
+            cloud_sync_failed: true,
+            wishlist: true,
+        }
+    }
+}
+
+/// An endpoint the daemon tells about installed updates, failed downloads and claimed
+/// giveaways
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookSettings {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// Events sent, by name (`update_installed`, `download_failed`, `update_run_failed`,
+    /// `free_game_claimed`); all of them when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+/// Body of a webhook request
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The event itself, as JSON
+    #[default]
+    Json,
+    /// A message for a Discord webhook (or anything that takes Discord's format)
+    Discord,
+}
+
+/// How the download queue picks the next job; ties keep queue order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueOrder {
+    /// In the order the jobs are listed, as enqueued and moved
+    #[default]
+    Manual,
+    /// The smallest download first, so small games finish while a large one waits
+    SmallestFirst,
+    /// The job with the highest priority first
+    Priority,
+}
+
+/// Downloads on metered and roaming connections, such as a phone's hotspot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeteredSettings {
+    pub action: MeteredAction,
+    /// Download speed limit in KiB/s with the `limit` action
+    pub limit_kbps: u64,
+}
+
+impl Default for MeteredSettings {
+    fn default() -> Self {
+        Self {
+            action: MeteredAction::default(),
+            limit_kbps: 512,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeteredAction {
+    /// Wait for an unmetered connection
+    #[default]
+    Pause,
+    /// Download at `limit_kbps`
+    Limit,
+    /// Download as on any other connection
+    Ignore,
+}
+
+/// Overrides of the client Epic's services see. Some endpoints answer differently per
+/// platform, so users on other systems may need to pose as a different one.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EpicSettings {
+    /// OAuth client id; logins belong to a client, so changing it means logging in again
+    pub client_id: Option<String>,
+    /// Secret of `client_id`, set together with it
+    pub client_secret: Option<String>,
+    /// `User-Agent` header sent with every request
+    pub user_agent: Option<String>,
+    /// Platform whose builds are installed, e.g. "Windows" or "Mac"
+    pub platform: Option<String>,
+}
+
+impl EpicSettings {
+    /// Check the overrides make a working client
+    pub(crate) fn validate(&self) -> Result<()> {
+        // One client's secret doesn't authenticate another
+        if self.client_id.is_some() != self.client_secret.is_some() {
+            return Err(Error::Config(
+                "epic.client_id and epic.client_secret must be set together".to_string(),
+            ));
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            if user_agent.trim().is_empty()
+                || reqwest::header::HeaderValue::from_str(user_agent).is_err()
+            {
+                return Err(Error::Config(format!(
+                    "Invalid epic.user_agent: '{}'. Must be non-empty printable text on one line",
+                    user_agent
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Settings applied when launching a specific game
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    /// Run through Feral's gamemoderun (Linux only)
+    pub gamemode: bool,
+    /// Show the MangoHud overlay (Linux only)
+    pub mangohud: bool,
+    /// Run inside a gamescope session (Linux only)
+    pub gamescope: bool,
+    /// Extra options passed to gamescope, e.g. "-W 2560 -H 1440 -f"
+    pub gamescope_args: String,
+    /// Wine or Proton executable used for Windows games on other platforms
+    pub wine: Option<PathBuf>,
+    /// Wine or Proton build installed with `rauncher runners install`, by name, e.g.
+    /// "GE-Proton9-20"; takes precedence over `wine`
+    pub runner: Option<String>,
+    /// Arguments passed to the game itself, e.g. "-windowed -dx11"
+    pub launch_args: String,
+    /// Executable to launch instead of the game's own, e.g. a mod loader, relative to the
+    /// install directory
+    pub executable: Option<PathBuf>,
+    /// Working directory for the game, relative to the install directory
+    pub working_dir: Option<PathBuf>,
+    /// Environment variables set for the game, e.g. `DXVK_HUD = "fps"`
+    pub env: BTreeMap<String, String>,
+    /// Shader cache source for this game, overriding the global `shader_cache_url`
+    pub shader_cache_url: Option<String>,
+    /// Run the game confined by bubblewrap or firejail (Linux only): the home directory is
+    /// hidden and only the install directory, Wine prefix and `sandbox_paths` are writable
+    pub sandbox: Option<SandboxTool>,
+    /// Let a sandboxed game use the network
+    pub sandbox_network: bool,
+    /// More directories a sandboxed game may read and write, e.g. where it keeps its saves
+    pub sandbox_paths: Vec<PathBuf>,
+    /// Files and folders the user manages, such as mods and config tweaks, relative to the
+    /// install directory with `/` separators. Installs and updates leave them as they are, and
+    /// `verify` neither reports nor deletes them.
+    pub protected: Vec<String>,
+    /// Where the game keeps its saves, relative to the install directory unless absolute.
+    /// Overrides the location the store gives for cloud saves.
+    pub save_path: Option<PathBuf>,
+    /// Shell command run after the game exits, if rauncher waits for it (`launch --wait`),
+    /// with `RAUNCHER_APP_NAME` and `RAUNCHER_EXIT_CODE` set
+    pub post_exit: Option<String>,
+}
+
+/// Tools a game can be sandboxed with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SandboxTool {
+    /// bubblewrap
+    Bwrap,
+    Firejail,
+}
+
+impl SandboxTool {
+    /// Name of the executable
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Bwrap => "bwrap",
+            Self::Firejail => "firejail",
+        }
+    }
+}
+
+impl GameSettings {
+    /// Whether `path`, relative to the install directory, is or is inside a protected path
+    pub fn is_protected(&self, path: &str) -> bool {
+        self.protected.iter().any(|protected| {
+            path.strip_prefix(protected.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let data_dir = Self::data_dir().expect("Failed to determine project directories");
+
+        Self {
+            install_dir: crate::sandbox::default_install_dir()
+                .unwrap_or_else(|| data_dir.join("games")),
+            log_level: "info".to_string(),
+            log_format: "text".to_string(),
+            transcripts: false,
+            language: None,
+            theme: None,
+            ui_scale: None,
+            ue_assets_enabled: false,
+            backup_retention: 10,
+            sync_saves_on_exit: false,
+            notifications: NotificationSettings::default(),
+            webhooks: Vec::new(),
+            prompt_answers: BTreeMap::new(),
+            shader_cache_url: None,
+            cache_size_mb: 1024,
+            preallocate: true,
+            staging_dir: None,
+            download_order: QueueOrder::default(),
+            metered: MeteredSettings::default(),
+            verify_workers: 0,
+            library_refresh_minutes: 30,
+            aliases: BTreeMap::new(),
+            epic: EpicSettings::default(),
+            games: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        // TODO: Handle config migration for version changes
+        // TODO: Add config file watching for hot-reload
+
+        let config_path = Self::config_path()?;
+
+        if config_path.exists() {
+            let contents = fs::read_to_string(&config_path)?;
+            let config: Config = toml::from_str(&contents)?;
+            config.validate()?;
+            Ok(config)
+        } else {
+            let config = Self::default();
+            config.save()?;
+            Ok(config)
+        }
+    }
+
+    /// Validate configuration values
+    pub(crate) fn validate(&self) -> Result<()> {
+        // Validate log level
+        let valid_log_levels = ["trace", "debug", "info", "warn", "error"];
+        if !valid_log_levels.contains(&self.log_level.as_str()) {
+            return Err(Error::Config(format!(
+                "Invalid log level: '{}'. Must be one of: {}",
+                self.log_level,
+                valid_log_levels.join(", ")
+            )));
+        }
+
+        let valid_log_formats = ["text", "json"];
+        if !valid_log_formats.contains(&self.log_format.as_str()) {
+            return Err(Error::Config(format!(
+                "Invalid log format: '{}'. Must be one of: {}",
+                self.log_format,
+                valid_log_formats.join(", ")
+            )));
+        }
+
+        if let Some(language) = &self.language {
+            let languages = crate::i18n::languages();
+            if !languages.contains(&language.as_str()) {
+                return Err(Error::Config(format!(
+                    "Unsupported language: '{}'. Must be one of: {}",
+                    language,
+                    languages.join(", ")
+                )));
+            }
+        }
+
+        if let Some(scale) = self.ui_scale {
+            if !(MIN_UI_SCALE..=MAX_UI_SCALE).contains(&scale) {
+                return Err(Error::Config(format!(
+                    "Invalid UI scale: {}. Must be between {} and {}",
+                    scale, MIN_UI_SCALE, MAX_UI_SCALE
+                )));
+            }
+        }
+
+        crate::prompt::validate_answers(&self.prompt_answers).map_err(Error::Config)?;
+        self.epic.validate()?;
+
+        for hook in &self.webhooks {
+            if !hook.url.starts_with("http://") && !hook.url.starts_with("https://") {
+                return Err(Error::Config(format!(
+                    "Invalid webhook URL: '{}'. Must be an http:// or https:// URL",
+                    hook.url
+                )));
+            }
+            let events = crate::notify::webhook::EVENTS;
+            if let Some(event) = hook.events.iter().find(|e| !events.contains(&e.as_str())) {
+                return Err(Error::Config(format!(
+                    "Unknown webhook event: '{}'. Must be one of: {}",
+                    event,
+                    events.join(", ")
+                )));
+            }
+        }
+
+        // Validate install directory - ensure parent exists or can be created
+        if let Some(parent) = self.install_dir.parent() {
+            if !parent.exists() {
+                return Err(Error::Config(format!(
+                    "Install directory parent does not exist: {}",
+                    parent.display()
+                )));
+            }
+        }
+
+        // Moving staged files into the library would otherwise delete them
+        if self.staging_dir.as_ref() == Some(&self.install_dir) {
+            return Err(Error::Config(
+                "staging_dir must be a different directory than install_dir".to_string(),
+            ));
+        }
+
+        // They'd be read as a store's address instead
+        if let Some(alias) = self
+            .aliases
+            .keys()
+            .find(|alias| crate::providers::parse_address(alias).0.is_some())
+        {
+            return Err(Error::Config(format!(
+                "Invalid alias '{}': aliases can't start with a store's prefix",
+                alias
+            )));
+        }
+
+        if let Some(platform) = &self.epic.platform {
+            if platform.is_empty() || !platform.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(Error::Config(format!(
+                    "Invalid Epic platform: '{}'. Must be a name like Windows or Mac",
+                    platform
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Settings for a game, falling back to defaults when none are configured
+    pub fn game_settings(&self, app_name: &str) -> GameSettings {
+        self.games.get(app_name).cloned().unwrap_or_default()
+    }
+
+    /// Look up a setting by dotted key, e.g. `notifications.install_finished`
+    pub fn get_value(&self, key: &str) -> Result<Option<toml::Value>> {
+        let root = self.to_value()?;
+        let mut value = &root;
+        for part in key.split('.') {
+            match value.get(part) {
+                Some(next) => value = next,
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(value.clone()))
+    }
+
+    /// Change a setting by dotted key, e.g. `games.Fortnite.env.DXVK_HUD`. The value is read
+    /// as TOML (`true`, `10`) where the setting has that type, and as a string otherwise.
+    /// Nothing changes unless the result is a valid configuration.
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
+        let typed = toml::from_str::<toml::Table>(&format!("value = {}", value))
+            .ok()
+            .and_then(|mut table| table.remove("value"));
+        let candidates = typed
+            .into_iter()
+            .chain(std::iter::once(toml::Value::String(value.to_string())));
+
+        // The typed reading's error says more than that of the string fallback
+        let mut first_error = None;
+        for candidate in candidates {
+            let mut root = self.to_value()?;
+            set_path(&mut root, key, candidate)?;
+            match Self::from_value(root) {
+                Ok(config) => {
+                    if config.get_value(key)?.is_none() {
+                        return Err(Error::Config(format!("Unknown setting: '{}'", key)));
+                    }
+                    *self = config;
+                    return Ok(());
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or_else(|| Error::Config(format!("Invalid value for '{}'", key))))
+    }
+
+    /// Remove a setting by dotted key, returning it to its default. Returns whether it was set.
+    pub fn unset_value(&mut self, key: &str) -> Result<bool> {
+        let mut root = self.to_value()?;
+        let (parent, name) = match key.rsplit_once('.') {
+            Some((parent, name)) => (Some(parent), name),
+            None => (None, key),
+        };
+
+        let mut table = root.as_table_mut();
+        for part in parent.into_iter().flat_map(|parent| parent.split('.')) {
+            table = table
+                .and_then(|table| table.get_mut(part))
+                .and_then(toml::Value::as_table_mut);
+        }
+        if table.and_then(|table| table.remove(name)).is_none() {
+            return Ok(false);
+        }
+
+        *self = Self::from_value(root)?;
+        Ok(true)
+    }
+
+    fn to_value(&self) -> Result<toml::Value> {
+        toml::Value::try_from(self).map_err(|e| Error::Config(e.to_string()))
+    }
+
+    fn from_value(value: toml::Value) -> Result<Self> {
+        let config: Config = value
+            .try_into()
+            .map_err(|e: toml::de::Error| Error::Config(e.message().to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let config_path = Self::config_path()?;
+
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let contents = toml::to_string_pretty(self).map_err(|e| Error::Config(e.to_string()))?;
+        fs::write(&config_path, contents)?;
+        crate::logs::transcript::touched("wrote", &config_path);
+
+        Ok(())
+    }
+
+    pub fn config_path() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("config.toml"))
+    }
+
+    /// `RAUNCHER_CONFIG_DIR`, else `$XDG_CONFIG_HOME/rauncher`, else the platform default
+    pub fn config_dir() -> Result<PathBuf> {
+        if let Some(dir) = dir_from_env("RAUNCHER_CONFIG_DIR", "XDG_CONFIG_HOME") {
+            return Ok(dir);
+        }
+
+        Ok(project_dirs()?.config_dir().to_path_buf())
+    }
+
+    /// `RAUNCHER_DATA_DIR`, else `$XDG_DATA_HOME/rauncher`, else the platform default
+    pub fn data_dir() -> Result<PathBuf> {
+        if let Some(dir) = dir_from_env("RAUNCHER_DATA_DIR", "XDG_DATA_HOME") {
+            return Ok(dir);
+        }
+
+        Ok(project_dirs()?.data_dir().to_path_buf())
+    }
+
+    /// Directory holding the per-game Wine prefixes
+    pub fn prefixes_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("prefixes"))
+    }
+
+    /// Directory holding the per-game DXVK/VKD3D shader caches
+    pub fn shader_cache_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("shader_cache"))
+    }
+
+    /// Directory holding recently downloaded chunks
+    pub fn chunk_cache_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("cache").join("chunks"))
+    }
+
+    /// Directory holding the Wine and Proton builds rauncher downloaded
+    pub fn runners_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("runners"))
+    }
+
+    /// Directory holding the save backup archives
+    pub fn backups_dir() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("backups"))
+    }
+}
+
+/// Set a dotted key in a TOML tree, creating the tables along the way
+fn set_path(root: &mut toml::Value, key: &str, value: toml::Value) -> Result<()> {
+    let parts: Vec<&str> = key.split('.').collect();
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(Error::Config(format!("Invalid setting name: '{}'", key)));
+    }
+
+    let (name, parents) = parts.split_last().expect("split yields at least one part");
+    let mut table = root
+        .as_table_mut()
+        .ok_or_else(|| Error::Config("Configuration is not a table".to_string()))?;
+    for part in parents {
+        table = table
+            .entry(part.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| Error::Config(format!("'{}' in '{}' is not a table", part, key)))?;
+    }
+    table.insert(name.to_string(), value);
+
+    Ok(())
+}
+
+/// Directory named by the environment. Checked before the platform lookup, which needs a
+/// home directory that containers often don't have.
+fn dir_from_env(override_var: &str, xdg_var: &str) -> Option<PathBuf> {
+    let non_empty = |var: &str| std::env::var_os(var).filter(|value| !value.is_empty());
+
+    non_empty(override_var)
+        .map(PathBuf::from)
+        .or_else(|| non_empty(xdg_var).map(|base| PathBuf::from(base).join("rauncher")))
+}
+
+fn project_dirs() -> Result<ProjectDirs> {
+    ProjectDirs::from("", "", "rauncher")
+        .ok_or_else(|| Error::Config("Failed to determine project directories".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_default() {
+        let config = Config::default();
+        assert_eq!(config.log_level, "info");
+        assert!(config.install_dir.to_string_lossy().contains("games"));
+    }
+
+    #[test]
+    fn test_config_serialization() {
+        let config = Config::default();
+        let serialized = toml::to_string(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(config.log_level, deserialized.log_level);
+    }
+
+    #[test]
+    fn test_config_missing_values_use_defaults() {
+        let config: Config = toml::from_str("log_level = \"debug\"").unwrap();
+        assert_eq!(config.log_level, "debug");
+        assert!(!config.ue_assets_enabled);
+        assert!(config.notifications.update_available);
+        assert_eq!(config.library_refresh_minutes, 30);
+    }
+
+    #[test]
+    fn test_dir_from_env() {
+        std::env::set_var("RAUNCHER_TEST_XDG_HOME", "/xdg");
+        assert_eq!(
+            dir_from_env("RAUNCHER_TEST_UNSET_DIR", "RAUNCHER_TEST_XDG_HOME"),
+            Some(PathBuf::from("/xdg/rauncher"))
+        );
+
+        std::env::set_var("RAUNCHER_TEST_OVERRIDE_DIR", "/data");
+        assert_eq!(
+            dir_from_env("RAUNCHER_TEST_OVERRIDE_DIR", "RAUNCHER_TEST_XDG_HOME"),
+            Some(PathBuf::from("/data"))
+        );
+
+        assert_eq!(
+            dir_from_env("RAUNCHER_TEST_UNSET_DIR", "RAUNCHER_TEST_UNSET_XDG"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_set_value_types_and_game_env() {
+        let mut config = Config {
+            install_dir: std::env::temp_dir().join("games"),
+            ..Default::default()
+        };
+
+        config.set_value("backup_retention", "3").unwrap();
+        assert_eq!(config.backup_retention, 3);
+        config
+            .set_value("notifications.install_finished", "false")
+            .unwrap();
+        assert!(!config.notifications.install_finished);
+
+        // Looks like a number but the setting is a string
+        config
+            .set_value("games.Fortnite.env.DXVK_HUD", "1")
+            .unwrap();
+        config
+            .set_value("games.Fortnite.env.PROTON_LOG", "1")
+            .unwrap();
+        let env = &config.game_settings("Fortnite").env;
+        assert_eq!(env.get("DXVK_HUD").map(String::as_str), Some("1"));
+        assert_eq!(
+            config.get_value("games.Fortnite.env.DXVK_HUD").unwrap(),
+            Some(toml::Value::String("1".to_string()))
+        );
+
+        assert!(config.set_value("backup_retention", "many").is_err());
+        assert!(config.set_value("log_level", "loud").is_err());
+        assert!(config.set_value("no_such_setting", "1").is_err());
+        config.set_value("ui_scale", "1.5").unwrap();
+        assert_eq!(config.ui_scale, Some(1.5));
+        assert!(config.set_value("ui_scale", "10").is_err());
+        assert_eq!(config.backup_retention, 3);
+
+        assert!(config.unset_value("games.Fortnite.env.DXVK_HUD").unwrap());
+        assert!(!config.unset_value("games.Fortnite.env.DXVK_HUD").unwrap());
+        assert_eq!(config.game_settings("Fortnite").env.len(), 1);
+        assert!(config.unset_value("backup_retention").unwrap());
+        assert_eq!(config.backup_retention, 10);
+
+        config.set_value("epic.platform", "Mac").unwrap();
+        assert_eq!(config.epic.platform.as_deref(), Some("Mac"));
+        assert!(config.set_value("epic.platform", "Windows/../Mac").is_err());
+    }
+
+    #[test]
+    fn test_game_settings_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [games.Fortnite]
+            gamemode = true
+            gamescope_args = "-f"
+            launch_args = "-windowed"
+            "#,
+        )
+        .unwrap();
+
+        let settings = config.game_settings("Fortnite");
+        assert!(settings.gamemode);
+        assert!(!settings.mangohud);
+        assert_eq!(settings.gamescope_args, "-f");
+        assert_eq!(settings.launch_args, "-windowed");
+        assert_eq!(config.game_settings("Other"), GameSettings::default());
+    }
+
+    #[test]
+    fn test_webhook_tables() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [[webhooks]]
+            url = "https://example.com/hook"
+
+            [[webhooks]]
+            url = "https://discord.com/api/webhooks/1/abc"
+            format = "discord"
+            events = ["update_installed"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.webhooks[0].format, WebhookFormat::Json);
+        assert!(config.webhooks[0].events.is_empty());
+        assert_eq!(config.webhooks[1].format, WebhookFormat::Discord);
+        let serialized = toml::to_string(&config).unwrap();
+        assert_eq!(
+            toml::from_str::<Config>(&serialized).unwrap().webhooks,
+            config.webhooks
+        );
+
+        config.webhooks[1].events.push("game_launched".to_string());
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Unknown webhook event"), "{}", error);
+        config.webhooks[1].events.pop();
+        config.webhooks[0].url = "example.com/hook".to_string();
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("Invalid webhook URL"), "{}", error);
+    }
+
+    #[test]
+    fn test_epic_settings_validation() {
+        let mut config = Config::default();
+        config.epic.client_id = Some("id".to_string());
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains("must be set together"), "{}", error);
+        config.epic.client_secret = Some("secret".to_string());
+        assert!(config.epic.validate().is_ok());
+
+        config.epic.user_agent = Some("Rauncher\n2".to_string());
+        let error = config.epic.validate().unwrap_err().to_string();
+        assert!(error.contains("Invalid epic.user_agent"), "{}", error);
+        config.epic.user_agent = Some(" ".to_string());
+        assert!(config.epic.validate().is_err());
+        config.epic.user_agent = Some("EpicGamesLauncher/16.0".to_string());
+        assert!(config.epic.validate().is_ok());
+    }
+}
@@ -0,0 +1,712 @@
+//! Queue of game downloads, shared between a frontend and the worker thread installing them
+//! one at a time.
+//!
+//! The frontend enqueues, reorders, pauses, resumes and cancels jobs and reads their progress
+//! from snapshots; the worker takes the next queued job, as the queue's `QueueOrder` picks it,
+//! and installs it, checking back with the queue between chunks so pausing and cancelling
+//! take effect mid-download. With the `Priority` order, a job given a higher priority than the
+//! running one preempts it: the running download stops and goes back to the queue, to be
+//! restarted from the chunk cache once its turn comes again.
+
+mod stats;
+
+pub(crate) use stats::SpeedMeter;
+pub use stats::TransferStats;
+
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::config::QueueOrder;
+use crate::games::{GameManager, InstallProgress};
+use crate::providers;
+use crate::{Error, Result};
+
+pub type JobId = u64;
+
+/// Speed samples kept per job, one a second
+const SPEED_SAMPLES: usize = 60;
+
+/// Time between speed samples
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often a paused download checks whether it was resumed
+const PAUSE_POLL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Active,
+    Paused,
+    Completed,
+    Failed(String),
+    Cancelled,
+}
+
+impl JobState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed(_) | Self::Cancelled)
+    }
+}
+
+/// How urgent a job is, for the `Priority` queue order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadJob {
+    pub id: JobId,
+    /// Game address: a bare Epic app name or `provider:app_name`
+    pub address: String,
+    pub state: JobState,
+    pub priority: JobPriority,
+    /// Bytes to download, once the worker has sized the job for the `SmallestFirst` order
+    pub size: Option<u64>,
+    /// Version being installed, once the manifest is known
+    pub version: Option<String>,
+    pub files_total: usize,
+    pub files_done: usize,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+    /// Speed and ETA as of the last progress event
+    pub stats: TransferStats,
+    /// Download speed in bytes per second, oldest sample first
+    pub speed_history: Vec<f64>,
+    /// Whether sizing the job was tried, so a failure isn't retried
+    #[serde(skip)]
+    sized: bool,
+    /// Whether the worker has picked the job up, so resuming continues it
+    #[serde(skip)]
+    started: bool,
+    /// Whether the running download is being stopped for a job with a higher priority
+    #[serde(skip)]
+    preempted: bool,
+    /// Whether `take_finished` has handed out the finished job yet
+    #[serde(skip)]
+    reported: bool,
+    /// When `speed_history` last got a sample
+    #[serde(skip)]
+    last_sample: Option<Instant>,
+}
+
+impl DownloadJob {
+    fn new(id: JobId, address: &str) -> Self {
+        Self {
+            id,
+            address: address.to_string(),
+            state: JobState::Queued,
+            priority: JobPriority::default(),
+            size: None,
+            version: None,
+            files_total: 0,
+            files_done: 0,
+            bytes_total: 0,
+            bytes_done: 0,
+            stats: TransferStats::default(),
+            speed_history: Vec::new(),
+            sized: false,
+            started: false,
+            preempted: false,
+            reported: false,
+            last_sample: None,
+        }
+    }
+
+    /// Fraction of the bytes downloaded, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        match self.state {
+            JobState::Completed => 1.0,
+            _ if self.bytes_total == 0 => 0.0,
+            _ => (self.bytes_done as f64 / self.bytes_total as f64).min(1.0) as f32,
+        }
+    }
+
+    /// Download speed in bytes per second, over the last second
+    pub fn speed(&self) -> f64 {
+        self.stats.speed
+    }
+
+    /// Time left at the recent average speed
+    pub fn eta(&self) -> Option<Duration> {
+        self.stats.eta
+    }
+
+    fn record(&mut self, event: &InstallProgress) {
+        match event {
+            InstallProgress::Started {
+                version,
+                files,
+                bytes,
+                ..
+            } => {
+                self.version = Some(version.clone());
+                self.files_total = *files;
+                self.bytes_total = *bytes;
+            }
+            InstallProgress::FileDone {
+                index, bytes_done, ..
+            } => {
+                self.files_done = *index;
+                self.bytes_done = *bytes_done;
+            }
+            InstallProgress::Transfer { stats, .. } => {
+                self.stats = *stats;
+                self.bytes_done = stats.bytes_done;
+                if stats.bytes_total > 0 {
+                    self.bytes_total = stats.bytes_total;
+                }
+
+                let now = Instant::now();
+                if self
+                    .last_sample
+                    .is_none_or(|at| now.duration_since(at) >= SAMPLE_INTERVAL)
+                {
+                    self.speed_history.push(stats.speed);
+                    if self.speed_history.len() > SPEED_SAMPLES {
+                        self.speed_history.remove(0);
+                    }
+                    self.last_sample = Some(now);
+                }
+            }
+            InstallProgress::Finished { .. }
+            | InstallProgress::VerifyStarted { .. }
+            | InstallProgress::FileVerified { .. } => {}
+        }
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<QueueState>,
+    /// Signalled whenever a job may have become ready to run
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct QueueState {
+    /// Jobs in queue order
+    jobs: Vec<DownloadJob>,
+    next_id: JobId,
+    order: QueueOrder,
+}
+
+impl QueueState {
+    /// Index of the queued job to run next. Jobs of unknown size go after the sized ones.
+    fn pick(&self) -> Option<usize> {
+        let mut queued = self
+            .jobs
+            .iter()
+            .enumerate()
+            .filter(|(_, job)| job.state == JobState::Queued);
+        // `min_by_key` keeps the first of equal jobs, so ties run in queue order
+        let picked = match self.order {
+            QueueOrder::Manual => queued.next(),
+            QueueOrder::SmallestFirst => queued.min_by_key(|(_, job)| job.size.unwrap_or(u64::MAX)),
+            QueueOrder::Priority => queued.min_by_key(|(_, job)| Reverse(job.priority)),
+        };
+        picked.map(|(index, _)| index)
+    }
+
+    /// Whether the running job `id` should make way for a queued one with a higher priority
+    fn should_preempt(&self, id: JobId) -> bool {
+        let Some(running) = self.jobs.iter().find(|job| job.id == id) else {
+            return false;
+        };
+        self.order == QueueOrder::Priority
+            && self
+                .jobs
+                .iter()
+                .any(|job| job.state == JobState::Queued && job.priority > running.priority)
+    }
+}
+
+/// Handle to the download queue; clones share the same jobs
+#[derive(Clone, Default)]
+pub struct DownloadQueue {
+    shared: Arc<Shared>,
+}
+
+impl DownloadQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.shared.state.lock().unwrap()
+    }
+
+    /// Change the job `id` with `change` if it still exists, waking the worker afterwards
+    fn update(&self, id: JobId, change: impl FnOnce(&mut DownloadJob)) {
+        if let Some(job) = self.lock().jobs.iter_mut().find(|job| job.id == id) {
+            change(job);
+        }
+        self.shared.changed.notify_all();
+    }
+
+    pub fn order(&self) -> QueueOrder {
+        self.lock().order
+    }
+
+    /// Change how the next job is picked; the running one carries on
+    pub fn set_order(&self, order: QueueOrder) {
+        self.lock().order = order;
+        self.shared.changed.notify_all();
+    }
+
+    pub fn set_priority(&self, id: JobId, priority: JobPriority) {
+        self.update(id, |job| job.priority = priority);
+    }
+
+    /// Give a job high priority and move it to the front of the queue. With the `Priority`
+    /// order, a running job of lower priority is preempted at its next chunk.
+    pub fn prioritize(&self, id: JobId) {
+        let mut state = self.lock();
+        if let Some(index) = state.jobs.iter().position(|job| job.id == id) {
+            let mut job = state.jobs.remove(index);
+            job.priority = JobPriority::High;
+            state.jobs.insert(0, job);
+        }
+        drop(state);
+        self.shared.changed.notify_all();
+    }
+
+    /// The unfinished job downloading `address`, if any
+    pub fn find(&self, address: &str) -> Option<JobId> {
+        self.lock()
+            .jobs
+            .iter()
+            .find(|job| job.address == address && !job.state.is_finished())
+            .map(|job| job.id)
+    }
+
+    /// Add a game to the end of the queue
+    pub fn enqueue(&self, address: &str) -> JobId {
+        let mut state = self.lock();
+        state.next_id += 1;
+        let id = state.next_id;
+        state.jobs.push(DownloadJob::new(id, address));
+        drop(state);
+
+        self.shared.changed.notify_all();
+        id
+    }
+
+    /// Snapshot of every job, in queue order
+    pub fn jobs(&self) -> Vec<DownloadJob> {
+        self.lock().jobs.clone()
+    }
+
+    /// Whether a game is queued or downloading
+    pub fn contains(&self, address: &str) -> bool {
+        self.lock()
+            .jobs
+            .iter()
+            .any(|job| job.address == address && !job.state.is_finished())
+    }
+
+    pub fn pause(&self, id: JobId) {
+        self.update(id, |job| {
+            if matches!(job.state, JobState::Queued | JobState::Active) {
+                job.state = JobState::Paused;
+            }
+        });
+    }
+
+    pub fn resume(&self, id: JobId) {
+        self.update(id, |job| {
+            if job.state == JobState::Paused {
+                job.state = if job.started {
+                    JobState::Active
+                } else {
+                    JobState::Queued
+                };
+            }
+        });
+    }
+
+    /// Cancel a job; an active download stops before its next chunk
+    pub fn cancel(&self, id: JobId) {
+        self.update(id, |job| {
+            if !job.state.is_finished() {
+                job.state = JobState::Cancelled;
+            }
+        });
+    }
+
+    /// Move a job one place towards the front of the queue
+    pub fn move_up(&self, id: JobId) {
+        let mut state = self.lock();
+        if let Some(index) = state.jobs.iter().position(|job| job.id == id) {
+            if index > 0 {
+                state.jobs.swap(index, index - 1);
+            }
+        }
+    }
+
+    /// Move a job one place towards the back of the queue
+    pub fn move_down(&self, id: JobId) {
+        let mut state = self.lock();
+        if let Some(index) = state.jobs.iter().position(|job| job.id == id) {
+            if index + 1 < state.jobs.len() {
+                state.jobs.swap(index, index + 1);
+            }
+        }
+    }
+
+    /// Move a job to where `target` is, as dropping a dragged row on another does: moved up,
+    /// it lands just before `target`; moved down, just after it
+    pub fn move_to(&self, id: JobId, target: JobId) {
+        let mut state = self.lock();
+        let Some(from) = state.jobs.iter().position(|job| job.id == id) else {
+            return;
+        };
+        let job = state.jobs.remove(from);
+        let to = state
+            .jobs
+            .iter()
+            .position(|job| job.id == target)
+            // Dropped below its old place: after the target, which moved up
+            .map(|to| if to >= from { to + 1 } else { to })
+            .unwrap_or(from);
+        state.jobs.insert(to, job);
+    }
+
+    /// Forget completed, failed and cancelled jobs
+    pub fn clear_finished(&self) {
+        self.lock()
+            .jobs
+            .retain(|job| !job.state.is_finished() || !job.reported);
+    }
+
+    /// Jobs that finished since the last call, each handed out once
+    pub fn take_finished(&self) -> Vec<DownloadJob> {
+        self.lock()
+            .jobs
+            .iter_mut()
+            .filter(|job| job.state.is_finished() && !job.reported)
+            .map(|job| {
+                job.reported = true;
+                job.clone()
+            })
+            .collect()
+    }
+
+    /// Block until a job is queued, then return the queued jobs the worker should size
+    /// before picking one: those not sized yet when ordering by size, none otherwise
+    fn wait_for_jobs(&self) -> Vec<(JobId, String)> {
+        let mut state = self.lock();
+        while state.pick().is_none() {
+            state = self.shared.changed.wait(state).unwrap();
+        }
+
+        if state.order != QueueOrder::SmallestFirst {
+            return Vec::new();
+        }
+        state
+            .jobs
+            .iter()
+            .filter(|job| job.state == JobState::Queued && !job.sized)
+            .map(|job| (job.id, job.address.clone()))
+            .collect()
+    }
+
+    /// Record a job's download size, `None` when it couldn't be found out
+    fn set_size(&self, id: JobId, size: Option<u64>) {
+        self.update(id, |job| {
+            job.size = size;
+            job.sized = true;
+        });
+    }
+
+    /// Block until a job is queued, then mark the one the queue order picks active and
+    /// return it
+    fn next(&self) -> (JobId, String) {
+        let mut state = self.lock();
+        loop {
+            if let Some(index) = state.pick() {
+                let job = &mut state.jobs[index];
+                job.state = JobState::Active;
+                job.started = true;
+                return (job.id, job.address.clone());
+            }
+            state = self.shared.changed.wait(state).unwrap();
+        }
+    }
+
+    fn finish(&self, id: JobId, result: Result<()>) {
+        self.update(id, |job| {
+            job.state = match result {
+                // Cancelling wins over whatever the install made of being stopped
+                _ if job.state == JobState::Cancelled => JobState::Cancelled,
+                // Back in line, starting over when picked again; a pause is kept
+                _ if job.preempted => {
+                    job.preempted = false;
+                    job.started = false;
+                    if job.state == JobState::Paused {
+                        JobState::Paused
+                    } else {
+                        JobState::Queued
+                    }
+                }
+                Ok(()) => JobState::Completed,
+                Err(e) => JobState::Failed(e.to_string()),
+            };
+        });
+    }
+
+    pub(crate) fn control(&self, id: JobId) -> DownloadControl {
+        DownloadControl {
+            queue: self.clone(),
+            id,
+        }
+    }
+
+    /// Start the thread installing queued games one at a time, each with a `GameManager`
+    /// from `make_manager`
+    pub fn spawn_worker<F>(&self, make_manager: F) -> JoinHandle<()>
+    where
+        F: Fn() -> Result<GameManager> + Send + 'static,
+    {
+        let queue = self.clone();
+
+        std::thread::spawn(move || {
+            let runtime = match tokio::runtime::Runtime::new() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    log::error!("Cannot start the download worker: {}", e);
+                    return;
+                }
+            };
+
+            loop {
+                for (id, address) in queue.wait_for_jobs() {
+                    let size = runtime.block_on(async {
+                        let (provider, app_name) = providers::parse_address(&address);
+                        let provider = provider.unwrap_or(providers::EPIC);
+                        let plan = make_manager()?
+                            .plan_install(provider, app_name, None)
+                            .await?;
+                        Ok::<_, Error>(plan.download_bytes)
+                    });
+                    if let Err(e) = &size {
+                        log::debug!("Cannot size the download of {}: {}", address, e);
+                    }
+                    queue.set_size(id, size.ok());
+                }
+
+                let (id, address) = queue.next();
+                log::info!("Downloading {}", address);
+
+                let result = runtime.block_on(async {
+                    let mut manager = make_manager()?;
+                    manager.set_download_control(queue.control(id));
+
+                    let (provider, app_name) = providers::parse_address(&address);
+                    let provider = provider.unwrap_or(providers::EPIC);
+                    manager.install_provider_game(provider, app_name).await
+                });
+
+                if let Err(e) = &result {
+                    log::warn!("Download of {} stopped: {}", address, e);
+                }
+                queue.finish(id, result);
+            }
+        })
+    }
+}
+
+/// The worker's link to its job in the queue
+#[derive(Clone)]
+pub struct DownloadControl {
+    queue: DownloadQueue,
+    id: JobId,
+}
+
+impl DownloadControl {
+    fn state(&self) -> Option<JobState> {
+        self.queue
+            .lock()
+            .jobs
+            .iter()
+            .find(|job| job.id == self.id)
+            .map(|job| job.state.clone())
+    }
+
+    /// Marks the job preempted when a queued job should run before it
+    fn preempt(&self) -> bool {
+        let mut state = self.queue.lock();
+        if !state.should_preempt(self.id) {
+            return false;
+        }
+        if let Some(job) = state.jobs.iter_mut().find(|job| job.id == self.id) {
+            job.preempted = true;
+        }
+        true
+    }
+
+    /// Wait while the job is paused; fails once it has been cancelled or preempted
+    pub(crate) async fn checkpoint(&self) -> Result<()> {
+        loop {
+            match self.state() {
+                Some(JobState::Cancelled) | None => {
+                    return Err(Error::Other("Download cancelled".to_string()))
+                }
+                Some(_) if self.preempt() => {
+                    return Err(Error::Other(
+                        "Download stopped for one with a higher priority".to_string(),
+                    ))
+                }
+                Some(JobState::Paused) => tokio::time::sleep(PAUSE_POLL).await,
+                Some(_) => return Ok(()),
+            }
+        }
+    }
+
+    pub(crate) fn record(&self, event: &InstallProgress) {
+        if let Some(job) = self
+            .queue
+            .lock()
+            .jobs
+            .iter_mut()
+            .find(|job| job.id == self.id)
+        {
+            job.record(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_run_in_queue_order() {
+        let queue = DownloadQueue::new();
+        let first = queue.enqueue("Fortnite");
+        let second = queue.enqueue("gog:1207658924");
+        let third = queue.enqueue("Celeste");
+
+        queue.move_up(third);
+        queue.pause(first);
+        assert_eq!(queue.next(), (third, "Celeste".to_string()));
+        assert_eq!(queue.next(), (second, "gog:1207658924".to_string()));
+
+        queue.resume(first);
+        queue.finish(third, Ok(()));
+        queue.cancel(second);
+        queue.finish(second, Err(Error::Other("Download cancelled".to_string())));
+
+        let finished = queue.take_finished();
+        assert_eq!(finished.len(), 2);
+        assert!(queue.take_finished().is_empty());
+        assert_eq!(queue.jobs()[2].state, JobState::Cancelled);
+
+        queue.clear_finished();
+        let jobs = queue.jobs();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!((jobs[0].id, &jobs[0].state), (first, &JobState::Queued));
+    }
+
+    #[test]
+    fn test_queue_orders() {
+        let queue = DownloadQueue::new();
+        let large = queue.enqueue("Fortnite");
+        let unknown = queue.enqueue("gog:1207658924");
+        let small = queue.enqueue("Celeste");
+        let medium = queue.enqueue("Hades");
+
+        queue.set_order(QueueOrder::SmallestFirst);
+        assert_eq!(queue.wait_for_jobs().len(), 4);
+        queue.set_size(large, Some(100 << 30));
+        queue.set_size(unknown, None);
+        queue.set_size(small, Some(1 << 30));
+        queue.set_size(medium, Some(20 << 30));
+        assert!(queue.wait_for_jobs().is_empty());
+        assert_eq!(queue.next().0, small);
+        assert_eq!(queue.next().0, medium);
+
+        queue.set_order(QueueOrder::Priority);
+        assert!(queue.wait_for_jobs().is_empty());
+        queue.set_priority(unknown, JobPriority::High);
+        assert_eq!(queue.next().0, unknown);
+
+        queue.set_priority(large, JobPriority::Low);
+        let last = queue.enqueue("Hollow Knight");
+        assert_eq!(queue.next().0, last);
+        assert_eq!(queue.next().0, large);
+    }
+
+    #[tokio::test]
+    async fn test_prioritize_preempts_running_job() {
+        let queue = DownloadQueue::new();
+        let running = queue.enqueue("Fortnite");
+        let waiting = queue.enqueue("Celeste");
+        let boosted = queue.enqueue("Hades");
+        assert_eq!(queue.next().0, running);
+
+        // Only the priority order preempts
+        let control = queue.control(running);
+        queue.prioritize(boosted);
+        assert_eq!(queue.jobs()[0].id, boosted);
+        assert!(control.checkpoint().await.is_ok());
+
+        queue.set_order(QueueOrder::Priority);
+        assert!(control.checkpoint().await.is_err());
+        queue.finish(running, Err(Error::Other("stopped".to_string())));
+        assert!(queue.take_finished().is_empty());
+        assert_eq!(queue.next().0, boosted);
+        assert_eq!(queue.next().0, running);
+
+        queue.move_to(waiting, running);
+        let order: Vec<_> = queue.jobs().iter().map(|job| job.id).collect();
+        assert_eq!(order, [boosted, waiting, running]);
+        queue.move_to(boosted, running);
+        let order: Vec<_> = queue.jobs().iter().map(|job| job.id).collect();
+        assert_eq!(order, [waiting, running, boosted]);
+    }
+
+    #[test]
+    fn test_move_to_takes_target_place_both_ways() {
+        let queue = DownloadQueue::new();
+        let a = queue.enqueue("Fortnite");
+        let b = queue.enqueue("Celeste");
+        let c = queue.enqueue("Hades");
+        let d = queue.enqueue("Hollow Knight");
+        let order = || queue.jobs().iter().map(|job| job.id).collect::<Vec<_>>();
+
+        // Up: before the target, which shifts back
+        queue.move_to(d, b);
+        assert_eq!(order(), [a, d, b, c]);
+        // Down: after the target, which shifts forward
+        queue.move_to(a, b);
+        assert_eq!(order(), [d, b, a, c]);
+        queue.move_to(d, c);
+        assert_eq!(order(), [b, a, c, d]);
+
+        // Onto itself or an unknown job: nothing moves
+        queue.move_to(a, a);
+        queue.move_to(a, 999);
+        assert_eq!(order(), [b, a, c, d]);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_active_download() {
+        let queue = DownloadQueue::new();
+        let id = queue.enqueue("Fortnite");
+        queue.next();
+
+        let control = queue.control(id);
+        assert!(control.checkpoint().await.is_ok());
+        queue.cancel(id);
+        assert!(control.checkpoint().await.is_err());
+    }
+}
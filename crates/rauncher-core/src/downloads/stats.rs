@@ -0,0 +1,162 @@
+//! Transfer statistics of a download, computed once in core so every frontend shows the same
+//! speed and ETA.
+
+use serde::{Serialize, Serializer};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Window of the instantaneous speed
+const CURRENT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Window of the rolling average, which the ETA is based on
+const AVERAGE_WINDOW: Duration = Duration::from_secs(10);
+
+/// Where a download stands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TransferStats {
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// Bytes per second over the last second
+    pub speed: f64,
+    /// Bytes per second over the last ten seconds
+    pub average_speed: f64,
+    /// Time left at the average speed; None until there is a speed to go by
+    #[serde(serialize_with = "seconds")]
+    pub eta: Option<Duration>,
+}
+
+/// Durations go out as whole seconds, which is all the ETA is accurate to
+fn seconds<S: Serializer>(eta: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    eta.map(|eta| eta.as_secs()).serialize(serializer)
+}
+
+impl TransferStats {
+    /// Fraction of the bytes done, from 0 to 1
+    pub fn fraction(&self) -> f32 {
+        if self.bytes_total == 0 {
+            return 0.0;
+        }
+        (self.bytes_done as f64 / self.bytes_total as f64).min(1.0) as f32
+    }
+}
+
+/// Turns byte counts, as they come in, into `TransferStats`
+pub(crate) struct SpeedMeter {
+    bytes_total: u64,
+    bytes_done: u64,
+    /// (time, bytes done then), oldest first, reaching back `AVERAGE_WINDOW`
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl SpeedMeter {
+    pub(crate) fn new(bytes_total: u64) -> Self {
+        Self::starting_at(bytes_total, Instant::now())
+    }
+
+    fn starting_at(bytes_total: u64, now: Instant) -> Self {
+        Self {
+            bytes_total,
+            bytes_done: 0,
+            samples: VecDeque::from([(now, 0)]),
+        }
+    }
+
+    pub(crate) fn add(&mut self, bytes: u64) {
+        self.add_at(bytes, Instant::now());
+    }
+
+    fn add_at(&mut self, bytes: u64, now: Instant) {
+        self.bytes_done += bytes;
+        self.samples.push_back((now, self.bytes_done));
+        // Keep one sample older than the window, so the window is always covered
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) > AVERAGE_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    pub(crate) fn stats(&self) -> TransferStats {
+        self.stats_at(Instant::now())
+    }
+
+    fn stats_at(&self, now: Instant) -> TransferStats {
+        let speed = self.speed_over(CURRENT_WINDOW, now);
+        let average_speed = self.speed_over(AVERAGE_WINDOW, now);
+        let left = self.bytes_total.saturating_sub(self.bytes_done);
+        let eta = (average_speed > 0.0)
+            .then(|| Duration::from_secs((left as f64 / average_speed).round() as u64));
+
+        TransferStats {
+            bytes_done: self.bytes_done,
+            bytes_total: self.bytes_total,
+            speed,
+            average_speed,
+            eta,
+        }
+    }
+
+    /// Bytes per second from the oldest sample within `window` until `now`
+    fn speed_over(&self, window: Duration, now: Instant) -> f64 {
+        let Some(&(since, bytes_then)) = self
+            .samples
+            .iter()
+            .find(|(time, _)| now.duration_since(*time) <= window)
+            .or(self.samples.back())
+        else {
+            return 0.0;
+        };
+
+        // Too short a span says nothing about the speed yet
+        let elapsed = now
+            .duration_since(since)
+            .as_secs_f64()
+            .max(window.as_secs_f64() / 4.0);
+        (self.bytes_done - bytes_then) as f64 / elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed_and_eta() {
+        let start = Instant::now();
+        let mut meter = SpeedMeter::starting_at(10_000, start);
+        assert_eq!(meter.stats_at(start).eta, None);
+
+        // 100 bytes a second for five seconds, then 500 bytes in the last one
+        for second in 1..=5 {
+            meter.add_at(100, start + Duration::from_secs(second));
+        }
+        meter.add_at(500, start + Duration::from_secs(6));
+
+        let stats = meter.stats_at(start + Duration::from_secs(6));
+        assert_eq!(stats.bytes_done, 1_000);
+        assert_eq!(stats.speed, 500.0);
+        assert!((stats.average_speed - 1_000.0 / 6.0).abs() < 0.01);
+        assert_eq!(stats.eta, Some(Duration::from_secs(54)));
+        assert_eq!(stats.fraction(), 0.1);
+
+        // Samples beyond the window no longer count
+        meter.add_at(0, start + Duration::from_secs(20));
+        let stats = meter.stats_at(start + Duration::from_secs(20));
+        assert_eq!(stats.speed, 0.0);
+        assert_eq!(stats.average_speed, 0.0);
+        assert_eq!(stats.eta, None);
+    }
+
+    #[test]
+    fn test_stats_serialize_eta_in_seconds() {
+        let stats = TransferStats {
+            bytes_done: 10,
+            bytes_total: 40,
+            speed: 5.0,
+            average_speed: 2.5,
+            eta: Some(Duration::from_millis(12_400)),
+        };
+        assert_eq!(
+            serde_json::to_string(&stats).unwrap(),
+            r#"{"bytes_done":10,"bytes_total":40,"speed":5.0,"average_speed":2.5,"eta":12}"#
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::games::InstalledGame;
+use crate::Result;
+
+/// Install record written by the official Epic Games Launcher (`Manifests/*.item`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EglManifest {
+    #[serde(rename = "AppName")]
+    pub app_name: String,
+    #[serde(rename = "DisplayName", default)]
+    pub display_name: String,
+    #[serde(rename = "AppVersionString", default)]
+    pub app_version: String,
+    #[serde(rename = "InstallLocation")]
+    pub install_location: PathBuf,
+    #[serde(rename = "LaunchExecutable", default)]
+    pub launch_executable: String,
+    #[serde(rename = "bIsIncompleteInstall", default)]
+    pub is_incomplete_install: bool,
+    /// The `.item` file the record was read from
+    #[serde(skip)]
+    pub file: PathBuf,
+}
+
+impl EglManifest {
+    /// Convert to a rauncher install record pointing at the existing files
+    pub fn to_installed_game(&self) -> InstalledGame {
+        InstalledGame {
+            app_name: self.app_name.clone(),
+            app_title: if self.display_name.is_empty() {
+                self.app_name.clone()
+            } else {
+                self.display_name.clone()
+            },
+            app_version: self.app_version.clone(),
+            install_path: self.install_location.clone(),
+            executable: self.launch_executable.clone(),
+            pinned: false,
+            provider: crate::providers::EPIC.to_string(),
+            install_size: 0,
+            download_size: 0,
+            installed_at: None,
+            last_played: None,
+            volume: None,
+            save_path_template: None,
+        }
+    }
+}
+
+/// Locate the official launcher's manifest directory on this machine
+pub fn manifests_dir() -> Option<PathBuf> {
+    crate::registry::epic_launcher_data_path().map(|data| data.join("Manifests"))
+}
+
+/// `dir`, or the manifest directory of the official launcher on this machine
+pub fn find_manifests_dir(dir: Option<&Path>) -> Result<PathBuf> {
+    match dir {
+        Some(dir) => Ok(dir.to_path_buf()),
+        None => manifests_dir().ok_or_else(|| {
+            crate::Error::Other(
+                "Epic Games Launcher not found; pass its Manifests directory explicitly"
+                    .to_string(),
+            )
+        }),
+    }
+}
+
+/// Read every complete install record in an EGL manifest directory
+pub fn read_manifests(dir: &Path) -> Result<Vec<EglManifest>> {
+    let mut manifests = Vec::new();
+
+    if !dir.exists() {
+        return Ok(manifests);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.extension().and_then(|s| s.to_str()) != Some("item") {
+            continue;
+        }
+
+        match fs::read_to_string(&path)
+            .map_err(crate::Error::from)
+            .and_then(|contents| Ok(serde_json::from_str::<EglManifest>(&contents)?))
+        {
+            Ok(manifest) if manifest.is_incomplete_install => {
+                log::debug!("Skipping incomplete EGL install: {}", manifest.app_name);
+            }
+            Ok(manifest) => manifests.push(EglManifest {
+                file: path,
+                ..manifest
+            }),
+            Err(e) => log::warn!("Ignoring unreadable EGL manifest {:?}: {}", path, e),
+        }
+    }
+
+    manifests.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+
+    Ok(manifests)
+}
+
+/// Write an install record for `game` into the manifest directory, so the official launcher
+/// lists it as installed where it is
+pub fn write_manifest(dir: &Path, game: &InstalledGame) -> Result<PathBuf> {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let guid: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+    let egstore = game.install_path.join(".egstore");
+
+    let manifest = json!({
+        "FormatVersion": 0,
+        "bIsIncompleteInstall": false,
+        "LaunchCommand": "",
+        "LaunchExecutable": game.executable,
+        "ManifestLocation": egstore,
+        "bIsApplication": true,
+        "bIsExecutable": true,
+        "bIsManaged": false,
+        "bNeedsValidation": false,
+        "bRequiresAuth": true,
+        "bCanRunOffline": true,
+        "AppName": game.app_name,
+        "BaseURLs": [],
+        "InstallLocation": game.install_path,
+        "InstallSize": game.install_size,
+        "InstallTags": [],
+        "InstallationGuid": guid,
+        "MainGameAppName": game.app_name,
+        "DisplayName": game.app_title,
+        "AppVersionString": game.app_version,
+        "StagingLocation": egstore.join("bps"),
+        "TechnicalType": "games,applications",
+        "AppCategories": ["public", "games", "applications"],
+    });
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.item", guid));
+    fs::write(&path, serde_json::to_string_pretty(&manifest)?)?;
+    crate::logs::transcript::touched("wrote", &path);
+    Ok(path)
+}
+
+/// Record that the game of `manifest` is now at `version`, keeping the rest of the record
+pub fn update_manifest(manifest: &EglManifest, version: &str) -> Result<()> {
+    let mut record: Value = serde_json::from_str(&fs::read_to_string(&manifest.file)?)?;
+    record["AppVersionString"] = json!(version);
+    fs::write(&manifest.file, serde_json::to_string_pretty(&record)?)?;
+    crate::logs::transcript::touched("wrote", &manifest.file);
+    Ok(())
+}
+
+/// Drop the official launcher's install record of a game, leaving its files alone
+pub fn remove_manifest(manifest: &EglManifest) -> Result<()> {
+    fs::remove_file(&manifest.file)?;
+    crate::logs::transcript::touched("removed", &manifest.file);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_manifests_skips_incomplete_installs() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("A.item"),
+            r#"{
+                "FormatVersion": 0,
+                "AppName": "Fortnite",
+                "DisplayName": "Fortnite",
+                "AppVersionString": "++Fortnite+Release-28.00",
+                "InstallLocation": "C:\\Program Files\\Epic Games\\Fortnite",
+                "LaunchExecutable": "FortniteGame/Binaries/Win64/FortniteLauncher.exe",
+                "bIsIncompleteInstall": false
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("B.item"),
+            r#"{ "AppName": "Half", "InstallLocation": "D:\\Half", "bIsIncompleteInstall": true }"#,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "not a manifest").unwrap();
+
+        let manifests = read_manifests(temp_dir.path()).unwrap();
+        assert_eq!(manifests.len(), 1);
+
+        let game = manifests[0].to_installed_game();
+        assert_eq!(game.app_name, "Fortnite");
+        assert_eq!(game.app_version, "++Fortnite+Release-28.00");
+        assert!(!game.pinned);
+    }
+
+    #[test]
+    fn test_read_manifests_sorted_by_app_name() {
+        let temp_dir = TempDir::new().unwrap();
+        for (file, app) in [("1.item", "Zeta"), ("2.item", "Alpha"), ("3.item", "Mid")] {
+            fs::write(
+                temp_dir.path().join(file),
+                format!(
+                    r#"{{ "AppName": "{}", "InstallLocation": "C:\\{}" }}"#,
+                    app, app
+                ),
+            )
+            .unwrap();
+        }
+
+        let names: Vec<String> = read_manifests(temp_dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|m| m.app_name)
+            .collect();
+        assert_eq!(names, ["Alpha", "Mid", "Zeta"]);
+    }
+
+    #[test]
+    fn test_write_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let game = InstalledGame {
+            app_name: "Kiwi".to_string(),
+            app_title: "Kiwi Quest".to_string(),
+            app_version: "1.2".to_string(),
+            install_path: PathBuf::from("C:\\Games\\Kiwi"),
+            executable: "Bin/Kiwi.exe".to_string(),
+            pinned: false,
+            provider: crate::providers::EPIC.to_string(),
+            install_size: 1024,
+            download_size: 0,
+            installed_at: None,
+            last_played: None,
+            volume: None,
+            save_path_template: None,
+        };
+
+        write_manifest(temp_dir.path(), &game).unwrap();
+        let manifests = read_manifests(temp_dir.path()).unwrap();
+        assert_eq!(manifests.len(), 1);
+        assert_eq!(manifests[0].to_installed_game().app_title, "Kiwi Quest");
+
+        update_manifest(&manifests[0], "1.3").unwrap();
+        let manifests = read_manifests(temp_dir.path()).unwrap();
+        assert_eq!(manifests[0].app_version, "1.3");
+        assert_eq!(manifests[0].launch_executable, "Bin/Kiwi.exe");
+
+        remove_manifest(&manifests[0]).unwrap();
+        assert!(read_manifests(temp_dir.path()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("API error: {0}")]
+    Api(String),
+
+    #[error("Authentication error: {0}")]
+    Auth(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("TOML error: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Not authenticated")]
+    NotAuthenticated,
+
+    #[error("Offline: this needs a connection to the store")]
+    Offline,
+
+    #[error("Session expired ({0}). Run 'rauncher auth' to log in again")]
+    ReauthRequired(String),
+
+    /// The store turned down an access token that refreshing may make good again
+    #[error("Access token rejected: {0}")]
+    TokenRejected(String),
+
+    #[error("Epic requires a captcha ({0}). Log in once at epicgames.com, then try again")]
+    CaptchaRequired(String),
+
+    #[error("Not available in your region: {0}")]
+    RegionBlocked(String),
+
+    #[error("{0} was written by a newer version of rauncher; upgrade rauncher to use it")]
+    IncompatibleFormat(String),
+
+    #[error("{0} is already running")]
+    AlreadyRunning(String),
+
+    #[error("Game not found: {0}")]
+    GameNotFound(String),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
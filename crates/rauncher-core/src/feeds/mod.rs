@@ -0,0 +1,240 @@
+//! RSS and iCalendar feeds of Epic's free games and pending game updates, written to
+//! `<data dir>/feeds` so feed readers and calendar apps can subscribe to them.
+
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::FreeGame;
+use crate::config::Config;
+use crate::games::InstalledGame;
+use crate::Result;
+
+pub const RSS_FILE: &str = "rauncher.rss";
+pub const ICAL_FILE: &str = "rauncher.ics";
+
+/// One item of a feed; becomes an RSS `<item>` and an iCalendar `VEVENT`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    /// Stable identifier, so readers don't show an entry twice across refreshes
+    pub id: String,
+    pub title: String,
+    pub link: Option<String>,
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl FeedEntry {
+    pub fn free_game(game: &FreeGame) -> Self {
+        let state = if game.starts_at > Utc::now() {
+            "Free from"
+        } else {
+            "Free since"
+        };
+
+        Self {
+            id: format!("free:{}:{}", game.title, game.starts_at.timestamp()),
+            title: format!("Free on Epic: {}", game.title),
+            link: game.url.clone(),
+            description: format!(
+                "{} {} until {}",
+                state,
+                game.starts_at.format("%Y-%m-%d %H:%M UTC"),
+                game.ends_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+            starts_at: game.starts_at,
+            ends_at: Some(game.ends_at),
+        }
+    }
+
+    /// An update to `version` found at `found_at`
+    pub fn update(game: &InstalledGame, version: &str, found_at: DateTime<Utc>) -> Self {
+        Self {
+            id: format!("update:{}:{}", game.id(), version),
+            title: format!("Update available: {} {}", game.app_title, version),
+            link: None,
+            description: format!(
+                "{} can be updated from {} to {} (rauncher update {})",
+                game.app_title,
+                game.app_version,
+                version,
+                game.id()
+            ),
+            starts_at: found_at,
+            ends_at: None,
+        }
+    }
+}
+
+/// Directory the feeds are written to
+pub fn feeds_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("feeds"))
+}
+
+/// Write both feeds into `feeds_dir`, returning their paths
+pub fn write(entries: &[FeedEntry]) -> Result<(PathBuf, PathBuf)> {
+    let dir = feeds_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    let now = Utc::now();
+    let rss_path = dir.join(RSS_FILE);
+    let ical_path = dir.join(ICAL_FILE);
+    fs::write(&rss_path, to_rss(entries, now))?;
+    fs::write(&ical_path, to_ical(entries, now))?;
+    crate::logs::transcript::touched("wrote", &rss_path);
+    crate::logs::transcript::touched("wrote", &ical_path);
+
+    Ok((rss_path, ical_path))
+}
+
+/// Render an RSS 2.0 feed
+pub fn to_rss(entries: &[FeedEntry], now: DateTime<Utc>) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<rss version=\"2.0\">\n<channel>\n");
+    out.push_str("<title>rauncher</title>\n");
+    out.push_str("<link>https://github.com/kairosci/rauncher</link>\n");
+    out.push_str("<description>Free games on Epic and pending game updates</description>\n");
+    out.push_str(&format!(
+        "<lastBuildDate>{}</lastBuildDate>\n",
+        now.to_rfc2822()
+    ));
+
+    for entry in entries {
+        out.push_str("<item>\n");
+        out.push_str(&format!("<title>{}</title>\n", escape_xml(&entry.title)));
+        if let Some(link) = &entry.link {
+            out.push_str(&format!("<link>{}</link>\n", escape_xml(link)));
+        }
+        out.push_str(&format!(
+            "<description>{}</description>\n",
+            escape_xml(&entry.description)
+        ));
+        out.push_str(&format!(
+            "<guid isPermaLink=\"false\">{}</guid>\n",
+            escape_xml(&entry.id)
+        ));
+        out.push_str(&format!(
+            "<pubDate>{}</pubDate>\n",
+            entry.starts_at.to_rfc2822()
+        ));
+        out.push_str("</item>\n");
+    }
+
+    out.push_str("</channel>\n</rss>\n");
+    out
+}
+
+/// Render an iCalendar (RFC 5545) calendar with one event per entry
+pub fn to_ical(entries: &[FeedEntry], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//rauncher//feeds//EN".to_string(),
+        "X-WR-CALNAME:rauncher".to_string(),
+    ];
+
+    for entry in entries {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@rauncher", escape_ical(&entry.id)));
+        lines.push(format!("DTSTAMP:{}", ical_time(now)));
+        lines.push(format!("DTSTART:{}", ical_time(entry.starts_at)));
+        if let Some(ends_at) = entry.ends_at {
+            lines.push(format!("DTEND:{}", ical_time(ends_at)));
+        }
+        lines.push(format!("SUMMARY:{}", escape_ical(&entry.title)));
+        lines.push(format!("DESCRIPTION:{}", escape_ical(&entry.description)));
+        if let Some(link) = &entry.link {
+            lines.push(format!("URL:{}", link));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_ical_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn ical_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn escape_ical(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// iCalendar lines longer than 75 octets continue on the next line after a space
+fn fold_ical_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry() -> FeedEntry {
+        FeedEntry {
+            id: "free:Tom & Jerry:1".to_string(),
+            title: "Free on Epic: Tom & Jerry, <Remastered>".to_string(),
+            link: Some("https://store.epicgames.com/p/tom-and-jerry".to_string()),
+            description: "Free until Thursday; grab it".to_string(),
+            starts_at: Utc.with_ymd_and_hms(2026, 10, 8, 15, 0, 0).unwrap(),
+            ends_at: Some(Utc.with_ymd_and_hms(2026, 10, 15, 15, 0, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_rss_escapes_entries() {
+        let rss = to_rss(&[entry()], Utc::now());
+        assert!(rss.contains("<title>Free on Epic: Tom &amp; Jerry, &lt;Remastered&gt;</title>"));
+        assert!(rss.contains("<pubDate>Thu, 8 Oct 2026 15:00:00 +0000</pubDate>"));
+    }
+
+    #[test]
+    fn test_ical_events() {
+        let ical = to_ical(&[entry()], Utc::now());
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ical.contains("DTSTART:20261008T150000Z\r\n"));
+        assert!(ical.contains("DTEND:20261015T150000Z\r\n"));
+        assert!(ical.contains("SUMMARY:Free on Epic: Tom & Jerry\\, <Remastered>\r\n"));
+        assert!(ical.contains("DESCRIPTION:Free until Thursday\\; grab it\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_fold_long_ical_lines() {
+        let folded = fold_ical_line(&"x".repeat(160));
+        let lines: Vec<_> = folded.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        assert!(lines[1].starts_with(' '));
+    }
+}
@@ -0,0 +1,283 @@
+//! Human-friendly names for games. App names are often opaque ids, so wherever a game is named,
+//! an alias from the configuration or the game's title does as well. Names matching nothing
+//! get suggestions of the games they were likely meant for.
+
+use super::{offline, InstalledGame};
+use crate::api::Game;
+use crate::config::Config;
+use crate::providers;
+use crate::{Error, Result};
+
+/// Shortest part of a title matched on its own; shorter ones match too much
+const MIN_PARTIAL_MATCH: usize = 3;
+
+/// Most suggestions offered for a name matching no game
+const MAX_SUGGESTIONS: usize = 3;
+
+/// The address `name` stands for: the game an alias points to, or the one whose title it is.
+/// App names, addresses, and names matching no game are returned as they are. A name that is
+/// only part of some titles is an error listing those games, rather than a guess among them.
+pub fn resolve_name(config: &Config, name: &str) -> Result<String> {
+    if providers::parse_address(name).0.is_some() {
+        return Ok(name.to_string());
+    }
+    if let Some(target) = find_alias(config, name) {
+        log::debug!("{} is an alias of {}", name, target);
+        return Ok(target.clone());
+    }
+
+    let games = known_games(config);
+    if games.iter().any(|game| game.app_name == name) {
+        return Ok(name.to_string());
+    }
+    if let Some(game) = find_by_title(&games, name) {
+        log::debug!("Taking {} as {} ({})", name, game.app_name, game.app_title);
+        return Ok(address(game));
+    }
+
+    let candidates = partial_matches(&games, name);
+    if candidates.is_empty() {
+        return Ok(name.to_string());
+    }
+    let candidates: Vec<String> = candidates
+        .iter()
+        .map(|game| format!("{} ({})", game.app_title, address(game)))
+        .collect();
+    Err(Error::Other(format!(
+        "'{}' is only part of the title of {}. Name the game in full, or prefix an app name \
+         with its store (epic:{}) to use it as it is",
+        name,
+        candidates.join(", "),
+        name
+    )))
+}
+
+/// Games `name` was likely meant for, as addresses with their titles, closest first: those
+/// whose app name or title is a few typos away. Empty when `name` names a game already.
+pub fn suggest_names(config: &Config, name: &str) -> Vec<(String, String)> {
+    if find_alias(config, name).is_some() {
+        return Vec::new();
+    }
+    let games = known_games(config);
+    let (provider, app_name) = providers::parse_address(name);
+    let named = games.iter().any(|game| {
+        game.app_name == app_name && provider.is_none_or(|provider| game.provider == provider)
+    });
+    if named || find_by_title(&games, name).is_some() {
+        return Vec::new();
+    }
+
+    let wanted = normalize(app_name);
+    let mut scored: Vec<(usize, &Game)> = games
+        .iter()
+        .filter_map(|game| {
+            let title = normalize(&game.app_title);
+            // Part of a title, which resolve_name won't guess from
+            if wanted.chars().count() >= MIN_PARTIAL_MATCH && title.contains(&wanted) {
+                return Some((0, game));
+            }
+            let distance =
+                distance(&wanted, &normalize(&game.app_name)).min(distance(&wanted, &title));
+            // Up to one typo in three characters
+            (distance <= wanted.chars().count().div_ceil(3)).then_some((distance, game))
+        })
+        .collect();
+    scored.sort_by_key(|(distance, game)| (*distance, game.app_title.clone()));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, game)| (address(game), game.app_title.clone()))
+        .collect()
+}
+
+/// Give the game `name` stands for the alias `alias`. Returns the address it points to.
+pub fn add_alias(config: &mut Config, alias: &str, name: &str) -> Result<String> {
+    let alias = alias.trim();
+    if alias.is_empty() {
+        return Err(Error::Config("An alias can't be empty".to_string()));
+    }
+    if known_games(config)
+        .iter()
+        .any(|game| game.app_name == alias)
+    {
+        return Err(Error::Config(format!(
+            "'{}' is the app name of a game already",
+            alias
+        )));
+    }
+
+    let target = resolve_name(config, name)?;
+    config.aliases.insert(alias.to_string(), target.clone());
+    if let Err(e) = config.validate() {
+        config.aliases.remove(alias);
+        return Err(e);
+    }
+    Ok(target)
+}
+
+/// Drop an alias. Returns whether there was one.
+pub fn remove_alias(config: &mut Config, alias: &str) -> bool {
+    config.aliases.remove(alias).is_some()
+}
+
+/// The target of the alias `name`, matched as given, then ignoring case
+fn find_alias<'a>(config: &'a Config, name: &str) -> Option<&'a String> {
+    config.aliases.get(name).or_else(|| {
+        config
+            .aliases
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|(_, target)| target)
+    })
+}
+
+/// The games rauncher knows the names of: the installed ones, and those in every store's last
+/// library listing
+fn known_games(config: &Config) -> Vec<Game> {
+    let mut games: Vec<Game> = InstalledGame::list_installed(config)
+        .unwrap_or_default()
+        .iter()
+        .map(offline::installed_as_game)
+        .collect();
+    for provider in providers::PROVIDER_IDS {
+        for game in offline::load_listing(provider)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+        {
+            if !games.iter().any(|known| known.id() == game.id()) {
+                games.push(game);
+            }
+        }
+    }
+    games
+}
+
+/// The one game whose title is `name`. Case, spacing and punctuation don't count.
+fn find_by_title<'a>(games: &'a [Game], name: &str) -> Option<&'a Game> {
+    let wanted = normalize(name);
+    if wanted.is_empty() {
+        return None;
+    }
+    let exact: Vec<&Game> = games
+        .iter()
+        .filter(|game| normalize(&game.app_title) == wanted)
+        .collect();
+
+    match exact.as_slice() {
+        [game] => Some(game),
+        _ => None,
+    }
+}
+
+/// The games whose titles contain `name` without being it, by title
+fn partial_matches<'a>(games: &'a [Game], name: &str) -> Vec<&'a Game> {
+    let wanted = normalize(name);
+    if wanted.chars().count() < MIN_PARTIAL_MATCH {
+        return Vec::new();
+    }
+    let mut matches: Vec<&Game> = games
+        .iter()
+        .filter(|game| {
+            let title = normalize(&game.app_title);
+            title != wanted && title.contains(&wanted)
+        })
+        .collect();
+    matches.sort_by(|a, b| a.app_title.cmp(&b.app_title));
+    matches
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance: the fewest characters added, removed or changed to turn `a` into `b`
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// How commands take the game: Epic's by bare app name, other stores' with their prefix
+fn address(game: &Game) -> String {
+    if game.provider == providers::EPIC {
+        game.app_name.clone()
+    } else {
+        game.id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(provider: &str, app_name: &str, title: &str) -> Game {
+        Game {
+            app_name: app_name.to_string(),
+            app_title: title.to_string(),
+            app_version: "1".to_string(),
+            install_path: None,
+            provider: provider.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_by_title() {
+        let games = [
+            game(providers::EPIC, "Catnip", "Borderlands 3"),
+            game(providers::EPIC, "Kiwi", "Kiwi Quest"),
+            game(providers::GOG, "1207658924", "Kiwi Quest: Director's Cut"),
+        ];
+        let found = |name| find_by_title(&games, name).map(address);
+
+        assert_eq!(found("borderlands 3"), Some("Catnip".to_string()));
+        assert_eq!(found("Borderlands3"), Some("Catnip".to_string()));
+        assert_eq!(found("kiwi quest"), Some("Kiwi".to_string()));
+        // Part of a title is never taken for the game
+        assert_eq!(found("border"), None);
+        assert_eq!(found("  "), None);
+    }
+
+    #[test]
+    fn test_partial_matches() {
+        let games = [
+            game(providers::EPIC, "Catnip", "Borderlands 3"),
+            game(providers::EPIC, "Kiwi", "Kiwi Quest"),
+            game(providers::GOG, "1207658924", "Kiwi Quest: Director's Cut"),
+        ];
+        let found = |name| {
+            partial_matches(&games, name)
+                .into_iter()
+                .map(address)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(found("border"), ["Catnip"]);
+        assert_eq!(found("kiwi"), ["Kiwi", "gog:1207658924"]);
+        // The game titled so isn't a partial match of itself
+        assert_eq!(found("kiwi quest"), ["gog:1207658924"]);
+        // Too short to say
+        assert!(found("bo").is_empty());
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("fortnite", "fortnite"), 0);
+        assert_eq!(distance("fortnit", "fortnite"), 1);
+        assert_eq!(distance("frotnite", "fortnite"), 2);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("kitten", "sitting"), 3);
+    }
+}
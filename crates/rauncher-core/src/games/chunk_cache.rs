@@ -0,0 +1,248 @@
+//! Recently downloaded chunks, kept on disk so reinstalling a game, installing it to a second
+//! library or retrying a failed update doesn't fetch them again. The cache is capped at
+//! `cache_size_mb`; the chunks used longest ago go first.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::config::Config;
+use crate::Result;
+
+/// Extension of chunks still being written, which eviction leaves alone
+const PARTIAL_EXTENSION: &str = "part";
+
+pub(crate) struct ChunkCache {
+    dir: PathBuf,
+    limit: u64,
+    /// Bytes cached, measured on the first write and counted from there. Held while evicting,
+    /// so the download workers never evict at the same time.
+    used: Mutex<Option<u64>>,
+}
+
+impl ChunkCache {
+    /// The cache in the data directory, or None when `cache_size_mb` turns it off
+    pub(crate) fn new(config: &Config) -> Result<Option<Self>> {
+        Ok(Self::at(Config::chunk_cache_dir()?, config.cache_size_mb))
+    }
+
+    fn at(dir: PathBuf, limit_mb: u64) -> Option<Self> {
+        (limit_mb > 0).then(|| Self {
+            dir,
+            limit: limit_mb * 1024 * 1024,
+            used: Mutex::new(None),
+        })
+    }
+
+    /// A cached chunk, marked as just used
+    pub(crate) fn get(&self, provider: &str, guid: &str) -> Option<Vec<u8>> {
+        let path = self.path(provider, guid);
+        let data = fs::read(&path).ok()?;
+        if let Err(e) = fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            log::debug!("Could not mark cached chunk {:?} as used: {}", path, e);
+        }
+        Some(data)
+    }
+
+    /// Keep a chunk, dropping the least recently used ones beyond the size limit. Chunks
+    /// bigger than the whole cache aren't kept.
+    pub(crate) fn put(&self, provider: &str, guid: &str, data: &[u8]) -> Result<()> {
+        let size = data.len() as u64;
+        if size > self.limit {
+            return Ok(());
+        }
+
+        let path = self.path(provider, guid);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Written aside first, so an interrupted write never leaves a truncated chunk behind
+        let partial = path.with_extension(PARTIAL_EXTENSION);
+        fs::write(&partial, data)?;
+
+        // Put in place while counted, so an eviction never sees it uncounted
+        let mut used = self.used.lock().unwrap();
+        let replaced = fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        fs::rename(&partial, &path)?;
+        let total = match *used {
+            Some(total) => (total + size).saturating_sub(replaced),
+            None => self.chunks()?.iter().map(|(_, size, _)| size).sum(),
+        };
+        // Evicting leaves room to spare, so the cache isn't listed again for every chunk
+        *used = Some(if total > self.limit {
+            self.evict(self.limit / 10 * 9)?
+        } else {
+            total
+        });
+        Ok(())
+    }
+
+    /// Delete the least recently used chunks until the cache takes no more than `target`
+    /// bytes, returning how many it takes
+    fn evict(&self, target: u64) -> Result<u64> {
+        let mut chunks = self.chunks()?;
+        let mut total: u64 = chunks.iter().map(|(_, size, _)| size).sum();
+        chunks.sort_by_key(|(used, _, _)| *used);
+        for (_, size, path) in chunks {
+            if total <= target {
+                break;
+            }
+            if remove_chunk(&path) {
+                total -= size;
+            }
+        }
+
+        Ok(total)
+    }
+
+    /// Every complete chunk, with when it was last used and its size
+    fn chunks(&self) -> Result<Vec<(SystemTime, u64, PathBuf)>> {
+        let mut chunks = Vec::new();
+        for provider in fs::read_dir(&self.dir)? {
+            let provider = provider?;
+            if !provider.file_type()?.is_dir() {
+                continue;
+            }
+            for chunk in fs::read_dir(provider.path())? {
+                let chunk = chunk?;
+                let path = chunk.path();
+                if path
+                    .extension()
+                    .is_some_and(|extension| extension == PARTIAL_EXTENSION)
+                {
+                    continue;
+                }
+                // Gone since it was listed, evicted or replaced
+                let Ok(metadata) = chunk.metadata() else {
+                    continue;
+                };
+                if metadata.is_file() {
+                    let used = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    chunks.push((used, metadata.len(), path));
+                }
+            }
+        }
+        Ok(chunks)
+    }
+
+    fn path(&self, provider: &str, guid: &str) -> PathBuf {
+        // Guids are hex on every store, but keep anything else out of the path
+        let name: String = guid
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(provider).join(name)
+    }
+}
+
+/// Returns whether the chunk is gone
+fn remove_chunk(path: &Path) -> bool {
+    match fs::remove_file(path) {
+        Ok(()) => true,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => true,
+        Err(e) => {
+            log::debug!("Could not remove cached chunk {:?}: {}", path, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Bytes of the complete chunks on disk
+    fn measured(cache: &ChunkCache) -> u64 {
+        cache
+            .chunks()
+            .unwrap()
+            .iter()
+            .map(|(_, size, _)| size)
+            .sum()
+    }
+
+    #[test]
+    fn test_least_recently_used_chunks_are_evicted() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ChunkCache::at(dir.path().to_path_buf(), 1).unwrap();
+        let chunk = vec![7u8; 400 * 1024];
+
+        cache.put("epic", "AAAA", &chunk).unwrap();
+        cache.put("gog", "BBBB", &chunk).unwrap();
+        // Mark the first chunk as used after the second, so the second is evicted instead
+        let earlier = SystemTime::now() - Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(cache.path("gog", "BBBB"))
+            .unwrap()
+            .set_modified(earlier)
+            .unwrap();
+        assert_eq!(cache.get("epic", "AAAA").as_deref(), Some(chunk.as_slice()));
+
+        cache.put("epic", "CCCC", &chunk).unwrap();
+        assert!(cache.get("epic", "AAAA").is_some());
+        assert!(cache.get("gog", "BBBB").is_none());
+        assert!(cache.get("epic", "CCCC").is_some());
+        assert_eq!(*cache.used.lock().unwrap(), Some(measured(&cache)));
+
+        // Replacing a chunk counts it once
+        cache.put("epic", "CCCC", &chunk).unwrap();
+        assert_eq!(*cache.used.lock().unwrap(), Some(measured(&cache)));
+
+        // Too big to ever fit
+        cache
+            .put("epic", "DDDD", &vec![0u8; 2 * 1024 * 1024])
+            .unwrap();
+        assert!(cache.get("epic", "DDDD").is_none());
+
+        assert!(ChunkCache::at(dir.path().to_path_buf(), 0).is_none());
+    }
+
+    #[test]
+    fn test_chunks_being_written_are_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ChunkCache::at(dir.path().to_path_buf(), 1).unwrap();
+        let chunk = vec![7u8; 400 * 1024];
+
+        // Another worker's chunk, halfway written
+        let partial = cache.path("epic", "AAAA").with_extension(PARTIAL_EXTENSION);
+        fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        fs::write(&partial, &chunk).unwrap();
+
+        for guid in ["BBBB", "CCCC", "DDDD"] {
+            cache.put("epic", guid, &chunk).unwrap();
+        }
+        assert!(partial.is_file());
+        assert_eq!(measured(&cache), 800 * 1024);
+    }
+
+    #[test]
+    fn test_workers_share_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ChunkCache::at(dir.path().to_path_buf(), 1).unwrap();
+
+        std::thread::scope(|scope| {
+            for worker in 0..4 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for n in 0..20 {
+                        let guid = format!("{:02}{:02}", worker, n);
+                        cache
+                            .put("epic", &guid, &[worker as u8; 100 * 1024])
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        let used = measured(&cache);
+        assert!(used <= cache.limit);
+        assert_eq!(*cache.used.lock().unwrap(), Some(used));
+    }
+}
@@ -0,0 +1,436 @@
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use super::launch::is_proton;
+use super::{record_session, InstalledGame, LaunchCommand};
+use crate::config::Config;
+use crate::stats::PlaySession;
+use crate::Result;
+
+/// Crash reports kept per game
+const KEPT_CRASH_REPORTS: usize = 10;
+
+/// Environment variables whose values are left out of crash reports
+const SECRET_MARKERS: [&str; 5] = ["TOKEN", "SECRET", "PASSWORD", "KEY", "AUTH"];
+
+/// How long the game's last output is waited for once it exits. Processes it started may
+/// hold its output open for much longer, and aren't waited on.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A launched game, which can be waited on to find out whether it crashed
+#[derive(Debug)]
+pub struct RunningGame {
+    pub game: InstalledGame,
+    pub command: LaunchCommand,
+    /// File the game's output is captured in
+    pub log_path: Option<PathBuf>,
+    pub started_at: DateTime<Utc>,
+    child: Child,
+    /// Log file the game's piped output is copied to while it's relayed
+    output_log: Option<fs::File>,
+}
+
+/// How a game that was waited on ended
+#[derive(Debug)]
+pub struct GameExit {
+    /// The game's exit code; `None` when it was killed by a signal
+    pub code: Option<i32>,
+    /// The signal that killed the game, on Unix
+    pub signal: Option<i32>,
+    /// Left behind when the game exited with an error
+    pub crash_report: Option<CrashReport>,
+}
+
+/// A crash report bundle: a directory holding `report.txt`, `output.log` and `environment.txt`
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub app_name: String,
+    pub app_title: String,
+    /// How the game ended, e.g. "exit code 1" or "killed by signal 11"
+    pub exit: String,
+    pub dir: PathBuf,
+}
+
+impl CrashReport {
+    /// Contents of `report.txt`
+    pub fn summary(&self) -> Result<String> {
+        Ok(fs::read_to_string(self.dir.join("report.txt"))?)
+    }
+
+    /// Last `lines` lines of the game's output
+    pub fn output_tail(&self, lines: usize) -> Result<String> {
+        let bytes = fs::read(self.dir.join("output.log"))?;
+        let output = String::from_utf8_lossy(&bytes);
+        let all: Vec<_> = output.lines().collect();
+        Ok(all[all.len().saturating_sub(lines)..].join("\n"))
+    }
+}
+
+impl RunningGame {
+    pub(crate) fn new(
+        game: InstalledGame,
+        command: LaunchCommand,
+        log_path: Option<PathBuf>,
+        child: Child,
+        output_log: Option<fs::File>,
+    ) -> Self {
+        Self {
+            game,
+            command,
+            log_path,
+            started_at: Utc::now(),
+            child,
+            output_log,
+        }
+    }
+
+    /// Id of the launched process
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Block until the game exits; a non-zero exit leaves a crash report behind
+    pub fn wait(self) -> Result<Option<CrashReport>> {
+        self.wait_for_exit().map(|exit| exit.crash_report)
+    }
+
+    /// Block until the game exits, relaying its output to the debug log when it was launched
+    /// with `stream_output`. A non-zero exit leaves a crash report behind.
+    pub fn wait_for_exit(mut self) -> Result<GameExit> {
+        let pid = self.child.id();
+        if let Err(e) = super::running::mark_waited(&self.game.app_name, pid) {
+            log::warn!(
+                "Failed to record that {} is waited on: {}",
+                self.game.app_name,
+                e
+            );
+        }
+
+        let status = wait_relaying(
+            &mut self.child,
+            self.output_log.as_ref(),
+            &self.game.app_name,
+        )?;
+        let stopped =
+            super::running::record(&self.game.app_name, pid).is_some_and(|record| record.stopping);
+        super::running::untrack(&self.game.app_name, pid);
+        record_session(&PlaySession::ended_now(&self.game, self.started_at));
+
+        if status.success() || stopped {
+            if stopped {
+                log::info!("{} was stopped", self.game.app_title);
+            } else {
+                log::info!("{} exited normally", self.game.app_title);
+            }
+            return Ok(GameExit {
+                code: status.code(),
+                signal: exit_signal(status),
+                crash_report: None,
+            });
+        }
+
+        let exit = describe_exit(status);
+        log::warn!("{} crashed: {}", self.game.app_title, exit);
+        let report = self.write_report(exit)?;
+        log::info!("Crash report written to {:?}", report.dir);
+        Ok(GameExit {
+            code: status.code(),
+            signal: exit_signal(status),
+            crash_report: Some(report),
+        })
+    }
+
+    fn write_report(&self, exit: String) -> Result<CrashReport> {
+        let app_name = &self.game.app_name;
+        let ended_at = Utc::now();
+        let dir = crashes_dir(app_name)?.join(ended_at.format("%Y-%m-%d_%H-%M-%S").to_string());
+        fs::create_dir_all(&dir)?;
+        crate::logs::transcript::touched("created", &dir);
+
+        let command_line = std::iter::once(&self.command.program)
+            .chain(&self.command.args)
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let runner = match &self.command.runner {
+            Some(runner) => format!("{} ({})", runner.display(), runner_version(runner)),
+            None => "none".to_string(),
+        };
+
+        let summary = format!(
+            "Game: {} ({})\n\
+             Version: {}\n\
+             Store: {}\n\
+             Exit: {}\n\
+             Started: {}\n\
+             Ended: {}\n\
+             Command: {}\n\
+             Working directory: {}\n\
+             Wine/Proton: {}\n\
+             rauncher: {}\n\
+             System: {} {}\n",
+            self.game.app_title,
+            app_name,
+            self.game.app_version,
+            self.game.provider,
+            exit,
+            self.started_at.to_rfc3339(),
+            ended_at.to_rfc3339(),
+            command_line,
+            self.command.working_dir.display(),
+            runner,
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+        );
+        fs::write(dir.join("report.txt"), summary)?;
+
+        match &self.log_path {
+            Some(log_path) => {
+                fs::copy(log_path, dir.join("output.log"))?;
+            }
+            None => fs::write(
+                dir.join("output.log"),
+                "The game's output was not captured\n",
+            )?,
+        }
+
+        fs::write(dir.join("environment.txt"), self.environment())?;
+        prune_reports(&crashes_dir(app_name)?);
+
+        Ok(CrashReport {
+            app_name: app_name.clone(),
+            app_title: self.game.app_title.clone(),
+            exit,
+            dir,
+        })
+    }
+
+    /// The environment the game ran with, secrets redacted, one `NAME=value` per line
+    fn environment(&self) -> String {
+        let mut vars: Vec<(String, String)> = std::env::vars_os()
+            .map(|(k, v)| {
+                (
+                    k.to_string_lossy().into_owned(),
+                    v.to_string_lossy().into_owned(),
+                )
+            })
+            .filter(|(k, _)| !self.command.env.iter().any(|(name, _)| name == k))
+            .chain(
+                self.command
+                    .env
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.to_string_lossy().into_owned())),
+            )
+            .collect();
+        vars.sort();
+
+        vars.into_iter()
+            .map(|(name, value)| {
+                let upper = name.to_ascii_uppercase();
+                if SECRET_MARKERS.iter().any(|marker| upper.contains(marker)) {
+                    format!("{}=<redacted>\n", name)
+                } else {
+                    format!("{}={}\n", name, value)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Wait for `child` to exit while relaying its piped output. Relays run on threads of their
+/// own, so output that processes the game started still hold open once it's gone can't keep
+/// this from returning.
+fn wait_relaying(
+    child: &mut Child,
+    output_log: Option<&fs::File>,
+    app_name: &str,
+) -> std::io::Result<ExitStatus> {
+    let mut streams: Vec<Box<dyn Read + Send>> = Vec::new();
+    streams.extend(child.stdout.take().map(|out| Box::new(out) as _));
+    streams.extend(child.stderr.take().map(|err| Box::new(err) as _));
+
+    let (done, relays_done) = mpsc::channel();
+    let relays = streams.len();
+    for stream in streams {
+        let log = output_log.and_then(|f| f.try_clone().ok());
+        let app_name = app_name.to_string();
+        let done = done.clone();
+        std::thread::spawn(move || {
+            relay_output(stream, log, &app_name);
+            let _ = done.send(());
+        });
+    }
+
+    let status = child.wait()?;
+    let deadline = Instant::now() + OUTPUT_DRAIN_TIMEOUT;
+    for _ in 0..relays {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if relays_done.recv_timeout(left).is_err() {
+            log::debug!(
+                "{}'s output is still open after it exited; no longer waiting for it",
+                app_name
+            );
+            break;
+        }
+    }
+    Ok(status)
+}
+
+/// Copy a game's output into its log file and the debug log, line by line, until it closes
+fn relay_output(stream: impl Read, mut log: Option<fs::File>, app_name: &str) {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if !matches!(reader.read_until(b'\n', &mut line), Ok(read) if read > 0) {
+            return;
+        }
+        if let Some(file) = &mut log {
+            if file.write_all(&line).is_err() {
+                log = None;
+            }
+        }
+        let text = String::from_utf8_lossy(&line);
+        log::debug!("{}: {}", app_name, text.trim_end());
+    }
+}
+
+/// Where the crash reports of a game are kept, newest last
+pub fn crashes_dir(app_name: &str) -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("crashes").join(app_name))
+}
+
+/// The signal that ended a process, on Unix
+fn exit_signal(status: ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
+
+fn describe_exit(status: ExitStatus) -> String {
+    if let Some(signal) = exit_signal(status) {
+        return format!("killed by signal {}", signal);
+    }
+
+    match status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => "unknown exit status".to_string(),
+    }
+}
+
+/// Version of a Wine binary (`wine --version`) or Proton build (its `version` file)
+fn runner_version(runner: &Path) -> String {
+    if is_proton(runner) {
+        let version_file = runner.with_file_name("version");
+        return fs::read_to_string(version_file)
+            .ok()
+            .and_then(|contents| contents.split_whitespace().last().map(str::to_string))
+            .unwrap_or_else(|| "unknown version".to_string());
+    }
+
+    Command::new(runner)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown version".to_string())
+}
+
+/// Remove the oldest reports beyond `KEPT_CRASH_REPORTS`
+fn prune_reports(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut reports: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    reports.sort();
+
+    let excess = reports.len().saturating_sub(KEPT_CRASH_REPORTS);
+    for old in &reports[..excess] {
+        if let Err(e) = fs::remove_dir_all(old) {
+            log::debug!("Could not remove old crash report {:?}: {}", old, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proton_version_comes_from_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let proton = dir.path().join("proton");
+        fs::write(&proton, "").unwrap();
+        assert_eq!(runner_version(&proton), "unknown version");
+
+        fs::write(dir.path().join("version"), "1718000000 GE-Proton9-20\n").unwrap();
+        assert_eq!(runner_version(&proton), "GE-Proton9-20");
+    }
+
+    #[test]
+    fn test_relay_output_copies_to_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("game.log");
+        let log = fs::File::create(&log_path).unwrap();
+
+        let output: &[u8] = b"starting\n\xffbroken\nno newline";
+        relay_output(output, Some(log), "Kiwi");
+        assert_eq!(fs::read(&log_path).unwrap(), output);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_left_open_does_not_hold_up_the_exit() {
+        use std::process::Stdio;
+
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("game.log");
+        let log = fs::File::create(&log_path).unwrap();
+        // The game exits at once, but what it started keeps its output open
+        let mut child = Command::new("sh")
+            .args(["-c", "echo bye; sleep 10 &"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let started = Instant::now();
+        let status = wait_relaying(&mut child, Some(&log), "Kiwi").unwrap();
+        assert!(status.success());
+        assert!(started.elapsed() < Duration::from_secs(8));
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "bye\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_describe_exit() {
+        use std::os::unix::process::ExitStatusExt;
+
+        assert_eq!(describe_exit(ExitStatus::from_raw(1 << 8)), "exit code 1");
+        assert_eq!(
+            describe_exit(ExitStatus::from_raw(11)),
+            "killed by signal 11"
+        );
+        assert_eq!(exit_signal(ExitStatus::from_raw(9)), Some(9));
+        assert_eq!(exit_signal(ExitStatus::from_raw(1 << 8)), None);
+    }
+}
@@ -0,0 +1,305 @@
+//! Self-diagnostics for `rauncher doctor`: whether Epic can be reached, the session and config
+//! are good, games can be installed and run, and there's room for them. Each check says what
+//! it found; frontends turn problems into suggested fixes.
+
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::launch::find_in_path;
+use super::{GameManager, InstalledGame};
+use crate::api::SERVICES;
+use crate::config::Config;
+use crate::runners;
+use crate::storage::{existing_ancestor, library_roots, volume};
+
+/// Free space below which a library is reported as running out
+pub const LOW_SPACE: u64 = 10 * 1024 * 1024 * 1024;
+
+/// File written to check that a library is writable
+const PROBE_FILE: &str = ".rauncher-doctor";
+
+/// How a check came out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Ok,
+    /// Works, but something is likely to go wrong later
+    Warning,
+    /// Something rauncher needs is broken
+    Error,
+}
+
+/// What one check found
+#[derive(Debug, Clone, PartialEq)]
+pub enum Check {
+    /// An Epic service answered
+    Reachable {
+        service: String,
+    },
+    Unreachable {
+        service: String,
+        error: String,
+    },
+    /// Epic accepted the stored session
+    SessionValid {
+        account: String,
+        expires_at: DateTime<Utc>,
+    },
+    /// The access token expired, and is refreshed with the stored refresh token on the next
+    /// command that needs it
+    SessionExpired {
+        expired_at: DateTime<Utc>,
+    },
+    NotLoggedIn,
+    SessionRejected {
+        error: String,
+    },
+    ConfigValid {
+        path: PathBuf,
+    },
+    ConfigInvalid {
+        path: PathBuf,
+        error: String,
+    },
+    LibraryWritable {
+        path: PathBuf,
+    },
+    LibraryNotWritable {
+        path: PathBuf,
+        error: String,
+    },
+    /// A library on a removable or network drive that isn't there
+    DriveNotMounted {
+        path: PathBuf,
+    },
+    /// Wine or Proton is available for Windows games (not checked on Windows)
+    RunnerFound {
+        runner: String,
+    },
+    NoRunner,
+    /// A game's configured runner or Wine is gone
+    RunnerMissing {
+        app_name: String,
+        runner: String,
+    },
+    FreeSpace {
+        path: PathBuf,
+        free: u64,
+    },
+    LowSpace {
+        path: PathBuf,
+        free: u64,
+    },
+}
+
+impl Check {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::Reachable { .. }
+            | Self::SessionValid { .. }
+            | Self::SessionExpired { .. }
+            | Self::ConfigValid { .. }
+            | Self::LibraryWritable { .. }
+            | Self::RunnerFound { .. }
+            | Self::FreeSpace { .. } => Severity::Ok,
+            Self::NoRunner
+            | Self::RunnerMissing { .. }
+            | Self::LowSpace { .. }
+            | Self::DriveNotMounted { .. } => Severity::Warning,
+            Self::Unreachable { .. }
+            | Self::NotLoggedIn
+            | Self::SessionRejected { .. }
+            | Self::ConfigInvalid { .. }
+            | Self::LibraryNotWritable { .. } => Severity::Error,
+        }
+    }
+}
+
+impl GameManager {
+    /// Run every check, in the order a broken setup is best fixed in
+    pub async fn doctor(&self) -> Vec<Check> {
+        let mut checks = Vec::new();
+
+        for (service, url) in SERVICES {
+            checks.push(match self.shared.client.ping(url).await {
+                Ok(()) => Check::Reachable {
+                    service: service.to_string(),
+                },
+                Err(e) => Check::Unreachable {
+                    service: service.to_string(),
+                    error: e.to_string(),
+                },
+            });
+        }
+
+        let auth = self.auth();
+        checks.push(if auth.is_authenticated() {
+            match self.session_info().await {
+                Ok(session) => Check::SessionValid {
+                    account: session.display_name.unwrap_or(session.account_id),
+                    expires_at: session.expires_at,
+                },
+                Err(e) => Check::SessionRejected {
+                    error: e.to_string(),
+                },
+            }
+        } else {
+            // Checking the session would refresh it, which a diagnosis shouldn't do
+            match auth.token_expires_at().filter(|_| auth.has_session()) {
+                Some(expired_at) => Check::SessionExpired { expired_at },
+                None => Check::NotLoggedIn,
+            }
+        });
+
+        if let Ok(path) = Config::config_path() {
+            checks.push(check_config(&path));
+        }
+
+        let installed = InstalledGame::list_installed(&self.config()).unwrap_or_default();
+        let (roots, unmounted): (Vec<_>, Vec<_>) = library_roots(&self.config(), &installed)
+            .into_iter()
+            .partition(|root| !is_unmounted(root, &installed));
+        checks.extend(
+            unmounted
+                .into_iter()
+                .map(|path| Check::DriveNotMounted { path }),
+        );
+        checks.extend(roots.iter().map(|root| check_writable(root)));
+
+        if !cfg!(target_os = "windows") {
+            checks.extend(self.check_runners());
+        }
+
+        checks.extend(roots.iter().filter_map(|root| {
+            let free = fs4::available_space(existing_ancestor(root)).ok()?;
+            let path = root.clone();
+            Some(if free < LOW_SPACE {
+                Check::LowSpace { path, free }
+            } else {
+                Check::FreeSpace { path, free }
+            })
+        }));
+
+        checks
+    }
+
+    /// Whether Windows games have something to run with, and every runner the config names
+    /// still exists
+    fn check_runners(&self) -> Vec<Check> {
+        let mut checks = Vec::new();
+        let installed = runners::installed().unwrap_or_default();
+
+        for (app_name, settings) in &self.config().games {
+            let missing = match (&settings.runner, &settings.wine) {
+                (Some(runner), _) if !installed.iter().any(|r| &r.name == runner) => {
+                    Some(runner.clone())
+                }
+                (None, Some(wine)) if !wine.is_file() => Some(wine.display().to_string()),
+                _ => None,
+            };
+            if let Some(runner) = missing {
+                checks.push(Check::RunnerMissing {
+                    app_name: app_name.clone(),
+                    runner,
+                });
+            }
+        }
+
+        let runner = installed
+            .first()
+            .map(|runner| runner.name.clone())
+            .or_else(|| find_in_path("wine").map(|wine| wine.display().to_string()));
+        checks.insert(
+            0,
+            match runner {
+                Some(runner) => Check::RunnerFound { runner },
+                None => Check::NoRunner,
+            },
+        );
+
+        checks
+    }
+}
+
+/// Whether the config file parses and holds valid values. Checked from the file rather than
+/// the loaded config, which may be the defaults rauncher fell back to.
+fn check_config(path: &Path) -> Check {
+    let parsed = fs::read_to_string(path)
+        .map_err(crate::Error::from)
+        .and_then(|contents| Ok(toml::from_str::<Config>(&contents)?))
+        .and_then(|config| config.validate());
+
+    match parsed {
+        Ok(()) => Check::ConfigValid {
+            path: path.to_path_buf(),
+        },
+        // No file yet just means the defaults
+        Err(crate::Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            Check::ConfigValid {
+                path: path.to_path_buf(),
+            }
+        }
+        Err(e) => Check::ConfigInvalid {
+            path: path.to_path_buf(),
+            error: e.to_string(),
+        },
+    }
+}
+
+/// Whether `root` is on a drive that's away, where checking it would create it on the disk
+/// underneath
+fn is_unmounted(root: &Path, installed: &[InstalledGame]) -> bool {
+    volume::drive_missing(root)
+        || installed
+            .iter()
+            .any(|game| game.install_path.parent() == Some(root) && game.is_unavailable())
+}
+
+/// Whether games can be installed into `root`, created if missing
+fn check_writable(root: &Path) -> Check {
+    let probe = root.join(PROBE_FILE);
+    let written = fs::create_dir_all(root)
+        .and_then(|()| fs::write(&probe, b""))
+        .and_then(|()| fs::remove_file(&probe));
+
+    match written {
+        Ok(()) => Check::LibraryWritable {
+            path: root.to_path_buf(),
+        },
+        Err(e) => Check::LibraryNotWritable {
+            path: root.to_path_buf(),
+            error: e.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_and_library_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config.toml");
+
+        assert_eq!(check_config(&config).severity(), Severity::Ok);
+        fs::write(&config, "log_level = \"loud\"\n").unwrap();
+        assert!(matches!(
+            check_config(&config),
+            Check::ConfigInvalid { error, .. } if error.contains("loud")
+        ));
+        fs::write(&config, "log_level = [\n").unwrap();
+        assert_eq!(check_config(&config).severity(), Severity::Error);
+
+        let library = dir.path().join("Games");
+        assert!(matches!(
+            check_writable(&library),
+            Check::LibraryWritable { .. }
+        ));
+        assert!(!library.join(PROBE_FILE).exists());
+
+        // A file where the library should be
+        let blocked = dir.path().join("config.toml").join("Games");
+        assert_eq!(check_writable(&blocked).severity(), Severity::Error);
+    }
+}
@@ -0,0 +1,293 @@
+//! Keeping rauncher's installs and the official Epic Games Launcher's in step, through the
+//! launcher's `.item` manifests. Games installed by either show up in both; an update or an
+//! uninstall in one is carried over to the other. What was in step after the last sync is
+//! kept in `egl-sync.json`, which tells a game uninstalled on one side from one newly
+//! installed on the other. Only install records are touched, never the games' files.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{GameManager, InstalledGame};
+use crate::config::Config;
+use crate::egl::EglManifest;
+use crate::Result;
+
+/// What a sync changed, or would with `dry_run`
+#[derive(Debug, Clone, Default)]
+pub struct EglSyncReport {
+    /// Games installed by the Epic Games Launcher, now known to rauncher
+    pub imported: Vec<InstalledGame>,
+    /// Games installed by rauncher, now known to the Epic Games Launcher
+    pub exported: Vec<InstalledGame>,
+    /// Games updated on one side, with the version recorded on the other
+    pub updated: Vec<(String, String)>,
+    /// Games uninstalled in the Epic Games Launcher, forgotten by rauncher
+    pub forgotten: Vec<String>,
+    /// Games uninstalled by rauncher, removed from the Epic Games Launcher
+    pub removed: Vec<String>,
+}
+
+impl EglSyncReport {
+    pub fn is_empty(&self) -> bool {
+        self.imported.is_empty()
+            && self.exported.is_empty()
+            && self.updated.is_empty()
+            && self.forgotten.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
+impl GameManager {
+    /// Sync installed Epic games with the Epic Games Launcher's manifests in `manifests_dir`,
+    /// or in the launcher's own directory
+    pub fn egl_sync(&self, manifests_dir: Option<&Path>, dry_run: bool) -> Result<EglSyncReport> {
+        let dir = crate::egl::find_manifests_dir(manifests_dir)?;
+        let state_path = state_path()?;
+        // Versions by app name, as both sides had them after the last sync
+        let synced: BTreeMap<String, String> = fs::read_to_string(&state_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        let mut ours: BTreeMap<String, InstalledGame> = self
+            .list_installed()?
+            .into_iter()
+            .filter(|game| game.provider == crate::providers::EPIC)
+            .map(|game| (game.app_name.clone(), game))
+            .collect();
+        let mut report = EglSyncReport::default();
+        let mut in_step = BTreeMap::new();
+
+        let manifests = crate::egl::read_manifests(&dir)?;
+        // No manifests at all is a launcher that's gone or a wrong directory, rather than
+        // every game uninstalled
+        let launcher_found = !manifests.is_empty();
+        if !launcher_found {
+            log::warn!(
+                "No Epic Games Launcher manifests in {:?}; not forgetting any game",
+                dir
+            );
+        }
+
+        for manifest in manifests {
+            let app_name = manifest.app_name.clone();
+            match ours.remove(&app_name) {
+                Some(mut game) => {
+                    let last = synced.get(&app_name);
+                    match self.reconcile(&manifest, &mut game, last, dry_run)? {
+                        Some(version) => report.updated.push((app_name.clone(), version)),
+                        // Left as it was until the versions agree again
+                        None if manifest.app_version != game.app_version => {
+                            if let Some(last) = last {
+                                in_step.insert(app_name, last.clone());
+                            }
+                            continue;
+                        }
+                        None => {}
+                    }
+                    in_step.insert(app_name, game.app_version);
+                }
+                None if synced.contains_key(&app_name) => {
+                    if !dry_run {
+                        crate::egl::remove_manifest(&manifest)?;
+                    }
+                    report.removed.push(app_name);
+                }
+                None => {
+                    let game = manifest.to_installed_game();
+                    if !dry_run {
+                        game.save(&self.config())?;
+                    }
+                    in_step.insert(app_name, game.app_version.clone());
+                    report.imported.push(game);
+                }
+            }
+        }
+
+        // What's left is installed by rauncher only
+        for (app_name, game) in ours {
+            let last = synced.get(&app_name);
+            match unmatched(&game, last.is_some(), launcher_found) {
+                Unmatched::Keep => {
+                    if let Some(last) = last {
+                        in_step.insert(app_name, last.clone());
+                    }
+                }
+                Unmatched::Forget => {
+                    if !dry_run {
+                        game.delete(&self.config())?;
+                    }
+                    report.forgotten.push(app_name);
+                }
+                Unmatched::Export => {
+                    if !dry_run {
+                        crate::egl::write_manifest(&dir, &game)?;
+                    }
+                    in_step.insert(app_name, game.app_version.clone());
+                    report.exported.push(game);
+                }
+            }
+        }
+
+        if !dry_run {
+            fs::write(&state_path, serde_json::to_string_pretty(&in_step)?)?;
+        }
+        Ok(report)
+    }
+
+    /// Carry an update made on one side over to the other: whichever side no longer has the
+    /// version of the last sync was updated. Returns the version recorded, if any was.
+    fn reconcile(
+        &self,
+        manifest: &EglManifest,
+        game: &mut InstalledGame,
+        synced: Option<&String>,
+        dry_run: bool,
+    ) -> Result<Option<String>> {
+        match updated_side(&game.app_version, &manifest.app_version, synced) {
+            None if manifest.app_version == game.app_version => Ok(None),
+            Some(Side::Launcher) => {
+                game.app_version = manifest.app_version.clone();
+                if !manifest.launch_executable.is_empty() {
+                    game.executable = manifest.launch_executable.clone();
+                }
+                if !dry_run {
+                    game.save(&self.config())?;
+                }
+                Ok(Some(game.app_version.clone()))
+            }
+            Some(Side::Rauncher) => {
+                if !dry_run {
+                    crate::egl::update_manifest(manifest, &game.app_version)?;
+                }
+                Ok(Some(game.app_version.clone()))
+            }
+            None => {
+                log::warn!(
+                    "{} is at {} in rauncher but {} in the Epic Games Launcher; verify it in \
+                     one of them",
+                    game.app_name,
+                    game.app_version,
+                    manifest.app_version
+                );
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Which side a game was updated on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Rauncher,
+    Launcher,
+}
+
+/// The side that no longer has the version both had at the last sync, when the other still
+/// does. `None` when the versions agree, or both changed.
+fn updated_side(ours: &str, theirs: &str, synced: Option<&String>) -> Option<Side> {
+    if ours == theirs {
+        return None;
+    }
+    match synced.map(String::as_str) {
+        Some(last) if last == ours => Some(Side::Launcher),
+        Some(last) if last == theirs => Some(Side::Rauncher),
+        _ => None,
+    }
+}
+
+/// What becomes of a game rauncher has that the Epic Games Launcher has no manifest for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unmatched {
+    /// Left alone for now
+    Keep,
+    /// Uninstalled in the launcher, so forgotten here too
+    Forget,
+    /// Installed by rauncher, so given to the launcher
+    Export,
+}
+
+fn unmatched(game: &InstalledGame, synced: bool, launcher_found: bool) -> Unmatched {
+    if game.is_unavailable() {
+        log::debug!("Not syncing {}: its drive isn't mounted", game.app_name);
+        Unmatched::Keep
+    } else if !synced {
+        Unmatched::Export
+    } else if !launcher_found {
+        Unmatched::Keep
+    } else if game.install_path.exists() {
+        // The launcher deletes the files of games it uninstalls; these may have been moved
+        // or repaired, so only the record on its side went
+        log::info!(
+            "{} has no Epic Games Launcher manifest but is still at {:?}; keeping it",
+            game.app_name,
+            game.install_path
+        );
+        Unmatched::Keep
+    } else {
+        Unmatched::Forget
+    }
+}
+
+fn state_path() -> Result<PathBuf> {
+    let dir = Config::data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("egl-sync.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(install_path: &Path) -> InstalledGame {
+        InstalledGame {
+            app_name: "Kiwi".to_string(),
+            app_title: "Kiwi".to_string(),
+            app_version: "1.0".to_string(),
+            install_path: install_path.to_path_buf(),
+            executable: "Kiwi.exe".to_string(),
+            pinned: false,
+            provider: crate::providers::EPIC.to_string(),
+            install_size: 0,
+            download_size: 0,
+            installed_at: None,
+            last_played: None,
+            volume: None,
+            save_path_template: None,
+        }
+    }
+
+    #[test]
+    fn test_updates_are_carried_over_from_the_side_that_changed() {
+        let synced = "1.0".to_string();
+        assert_eq!(
+            updated_side("1.0", "1.1", Some(&synced)),
+            Some(Side::Launcher)
+        );
+        assert_eq!(
+            updated_side("1.1", "1.0", Some(&synced)),
+            Some(Side::Rauncher)
+        );
+        // Both moved on, or nothing to compare with: left for the user to sort out
+        assert_eq!(updated_side("1.1", "1.2", Some(&synced)), None);
+        assert_eq!(updated_side("1.0", "1.1", None), None);
+        assert_eq!(updated_side("1.0", "1.0", Some(&synced)), None);
+    }
+
+    #[test]
+    fn test_games_are_only_forgotten_once_their_files_are_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let installed = game(dir.path());
+        let removed = game(&dir.path().join("Gone"));
+
+        // Never synced before: the launcher is told about it
+        assert_eq!(unmatched(&installed, false, true), Unmatched::Export);
+        // Synced before and uninstalled in the launcher, files and all
+        assert_eq!(unmatched(&removed, true, true), Unmatched::Forget);
+        // Its files are still there, so only the launcher's record went
+        assert_eq!(unmatched(&installed, true, true), Unmatched::Keep);
+        // No manifests at all: the launcher is gone or the directory is wrong
+        assert_eq!(unmatched(&removed, true, false), Unmatched::Keep);
+    }
+}
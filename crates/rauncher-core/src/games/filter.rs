@@ -0,0 +1,223 @@
+use chrono::{DateTime, Duration, Utc};
+use std::fs;
+use std::path::Path;
+
+use super::InstalledGame;
+use crate::{Error, Result};
+
+/// Which installed games a bulk uninstall picks; every criterion that is set must match
+#[derive(Debug, Clone, Default)]
+pub struct UninstallFilter {
+    /// Games not launched within this long (never-launched games count from their install,
+    /// games with neither date never match)
+    pub not_played_since: Option<Duration>,
+    /// Games taking more than this many bytes on disk
+    pub larger_than: Option<u64>,
+}
+
+impl UninstallFilter {
+    pub fn is_empty(&self) -> bool {
+        self.not_played_since.is_none() && self.larger_than.is_none()
+    }
+
+    /// Whether `game`, taking `size` bytes on disk, should be uninstalled at `now`
+    pub fn matches(&self, game: &InstalledGame, size: u64, now: DateTime<Utc>) -> bool {
+        let stale = self.not_played_since.is_none_or(|age| {
+            game.last_played
+                .or(game.installed_at)
+                .is_some_and(|played| now - played > age)
+        });
+        let large = self.larger_than.is_none_or(|limit| size > limit);
+
+        !self.is_empty() && stale && large
+    }
+}
+
+/// Parse an age such as "6mo", "2w", "30d", "12h" or "1y"
+pub fn parse_age(text: &str) -> Result<Duration> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| Error::Other(format!("Invalid age '{}', expected e.g. 6mo or 30d", text)))?;
+    let invalid = || Error::Other(format!("Age '{}' is too large", text));
+    let days = match unit.trim().to_ascii_lowercase().as_str() {
+        "h" => return Duration::try_hours(amount).ok_or_else(invalid),
+        "d" => 1,
+        "w" => 7,
+        "mo" => 30,
+        "y" => 365,
+        _ => {
+            return Err(Error::Other(format!(
+                "Invalid age unit in '{}', use h, d, w, mo or y",
+                text
+            )))
+        }
+    };
+
+    amount
+        .checked_mul(days)
+        .and_then(Duration::try_days)
+        .ok_or_else(invalid)
+}
+
+/// Parse a size such as "50GB", "500 MB", "1.5GiB" or a plain number of bytes
+pub fn parse_size(text: &str) -> Result<u64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+
+    let amount: f64 = amount
+        .parse()
+        .map_err(|_| Error::Other(format!("Invalid size '{}', expected e.g. 50GB", text)))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => {
+            return Err(Error::Other(format!(
+                "Invalid size unit in '{}', use B, KB, MB, GB, TB or KiB..TiB",
+                text
+            )))
+        }
+    };
+
+    Ok((amount * multiplier as f64) as u64)
+}
+
+/// Human-readable size in decimal units, the same ones `parse_size` accepts: "1.5 GB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1_000 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1_000.0;
+    let mut unit = 0;
+    while value >= 1_000.0 && unit < UNITS.len() - 1 {
+        value /= 1_000.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Short human-readable duration, down to the second: "1h 05m", "3m 07s", "42s"
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let seconds = duration.as_secs();
+    match (seconds / 3600, seconds % 3600 / 60, seconds % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {:02}s", m, s),
+        (h, m, _) => format!("{}h {:02}m", h, m),
+    }
+}
+
+/// Total size of the files under `path`, or 0 if it doesn't exist
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn game(last_played: Option<DateTime<Utc>>) -> InstalledGame {
+        InstalledGame {
+            app_name: "Game".to_string(),
+            app_title: "Game".to_string(),
+            app_version: "1.0".to_string(),
+            install_path: PathBuf::from("/games/Game"),
+            executable: "game".to_string(),
+            pinned: false,
+            provider: crate::providers::EPIC.to_string(),
+            install_size: 0,
+            download_size: 0,
+            installed_at: None,
+            last_played,
+            volume: None,
+            save_path_template: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("6mo").unwrap(), Duration::days(180));
+        assert_eq!(parse_age("2w").unwrap(), Duration::days(14));
+        assert_eq!(parse_age("12h").unwrap(), Duration::hours(12));
+        assert!(parse_age("6").is_err());
+        assert!(parse_age("mo").is_err());
+        assert!(parse_age("99999999999999999y").is_err());
+        assert!(parse_age("9223372036854775807h").is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("50GB").unwrap(), 50_000_000_000);
+        assert_eq!(parse_size("1.5 GiB").unwrap(), 3 << 29);
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert!(parse_size("50 parsecs").is_err());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1_500_000_000), "1.5 GB");
+        assert_eq!(format_size(parse_size("50GB").unwrap()), "50.0 GB");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        use std::time::Duration;
+
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(187)), "3m 07s");
+        assert_eq!(format_duration(Duration::from_secs(3_900)), "1h 05m");
+    }
+
+    #[test]
+    fn test_filter_requires_every_criterion() {
+        let now = Utc::now();
+        let filter = UninstallFilter {
+            not_played_since: Some(Duration::days(180)),
+            larger_than: Some(1_000),
+        };
+
+        let stale = game(Some(now - Duration::days(200)));
+        assert!(filter.matches(&stale, 2_000, now));
+        assert!(!filter.matches(&stale, 500, now));
+        assert!(!filter.matches(&game(Some(now - Duration::days(10))), 2_000, now));
+        assert!(!filter.matches(&game(None), 2_000, now));
+
+        let installed = InstalledGame {
+            installed_at: Some(now - Duration::days(200)),
+            ..game(None)
+        };
+        assert!(filter.matches(&installed, 2_000, now));
+        assert!(!UninstallFilter::default().matches(&stale, 2_000, now));
+    }
+}
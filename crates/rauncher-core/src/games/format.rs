@@ -0,0 +1,83 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{Error, Result};
+
+/// Format of the install records under `installed/`. Bump when a change can't be read
+/// by older versions, and migrate older records in `read_versioned`'s callers.
+pub const INSTALL_RECORD_VERSION: u32 = 1;
+
+const VERSION_FIELD: &str = "format_version";
+
+/// Serialize a document, stamping it with its format version
+pub fn write_versioned<T: Serialize>(value: &T, version: u32) -> Result<String> {
+    let mut document = serde_json::to_value(value)?;
+
+    if let Some(object) = document.as_object_mut() {
+        object.insert(VERSION_FIELD.to_string(), version.into());
+    }
+
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Parse a document written by `write_versioned`, refusing ones from a newer format than
+/// `supported`. Documents from before versioning count as version 0.
+/// Returns the document together with the version it was written in.
+pub fn read_versioned<T: DeserializeOwned>(
+    contents: &str,
+    supported: u32,
+    what: &str,
+) -> Result<(T, u32)> {
+    let document: serde_json::Value = serde_json::from_str(contents)?;
+    let version = document
+        .get(VERSION_FIELD)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+
+    if version > u64::from(supported) {
+        return Err(Error::IncompatibleFormat(format!(
+            "{} (format {}, this version supports up to {})",
+            what, version, supported
+        )));
+    }
+
+    Ok((serde_json::from_value(document)?, version as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Record {
+        name: String,
+    }
+
+    #[test]
+    fn test_roundtrip_keeps_version() {
+        let record = Record {
+            name: "game".to_string(),
+        };
+        let contents = write_versioned(&record, 3).unwrap();
+
+        let (read, version): (Record, u32) = read_versioned(&contents, 3, "record").unwrap();
+        assert_eq!(read, record);
+        assert_eq!(version, 3);
+    }
+
+    #[test]
+    fn test_unversioned_documents_are_version_zero() {
+        let (_, version): (Record, u32) =
+            read_versioned(r#"{"name": "game"}"#, 1, "record").unwrap();
+        assert_eq!(version, 0);
+    }
+
+    #[test]
+    fn test_newer_format_is_refused() {
+        let err = read_versioned::<Record>(r#"{"name": "game", "format_version": 2}"#, 1, "record")
+            .unwrap_err();
+        assert!(matches!(err, Error::IncompatibleFormat(_)));
+        assert!(err.to_string().contains("newer version of rauncher"));
+    }
+}
@@ -0,0 +1,235 @@
+//! Confining a game with bubblewrap or firejail, for games not trusted with the whole home
+//! directory. The sandbox wraps the complete launch command, gamescope and Wine/Proton
+//! included, and lets the game write only to its install directory, Wine prefix, shader cache,
+//! save folder and the `sandbox_paths` it is given. The network is cut off unless
+//! `sandbox_network` is set.
+
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use super::launch::{find_in_path, is_proton, LaunchCommand};
+use crate::config::{GameSettings, SandboxTool};
+use crate::{Error, Result};
+
+/// What a sandboxed game may reach
+#[derive(Debug, Clone, PartialEq)]
+struct Profile {
+    network: bool,
+    writable: Vec<PathBuf>,
+    readable: Vec<PathBuf>,
+    working_dir: PathBuf,
+}
+
+/// Run `command` inside the sandbox the game's settings ask for. `writable` are the game's
+/// own directories; the runner's directory is made readable.
+pub(super) fn confine(
+    command: &mut LaunchCommand,
+    tool: SandboxTool,
+    settings: &GameSettings,
+    writable: &[PathBuf],
+) -> Result<()> {
+    let program = find_in_path(tool.as_str()).ok_or_else(|| {
+        Error::Other(format!(
+            "{} is set as the game's sandbox but was not found in PATH",
+            tool.as_str()
+        ))
+    })?;
+
+    let mut readable: Vec<PathBuf> = command
+        .runner
+        .iter()
+        .filter_map(|r| runner_root(r))
+        .collect();
+    // X11 clients authenticate with a cookie usually kept in the home directory
+    readable.extend(
+        env::var_os("XAUTHORITY")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".Xauthority")))
+            .filter(|path| path.is_file()),
+    );
+
+    let profile = Profile {
+        network: settings.sandbox_network,
+        writable: writable
+            .iter()
+            .chain(&settings.sandbox_paths)
+            .cloned()
+            .collect(),
+        readable,
+        working_dir: command.working_dir.clone(),
+    };
+    let mut args = match tool {
+        SandboxTool::Bwrap => bwrap_args(&profile, home().as_deref()),
+        SandboxTool::Firejail => firejail_args(&profile, home().as_deref()),
+    };
+
+    args.push(std::mem::replace(&mut command.program, program.into()));
+    args.append(&mut command.args);
+    command.args = args;
+    Ok(())
+}
+
+/// The whole system read-only, the home directory and /tmp emptied, then the game's
+/// directories mounted back on top
+fn bwrap_args(profile: &Profile, home: Option<&Path>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = ["--ro-bind", "/", "/", "--dev-bind", "/dev", "/dev"]
+        .into_iter()
+        .chain(["--proc", "/proc", "--tmpfs", "/tmp"])
+        .map(OsString::from)
+        .collect();
+    if let Some(home) = home {
+        args.extend(["--tmpfs".into(), home.into()]);
+    }
+    // The X11 sockets live in /tmp
+    let x11 = Path::new("/tmp/.X11-unix");
+    if x11.is_dir() {
+        args.extend(["--ro-bind".into(), x11.into(), x11.into()]);
+    }
+
+    for path in &profile.readable {
+        args.extend(["--ro-bind".into(), path.into(), path.into()]);
+    }
+    for path in &profile.writable {
+        args.extend(["--bind".into(), path.into(), path.into()]);
+    }
+    if !profile.network {
+        args.push("--unshare-net".into());
+    }
+    args.extend(["--die-with-parent".into(), "--new-session".into()]);
+    args.extend(["--chdir".into(), profile.working_dir.as_os_str().to_owned()]);
+    args
+}
+
+/// firejail without a profile of its own: the system read-only and the home directory left
+/// with only the game's directories in it, or emptied when none of them is there
+fn firejail_args(profile: &Profile, home: Option<&Path>) -> Vec<OsString> {
+    let mut args: Vec<OsString> = ["--quiet", "--noprofile", "--read-only=/"]
+        .into_iter()
+        .map(OsString::from)
+        .collect();
+
+    // Whitelisting anything in the home directory hides the rest of it, so a game installed
+    // elsewhere would otherwise see all of it
+    let paths = || profile.writable.iter().chain(&profile.readable);
+    let in_home = |path: &&PathBuf| home.is_some_and(|home| path.starts_with(home));
+    if paths().any(|path| in_home(&path)) {
+        for path in paths().filter(in_home) {
+            args.push(flag("--whitelist=", path));
+        }
+    } else {
+        args.push("--private".into());
+    }
+    // /tmp is emptied as with bubblewrap; firejail keeps the X11 sockets in it
+    args.push("--private-tmp".into());
+
+    for path in &profile.writable {
+        args.push(flag("--read-write=", path));
+    }
+    if !profile.network {
+        args.push("--net=none".into());
+    }
+    args
+}
+
+fn flag(name: &str, path: &Path) -> OsString {
+    let mut flag = OsString::from(name);
+    flag.push(path);
+    flag
+}
+
+/// The directory a Wine or Proton build lives in: next to the `proton` script, or above the
+/// `bin` directory holding `wine`
+fn runner_root(runner: &Path) -> Option<PathBuf> {
+    let dir = runner.parent()?;
+    if is_proton(runner) {
+        return Some(dir.to_path_buf());
+    }
+    match dir.file_name() {
+        Some(name) if name == "bin" => dir.parent().map(Path::to_path_buf),
+        _ => Some(dir.to_path_buf()),
+    }
+}
+
+fn home() -> Option<PathBuf> {
+    env::var_os("HOME").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(network: bool) -> Profile {
+        Profile {
+            network,
+            writable: vec![PathBuf::from("/home/me/Games/Kiwi")],
+            readable: vec![PathBuf::from("/home/me/runners/GE-Proton9-20")],
+            working_dir: PathBuf::from("/home/me/Games/Kiwi/bin"),
+        }
+    }
+
+    fn joined(args: &[OsString]) -> String {
+        args.iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_bwrap_hides_home_and_network() {
+        let args = joined(&bwrap_args(&profile(false), Some(Path::new("/home/me"))));
+        assert!(args.starts_with("--ro-bind / / --dev-bind /dev /dev --proc /proc --tmpfs /tmp"));
+        // The game's directories are mounted after the home directory is hidden
+        let home = args.find("--tmpfs /home/me ").unwrap();
+        let game = args
+            .find("--bind /home/me/Games/Kiwi /home/me/Games/Kiwi")
+            .unwrap();
+        assert!(home < game);
+        assert!(args
+            .contains("--ro-bind /home/me/runners/GE-Proton9-20 /home/me/runners/GE-Proton9-20"));
+        assert!(args.contains("--unshare-net"));
+        assert!(args.ends_with("--chdir /home/me/Games/Kiwi/bin"));
+
+        let args = joined(&bwrap_args(&profile(true), None));
+        assert!(!args.contains("--unshare-net"));
+    }
+
+    #[test]
+    fn test_firejail_whitelists_the_game() {
+        let home = Some(Path::new("/home/me"));
+        assert_eq!(
+            joined(&firejail_args(&profile(false), home)),
+            "--quiet --noprofile --read-only=/ --whitelist=/home/me/Games/Kiwi \
+             --whitelist=/home/me/runners/GE-Proton9-20 --private-tmp \
+             --read-write=/home/me/Games/Kiwi --net=none"
+        );
+        assert!(!joined(&firejail_args(&profile(true), home)).contains("--net"));
+    }
+
+    #[test]
+    fn test_firejail_hides_home_from_games_elsewhere() {
+        let profile = Profile {
+            network: false,
+            writable: vec![PathBuf::from("/mnt/games/Kiwi")],
+            readable: vec![PathBuf::from("/opt/runners/GE-Proton9-20")],
+            working_dir: PathBuf::from("/mnt/games/Kiwi"),
+        };
+        let args = joined(&firejail_args(&profile, Some(Path::new("/home/me"))));
+        assert!(args.contains("--read-only=/ "), "{}", args);
+        assert!(args.contains("--private "), "{}", args);
+        assert!(!args.contains("--whitelist"), "{}", args);
+        assert!(args.contains("--read-write=/mnt/games/Kiwi"), "{}", args);
+    }
+
+    #[test]
+    fn test_runner_root() {
+        assert_eq!(
+            runner_root(Path::new("/runners/GE-Proton9-20/proton")),
+            Some(PathBuf::from("/runners/GE-Proton9-20"))
+        );
+        assert_eq!(
+            runner_root(Path::new("/runners/wine-ge/bin/wine")),
+            Some(PathBuf::from("/runners/wine-ge"))
+        );
+    }
+}
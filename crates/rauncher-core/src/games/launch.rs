@@ -0,0 +1,385 @@
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::GameSettings;
+use crate::{Error, Result};
+
+/// A fully resolved launch: the program to spawn, its arguments and extra environment
+#[derive(Debug, Clone)]
+pub struct LaunchCommand {
+    pub program: OsString,
+    pub args: Vec<OsString>,
+    pub env: Vec<(String, OsString)>,
+    pub working_dir: PathBuf,
+    /// Wine or Proton running a Windows executable
+    pub runner: Option<PathBuf>,
+}
+
+impl LaunchCommand {
+    /// Build the command line for a game executable. Wrappers are layered from the outside in:
+    /// gamescope, gamemoderun, mangohud, then Wine/Proton for Windows executables. The game's
+    /// configured launch arguments follow the executable.
+    pub fn build(
+        executable: &Path,
+        working_dir: &Path,
+        settings: &GameSettings,
+        prefix: &Path,
+    ) -> Result<Self> {
+        let mut argv: Vec<OsString> = Vec::new();
+        let mut env = Vec::new();
+        let mut runner = None;
+
+        if cfg!(target_os = "linux") {
+            if settings.gamescope {
+                argv.push(require_in_path("gamescope")?.into());
+                argv.extend(
+                    settings
+                        .gamescope_args
+                        .split_whitespace()
+                        .map(OsString::from),
+                );
+                argv.push("--".into());
+            }
+
+            if settings.gamemode {
+                argv.push(require_in_path("gamemoderun")?.into());
+            }
+
+            if settings.mangohud {
+                argv.push(require_in_path("mangohud")?.into());
+            }
+        }
+
+        if needs_wine(executable) {
+            let wine = wine_for(settings)?;
+            push_runner(&wine, prefix, &mut argv, &mut env);
+            runner = Some(wine);
+        }
+
+        argv.push(executable.into());
+        argv.extend(settings.launch_args.split_whitespace().map(OsString::from));
+
+        // The game's own variables come last so they can override the ones set above
+        env.extend(settings.env.iter().map(|(k, v)| (k.clone(), v.into())));
+
+        let mut argv = argv.into_iter();
+        let program = argv.next().unwrap_or_default();
+
+        Ok(Self {
+            program,
+            args: argv.collect(),
+            env,
+            working_dir: working_dir.to_path_buf(),
+            runner,
+        })
+    }
+
+    /// Run a program through the game's Wine or Proton inside its prefix, e.g. `winecfg` or an
+    /// installer. Unlike `build`, no launch wrappers or launch arguments are added.
+    pub fn in_prefix(
+        args: &[OsString],
+        working_dir: &Path,
+        settings: &GameSettings,
+        prefix: &Path,
+    ) -> Result<Self> {
+        let wine = wine_for(settings)?;
+        let mut argv = Vec::new();
+        let mut env = Vec::new();
+        push_runner(&wine, prefix, &mut argv, &mut env);
+        argv.extend(args.iter().cloned());
+        env.extend(settings.env.iter().map(|(k, v)| (k.clone(), v.into())));
+
+        let mut argv = argv.into_iter();
+        Ok(Self {
+            program: argv.next().unwrap_or_default(),
+            args: argv.collect(),
+            env,
+            working_dir: working_dir.to_path_buf(),
+            runner: Some(wine),
+        })
+    }
+
+    /// Run winetricks on the game's prefix with the game's Wine. Proton keeps the actual Wine
+    /// prefix in `pfx` and its Wine binaries under `files` (`dist` in older builds).
+    pub fn winetricks(verbs: &[String], settings: &GameSettings, prefix: &Path) -> Result<Self> {
+        let winetricks = find_in_path("winetricks")
+            .ok_or_else(|| Error::Other("winetricks was not found in PATH".to_string()))?;
+        let runner = wine_for(settings)?;
+
+        let (wine, wine_prefix) = if is_proton(&runner) {
+            (proton_wine(&runner)?, prefix.join("pfx"))
+        } else {
+            (runner.clone(), prefix.to_path_buf())
+        };
+
+        let mut env = vec![
+            ("WINEPREFIX".to_string(), wine_prefix.into()),
+            ("WINE".to_string(), wine.clone().into()),
+        ];
+        let wineserver = wine.with_file_name("wineserver");
+        if wineserver.is_file() {
+            env.push(("WINESERVER".to_string(), wineserver.into()));
+        }
+        env.extend(settings.env.iter().map(|(k, v)| (k.clone(), v.into())));
+
+        Ok(Self {
+            program: winetricks.into(),
+            args: verbs.iter().map(OsString::from).collect(),
+            env,
+            working_dir: prefix.to_path_buf(),
+            runner: Some(runner),
+        })
+    }
+
+    /// Whether the command runs through Wine/Proton and therefore needs its prefix
+    pub fn uses_prefix(&self) -> bool {
+        self.env
+            .iter()
+            .any(|(k, _)| k == "WINEPREFIX" || k == "STEAM_COMPAT_DATA_PATH")
+    }
+
+    pub fn to_command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .current_dir(&self.working_dir);
+        command
+    }
+}
+
+/// Windows executables need a compatibility layer everywhere but on Windows
+fn needs_wine(executable: &Path) -> bool {
+    !cfg!(target_os = "windows")
+        && executable
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+/// The game's Wine or Proton, or wine from PATH
+fn wine_for(settings: &GameSettings) -> Result<PathBuf> {
+    match &settings.wine {
+        Some(wine) => Ok(wine.clone()),
+        None => require_in_path("wine"),
+    }
+}
+
+/// Put the runner in front of the command line and point it at the prefix
+fn push_runner(
+    wine: &Path,
+    prefix: &Path,
+    argv: &mut Vec<OsString>,
+    env: &mut Vec<(String, OsString)>,
+) {
+    if is_proton(wine) {
+        env.push(("STEAM_COMPAT_DATA_PATH".to_string(), prefix.into()));
+        env.push((
+            "STEAM_COMPAT_CLIENT_INSTALL_PATH".to_string(),
+            prefix.into(),
+        ));
+        argv.push(wine.into());
+        argv.push("run".into());
+    } else {
+        env.push(("WINEPREFIX".to_string(), prefix.into()));
+        argv.push(wine.into());
+    }
+}
+
+/// The wine binary shipped inside a Proton build
+fn proton_wine(proton: &Path) -> Result<PathBuf> {
+    let dir = proton.parent().unwrap_or(Path::new("."));
+    ["files", "dist"]
+        .iter()
+        .map(|sub| dir.join(sub).join("bin").join("wine"))
+        .find(|wine| wine.is_file())
+        .ok_or_else(|| Error::Other(format!("No wine binary found next to {:?}", proton)))
+}
+
+/// Proton is driven through its `proton` script rather than a wine binary
+pub(crate) fn is_proton(runner: &Path) -> bool {
+    runner.file_name().is_some_and(|name| name == "proton")
+}
+
+/// Look up an executable in PATH
+pub(crate) fn find_in_path(name: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+fn require_in_path(name: &str) -> Result<PathBuf> {
+    find_in_path(name)
+        .ok_or_else(|| Error::Other(format!("{} is enabled but was not found in PATH", name)))
+}
+
+/// Resolve a launch override against the install directory, refusing paths that lead out of
+/// it, whether through `..`, an absolute path or a symlink
+pub(crate) fn resolve_in_install_dir(install_path: &Path, path: &Path) -> Result<PathBuf> {
+    let joined = install_path.join(path);
+    let resolved = joined
+        .canonicalize()
+        .map_err(|e| Error::Other(format!("{:?} not found: {}", joined, e)))?;
+
+    if !resolved.starts_with(install_path.canonicalize()?) {
+        return Err(Error::Other(format!(
+            "{:?} is outside the install directory {:?}",
+            path, install_path
+        )));
+    }
+
+    Ok(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_executable_runs_directly() {
+        let settings = GameSettings::default();
+        let cmd = LaunchCommand::build(
+            Path::new("/games/app/run.sh"),
+            Path::new("/games/app"),
+            &settings,
+            Path::new("/prefixes/app"),
+        )
+        .unwrap();
+
+        assert_eq!(cmd.program, OsString::from("/games/app/run.sh"));
+        assert!(cmd.args.is_empty());
+        assert!(cmd.env.is_empty());
+    }
+
+    #[test]
+    fn test_launch_args_follow_executable() {
+        let settings = GameSettings {
+            launch_args: "-windowed  -dx11".to_string(),
+            ..Default::default()
+        };
+        let cmd = LaunchCommand::build(
+            Path::new("/games/app/run.sh"),
+            Path::new("/games/app"),
+            &settings,
+            Path::new("/prefixes/app"),
+        )
+        .unwrap();
+
+        assert_eq!(cmd.program, OsString::from("/games/app/run.sh"));
+        assert_eq!(
+            cmd.args,
+            vec![OsString::from("-windowed"), OsString::from("-dx11")]
+        );
+    }
+
+    #[test]
+    fn test_game_env_is_applied() {
+        let settings = GameSettings {
+            env: [("DXVK_HUD".to_string(), "fps".to_string())].into(),
+            ..Default::default()
+        };
+        let cmd = LaunchCommand::build(
+            Path::new("/games/app/run.sh"),
+            Path::new("/games/app"),
+            &settings,
+            Path::new("/prefixes/app"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            cmd.env,
+            vec![("DXVK_HUD".to_string(), OsString::from("fps"))]
+        );
+    }
+
+    #[test]
+    fn test_overrides_stay_in_install_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let install = root.path().join("game");
+        std::fs::create_dir_all(install.join("mods")).unwrap();
+        std::fs::write(install.join("mods/loader.sh"), "").unwrap();
+        std::fs::write(root.path().join("outside.sh"), "").unwrap();
+
+        assert_eq!(
+            resolve_in_install_dir(&install, Path::new("mods/loader.sh")).unwrap(),
+            install.join("mods/loader.sh")
+        );
+        assert!(resolve_in_install_dir(&install, Path::new("mods")).is_ok());
+        assert!(resolve_in_install_dir(&install, Path::new("../outside.sh")).is_err());
+        assert!(resolve_in_install_dir(&install, &root.path().join("outside.sh")).is_err());
+        assert!(resolve_in_install_dir(&install, Path::new("missing.sh")).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_proton_runner_uses_compat_data_path() {
+        let settings = GameSettings {
+            wine: Some(PathBuf::from("/runners/GE-Proton9/proton")),
+            ..Default::default()
+        };
+        let cmd = LaunchCommand::build(
+            Path::new("/games/app/Game.exe"),
+            Path::new("/games/app"),
+            &settings,
+            Path::new("/prefixes/app"),
+        )
+        .unwrap();
+
+        assert_eq!(cmd.program, OsString::from("/runners/GE-Proton9/proton"));
+        assert_eq!(cmd.args[0], OsString::from("run"));
+        assert_eq!(cmd.args[1], OsString::from("/games/app/Game.exe"));
+        assert!(cmd
+            .env
+            .iter()
+            .any(|(k, v)| k == "STEAM_COMPAT_DATA_PATH" && v == "/prefixes/app"));
+    }
+
+    #[test]
+    fn test_prefix_command_skips_wrappers() {
+        let settings = GameSettings {
+            wine: Some(PathBuf::from("/runners/wine-ge/bin/wine")),
+            gamescope: true,
+            launch_args: "-windowed".to_string(),
+            ..Default::default()
+        };
+        let cmd = LaunchCommand::in_prefix(
+            &[OsString::from("winecfg")],
+            Path::new("/prefixes/app"),
+            &settings,
+            Path::new("/prefixes/app"),
+        )
+        .unwrap();
+
+        assert_eq!(cmd.program, OsString::from("/runners/wine-ge/bin/wine"));
+        assert_eq!(cmd.args, vec![OsString::from("winecfg")]);
+        assert!(cmd
+            .env
+            .iter()
+            .any(|(k, v)| k == "WINEPREFIX" && v == "/prefixes/app"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_missing_wrapper_is_reported() {
+        // Only meaningful on machines without gamescope installed
+        if find_in_path("gamescope").is_some() {
+            return;
+        }
+
+        let settings = GameSettings {
+            gamescope: true,
+            ..Default::default()
+        };
+        let err = LaunchCommand::build(
+            Path::new("/games/app/run.sh"),
+            Path::new("/games/app"),
+            &settings,
+            Path::new("/prefixes/app"),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("gamescope"));
+    }
+}
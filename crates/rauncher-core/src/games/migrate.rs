@@ -0,0 +1,141 @@
+//! Taking over the games and settings of Legendary or Heroic, read by `crate::legendary`.
+//! Games already managed by rauncher and settings already made in rauncher are left as they
+//! are. Exporting goes the other way, so both can share the same installs for a while.
+
+use std::path::{Path, PathBuf};
+
+use super::{GameManager, InstalledGame};
+use crate::config::Config;
+use crate::{Error, Result};
+
+/// What a migration brought over, or would with `dry_run`
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    /// Account of the session taken over, when rauncher wasn't logged in
+    pub account: Option<String>,
+    pub games: Vec<InstalledGame>,
+    /// Games whose launch settings were taken over
+    pub settings: Vec<String>,
+    /// Wine prefixes linked into rauncher's prefixes directory, by app name
+    pub prefixes: Vec<(String, PathBuf)>,
+    /// Aliases taken over
+    pub aliases: Vec<String>,
+}
+
+impl GameManager {
+    /// Migrate from `source` ("legendary" or "heroic"), reading its configuration from `dir`
+    /// or from where the launcher keeps it
+    pub fn migrate(
+        &self,
+        source: &str,
+        dir: Option<&Path>,
+        dry_run: bool,
+    ) -> Result<MigrationReport> {
+        let migration = crate::legendary::read(source, dir)?;
+        let mut report = MigrationReport::default();
+
+        if let Some(token) = migration
+            .token
+            .filter(|_| self.auth().get_refresh_token().is_none())
+        {
+            report.account = Some(token.account_id.clone());
+            if !dry_run {
+                let mut auth = self.auth();
+                auth.set_token(token)?;
+                self.set_auth(auth);
+            }
+        }
+
+        let installed = self.list_installed()?;
+        let mut config = Config::clone(&self.config());
+        for game in migration.games {
+            if installed.iter().any(|g| g.app_name == game.app_name) {
+                log::debug!("{} is already managed by rauncher", game.app_name);
+                continue;
+            }
+
+            if let Some(settings) = migration.settings.get(&game.app_name) {
+                if !config.games.contains_key(&game.app_name) {
+                    report.settings.push(game.app_name.clone());
+                    config.games.insert(game.app_name.clone(), settings.clone());
+                }
+            }
+
+            // The prefix holds the game's saves and installed runtimes, so it's reused
+            // rather than copied
+            #[cfg(unix)]
+            if let Some(target) = migration.prefixes.get(&game.app_name) {
+                let prefix = Config::prefixes_dir()?.join(&game.app_name);
+                if target.is_dir() && std::fs::symlink_metadata(&prefix).is_err() {
+                    if !dry_run {
+                        link_prefix(target, &prefix)?;
+                    }
+                    report
+                        .prefixes
+                        .push((game.app_name.clone(), target.clone()));
+                }
+            }
+
+            if !dry_run {
+                game.save(&config)?;
+            }
+            report.games.push(game);
+        }
+
+        for (alias, app_name) in migration.aliases {
+            if config.aliases.contains_key(&alias)
+                || crate::providers::parse_address(&alias).0.is_some()
+            {
+                continue;
+            }
+            config.aliases.insert(alias.clone(), app_name);
+            report.aliases.push(alias);
+        }
+
+        let config_changed = !report.settings.is_empty() || !report.aliases.is_empty();
+        if !dry_run && config_changed {
+            config.save()?;
+            self.set_config(config);
+        }
+        Ok(report)
+    }
+
+    /// Record the installed Epic games in `format` ("legendary") in `dir`, or in Legendary's
+    /// configuration directory. Returns the games exported; other stores' are left out, as
+    /// Legendary only handles Epic's.
+    pub fn export(&self, format: &str, dir: Option<&Path>) -> Result<Vec<InstalledGame>> {
+        if !crate::legendary::EXPORT_FORMATS.contains(&format) {
+            return Err(Error::Other(format!(
+                "Unknown export format '{}'. Must be one of: {}",
+                format,
+                crate::legendary::EXPORT_FORMATS.join(", ")
+            )));
+        }
+        let dir = match dir {
+            Some(dir) => dir.to_path_buf(),
+            None => crate::legendary::legendary_dir().ok_or_else(|| {
+                Error::Other("No home directory to find Legendary's configuration in".to_string())
+            })?,
+        };
+
+        let games: Vec<InstalledGame> = self
+            .list_installed()?
+            .into_iter()
+            .filter(|game| game.provider == crate::providers::EPIC)
+            .collect();
+        if !games.is_empty() {
+            crate::legendary::write(&dir, &games)?;
+        }
+        Ok(games)
+    }
+}
+
+#[cfg(unix)]
+fn link_prefix(target: &Path, prefix: &Path) -> Result<()> {
+    if let Some(parent) = prefix.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::os::unix::fs::symlink(target, prefix)?;
+    crate::logs::transcript::touched("linked", prefix);
+    Ok(())
+}
@@ -0,0 +1,2328 @@
+mod aliases;
+mod chunk_cache;
+mod crash;
+mod doctor;
+mod egl_sync;
+mod filter;
+mod format;
+mod isolation;
+mod launch;
+mod migrate;
+mod mods;
+mod offline;
+mod orphans;
+mod plan;
+mod positioned;
+mod prefix;
+mod prerequisites;
+mod running;
+mod session;
+mod shader_cache;
+mod store;
+mod verify;
+mod workers;
+
+pub use aliases::{add_alias, remove_alias, resolve_name, suggest_names};
+pub use crash::{crashes_dir, CrashReport, GameExit, RunningGame};
+pub use doctor::{Check, Severity, LOW_SPACE};
+pub use egl_sync::EglSyncReport;
+pub use filter::{dir_size, format_duration, format_size, parse_age, parse_size, UninstallFilter};
+use launch::resolve_in_install_dir;
+pub use launch::LaunchCommand;
+pub use migrate::MigrationReport;
+pub use orphans::{OrphanMatch, OrphanedInstall};
+pub use plan::InstallPlan;
+pub use running::RunningRecord;
+pub use shader_cache::ShaderCacheUpdate;
+pub use store::DEFAULT_STORE_COUNTRY;
+pub use verify::{FileCheck, FileStatus, VerifyReport};
+
+use chunk_cache::ChunkCache;
+use format::{read_versioned, write_versioned, INSTALL_RECORD_VERSION};
+use positioned::PositionedFile;
+use workers::WorkerPool;
+
+use chrono::{DateTime, Utc};
+use fs4::fs_std::FileExt;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::api::{
+    BuildInfo, BuildVersion, ChunkPart, CloudSave, Entitlement, EpicClient, Game, GameManifest,
+    ReleaseLookup, SessionInfo, UeAsset,
+};
+use crate::auth::{AuthManager, AuthToken};
+use crate::config::{Config, GameSettings};
+use crate::downloads::{DownloadControl, SpeedMeter, TransferStats};
+use crate::feeds::FeedEntry;
+use crate::history::{HistoryAction, HistoryEntry};
+use crate::metrics;
+use crate::network::NetworkGate;
+use crate::notify::{self, Notification};
+use crate::prompt::{PolicyPrompter, Prompter, Question, QuestionKind};
+use crate::providers::{self, StoreProvider};
+use crate::stats::{LibraryStats, PlaySession};
+use crate::storage::{volume, Removal};
+use crate::{Error, Result};
+
+/// Chunks of a file downloaded at the same time
+const CHUNK_DOWNLOADS: usize = 8;
+
+/// Time between `InstallProgress::Transfer` events
+const TRANSFER_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Folder in the install directory cloud saves are synced with when the game's save
+/// directory isn't known
+pub(crate) const FALLBACK_SAVE_DIR: &str = "saves";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledGame {
+    pub app_name: String,
+    pub app_title: String,
+    pub app_version: String,
+    pub install_path: PathBuf,
+    pub executable: String,
+    /// Pinned games keep their installed build and are skipped by `update --all`
+    #[serde(default)]
+    pub pinned: bool,
+    /// Store the game was installed from
+    #[serde(default = "providers::default_provider")]
+    pub provider: String,
+    /// Bytes written by the install; 0 for records from before sizes were tracked
+    #[serde(default)]
+    pub install_size: u64,
+    /// Bytes downloaded for the install, compressed; 0 when not known
+    #[serde(default)]
+    pub download_size: u64,
+    #[serde(default)]
+    pub installed_at: Option<DateTime<Utc>>,
+    /// Last time rauncher launched the game
+    #[serde(default)]
+    pub last_played: Option<DateTime<Utc>>,
+    /// Marker id of the removable or network library the game is installed in
+    #[serde(default)]
+    pub volume: Option<String>,
+    /// Where the game keeps its saves, as given by the store; see `crate::saves::resolve_template`
+    #[serde(default)]
+    pub save_path_template: Option<String>,
+}
+
+impl InstalledGame {
+    /// Identifier that stays the same across listings and stores
+    pub fn id(&self) -> String {
+        providers::qualified_id(&self.provider, &self.app_name)
+    }
+
+    /// Bytes the game takes on disk, measuring the install directory when the record
+    /// doesn't know
+    pub fn disk_usage(&self) -> u64 {
+        if self.install_size > 0 {
+            self.install_size
+        } else {
+            dir_size(&self.install_path)
+        }
+    }
+
+    /// Whether the game is on a removable or network drive that isn't mounted, or has been
+    /// swapped for another
+    pub fn is_unavailable(&self) -> bool {
+        self.volume.as_ref().is_some_and(|id| {
+            let root = self.install_path.parent().unwrap_or(&self.install_path);
+            crate::storage::volume::marker(root).as_ref() != Some(id)
+        })
+    }
+
+    pub fn save(&self, config: &Config) -> Result<()> {
+        let games_dir = Self::installed_games_dir(config)?;
+        fs::create_dir_all(&games_dir)?;
+
+        let game_file = games_dir.join(format!("{}.json", self.app_name));
+        let contents = write_versioned(self, INSTALL_RECORD_VERSION)?;
+        fs::write(&game_file, contents)?;
+        crate::logs::transcript::touched("wrote", &game_file);
+
+        Ok(())
+    }
+
+    pub fn load(config: &Config, app_name: &str) -> Result<Self> {
+        let games_dir = Self::installed_games_dir(config)?;
+        let game_file = games_dir.join(format!("{}.json", app_name));
+
+        if !game_file.exists() {
+            return Err(Error::GameNotFound(app_name.to_string()));
+        }
+
+        let contents = fs::read_to_string(&game_file)?;
+        Self::from_record(config, &contents, app_name)
+    }
+
+    /// Load an installed game by bare app name or `provider:app_name` address
+    pub fn resolve(config: &Config, address: &str) -> Result<Self> {
+        let (provider, app_name) = providers::parse_address(address);
+        let game = Self::load(config, app_name)?;
+
+        match provider {
+            Some(provider) if provider != game.provider => {
+                Err(Error::GameNotFound(address.to_string()))
+            }
+            _ => Ok(game),
+        }
+    }
+
+    /// Parse an install record, rewriting records from older formats in the current one.
+    /// Fields added since then have serde defaults, so reading them is the migration.
+    fn from_record(config: &Config, contents: &str, app_name: &str) -> Result<Self> {
+        let what = format!("The install record of {}", app_name);
+        let (game, version): (Self, u32) = read_versioned(contents, INSTALL_RECORD_VERSION, &what)?;
+
+        if version < INSTALL_RECORD_VERSION {
+            log::debug!(
+                "Migrating install record of {} from format {}",
+                app_name,
+                version
+            );
+            game.save(config)?;
+        }
+
+        Ok(game)
+    }
+
+    pub fn list_installed(config: &Config) -> Result<Vec<Self>> {
+        let games_dir = Self::installed_games_dir(config)?;
+
+        if !games_dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut games = Vec::new();
+
+        for entry in fs::read_dir(&games_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                if let Ok(contents) = fs::read_to_string(&path) {
+                    let app_name = path.file_stem().unwrap_or_default().to_string_lossy();
+
+                    match Self::from_record(config, &contents, &app_name) {
+                        Ok(game) => games.push(game),
+                        Err(e @ Error::IncompatibleFormat(_)) => log::warn!("{}", e),
+                        Err(e) => {
+                            log::debug!("Ignoring unreadable install record {:?}: {}", path, e)
+                        }
+                    }
+                }
+            }
+        }
+
+        // Directory order is arbitrary; keep listings stable between runs
+        games.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+
+        Ok(games)
+    }
+
+    pub fn delete(&self, config: &Config) -> Result<()> {
+        let games_dir = Self::installed_games_dir(config)?;
+        let game_file = games_dir.join(format!("{}.json", self.app_name));
+
+        if game_file.exists() {
+            fs::remove_file(&game_file)?;
+            crate::logs::transcript::touched("removed", &game_file);
+        }
+
+        Ok(())
+    }
+
+    fn installed_games_dir(_config: &Config) -> Result<PathBuf> {
+        let data_dir = Config::data_dir()?;
+        Ok(data_dir.join("installed"))
+    }
+}
+
+/// Options controlling what `GameManager::uninstall_game_with_options` removes
+#[derive(Debug, Clone, Default)]
+pub struct UninstallOptions {
+    /// Also remove the game's Wine prefix and shader cache
+    pub purge: bool,
+    /// Whether what's removed goes to the OS trash or is deleted for good
+    pub removal: Removal,
+}
+
+/// Per-session changes to how `GameManager::launch_game_with_options` starts a game, taking
+/// precedence over the game's settings
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// Arguments passed after the game's configured ones
+    pub args: Vec<String>,
+    /// Executable to run instead, relative to the install directory
+    pub executable: Option<PathBuf>,
+    /// Working directory, relative to the install directory
+    pub working_dir: Option<PathBuf>,
+    /// Relay the game's output to the debug log as well as its log file, for callers that
+    /// wait on the game with `RunningGame::wait_for_exit`
+    pub stream_output: bool,
+    /// Launch even when the game is already running, which risks two instances writing the
+    /// same saves
+    pub force: bool,
+}
+
+/// Progress of an install or a verification, reported to the sender set with
+/// `GameManager::set_progress`. Serializes as a flat object tagged with its `event`
+/// (`started`, `file_done`, `transfer`, `finished`, `verify_started`, `file_verified`), the
+/// format of `--progress-json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum InstallProgress {
+    /// The manifest is known and `files` files are about to be downloaded
+    Started {
+        app_name: String,
+        version: String,
+        files: usize,
+        bytes: u64,
+    },
+    /// File `index` (1-based) of the install has been written
+    FileDone {
+        app_name: String,
+        index: usize,
+        filename: String,
+        bytes_done: u64,
+    },
+    /// Bytes were downloaded; sent at most twice a second
+    Transfer {
+        app_name: String,
+        #[serde(flatten)]
+        stats: TransferStats,
+    },
+    /// Every file is in place and the install has been recorded
+    Finished { app_name: String },
+    /// A verification is about to hash `files` files
+    VerifyStarted {
+        app_name: String,
+        files: usize,
+        bytes: u64,
+    },
+    /// The `index`th file (1-based) of a verification has been checked; files are checked
+    /// in parallel, so not in the manifest's order
+    FileVerified {
+        app_name: String,
+        index: usize,
+        filename: String,
+        status: FileStatus,
+        bytes_done: u64,
+    },
+}
+
+/// Installs, launches and library listings. A manager is meant to live for the whole run and
+/// be shared: clones are cheap and share the configuration, the session, the store clients and
+/// what's known of the network, so the GUI, the CLI and the daemon can all work through one.
+/// What the `set_*` methods change (progress reporting, prompts, offline mode) belongs to the
+/// clone it's called on.
+#[derive(Clone)]
+pub struct GameManager {
+    shared: Arc<Shared>,
+    progress: Option<UnboundedSender<InstallProgress>>,
+    /// Show desktop notifications, for frontends running unattended or in the background
+    notifications: bool,
+    /// Answers questions asked mid-operation; the config's policy unless a frontend asks
+    prompter: Arc<dyn Prompter>,
+    /// Set when installing for the download queue, which can pause and cancel the install
+    download_control: Option<DownloadControl>,
+    /// Offline mode, turned on with `set_offline`
+    offline: bool,
+}
+
+/// What every clone of a manager sees
+struct Shared {
+    /// Replaced as a whole on changes, so readers keep a consistent snapshot
+    config: RwLock<Arc<Config>>,
+    auth: RwLock<AuthManager>,
+    /// Held while a token is refreshed, so concurrent operations refresh it once
+    refreshing: tokio::sync::Mutex<()>,
+    client: EpicClient,
+    /// Stores other than Epic, which keeps its own client for Epic-only features
+    stores: Vec<Box<dyn StoreProvider>>,
+    /// Whether the last library listing was served from the cache, a store being unreachable
+    served_from_cache: AtomicBool,
+    network: NetworkGate,
+}
+
+impl GameManager {
+    pub fn new(config: Config, auth: AuthManager) -> Result<Self> {
+        let client = EpicClient::with_settings(&config.epic)?;
+        let stores = providers::PROVIDER_IDS
+            .iter()
+            .filter(|id| **id != providers::EPIC)
+            .map(|id| providers::create(id))
+            .collect::<Result<_>>()?;
+
+        let prompter = Arc::new(PolicyPrompter::new(config.prompt_answers.clone()));
+
+        Ok(Self {
+            shared: Arc::new(Shared {
+                config: RwLock::new(Arc::new(config)),
+                auth: RwLock::new(auth),
+                refreshing: tokio::sync::Mutex::new(()),
+                client,
+                stores,
+                served_from_cache: AtomicBool::new(false),
+                network: NetworkGate::default(),
+            }),
+            progress: None,
+            notifications: false,
+            prompter,
+            download_control: None,
+            offline: false,
+        })
+    }
+
+    /// The configuration as it is now
+    pub fn config(&self) -> Arc<Config> {
+        self.shared
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Use `config` from now on, e.g. after the user changed settings elsewhere
+    pub fn set_config(&self, config: Config) {
+        *self
+            .shared
+            .config
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = Arc::new(config);
+    }
+
+    /// Change the configuration and save it
+    pub fn update_config<T>(&self, change: impl FnOnce(&mut Config) -> T) -> Result<T> {
+        let mut config = self
+            .shared
+            .config
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut changed = Config::clone(&config);
+        let result = change(&mut changed);
+        changed.save()?;
+        *config = Arc::new(changed);
+        Ok(result)
+    }
+
+    /// The Epic session as it is now
+    pub fn auth(&self) -> AuthManager {
+        self.shared
+            .auth
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Use `auth` from now on, e.g. after logging in or switching profiles
+    pub fn set_auth(&self, auth: AuthManager) {
+        *self.shared.auth.write().unwrap_or_else(|e| e.into_inner()) = auth;
+    }
+
+    /// Report install progress to `sender` from now on
+    pub fn set_progress(&mut self, sender: UnboundedSender<InstallProgress>) {
+        self.progress = Some(sender);
+    }
+
+    /// Show desktop notifications for finished installs and failed cloud save syncs, as
+    /// turned on in the config
+    pub fn set_notifications(&mut self, enabled: bool) {
+        self.notifications = enabled;
+    }
+
+    /// Ask the user through `prompter` when an operation needs a decision, such as a cloud
+    /// save conflicting with a local one
+    pub fn set_prompter(&mut self, prompter: Arc<dyn Prompter>) {
+        self.prompter = prompter;
+    }
+
+    pub(crate) fn set_download_control(&mut self, control: DownloadControl) {
+        self.download_control = Some(control);
+    }
+
+    /// Wait here while the queued download is paused or the connection is metered, and stop
+    /// if it was cancelled
+    async fn download_checkpoint(&self) -> Result<()> {
+        self.shared
+            .network
+            .before_chunk(&self.config().metered, self.download_control.as_ref())
+            .await
+    }
+
+    /// Whether the connection was metered when downloads last looked at it
+    pub fn is_metered(&self) -> bool {
+        self.shared.network.is_metered()
+    }
+
+    /// Show a desktop notification if notifications are on
+    pub fn notify(&self, notification: Notification) {
+        if self.notifications {
+            notify::send(&self.config().notifications, &notification);
+        }
+    }
+
+    fn report(&self, event: InstallProgress) {
+        if let Some(control) = &self.download_control {
+            control.record(&event);
+        }
+        if let InstallProgress::Transfer { app_name, stats } = &event {
+            metrics::record_speed(app_name, stats.speed);
+        }
+
+        // Nobody listening is fine; progress is purely informational
+        if let Some(sender) = &self.progress {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn provider(&self, id: &str) -> Result<&dyn StoreProvider> {
+        if id == providers::EPIC {
+            return Ok(&self.shared.client);
+        }
+
+        self.shared
+            .stores
+            .iter()
+            .find(|store| store.id() == id)
+            .map(|store| store.as_ref())
+            .ok_or_else(|| Error::Other(format!("Unknown provider '{}'", id)))
+    }
+
+    /// Stored token for a provider, without refreshing it
+    fn stored_token(&self, provider: &str) -> Result<AuthToken> {
+        if provider == providers::EPIC {
+            return self.auth().get_token().cloned();
+        }
+
+        AuthToken::load_for(provider)?.ok_or_else(|| {
+            Error::Auth(format!(
+                "Not logged in to {}. Run 'rauncher auth --provider {}' first",
+                provider, provider
+            ))
+        })
+    }
+
+    /// Token for a provider, refreshed and persisted when it's about to expire
+    async fn provider_token(&self, provider: &str) -> Result<AuthToken> {
+        self.require_online()?;
+        if provider == providers::EPIC {
+            return self.ensure_valid_token().await;
+        }
+
+        let token = self.stored_token(provider)?;
+        if token.expires_at - chrono::Utc::now() > chrono::Duration::minutes(5) {
+            return Ok(token);
+        }
+
+        let _refreshing = self.shared.refreshing.lock().await;
+        // Another operation may have refreshed it meanwhile
+        let token = self.stored_token(provider)?;
+        if token.expires_at - chrono::Utc::now() > chrono::Duration::minutes(5) {
+            return Ok(token);
+        }
+        self.refresh_provider_token(provider, token).await
+    }
+
+    /// A new token for a provider whose store turned `rejected` down. Downloads running at
+    /// once all hit this together; the first one refreshes and the others get its token.
+    async fn renew_token(&self, provider: &str, rejected: &AuthToken) -> Result<AuthToken> {
+        self.require_online()?;
+
+        let _refreshing = self.shared.refreshing.lock().await;
+        let token = self.stored_token(provider)?;
+        if token.access_token != rejected.access_token {
+            return Ok(token);
+        }
+        self.refresh_provider_token(provider, token).await
+    }
+
+    /// Refresh a provider's token and persist the new one. Callers hold `refreshing`.
+    async fn refresh_provider_token(&self, provider: &str, token: AuthToken) -> Result<AuthToken> {
+        let refreshed = self.refresh_provider_token_now(provider, token).await;
+        metrics::record_token_refresh(refreshed.is_ok());
+        refreshed
+    }
+
+    async fn refresh_provider_token_now(
+        &self,
+        provider: &str,
+        token: AuthToken,
+    ) -> Result<AuthToken> {
+        if provider == providers::EPIC {
+            let mut auth = self.auth();
+            let refreshed = auth.refresh_with(&self.shared.client).await?.clone();
+            self.set_auth(auth);
+            return Ok(refreshed);
+        }
+
+        let mut refreshed = self
+            .provider(provider)?
+            .refresh_token(&token.refresh_token)
+            .await?;
+        if refreshed.account_id.is_empty() {
+            refreshed.account_id = token.account_id;
+        }
+        refreshed.save_for(provider)?;
+
+        Ok(refreshed)
+    }
+
+    async fn ensure_valid_token(&self) -> Result<crate::auth::AuthToken> {
+        self.require_online()?;
+
+        let _refreshing = self.shared.refreshing.lock().await;
+        // Se il token è valido e non in scadenza imminente, riutilizziamolo
+        let mut auth = self.auth();
+        if let Ok(tok) = auth.get_token() {
+            if !auth.token_needs_refresh() {
+                return Ok(tok.clone());
+            }
+        }
+
+        // Prova a fare refresh; AuthManager lo persiste su disco per gli altri componenti
+        let refreshed = auth.ensure_valid_token(&self.shared.client).await;
+        metrics::record_token_refresh(refreshed.is_ok());
+        let new_tok = refreshed?.clone();
+        self.set_auth(auth);
+        Ok(new_tok)
+    }
+
+    pub async fn list_library(&self) -> Result<Vec<Game>> {
+        self.list_provider_library(providers::EPIC).await
+    }
+
+    /// List the games owned on a specific store. When the store can't be reached, or in
+    /// offline mode, the last listing is returned instead and `is_offline` turns true.
+    pub async fn list_provider_library(&self, provider: &str) -> Result<Vec<Game>> {
+        let (games, cached) = self.fetch_library(provider).await?;
+        self.shared
+            .served_from_cache
+            .store(cached, Ordering::Relaxed);
+        Ok(games)
+    }
+
+    /// A store's library, and whether it's the cached listing
+    async fn fetch_library(&self, provider: &str) -> Result<(Vec<Game>, bool)> {
+        let listing = async {
+            let token = self.provider_token(provider).await?;
+            self.provider(provider)?.get_games(&token).await
+        }
+        .await;
+
+        let (mut games, cached) = match listing {
+            Ok(games) => {
+                if let Err(e) = offline::save_listing(provider, &games) {
+                    log::warn!("Failed to cache the {} library: {}", provider, e);
+                }
+                (games, false)
+            }
+            Err(e) if offline::is_network_error(&e) => {
+                log::debug!("Listing the cached {} library: {}", provider, e);
+                (self.offline_library(provider)?, true)
+            }
+            Err(e) => return Err(e),
+        };
+        games.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+
+        Ok((games, cached))
+    }
+
+    /// Stores with a saved login, Epic first. Logins whose access token has expired count,
+    /// since they are refreshed on use.
+    pub fn logged_in_providers(&self) -> Vec<&'static str> {
+        providers::PROVIDER_IDS
+            .into_iter()
+            .filter(|id| match *id {
+                providers::EPIC => self.auth().get_refresh_token().is_some(),
+                id => matches!(AuthToken::load_for(id), Ok(Some(_))),
+            })
+            .collect()
+    }
+
+    /// Refresh the token of every logged in store that is close to expiring, so that
+    /// long-running processes keep working with `&self` methods like `update_all`
+    pub async fn refresh_tokens(&self) -> Vec<(&'static str, Result<()>)> {
+        let mut results = Vec::new();
+
+        for provider in self.logged_in_providers() {
+            let result = self.provider_token(provider).await.map(|_| ());
+            results.push((provider, result));
+        }
+
+        results
+    }
+
+    /// List the games owned on every store the user is logged in to.
+    /// A store that fails to answer is skipped with a warning rather than failing the listing.
+    pub async fn list_merged_library(&self) -> Result<Vec<Game>> {
+        let stores = self.logged_in_providers();
+        if stores.is_empty() {
+            return Err(Error::NotAuthenticated);
+        }
+
+        let mut games = Vec::new();
+        let mut any_cached = false;
+        for provider in stores {
+            match self.fetch_library(provider).await {
+                Ok((owned, cached)) => {
+                    games.extend(owned);
+                    any_cached |= cached;
+                }
+                Err(e) => log::warn!("Skipping {} library: {}", provider, e),
+            }
+        }
+        games.sort_by(|a, b| (&a.app_name, &a.provider).cmp(&(&b.app_name, &b.provider)));
+        self.shared
+            .served_from_cache
+            .store(any_cached, Ordering::Relaxed);
+
+        Ok(games)
+    }
+
+    pub fn list_installed(&self) -> Result<Vec<InstalledGame>> {
+        InstalledGame::list_installed(&self.config())
+    }
+
+    /// Statistics of the library, play time and downloads. The owned games are left out when
+    /// no store can be asked, e.g. offline.
+    pub async fn stats(&self) -> Result<LibraryStats> {
+        let installed = self.list_installed()?;
+        let owned = match self.list_merged_library().await {
+            Ok(games) => Some(games.len()),
+            Err(e) => {
+                log::debug!("Counting owned games skipped: {}", e);
+                None
+            }
+        };
+        let history = crate::history::load(None)?;
+        let sessions = crate::stats::sessions()?;
+
+        let stats = LibraryStats::compute(&installed, owned, &history, &sessions);
+        Ok(stats)
+    }
+
+    /// Install records are keyed by app name, so refuse to overwrite one from another store
+    fn check_not_installed_elsewhere(&self, provider: &str, app_name: &str) -> Result<()> {
+        match InstalledGame::load(&self.config(), app_name) {
+            Ok(game) if game.provider != provider => Err(Error::Other(format!(
+                "{} is already installed from {}; uninstall {} first",
+                app_name,
+                game.provider,
+                game.id()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn install_game(&self, app_name: &str) -> Result<()> {
+        self.install_game_version(app_name, None).await
+    }
+
+    /// Install the latest build of a game from a specific store
+    pub async fn install_provider_game(&self, provider: &str, app_name: &str) -> Result<()> {
+        if provider == providers::EPIC {
+            return self.install_game(app_name).await;
+        }
+
+        self.check_not_installed_elsewhere(provider, app_name)?;
+        let token = self.provider_token(provider).await?;
+
+        log::info!("Starting installation for game: {}", app_name);
+        log::info!("Downloading game manifest...");
+        let manifest = self
+            .provider(provider)?
+            .download_manifest(&token, app_name)
+            .await?;
+
+        self.install_manifest(provider, &token, manifest, false)
+            .await
+    }
+
+    /// List the builds Epic offers for a game, newest first
+    pub async fn list_builds(&self, app_name: &str) -> Result<Vec<BuildVersion>> {
+        let token = self.ensure_valid_token().await?;
+        self.shared.client.get_build_index(&token, app_name).await
+    }
+
+    /// Install a specific build of a game, or the latest one when `build_version` is None.
+    /// Installing an explicit build pins the game so it isn't updated behind the user's back.
+    pub async fn install_game_version(
+        &self,
+        app_name: &str,
+        build_version: Option<&str>,
+    ) -> Result<()> {
+        // TODO: Check available disk space before installation
+        // TODO: Implement resume capability for interrupted installations
+        // TODO: Add progress tracking with download speed and ETA
+        // TODO: Verify file integrity after reconstruction
+        // TODO: Handle installation cancellation gracefully
+        // TODO: Support selective installation (choose components/languages)
+
+        self.check_not_installed_elsewhere(providers::EPIC, app_name)?;
+        let token = self.ensure_valid_token().await?;
+
+        log::info!("Starting installation for game: {}", app_name);
+
+        // Surface region locks up front instead of as a bare 403 mid-download
+        match self.shared.client.check_region(&token, app_name).await {
+            Err(e @ Error::RegionBlocked(_)) => return Err(e),
+            Err(e) => log::debug!("Region check skipped: {}", e),
+            Ok(()) => {}
+        }
+
+        // Download and parse game manifest
+        log::info!("Downloading game manifest...");
+        let manifest = self
+            .shared
+            .client
+            .download_manifest_for_build(&token, app_name, build_version)
+            .await?;
+
+        self.install_manifest(providers::EPIC, &token, manifest, build_version.is_some())
+            .await
+    }
+
+    /// Install a manifest's build, noting it in the history as an install, an update, or a
+    /// repair when the same build was installed already
+    async fn install_manifest(
+        &self,
+        provider: &str,
+        token: &AuthToken,
+        manifest: GameManifest,
+        pinned: bool,
+    ) -> Result<()> {
+        // One snapshot for the whole install, so a settings change meanwhile can't send
+        // half of it somewhere else
+        let config = self.config();
+        let started_at = Utc::now();
+        let app_name = manifest.app_name.clone();
+        let version = manifest.app_version.clone();
+        let from_version = InstalledGame::load(&config, &app_name)
+            .ok()
+            .map(|game| game.app_version);
+
+        let download = metrics::ActiveDownload::start(&app_name);
+        let result = self
+            .install_manifest_files(&config, provider, token, manifest, pinned)
+            .await;
+        drop(download);
+        if let Ok(bytes) = result {
+            metrics::record_downloaded(bytes);
+        }
+
+        let action = match &from_version {
+            None => HistoryAction::Install,
+            Some(from) if *from == version => HistoryAction::Repair,
+            Some(_) => HistoryAction::Update,
+        };
+        record_history(HistoryEntry {
+            app_name,
+            provider: provider.to_string(),
+            action,
+            from_version,
+            version: Some(version),
+            bytes: *result.as_ref().unwrap_or(&0),
+            started_at,
+            finished_at: Utc::now(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        result.map(|_| ())
+    }
+
+    /// Download a manifest's files into the install directory and record the installation.
+    /// Returns the number of bytes downloaded.
+    async fn install_manifest_files(
+        &self,
+        config: &Config,
+        provider: &str,
+        token: &AuthToken,
+        manifest: GameManifest,
+        pinned: bool,
+    ) -> Result<u64> {
+        let app_name = manifest.app_name.as_str();
+        let store = self.provider(provider)?;
+
+        log::info!("Manifest downloaded: version {}", manifest.app_version);
+        log::info!("Manifest version: {}", manifest.app_version);
+        log::info!("Build size: {} bytes", manifest.build_size);
+        log::info!("Files to download: {}", manifest.file_list.len());
+        self.report(InstallProgress::Started {
+            app_name: app_name.to_string(),
+            version: manifest.app_version.clone(),
+            files: manifest.file_list.len(),
+            bytes: manifest.build_size,
+        });
+
+        // A Flatpak sandbox would create the directory inside itself, where it doesn't last
+        if let Some(command) = crate::sandbox::missing_permission(&config.install_dir) {
+            return Err(Error::Other(format!(
+                "{:?} is outside the Flatpak sandbox; allow access with: {}",
+                config.install_dir, command
+            )));
+        }
+
+        // Installing to a drive that isn't plugged in would fill the disk it's mounted on
+        if volume::drive_missing(&config.install_dir) {
+            return Err(Error::Other(format!(
+                "{:?} is on a drive that isn't mounted",
+                config.install_dir
+            )));
+        }
+
+        // Create install directory, making sure files already there may be replaced
+        let install_path = config.install_dir.join(app_name);
+        let partial_marker = install_path.join(crate::storage::PARTIAL_MARKER);
+        let fresh_install = InstalledGame::load(config, app_name).is_err();
+        if fresh_install && holds_files(&install_path) && !partial_marker.exists() {
+            let question = Question::new(
+                QuestionKind::OverwriteInstall,
+                crate::tr!(
+                    "prompt-overwrite-install",
+                    path = install_path.display().to_string(),
+                    title = app_name
+                ),
+            );
+            if self.prompter.ask(&question) != "overwrite" {
+                return Err(Error::Other(format!(
+                    "Installation cancelled: {:?} already contains files. Move them away, \
+                     answer the question on a terminal, or set prompt_answers.overwrite_install \
+                     = \"overwrite\" to install over them",
+                    install_path
+                )));
+            }
+        }
+        fs::create_dir_all(&install_path)?;
+        crate::logs::transcript::touched("created", &install_path);
+        // Marks the directory as a partial download until the install record exists
+        if fresh_install {
+            fs::write(&partial_marker, "")?;
+        }
+
+        log::info!("Created install directory: {:?}", install_path);
+
+        // Lets the game be told apart from a broken install while its drive is away
+        let volume = if volume::volume_kind(&config.install_dir).is_detachable() {
+            Some(volume::mark(&config.install_dir)?)
+        } else {
+            None
+        };
+
+        // Download game files
+        let mut bytes = 0;
+        if !manifest.file_list.is_empty() {
+            // TODO: Implement parallel file downloads with thread pool
+            // TODO: Verify file checksums against manifest
+            // TODO: Set proper file permissions (executable, read-only, etc.)
+            // TODO: Track and save download progress for resume capability
+
+            bytes = self
+                .download_into(config, store, &manifest, &install_path, token)
+                .await?;
+            log::info!("✓ Game files downloaded");
+        }
+
+        let executable = if manifest.launch_exe.is_empty() {
+            store.launch_executable(&install_path).unwrap_or_default()
+        } else {
+            manifest.launch_exe.clone()
+        };
+
+        // Redistributables run on the system, so only Windows installs need them. The game may
+        // well start without them, so a failure is reported without undoing the install.
+        let prerequisite = manifest.prerequisite.clone();
+        if let Some(prerequisite) = prerequisite.filter(|_| cfg!(target_os = "windows")) {
+            // Waits for the installer, and for the user at a UAC prompt
+            let path = install_path.clone();
+            let installed =
+                tokio::task::spawn_blocking(move || prerequisites::install(&prerequisite, &path))
+                    .await
+                    .map_err(|e| Error::Other(format!("Running the installer failed: {}", e)))
+                    .and_then(|result| result);
+            if let Err(e) = installed {
+                log::warn!("{}", e);
+            }
+        }
+
+        let save_path_template =
+            crate::saves::manifest_template(&manifest.custom_fields, &executable);
+
+        // Create installed game entry with manifest data
+        let installed_game = InstalledGame {
+            app_name: app_name.to_string(),
+            app_title: app_name.to_string(),
+            app_version: manifest.app_version.clone(),
+            install_path: install_path.clone(),
+            executable,
+            pinned,
+            provider: provider.to_string(),
+            install_size: manifest.build_size,
+            download_size: manifest.download_size(),
+            installed_at: Some(Utc::now()),
+            last_played: None,
+            volume,
+            save_path_template,
+        };
+
+        installed_game.save(config)?;
+        if partial_marker.exists() {
+            fs::remove_file(&partial_marker)?;
+        }
+
+        if let Err(e) = crate::registry::register_install(&installed_game) {
+            log::warn!("Failed to register {} with the system: {}", app_name, e);
+        }
+
+        if installed_game.pinned {
+            log::info!(
+                "Pinned {} to build {} (run 'rauncher unpin {}' to allow updates)",
+                app_name,
+                installed_game.app_version,
+                app_name
+            );
+        }
+
+        log::info!("Game installation completed for: {}", app_name);
+        log::info!("✓ Installation complete!");
+        self.report(InstallProgress::Finished {
+            app_name: app_name.to_string(),
+        });
+        self.notify(Notification::InstallFinished {
+            title: installed_game.app_title.clone(),
+        });
+
+        Ok(bytes)
+    }
+
+    /// Download a manifest's files and put them in `install_path`, through the staging
+    /// directory if there's one. Returns the number of bytes downloaded.
+    async fn download_into(
+        &self,
+        config: &Config,
+        store: &dyn StoreProvider,
+        manifest: &GameManifest,
+        install_path: &Path,
+        token: &AuthToken,
+    ) -> Result<u64> {
+        // Files are assembled in the staging directory, if there's one, and moved over
+        // once they're all there
+        let staging = config
+            .staging_dir
+            .as_ref()
+            .map(|dir| dir.join(&manifest.app_name))
+            .filter(|staging| staging != install_path);
+        let download_dir = staging.as_deref().unwrap_or(install_path);
+        if let Some(staging) = &staging {
+            fs::create_dir_all(staging)?;
+            fs::write(staging.join(crate::storage::PARTIAL_MARKER), "")?;
+            log::info!("Staging files in {:?}", staging);
+        }
+
+        log::info!("Downloading game files...");
+        let started = std::time::Instant::now();
+        let bytes = self
+            .download_files(config, store, manifest, download_dir, install_path, token)
+            .await?;
+        crate::profiling::throughput("install download", bytes, started.elapsed());
+        store.finalize_install(manifest, download_dir)?;
+
+        if let Some(staging) = &staging {
+            log::info!("Moving files to {:?}...", install_path);
+            let started = std::time::Instant::now();
+            crate::storage::move_tree(staging, install_path)?;
+            crate::profiling::throughput("install move", bytes, started.elapsed());
+        }
+        Ok(bytes)
+    }
+
+    /// Download a chunk with the current token. A token turned down is refreshed and the chunk
+    /// tried again, as downloads can take longer than tokens last.
+    async fn download_chunk(
+        &self,
+        provider: &dyn StoreProvider,
+        token: &Mutex<AuthToken>,
+        app_name: &str,
+        chunk_guid: &str,
+    ) -> Result<Vec<u8>> {
+        let current = token.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        match provider
+            .download_chunk(&current, app_name, chunk_guid)
+            .await
+        {
+            Err(Error::TokenRejected(reason)) => {
+                log::info!("Refreshing the {} token: {}", provider.id(), reason);
+                let renewed = self.renew_token(provider.id(), &current).await?;
+                *token.lock().unwrap_or_else(|e| e.into_inner()) = renewed.clone();
+                provider
+                    .download_chunk(&renewed, app_name, chunk_guid)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    /// Download every file in a manifest into `dest`, rebuilding each one from its chunks.
+    /// `install_path` is where the game ends up, when `dest` is a staging directory. Returns
+    /// the number of bytes written.
+    async fn download_files(
+        &self,
+        config: &Config,
+        provider: &dyn StoreProvider,
+        manifest: &GameManifest,
+        dest: &Path,
+        install_path: &Path,
+        token: &AuthToken,
+    ) -> Result<u64> {
+        let cache = ChunkCache::new(config)?.map(Arc::new);
+        // Replaced when the store turns the token down partway through
+        let token = Mutex::new(token.clone());
+        let token = &token;
+        let workers = WorkerPool::new();
+        let workers = &workers;
+        let mut written = 0;
+        // Protected files the user already has stay as they are
+        let settings = config.game_settings(&manifest.app_name);
+        let kept = |filename: &str| {
+            settings.is_protected(filename) && install_path.join(filename).is_file()
+        };
+        let total = manifest
+            .file_list
+            .iter()
+            .filter(|file| !kept(&file.filename))
+            .flat_map(|file| &file.file_chunk_parts)
+            .map(|part| part.size)
+            .sum();
+        let mut meter = SpeedMeter::new(total);
+        let mut last_transfer = Instant::now();
+
+        for (idx, file) in manifest.file_list.iter().enumerate() {
+            log::info!(
+                "  [{}/{}] {}",
+                idx + 1,
+                manifest.file_list.len(),
+                file.filename
+            );
+
+            if kept(&file.filename) {
+                log::info!("    Keeping protected file {}", file.filename);
+                continue;
+            }
+
+            let file_path = dest.join(&file.filename);
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let output = fs::File::create(&file_path)?;
+            crate::logs::transcript::touched("wrote", &file_path);
+            let size = file.file_chunk_parts.iter().map(|part| part.size).sum();
+            reserve(&output, size, config.preallocate).map_err(|e| {
+                Error::Other(format!(
+                    "Could not reserve {} for {}: {}",
+                    format_size(size),
+                    file.filename,
+                    e
+                ))
+            })?;
+
+            // Each part goes to its own range of the file, so several download at once and
+            // the workers decode and write them as they arrive
+            let output = Arc::new(PositionedFile::new(output));
+            let ranges = file.file_chunk_parts.iter().scan(0, |offset, part| {
+                let start = *offset;
+                *offset += part.size;
+                Some((start, part))
+            });
+            written += stream::iter(ranges)
+                .map(|(start, part)| {
+                    let output = Arc::clone(&output);
+                    let cache = cache.clone();
+                    let part = part.clone();
+                    let filename = file.filename.clone();
+                    let sha = manifest.chunk_sha_list.get(&part.guid).cloned();
+                    async move {
+                        self.download_checkpoint().await?;
+                        let cached = cache
+                            .as_ref()
+                            .and_then(|cache| cache.get(provider.id(), &part.guid));
+                        let (data, downloaded) = match cached {
+                            Some(data) => (data, false),
+                            None => {
+                                let data = self
+                                    .download_chunk(provider, token, &manifest.app_name, &part.guid)
+                                    .await?;
+                                self.shared
+                                    .network
+                                    .after_chunk(&config.metered, data.len())
+                                    .await;
+                                (data, true)
+                            }
+                        };
+
+                        let (provider_id, encoding) = (provider.id(), provider.chunk_encoding());
+                        workers
+                            .run(move || {
+                                let chunk = if downloaded {
+                                    let chunk = encoding.decode(data)?;
+                                    check_chunk(&chunk, sha.as_deref(), &part.guid)?;
+                                    cache_chunk(cache.as_deref(), provider_id, &part.guid, &chunk);
+                                    chunk
+                                } else {
+                                    data
+                                };
+                                write_part(&output, &chunk, &part, start, &filename)
+                            })
+                            .await
+                    }
+                })
+                .buffer_unordered(CHUNK_DOWNLOADS)
+                .try_fold(0, |total, bytes| {
+                    meter.add(bytes);
+                    if last_transfer.elapsed() >= TRANSFER_INTERVAL {
+                        last_transfer = Instant::now();
+                        self.report(InstallProgress::Transfer {
+                            app_name: manifest.app_name.clone(),
+                            stats: meter.stats(),
+                        });
+                    }
+                    async move { Ok(total + bytes) }
+                })
+                .await?;
+
+            self.report(InstallProgress::FileDone {
+                app_name: manifest.app_name.clone(),
+                index: idx + 1,
+                filename: file.filename.clone(),
+                bytes_done: written,
+            });
+        }
+
+        Ok(written)
+    }
+
+    /// List the Unreal Engine marketplace assets the account owns
+    pub async fn list_ue_assets(&self) -> Result<Vec<UeAsset>> {
+        let token = self.ensure_valid_token().await?;
+        let mut assets = self.shared.client.get_ue_assets(&token).await?;
+        assets.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+
+        Ok(assets)
+    }
+
+    /// Epic's view of the active session: who it belongs to, when it expires and its scopes
+    pub async fn session_info(&self) -> Result<SessionInfo> {
+        let token = self.ensure_valid_token().await?;
+        self.shared.client.verify_token(&token).await
+    }
+
+    /// The raw entitlements of the active Epic account
+    pub async fn entitlements(&self) -> Result<Vec<Entitlement>> {
+        let token = self.ensure_valid_token().await?;
+        self.shared.client.get_entitlements(&token).await
+    }
+
+    /// Download an owned Unreal Engine asset into `dest_dir`/<app_name>.
+    /// Requires `ue_assets_enabled` in the configuration.
+    pub async fn download_ue_asset(&self, app_name: &str, dest_dir: &Path) -> Result<PathBuf> {
+        if !self.config().ue_assets_enabled {
+            return Err(Error::Config(
+                "Unreal Engine asset downloads are disabled. \
+                 Set 'ue_assets_enabled = true' in the configuration to opt in"
+                    .to_string(),
+            ));
+        }
+
+        let token = self.ensure_valid_token().await?;
+
+        let owned = self.shared.client.get_ue_assets(&token).await?;
+        if !owned.iter().any(|a| a.app_name == app_name) {
+            return Err(Error::GameNotFound(app_name.to_string()));
+        }
+
+        log::info!("Downloading manifest for asset: {}", app_name);
+        let manifest = self
+            .shared
+            .client
+            .download_manifest(&token, app_name)
+            .await?;
+
+        let asset_path = dest_dir.join(app_name);
+        fs::create_dir_all(&asset_path)?;
+        crate::logs::transcript::touched("created", &asset_path);
+
+        log::info!(
+            "Downloading {} file(s) to {:?}",
+            manifest.file_list.len(),
+            asset_path
+        );
+        self.download_files(
+            &self.config(),
+            &self.shared.client,
+            &manifest,
+            &asset_path,
+            &asset_path,
+            &token,
+        )
+        .await?;
+
+        Ok(asset_path)
+    }
+
+    /// Start a game. The returned handle can be waited on to learn whether the game crashed;
+    /// dropping it leaves the game running.
+    pub fn launch_game(&self, address: &str) -> Result<RunningGame> {
+        self.launch_game_with_options(address, &LaunchOptions::default())
+    }
+
+    /// Start a game with overrides for this session. Executable and working directory
+    /// overrides, from `options` or the game's settings, must stay inside the install directory.
+    pub fn launch_game_with_options(
+        &self,
+        address: &str,
+        options: &LaunchOptions,
+    ) -> Result<RunningGame> {
+        let mut game = InstalledGame::resolve(&self.config(), address)?;
+        if game.is_unavailable() {
+            return Err(Error::Other(format!(
+                "{} is unavailable: the drive holding {:?} isn't mounted",
+                game.app_title, game.install_path
+            )));
+        }
+        let app_name = game.app_name.as_str();
+        let settings = self.launch_settings(app_name)?;
+
+        let executable_path = match options.executable.as_ref().or(settings.executable.as_ref()) {
+            Some(executable) => resolve_in_install_dir(&game.install_path, executable)?,
+            None => game.install_path.join(&game.executable),
+        };
+
+        if !executable_path.is_file() {
+            return Err(Error::Other(format!(
+                "Executable not found: {:?}",
+                executable_path
+            )));
+        }
+
+        if !options.force {
+            if let Some(pid) = running::find_instance(app_name, &executable_path)? {
+                log::debug!("{} is already running as process {}", app_name, pid);
+                return Err(Error::AlreadyRunning(game.app_title.clone()));
+            }
+        }
+
+        let working_dir = match options
+            .working_dir
+            .as_ref()
+            .or(settings.working_dir.as_ref())
+        {
+            Some(dir) => resolve_in_install_dir(&game.install_path, dir)?,
+            None => game.install_path.clone(),
+        };
+        if !working_dir.is_dir() {
+            return Err(Error::Other(format!(
+                "Working directory not found: {:?}",
+                working_dir
+            )));
+        }
+
+        let prefix = Config::prefixes_dir()?.join(app_name);
+        let mut command = LaunchCommand::build(&executable_path, &working_dir, &settings, &prefix)?;
+        command.args.extend(options.args.iter().map(OsString::from));
+
+        let mut writable = vec![game.install_path.clone()];
+        if command.uses_prefix() {
+            fs::create_dir_all(&prefix)?;
+
+            let shader_cache = Config::shader_cache_dir()?.join(app_name);
+            fs::create_dir_all(&shader_cache)?;
+            shader_cache::use_cache_dir(&mut command, &shader_cache);
+            writable.extend([prefix, shader_cache]);
+        }
+
+        if let Some(tool) = settings.sandbox.filter(|_| cfg!(target_os = "linux")) {
+            // Saves kept outside the install and prefix have to stay writable too
+            if let Some(save_dir) = self.known_save_dir(&game) {
+                if !writable.iter().any(|dir| save_dir.starts_with(dir)) {
+                    fs::create_dir_all(&save_dir)?;
+                    writable.push(save_dir);
+                }
+            }
+            isolation::confine(&mut command, tool, &settings, &writable)?;
+            log::info!("Running {} in a {} sandbox", app_name, tool.as_str());
+        }
+
+        log::info!("Launching game: {} ({})", game.app_title, game.app_name);
+        log::debug!("Launch command: {:?} {:?}", command.program, command.args);
+
+        let mut process = command.to_command();
+        let (log_path, output_log) = match crate::logs::create_game_log(app_name) {
+            Ok((path, log)) => {
+                log::info!("Game output is logged to {:?}", path);
+                if options.stream_output {
+                    process.stdout(Stdio::piped()).stderr(Stdio::piped());
+                    (Some(path), Some(log))
+                } else {
+                    process.stdout(log.try_clone()?).stderr(log);
+                    (Some(path), None)
+                }
+            }
+            Err(e) => {
+                log::warn!("Not capturing the game's output: {}", e);
+                if options.stream_output {
+                    process.stdout(Stdio::piped()).stderr(Stdio::piped());
+                }
+                (None, None)
+            }
+        };
+
+        // Its own process group, so stopping the game reaches everything it started
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut process, 0);
+
+        let child = process
+            .spawn()
+            .map_err(|e| Error::Other(format!("Failed to launch game: {}", e)))?;
+
+        let record = running::RunningRecord {
+            app_name: app_name.to_string(),
+            pid: child.id(),
+            started_at: Utc::now(),
+            waited: false,
+            stopping: false,
+        };
+        if let Err(e) = running::track(&record) {
+            log::warn!("Failed to record that {} is running: {}", app_name, e);
+        }
+
+        game.last_played = Some(Utc::now());
+        if let Err(e) = game.save(&self.config()) {
+            log::warn!("Failed to record when {} was played: {}", game.app_name, e);
+        }
+
+        Ok(RunningGame::new(game, command, log_path, child, output_log))
+    }
+
+    /// The game's settings with its `runner` resolved to the Wine or Proton it names
+    fn launch_settings(&self, app_name: &str) -> Result<GameSettings> {
+        let mut settings = self.config().game_settings(app_name);
+        if let Some(runner) = &settings.runner {
+            settings.wine = Some(crate::runners::find(runner)?.executable());
+        }
+        Ok(settings)
+    }
+
+    pub fn uninstall_game(&self, address: &str) -> Result<()> {
+        self.uninstall_game_with_options(address, &UninstallOptions::default())
+    }
+
+    /// Uninstall a game. Saves are always archived first; with `purge` set the game's
+    /// Wine prefix and shader cache are removed as well.
+    pub fn uninstall_game_with_options(
+        &self,
+        address: &str,
+        options: &UninstallOptions,
+    ) -> Result<()> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let started_at = Utc::now();
+        let result = self.remove_game(&game, options);
+
+        record_history(HistoryEntry {
+            app_name: game.app_name.clone(),
+            provider: game.provider.clone(),
+            action: HistoryAction::Uninstall,
+            from_version: Some(game.app_version.clone()),
+            version: None,
+            bytes: 0,
+            started_at,
+            finished_at: Utc::now(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        });
+        result
+    }
+
+    fn remove_game(&self, game: &InstalledGame, options: &UninstallOptions) -> Result<()> {
+        let app_name = game.app_name.as_str();
+
+        // Back up saves before anything is deleted, and abort if that fails
+        let save_dir = self.known_save_dir(game);
+        match crate::saves::backup_saves(&self.config(), game, save_dir.as_deref(), options.purge)?
+        {
+            Some(archive) => log::info!("Saves preserved in {:?}", archive),
+            None => log::debug!("No saves found for {}", app_name),
+        }
+
+        // Remove game files
+        if game.install_path.exists() {
+            crate::storage::remove_dir(&game.install_path, options.removal)?;
+        }
+
+        if options.purge {
+            let leftovers = [
+                Config::prefixes_dir()?.join(app_name),
+                Config::shader_cache_dir()?.join(app_name),
+            ];
+
+            for dir in leftovers.iter().filter(|d| d.exists()) {
+                crate::storage::remove_dir(dir, options.removal)?;
+                log::info!("Removed {:?}", dir);
+            }
+        }
+
+        // Remove metadata
+        game.delete(&self.config())?;
+
+        if let Err(e) = crate::registry::unregister_install(app_name) {
+            log::warn!("Failed to unregister {} from the system: {}", app_name, e);
+        }
+
+        log::info!("Uninstalled game: {} ({})", game.app_title, game.app_name);
+
+        Ok(())
+    }
+
+    /// Installed games matching `filter`, with the bytes each takes on disk, largest first
+    pub fn uninstall_candidates(
+        &self,
+        filter: &UninstallFilter,
+    ) -> Result<Vec<(InstalledGame, u64)>> {
+        let now = Utc::now();
+        let mut candidates: Vec<_> = self
+            .list_installed()?
+            .into_iter()
+            .map(|game| {
+                let size = game.disk_usage();
+                (game, size)
+            })
+            .filter(|(game, size)| filter.matches(game, *size, now))
+            .collect();
+
+        candidates.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        Ok(candidates)
+    }
+
+    /// Snapshot a game's saves into a new local backup archive
+    pub fn backup_saves(&self, address: &str) -> Result<Option<PathBuf>> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let save_dir = self.known_save_dir(&game);
+        crate::saves::backup_saves(&self.config(), &game, save_dir.as_deref(), true)
+    }
+
+    /// List a game's local save backups, newest first
+    pub fn list_save_backups(&self, address: &str) -> Result<Vec<crate::saves::Snapshot>> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        crate::saves::list_snapshots(&game.app_name)
+    }
+
+    /// Restore the `snapshot`-th newest save backup (1-based, defaults to the newest)
+    pub fn restore_saves(&self, address: &str, snapshot: Option<usize>) -> Result<usize> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let app_name = game.app_name.as_str();
+        let snapshots = crate::saves::list_snapshots(app_name)?;
+
+        let index = snapshot.unwrap_or(1);
+        let chosen = index
+            .checked_sub(1)
+            .and_then(|i| snapshots.get(i))
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "No backup #{} for {} ({} available)",
+                    index,
+                    app_name,
+                    snapshots.len()
+                ))
+            })?;
+
+        let save_dir = self.known_save_dir(&game);
+        crate::saves::restore_snapshot(&game, save_dir.as_deref(), chosen)
+    }
+
+    /// Check for game updates, returning the newer build if there is one. Its date and release
+    /// notes are filled in where the store has them.
+    pub async fn check_for_updates(&self, address: &str) -> Result<Option<BuildInfo>> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        self.check_game_for_updates(&game, &ReleaseLookup::new())
+            .await
+    }
+
+    /// Check every installed game for updates, looking up release details in the library
+    /// once for all of them
+    pub async fn check_all_for_updates(
+        &self,
+    ) -> Result<Vec<(InstalledGame, Result<Option<BuildInfo>>)>> {
+        let releases = ReleaseLookup::new();
+        let mut checks = Vec::new();
+        for game in self.list_installed()? {
+            let result = self.check_game_for_updates(&game, &releases).await;
+            checks.push((game, result));
+        }
+        Ok(checks)
+    }
+
+    async fn check_game_for_updates(
+        &self,
+        game: &InstalledGame,
+        releases: &ReleaseLookup,
+    ) -> Result<Option<BuildInfo>> {
+        let app_name = game.app_name.as_str();
+        let token = self.stored_token(&game.provider)?;
+
+        log::info!(
+            "Checking for updates for {} (current: {})",
+            app_name,
+            game.app_version
+        );
+
+        if game.provider == providers::EPIC {
+            return self
+                .shared
+                .client
+                .check_for_updates(&token, app_name, &game.app_version, releases)
+                .await;
+        }
+
+        let manifest = self
+            .provider(&game.provider)?
+            .download_manifest(&token, app_name)
+            .await?;
+
+        Ok((manifest.app_version != game.app_version)
+            .then(|| BuildInfo::version(manifest.app_version)))
+    }
+
+    /// Import games installed by the official Epic Games Launcher. `manifests_dir` overrides
+    /// the location found in the registry (e.g. an EGL running inside a Wine prefix).
+    /// Returns the games that were (or, with `dry_run`, would be) imported.
+    pub fn import_egl_games(
+        &self,
+        manifests_dir: Option<&Path>,
+        dry_run: bool,
+    ) -> Result<Vec<InstalledGame>> {
+        let dir = crate::egl::find_manifests_dir(manifests_dir)?;
+        let installed = self.list_installed()?;
+        let mut imported = Vec::new();
+
+        for manifest in crate::egl::read_manifests(&dir)? {
+            if installed.iter().any(|g| g.app_name == manifest.app_name) {
+                log::debug!("{} is already managed by rauncher", manifest.app_name);
+                continue;
+            }
+
+            let game = manifest.to_installed_game();
+            if !dry_run {
+                game.save(&self.config())?;
+            }
+            imported.push(game);
+        }
+
+        Ok(imported)
+    }
+
+    /// App names of the Epic games in the library with a build for `platform`, e.g. "Windows"
+    /// or "Mac"
+    pub async fn epic_games_for_platform(&self, platform: &str) -> Result<HashSet<String>> {
+        let token = self.stored_token(providers::EPIC)?;
+        self.shared.client.get_platform_apps(&token, platform).await
+    }
+
+    /// Installed games with a newer build available, and that build.
+    /// Games whose store can't be reached are left out.
+    pub async fn pending_updates(&self) -> Result<Vec<(InstalledGame, BuildInfo)>> {
+        let mut pending = Vec::new();
+
+        for (game, result) in self.check_all_for_updates().await? {
+            match result {
+                Ok(Some(build)) => pending.push((game, build)),
+                Ok(None) => {}
+                Err(e) => log::debug!("Could not check {} for updates: {}", game.id(), e),
+            }
+        }
+
+        Ok(pending)
+    }
+
+    /// Regenerate the RSS and iCalendar feeds of Epic's free games (as offered in `country`)
+    /// and the `pending` updates, returning the paths of both files
+    pub async fn write_feeds(
+        &self,
+        country: &str,
+        pending: &[(InstalledGame, BuildInfo)],
+    ) -> Result<(PathBuf, PathBuf)> {
+        let mut entries = Vec::new();
+
+        match self.shared.client.get_free_games(country).await {
+            Ok(games) => entries.extend(games.iter().map(FeedEntry::free_game)),
+            Err(e) => log::warn!("Leaving free games out of the feeds: {}", e),
+        }
+
+        let now = chrono::Utc::now();
+        for (game, build) in pending {
+            entries.push(FeedEntry::update(game, &build.version, now));
+        }
+
+        crate::feeds::write(&entries)
+    }
+
+    /// Pin or unpin an installed game's current build
+    pub fn set_pinned(&self, address: &str, pinned: bool) -> Result<()> {
+        let mut game = InstalledGame::resolve(&self.config(), address)?;
+        game.pinned = pinned;
+        game.save(&self.config())
+    }
+
+    /// Update every installed game that isn't pinned, returning the outcome per game
+    pub async fn update_all(&self) -> Result<Vec<(String, Result<()>)>> {
+        let mut results = Vec::new();
+
+        let games = self.list_installed()?;
+        let mut queued = games.iter().filter(|game| !game.pinned).count();
+        let releases = ReleaseLookup::new();
+        for game in games {
+            if game.pinned {
+                log::info!(
+                    "Skipping {} (pinned to {})",
+                    game.app_name,
+                    game.app_version
+                );
+                continue;
+            }
+
+            queued -= 1;
+            metrics::set_queued_updates(queued);
+            let result = self.update_installed(game.clone(), &releases).await;
+            results.push((game.app_name, result));
+        }
+
+        Ok(results)
+    }
+
+    /// Update a game to the latest version, downloading the files that are missing or differ
+    /// from the new build
+    pub async fn update_game(&self, address: &str) -> Result<()> {
+        let installed = InstalledGame::resolve(&self.config(), address)?;
+        self.update_installed(installed, &ReleaseLookup::new())
+            .await
+    }
+
+    async fn update_installed(
+        &self,
+        installed: InstalledGame,
+        releases: &ReleaseLookup,
+    ) -> Result<()> {
+        // TODO: Delete files the new build no longer has
+        // TODO: Support update rollback in case of failure
+        // TODO: Preserve user settings and save files during update
+
+        let app_name = installed.app_name.as_str();
+        let token = self.stored_token(&installed.provider)?;
+
+        if installed.pinned {
+            return Err(Error::Other(format!(
+                "{} is pinned to its current build (run 'rauncher unpin {}' first)",
+                app_name, app_name
+            )));
+        }
+
+        log::info!("Updating game: {}", app_name);
+
+        // Check if update is available
+        match self.check_game_for_updates(&installed, releases).await? {
+            Some(build) => {
+                log::info!("Update available: {}", build.version);
+                if let Some(notes) = &build.release_notes {
+                    log::info!("What's new:\n{}", notes);
+                }
+                log::info!("Downloading update...");
+
+                let started_at = Utc::now();
+                let download = metrics::ActiveDownload::start(app_name);
+                let result: Result<(String, u64)> = async {
+                    let config = self.config();
+                    let store = self.provider(&installed.provider)?;
+                    let manifest = store.download_manifest(&token, app_name).await?;
+
+                    let install_path = installed.install_path.clone();
+                    let changed = plan::changed_in_background(&manifest, &install_path).await?;
+                    log::info!(
+                        "Updating {} of {} files...",
+                        changed.file_list.len(),
+                        manifest.file_list.len()
+                    );
+                    let bytes = self
+                        .download_into(&config, store, &changed, &install_path, &token)
+                        .await?;
+
+                    // Update installation record
+                    let mut game = InstalledGame::load(&config, app_name)?;
+                    game.app_version = manifest.app_version.clone();
+                    game.install_size = manifest.build_size;
+                    game.download_size = manifest.download_size();
+                    if !manifest.launch_exe.is_empty() {
+                        game.executable = manifest.launch_exe.clone();
+                    }
+                    game.save(&config)?;
+                    self.refresh_shader_cache(&game).await;
+
+                    log::info!("✓ Game updated to version {}", manifest.app_version);
+                    Ok((manifest.app_version, bytes))
+                }
+                .await;
+                drop(download);
+                if let Ok((_, bytes)) = &result {
+                    metrics::record_downloaded(*bytes);
+                }
+
+                record_history(HistoryEntry {
+                    app_name: app_name.to_string(),
+                    provider: installed.provider.clone(),
+                    action: HistoryAction::Update,
+                    from_version: Some(installed.app_version.clone()),
+                    version: Some(
+                        result
+                            .as_ref()
+                            .map_or(&build.version, |(version, _)| version)
+                            .clone(),
+                    ),
+                    bytes: result.as_ref().map_or(0, |(_, bytes)| *bytes),
+                    started_at,
+                    finished_at: Utc::now(),
+                    error: result.as_ref().err().map(|e| e.to_string()),
+                });
+                result.map(|_| ())
+            }
+            None => {
+                log::info!("Game is already up to date");
+                Ok(())
+            }
+        }
+    }
+
+    /// Where a game keeps its saves: the `save_path` setting, or the store's template resolved
+    /// for this system. `None` when neither is known.
+    pub fn save_dir(&self, address: &str) -> Result<Option<PathBuf>> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let settings = self.launch_settings(&game.app_name)?;
+
+        if let Some(path) = &settings.save_path {
+            return Ok(Some(game.install_path.join(path)));
+        }
+        self.store_save_dir(&game, &settings)
+    }
+
+    /// The store's save location for `game`, resolved for this system
+    fn store_save_dir(
+        &self,
+        game: &InstalledGame,
+        settings: &GameSettings,
+    ) -> Result<Option<PathBuf>> {
+        let Some(template) = &game.save_path_template else {
+            return Ok(None);
+        };
+
+        let roots = crate::saves::SaveRoots {
+            profile: save_profile(game, settings)?,
+            install_dir: game.install_path.clone(),
+            epic_id: self
+                .stored_token(&game.provider)
+                .ok()
+                .map(|token| token.account_id),
+            mac: !crate::saves::is_windows_build(&game.executable),
+        };
+
+        crate::saves::resolve_template(template, &roots).map(Some)
+    }
+
+    /// `save_dir`, with failures to work it out logged and treated as unknown
+    fn known_save_dir(&self, game: &InstalledGame) -> Option<PathBuf> {
+        self.save_dir(&game.app_name).unwrap_or_else(|e| {
+            log::warn!("{}", e);
+            None
+        })
+    }
+
+    /// Guess where a game keeps its saves, from folders named after it in the usual save
+    /// locations and from files that changed since it was last started
+    pub fn detect_save_dirs(&self, address: &str) -> Result<Vec<crate::saves::SaveSuggestion>> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let settings = self.launch_settings(&game.app_name)?;
+        let profile = save_profile(&game, &settings)?;
+
+        Ok(crate::saves::detect_save_dirs(
+            &profile,
+            &[&game.app_title, &game.app_name],
+            game.last_played.map(std::time::SystemTime::from),
+        ))
+    }
+
+    /// Record where a game keeps its saves, as its `save_path` setting
+    pub fn set_save_path(&self, address: &str, path: &Path) -> Result<()> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        self.update_config(|config| {
+            config.games.entry(game.app_name).or_default().save_path = Some(path.to_path_buf());
+        })
+    }
+
+    /// A game's cloud save snapshots, newest first
+    pub async fn list_cloud_saves(&self, address: &str) -> Result<Vec<CloudSave>> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let token = self.stored_token(&game.provider)?;
+        self.provider(&game.provider)?
+            .get_cloud_saves(&token, &game.app_name)
+            .await
+    }
+
+    /// Download a game's cloud save: the snapshot named `snapshot`, as listed by
+    /// `list_cloud_saves`, or the newest one
+    pub async fn download_cloud_saves(&self, address: &str, snapshot: Option<&str>) -> Result<()> {
+        let result = self.fetch_cloud_saves(address, snapshot).await;
+        self.notify_sync_failure(address, &result);
+        result
+    }
+
+    /// Upload cloud saves for a game
+    pub async fn upload_cloud_saves(&self, address: &str) -> Result<()> {
+        let result = self.push_cloud_saves(address).await;
+        self.notify_sync_failure(address, &result);
+        result
+    }
+
+    /// Directory cloud saves are synced with: the game's save directory, or `saves` in the
+    /// install directory when it isn't known
+    fn cloud_saves_dir(&self, game: &InstalledGame) -> Result<PathBuf> {
+        Ok(self
+            .save_dir(&game.app_name)?
+            .unwrap_or_else(|| game.install_path.join(FALLBACK_SAVE_DIR)))
+    }
+
+    fn notify_sync_failure(&self, address: &str, result: &Result<()>) {
+        if let Err(e) = result {
+            let title = InstalledGame::resolve(&self.config(), address)
+                .map(|game| game.app_title)
+                .unwrap_or_else(|_| address.to_string());
+
+            self.notify(Notification::CloudSyncFailed {
+                title,
+                error: e.to_string(),
+            });
+        }
+    }
+
+    async fn fetch_cloud_saves(&self, address: &str, snapshot: Option<&str>) -> Result<()> {
+        // TODO: Compare timestamps to detect newer save
+        // TODO: Create backup of local saves before overwriting
+        // TODO: Support automatic sync on game launch/exit
+
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let app_name = game.app_name.as_str();
+        let token = self.stored_token(&game.provider)?;
+        let provider = self.provider(&game.provider)?;
+
+        log::info!("Downloading cloud saves for {}", app_name);
+        log::info!("Fetching cloud saves...");
+
+        let saves = provider.get_cloud_saves(&token, app_name).await?;
+
+        if saves.is_empty() {
+            log::info!("No cloud saves found");
+            return Ok(());
+        }
+
+        log::info!("Found {} cloud save(s)", saves.len());
+        let save = pick_cloud_save(saves, snapshot).ok_or_else(|| {
+            Error::Other(format!(
+                "{} has no cloud save snapshot {} (run 'rauncher cloud-save list {}')",
+                app_name,
+                snapshot.unwrap_or_default(),
+                address
+            ))
+        })?;
+
+        log::info!("  Downloading: {}", save.filename);
+        let files = provider.download_cloud_save(&token, &save).await?;
+
+        let saves_dir = self.cloud_saves_dir(&game)?;
+        let mut changed = Vec::new();
+        for file in &files {
+            // Paths come from the store; none may lead out of the save folder
+            let relative = Path::new(&file.path);
+            if !relative
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)))
+            {
+                return Err(Error::Other(format!(
+                    "Cloud save {} holds a file outside the save folder: {}",
+                    save.id, file.path
+                )));
+            }
+            let path = saves_dir.join(relative);
+            if path.exists() && fs::read(&path)? != file.data {
+                changed.push(file.path.as_str());
+            }
+        }
+
+        if !changed.is_empty() {
+            let question = Question::new(
+                QuestionKind::SaveConflict,
+                crate::tr!(
+                    "prompt-save-conflict",
+                    file = changed.join(", "),
+                    title = game.app_title.as_str()
+                ),
+            );
+            if self.prompter.ask(&question) == "keep_local" {
+                log::info!("Kept local saves in {:?}", saves_dir);
+                return Ok(());
+            }
+        }
+
+        for file in &files {
+            let path = saves_dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &file.data)?;
+            crate::logs::transcript::touched("wrote", &path);
+            log::info!("Downloaded save: {:?}", path);
+        }
+        log::info!("✓ Cloud save {} downloaded", save.id);
+        Ok(())
+    }
+
+    async fn push_cloud_saves(&self, address: &str) -> Result<()> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let app_name = game.app_name.as_str();
+        let token = self.stored_token(&game.provider)?;
+        let provider = self.provider(&game.provider)?;
+
+        log::info!("Uploading cloud saves for {}", app_name);
+        log::info!("Uploading cloud saves...");
+
+        let saves_dir = self.cloud_saves_dir(&game)?;
+
+        if !saves_dir.exists() {
+            log::info!("No local saves found");
+            return Ok(());
+        }
+
+        let mut uploaded = 0;
+
+        for entry in fs::read_dir(&saves_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_file() {
+                let save_data = fs::read(&path)?;
+                log::info!(
+                    "  Uploading: {}",
+                    path.file_name().unwrap().to_string_lossy()
+                );
+
+                provider
+                    .upload_cloud_save(&token, app_name, &save_data)
+                    .await?;
+                uploaded += 1;
+            }
+        }
+
+        log::info!("✓ Uploaded {} save file(s)", uploaded);
+        Ok(())
+    }
+}
+
+/// Note an operation in the history. A history that can't be written doesn't fail the
+/// operation itself.
+fn record_history(entry: HistoryEntry) {
+    if let Err(e) = crate::history::record(&entry) {
+        log::warn!("Failed to record {} in the history: {}", entry.app_name, e);
+    }
+}
+
+/// Note a play session. Like the history, a failure only gets logged.
+fn record_session(session: &PlaySession) {
+    if let Err(e) = crate::stats::record_session(session) {
+        log::warn!("Failed to record {}'s play time: {}", session.app_name, e);
+    }
+}
+
+/// The user profile a game's saves are relative to: the one inside its Wine prefix when it
+/// runs through Wine, the home directory otherwise
+fn save_profile(game: &InstalledGame, settings: &GameSettings) -> Result<PathBuf> {
+    if crate::saves::is_windows_build(&game.executable) && !cfg!(target_os = "windows") {
+        let proton = settings.wine.as_deref().is_some_and(launch::is_proton);
+        let prefix = Config::prefixes_dir()?.join(&game.app_name);
+        return Ok(crate::saves::prefix_profile(&prefix, proton));
+    }
+
+    directories::BaseDirs::new()
+        .map(|dirs| dirs.home_dir().to_path_buf())
+        .ok_or_else(|| Error::Other("Could not find the home directory".to_string()))
+}
+
+/// The snapshot named `id` among a game's cloud saves, or the newest when no name is given.
+/// `saves` are newest first, as listed.
+fn pick_cloud_save(saves: Vec<CloudSave>, id: Option<&str>) -> Option<CloudSave> {
+    match id {
+        Some(id) => saves.into_iter().find(|save| save.id == id),
+        None => saves.into_iter().next(),
+    }
+}
+
+/// Whether `path` is a directory with anything in it
+fn holds_files(path: &Path) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// Keep a decoded chunk for later installs; failing to is no reason to fail this one
+fn cache_chunk(cache: Option<&ChunkCache>, provider: &str, guid: &str, chunk: &[u8]) {
+    if let Some(cache) = cache {
+        if let Err(e) = cache.put(provider, guid, chunk) {
+            log::debug!("Could not cache chunk {}: {}", guid, e);
+        }
+    }
+}
+
+/// Make sure a downloaded chunk is the one the manifest lists, when it gives the chunk's SHA-1,
+/// before any of it is written or cached
+fn check_chunk(chunk: &[u8], sha: Option<&[u8]>, guid: &str) -> Result<()> {
+    use sha1::{Digest, Sha1};
+
+    match sha {
+        Some(sha) if Sha1::digest(chunk)[..] != *sha => Err(Error::Other(format!(
+            "Chunk {} is damaged: its SHA-1 doesn't match the manifest",
+            guid
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Write the range of `chunk` that `part` names at `start` in the file. Returns the bytes
+/// written.
+fn write_part(
+    output: &PositionedFile,
+    chunk: &[u8],
+    part: &ChunkPart,
+    start: u64,
+    filename: &str,
+) -> Result<u64> {
+    let begin = part.offset as usize;
+    let end = begin + part.size as usize;
+    let data = chunk.get(begin..end).ok_or_else(|| {
+        Error::Other(format!(
+            "Chunk {} is too short for {} ({} bytes, need {})",
+            part.guid,
+            filename,
+            chunk.len(),
+            end
+        ))
+    })?;
+
+    output.write_all_at(data, start)?;
+    Ok(data.len() as u64)
+}
+
+/// Give a file its final size before its chunks are written. Preallocated space comes out of
+/// the disk right away, so a full disk fails the install before the download rather than
+/// halfway through, and the file isn't fragmented; otherwise the file is left sparse.
+fn reserve(file: &fs::File, size: u64, preallocate: bool) -> std::io::Result<()> {
+    if preallocate {
+        file.allocate(size)
+    } else {
+        file.set_len(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Game;
+    use crate::manifest::{self, chunk};
+    use crate::providers::LoginFlow;
+    use crate::test_support::{isolate_data_dir, serve_files, serve_responses};
+    use async_trait::async_trait;
+    use sha1::{Digest, Sha1};
+    use std::collections::HashMap;
+
+    /// A store whose chunks come from `url`, where 401 turns the token down
+    struct MockStore {
+        url: String,
+        /// Access tokens chunks were asked for with, in order
+        used: Arc<Mutex<Vec<String>>>,
+    }
+
+    fn token(access_token: &str) -> AuthToken {
+        AuthToken {
+            access_token: access_token.to_string(),
+            refresh_token: format!("{}-refresh", access_token),
+            expires_at: Utc::now() + chrono::Duration::hours(1),
+            account_id: "acc".to_string(),
+        }
+    }
+
+    #[async_trait]
+    impl StoreProvider for MockStore {
+        fn id(&self) -> &'static str {
+            "mock"
+        }
+
+        fn login_flow(&self) -> Result<LoginFlow> {
+            Ok(LoginFlow::DeviceCode)
+        }
+
+        async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken> {
+            assert_eq!(refresh_token, "stale-refresh");
+            Ok(token("renewed"))
+        }
+
+        async fn get_games(&self, _token: &AuthToken) -> Result<Vec<Game>> {
+            Ok(Vec::new())
+        }
+
+        async fn download_manifest(
+            &self,
+            _token: &AuthToken,
+            app_name: &str,
+        ) -> Result<GameManifest> {
+            Err(Error::GameNotFound(app_name.to_string()))
+        }
+
+        async fn download_chunk(
+            &self,
+            token: &AuthToken,
+            _app_name: &str,
+            chunk_guid: &str,
+        ) -> Result<Vec<u8>> {
+            self.used.lock().unwrap().push(token.access_token.clone());
+            let response = reqwest::Client::new()
+                .get(format!("{}/{}", self.url, chunk_guid))
+                .bearer_auth(&token.access_token)
+                .send()
+                .await?;
+            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(Error::TokenRejected("Failed to download chunk".to_string()));
+            }
+            Ok(response.bytes().await?.to_vec())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_is_renewed_and_the_chunk_retried() {
+        isolate_data_dir();
+        let stale = token("stale");
+        stale.save_for("mock").unwrap();
+
+        let url = serve_responses(vec![(401, Vec::new()), (200, b"chunk".to_vec())]).await;
+        let used = Arc::new(Mutex::new(Vec::new()));
+        let mut manager = GameManager::new(Config::default(), AuthManager::default()).unwrap();
+        Arc::get_mut(&mut manager.shared)
+            .unwrap()
+            .stores
+            .push(Box::new(MockStore {
+                url,
+                used: used.clone(),
+            }));
+
+        let current = Mutex::new(stale);
+        let store = manager.provider("mock").unwrap();
+        let chunk = manager
+            .download_chunk(store, &current, "Kiwi", "chunk1")
+            .await
+            .unwrap();
+
+        assert_eq!(chunk, b"chunk");
+        assert_eq!(*used.lock().unwrap(), ["stale", "renewed"]);
+        // Later chunks go out with the renewed token, which is kept for next time too
+        assert_eq!(current.lock().unwrap().access_token, "renewed");
+        let stored = AuthToken::load_for("mock").unwrap().unwrap();
+        assert_eq!(stored.access_token, "renewed");
+    }
+
+    #[test]
+    fn test_check_chunk() {
+        let sha = Sha1::digest(b"kiwi").to_vec();
+        assert!(check_chunk(b"kiwi", Some(&sha), "A").is_ok());
+        assert!(check_chunk(b"kiwi", None, "A").is_ok());
+        let err = check_chunk(b"kiwi!", Some(&sha), "A").unwrap_err();
+        assert!(err.to_string().contains("Chunk A is damaged"));
+    }
+
+    #[tokio::test]
+    async fn test_install_epic_build_from_served_chunks() {
+        isolate_data_dir();
+        let mut build =
+            manifest::parse(include_bytes!("../manifest/fixtures/kiwi.manifest")).unwrap();
+
+        // The fixture's hashes are made up; give its chunks data and hash that instead
+        let mut chunk_data = HashMap::new();
+        for (i, info) in build.chunks.iter_mut().enumerate() {
+            let data: Vec<u8> = (0..info.window_size as usize)
+                .map(|n| (n * (i + 3) % 251) as u8)
+                .collect();
+            info.sha = Sha1::digest(&data).into();
+            chunk_data.insert(info.guid, data);
+        }
+        let mut contents = HashMap::new();
+        for file in &mut build.files {
+            let mut data = Vec::new();
+            for part in &file.parts {
+                let start = part.offset as usize;
+                data.extend_from_slice(&chunk_data[&part.guid][start..start + part.size as usize]);
+            }
+            file.sha = Sha1::digest(&data).into();
+            contents.insert(file.filename.clone(), data);
+        }
+
+        let mut served = HashMap::new();
+        let manifest_file = build.to_binary();
+        let manifest_sha: String = Sha1::digest(&manifest_file)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        served.insert("/Builds/Kiwi/kiwi.manifest".to_string(), manifest_file);
+        for info in &build.chunks {
+            let path = chunk::path(build.version, info.group, info.hash, &info.guid);
+            let file = chunk::encode(&info.guid, info.hash, &chunk_data[&info.guid]);
+            served.insert(format!("/Builds/Kiwi/{}", path), file);
+        }
+        let url = serve_files(served).await;
+
+        let install_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            install_dir: install_dir.path().to_path_buf(),
+            cache_size_mb: 0,
+            ..Config::default()
+        };
+        let manager = GameManager::new(config.clone(), AuthManager::default()).unwrap();
+        // The first mirror is down; chunks come from the second, next to the manifest
+        let mirrors = [
+            format!("{}/Gone/Kiwi/kiwi.manifest", url),
+            format!("{}/Builds/Kiwi/kiwi.manifest?signed=1", url),
+        ];
+        let game_manifest = manager
+            .shared
+            .client
+            .manifest_from_mirrors("Kiwi", &mirrors, &manifest_sha)
+            .await
+            .unwrap();
+        manager
+            .install_manifest(providers::EPIC, &token("epic"), game_manifest, false)
+            .await
+            .unwrap();
+
+        let installed = InstalledGame::load(&config, "Kiwi").unwrap();
+        assert_eq!(installed.app_version, "1.2.0-CL-1234+++Kiwi+Release");
+        assert_eq!(installed.install_size, 301_350);
+        for (filename, data) in &contents {
+            let written = fs::read(installed.install_path.join(filename)).unwrap();
+            assert!(written == *data, "{} differs", filename);
+        }
+    }
+}
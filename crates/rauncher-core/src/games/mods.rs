@@ -0,0 +1,117 @@
+//! Protecting user-managed files in an install (mods, edited configs) from installs, updates
+//! and `verify --delete-extra`. The protected paths live in the game's settings.
+
+use std::path::{Component, Path};
+
+use super::{GameManager, InstalledGame};
+use crate::{Error, Result};
+
+impl GameManager {
+    /// Protect a file or folder of an installed game. `path` is relative to the install
+    /// directory, or an absolute path inside it. Returns the path as stored, and whether it
+    /// wasn't protected yet.
+    pub fn protect_path(&self, address: &str, path: &Path) -> Result<(String, bool)> {
+        let installed = InstalledGame::resolve(&self.config(), address)?;
+        let path = relative_path(&installed.install_path, path)?;
+
+        let added = self.update_config(|config| {
+            let settings = config.games.entry(installed.app_name).or_default();
+            if settings.protected.contains(&path) {
+                return false;
+            }
+            settings.protected.push(path.clone());
+            settings.protected.sort();
+            true
+        })?;
+        Ok((path, added))
+    }
+
+    /// Stop protecting a path. Returns whether it was protected.
+    pub fn unprotect_path(&self, address: &str, path: &Path) -> Result<bool> {
+        let installed = InstalledGame::resolve(&self.config(), address)?;
+        let path = relative_path(&installed.install_path, path)?;
+
+        if !self
+            .config()
+            .game_settings(&installed.app_name)
+            .protected
+            .contains(&path)
+        {
+            return Ok(false);
+        }
+        self.update_config(|config| {
+            if let Some(settings) = config.games.get_mut(&installed.app_name) {
+                settings.protected.retain(|protected| *protected != path);
+            }
+        })?;
+        Ok(true)
+    }
+
+    /// The protected paths of an installed game
+    pub fn protected_paths(&self, address: &str) -> Result<Vec<String>> {
+        let installed = InstalledGame::resolve(&self.config(), address)?;
+        Ok(self.config().game_settings(&installed.app_name).protected)
+    }
+}
+
+/// `path` relative to `install_path` with `/` separators, refusing paths that lead out of it
+fn relative_path(install_path: &Path, path: &Path) -> Result<String> {
+    let relative = if path.is_absolute() {
+        path.strip_prefix(install_path)
+            .map_err(|_| Error::Other(format!("{:?} is not inside {:?}", path, install_path)))?
+    } else {
+        path
+    };
+
+    let mut parts = Vec::new();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy()),
+            Component::CurDir => {}
+            _ => {
+                return Err(Error::Other(format!(
+                    "{:?} leads outside the install directory",
+                    path
+                )))
+            }
+        }
+    }
+    if parts.is_empty() {
+        return Err(Error::Other(
+            "The whole install directory can't be protected".to_string(),
+        ));
+    }
+    Ok(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameSettings;
+
+    #[test]
+    fn test_protected_paths() {
+        let install = Path::new("/games/Kiwi");
+        assert_eq!(
+            relative_path(install, Path::new("./Mods/loader.dll")).unwrap(),
+            "Mods/loader.dll"
+        );
+        assert_eq!(
+            relative_path(install, Path::new("/games/Kiwi/Config")).unwrap(),
+            "Config"
+        );
+        assert!(relative_path(install, Path::new("/games/Other/Mods")).is_err());
+        assert!(relative_path(install, Path::new("Mods/../../Other")).is_err());
+        assert!(relative_path(install, Path::new(".")).is_err());
+
+        let settings = GameSettings {
+            protected: vec!["Mods".to_string(), "Config/user.ini".to_string()],
+            ..GameSettings::default()
+        };
+        assert!(settings.is_protected("Mods"));
+        assert!(settings.is_protected("Mods/loader.dll"));
+        assert!(settings.is_protected("Config/user.ini"));
+        assert!(!settings.is_protected("ModsExtra/loader.dll"));
+        assert!(!settings.is_protected("Config/engine.ini"));
+    }
+}
@@ -0,0 +1,100 @@
+//! Working without the network: the last library listing of every store is kept on disk and
+//! served when the store can't be reached, or when offline mode is turned on.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+
+use super::{GameManager, InstalledGame};
+use crate::api::Game;
+use crate::config::Config;
+use crate::{Error, Result};
+
+impl GameManager {
+    /// Never touch the network: libraries come from the last listing, and operations that
+    /// need a store fail right away
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    /// Whether offline mode is on, or the last library listing had to be served from the cache
+    pub fn is_offline(&self) -> bool {
+        self.offline || self.shared.served_from_cache.load(Ordering::Relaxed)
+    }
+
+    /// Fail with `Error::Offline` in offline mode
+    pub(super) fn require_online(&self) -> Result<()> {
+        if self.offline {
+            return Err(Error::Offline);
+        }
+        Ok(())
+    }
+
+    /// The last listing of a store's library, or its installed games when it was never listed
+    pub(super) fn offline_library(&self, provider: &str) -> Result<Vec<Game>> {
+        if let Some(games) = load_listing(provider)? {
+            return Ok(games);
+        }
+
+        Ok(self
+            .list_installed()?
+            .into_iter()
+            .filter(|game| game.provider == provider)
+            .map(|game| installed_as_game(&game))
+            .collect())
+    }
+}
+
+/// Errors meaning the store couldn't be reached at all, as opposed to refusing the request
+pub(super) fn is_network_error(error: &Error) -> bool {
+    matches!(error, Error::Offline)
+        || matches!(error, Error::Http(e) if e.is_connect() || e.is_timeout())
+}
+
+pub(super) fn save_listing(provider: &str, games: &[Game]) -> Result<()> {
+    let path = listing_path(provider)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string(games)?)?;
+    crate::logs::transcript::touched("wrote", &path);
+
+    Ok(())
+}
+
+pub(super) fn load_listing(provider: &str) -> Result<Option<Vec<Game>>> {
+    let path = listing_path(provider)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+}
+
+fn listing_path(provider: &str) -> Result<PathBuf> {
+    Ok(Config::data_dir()?
+        .join("cache")
+        .join(format!("games_{}.json", provider)))
+}
+
+pub(super) fn installed_as_game(game: &InstalledGame) -> Game {
+    Game {
+        app_name: game.app_name.clone(),
+        app_title: game.app_title.clone(),
+        app_version: game.app_version.clone(),
+        install_path: Some(game.install_path.display().to_string()),
+        provider: game.provider.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_unreachable_stores_count_as_offline() {
+        assert!(is_network_error(&Error::Offline));
+        assert!(!is_network_error(&Error::NotAuthenticated));
+        assert!(!is_network_error(&Error::Api("500".to_string())));
+    }
+}
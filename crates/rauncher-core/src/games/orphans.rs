@@ -0,0 +1,276 @@
+//! Game folders in the library that rauncher has no install record for, e.g. after the
+//! records were lost or a game was copied in by hand, and adopting them back.
+
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{dir_size, GameManager, InstalledGame};
+use crate::api::{Game, GameManifest};
+use crate::storage::Removal;
+use crate::Result;
+
+/// Share of a manifest's files that must be in a folder, at their expected size, for it to
+/// count as that game
+const MATCH_THRESHOLD: f64 = 0.9;
+
+/// A folder in a library root that no install record points at
+#[derive(Debug, Clone)]
+pub struct OrphanedInstall {
+    pub path: PathBuf,
+    pub size: u64,
+    /// The owned game whose manifest the folder's files match, if any
+    pub matched: Option<OrphanMatch>,
+}
+
+/// An owned game identified in an orphaned folder, with what its install record needs
+#[derive(Debug, Clone)]
+pub struct OrphanMatch {
+    pub provider: String,
+    pub app_name: String,
+    pub app_title: String,
+    pub app_version: String,
+    pub executable: String,
+    pub install_size: u64,
+    /// Manifest files found at their expected size, out of `files_total`
+    pub files_found: usize,
+    pub files_total: usize,
+}
+
+impl OrphanedInstall {
+    /// Get rid of the folder, returning the bytes removed
+    pub fn delete(&self, removal: Removal) -> Result<u64> {
+        crate::storage::remove_dir(&self.path, removal)?;
+        log::debug!("Removed orphaned folder {:?}", self.path);
+        Ok(self.size)
+    }
+}
+
+impl GameManager {
+    /// Find unregistered folders in the library roots and identify them against the manifests
+    /// of owned games. Outside the configured install directory only identified folders are
+    /// returned, since other launchers' games may share those directories.
+    pub async fn find_orphans(&self) -> Result<Vec<OrphanedInstall>> {
+        let installed = self.list_installed()?;
+        let folders = crate::storage::unregistered_dirs(&self.config(), &installed);
+        if folders.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Without a library nothing can be identified, but the folders are still worth listing
+        let library = self.list_merged_library().await.unwrap_or_else(|e| {
+            log::warn!("Cannot identify folders against the library: {}", e);
+            Vec::new()
+        });
+        let library: Vec<Game> = library
+            .into_iter()
+            .filter(|game| !installed.iter().any(|i| i.app_name == game.app_name))
+            .collect();
+
+        let mut orphans = Vec::new();
+        for (path, in_install_dir) in folders {
+            let matched = match candidate(&path, &library) {
+                Some(game) => self.match_manifest(game, &path).await,
+                None => None,
+            };
+            if matched.is_none() && !in_install_dir {
+                continue;
+            }
+
+            orphans.push(OrphanedInstall {
+                size: dir_size(&path),
+                path,
+                matched,
+            });
+        }
+
+        Ok(orphans)
+    }
+
+    /// Check a folder against a game's current manifest
+    async fn match_manifest(&self, game: &Game, path: &Path) -> Option<OrphanMatch> {
+        let manifest = async {
+            let token = self.provider_token(&game.provider).await?;
+            self.provider(&game.provider)?
+                .download_manifest(&token, &game.app_name)
+                .await
+        }
+        .await;
+        let manifest = match manifest {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                log::debug!("No manifest to identify {:?} by: {}", path, e);
+                return None;
+            }
+        };
+
+        let files_found = matching_files(&manifest, path);
+        let files_total = manifest.file_list.len();
+        let matches = if files_total == 0 {
+            !manifest.launch_exe.is_empty() && path.join(&manifest.launch_exe).is_file()
+        } else {
+            files_found as f64 / files_total as f64 >= MATCH_THRESHOLD
+        };
+        if !matches {
+            log::debug!(
+                "{:?} is not {}: {}/{} files match",
+                path,
+                game.app_name,
+                files_found,
+                files_total
+            );
+            return None;
+        }
+
+        let executable = if manifest.launch_exe.is_empty() {
+            self.provider(&game.provider)
+                .ok()
+                .and_then(|store| store.launch_executable(path))
+                .unwrap_or_default()
+        } else {
+            manifest.launch_exe.clone()
+        };
+
+        Some(OrphanMatch {
+            provider: game.provider.clone(),
+            app_name: game.app_name.clone(),
+            app_title: game.app_title.clone(),
+            app_version: manifest.app_version,
+            executable,
+            install_size: manifest.build_size,
+            files_found,
+            files_total,
+        })
+    }
+
+    /// Write an install record for an identified orphaned folder, leaving its files in place
+    pub fn adopt_orphan(&self, orphan: &OrphanedInstall) -> Result<InstalledGame> {
+        let Some(matched) = &orphan.matched else {
+            return Err(crate::Error::Other(format!(
+                "{:?} was not identified as an owned game",
+                orphan.path
+            )));
+        };
+        self.check_not_installed_elsewhere(&matched.provider, &matched.app_name)?;
+
+        let game = InstalledGame {
+            app_name: matched.app_name.clone(),
+            app_title: matched.app_title.clone(),
+            app_version: matched.app_version.clone(),
+            install_path: orphan.path.clone(),
+            executable: matched.executable.clone(),
+            pinned: false,
+            provider: matched.provider.clone(),
+            install_size: matched.install_size,
+            download_size: 0,
+            installed_at: Some(Utc::now()),
+            last_played: None,
+            volume: None,
+            save_path_template: None,
+        };
+        game.save(&self.config())?;
+
+        if let Err(e) = crate::registry::register_install(&game) {
+            log::warn!(
+                "Failed to register {} with the system: {}",
+                game.app_name,
+                e
+            );
+        }
+        log::debug!("Adopted {:?} as {}", orphan.path, game.id());
+
+        Ok(game)
+    }
+}
+
+/// The owned game a folder is most likely an install of: the one named like the folder, as
+/// rauncher names install directories, or else the one whose title reads the same
+fn candidate<'a>(path: &Path, library: &'a [Game]) -> Option<&'a Game> {
+    let name = path.file_name()?.to_string_lossy();
+    library
+        .iter()
+        .find(|game| game.app_name == name)
+        .or_else(|| {
+            let wanted = normalize(&name);
+            library
+                .iter()
+                .find(|game| normalize(&game.app_title) == wanted)
+        })
+}
+
+/// Lowercase letters and digits only, so "Hades II" and "hades_ii" compare equal
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Manifest files present under `path` with the size the manifest gives them
+fn matching_files(manifest: &GameManifest, path: &Path) -> usize {
+    manifest
+        .file_list
+        .iter()
+        .filter(|file| {
+            let expected: u64 = file.file_chunk_parts.iter().map(|part| part.size).sum();
+            fs::metadata(path.join(&file.filename)).is_ok_and(|m| m.len() == expected)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ChunkPart, FileManifest};
+
+    fn game(app_name: &str, app_title: &str) -> Game {
+        Game {
+            app_name: app_name.to_string(),
+            app_title: app_title.to_string(),
+            app_version: "1.0".to_string(),
+            install_path: None,
+            provider: "epic".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_candidate_by_app_name_then_title() {
+        let library = vec![game("Fortnite", "Fortnite"), game("Kiwi", "Hades II")];
+
+        let by_name = candidate(Path::new("/games/Fortnite"), &library);
+        assert_eq!(by_name.map(|g| g.app_name.as_str()), Some("Fortnite"));
+        let by_title = candidate(Path::new("/games/hades_ii"), &library);
+        assert_eq!(by_title.map(|g| g.app_name.as_str()), Some("Kiwi"));
+        assert!(candidate(Path::new("/games/Celeste"), &library).is_none());
+    }
+
+    #[test]
+    fn test_matching_files_checks_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("game.exe"), [0u8; 30]).unwrap();
+        fs::write(dir.path().join("data.pak"), [0u8; 5]).unwrap();
+
+        let file = |filename: &str, sizes: &[u64]| FileManifest {
+            filename: filename.to_string(),
+            file_hash: Vec::new(),
+            file_chunk_parts: sizes
+                .iter()
+                .map(|size| ChunkPart {
+                    guid: String::new(),
+                    offset: 0,
+                    size: *size,
+                })
+                .collect(),
+        };
+        let manifest = GameManifest {
+            file_list: vec![
+                file("game.exe", &[10, 20]),
+                file("data.pak", &[50]),
+                file("missing.dll", &[1]),
+            ],
+            ..GameManifest::default()
+        };
+
+        assert_eq!(matching_files(&manifest, dir.path()), 1);
+    }
+}
@@ -0,0 +1,282 @@
+//! What an install or update would do, worked out from the manifest without writing anything,
+//! for `--dry-run`.
+
+use std::path::{Path, PathBuf};
+
+use super::{dir_size, verify, GameManager, InstalledGame};
+use crate::api::{FileManifest, GameManifest};
+use crate::providers;
+use crate::storage::existing_ancestor;
+use crate::{Error, Result};
+
+/// The files an install or update would write and the space they take
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallPlan {
+    pub app_name: String,
+    pub provider: String,
+    /// Version the install or update brings
+    pub version: String,
+    /// Installed version, for updates
+    pub current_version: Option<String>,
+    pub install_path: PathBuf,
+    /// Files in the new build
+    pub files: usize,
+    /// Files that are missing or differ in size or hash on disk, and would be downloaded
+    pub changed_files: Vec<String>,
+    /// Bytes to download: the chunks the changed files are made of, compressed
+    pub download_bytes: u64,
+    /// Bytes of file data the changed files hold once unpacked
+    pub write_bytes: u64,
+    /// Size of the install directory now
+    pub current_disk_usage: u64,
+    /// Size of the new build
+    pub disk_usage: u64,
+    /// Space left on the install directory's filesystem, when known
+    pub free_space: Option<u64>,
+}
+
+impl InstallPlan {
+    /// Compare `manifest` against what's already in `install_path`
+    fn new(
+        provider: &str,
+        manifest: &GameManifest,
+        install_path: PathBuf,
+        current_version: Option<String>,
+    ) -> Result<Self> {
+        let changed = changed_files(manifest, &install_path)?;
+        let write_bytes = changed.iter().map(|file| file_size(file)).sum();
+        let disk_usage = manifest.file_list.iter().map(file_size).sum();
+        let download_bytes = manifest.download_size_of(changed.iter().copied());
+
+        Ok(Self {
+            app_name: manifest.app_name.clone(),
+            provider: provider.to_string(),
+            version: manifest.app_version.clone(),
+            current_version,
+            current_disk_usage: dir_size(&install_path),
+            free_space: fs4::available_space(existing_ancestor(&install_path)).ok(),
+            install_path,
+            files: manifest.file_list.len(),
+            changed_files: changed.iter().map(|file| file.filename.clone()).collect(),
+            download_bytes,
+            write_bytes,
+            disk_usage,
+        })
+    }
+
+    /// Extra space the install or update needs, beyond what the game takes now
+    pub fn space_needed(&self) -> u64 {
+        self.disk_usage.saturating_sub(self.current_disk_usage)
+    }
+
+    /// Whether the filesystem is known to be too full
+    pub fn fits(&self) -> bool {
+        self.free_space
+            .is_none_or(|free| free >= self.space_needed())
+    }
+}
+
+fn file_size(file: &FileManifest) -> u64 {
+    file.file_chunk_parts.iter().map(|part| part.size).sum()
+}
+
+/// The files of `manifest` that are missing from `install_path`, or differ there in size or,
+/// where the manifest has one, hash
+fn changed_files<'a>(
+    manifest: &'a GameManifest,
+    install_path: &Path,
+) -> Result<Vec<&'a FileManifest>> {
+    let mut changed = Vec::new();
+    for file in &manifest.file_list {
+        if verify::check_file(file, file_size(file), install_path)?.is_some() {
+            changed.push(file);
+        }
+    }
+    Ok(changed)
+}
+
+/// `manifest` narrowed down to the files that are missing or differ in `install_path`,
+/// hashed off the async runtime
+pub(super) async fn changed_in_background(
+    manifest: &GameManifest,
+    install_path: &Path,
+) -> Result<GameManifest> {
+    let mut manifest = manifest.clone();
+    let install_path = install_path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let changed: Vec<FileManifest> = changed_files(&manifest, &install_path)?
+            .into_iter()
+            .cloned()
+            .collect();
+        manifest.file_list = changed;
+        Ok(manifest)
+    })
+    .await
+    .map_err(|e| Error::Other(format!("Comparing the installed files failed: {}", e)))?
+}
+
+impl GameManager {
+    /// What installing a game would download and write, like `install_provider_game` and
+    /// `install_game_version` but stopping after the manifest
+    pub async fn plan_install(
+        &self,
+        provider: &str,
+        app_name: &str,
+        build_version: Option<&str>,
+    ) -> Result<InstallPlan> {
+        let manifest = if provider == providers::EPIC {
+            let token = self.ensure_valid_token().await?;
+            self.shared
+                .client
+                .download_manifest_for_build(&token, app_name, build_version)
+                .await?
+        } else if build_version.is_some() {
+            return Err(Error::Other(format!(
+                "Only Epic Games Store games can be installed at a given version, not {} ones",
+                self.provider(provider)?.name()
+            )));
+        } else {
+            let token = self.provider_token(provider).await?;
+            self.provider(provider)?
+                .download_manifest(&token, app_name)
+                .await?
+        };
+
+        let current_version = InstalledGame::load(&self.config(), app_name)
+            .ok()
+            .map(|game| game.app_version);
+        let install_path = self.config().install_dir.join(app_name);
+        let provider = provider.to_string();
+        plan_in_background(move || {
+            InstallPlan::new(&provider, &manifest, install_path, current_version)
+        })
+        .await
+    }
+
+    /// What updating an installed game to its latest build would download and write
+    pub async fn plan_update(&self, address: &str) -> Result<InstallPlan> {
+        let installed = InstalledGame::resolve(&self.config(), address)?;
+        let token = self.stored_token(&installed.provider)?;
+        let manifest = self
+            .provider(&installed.provider)?
+            .download_manifest(&token, &installed.app_name)
+            .await?;
+
+        plan_in_background(move || {
+            InstallPlan::new(
+                &installed.provider,
+                &manifest,
+                installed.install_path,
+                Some(installed.app_version),
+            )
+        })
+        .await
+    }
+}
+
+/// Work out a plan off the async runtime, as it hashes the installed files
+async fn plan_in_background(
+    plan: impl FnOnce() -> Result<InstallPlan> + Send + 'static,
+) -> Result<InstallPlan> {
+    tokio::task::spawn_blocking(plan)
+        .await
+        .map_err(|e| Error::Other(format!("Planning failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ChunkPart;
+    use sha1::{Digest, Sha1};
+    use std::fs;
+
+    fn file(filename: &str, size: u64) -> FileManifest {
+        FileManifest {
+            filename: filename.to_string(),
+            file_hash: Vec::new(),
+            file_chunk_parts: vec![ChunkPart {
+                guid: filename.to_string(),
+                offset: 0,
+                size,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_only_missing_and_changed_files_are_downloaded() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("same.pak"), vec![0u8; 10]).unwrap();
+        fs::write(dir.path().join("resized.pak"), vec![0u8; 5]).unwrap();
+        fs::write(dir.path().join("kept.pak"), b"old").unwrap();
+        fs::write(dir.path().join("edited.pak"), b"old").unwrap();
+
+        let hashed = |filename: &str, data: &[u8]| FileManifest {
+            file_hash: Sha1::digest(data).to_vec(),
+            ..file(filename, data.len() as u64)
+        };
+        let manifest = GameManifest {
+            app_name: "Kiwi".to_string(),
+            app_version: "1.1".to_string(),
+            file_list: vec![
+                file("same.pak", 10),
+                file("resized.pak", 20),
+                file("new.pak", 30),
+                hashed("kept.pak", b"old"),
+                // As big as before, but not the same
+                hashed("edited.pak", b"new"),
+            ],
+            ..GameManifest::default()
+        };
+        let plan = InstallPlan::new(
+            providers::EPIC,
+            &manifest,
+            dir.path().to_path_buf(),
+            Some("1.0".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(plan.files, 5);
+        assert_eq!(
+            plan.changed_files,
+            vec!["resized.pak", "new.pak", "edited.pak"]
+        );
+        assert_eq!(plan.download_bytes, 53);
+        assert_eq!(plan.write_bytes, 53);
+        assert_eq!(plan.current_disk_usage, 21);
+        assert_eq!(plan.disk_usage, 66);
+        assert_eq!(plan.space_needed(), 45);
+        assert!(plan.free_space.is_some());
+    }
+
+    #[test]
+    fn test_download_counts_compressed_chunks_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let part = |offset, size| ChunkPart {
+            guid: "chunk".to_string(),
+            offset,
+            size,
+        };
+        let manifest = GameManifest {
+            file_list: vec![
+                FileManifest {
+                    filename: "a.pak".to_string(),
+                    file_hash: Vec::new(),
+                    file_chunk_parts: vec![part(0, 600)],
+                },
+                FileManifest {
+                    filename: "b.pak".to_string(),
+                    file_hash: Vec::new(),
+                    file_chunk_parts: vec![part(600, 400)],
+                },
+            ],
+            chunk_filesize_list: [("chunk".to_string(), 300)].into(),
+            ..GameManifest::default()
+        };
+        let plan =
+            InstallPlan::new(providers::EPIC, &manifest, dir.path().to_path_buf(), None).unwrap();
+
+        assert_eq!(plan.download_bytes, 300);
+        assert_eq!(plan.write_bytes, 1000);
+        assert_eq!(plan.disk_usage, 1000);
+    }
+}
@@ -0,0 +1,66 @@
+//! Writing a file at given offsets through one shared handle, so the chunks of a file can be
+//! written in whatever order their downloads finish, by several workers at once, without
+//! seeking or buffering them in between.
+
+use std::fs::File;
+use std::io;
+
+/// A file written with positioned writes (`pwrite` on Unix, overlapped offsets on Windows).
+/// Writes to disjoint ranges don't interfere, so the handle can be shared between workers.
+pub(crate) struct PositionedFile {
+    file: File,
+}
+
+impl PositionedFile {
+    pub(crate) fn new(file: File) -> Self {
+        Self { file }
+    }
+
+    /// Write all of `data` starting at `offset`, leaving the rest of the file alone
+    pub(crate) fn write_all_at(&self, data: &[u8], offset: u64) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileExt;
+            self.file.write_all_at(data, offset)
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::FileExt;
+            let mut written = 0;
+            while written < data.len() {
+                match self
+                    .file
+                    .seek_write(&data[written..], offset + written as u64)
+                {
+                    Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                    Ok(n) => written += n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges_can_be_written_out_of_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("game.pak");
+        let file = File::create(&path).unwrap();
+        file.set_len(9).unwrap();
+
+        let file = PositionedFile::new(file);
+        file.write_all_at(b"ghi", 6).unwrap();
+        file.write_all_at(b"abc", 0).unwrap();
+        file.write_all_at(b"def", 3).unwrap();
+        drop(file);
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"abcdefghi");
+    }
+}
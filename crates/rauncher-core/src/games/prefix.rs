@@ -0,0 +1,81 @@
+//! A game's Wine prefix outside of launching it: creating the prefix and running winecfg,
+//! winetricks or any other program inside it, always through the game's own runner.
+
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{GameManager, InstalledGame, LaunchCommand};
+use crate::config::Config;
+use crate::{Error, Result};
+
+impl GameManager {
+    /// Create and initialize a game's prefix with `wineboot`. Returns the prefix, and whether
+    /// it was created now rather than already there.
+    pub fn create_prefix(&self, address: &str) -> Result<(PathBuf, bool)> {
+        let (game, prefix) = self.game_prefix(address)?;
+        if is_initialized(&prefix) {
+            return Ok((prefix, false));
+        }
+
+        let args = [OsString::from("wineboot"), OsString::from("--init")];
+        let command = LaunchCommand::in_prefix(
+            &args,
+            &prefix,
+            &self.launch_settings(&game.app_name)?,
+            &prefix,
+        )?;
+        log::debug!("Creating prefix: {:?} {:?}", command.program, command.args);
+
+        let status = command.to_command().status()?;
+        if !status.success() {
+            return Err(Error::Other(format!(
+                "wineboot failed to create {:?} ({})",
+                prefix, status
+            )));
+        }
+        crate::logs::transcript::touched("wrote", &prefix);
+
+        Ok((prefix, true))
+    }
+
+    /// A command running `args` inside a game's prefix through its runner, e.g. an installer
+    /// or `regedit`, from the current directory
+    pub fn prefix_command(&self, address: &str, args: &[OsString]) -> Result<LaunchCommand> {
+        let (game, prefix) = self.game_prefix(address)?;
+        LaunchCommand::in_prefix(
+            args,
+            &std::env::current_dir()?,
+            &self.launch_settings(&game.app_name)?,
+            &prefix,
+        )
+    }
+
+    /// A command applying winetricks verbs to a game's prefix, which is created first since
+    /// winetricks would otherwise set it up without the runner
+    pub fn winetricks_command(&self, address: &str, verbs: &[String]) -> Result<LaunchCommand> {
+        let (prefix, _) = self.create_prefix(address)?;
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        LaunchCommand::winetricks(verbs, &self.launch_settings(&game.app_name)?, &prefix)
+    }
+
+    /// An installed game and its prefix directory, which is created if missing
+    fn game_prefix(&self, address: &str) -> Result<(InstalledGame, PathBuf)> {
+        if cfg!(target_os = "windows") {
+            return Err(Error::Other(
+                "Wine prefixes are not used on Windows".to_string(),
+            ));
+        }
+
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let prefix = Config::prefixes_dir()?.join(&game.app_name);
+        fs::create_dir_all(&prefix)?;
+
+        Ok((game, prefix))
+    }
+}
+
+/// Whether Wine has set the prefix up; Proton keeps the Wine prefix in `pfx`
+fn is_initialized(prefix: &Path) -> bool {
+    prefix.join("system.reg").is_file() || prefix.join("pfx").join("system.reg").is_file()
+}
@@ -0,0 +1,163 @@
+//! Running the redistributable installers a game ships with (Visual C++, DirectX, .NET) after
+//! it's installed on Windows. Most of them need administrator rights: when Windows refuses to
+//! start one without elevation, it's started again through a UAC prompt and the install carries
+//! on once it's done. What's been installed is remembered across games, since the runtimes are
+//! shared by the whole system.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+use crate::api::Prerequisite;
+use crate::config::Config;
+use crate::{Error, Result};
+
+/// `ERROR_ELEVATION_REQUIRED`: the program's manifest asks for administrator rights
+const ELEVATION_REQUIRED: i32 = 740;
+/// `ERROR_ACCESS_DENIED`
+const ACCESS_DENIED: i32 = 5;
+/// Exit codes installers use for success: done, done but a reboot is needed, and a newer
+/// version is already installed
+const SUCCESS_CODES: [i32; 3] = [0, 3010, 1638];
+
+/// Run `prerequisite` from `install_path`, unless what it installs is already there. Returns
+/// whether it ran.
+pub(super) fn install(prerequisite: &Prerequisite, install_path: &Path) -> Result<bool> {
+    let state = state_path()?;
+    let mut installed = load(&state);
+    if !prerequisite.ids.is_empty() && prerequisite.ids.iter().all(|id| installed.contains(id)) {
+        log::debug!("{} is already installed", prerequisite.name);
+        return Ok(false);
+    }
+
+    let program = install_path.join(&prerequisite.path);
+    if !program.is_file() {
+        return Err(Error::Other(format!(
+            "The installer for {} is missing: {:?}",
+            prerequisite.name, program
+        )));
+    }
+    let dir = program.parent().unwrap_or(install_path);
+
+    log::info!("Installing {}...", prerequisite.name);
+    let status = match run(&program, &prerequisite.args, dir) {
+        Err(e) if needs_elevation(e.raw_os_error()) => None,
+        Ok(status) if needs_elevation(status.code()) => None,
+        result => Some(result?),
+    };
+    let code = match status {
+        Some(status) => status.code(),
+        None => {
+            log::info!(
+                "{} needs administrator rights, asking for them...",
+                prerequisite.name
+            );
+            run_elevated(&program, &prerequisite.args, dir)?.code()
+        }
+    };
+    if !code.is_some_and(|code| SUCCESS_CODES.contains(&code)) {
+        return Err(Error::Other(format!(
+            "Installing {} failed (exit code {})",
+            prerequisite.name,
+            code.map(|code| code.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        )));
+    }
+
+    installed.extend(prerequisite.ids.iter().cloned());
+    fs::write(&state, serde_json::to_string_pretty(&installed)?)?;
+    Ok(true)
+}
+
+/// Whether an installer was refused, or gave up, for lack of administrator rights
+fn needs_elevation(code: Option<i32>) -> bool {
+    matches!(code, Some(ELEVATION_REQUIRED | ACCESS_DENIED))
+}
+
+fn run(program: &Path, args: &str, dir: &Path) -> io::Result<ExitStatus> {
+    let mut command = Command::new(program);
+    command.current_dir(dir);
+    // The manifest holds a ready-made Windows command line
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.raw_arg(args);
+    }
+    #[cfg(not(windows))]
+    command.args(args.split_whitespace());
+    command.status()
+}
+
+/// Start `program` through a UAC prompt and wait for it. PowerShell does the asking, and exits
+/// with the installer's code, or with 1 when the prompt is declined.
+fn run_elevated(program: &Path, args: &str, dir: &Path) -> io::Result<ExitStatus> {
+    Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command"])
+        .arg(elevation_script(program, args, dir))
+        .status()
+}
+
+fn elevation_script(program: &Path, args: &str, dir: &Path) -> String {
+    let mut script = format!(
+        "Start-Process -FilePath {} -WorkingDirectory {}",
+        quote(&program.to_string_lossy()),
+        quote(&dir.to_string_lossy())
+    );
+    // Start-Process refuses an empty argument list
+    if !args.trim().is_empty() {
+        script.push_str(&format!(" -ArgumentList {}", quote(args)));
+    }
+    script.push_str(" -Verb RunAs -Wait -PassThru | ForEach-Object { exit $_.ExitCode }");
+    script
+}
+
+/// A PowerShell string literal, taken as is
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// The ids of the prerequisites installed so far
+fn state_path() -> Result<PathBuf> {
+    let dir = Config::data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("prerequisites.json"))
+}
+
+fn load(path: &Path) -> BTreeSet<String> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elevation() {
+        assert!(needs_elevation(Some(740)));
+        assert!(needs_elevation(Some(5)));
+        assert!(!needs_elevation(Some(0)));
+        assert!(!needs_elevation(None));
+
+        let script = elevation_script(
+            Path::new(r"C:\Games\Kiwi's Quest\redist\vc_redist.x64.exe"),
+            "/install /quiet /norestart",
+            Path::new(r"C:\Games\Kiwi's Quest\redist"),
+        );
+        assert_eq!(
+            script,
+            "Start-Process -FilePath 'C:\\Games\\Kiwi''s Quest\\redist\\vc_redist.x64.exe' \
+             -WorkingDirectory 'C:\\Games\\Kiwi''s Quest\\redist' \
+             -ArgumentList '/install /quiet /norestart' \
+             -Verb RunAs -Wait -PassThru | ForEach-Object { exit $_.ExitCode }"
+        );
+        assert!(
+            !elevation_script(Path::new("setup.exe"), " ", Path::new("."))
+                .contains("-ArgumentList")
+        );
+    }
+}
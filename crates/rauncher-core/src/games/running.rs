@@ -0,0 +1,593 @@
+//! Games rauncher started that may still be running, tracked by process id in the data
+//! directory so `rauncher stop` and the GUI can find them from any rauncher process.
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::{Error, Result};
+
+/// How long a game gets to quit by itself before it's killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a killed game's processes get to disappear
+const KILL_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time between checks whether a stopped game is gone
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How far a process's start may be from the `started_at` of its record, taken just after the
+/// launch, for it to still be the launched game
+const START_TOLERANCE: Duration = Duration::from_secs(10);
+
+/// Clock ticks per second in `/proc/<pid>/stat`; Linux fixes USER_HZ at 100 for userspace
+const CLOCK_TICKS: i64 = 100;
+
+/// A launched game's process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunningRecord {
+    pub app_name: String,
+    /// Id of the launched process, which leads the game's process group on Unix
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    /// A rauncher process waits on the game and runs its post-exit steps when it exits
+    #[serde(default)]
+    pub waited: bool,
+    /// `rauncher stop` or the GUI is stopping the game, so its exit isn't a crash
+    #[serde(default)]
+    pub stopping: bool,
+}
+
+impl RunningRecord {
+    /// Whether the launched game is still running: a process has its id and started when the
+    /// game was launched, rather than being a later one that was given the same id, e.g. after
+    /// a reboot. Only the id is checked where the start time can't be looked up.
+    pub fn is_alive(&self) -> bool {
+        match process_started_at(self.pid) {
+            Some(started) => started_near(started, self.started_at),
+            None => is_alive(self.pid),
+        }
+    }
+}
+
+fn started_near(process_start: DateTime<Utc>, launched: DateTime<Utc>) -> bool {
+    (process_start - launched)
+        .abs()
+        .to_std()
+        .is_ok_and(|gap| gap <= START_TOLERANCE)
+}
+
+fn records_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("running"))
+}
+
+/// Each launch of a game has a record of its own, so a second instance started with
+/// `--force` doesn't hide the first
+fn record_path(dir: &Path, app_name: &str, pid: u32) -> PathBuf {
+    dir.join(app_name).join(format!("{}.json", pid))
+}
+
+/// Remember a launched game's process
+pub(crate) fn track(record: &RunningRecord) -> Result<()> {
+    track_in(&records_dir()?, record)
+}
+
+fn track_in(dir: &Path, record: &RunningRecord) -> Result<()> {
+    let path = record_path(dir, &record.app_name, record.pid);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string_pretty(record)?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Forget a game's process
+pub(crate) fn untrack(app_name: &str, pid: u32) {
+    if let Ok(dir) = records_dir() {
+        untrack_in(&dir, app_name, pid);
+    }
+}
+
+fn untrack_in(dir: &Path, app_name: &str, pid: u32) {
+    let _ = fs::remove_file(record_path(dir, app_name, pid));
+    // Only goes once the game's last record has
+    let _ = fs::remove_dir(dir.join(app_name));
+}
+
+/// Note that a rauncher process now waits on the game
+pub(crate) fn mark_waited(app_name: &str, pid: u32) -> Result<()> {
+    update(app_name, pid, |record| record.waited = true)
+}
+
+/// Note that the game is being stopped
+pub(crate) fn mark_stopping(app_name: &str, pid: u32) -> Result<()> {
+    update(app_name, pid, |record| record.stopping = true)
+}
+
+fn update(app_name: &str, pid: u32, change: impl FnOnce(&mut RunningRecord)) -> Result<()> {
+    let dir = records_dir()?;
+    match read_record(&dir, app_name, pid) {
+        Some(mut record) => {
+            change(&mut record);
+            track_in(&dir, &record)
+        }
+        None => Ok(()),
+    }
+}
+
+/// The record of a game's process, whether or not it's still running
+pub(crate) fn record(app_name: &str, pid: u32) -> Option<RunningRecord> {
+    read_record(&records_dir().ok()?, app_name, pid)
+}
+
+fn read_record(dir: &Path, app_name: &str, pid: u32) -> Option<RunningRecord> {
+    let contents = fs::read_to_string(record_path(dir, app_name, pid)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// The running processes of a game rauncher launched, the earliest first. Records of
+/// processes that are gone are removed.
+pub fn running(app_name: &str) -> Result<Vec<RunningRecord>> {
+    Ok(instances_in(&records_dir()?, app_name))
+}
+
+fn running_in(dir: &Path, app_name: &str) -> Option<RunningRecord> {
+    instances_in(dir, app_name).into_iter().next()
+}
+
+fn instances_in(dir: &Path, app_name: &str) -> Vec<RunningRecord> {
+    let Ok(entries) = fs::read_dir(dir.join(app_name)) else {
+        return Vec::new();
+    };
+
+    let mut records = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let Some(pid) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+        else {
+            continue;
+        };
+        match read_record(dir, app_name, pid) {
+            Some(record) if record.is_alive() => records.push(record),
+            _ => untrack_in(dir, app_name, pid),
+        }
+    }
+    records.sort_by_key(|record| record.started_at);
+    records
+}
+
+/// Every game rauncher launched that is still running
+pub fn running_games() -> Result<Vec<RunningRecord>> {
+    let dir = records_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut records = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        records.extend(instances_in(&dir, &entry.file_name().to_string_lossy()));
+    }
+    records.sort_by(|a, b| a.app_name.cmp(&b.app_name));
+    Ok(records)
+}
+
+/// Whether a process with this id exists
+pub fn is_alive(pid: u32) -> bool {
+    if cfg!(target_os = "windows") {
+        // Lists the process as "name","pid",... when it exists
+        let listed = format!("\"{}\"", pid);
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH", "/FO", "CSV"])
+            .stderr(Stdio::null())
+            .output()
+            .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains(&listed))
+    } else {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+}
+
+/// When the process with this id started, if it exists and the system says
+fn process_started_at(pid: u32) -> Option<DateTime<Utc>> {
+    if cfg!(target_os = "linux") {
+        let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        let boot_time = fs::read_to_string("/proc/stat")
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("btime ")?.trim().parse().ok())?;
+        proc_start_time(&stat, boot_time)
+    } else if cfg!(target_os = "windows") {
+        let script = format!(
+            "(Get-Process -Id {}).StartTime.ToUniversalTime().ToString('o')",
+            pid
+        );
+        let out =
+            command_output(Command::new("powershell").args(["-NoProfile", "-Command", &script]))?;
+        DateTime::parse_from_rfc3339(out.trim())
+            .ok()
+            .map(|time| time.with_timezone(&Utc))
+    } else {
+        let out =
+            command_output(Command::new("ps").args(["-o", "lstart=", "-p", &pid.to_string()]))?;
+        ps_start_time(&out)
+    }
+}
+
+fn command_output(command: &mut Command) -> Option<String> {
+    let output = command.stderr(Stdio::null()).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The start of a process from its `/proc/<pid>/stat` line, given when the system booted in
+/// seconds since the epoch
+fn proc_start_time(stat: &str, boot_time: i64) -> Option<DateTime<Utc>> {
+    // The command name in parentheses may hold spaces; the start time, in clock ticks after
+    // boot, is the 20th field after it
+    let (_, fields) = stat.rsplit_once(')')?;
+    let ticks: i64 = fields.split_whitespace().nth(19)?.parse().ok()?;
+    let millis = boot_time * 1000 + ticks * 1000 / CLOCK_TICKS;
+    DateTime::from_timestamp_millis(millis)
+}
+
+/// The start of a process as `ps -o lstart=` prints it, in local time, e.g.
+/// `Thu Oct 15 17:59:57 2026`
+fn ps_start_time(out: &str) -> Option<DateTime<Utc>> {
+    let text = out.split_whitespace().collect::<Vec<_>>().join(" ");
+    let time = NaiveDateTime::parse_from_str(&text, "%a %b %d %H:%M:%S %Y").ok()?;
+    Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// A running instance of a game: the process rauncher launched, or else any process running
+/// its executable, e.g. one started from another launcher or a desktop shortcut
+pub fn find_instance(app_name: &str, executable: &Path) -> Result<Option<u32>> {
+    Ok(find_instance_in(&records_dir()?, app_name, executable))
+}
+
+fn find_instance_in(dir: &Path, app_name: &str, executable: &Path) -> Option<u32> {
+    if let Some(record) = running_in(dir, app_name) {
+        return Some(record.pid);
+    }
+    process_listing().and_then(|listing| find_in_listing(&listing, executable, std::process::id()))
+}
+
+/// Every process, one per line after its id: with its executable's path on Windows, its
+/// command line elsewhere
+fn process_listing() -> Option<String> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Process | ForEach-Object { \"$($_.ProcessId) $($_.ExecutablePath)\" }",
+        ]);
+        command
+    } else {
+        let mut command = Command::new("ps");
+        command.args(["-axo", "pid=,args="]);
+        command
+    };
+    command_output(&mut command)
+}
+
+/// The first process in `listing` running `executable`, other than `own_pid`. Paths are
+/// compared case-insensitively with either separator, since Wine shows them as Windows paths
+/// (`Z:\home\...`).
+fn find_in_listing(listing: &str, executable: &Path, own_pid: u32) -> Option<u32> {
+    let normalize = |text: &str| text.replace('\\', "/").to_lowercase();
+    let executable_path = normalize(&executable.to_string_lossy());
+
+    listing.lines().find_map(|line| {
+        let (pid, command) = line.trim_start().split_once(char::is_whitespace)?;
+        let pid: u32 = pid.parse().ok()?;
+        let command = normalize(command.trim());
+        let matches = if cfg!(target_os = "windows") {
+            command == executable_path
+        } else {
+            has_argument(&command, &executable_path)
+        };
+        (matches && pid != own_pid).then_some(pid)
+    })
+}
+
+/// Whether `path` is a whole argument of a command line rather than part of a longer one,
+/// on its own or behind the `z:` drive Wine maps the root to
+fn has_argument(command: &str, path: &str) -> bool {
+    command.match_indices(path).any(|(start, _)| {
+        let before = &command[..start];
+        let before = before.strip_suffix("z:").unwrap_or(before);
+        let after = &command[start + path.len()..];
+        (before.is_empty() || before.ends_with(char::is_whitespace))
+            && (after.is_empty() || after.starts_with(char::is_whitespace))
+    })
+}
+
+/// Stop a game's process tree: ask it to quit (SIGTERM to its process group, or `taskkill`
+/// without `/F`), then kill it if anything of it is still there after `STOP_TIMEOUT`. Blocks
+/// until it's gone. Nothing is signalled when the record's process is no longer the game.
+pub fn terminate(record: &RunningRecord) -> Result<()> {
+    terminate_within(record, STOP_TIMEOUT)
+}
+
+fn terminate_within(record: &RunningRecord, timeout: Duration) -> Result<()> {
+    if !record.is_alive() {
+        return Ok(());
+    }
+    let pid = record.pid;
+    signal_tree(pid, false)?;
+    // The launched process often quits first and leaves the game itself behind
+    if wait_for_tree(pid, timeout) {
+        return Ok(());
+    }
+
+    log::info!("Process {} didn't quit in time, killing it", pid);
+    signal_tree(pid, true)?;
+    if wait_for_tree(pid, KILL_TIMEOUT) {
+        Ok(())
+    } else {
+        Err(Error::Other(format!(
+            "Process {} is still running after being killed",
+            pid
+        )))
+    }
+}
+
+/// Wait for the game's processes to be gone, returning whether they went in time
+fn wait_for_tree(pid: u32, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if !tree_alive(pid) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(STOP_POLL_INTERVAL);
+    }
+}
+
+/// Whether any process of the game is left: one in the process group the game leads on
+/// Unix, or the launched process on Windows, where `taskkill /T` takes care of the rest
+fn tree_alive(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        // Exited processes nobody reaped yet, e.g. in containers without an init, still
+        // count for `kill -0`, so they're left out
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return is_alive(pid);
+        };
+        entries.flatten().any(|entry| {
+            fs::read_to_string(entry.path().join("stat"))
+                .ok()
+                .and_then(|stat| proc_state_and_group(&stat))
+                .is_some_and(|(state, group)| group == pid && state != 'Z')
+        })
+    } else if cfg!(target_os = "windows") {
+        is_alive(pid)
+    } else {
+        Command::new("kill")
+            .args(["-0", "--", &format!("-{}", pid)])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+}
+
+/// A process's state and process group from its `/proc/<pid>/stat` line
+fn proc_state_and_group(stat: &str) -> Option<(char, u32)> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    let mut fields = fields.split_whitespace();
+    let state = fields.next()?.chars().next()?;
+    let group = fields.nth(1)?.parse().ok()?;
+    Some((state, group))
+}
+
+fn signal_tree(pid: u32, force: bool) -> Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("taskkill");
+        command.args(["/PID", &pid.to_string(), "/T"]);
+        if force {
+            command.arg("/F");
+        }
+        command
+    } else {
+        let mut command = Command::new("kill");
+        let signal = if force { "-KILL" } else { "-TERM" };
+        // A negative id signals the whole process group the game leads
+        command.args([signal, "--", &format!("-{}", pid)]);
+        command
+    };
+
+    let status = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| Error::Other(format!("Failed to stop process {}: {}", pid, e)))?;
+    // The processes may have exited in the meantime
+    if !status.success() && tree_alive(pid) {
+        return Err(Error::Other(format!("Failed to stop process {}", pid)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running_record(app_name: &str, pid: u32) -> RunningRecord {
+        RunningRecord {
+            app_name: app_name.to_string(),
+            pid,
+            started_at: Utc::now(),
+            waited: false,
+            stopping: false,
+        }
+    }
+
+    #[test]
+    fn test_records_of_exited_processes_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut alive = running_record("Kiwi", std::process::id());
+        alive.started_at = process_started_at(alive.pid).unwrap_or(alive.started_at);
+        track_in(dir.path(), &alive).unwrap();
+        assert_eq!(running_in(dir.path(), "Kiwi"), Some(alive));
+
+        // Process ids are at most 2^22 on Linux, and far from u32::MAX elsewhere
+        track_in(dir.path(), &running_record("Gone", u32::MAX - 1)).unwrap();
+        assert_eq!(running_in(dir.path(), "Gone"), None);
+        assert!(!record_path(dir.path(), "Gone", u32::MAX - 1).exists());
+    }
+
+    #[test]
+    fn test_process_start_times() {
+        // Command names may hold spaces and parentheses
+        let stat = "4242 (Kiwi (x64) game) S 1 4242 4242 0 -1 4194560 1000 0 0 0 10 5 0 0 20 0 \
+                    12 0 150000 123456789 1000";
+        assert_eq!(
+            proc_start_time(stat, 1_700_000_000),
+            DateTime::from_timestamp(1_700_001_500, 0)
+        );
+        assert_eq!(proc_start_time("4242 (Kiwi) S 1", 1_700_000_000), None);
+
+        let local = ps_start_time("Thu Oct  15 17:59:57 2026\n").unwrap();
+        assert_eq!(
+            local.with_timezone(&Local).naive_local().to_string(),
+            "2026-10-15 17:59:57"
+        );
+        assert_eq!(ps_start_time(""), None);
+
+        let launched = Utc::now();
+        assert!(started_near(
+            launched - chrono::Duration::seconds(1),
+            launched
+        ));
+        assert!(!started_near(
+            launched + chrono::Duration::hours(3),
+            launched
+        ));
+        assert!(!started_near(
+            launched - chrono::Duration::days(2),
+            launched
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_own_process_start_is_found() {
+        let started = process_started_at(std::process::id()).unwrap();
+        assert!(started <= Utc::now());
+        assert!(started > Utc::now() - chrono::Duration::hours(1));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_stale_record_doesnt_block_launch() {
+        let dir = tempfile::tempdir().unwrap();
+        let executable = dir.path().join("Kiwi/Kiwi.exe");
+
+        // A record from before a reboot, whose process id now belongs to another process
+        let mut stale = running_record("Kiwi", std::process::id());
+        stale.started_at = Utc::now() - chrono::Duration::days(3);
+        track_in(dir.path(), &stale).unwrap();
+        assert!(!stale.is_alive());
+        assert_eq!(find_instance_in(dir.path(), "Kiwi", &executable), None);
+        assert!(!record_path(dir.path(), "Kiwi", stale.pid).exists());
+
+        // Stopping it signals nothing
+        terminate(&stale).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_terminate_stops_the_whole_tree() {
+        use std::os::unix::process::CommandExt;
+
+        // The shell quits when asked, leaving a child that ignores SIGTERM behind, like a
+        // launcher leaving the game
+        let mut child = Command::new("sh")
+            .args(["-c", "(trap '' TERM; sleep 60) & sleep 60"])
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let mut record = running_record("Kiwi", pid);
+        record.started_at = process_started_at(pid).unwrap_or(record.started_at);
+        // Reaped as the launch waiting on the game would
+        let waiter = std::thread::spawn(move || child.wait());
+        std::thread::sleep(Duration::from_millis(500));
+        assert!(tree_alive(pid));
+
+        terminate_within(&record, Duration::from_secs(1)).unwrap();
+        assert!(!tree_alive(pid));
+        assert!(waiter.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_proc_state_and_group() {
+        let stat = "4243 (Kiwi (x64) game) Z 1 4242 4242 0 -1 4194560";
+        assert_eq!(proc_state_and_group(stat), Some(('Z', 4242)));
+        assert_eq!(proc_state_and_group("4243 (Kiwi) S"), None);
+    }
+
+    #[test]
+    fn test_instances_are_tracked_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut first = running_record("Kiwi", std::process::id());
+        first.started_at = process_started_at(first.pid).unwrap_or(first.started_at);
+        track_in(dir.path(), &first).unwrap();
+        track_in(dir.path(), &running_record("Kiwi", u32::MAX - 1)).unwrap();
+
+        // The second instance's record doesn't replace the first, and goes once it's gone
+        assert_eq!(instances_in(dir.path(), "Kiwi"), vec![first.clone()]);
+        assert!(!record_path(dir.path(), "Kiwi", u32::MAX - 1).exists());
+
+        untrack_in(dir.path(), "Kiwi", 41);
+        assert_eq!(
+            read_record(dir.path(), "Kiwi", first.pid),
+            Some(first.clone())
+        );
+
+        untrack_in(dir.path(), "Kiwi", first.pid);
+        assert!(read_record(dir.path(), "Kiwi", first.pid).is_none());
+        assert!(!dir.path().join("Kiwi").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_in_listing() {
+        let executable = Path::new("/home/kiwi/Games/Kiwi/Binaries/Kiwi.exe");
+        let listing = "\
+              1 /sbin/init
+            100 rauncher launch Kiwi
+            150 grep Kiwi.exe
+            160 /home/kiwi/Games/Kiwi/Binaries/Kiwi.exe.bak
+            170 /mnt/home/kiwi/Games/Kiwi/Binaries/Kiwi.exe
+            200 wine64-preloader Z:\\home\\kiwi\\Games\\Kiwi\\Binaries\\KIWI.exe -windowed
+            300 /home/kiwi/Games/Kiwi/Binaries/Kiwi.exe
+        ";
+        assert_eq!(find_in_listing(listing, executable, 1), Some(200));
+        assert_eq!(find_in_listing(listing, executable, 200), Some(300));
+        assert_eq!(
+            find_in_listing(listing, Path::new("/home/kiwi/Games/Pyre/Pyre.exe"), 1),
+            None
+        );
+    }
+}
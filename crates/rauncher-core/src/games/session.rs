@@ -0,0 +1,155 @@
+//! Stopping running games, and what happens once a game rauncher waited on or stopped has
+//! exited: the game's `post_exit` command, then the cloud save upload when
+//! `sync_saves_on_exit` is set.
+
+use tokio::process::Command;
+
+use super::running::{self, RunningRecord};
+use super::{GameExit, GameManager, InstalledGame};
+use crate::stats::PlaySession;
+use crate::{Error, Result};
+
+impl GameManager {
+    /// Games rauncher launched that are still running
+    pub fn running_games(&self) -> Result<Vec<RunningRecord>> {
+        running::running_games()
+    }
+
+    /// Stop a running game and everything it started, every instance of it when it was
+    /// launched more than once: it's asked to quit, then killed if it hasn't after a while.
+    /// Its post-exit steps run here unless a rauncher process waiting on it runs them. Returns
+    /// false when the game wasn't running.
+    pub async fn stop_game(&self, address: &str) -> Result<bool> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let records = running::running(&game.app_name)?;
+        for record in &records {
+            self.stop_record(&game, record).await?;
+        }
+        Ok(!records.is_empty())
+    }
+
+    /// Stop the instance of a game running as process `pid`, leaving any other one running
+    pub async fn stop_instance(&self, address: &str, pid: u32) -> Result<bool> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let records = running::running(&game.app_name)?;
+        let Some(record) = records.iter().find(|record| record.pid == pid) else {
+            return Ok(false);
+        };
+        self.stop_record(&game, record).await?;
+        Ok(true)
+    }
+
+    async fn stop_record(&self, game: &InstalledGame, record: &RunningRecord) -> Result<()> {
+        log::info!("Stopping {} (process {})", game.app_title, record.pid);
+        let pid = record.pid;
+        // Marked before any signal, so a rauncher waiting on the game sees a stop, not a crash
+        running::mark_stopping(&game.app_name, pid)?;
+        let stopping = record.clone();
+        tokio::task::spawn_blocking(move || running::terminate(&stopping))
+            .await
+            .map_err(|e| Error::Other(format!("Failed to stop {}: {}", game.app_title, e)))??;
+
+        // The rauncher waiting on the game forgets it once it has seen the exit
+        if !record.waited {
+            running::untrack(&game.app_name, pid);
+            super::record_session(&PlaySession::ended_now(game, record.started_at));
+            let exit = GameExit {
+                code: None,
+                signal: None,
+                crash_report: None,
+            };
+            self.after_exit(game, &exit).await;
+        }
+        Ok(())
+    }
+
+    /// Run the post-exit steps for a game that exited. Failures are logged rather than
+    /// returned, so they don't hide how the game itself ended.
+    pub async fn after_exit(&self, game: &InstalledGame, exit: &GameExit) {
+        let settings = self.config().game_settings(&game.app_name);
+        if let Some(command) = &settings.post_exit {
+            if let Err(e) = run_post_exit(command, game, exit).await {
+                log::warn!("{}", e);
+            }
+        }
+
+        if self.config().sync_saves_on_exit {
+            log::info!("Uploading the cloud saves of {}", game.app_title);
+            if let Err(e) = self.upload_cloud_saves(&game.app_name).await {
+                log::warn!("Failed to upload the saves of {}: {}", game.app_title, e);
+            }
+        }
+    }
+}
+
+/// Run a `post_exit` command through the shell, in the game's install directory
+async fn run_post_exit(command: &str, game: &InstalledGame, exit: &GameExit) -> Result<()> {
+    let mut process = if cfg!(target_os = "windows") {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+    let exit_code = exit.code.map(|code| code.to_string()).unwrap_or_default();
+
+    log::info!(
+        "Running the post-exit command of {}: {}",
+        game.app_title,
+        command
+    );
+    let status = process
+        .current_dir(&game.install_path)
+        .env("RAUNCHER_APP_NAME", &game.app_name)
+        .env("RAUNCHER_EXIT_CODE", exit_code)
+        .status()
+        .await
+        .map_err(|e| Error::Other(format!("Failed to run the post-exit command: {}", e)))?;
+
+    if !status.success() {
+        return Err(Error::Other(format!(
+            "The post-exit command of {} failed ({})",
+            game.app_title, status
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_post_exit_gets_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let game = InstalledGame {
+            app_name: "Kiwi".to_string(),
+            app_title: "Kiwi".to_string(),
+            app_version: "1.0".to_string(),
+            install_path: dir.path().to_path_buf(),
+            executable: "Kiwi.exe".to_string(),
+            pinned: false,
+            provider: crate::providers::EPIC.to_string(),
+            install_size: 0,
+            download_size: 0,
+            installed_at: None,
+            last_played: None,
+            volume: None,
+            save_path_template: None,
+        };
+        let exit = GameExit {
+            code: Some(3),
+            signal: None,
+            crash_report: None,
+        };
+
+        let command = "echo \"$RAUNCHER_APP_NAME $RAUNCHER_EXIT_CODE\" > exited.txt";
+        run_post_exit(command, &game, &exit).await.unwrap();
+        let written = std::fs::read_to_string(dir.path().join("exited.txt")).unwrap();
+        assert_eq!(written.trim(), "Kiwi 3");
+
+        assert!(run_post_exit("exit 1", &game, &exit).await.is_err());
+    }
+}
@@ -0,0 +1,288 @@
+//! Prebuilt DXVK and VKD3D-Proton shader caches. Games run through Wine or Proton get their
+//! own cache directory, and a cache published by the community or the game's store can be
+//! downloaded into it so the first run doesn't stutter while pipelines are compiled.
+
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use super::{GameManager, InstalledGame, LaunchCommand};
+use crate::config::Config;
+use crate::logs::transcript::RecordedSend;
+use crate::{Error, Result};
+
+/// Caches can run to hundreds of megabytes, so allow far longer than an API call
+const DOWNLOAD_TIMEOUT_SECS: u64 = 600;
+
+/// Where the last download came from, kept inside the game's cache directory
+const SOURCE_FILE: &str = ".source.json";
+
+/// Environment variables pointing the translation layers at a cache directory
+const CACHE_VARIABLES: [&str; 2] = ["DXVK_STATE_CACHE_PATH", "VKD3D_SHADER_CACHE_PATH"];
+
+/// Outcome of refreshing a game's shader cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderCacheUpdate {
+    /// A new cache was installed; `bytes` is the size of the download
+    Installed { bytes: u64 },
+    /// The source has nothing newer than the installed cache
+    UpToDate,
+    /// The source has no cache for this game or version
+    Unavailable,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheSource {
+    url: String,
+    etag: Option<String>,
+}
+
+impl GameManager {
+    /// Download the shader cache of an installed game from its configured source, unless the
+    /// installed one is still current
+    pub async fn update_shader_cache(&self, address: &str) -> Result<ShaderCacheUpdate> {
+        let game = InstalledGame::resolve(&self.config(), address)?;
+        let url = source_url(&self.config(), &game).ok_or_else(|| {
+            Error::Config(format!(
+                "No shader cache source for {} (set shader_cache_url)",
+                game.app_name
+            ))
+        })?;
+        self.require_online()?;
+
+        download(&url, &Config::shader_cache_dir()?.join(&game.app_name)).await
+    }
+
+    /// Refresh a game's shader cache after an update, if a source is configured. The update
+    /// itself already succeeded, so failing here only warns.
+    pub(super) async fn refresh_shader_cache(&self, game: &InstalledGame) {
+        if source_url(&self.config(), game).is_none() {
+            return;
+        }
+
+        match self.update_shader_cache(&game.id()).await {
+            Ok(ShaderCacheUpdate::Installed { .. }) => {
+                log::info!("Shader cache updated for {}", game.app_title)
+            }
+            Ok(_) => log::debug!("No new shader cache for {}", game.app_name),
+            Err(e) => log::warn!("Failed to update the shader cache: {}", e),
+        }
+    }
+}
+
+/// The game's shader cache source, with its placeholders filled in
+fn source_url(config: &Config, game: &InstalledGame) -> Option<String> {
+    let template = config
+        .game_settings(&game.app_name)
+        .shader_cache_url
+        .or_else(|| config.shader_cache_url.clone())?;
+
+    Some(
+        template
+            .replace("{app_name}", &game.app_name)
+            .replace("{version}", &game.app_version)
+            .replace("{provider}", &game.provider),
+    )
+}
+
+async fn download(url: &str, dir: &Path) -> Result<ShaderCacheUpdate> {
+    let source_path = dir.join(SOURCE_FILE);
+    let previous: CacheSource = fs::read_to_string(&source_path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
+
+    let client = Client::builder()
+        .user_agent("rauncher/0.1.0")
+        .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .build()?;
+    let mut request = client.get(url);
+    if let Some(etag) = previous.etag.as_ref().filter(|_| previous.url == url) {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+
+    let response = request.send_recorded().await?;
+    match response.status() {
+        StatusCode::NOT_MODIFIED => return Ok(ShaderCacheUpdate::UpToDate),
+        StatusCode::NOT_FOUND => return Ok(ShaderCacheUpdate::Unavailable),
+        status if !status.is_success() => {
+            return Err(Error::Api(format!(
+                "Failed to download shader cache from {}: {}",
+                url, status
+            )))
+        }
+        _ => {}
+    }
+
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let file_name = response
+        .url()
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("shader_cache")
+        .to_string();
+    let bytes = response.bytes().await?;
+
+    install(&bytes, &file_name, dir)?;
+
+    let source = CacheSource {
+        url: url.to_string(),
+        etag,
+    };
+    fs::write(&source_path, serde_json::to_string(&source)?)?;
+    log::debug!("Installed shader cache {} into {:?}", file_name, dir);
+
+    Ok(ShaderCacheUpdate::Installed {
+        bytes: bytes.len() as u64,
+    })
+}
+
+/// Unpack a downloaded archive into the cache directory, or store a single cache file (such
+/// as a `.dxvk-cache`) there as is. Files the games wrote themselves are left alone.
+fn install(bytes: &[u8], file_name: &str, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let staged = dir.join(format!(".download-{}", file_name));
+    fs::write(&staged, bytes)?;
+
+    if crate::storage::extract_archive(&staged, dir)? {
+        fs::remove_file(&staged)?;
+    } else {
+        fs::rename(&staged, dir.join(file_name))?;
+    }
+    crate::logs::transcript::touched("wrote", dir);
+
+    Ok(())
+}
+
+/// Point DXVK and VKD3D-Proton at the game's cache directory, unless the game's own
+/// environment settings already chose a location
+pub(super) fn use_cache_dir(command: &mut LaunchCommand, dir: &Path) {
+    for variable in CACHE_VARIABLES {
+        if !command.env.iter().any(|(key, _)| key == variable) {
+            command
+                .env
+                .push((variable.to_string(), OsString::from(dir)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameSettings;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::path::PathBuf;
+
+    fn game() -> InstalledGame {
+        InstalledGame {
+            app_name: "Kiwi".to_string(),
+            app_title: "Hades II".to_string(),
+            app_version: "1.2".to_string(),
+            install_path: PathBuf::from("/games/Kiwi"),
+            executable: "Hades2.exe".to_string(),
+            pinned: false,
+            provider: "epic".to_string(),
+            install_size: 0,
+            download_size: 0,
+            installed_at: None,
+            last_played: None,
+            volume: None,
+            save_path_template: None,
+        }
+    }
+
+    #[test]
+    fn test_source_url_prefers_game_setting() {
+        let mut config = Config {
+            shader_cache_url: Some(
+                "https://caches.example/{provider}/{app_name}/{version}.tar.gz".to_string(),
+            ),
+            ..Config::default()
+        };
+        assert_eq!(
+            source_url(&config, &game()).as_deref(),
+            Some("https://caches.example/epic/Kiwi/1.2.tar.gz")
+        );
+
+        config.games.insert(
+            "Kiwi".to_string(),
+            GameSettings {
+                shader_cache_url: Some("https://mirror.example/Hades2.dxvk-cache".to_string()),
+                ..GameSettings::default()
+            },
+        );
+        assert_eq!(
+            source_url(&config, &game()).as_deref(),
+            Some("https://mirror.example/Hades2.dxvk-cache")
+        );
+
+        assert_eq!(source_url(&Config::default(), &game()), None);
+    }
+
+    #[test]
+    fn test_install_unpacks_archives_and_keeps_single_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("local.dxvk-cache"), "built locally").unwrap();
+
+        install(b"cache", "Hades2.dxvk-cache", dir.path()).unwrap();
+        assert_eq!(
+            fs::read(dir.path().join("Hades2.dxvk-cache")).unwrap(),
+            b"cache"
+        );
+
+        let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(6);
+        header.set_mode(0o644);
+        header.set_cksum();
+        archive
+            .append_data(&mut header, "vkd3d-proton.cache", &b"vkd3d!"[..])
+            .unwrap();
+        let bytes = archive.into_inner().unwrap().finish().unwrap();
+
+        install(&bytes, "caches.tar.gz", dir.path()).unwrap();
+        assert_eq!(
+            fs::read(dir.path().join("vkd3d-proton.cache")).unwrap(),
+            b"vkd3d!"
+        );
+        assert!(!dir.path().join(".download-caches.tar.gz").exists());
+        assert!(dir.path().join("local.dxvk-cache").exists());
+    }
+
+    #[test]
+    fn test_cache_dir_respects_game_env() {
+        let mut command = LaunchCommand {
+            program: OsString::from("wine"),
+            args: Vec::new(),
+            env: vec![(
+                "DXVK_STATE_CACHE_PATH".to_string(),
+                OsString::from("/elsewhere"),
+            )],
+            working_dir: PathBuf::from("/games/Kiwi"),
+            runner: None,
+        };
+        use_cache_dir(&mut command, Path::new("/cache/Kiwi"));
+
+        let value = |key: &str| {
+            command
+                .env
+                .iter()
+                .filter(|(k, _)| k == key)
+                .map(|(_, v)| v.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(value("DXVK_STATE_CACHE_PATH"), vec!["/elsewhere"]);
+        assert_eq!(value("VKD3D_SHADER_CACHE_PATH"), vec!["/cache/Kiwi"]);
+    }
+}
@@ -0,0 +1,198 @@
+//! Browsing the store from the launcher, with prices for the logged in account's country,
+//! and keeping an eye on the wishlist's prices
+
+use super::{offline, GameManager};
+use crate::api::{FreeGame, StoreCache, StoreOffer, StorePage, StoreQuery};
+use crate::notify::Notification;
+use crate::wishlist::{Wishlist, WishlistEvent};
+use crate::{Error, Result};
+
+/// Country whose prices are shown without a login, or when the account has none set
+pub const DEFAULT_STORE_COUNTRY: &str = "US";
+
+impl GameManager {
+    /// A page of store results. Answers come from the cache while fresh, and from the cache
+    /// regardless (marked stale) when Epic can't be reached.
+    pub async fn browse_store(&self, query: &StoreQuery) -> Result<StorePage> {
+        let cache = StoreCache::new()?;
+        let country = self.store_country(&cache).await;
+        if let Some(page) = cache.fresh(query, &country) {
+            return Ok(page);
+        }
+
+        let fetched = async {
+            self.require_online()?;
+            let mut page = self.shared.client.search_store(query, &country).await?;
+            if query.free {
+                page.giveaways = self.current_giveaways(&country).await;
+            }
+            Ok(page)
+        }
+        .await;
+
+        match fetched {
+            Ok(page) => {
+                if let Err(e) = cache.put(query, &page) {
+                    log::warn!("Failed to cache store results: {}", e);
+                }
+                Ok(page)
+            }
+            Err(e) if offline::is_network_error(&e) => {
+                let mut page = cache.get(query, &country).ok_or(e)?;
+                page.stale = true;
+                Ok(page)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wishlist the store offer best matching `keywords`: the one titled exactly so, or else
+    /// the most relevant. Returns the offer and whether it wasn't wishlisted before.
+    pub async fn wishlist_add(&self, keywords: &str) -> Result<(StoreOffer, bool)> {
+        let query = StoreQuery {
+            keywords: keywords.trim().to_string(),
+            ..StoreQuery::default()
+        };
+        let page = self.browse_store(&query).await?;
+        let mut offers = page.offers.into_iter();
+        let first = offers.next();
+        let offer = offers
+            .find(|offer| offer.title.eq_ignore_ascii_case(keywords.trim()))
+            .or(first)
+            .ok_or_else(|| Error::Other(format!("No store offer matches '{}'", keywords)))?;
+
+        let mut wishlist = Wishlist::load()?;
+        let added = wishlist.add(&offer);
+        wishlist.save()?;
+        Ok((offer, added))
+    }
+
+    /// Look up the current price of every wishlisted offer, and whether it's given away or
+    /// owned by now. Sales and giveaways not seen before are notified and returned.
+    pub async fn check_wishlist(&self) -> Result<Vec<WishlistEvent>> {
+        let mut wishlist = Wishlist::load()?;
+        if wishlist.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.require_online()?;
+
+        let cache = StoreCache::new()?;
+        let country = self.store_country(&cache).await;
+        let giveaways = self.current_giveaways(&country).await;
+        let library = match self.list_library().await {
+            Ok(games) => Some(games),
+            Err(e) => {
+                log::debug!("Checking the wishlist without the library: {}", e);
+                None
+            }
+        };
+
+        let items: Vec<_> = wishlist.items().into_iter().cloned().collect();
+        let mut events = Vec::new();
+        for item in items {
+            let query = StoreQuery {
+                keywords: item.title.clone(),
+                ..StoreQuery::default()
+            };
+            let page = self.shared.client.search_store(&query, &country).await?;
+            let offer = page
+                .offers
+                .iter()
+                .find(|offer| offer.namespace == item.namespace && offer.id == item.offer_id);
+            let giveaway = giveaways
+                .iter()
+                .any(|game| game.title.eq_ignore_ascii_case(&item.title));
+            let owned = library.as_ref().map_or(item.owned, |games| {
+                games
+                    .iter()
+                    .any(|game| game.app_title.eq_ignore_ascii_case(&item.title))
+            });
+            events.extend(wishlist.record(&item.key(), offer, giveaway, owned));
+        }
+        wishlist.save()?;
+
+        for event in &events {
+            self.notify(match event.clone() {
+                WishlistEvent::OnSale {
+                    title,
+                    discount,
+                    price,
+                } => Notification::WishlistSale {
+                    title,
+                    discount,
+                    price,
+                },
+                WishlistEvent::Free { title } => Notification::WishlistFree { title },
+            });
+        }
+
+        Ok(events)
+    }
+
+    /// Games given away right now that are in the library, i.e. already claimed
+    pub async fn claimed_giveaways(&self) -> Result<Vec<FreeGame>> {
+        self.require_online()?;
+        let cache = StoreCache::new()?;
+        let country = self.store_country(&cache).await;
+        let giveaways = self.current_giveaways(&country).await;
+        if giveaways.is_empty() {
+            return Ok(giveaways);
+        }
+
+        let library = self.list_library().await?;
+        Ok(giveaways
+            .into_iter()
+            .filter(|giveaway| {
+                library
+                    .iter()
+                    .any(|game| game.app_title.eq_ignore_ascii_case(&giveaway.title))
+            })
+            .collect())
+    }
+
+    /// The account's country, looked up once per account and remembered in the cache
+    async fn store_country(&self, cache: &StoreCache) -> String {
+        if self.offline {
+            return DEFAULT_STORE_COUNTRY.to_string();
+        }
+        let token = match self.ensure_valid_token().await {
+            Ok(token) => token,
+            Err(e) => {
+                log::debug!("Showing {} store prices: {}", DEFAULT_STORE_COUNTRY, e);
+                return DEFAULT_STORE_COUNTRY.to_string();
+            }
+        };
+
+        if let Some(country) = cache.country(&token.account_id) {
+            return country;
+        }
+        match self.shared.client.account_country(&token).await {
+            Ok(Some(country)) => {
+                if let Err(e) = cache.set_country(&token.account_id, &country) {
+                    log::debug!("Failed to remember the account's country: {}", e);
+                }
+                country
+            }
+            Ok(None) => DEFAULT_STORE_COUNTRY.to_string(),
+            Err(e) => {
+                log::debug!("Showing {} store prices: {}", DEFAULT_STORE_COUNTRY, e);
+                DEFAULT_STORE_COUNTRY.to_string()
+            }
+        }
+    }
+
+    /// Games given away right now; the store listing still shows without them
+    async fn current_giveaways(&self, country: &str) -> Vec<FreeGame> {
+        let now = chrono::Utc::now();
+        match self.shared.client.get_free_games(country).await {
+            Ok(games) => games
+                .into_iter()
+                .filter(|game| game.starts_at <= now && now < game.ends_at)
+                .collect(),
+            Err(e) => {
+                log::warn!("Leaving the current giveaways out of the store: {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
@@ -0,0 +1,731 @@
+//! Checking an install against its manifest: files that are missing, differ from the build,
+//! or aren't part of it at all (leftover mods, stray saves). The result can be exported as a
+//! JSON or CSV report, and the extra files deleted to get back to a vanilla install.
+
+use chrono::{DateTime, Utc};
+use rayon::prelude::*;
+use serde::Serialize;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use super::{GameManager, InstallProgress, InstalledGame, FALLBACK_SAVE_DIR};
+use crate::api::{FileManifest, GameManifest};
+use crate::config::GameSettings;
+use crate::csv::csv_field;
+use crate::storage::{self, Removal};
+use crate::{Error, Result};
+
+/// Names at the top of an install directory that aren't game files: rauncher's own markers,
+/// and the Epic Games Launcher's metadata in games imported from it
+const NOT_GAME_FILES: [&str; 2] = [".rauncher", ".egstore"];
+
+/// Size of the blocks files are read and hashed in
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Files at least this large are read on a thread of their own, a few blocks ahead of the
+/// hashing, so the disk doesn't sit idle while a block is hashed
+const READ_AHEAD_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+/// Blocks read ahead of the one being hashed
+const READ_AHEAD_BLOCKS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    Ok,
+    /// On disk, but not the size or hash the manifest has
+    Mismatched,
+    Missing,
+    /// On disk, but not in the manifest
+    Extra,
+}
+
+impl FileStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Mismatched => "mismatched",
+            Self::Missing => "missing",
+            Self::Extra => "extra",
+        }
+    }
+}
+
+/// One file of the install, as the manifest has it and as it is on disk. Hashes are hex, in
+/// the algorithm the store uses (SHA-1 for Epic, SHA-256 for Amazon); stores whose manifests
+/// carry no hash are checked by size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileCheck {
+    pub path: String,
+    pub status: FileStatus,
+    pub expected_size: Option<u64>,
+    pub actual_size: Option<u64>,
+    pub expected_hash: Option<String>,
+    pub actual_hash: Option<String>,
+}
+
+/// The files of an install that don't match its manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    pub app_name: String,
+    pub version: String,
+    /// Version of the store's manifest the install was compared with
+    pub manifest_version: String,
+    pub checked_at: DateTime<Utc>,
+    /// Files checked, the good ones included
+    pub files_checked: usize,
+    /// Mismatched, missing and extra files, by path
+    pub problems: Vec<FileCheck>,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    pub fn count(&self, status: FileStatus) -> usize {
+        self.problems.iter().filter(|f| f.status == status).count()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// One row per problem file, with a header
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            String::from("path,status,expected_size,actual_size,expected_hash,actual_hash\n");
+        for file in &self.problems {
+            let size = |size: Option<u64>| size.map(|s| s.to_string()).unwrap_or_default();
+            let row = [
+                csv_field(&file.path),
+                file.status.as_str().to_string(),
+                size(file.expected_size),
+                size(file.actual_size),
+                file.expected_hash.clone().unwrap_or_default(),
+                file.actual_hash.clone().unwrap_or_default(),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+impl GameManager {
+    /// Compare an installed game with the manifest of its installed build. Reads every file,
+    /// so it takes about as long as copying the game; files are hashed `verify_workers` at a
+    /// time, and each one checked is reported as a `FileVerified` event.
+    pub async fn verify_game(&self, address: &str) -> Result<VerifyReport> {
+        let installed = InstalledGame::resolve(&self.config(), address)?;
+        let token = self.stored_token(&installed.provider)?;
+        let manifest = self
+            .provider(&installed.provider)?
+            .download_manifest(&token, &installed.app_name)
+            .await?;
+        let manifest_version = manifest.app_version.clone();
+        if manifest_version != installed.app_version {
+            log::warn!(
+                "{} is at {} but the store's manifest is for {}; files changed by the update \
+                 will show as mismatched",
+                installed.app_name,
+                installed.app_version,
+                manifest_version
+            );
+        }
+
+        let install_path = installed.install_path.clone();
+        let save_dirs = self.save_dirs(&installed)?;
+        let config = self.config();
+        let settings = config.game_settings(&installed.app_name);
+        let workers = config.verify_workers;
+        let manager = self.clone();
+        let app_name = installed.app_name.clone();
+        let (files_checked, problems) = tokio::task::spawn_blocking(move || {
+            let files_done = AtomicUsize::new(0);
+            let bytes_done = AtomicU64::new(0);
+            compare(
+                &manifest,
+                &install_path,
+                &settings,
+                &save_dirs,
+                workers,
+                &|started: &Started| {
+                    manager.report(InstallProgress::VerifyStarted {
+                        app_name: app_name.clone(),
+                        files: started.files,
+                        bytes: started.bytes,
+                    })
+                },
+                &|filename: &str, status: FileStatus, size: u64| {
+                    manager.report(InstallProgress::FileVerified {
+                        app_name: app_name.clone(),
+                        index: files_done.fetch_add(1, Ordering::Relaxed) + 1,
+                        filename: filename.to_string(),
+                        status,
+                        bytes_done: bytes_done.fetch_add(size, Ordering::Relaxed) + size,
+                    })
+                },
+            )
+        })
+        .await
+        .map_err(|e| Error::Other(format!("Verification failed: {}", e)))??;
+
+        Ok(VerifyReport {
+            app_name: installed.app_name,
+            version: installed.app_version,
+            manifest_version,
+            checked_at: Utc::now(),
+            files_checked,
+            problems,
+        })
+    }
+
+    /// Folders that hold the player's files rather than the game's, so what's in them is
+    /// never extra: the `saves` folder cloud saves fall back to, the `save_path` setting and
+    /// the store's save location
+    fn save_dirs(&self, game: &InstalledGame) -> Result<Vec<PathBuf>> {
+        // A runner gone missing only matters to saves inside a Wine prefix
+        let settings = self
+            .launch_settings(&game.app_name)
+            .unwrap_or_else(|_| self.config().game_settings(&game.app_name));
+        let mut dirs = vec![game.install_path.join(FALLBACK_SAVE_DIR)];
+        if let Some(path) = &settings.save_path {
+            dirs.push(game.install_path.join(path));
+        }
+        dirs.extend(self.store_save_dir(game, &settings)?);
+        Ok(dirs)
+    }
+
+    /// Get rid of the files `report` found that aren't part of the game, and the folders left
+    /// empty by it. Returns the bytes freed.
+    ///
+    /// Refused when the install was compared with the manifest of another version, whose file
+    /// list may not have the installed build's files.
+    pub fn delete_extra_files(&self, report: &VerifyReport, removal: Removal) -> Result<u64> {
+        let installed = InstalledGame::load(&self.config(), &report.app_name)?;
+        delete_extra(report, &installed.install_path, removal)
+    }
+}
+
+fn delete_extra(report: &VerifyReport, install_path: &Path, removal: Removal) -> Result<u64> {
+    if report.manifest_version != report.version {
+        return Err(Error::Other(format!(
+            "{} is at {} but was checked against the manifest of {}; update it or verify \
+             against its own build before deleting extra files",
+            report.app_name, report.version, report.manifest_version
+        )));
+    }
+
+    let mut freed = 0;
+    for file in &report.problems {
+        if file.status != FileStatus::Extra {
+            continue;
+        }
+        let path = install_path.join(&file.path);
+        storage::remove_file(&path, removal)?;
+        freed += file.actual_size.unwrap_or(0);
+
+        // Folders only the extra files lived in
+        for dir in path.ancestors().skip(1) {
+            if dir == install_path || fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+    Ok(freed)
+}
+
+/// What a verification is about to check
+struct Started {
+    files: usize,
+    bytes: u64,
+}
+
+/// Check every file in `manifest` against `install_path`, `workers` files at a time (one per
+/// CPU core when 0), then look for files it doesn't have. Protected paths and what's in
+/// `save_dirs` are left out.
+/// `on_started` is told what will be checked and `on_checked` about every file once it is.
+/// Returns how many files were checked and the ones that don't match.
+fn compare(
+    manifest: &GameManifest,
+    install_path: &Path,
+    settings: &GameSettings,
+    save_dirs: &[PathBuf],
+    workers: usize,
+    on_started: &(dyn Fn(&Started) + Sync),
+    on_checked: &(dyn Fn(&str, FileStatus, u64) + Sync),
+) -> Result<(usize, Vec<FileCheck>)> {
+    let mut files: Vec<(&FileManifest, u64)> = manifest
+        .file_list
+        .iter()
+        .filter(|file| !settings.is_protected(&file.filename))
+        .map(|file| {
+            (
+                file,
+                file.file_chunk_parts.iter().map(|part| part.size).sum(),
+            )
+        })
+        .collect();
+    // The largest files first, so one isn't left hashing alone at the end
+    files.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    on_started(&Started {
+        files: files.len(),
+        bytes: files.iter().map(|(_, size)| size).sum(),
+    });
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(workers)
+        .thread_name(|i| format!("verify-{}", i))
+        .build()
+        .map_err(|e| Error::Other(format!("Couldn't start verification: {}", e)))?;
+    let checks = pool.install(|| {
+        files
+            .par_iter()
+            .map(|(file, expected_size)| {
+                let check = check_file(file, *expected_size, install_path)?;
+                let status = check.as_ref().map_or(FileStatus::Ok, |check| check.status);
+                on_checked(&file.filename, status, *expected_size);
+                Ok(check)
+            })
+            .collect::<Result<Vec<_>>>()
+    })?;
+    let checked = files.len();
+    let mut problems: Vec<FileCheck> = checks.into_iter().flatten().collect();
+
+    // Where names differ only in case, the manifest's file is the one on disk
+    let case_insensitive = is_case_insensitive(install_path);
+    let known: HashSet<String> = manifest
+        .file_list
+        .iter()
+        .map(|file| name_key(&file.filename, case_insensitive))
+        .collect();
+    let mut on_disk = Vec::new();
+    list_files(install_path, install_path, save_dirs, &mut on_disk)?;
+    for (path, size) in on_disk {
+        if !known.contains(&name_key(&path, case_insensitive)) && !settings.is_protected(&path) {
+            problems.push(FileCheck {
+                path,
+                status: FileStatus::Extra,
+                expected_size: None,
+                actual_size: Some(size),
+                expected_hash: None,
+                actual_hash: None,
+            });
+        }
+    }
+
+    problems.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok((checked, problems))
+}
+
+/// Whether the filesystem holding `dir` ignores case in names, found by looking `dir` up with
+/// the case of its name flipped. Names without letters give no answer, so the platform's
+/// usual filesystems are assumed.
+fn is_case_insensitive(dir: &Path) -> bool {
+    let (Some(parent), Some(name)) = (dir.parent(), dir.file_name()) else {
+        return cfg!(any(windows, target_os = "macos"));
+    };
+    let name = name.to_string_lossy();
+    let flipped: String = name
+        .chars()
+        .map(|c| {
+            if c.is_uppercase() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                c.to_uppercase().next().unwrap_or(c)
+            }
+        })
+        .collect();
+    if flipped == name {
+        return cfg!(any(windows, target_os = "macos"));
+    }
+    same_file(dir, &parent.join(flipped))
+}
+
+/// Whether both paths lead to the same file or directory
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// Whether both paths lead to the same file or directory
+#[cfg(not(unix))]
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase(),
+        _ => false,
+    }
+}
+
+/// Whether `a` and `b` are the same directory, however either is spelled
+fn same_dir(a: &Path, b: &Path) -> bool {
+    a == b || same_file(a, b)
+}
+
+/// `path` as it is compared with the manifest's file names
+fn name_key(path: &str, case_insensitive: bool) -> String {
+    if case_insensitive {
+        path.to_lowercase()
+    } else {
+        path.to_string()
+    }
+}
+
+/// Check one file of the manifest, returning what's wrong with it if anything
+pub(super) fn check_file(
+    file: &FileManifest,
+    expected_size: u64,
+    install_path: &Path,
+) -> Result<Option<FileCheck>> {
+    let expected_hash = (!file.file_hash.is_empty()).then(|| hex(&file.file_hash));
+    let path = install_path.join(&file.filename);
+
+    let actual_size = fs::metadata(&path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len());
+    let actual_hash = match (actual_size, file.file_hash.len()) {
+        // A file of the wrong size is mismatched whatever it holds
+        (Some(size), _) if size != expected_size => None,
+        (Some(size), 20) => Some(hash_file::<Sha1>(&path, size)?),
+        (Some(size), 32) => Some(hash_file::<Sha256>(&path, size)?),
+        _ => None,
+    };
+
+    let status = match actual_size {
+        None => FileStatus::Missing,
+        Some(size) if size != expected_size => FileStatus::Mismatched,
+        Some(_) if actual_hash.is_some() && actual_hash != expected_hash => FileStatus::Mismatched,
+        Some(_) => return Ok(None),
+    };
+    Ok(Some(FileCheck {
+        path: file.filename.clone(),
+        status,
+        expected_size: Some(expected_size),
+        actual_size,
+        expected_hash,
+        actual_hash,
+    }))
+}
+
+/// Every file under `dir`, as a `/`-separated path relative to `root` with its size. What's
+/// in `skip` is left out, and symlinks aren't followed.
+fn list_files(
+    root: &Path,
+    dir: &Path,
+    skip: &[PathBuf],
+    files: &mut Vec<(String, u64)>,
+) -> Result<()> {
+    // Saves kept in the install directory itself leave nothing to tell apart
+    if skip.iter().any(|skipped| same_dir(skipped, dir)) {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let file_type = entry.file_type()?;
+        if dir == root {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if NOT_GAME_FILES.iter().any(|prefix| name.starts_with(prefix)) {
+                continue;
+            }
+        }
+
+        if file_type.is_dir() {
+            list_files(root, &path, skip, files)?;
+        } else {
+            let relative: Vec<_> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            files.push((relative.join("/"), entry.metadata()?.len()));
+        }
+    }
+    Ok(())
+}
+
+fn hash_file<D: Digest>(path: &Path, size: u64) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = D::new();
+    if size < READ_AHEAD_THRESHOLD {
+        let mut buffer = vec![0u8; BLOCK_SIZE];
+        loop {
+            match read_block(&mut file, &mut buffer)? {
+                0 => break,
+                n => hasher.update(&buffer[..n]),
+            }
+        }
+        return Ok(hex(&hasher.finalize()));
+    }
+
+    std::thread::scope(|scope| {
+        let (full_sender, full) = mpsc::sync_channel(READ_AHEAD_BLOCKS);
+        let (empty_sender, empty) = mpsc::channel();
+        for _ in 0..=READ_AHEAD_BLOCKS {
+            let _ = empty_sender.send(vec![0u8; BLOCK_SIZE]);
+        }
+
+        // Stops at the end of the file, or once the hashing below gives up
+        scope.spawn(move || {
+            for mut buffer in empty {
+                match read_block(&mut file, &mut buffer) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if full_sender.send(Ok((buffer, n))).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = full_sender.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
+
+        for block in full {
+            let (buffer, n) = block?;
+            hasher.update(&buffer[..n]);
+            let _ = empty_sender.send(buffer);
+        }
+        Ok(hex(&hasher.finalize()))
+    })
+}
+
+/// Fill `buffer` as far as the file goes, returning how much was read; 0 at the end
+fn read_block(file: &mut File, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match file.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ChunkPart, FileManifest};
+
+    fn file(filename: &str, data: &[u8]) -> FileManifest {
+        FileManifest {
+            filename: filename.to_string(),
+            file_hash: Sha1::digest(data).to_vec(),
+            file_chunk_parts: vec![ChunkPart {
+                guid: String::new(),
+                offset: 0,
+                size: data.len() as u64,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_compare_finds_mismatched_missing_and_extra_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("Content/Mods")).unwrap();
+        fs::write(dir.path().join("Game.exe"), b"game").unwrap();
+        fs::write(dir.path().join("Content/data.pak"), b"dat4").unwrap();
+        fs::write(dir.path().join("Content/Mods/cheat.pak"), b"mod").unwrap();
+        fs::write(dir.path().join(".rauncher-partial"), b"").unwrap();
+        fs::create_dir_all(dir.path().join("Config")).unwrap();
+        fs::write(dir.path().join("Config/user.ini"), b"tweaked").unwrap();
+        fs::write(dir.path().join("Config/extra.ini"), b"mine").unwrap();
+
+        let manifest = GameManifest {
+            file_list: vec![
+                file("Game.exe", b"game"),
+                file("Content/data.pak", b"data"),
+                file("Content/music.pak", b"music"),
+                file("Config/user.ini", b"default"),
+            ],
+            ..GameManifest::default()
+        };
+        let settings = GameSettings {
+            protected: vec!["Config".to_string()],
+            ..GameSettings::default()
+        };
+        let started = std::sync::Mutex::new(None);
+        let reported = std::sync::Mutex::new(Vec::new());
+        let (checked, problems) = compare(
+            &manifest,
+            dir.path(),
+            &settings,
+            &[],
+            2,
+            &|s: &Started| *started.lock().unwrap() = Some((s.files, s.bytes)),
+            &|filename: &str, status: FileStatus, _| {
+                reported
+                    .lock()
+                    .unwrap()
+                    .push((filename.to_string(), status))
+            },
+        )
+        .unwrap();
+
+        assert_eq!(checked, 3);
+        assert_eq!(*started.lock().unwrap(), Some((3, 13)));
+        let mut reported = reported.into_inner().unwrap();
+        reported.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            reported,
+            vec![
+                ("Content/data.pak".to_string(), FileStatus::Mismatched),
+                ("Content/music.pak".to_string(), FileStatus::Missing),
+                ("Game.exe".to_string(), FileStatus::Ok),
+            ]
+        );
+        let statuses: Vec<_> = problems
+            .iter()
+            .map(|f| (f.path.as_str(), f.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("Content/Mods/cheat.pak", FileStatus::Extra),
+                ("Content/data.pak", FileStatus::Mismatched),
+                ("Content/music.pak", FileStatus::Missing),
+            ]
+        );
+        assert_eq!(
+            problems[1].expected_hash.as_deref(),
+            Some(hex(&Sha1::digest(b"data")).as_str())
+        );
+        assert_eq!(
+            problems[1].actual_hash.as_deref(),
+            Some(hex(&Sha1::digest(b"dat4")).as_str())
+        );
+
+        let report = VerifyReport {
+            app_name: "Kiwi".to_string(),
+            version: "1.0".to_string(),
+            manifest_version: "1.0".to_string(),
+            checked_at: Utc::now(),
+            files_checked: checked,
+            problems,
+        };
+        let csv = report.to_csv();
+        assert!(csv.starts_with("path,status,"));
+        assert!(csv.contains("\nContent/music.pak,missing,5,,"));
+    }
+
+    #[test]
+    fn test_delete_extra_keeps_saves_and_refuses_other_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("Mods")).unwrap();
+        fs::create_dir_all(dir.path().join(FALLBACK_SAVE_DIR)).unwrap();
+        fs::create_dir_all(dir.path().join("Game/Saved/SaveGames")).unwrap();
+        fs::write(dir.path().join("Game.exe"), b"game").unwrap();
+        fs::write(dir.path().join("Mods/cheat.pak"), b"mod").unwrap();
+        fs::write(dir.path().join("saves/slot1.sav"), b"progress").unwrap();
+        fs::write(dir.path().join("Game/Saved/SaveGames/0.sav"), b"progress").unwrap();
+        fs::write(dir.path().join("savestate.bin"), b"cheat").unwrap();
+        let save_dirs = [
+            dir.path().join(FALLBACK_SAVE_DIR),
+            dir.path().join("Game/Saved/SaveGames"),
+        ];
+
+        let manifest = GameManifest {
+            file_list: vec![file("Game.exe", b"game")],
+            ..GameManifest::default()
+        };
+        let (checked, problems) = compare(
+            &manifest,
+            dir.path(),
+            &GameSettings::default(),
+            &save_dirs,
+            1,
+            &|_: &Started| {},
+            &|_: &str, _: FileStatus, _| {},
+        )
+        .unwrap();
+        let extra: Vec<_> = problems.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(extra, vec!["Mods/cheat.pak", "savestate.bin"]);
+
+        let mut report = VerifyReport {
+            app_name: "Kiwi".to_string(),
+            version: "1.0".to_string(),
+            manifest_version: "2.0".to_string(),
+            checked_at: Utc::now(),
+            files_checked: checked,
+            problems,
+        };
+        assert!(delete_extra(&report, dir.path(), Removal::Permanent).is_err());
+        assert!(dir.path().join("Mods/cheat.pak").exists());
+
+        report.manifest_version = "1.0".to_string();
+        assert_eq!(
+            delete_extra(&report, dir.path(), Removal::Permanent).unwrap(),
+            8
+        );
+        assert!(!dir.path().join("Mods").exists());
+        assert!(!dir.path().join("savestate.bin").exists());
+        assert!(dir.path().join("saves/slot1.sav").exists());
+        assert!(dir.path().join("Game/Saved/SaveGames/0.sav").exists());
+        assert!(dir.path().join("Game.exe").exists());
+    }
+
+    #[test]
+    fn test_saves_in_the_install_dir_leave_nothing_extra() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Game.exe"), b"game").unwrap();
+        fs::write(dir.path().join("slot1.sav"), b"progress").unwrap();
+
+        let mut files = Vec::new();
+        list_files(dir.path(), dir.path(), &[dir.path().join(".")], &mut files).unwrap();
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn test_case_insensitive_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let install = dir.path().join("Kiwi");
+        fs::create_dir_all(&install).unwrap();
+        // Linux filesystems, the one tests run on in CI, tell case apart
+        #[cfg(target_os = "linux")]
+        assert!(!is_case_insensitive(&install));
+
+        assert_eq!(name_key("Content/Data.PAK", true), "content/data.pak");
+        assert_eq!(name_key("Content/Data.PAK", false), "Content/Data.PAK");
+    }
+
+    #[test]
+    fn test_hash_file_reads_ahead_large_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.pak");
+        let data: Vec<u8> = (0..READ_AHEAD_THRESHOLD as usize + BLOCK_SIZE / 2)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        fs::write(&path, &data).unwrap();
+
+        assert_eq!(
+            hash_file::<Sha1>(&path, data.len() as u64).unwrap(),
+            hex(&Sha1::digest(&data))
+        );
+        // The same file read without a read-ahead thread
+        assert_eq!(
+            hash_file::<Sha256>(&path, 0).unwrap(),
+            hex(&Sha256::digest(&data))
+        );
+    }
+}
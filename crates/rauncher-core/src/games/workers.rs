@@ -0,0 +1,78 @@
+//! CPU-bound install work (decompressing chunks, checking their hashes, writing them out) run
+//! on blocking worker threads, so the async download tasks only wait on the network. The pool
+//! takes as many jobs at once as there are CPUs; downloads finishing faster than that wait for
+//! a free worker before fetching more, rather than piling decoded data up in memory.
+
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::{Error, Result};
+
+/// Jobs run at once when the number of CPUs is unknown
+const DEFAULT_WORKERS: usize = 4;
+
+pub(crate) struct WorkerPool {
+    permits: Arc<Semaphore>,
+}
+
+impl WorkerPool {
+    /// A pool with a worker per CPU
+    pub(crate) fn new() -> Self {
+        let workers = std::thread::available_parallelism().map_or(DEFAULT_WORKERS, |n| n.get());
+        Self::with_workers(workers)
+    }
+
+    fn with_workers(workers: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(workers.max(1))),
+        }
+    }
+
+    /// Run `job` on a worker thread once one is free
+    pub(crate) async fn run<T, F>(&self, job: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|e| Error::Other(format!("Worker pool closed: {}", e)))?;
+
+        tokio::task::spawn_blocking(job)
+            .await
+            .map_err(|e| Error::Other(format!("Install worker failed: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_jobs_beyond_the_workers_wait() {
+        let pool = WorkerPool::with_workers(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let most = Arc::new(AtomicUsize::new(0));
+
+        let jobs = (0..6).map(|_| {
+            let running = Arc::clone(&running);
+            let most = Arc::clone(&most);
+            pool.run(move || {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                most.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            })
+        });
+        for result in futures_util::future::join_all(jobs).await {
+            result.unwrap();
+        }
+
+        assert!(most.load(Ordering::SeqCst) <= 2);
+    }
+}
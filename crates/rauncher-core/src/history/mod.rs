@@ -0,0 +1,154 @@
+//! Log of every install, update, repair and uninstall, appended one JSON line per operation to
+//! `<data dir>/history.jsonl` so it survives the games it describes.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::providers;
+use crate::Result;
+
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// What was done to a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryAction {
+    Install,
+    Update,
+    /// Installed again over the same build
+    Repair,
+    Uninstall,
+}
+
+/// One operation on a game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub app_name: String,
+    pub provider: String,
+    pub action: HistoryAction,
+    /// Build installed before, for updates, repairs and uninstalls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_version: Option<String>,
+    /// Build installed after, for installs, updates and repairs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Bytes downloaded
+    #[serde(default)]
+    pub bytes: u64,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Why the operation failed; `None` when it succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Identifier of the game, as `InstalledGame::id` gives it
+    pub fn id(&self) -> String {
+        providers::qualified_id(&self.provider, &self.app_name)
+    }
+
+    pub fn duration(&self) -> chrono::Duration {
+        self.finished_at - self.started_at
+    }
+}
+
+fn history_path() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join(HISTORY_FILE))
+}
+
+/// Append an entry to the history
+pub fn record(entry: &HistoryEntry) -> Result<()> {
+    record_in(&history_path()?, entry)
+}
+
+fn record_in(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// The history, newest first, of every game or of the game whose app name or address is
+/// `game`. Lines that can't be read, e.g. from a newer rauncher, are skipped.
+pub fn load(game: Option<&str>) -> Result<Vec<HistoryEntry>> {
+    load_from(&history_path()?, game)
+}
+
+fn load_from(path: &Path, game: Option<&str>) -> Result<Vec<HistoryEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries: Vec<HistoryEntry> = fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                log::debug!("Skipping history entry: {}", e);
+                None
+            }
+        })
+        .filter(|entry: &HistoryEntry| {
+            game.is_none_or(|game| entry.app_name == game || entry.id() == game)
+        })
+        .collect();
+    entries.reverse();
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(app_name: &str, provider: &str, action: HistoryAction) -> HistoryEntry {
+        let started_at = Utc::now();
+        HistoryEntry {
+            app_name: app_name.to_string(),
+            provider: provider.to_string(),
+            action,
+            from_version: None,
+            version: Some("1.0".to_string()),
+            bytes: 1024,
+            started_at,
+            finished_at: started_at + chrono::Duration::seconds(90),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        assert!(load_from(&path, None).unwrap().is_empty());
+
+        let install = entry("Kiwi", providers::EPIC, HistoryAction::Install);
+        let gog = entry("1207658924", providers::GOG, HistoryAction::Install);
+        let uninstall = entry("Kiwi", providers::EPIC, HistoryAction::Uninstall);
+        for entry in [&install, &gog, &uninstall] {
+            record_in(&path, entry).unwrap();
+        }
+        fs::write(
+            &path,
+            fs::read_to_string(&path).unwrap() + "{\"action\":\"teleport\"}\n",
+        )
+        .unwrap();
+
+        let all = load_from(&path, None).unwrap();
+        assert_eq!(all, vec![uninstall.clone(), gog.clone(), install.clone()]);
+        assert_eq!(all[2].duration(), chrono::Duration::seconds(90));
+
+        assert_eq!(
+            load_from(&path, Some("Kiwi")).unwrap(),
+            vec![uninstall, install]
+        );
+        assert_eq!(load_from(&path, Some(&gog.id())).unwrap(), vec![gog]);
+    }
+}
@@ -0,0 +1,232 @@
+//! Translated user-facing messages for the CLI and GUI, using Fluent.
+//!
+//! Translations live in `locales/<language>/*.ftl` and are compiled into the binary. Add a
+//! language by copying `locales/en`, translating it and listing it in `LOCALES`. Messages
+//! missing from a translation fall back to English.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::FluentResource;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// Bundled languages and their Fluent sources
+const LOCALES: [(&str, &[&str]); 2] = [
+    (
+        "en",
+        &[
+            include_str!("../../locales/en/cli.ftl"),
+            include_str!("../../locales/en/gui.ftl"),
+            include_str!("../../locales/en/notify.ftl"),
+            include_str!("../../locales/en/prompt.ftl"),
+        ],
+    ),
+    (
+        "it",
+        &[
+            include_str!("../../locales/it/cli.ftl"),
+            include_str!("../../locales/it/gui.ftl"),
+            include_str!("../../locales/it/notify.ftl"),
+            include_str!("../../locales/it/prompt.ftl"),
+        ],
+    ),
+];
+
+/// Languages with a bundled translation
+pub fn languages() -> Vec<&'static str> {
+    LOCALES.iter().map(|(language, _)| *language).collect()
+}
+
+struct Localizer {
+    language: &'static str,
+    bundle: FluentBundle<FluentResource>,
+    fallback: Option<FluentBundle<FluentResource>>,
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Choose the language for the rest of the process: `language` when given (e.g. from the
+/// config), otherwise the system locale. Only the first call has an effect, and messages
+/// requested before any call use the system locale.
+pub fn init(language: Option<&str>) {
+    LOCALIZER.get_or_init(|| Localizer::new(language));
+}
+
+/// Language messages are being translated to
+pub fn language() -> &'static str {
+    localizer().language
+}
+
+fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(|| Localizer::new(None))
+}
+
+impl Localizer {
+    fn new(language: Option<&str>) -> Self {
+        let requested = language
+            .map(str::to_string)
+            .or_else(sys_locale::get_locale)
+            .unwrap_or_default();
+        let language = supported_language(&requested).unwrap_or(FALLBACK_LANGUAGE);
+
+        Self {
+            language,
+            bundle: bundle(language),
+            fallback: (language != FALLBACK_LANGUAGE).then(|| bundle(FALLBACK_LANGUAGE)),
+        }
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+        [Some(&self.bundle), self.fallback.as_ref()]
+            .into_iter()
+            .flatten()
+            .find_map(|bundle| format_message(bundle, id, args))
+    }
+}
+
+/// The bundled language matching a locale such as "it-IT", "it_IT.UTF-8" or "it"
+fn supported_language(locale: &str) -> Option<&'static str> {
+    let tag = locale.split(['.', '@']).next().unwrap_or_default();
+    let language = tag
+        .replace('_', "-")
+        .parse::<LanguageIdentifier>()
+        .ok()?
+        .language;
+
+    LOCALES
+        .iter()
+        .map(|(supported, _)| *supported)
+        .find(|supported| language.as_str() == *supported)
+}
+
+fn bundle(language: &str) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = language.parse().expect("bundled language ids are valid");
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Unicode isolation marks around arguments show up as garbage in terminals
+    bundle.set_use_isolating(false);
+
+    let sources = LOCALES
+        .iter()
+        .find(|(id, _)| *id == language)
+        .map(|(_, sources)| *sources)
+        .unwrap_or_default();
+
+    for source in sources {
+        let resource =
+            FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+                log::debug!("Errors in the {} translation: {:?}", language, errors);
+                resource
+            });
+        if let Err(errors) = bundle.add_resource(resource) {
+            log::debug!(
+                "Duplicate messages in the {} translation: {:?}",
+                language,
+                errors
+            );
+        }
+    }
+
+    bundle
+}
+
+fn format_message(
+    bundle: &FluentBundle<FluentResource>,
+    id: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = vec![];
+    let message = bundle.format_pattern(pattern, args, &mut errors);
+
+    if !errors.is_empty() {
+        log::debug!("Errors formatting message '{}': {:?}", id, errors);
+    }
+
+    Some(message.into_owned())
+}
+
+/// Translate message `id`; unknown ids come back unchanged so a missing string is visible
+/// rather than blank. Usually called through the `tr!` macro.
+pub fn message(id: &str, args: Option<&FluentArgs>) -> String {
+    localizer()
+        .format(id, args)
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Translate a message, with optional named arguments:
+/// `tr!("install-done")`, `tr!("install-failed", error = e.to_string())`
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::message($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $crate::i18n::FluentValue::from($value));)+
+        $crate::i18n::message($id, Some(&args))
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn message_ids(language: &str) -> BTreeSet<String> {
+        let (_, sources) = LOCALES.iter().find(|(id, _)| *id == language).unwrap();
+
+        sources
+            .iter()
+            .flat_map(|source| {
+                let resource = FluentResource::try_new(source.to_string()).unwrap();
+                resource
+                    .entries()
+                    .filter_map(|entry| match entry {
+                        fluent_syntax::ast::Entry::Message(message) => {
+                            Some(message.id.name.to_string())
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_translations_cover_every_message() {
+        let english = message_ids(FALLBACK_LANGUAGE);
+        assert!(!english.is_empty());
+
+        for language in languages() {
+            assert_eq!(
+                message_ids(language),
+                english,
+                "{} translation is out of sync with English",
+                language
+            );
+        }
+    }
+
+    #[test]
+    fn test_supported_language() {
+        assert_eq!(supported_language("it_IT.UTF-8"), Some("it"));
+        assert_eq!(supported_language("it-IT"), Some("it"));
+        assert_eq!(supported_language("en-GB"), Some("en"));
+        assert_eq!(supported_language("fr-FR"), None);
+        assert_eq!(supported_language(""), None);
+    }
+
+    #[test]
+    fn test_format_with_arguments() {
+        let italian = bundle("it");
+        let mut args = FluentArgs::new();
+        args.set("count", FluentValue::from(3));
+
+        let message = format_message(&italian, "restore-done", Some(&args)).unwrap();
+        assert!(message.contains('3'));
+        assert_eq!(format_message(&italian, "no-such-message", None), None);
+    }
+}
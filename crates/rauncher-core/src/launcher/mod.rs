@@ -0,0 +1,197 @@
+//! High-level entry point for embedding rauncher in other frontends.
+//!
+//! [`Rauncher`] bundles configuration, logins and every store behind one handle. Clones share
+//! all of it, so one instance can serve several tasks or threads at once. Games are addressed
+//! by bare app name or by `provider:app_name`, as on the command line.
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::api::{BuildInfo, BuildVersion, Game};
+use crate::auth::AuthManager;
+use crate::config::Config;
+use crate::games::{
+    GameExit, GameManager, InstallProgress, InstalledGame, LaunchOptions, RunningGame,
+    UninstallOptions,
+};
+use crate::providers;
+use crate::Result;
+
+/// Configures a [`Rauncher`]; anything left unset is loaded from the user's data directory
+#[derive(Default)]
+pub struct RauncherBuilder {
+    config: Option<Config>,
+    auth: Option<AuthManager>,
+}
+
+impl RauncherBuilder {
+    /// Use this configuration instead of the saved one
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Use these Epic logins instead of the saved ones
+    pub fn auth(mut self, auth: AuthManager) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    pub fn build(self) -> Result<Rauncher> {
+        let config = match self.config {
+            Some(config) => config,
+            None => Config::load()?,
+        };
+        let auth = match self.auth {
+            Some(auth) => auth,
+            None => AuthManager::new()?,
+        };
+
+        Ok(Rauncher {
+            manager: GameManager::new(config, auth)?,
+        })
+    }
+}
+
+/// A launcher instance: library, installs, updates and launching across every store
+#[derive(Clone)]
+pub struct Rauncher {
+    manager: GameManager,
+}
+
+impl Rauncher {
+    pub fn builder() -> RauncherBuilder {
+        RauncherBuilder::default()
+    }
+
+    pub fn config(&self) -> Arc<Config> {
+        self.manager.config()
+    }
+
+    /// The underlying manager, for operations this API doesn't cover yet
+    pub fn games(&mut self) -> &mut GameManager {
+        &mut self.manager
+    }
+
+    /// Stream of progress events for every install started through this handle after this
+    /// call. Only the most recent subscriber receives events.
+    pub fn subscribe_progress(&mut self) -> UnboundedReceiver<InstallProgress> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.manager.set_progress(sender);
+        receiver
+    }
+
+    /// Stores with a saved login
+    pub fn logged_in_providers(&self) -> Vec<&'static str> {
+        self.manager.logged_in_providers()
+    }
+
+    /// Games owned on every store the user is logged in to
+    pub async fn library(&self) -> Result<Vec<Game>> {
+        self.manager.list_merged_library().await
+    }
+
+    /// Games owned on one store
+    pub async fn provider_library(&self, provider: &str) -> Result<Vec<Game>> {
+        self.manager.list_provider_library(provider).await
+    }
+
+    pub fn installed(&self) -> Result<Vec<InstalledGame>> {
+        self.manager.list_installed()
+    }
+
+    /// Install the latest build of a game; bare app names install from Epic
+    pub async fn install(&self, address: &str) -> Result<()> {
+        let (provider, app_name) = providers::parse_address(address);
+        self.manager
+            .install_provider_game(provider.unwrap_or(providers::EPIC), app_name)
+            .await
+    }
+
+    /// Builds Epic offers for a game, newest first
+    pub async fn builds(&self, app_name: &str) -> Result<Vec<BuildVersion>> {
+        self.manager.list_builds(app_name).await
+    }
+
+    /// Install and pin a specific Epic build
+    pub async fn install_build(&self, app_name: &str, build_version: &str) -> Result<()> {
+        self.manager
+            .install_game_version(app_name, Some(build_version))
+            .await
+    }
+
+    /// The newer build available for an installed game, if any
+    pub async fn check_for_update(&self, address: &str) -> Result<Option<BuildInfo>> {
+        self.manager.check_for_updates(address).await
+    }
+
+    pub async fn update(&self, address: &str) -> Result<()> {
+        self.manager.update_game(address).await
+    }
+
+    pub fn launch(&self, address: &str) -> Result<RunningGame> {
+        self.manager.launch_game(address)
+    }
+
+    /// Launch with per-session overrides of the game's settings
+    pub fn launch_with_options(
+        &self,
+        address: &str,
+        options: &LaunchOptions,
+    ) -> Result<RunningGame> {
+        self.manager.launch_game_with_options(address, options)
+    }
+
+    /// Stop a running game; false when it wasn't running
+    pub async fn stop(&self, address: &str) -> Result<bool> {
+        self.manager.stop_game(address).await
+    }
+
+    /// Run a game's post-exit steps once a launched game has been waited on
+    pub async fn after_exit(&self, game: &InstalledGame, exit: &GameExit) {
+        self.manager.after_exit(game, exit).await
+    }
+
+    pub fn uninstall(&self, address: &str, options: &UninstallOptions) -> Result<()> {
+        self.manager.uninstall_game_with_options(address, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_builder_uses_given_config() {
+        let config = Config {
+            install_dir: PathBuf::from("/srv/games"),
+            ..Default::default()
+        };
+
+        let rauncher = Rauncher::builder()
+            .config(config)
+            .auth(AuthManager::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(rauncher.config().install_dir, PathBuf::from("/srv/games"));
+    }
+
+    #[test]
+    fn test_clones_share_config() {
+        let rauncher = Rauncher::builder()
+            .config(Config::default())
+            .auth(AuthManager::default())
+            .build()
+            .unwrap();
+        let clone = rauncher.clone();
+
+        rauncher.manager.set_config(Config {
+            install_dir: PathBuf::from("/srv/games"),
+            ..Default::default()
+        });
+        assert_eq!(clone.config().install_dir, PathBuf::from("/srv/games"));
+    }
+}
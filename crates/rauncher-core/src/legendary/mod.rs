@@ -0,0 +1,502 @@
+//! Reading the setup of Legendary and Heroic, for `rauncher migrate`, and writing rauncher's
+//! installs into Legendary's, for `rauncher export`. Heroic keeps a Legendary config directory
+//! of its own for Epic games, plus a JSON file of settings per game. What's read: the Epic
+//! session, install records, per-game Wine settings and prefixes, and Legendary's aliases.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::auth::AuthToken;
+use crate::config::GameSettings;
+use crate::games::InstalledGame;
+use crate::{Error, Result};
+
+/// Launchers rauncher can migrate from
+pub const SOURCES: [&str; 2] = ["legendary", "heroic"];
+
+/// Formats rauncher can export its installs in
+pub const EXPORT_FORMATS: [&str; 1] = ["legendary"];
+
+/// Heroic's Flatpak keeps its config inside the sandbox's home
+const HEROIC_FLATPAK: &str = ".var/app/com.heroicgameslauncher.hgl/config/heroic";
+
+/// Everything found in another launcher's files
+#[derive(Debug, Clone, Default)]
+pub struct Migration {
+    /// The launcher's Epic session, when its refresh token hasn't expired
+    pub token: Option<AuthToken>,
+    /// Installed Epic games, DLC left out
+    pub games: Vec<InstalledGame>,
+    /// Launch settings by app name
+    pub settings: BTreeMap<String, GameSettings>,
+    /// Wine prefixes by app name
+    pub prefixes: BTreeMap<String, PathBuf>,
+    /// App names by alias
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// Read Legendary's or Heroic's setup from `dir`, or from where the launcher keeps it
+pub fn read(source: &str, dir: Option<&Path>) -> Result<Migration> {
+    let dir = match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => default_dir(source).ok_or_else(|| {
+            Error::Other(format!(
+                "No {} configuration found; pass its directory explicitly",
+                source
+            ))
+        })?,
+    };
+
+    match source {
+        "legendary" => read_legendary(&dir),
+        "heroic" => read_heroic(&dir),
+        _ => Err(Error::Other(format!(
+            "Unknown launcher '{}'. Must be one of: {}",
+            source,
+            SOURCES.join(", ")
+        ))),
+    }
+}
+
+/// Where the launcher keeps its configuration on this machine, if it's there
+fn default_dir(source: &str) -> Option<PathBuf> {
+    let dirs = directories::BaseDirs::new()?;
+    let candidates = match source {
+        "legendary" => vec![legendary_dir()?],
+        _ => vec![
+            dirs.config_dir().join("heroic"),
+            dirs.home_dir().join(HEROIC_FLATPAK),
+        ],
+    };
+    candidates.into_iter().find(|dir| dir.is_dir())
+}
+
+/// Legendary's configuration directory, whether or not it exists yet
+pub fn legendary_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("LEGENDARY_CONFIG_PATH") {
+        return Some(PathBuf::from(dir));
+    }
+    Some(directories::BaseDirs::new()?.config_dir().join("legendary"))
+}
+
+/// Record `games` in the Legendary configuration at `dir`, so Legendary, or Heroic pointed at
+/// it, runs and updates them where they are. Legendary's other entries are kept, as are the
+/// fields of these entries rauncher has no counterpart for. Metadata is only written for games
+/// Legendary has none of; it's a stub, replaced by the store's when Legendary next refreshes.
+pub fn write(dir: &Path, games: &[InstalledGame]) -> Result<()> {
+    let path = dir.join("installed.json");
+    let mut installed: serde_json::Map<String, Value> = match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => serde_json::Map::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let metadata_dir = dir.join("metadata");
+    fs::create_dir_all(&metadata_dir)?;
+
+    for game in games {
+        let entry = installed
+            .entry(game.app_name.clone())
+            .or_insert_with(|| new_install_entry(game));
+        if let Value::Object(entry) = entry {
+            entry.insert("app_name".into(), json!(game.app_name));
+            entry.insert("title".into(), json!(game.app_title));
+            entry.insert("version".into(), json!(game.app_version));
+            entry.insert("install_path".into(), json!(game.install_path));
+            entry.insert("executable".into(), json!(game.executable));
+            entry.insert("install_size".into(), json!(game.install_size));
+        }
+
+        let metadata = metadata_dir.join(format!("{}.json", game.app_name));
+        if !metadata.exists() {
+            let stub = json!({
+                "app_name": game.app_name,
+                "app_title": game.app_title,
+                "asset_infos": {
+                    "Windows": {
+                        "app_name": game.app_name,
+                        "build_version": game.app_version,
+                    },
+                },
+                "base_urls": [],
+                "metadata": { "title": game.app_title },
+            });
+            fs::write(&metadata, serde_json::to_string_pretty(&stub)?)?;
+            crate::logs::transcript::touched("wrote", &metadata);
+        }
+    }
+
+    fs::write(&path, serde_json::to_string_pretty(&installed)?)?;
+    crate::logs::transcript::touched("wrote", &path);
+    Ok(())
+}
+
+/// The fields Legendary expects of an install, as it writes them for a fresh one
+fn new_install_entry(game: &InstalledGame) -> Value {
+    json!({
+        "app_name": game.app_name,
+        "base_urls": [],
+        "can_run_offline": true,
+        "egl_guid": "",
+        "install_tags": [],
+        "is_dlc": false,
+        "launch_parameters": "",
+        "manifest_path": null,
+        "needs_verification": false,
+        "platform": "Windows",
+        "prereq_info": null,
+        "requires_ot": false,
+        "save_path": null,
+        "uninstaller": null,
+    })
+}
+
+fn read_legendary(dir: &Path) -> Result<Migration> {
+    let installed = dir.join("installed.json");
+    if !installed.is_file() {
+        return Err(Error::Other(format!(
+            "{:?} isn't a Legendary configuration directory",
+            dir
+        )));
+    }
+
+    let mut migration = Migration {
+        token: read_session(&dir.join("user.json")),
+        games: read_installed(&installed)?,
+        ..Migration::default()
+    };
+
+    let ini = fs::read_to_string(dir.join("config.ini"))
+        .map(|contents| parse_ini(&contents))
+        .unwrap_or_default();
+    let empty = BTreeMap::new();
+    let defaults = ini.get("default").unwrap_or(&empty);
+    for game in &migration.games {
+        let section = ini.get(game.app_name.as_str()).unwrap_or(&empty);
+        let value = |key: &str| {
+            section
+                .get(key)
+                .or_else(|| defaults.get(key))
+                .filter(|value| !value.is_empty())
+        };
+
+        let mut env = ini.get("default.env").cloned().unwrap_or_default();
+        env.extend(
+            ini.get(format!("{}.env", game.app_name).as_str())
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let settings = GameSettings {
+            wine: value("wine_executable").map(PathBuf::from),
+            launch_args: value("start_params").cloned().unwrap_or_default(),
+            executable: value("override_exe").map(PathBuf::from),
+            env,
+            ..GameSettings::default()
+        };
+        if settings != GameSettings::default() {
+            migration.settings.insert(game.app_name.clone(), settings);
+        }
+        if let Some(prefix) = value("wine_prefix") {
+            migration
+                .prefixes
+                .insert(game.app_name.clone(), PathBuf::from(prefix));
+        }
+    }
+
+    migration.aliases = ini.get("Legendary.aliases").cloned().unwrap_or_default();
+    Ok(migration)
+}
+
+fn read_heroic(dir: &Path) -> Result<Migration> {
+    let mut migration = read_legendary(&dir.join("legendaryConfig").join("legendary"))
+        .map_err(|_| Error::Other(format!("{:?} has no Heroic Epic games", dir)))?;
+    // Heroic's settings are its own, not Legendary's
+    migration.settings.clear();
+    migration.prefixes.clear();
+
+    for game in &migration.games {
+        let path = dir
+            .join("GamesConfig")
+            .join(format!("{}.json", game.app_name));
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let config = serde_json::from_str::<BTreeMap<String, serde_json::Value>>(&contents)
+            .ok()
+            .and_then(|mut file| file.remove(&game.app_name))
+            .and_then(|config| serde_json::from_value::<HeroicGameConfig>(config).ok());
+        let Some(config) = config else {
+            log::warn!("Ignoring unreadable Heroic settings {:?}", path);
+            continue;
+        };
+
+        let settings = GameSettings {
+            gamemode: config.use_game_mode,
+            mangohud: config.show_mangohud,
+            wine: config
+                .wine_version
+                .map(|wine| PathBuf::from(wine.bin))
+                .filter(|bin| !bin.as_os_str().is_empty()),
+            launch_args: config.launcher_args,
+            executable: Some(PathBuf::from(config.target_exe))
+                .filter(|exe| !exe.as_os_str().is_empty()),
+            env: config
+                .enviroment_options
+                .into_iter()
+                .map(|option| (option.key, option.value))
+                .collect(),
+            ..GameSettings::default()
+        };
+        if settings != GameSettings::default() {
+            migration.settings.insert(game.app_name.clone(), settings);
+        }
+        if !config.wine_prefix.is_empty() {
+            migration
+                .prefixes
+                .insert(game.app_name.clone(), PathBuf::from(config.wine_prefix));
+        }
+    }
+
+    Ok(migration)
+}
+
+/// Legendary's session, `user.json`
+#[derive(Deserialize)]
+struct LegendarySession {
+    access_token: String,
+    expires_at: DateTime<Utc>,
+    refresh_token: String,
+    refresh_expires_at: Option<DateTime<Utc>>,
+    account_id: String,
+}
+
+fn read_session(path: &Path) -> Option<AuthToken> {
+    let contents = fs::read_to_string(path).ok()?;
+    let session: LegendarySession = match serde_json::from_str(&contents) {
+        Ok(session) => session,
+        Err(e) => {
+            log::warn!("Ignoring unreadable session {:?}: {}", path, e);
+            return None;
+        }
+    };
+    if session
+        .refresh_expires_at
+        .is_some_and(|expires| expires <= Utc::now())
+    {
+        log::debug!("The session in {:?} has expired", path);
+        return None;
+    }
+
+    Some(AuthToken {
+        access_token: session.access_token,
+        refresh_token: session.refresh_token,
+        expires_at: session.expires_at,
+        account_id: session.account_id,
+    })
+}
+
+/// An entry of Legendary's `installed.json`
+#[derive(Deserialize)]
+struct LegendaryInstall {
+    app_name: String,
+    title: String,
+    version: String,
+    install_path: PathBuf,
+    executable: String,
+    #[serde(default)]
+    install_size: u64,
+    #[serde(default)]
+    is_dlc: bool,
+}
+
+fn read_installed(path: &Path) -> Result<Vec<InstalledGame>> {
+    let installed: BTreeMap<String, LegendaryInstall> =
+        serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    Ok(installed
+        .into_values()
+        .filter(|install| !install.is_dlc)
+        .map(|install| InstalledGame {
+            app_name: install.app_name,
+            app_title: install.title,
+            app_version: install.version,
+            install_path: install.install_path,
+            executable: install.executable.replace('\\', "/"),
+            pinned: false,
+            provider: crate::providers::EPIC.to_string(),
+            install_size: install.install_size,
+            download_size: 0,
+            installed_at: None,
+            last_played: None,
+            volume: None,
+            save_path_template: None,
+        })
+        .collect())
+}
+
+/// A game's settings in Heroic's `GamesConfig/<app_name>.json`
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct HeroicGameConfig {
+    use_game_mode: bool,
+    show_mangohud: bool,
+    wine_version: Option<HeroicWine>,
+    wine_prefix: String,
+    launcher_args: String,
+    target_exe: String,
+    // Heroic's own spelling
+    enviroment_options: Vec<HeroicEnv>,
+}
+
+#[derive(Deserialize)]
+struct HeroicWine {
+    bin: String,
+}
+
+#[derive(Deserialize)]
+struct HeroicEnv {
+    key: String,
+    value: String,
+}
+
+/// Sections of an INI file with their keys. Keys and values are split on the first `=` or
+/// `:`, as Python's configparser does; comments start with `;` or `#`.
+fn parse_ini(contents: &str) -> BTreeMap<String, BTreeMap<String, String>> {
+    let mut sections: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    let mut section = String::new();
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with([';', '#']) {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        if let Some(at) = line.find(['=', ':']) {
+            sections.entry(section.clone()).or_default().insert(
+                line[..at].trim().to_string(),
+                line[at + 1..].trim().to_string(),
+            );
+        }
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTALLED: &str = r#"{
+        "Kiwi": {
+            "app_name": "Kiwi", "title": "Kiwi Quest", "version": "1.2",
+            "install_path": "/home/me/Games/Kiwi", "executable": "Bin\\Kiwi.exe",
+            "install_size": 1024, "is_dlc": false, "platform": "Windows"
+        },
+        "KiwiDLC": {
+            "app_name": "KiwiDLC", "title": "Kiwi Quest - Soundtrack", "version": "1.0",
+            "install_path": "/home/me/Games/Kiwi", "executable": "", "is_dlc": true
+        }
+    }"#;
+
+    #[test]
+    fn test_read_legendary() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("installed.json"), INSTALLED).unwrap();
+        fs::write(
+            dir.path().join("config.ini"),
+            "[Legendary]\ninstall_dir = /home/me/Games\n\n\
+             [Legendary.aliases]\nkiwi = Kiwi\n\n\
+             [default]\nwine_executable = /usr/bin/wine\n\n\
+             [default.env]\nDXVK_HUD = fps\n\n\
+             ; per game\n[Kiwi]\nstart_params = -windowed\n\
+             wine_prefix = /home/me/.wine-kiwi\n\n\
+             [Kiwi.env]\nDXVK_HUD = compiler\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("user.json"),
+            r#"{"access_token": "a", "expires_at": "2020-01-01T00:00:00.000Z",
+                "refresh_token": "r", "refresh_expires_at": "2099-01-01T00:00:00.000Z",
+                "account_id": "acc", "displayName": "me"}"#,
+        )
+        .unwrap();
+
+        let migration = read_legendary(dir.path()).unwrap();
+        assert_eq!(migration.games.len(), 1);
+        assert_eq!(migration.games[0].executable, "Bin/Kiwi.exe");
+        assert_eq!(migration.token.unwrap().refresh_token, "r");
+        assert_eq!(migration.aliases["kiwi"], "Kiwi");
+
+        let settings = &migration.settings["Kiwi"];
+        assert_eq!(settings.wine, Some(PathBuf::from("/usr/bin/wine")));
+        assert_eq!(settings.launch_args, "-windowed");
+        assert_eq!(settings.env["DXVK_HUD"], "compiler");
+        assert_eq!(
+            migration.prefixes["Kiwi"],
+            PathBuf::from("/home/me/.wine-kiwi")
+        );
+    }
+
+    #[test]
+    fn test_read_heroic() {
+        let dir = tempfile::tempdir().unwrap();
+        let legendary = dir.path().join("legendaryConfig/legendary");
+        fs::create_dir_all(&legendary).unwrap();
+        fs::write(legendary.join("installed.json"), INSTALLED).unwrap();
+        fs::create_dir_all(dir.path().join("GamesConfig")).unwrap();
+        fs::write(
+            dir.path().join("GamesConfig/Kiwi.json"),
+            r#"{"Kiwi": {
+                "useGameMode": true, "showMangohud": false,
+                "wineVersion": {"bin": "/opt/GE-Proton9-20/proton", "name": "GE", "type": "proton"},
+                "winePrefix": "/home/me/Games/Heroic/Prefixes/Kiwi",
+                "launcherArgs": "-dx11", "targetExe": "",
+                "enviromentOptions": [{"key": "PROTON_LOG", "value": "1"}]
+            }, "version": "v0"}"#,
+        )
+        .unwrap();
+
+        let migration = read_heroic(dir.path()).unwrap();
+        assert!(migration.token.is_none());
+        let settings = &migration.settings["Kiwi"];
+        assert!(settings.gamemode);
+        assert_eq!(
+            settings.wine,
+            Some(PathBuf::from("/opt/GE-Proton9-20/proton"))
+        );
+        assert_eq!(settings.launch_args, "-dx11");
+        assert_eq!(settings.executable, None);
+        assert_eq!(settings.env["PROTON_LOG"], "1");
+        assert!(migration.prefixes.contains_key("Kiwi"));
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("installed.json"), INSTALLED).unwrap();
+        let mut game = read_installed(&dir.path().join("installed.json")).unwrap()[0].clone();
+        game.app_version = "1.3".to_string();
+        game.install_path = PathBuf::from("/mnt/games/Kiwi");
+        let mut other = game.clone();
+        other.app_name = "Pear".to_string();
+
+        write(dir.path(), &[game, other]).unwrap();
+        let games = read_installed(&dir.path().join("installed.json")).unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].app_version, "1.3");
+        assert_eq!(games[0].install_path, PathBuf::from("/mnt/games/Kiwi"));
+        assert_eq!(games[1].app_name, "Pear");
+
+        // Legendary's own entries and fields stay
+        let installed: Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("installed.json")).unwrap())
+                .unwrap();
+        assert_eq!(installed["Kiwi"]["platform"], "Windows");
+        assert_eq!(installed["KiwiDLC"]["is_dlc"], true);
+        assert!(dir.path().join("metadata/Pear.json").is_file());
+    }
+}
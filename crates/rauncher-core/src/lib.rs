@@ -0,0 +1,62 @@
+//! Core of rauncher: store APIs, logins, downloads and game management, without any UI.
+//!
+//! Frontends usually only need [`Rauncher`]:
+//!
+//! ```no_run
+//! # async fn example() -> rauncher_core::Result<()> {
+//! use rauncher_core::{games::InstallProgress, Rauncher};
+//!
+//! let mut rauncher = Rauncher::builder().build()?;
+//! let mut progress = rauncher.subscribe_progress();
+//! tokio::spawn(async move {
+//!     while let Some(event) = progress.recv().await {
+//!         if let InstallProgress::FileDone { index, filename, .. } = event {
+//!             println!("{} {}", index, filename);
+//!         }
+//!     }
+//! });
+//!
+//! for game in rauncher.library().await? {
+//!     println!("{} {}", game.id(), game.app_title);
+//! }
+//! rauncher.install("gog:1207658924").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod api;
+pub mod auth;
+pub mod collections;
+pub mod config;
+pub mod csv;
+pub mod downloads;
+pub mod egl;
+pub mod error;
+pub mod feeds;
+pub mod games;
+pub mod history;
+pub mod i18n;
+pub mod launcher;
+pub mod legendary;
+pub mod logs;
+pub mod manifest;
+pub mod metrics;
+pub mod network;
+pub mod notify;
+pub mod power;
+pub mod profiling;
+pub mod prompt;
+pub mod providers;
+pub mod registry;
+pub mod runners;
+pub mod sandbox;
+pub mod saves;
+pub mod stats;
+pub mod storage;
+pub mod wishlist;
+
+#[cfg(test)]
+mod test_support;
+
+pub use error::{Error, Result};
+pub use launcher::{Rauncher, RauncherBuilder};
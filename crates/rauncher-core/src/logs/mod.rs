@@ -0,0 +1,180 @@
+//! Log files: rauncher's own log, rotated daily under `<data dir>/logs`, and the output of
+//! each game launch under `<data dir>/logs/games/<app_name>`.
+//!
+//! Records from the `log` macros are forwarded to `tracing`, so both end up in the same
+//! files. Files are written synchronously, so nothing is lost when the process exits abruptly.
+
+pub mod transcript;
+
+use chrono::Utc;
+use std::fs::{self, File};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+use crate::config::Config;
+use crate::{Error, Result};
+
+const LOG_PREFIX: &str = "rauncher";
+pub(crate) const LOG_SUFFIX: &str = "log";
+
+/// Daily log files kept before the oldest is deleted
+const KEPT_LOG_FILES: usize = 14;
+
+/// Launch logs kept per game
+const KEPT_GAME_LOGS: usize = 10;
+
+pub fn logs_dir() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join("logs"))
+}
+
+pub fn game_logs_dir(app_name: &str) -> Result<PathBuf> {
+    Ok(logs_dir()?.join("games").join(app_name))
+}
+
+/// Install the global logger: human-readable records at `console_level` (or as set by
+/// `RUST_LOG`) on stderr, and records at the configured level and format in the log files.
+/// Falls back to the console alone when the log directory can't be written. With
+/// `transcript` set, this run also gets a transcript (see [`transcript`]).
+pub fn init(config: &Config, console_level: &str, transcript: bool) {
+    let console = fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(console_level)),
+        );
+
+    let file = match file_appender() {
+        Ok(appender) => {
+            let layer = fmt::layer().with_writer(appender).with_ansi(false);
+            let layer = if config.log_format == "json" {
+                layer.json().boxed()
+            } else {
+                layer.boxed()
+            };
+            Some(layer.with_filter(EnvFilter::new(&config.log_level)))
+        }
+        Err(e) => {
+            eprintln!("Logging to the console only: {}", e);
+            None
+        }
+    };
+
+    let transcript = transcript
+        .then(|| match transcript::layer() {
+            Ok(layer) => Some(layer),
+            Err(e) => {
+                eprintln!("Not writing a transcript: {}", e);
+                None
+            }
+        })
+        .flatten();
+
+    tracing_subscriber::registry()
+        .with(console)
+        .with(file)
+        .with(transcript)
+        .init();
+
+    // Panics would otherwise only reach the terminal, which is gone for the GUI and daemon
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("rauncher panicked: {}", info);
+        default_hook(info);
+    }));
+}
+
+fn file_appender() -> Result<RollingFileAppender> {
+    let dir = logs_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix(LOG_SUFFIX)
+        .max_log_files(KEPT_LOG_FILES)
+        .build(&dir)
+        .map_err(|e| Error::Other(format!("Cannot write logs to {:?}: {}", dir, e)))
+}
+
+/// The log file currently being written, if any
+pub fn latest_log() -> Result<Option<PathBuf>> {
+    newest_file(&logs_dir()?)
+}
+
+/// Output of the most recent launch of a game, if any
+pub fn latest_game_log(app_name: &str) -> Result<Option<PathBuf>> {
+    newest_file(&game_logs_dir(app_name)?)
+}
+
+/// Create the file a game's output is captured into for a new launch, removing the oldest
+/// launch logs beyond `KEPT_GAME_LOGS`
+pub(crate) fn create_game_log(app_name: &str) -> Result<(PathBuf, File)> {
+    let dir = game_logs_dir(app_name)?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!(
+        "{}.{}",
+        Utc::now().format("%Y-%m-%d_%H-%M-%S"),
+        LOG_SUFFIX
+    ));
+    let file = File::create(&path)?;
+
+    for old in files_newest_first(&dir)?.into_iter().skip(KEPT_GAME_LOGS) {
+        if let Err(e) = fs::remove_file(&old) {
+            log::debug!("Could not remove old launch log {:?}: {}", old, e);
+        }
+    }
+
+    Ok((path, file))
+}
+
+fn newest_file(dir: &Path) -> Result<Option<PathBuf>> {
+    Ok(files_newest_first(dir)?.into_iter().next())
+}
+
+/// Log files in `dir`, whose names start with a date, newest first
+pub(crate) fn files_newest_first(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file() && path.extension().and_then(|e| e.to_str()) == Some(LOG_SUFFIX)
+        })
+        .collect();
+
+    files.sort();
+    files.reverse();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "rauncher.2026-10-14.log",
+            "rauncher.2026-10-15.log",
+            "rauncher.2026-09-30.log",
+            "notes.txt",
+        ] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let files = files_newest_first(dir.path()).unwrap();
+        assert_eq!(files.len(), 3);
+        assert!(files[0].ends_with("rauncher.2026-10-15.log"));
+        assert!(files[2].ends_with("rauncher.2026-09-30.log"));
+        assert_eq!(newest_file(&dir.path().join("missing")).unwrap(), None);
+    }
+}
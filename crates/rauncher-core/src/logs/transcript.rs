@@ -0,0 +1,129 @@
+//! Transcripts of single rauncher runs, for troubleshooting: the steps taken, files touched
+//! and endpoints called, each stamped with the time since the run started.
+//!
+//! Lines are written as they happen, so runs that end in an error exit still leave a
+//! transcript behind.
+
+use chrono::Utc;
+use reqwest::{RequestBuilder, Response};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{fmt, EnvFilter, Layer};
+
+use super::{files_newest_first, logs_dir, LOG_SUFFIX};
+use crate::Result;
+
+/// Transcripts kept before the oldest is deleted
+const KEPT_TRANSCRIPTS: usize = 20;
+
+const HTTP_TARGET: &str = "rauncher::http";
+const FILES_TARGET: &str = "rauncher::files";
+
+/// The transcript being written by this process and when the run started
+static CURRENT: OnceLock<(PathBuf, Instant)> = OnceLock::new();
+
+pub fn transcripts_dir() -> Result<PathBuf> {
+    Ok(logs_dir()?.join("transcripts"))
+}
+
+/// Transcript of the most recent run that wrote one
+pub fn latest() -> Result<Option<PathBuf>> {
+    Ok(files_newest_first(&transcripts_dir()?)?.into_iter().next())
+}
+
+/// Start a transcript for this run; records at info level, plus endpoints and files
+pub(crate) fn layer<S>() -> Result<impl Layer<S>>
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    let dir = transcripts_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!(
+        "{}.{}",
+        Utc::now().format("%Y-%m-%d_%H-%M-%S"),
+        LOG_SUFFIX
+    ));
+    let mut file = File::create(&path)?;
+    let command: Vec<String> = std::env::args().collect();
+    writeln!(file, "$ {}", command.join(" "))?;
+    writeln!(file, "Started {}", Utc::now().to_rfc3339())?;
+
+    for old in files_newest_first(&dir)?.into_iter().skip(KEPT_TRANSCRIPTS) {
+        if let Err(e) = fs::remove_file(&old) {
+            log::debug!("Could not remove old transcript {:?}: {}", old, e);
+        }
+    }
+
+    let _ = CURRENT.set((path, Instant::now()));
+
+    Ok(fmt::layer()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_target(false)
+        .with_timer(fmt::time::uptime())
+        .with_filter(EnvFilter::new(format!(
+            "warn,rauncher=info,rauncher_core=info,rauncher_gui=info,{}=debug,{}=debug",
+            HTTP_TARGET, FILES_TARGET
+        ))))
+}
+
+/// Close this run's transcript with its total duration
+pub fn finish() {
+    let Some((path, started)) = CURRENT.get() else {
+        return;
+    };
+
+    let footer = format!("Finished in {:.2?}\n", started.elapsed());
+    if let Err(e) = OpenOptions::new()
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(footer.as_bytes()))
+    {
+        log::debug!("Could not finish the transcript {:?}: {}", path, e);
+    }
+}
+
+/// Note in the transcript that a file or directory was `action` (created, wrote, removed)
+pub(crate) fn touched(action: &str, path: &Path) {
+    log::debug!(target: FILES_TARGET, "FILE {} {}", action, path.display());
+}
+
+/// `RequestBuilder::send`, noting the endpoint, status and duration in the transcript
+pub(crate) trait RecordedSend {
+    async fn send_recorded(self) -> reqwest::Result<Response>;
+}
+
+impl RecordedSend for RequestBuilder {
+    async fn send_recorded(self) -> reqwest::Result<Response> {
+        let (client, request) = self.build_split();
+        let request = request?;
+
+        // Queries can carry credentials; the endpoint is what matters
+        let mut url = request.url().clone();
+        url.set_query(None);
+        let method = request.method().clone();
+
+        let started = Instant::now();
+        let response = client.execute(request).await;
+        crate::metrics::record_request(response.as_ref().ok().map(|r| r.status().as_u16()));
+        let outcome = match &response {
+            Ok(response) => response.status().to_string(),
+            Err(e) => format!("failed: {}", e),
+        };
+        log::debug!(
+            target: HTTP_TARGET,
+            "HTTP {} {} -> {} in {:.0?}",
+            method,
+            url,
+            outcome,
+            started.elapsed()
+        );
+
+        response
+    }
+}
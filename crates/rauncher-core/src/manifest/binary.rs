@@ -0,0 +1,819 @@
+//! The binary manifest format: a fixed header, then a body that is usually zlib-compressed,
+//! holding the build's metadata, its chunks, its files and its custom fields. Each section
+//! starts with its size, so fields added by newer versions are skipped over.
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use sha1::{Digest, Sha1};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use super::{invalid, ChunkInfo, ChunkPart, FileEntry, Guid, Manifest, Meta};
+use crate::Result;
+
+const MAGIC: u32 = 0x44BE_C00C;
+const HEADER_SIZE: u32 = 41;
+
+/// Set in the header when the body is zlib-compressed
+const STORED_COMPRESSED: u8 = 0x1;
+/// Set in the header when the body is encrypted, which nothing published uses
+const STORED_ENCRYPTED: u8 = 0x2;
+
+/// Versions of each section written
+const META_VERSION: u8 = 2;
+const CHUNKS_VERSION: u8 = 0;
+/// Version 2 adds each file's SHA-256; version 1 is written when no file has one
+const FILES_VERSION: u8 = 2;
+const CUSTOM_FIELDS_VERSION: u8 = 0;
+
+/// Size of a chunk part as written, its own size field included
+const CHUNK_PART_SIZE: u32 = 28;
+
+/// Bodies larger than this are refused rather than allocated
+const MAX_BODY_SIZE: u32 = 512 * 1024 * 1024;
+
+pub(super) fn is_binary(data: &[u8]) -> bool {
+    data.get(..4) == Some(&MAGIC.to_le_bytes()[..])
+}
+
+pub(super) fn read(data: &[u8]) -> Result<Manifest> {
+    let mut header = Reader::new(data);
+    if header.u32()? != MAGIC {
+        return Err(invalid("not a binary manifest"));
+    }
+    let header_size = header.u32()?;
+    let size_uncompressed = header.u32()?;
+    let size_compressed = header.u32()?;
+    let sha = header.array::<20>()?;
+    let stored_as = header.u8()?;
+    let version = header.u32()?;
+
+    if stored_as & STORED_ENCRYPTED != 0 {
+        return Err(invalid("encrypted manifests aren't supported"));
+    }
+    if size_uncompressed > MAX_BODY_SIZE {
+        return Err(invalid("body too large"));
+    }
+    let body = data
+        .get(header_size as usize..)
+        .ok_or_else(|| invalid("truncated header"))?;
+
+    let body = if stored_as & STORED_COMPRESSED != 0 {
+        let compressed = body
+            .get(..size_compressed as usize)
+            .ok_or_else(|| invalid("truncated body"))?;
+        let mut body = Vec::with_capacity(size_uncompressed as usize);
+        ZlibDecoder::new(compressed)
+            .take(u64::from(size_uncompressed) + 1)
+            .read_to_end(&mut body)
+            .map_err(|e| invalid(&format!("can't decompress body: {}", e)))?;
+        body
+    } else {
+        body.get(..size_uncompressed as usize)
+            .ok_or_else(|| invalid("truncated body"))?
+            .to_vec()
+    };
+    if body.len() != size_uncompressed as usize {
+        return Err(invalid("body size doesn't match the header"));
+    }
+    if Sha1::digest(&body)[..] != sha {
+        return Err(invalid("body hash doesn't match the header"));
+    }
+
+    let mut reader = Reader::new(&body);
+    let meta = read_meta(&mut reader)?;
+    let chunks = read_chunks(&mut reader)?;
+    let files = read_files(&mut reader)?;
+    // Manifests older than custom fields end after the files
+    let custom_fields = if reader.is_empty() {
+        Default::default()
+    } else {
+        read_custom_fields(&mut reader)?
+    };
+
+    Ok(Manifest {
+        version,
+        meta,
+        chunks,
+        files,
+        custom_fields,
+    })
+}
+
+fn read_meta(reader: &mut Reader) -> Result<Meta> {
+    let (version, end) = reader.section()?;
+    let mut meta = Meta {
+        feature_level: reader.u32()?,
+        is_file_data: reader.u8()? != 0,
+        app_id: reader.u32()?,
+        app_name: reader.fstring()?,
+        build_version: reader.fstring()?,
+        launch_exe: reader.fstring()?,
+        launch_command: reader.fstring()?,
+        prereq_ids: reader.fstrings()?,
+        prereq_name: reader.fstring()?,
+        prereq_path: reader.fstring()?,
+        prereq_args: reader.fstring()?,
+        ..Meta::default()
+    };
+    if version >= 1 {
+        meta.build_id = reader.fstring()?;
+    }
+    if version >= 2 {
+        meta.uninstall_action_path = reader.fstring()?;
+        meta.uninstall_action_args = reader.fstring()?;
+    }
+
+    reader.seek(end)?;
+    Ok(meta)
+}
+
+fn read_chunks(reader: &mut Reader) -> Result<Vec<ChunkInfo>> {
+    let (_, end) = reader.section()?;
+    let count = reader.count()?;
+
+    // Stored a field at a time: every GUID, then every hash, and so on
+    let mut chunks = vec![ChunkInfo::default(); count];
+    for chunk in &mut chunks {
+        chunk.guid = reader.guid()?;
+    }
+    for chunk in &mut chunks {
+        chunk.hash = reader.u64()?;
+    }
+    for chunk in &mut chunks {
+        chunk.sha = reader.array()?;
+    }
+    for chunk in &mut chunks {
+        chunk.group = reader.u8()?;
+    }
+    for chunk in &mut chunks {
+        chunk.window_size = reader.u32()?;
+    }
+    for chunk in &mut chunks {
+        chunk.file_size =
+            u64::try_from(reader.i64()?).map_err(|_| invalid("negative chunk size"))?;
+    }
+
+    reader.seek(end)?;
+    Ok(chunks)
+}
+
+fn read_files(reader: &mut Reader) -> Result<Vec<FileEntry>> {
+    let (version, end) = reader.section()?;
+    let count = reader.count()?;
+
+    let mut files = vec![FileEntry::default(); count];
+    for file in &mut files {
+        file.filename = reader.fstring()?;
+    }
+    for file in &mut files {
+        file.symlink_target = reader.fstring()?;
+    }
+    for file in &mut files {
+        file.sha = reader.array()?;
+    }
+    for file in &mut files {
+        file.flags = reader.u8()?;
+    }
+    for file in &mut files {
+        file.install_tags = reader.fstrings()?;
+    }
+    for file in &mut files {
+        let parts = reader.count()?;
+        for _ in 0..parts {
+            let start = reader.pos;
+            let size = reader.u32()?;
+            file.parts.push(ChunkPart {
+                guid: reader.guid()?,
+                offset: reader.u32()?,
+                size: reader.u32()?,
+            });
+            reader.seek(start + size as usize)?;
+        }
+    }
+    if version >= 1 {
+        for file in &mut files {
+            if reader.u32()? != 0 {
+                file.md5 = Some(reader.array()?);
+            }
+        }
+        for file in &mut files {
+            file.mime_type = reader.fstring()?;
+        }
+    }
+    if version >= 2 {
+        // Zeroes stand for a file without one, next to files that have one
+        for file in &mut files {
+            let sha256: [u8; 32] = reader.array()?;
+            file.sha256 = (sha256 != [0; 32]).then_some(sha256);
+        }
+    }
+
+    reader.seek(end)?;
+    Ok(files)
+}
+
+fn read_custom_fields(reader: &mut Reader) -> Result<BTreeMap<String, String>> {
+    let (_, end) = reader.section()?;
+    let count = reader.count()?;
+
+    let keys = (0..count)
+        .map(|_| reader.fstring())
+        .collect::<Result<Vec<_>>>()?;
+    let values = (0..count)
+        .map(|_| reader.fstring())
+        .collect::<Result<Vec<_>>>()?;
+
+    reader.seek(end)?;
+    Ok(keys.into_iter().zip(values).collect())
+}
+
+pub(super) fn write(manifest: &Manifest) -> Vec<u8> {
+    let mut body = Vec::new();
+    write_meta(&mut body, &manifest.meta);
+    write_chunks(&mut body, &manifest.chunks);
+    write_files(&mut body, &manifest.files);
+    write_custom_fields(&mut body, manifest);
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&body).expect("writing to memory");
+    let compressed = encoder.finish().expect("writing to memory");
+
+    let mut data = Vec::with_capacity(HEADER_SIZE as usize + compressed.len());
+    put_u32(&mut data, MAGIC);
+    put_u32(&mut data, HEADER_SIZE);
+    put_u32(&mut data, body.len() as u32);
+    put_u32(&mut data, compressed.len() as u32);
+    data.extend_from_slice(&Sha1::digest(&body));
+    data.push(STORED_COMPRESSED);
+    put_u32(&mut data, manifest.version);
+    data.extend_from_slice(&compressed);
+    data
+}
+
+fn write_meta(out: &mut Vec<u8>, meta: &Meta) {
+    let mut section = Vec::new();
+    put_u32(&mut section, meta.feature_level);
+    section.push(u8::from(meta.is_file_data));
+    put_u32(&mut section, meta.app_id);
+    for value in [
+        &meta.app_name,
+        &meta.build_version,
+        &meta.launch_exe,
+        &meta.launch_command,
+    ] {
+        put_fstring(&mut section, value);
+    }
+    put_fstrings(&mut section, &meta.prereq_ids);
+    for value in [
+        &meta.prereq_name,
+        &meta.prereq_path,
+        &meta.prereq_args,
+        &meta.build_id,
+        &meta.uninstall_action_path,
+        &meta.uninstall_action_args,
+    ] {
+        put_fstring(&mut section, value);
+    }
+
+    put_section(out, META_VERSION, &section);
+}
+
+fn write_chunks(out: &mut Vec<u8>, chunks: &[ChunkInfo]) {
+    let mut section = Vec::new();
+    put_u32(&mut section, chunks.len() as u32);
+    for chunk in chunks {
+        put_guid(&mut section, chunk.guid);
+    }
+    for chunk in chunks {
+        section.extend_from_slice(&chunk.hash.to_le_bytes());
+    }
+    for chunk in chunks {
+        section.extend_from_slice(&chunk.sha);
+    }
+    for chunk in chunks {
+        section.push(chunk.group);
+    }
+    for chunk in chunks {
+        put_u32(&mut section, chunk.window_size);
+    }
+    for chunk in chunks {
+        section.extend_from_slice(&(chunk.file_size as i64).to_le_bytes());
+    }
+
+    put_section(out, CHUNKS_VERSION, &section);
+}
+
+fn write_files(out: &mut Vec<u8>, files: &[FileEntry]) {
+    let mut section = Vec::new();
+    put_u32(&mut section, files.len() as u32);
+    for file in files {
+        put_fstring(&mut section, &file.filename);
+    }
+    for file in files {
+        put_fstring(&mut section, &file.symlink_target);
+    }
+    for file in files {
+        section.extend_from_slice(&file.sha);
+    }
+    for file in files {
+        section.push(file.flags);
+    }
+    for file in files {
+        put_fstrings(&mut section, &file.install_tags);
+    }
+    for file in files {
+        put_u32(&mut section, file.parts.len() as u32);
+        for part in &file.parts {
+            put_u32(&mut section, CHUNK_PART_SIZE);
+            put_guid(&mut section, part.guid);
+            put_u32(&mut section, part.offset);
+            put_u32(&mut section, part.size);
+        }
+    }
+    for file in files {
+        match &file.md5 {
+            Some(md5) => {
+                put_u32(&mut section, 1);
+                section.extend_from_slice(md5);
+            }
+            None => put_u32(&mut section, 0),
+        }
+    }
+    for file in files {
+        put_fstring(&mut section, &file.mime_type);
+    }
+    // Hashes are left out when no file has one; otherwise files without one get zeroes,
+    // which read back as none
+    let version = if files.iter().any(|file| file.sha256.is_some()) {
+        for file in files {
+            section.extend_from_slice(&file.sha256.unwrap_or_default());
+        }
+        FILES_VERSION
+    } else {
+        FILES_VERSION - 1
+    };
+
+    put_section(out, version, &section);
+}
+
+fn write_custom_fields(out: &mut Vec<u8>, manifest: &Manifest) {
+    let mut section = Vec::new();
+    put_u32(&mut section, manifest.custom_fields.len() as u32);
+    for key in manifest.custom_fields.keys() {
+        put_fstring(&mut section, key);
+    }
+    for value in manifest.custom_fields.values() {
+        put_fstring(&mut section, value);
+    }
+
+    put_section(out, CUSTOM_FIELDS_VERSION, &section);
+}
+
+/// A section's size (its own field included), version and contents
+fn put_section(out: &mut Vec<u8>, version: u8, contents: &[u8]) {
+    put_u32(out, contents.len() as u32 + 5);
+    out.push(version);
+    out.extend_from_slice(contents);
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_guid(out: &mut Vec<u8>, guid: Guid) {
+    for part in guid.0 {
+        put_u32(out, part);
+    }
+}
+
+/// Unreal's string: a length counting the terminating NUL, negative for UTF-16
+fn put_fstring(out: &mut Vec<u8>, value: &str) {
+    if value.is_empty() {
+        put_u32(out, 0);
+    } else if value.is_ascii() {
+        out.extend_from_slice(&(value.len() as i32 + 1).to_le_bytes());
+        out.extend_from_slice(value.as_bytes());
+        out.push(0);
+    } else {
+        let units: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+        out.extend_from_slice(&(-(units.len() as i32)).to_le_bytes());
+        for unit in units {
+            out.extend_from_slice(&unit.to_le_bytes());
+        }
+    }
+}
+
+fn put_fstrings(out: &mut Vec<u8>, values: &[String]) {
+    put_u32(out, values.len() as u32);
+    for value in values {
+        put_fstring(out, value);
+    }
+}
+
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .pos
+            .checked_add(len)
+            .and_then(|end| self.data.get(self.pos..end))
+            .ok_or_else(|| invalid("unexpected end of data"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub(super) fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.bytes(N)?.try_into().expect("N bytes"))
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    pub(super) fn guid(&mut self) -> Result<Guid> {
+        Ok(Guid([self.u32()?, self.u32()?, self.u32()?, self.u32()?]))
+    }
+
+    /// A count of items that follow, each at least a byte long
+    fn count(&mut self) -> Result<usize> {
+        let count = self.u32()? as usize;
+        if count > self.data.len() - self.pos {
+            return Err(invalid("count larger than the data left"));
+        }
+        Ok(count)
+    }
+
+    /// Start of a section: its version, and where it ends
+    fn section(&mut self) -> Result<(u8, usize)> {
+        let start = self.pos;
+        let size = self.u32()? as usize;
+        let version = self.u8()?;
+        Ok((version, start + size))
+    }
+
+    fn seek(&mut self, pos: usize) -> Result<()> {
+        if pos < self.pos || pos > self.data.len() {
+            return Err(invalid("section overruns its size"));
+        }
+        self.pos = pos;
+        Ok(())
+    }
+
+    fn fstring(&mut self) -> Result<String> {
+        let len = self.u32()? as i32;
+        let mut value = if len >= 0 {
+            // Single-byte strings only hold Latin-1
+            self.bytes(len as usize)?
+                .iter()
+                .map(|&b| char::from(b))
+                .collect::<String>()
+        } else {
+            let units = self
+                .bytes(len.unsigned_abs() as usize * 2)?
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .collect::<Vec<_>>();
+            String::from_utf16(&units).map_err(|_| invalid("bad UTF-16 string"))?
+        };
+        if value.ends_with('\0') {
+            value.pop();
+        }
+        Ok(value)
+    }
+
+    fn fstrings(&mut self) -> Result<Vec<String>> {
+        let count = self.count()?;
+        (0..count).map(|_| self.fstring()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{FLAG_READ_ONLY, FLAG_UNIX_EXECUTABLE};
+
+    const FIXTURE: &[u8] = include_bytes!("fixtures/kiwi.manifest");
+
+    fn sha1(data: &[u8]) -> [u8; 20] {
+        Sha1::digest(data).into()
+    }
+
+    /// The fixture's header, marked `stored_as` and sized for `body`, followed by `body`
+    fn restored(stored_as: u8, body: &[u8]) -> Vec<u8> {
+        let mut data = FIXTURE[..HEADER_SIZE as usize].to_vec();
+        data[12..16].copy_from_slice(&(body.len() as u32).to_le_bytes());
+        data[36] = stored_as;
+        data.extend_from_slice(body);
+        data
+    }
+
+    fn fixture_body() -> Vec<u8> {
+        let mut body = Vec::new();
+        ZlibDecoder::new(&FIXTURE[HEADER_SIZE as usize..])
+            .read_to_end(&mut body)
+            .unwrap();
+        body
+    }
+
+    #[test]
+    fn test_read_fixture() {
+        let manifest = read(FIXTURE).unwrap();
+        assert_eq!(manifest.version, 18);
+
+        let meta = &manifest.meta;
+        assert_eq!(meta.feature_level, 18);
+        assert!(!meta.is_file_data);
+        assert_eq!(meta.app_id, 0);
+        assert_eq!(meta.app_name, "Kiwi");
+        assert_eq!(meta.build_version, "1.2.0-CL-1234+++Kiwi+Release");
+        assert_eq!(meta.launch_exe, "Binaries/Win64/Kiwi.exe");
+        assert_eq!(meta.launch_command, "-nosplash");
+        assert_eq!(meta.prereq_ids, vec!["vcredist-2019".to_string()]);
+        assert_eq!(meta.prereq_name, "Visual C++ 2019");
+        assert_eq!(meta.prereq_path, "Redist/vc_redist.x64.exe");
+        assert_eq!(meta.prereq_args, "/quiet /norestart");
+        assert_eq!(meta.build_id, "kiwi-build-7");
+        assert_eq!(meta.uninstall_action_path, "");
+
+        assert_eq!(
+            manifest.chunks,
+            vec![
+                ChunkInfo {
+                    guid: Guid([0x11111111, 0x22222222, 0x33333333, 0x44444444]),
+                    hash: 0x0123_4567_89AB_CDEF,
+                    sha: sha1(b"chunk a"),
+                    group: 12,
+                    window_size: 1024 * 1024,
+                    file_size: 524_288,
+                },
+                ChunkInfo {
+                    guid: Guid([0xAABBCCDD, 0x01020304, 0x0A0B0C0D, 0xDEADBEEF]),
+                    hash: 0xFEDC_BA98_7654_3210,
+                    sha: sha1(b"chunk b"),
+                    group: 40,
+                    window_size: 1024 * 1024,
+                    file_size: 1000,
+                },
+            ]
+        );
+
+        let [exe, pak, redist] = &manifest.files[..] else {
+            panic!("expected three files, got {:?}", manifest.files);
+        };
+        assert_eq!(exe.filename, "Binaries/Win64/Kiwi.exe");
+        assert_eq!(exe.sha, sha1(b"kiwi exe"));
+        assert_eq!(exe.flags, FLAG_UNIX_EXECUTABLE);
+        assert!(exe.install_tags.is_empty());
+        assert_eq!(exe.size(), 301_200);
+        assert_eq!(exe.parts[1].guid, manifest.chunks[1].guid);
+        assert_eq!(exe.mime_type, "application/octet-stream");
+        assert!(exe.md5.is_some());
+
+        // Stored as UTF-16
+        assert_eq!(pak.filename, "Content/Paks/Ünïcode ☕.pak");
+        assert_eq!(pak.flags, FLAG_READ_ONLY);
+        assert_eq!(pak.install_tags, vec!["content", "hd"]);
+        assert_eq!(
+            pak.parts,
+            vec![ChunkPart {
+                guid: manifest.chunks[0].guid,
+                offset: 300_000,
+                size: 100,
+            }]
+        );
+        assert_eq!(pak.md5, None);
+        assert_eq!(pak.mime_type, "");
+        assert_eq!(
+            pak.sha256.map(|sha| sha[..].to_vec()),
+            Some(sha2::Sha256::digest(pak.filename.as_bytes()).to_vec())
+        );
+
+        assert_eq!(redist.symlink_target, "");
+        assert_eq!(redist.install_tags, vec!["redist"]);
+
+        assert_eq!(manifest.custom_fields.len(), 2);
+        assert_eq!(
+            manifest.custom_fields["CloudSaveFolder"],
+            "{AppData}/Kiwi/Saves"
+        );
+    }
+
+    /// A manifest laid out byte by byte from the format's description rather than by `write`,
+    /// as older builds were published: version 0 sections, stored uncompressed, no custom
+    /// fields
+    const HAND_ASSEMBLED: &[u8] = &[
+        0x0C, 0xC0, 0xBE, 0x44, // magic
+        0x29, 0x00, 0x00, 0x00, // header size
+        0xD2, 0x00, 0x00, 0x00, // body size
+        0xD2, 0x00, 0x00, 0x00, // stored size
+        0x9D, 0x9E, 0x5A, 0x9C, 0x79, 0x13, 0xB1, 0x36, 0x69, 0x2C, // SHA-1 of the body
+        0xD5, 0x05, 0xAD, 0x42, 0x0F, 0xAF, 0x2C, 0xF9, 0x5B, 0x6D,
+        0x00, // stored uncompressed
+        0x11, 0x00, 0x00, 0x00, // version
+        0x3E, 0x00, 0x00, 0x00, 0x00, // meta section: size, version 0
+        0x11, 0x00, 0x00, 0x00, // feature level
+        0x00, // not file data
+        0x00, 0x00, 0x00, 0x00, // app id
+        0x04, 0x00, 0x00, 0x00, 0x46, 0x69, 0x67, 0x00, // app name
+        0x04, 0x00, 0x00, 0x00, 0x32, 0x2E, 0x30, 0x00, // build version
+        0x08, 0x00, 0x00, 0x00, 0x46, 0x69, 0x67, 0x2E, 0x65, 0x78, // launch exe
+        0x65, 0x00, 0x00, 0x00, 0x00, 0x00, // launch command
+        0x00, 0x00, 0x00, 0x00, // no prerequisite ids
+        0x00, 0x00, 0x00, 0x00, // prerequisite name
+        0x00, 0x00, 0x00, 0x00, // prerequisite path
+        0x00, 0x00, 0x00, 0x00, // prerequisite args
+        0x42, 0x00, 0x00, 0x00, 0x00, // chunks section: size, version 0
+        0x01, 0x00, 0x00, 0x00, // one chunk
+        0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00, // its GUID
+        0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22,
+        0x11, // rolling hash
+        0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, // SHA-1
+        0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0x05, // group
+        0x00, 0x00, 0x10, 0x00, // window size
+        0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // file size
+        0x52, 0x00, 0x00, 0x00, 0x00, // files section: size, version 0
+        0x01, 0x00, 0x00, 0x00, // one file
+        0x08, 0x00, 0x00, 0x00, 0x46, 0x69, 0x67, 0x2E, 0x65, 0x78, // filename
+        0x65, 0x00, 0x00, 0x00, 0x00, 0x00, // no symlink
+        0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, // SHA-1
+        0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0x04, // unix executable
+        0x00, 0x00, 0x00, 0x00, // no install tags
+        0x01, 0x00, 0x00, 0x00, // one part
+        0x1C, 0x00, 0x00, 0x00, // part size
+        0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00, // chunk GUID
+        0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // offset
+        0x64, 0x00, 0x00, 0x00, // size
+    ];
+
+    #[test]
+    fn test_read_hand_assembled_manifest() {
+        let manifest = read(HAND_ASSEMBLED).unwrap();
+        assert_eq!(manifest.version, 17);
+        assert_eq!(manifest.meta.feature_level, 17);
+        assert_eq!(manifest.meta.app_name, "Fig");
+        assert_eq!(manifest.meta.build_version, "2.0");
+        assert_eq!(manifest.meta.launch_exe, "Fig.exe");
+        assert_eq!(manifest.meta.build_id, "");
+        assert!(manifest.custom_fields.is_empty());
+
+        assert_eq!(
+            manifest.chunks,
+            vec![ChunkInfo {
+                guid: Guid([1, 2, 3, 4]),
+                hash: 0x1122_3344_5566_7788,
+                sha: [0xAA; 20],
+                group: 5,
+                window_size: 1024 * 1024,
+                file_size: 2048,
+            }]
+        );
+        let [file] = &manifest.files[..] else {
+            panic!("expected one file, got {:?}", manifest.files);
+        };
+        assert_eq!(file.filename, "Fig.exe");
+        assert_eq!(file.sha, [0xBB; 20]);
+        assert_eq!(file.flags, FLAG_UNIX_EXECUTABLE);
+        assert_eq!(
+            file.parts,
+            vec![ChunkPart {
+                guid: Guid([1, 2, 3, 4]),
+                offset: 0,
+                size: 100,
+            }]
+        );
+        assert_eq!((file.md5, file.sha256), (None, None));
+    }
+
+    #[test]
+    fn test_missing_sha256_is_left_out() {
+        let mut manifest = read(FIXTURE).unwrap();
+        for file in &mut manifest.files {
+            file.sha256 = None;
+        }
+        assert_eq!(read(&write(&manifest)).unwrap(), manifest);
+
+        // Next to a file that has one, it reads back as none too
+        manifest.files[0].sha256 = Some([7; 32]);
+        assert_eq!(read(&write(&manifest)).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_write_round_trips() {
+        let manifest = read(FIXTURE).unwrap();
+        let written = write(&manifest);
+        assert!(is_binary(&written));
+        assert_eq!(read(&written).unwrap(), manifest);
+
+        // The body is laid out byte for byte as in the fixture
+        let mut body = Vec::new();
+        ZlibDecoder::new(&written[HEADER_SIZE as usize..])
+            .read_to_end(&mut body)
+            .unwrap();
+        assert_eq!(body, fixture_body());
+    }
+
+    #[test]
+    fn test_write_empty_manifest() {
+        let manifest = Manifest::default();
+        assert_eq!(read(&write(&manifest)).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_read_uncompressed() {
+        let body = fixture_body();
+        let manifest = read(&restored(0, &body)).unwrap();
+        assert_eq!(manifest, read(FIXTURE).unwrap());
+    }
+
+    #[test]
+    fn test_skips_fields_of_newer_versions() {
+        let mut manifest = read(FIXTURE).unwrap();
+        manifest.custom_fields.clear();
+        let mut body = Vec::new();
+        write_meta(&mut body, &manifest.meta);
+        write_chunks(&mut body, &manifest.chunks);
+        write_files(&mut body, &manifest.files);
+
+        // A newer chunk list with a field after the known ones
+        let chunks_start = u32::from_le_bytes(body[..4].try_into().unwrap()) as usize;
+        let chunks_size =
+            u32::from_le_bytes(body[chunks_start..chunks_start + 4].try_into().unwrap()) as usize;
+        let chunks_end = chunks_start + chunks_size;
+        body.splice(chunks_end..chunks_end, [0xAB; 6]);
+        body[chunks_start..chunks_start + 4]
+            .copy_from_slice(&(chunks_size as u32 + 6).to_le_bytes());
+        body[chunks_start + 4] = 7;
+
+        let mut data = restored(0, &body);
+        data[8..12].copy_from_slice(&(body.len() as u32).to_le_bytes());
+        data[16..36].copy_from_slice(&sha1(&body));
+
+        // Without custom fields, as manifests older than them end
+        assert_eq!(read(&data).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_rejects_damaged_manifests() {
+        assert!(!is_binary(b"{}"));
+        assert!(read(&FIXTURE[..20]).is_err());
+        assert!(read(&FIXTURE[..FIXTURE.len() - 10]).is_err());
+
+        let mut wrong_hash = FIXTURE.to_vec();
+        wrong_hash[16] ^= 0xFF;
+        assert!(read(&wrong_hash).is_err());
+
+        let mut encrypted = FIXTURE.to_vec();
+        encrypted[36] |= STORED_ENCRYPTED;
+        assert!(read(&encrypted).is_err());
+
+        // A count running past the end of the body
+        let mut body = fixture_body();
+        let meta_size = u32::from_le_bytes(body[..4].try_into().unwrap()) as usize;
+        body[meta_size + 5..meta_size + 9].copy_from_slice(&u32::MAX.to_le_bytes());
+        let mut data = restored(0, &body);
+        data[16..36].copy_from_slice(&sha1(&body));
+        assert!(read(&data).is_err());
+    }
+
+    #[test]
+    fn test_fstring() {
+        for value in ["", "Kiwi", "Ünïcode ☕", "Latin-1 ÿ"] {
+            let mut data = Vec::new();
+            put_fstring(&mut data, value);
+            assert_eq!(Reader::new(&data).fstring().unwrap(), value);
+        }
+
+        // Epic writes Latin-1 as single bytes too
+        let latin1 = [4, 0, 0, 0, b'K', 0xE9, b'w', 0];
+        assert_eq!(Reader::new(&latin1).fstring().unwrap(), "Kéw");
+
+        let truncated = [10, 0, 0, 0, b'K'];
+        assert!(Reader::new(&truncated).fstring().is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Chunk files as stored on Epic's CDN: a header naming the chunk and how it's stored, then
+//! its data, usually zlib-compressed. Chunks sit under a directory picked by the manifest's
+//! version and their group, named after their rolling hash and GUID.
+
+use flate2::read::ZlibDecoder;
+use sha1::{Digest, Sha1};
+use std::io::Read;
+
+use super::binary::Reader;
+use super::{invalid, Guid};
+use crate::Result;
+
+const MAGIC: u32 = 0xB1FE_3AA2;
+
+/// Set in the header when the data is zlib-compressed
+const STORED_COMPRESSED: u8 = 0x1;
+/// Set in the header when the data is encrypted, which nothing published uses
+const STORED_ENCRYPTED: u8 = 0x2;
+
+/// Set in the header's hash type when it carries the SHA-1 of the data
+const HASH_SHA1: u8 = 0x2;
+
+/// Chunks are at most 1 MiB uncompressed; anything claiming much more is refused rather
+/// than allocated
+const MAX_DATA_SIZE: u32 = 64 * 1024 * 1024;
+
+/// Where a chunk is found below a CDN base URL, for a manifest of `version`
+pub fn path(version: u32, group: u8, hash: u64, guid: &Guid) -> String {
+    let dir = match version {
+        15.. => "ChunksV4",
+        6.. => "ChunksV3",
+        3.. => "ChunksV2",
+        _ => "Chunks",
+    };
+    format!("{}/{:02}/{:016X}_{}.chunk", dir, group, hash, guid)
+}
+
+/// The data of a downloaded chunk file, decompressed and checked against the SHA-1 in its
+/// header when there is one
+pub fn decode(file: &[u8]) -> Result<Vec<u8>> {
+    let mut header = Reader::new(file);
+    if header.u32()? != MAGIC {
+        return Err(invalid("not a chunk file"));
+    }
+    let version = header.u32()?;
+    let header_size = header.u32()?;
+    let stored_size = header.u32()?;
+    let guid = header.guid()?;
+    let _rolling_hash = header.u64()?;
+    let stored_as = header.u8()?;
+    let sha = if version >= 2 {
+        let sha = header.array::<20>()?;
+        (header.u8()? & HASH_SHA1 != 0).then_some(sha)
+    } else {
+        None
+    };
+    let size = if version >= 3 {
+        Some(header.u32()?)
+    } else {
+        None
+    };
+
+    if stored_as & STORED_ENCRYPTED != 0 {
+        return Err(invalid(&format!("chunk {} is encrypted", guid)));
+    }
+    let stored = (header_size as usize)
+        .checked_add(stored_size as usize)
+        .and_then(|end| file.get(header_size as usize..end))
+        .ok_or_else(|| invalid(&format!("chunk {} is truncated", guid)))?;
+
+    let data = if stored_as & STORED_COMPRESSED != 0 {
+        let limit = size.unwrap_or(MAX_DATA_SIZE).min(MAX_DATA_SIZE);
+        let mut data = Vec::with_capacity(limit as usize);
+        ZlibDecoder::new(stored)
+            .take(u64::from(limit) + 1)
+            .read_to_end(&mut data)
+            .map_err(|e| invalid(&format!("can't decompress chunk {}: {}", guid, e)))?;
+        data
+    } else {
+        stored.to_vec()
+    };
+    if size.is_some_and(|size| data.len() != size as usize) {
+        return Err(invalid(&format!(
+            "chunk {} size doesn't match its header",
+            guid
+        )));
+    }
+    if sha.is_some_and(|sha| Sha1::digest(&data)[..] != sha) {
+        return Err(invalid(&format!(
+            "chunk {} hash doesn't match its header",
+            guid
+        )));
+    }
+    Ok(data)
+}
+
+/// A chunk file holding `data`, compressed, as the CDN serves it
+#[cfg(test)]
+pub(crate) fn encode(guid: &Guid, hash: u64, data: &[u8]) -> Vec<u8> {
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    let stored = encoder.finish().unwrap();
+
+    let mut file = Vec::new();
+    file.extend_from_slice(&MAGIC.to_le_bytes());
+    file.extend_from_slice(&3u32.to_le_bytes());
+    file.extend_from_slice(&66u32.to_le_bytes());
+    file.extend_from_slice(&(stored.len() as u32).to_le_bytes());
+    for part in guid.0 {
+        file.extend_from_slice(&part.to_le_bytes());
+    }
+    file.extend_from_slice(&hash.to_le_bytes());
+    file.push(STORED_COMPRESSED);
+    file.extend_from_slice(&Sha1::digest(data));
+    file.push(HASH_SHA1);
+    file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    file.extend_from_slice(&stored);
+    file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUID: Guid = Guid([0xAABBCCDD, 0x01020304, 0x0A0B0C0D, 0xDEADBEEF]);
+
+    #[test]
+    fn test_path() {
+        assert_eq!(
+            path(18, 4, 0x0123_4567_89AB_CDEF, &GUID),
+            "ChunksV4/04/0123456789ABCDEF_AABBCCDD010203040A0B0C0DDEADBEEF.chunk"
+        );
+        assert!(path(6, 40, 1, &GUID).starts_with("ChunksV3/40/0000000000000001_"));
+        assert!(path(2, 0, 1, &GUID).starts_with("Chunks/00/"));
+    }
+
+    #[test]
+    fn test_decode_round_trips() {
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+        let file = encode(&GUID, 7, &data);
+        assert!(file.len() < data.len());
+        assert_eq!(decode(&file).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_stored_uncompressed() {
+        // A version 1 header: no SHA-1 or size, data stored as is
+        let mut file = encode(&GUID, 7, b"")[..41].to_vec();
+        file[4..8].copy_from_slice(&1u32.to_le_bytes());
+        file[8..12].copy_from_slice(&41u32.to_le_bytes());
+        file[12..16].copy_from_slice(&4u32.to_le_bytes());
+        file[40] = 0;
+        file.extend_from_slice(b"kiwi");
+        assert_eq!(decode(&file).unwrap(), b"kiwi");
+    }
+
+    #[test]
+    fn test_rejects_damaged_chunks() {
+        let file = encode(&GUID, 7, b"kiwi kiwi kiwi");
+        assert!(decode(b"").is_err());
+        assert!(decode(&file[..file.len() - 1]).is_err());
+
+        let mut wrong_magic = file.clone();
+        wrong_magic[0] ^= 0xFF;
+        assert!(decode(&wrong_magic).is_err());
+
+        // The data no longer matches the SHA-1 in the header
+        let mut wrong_sha = file.clone();
+        wrong_sha[41] ^= 0xFF;
+        assert!(decode(&wrong_sha).is_err());
+
+        let mut encrypted = file;
+        encrypted[40] |= STORED_ENCRYPTED;
+        assert!(decode(&encrypted).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+//! The JSON manifest format. Numbers and hashes are "blobs": every byte written as three
+//! decimal digits, least significant byte first, so "018000000000" is the 32-bit 18.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use super::{
+    invalid, ChunkInfo, ChunkPart, FileEntry, Guid, Manifest, Meta, FLAG_COMPRESSED,
+    FLAG_READ_ONLY, FLAG_UNIX_EXECUTABLE,
+};
+use crate::Result;
+
+/// Chunks are always this large once uncompressed; the format doesn't record it
+const WINDOW_SIZE: u32 = 1024 * 1024;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct JsonManifest {
+    manifest_file_version: String,
+    #[serde(rename = "bIsFileData", default)]
+    is_file_data: bool,
+    #[serde(rename = "AppID", default)]
+    app_id: Option<String>,
+    app_name_string: String,
+    build_version_string: String,
+    #[serde(default)]
+    launch_exe_string: String,
+    #[serde(default)]
+    launch_command: String,
+    #[serde(default)]
+    prereq_ids: Vec<String>,
+    #[serde(default)]
+    prereq_name: String,
+    #[serde(default)]
+    prereq_path: String,
+    #[serde(default)]
+    prereq_args: String,
+    file_manifest_list: Vec<JsonFile>,
+    chunk_hash_list: BTreeMap<String, String>,
+    #[serde(default)]
+    chunk_sha_list: BTreeMap<String, String>,
+    data_group_list: BTreeMap<String, String>,
+    chunk_filesize_list: BTreeMap<String, String>,
+    #[serde(default)]
+    custom_fields: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct JsonFile {
+    filename: String,
+    file_hash: String,
+    file_chunk_parts: Vec<JsonChunkPart>,
+    #[serde(default)]
+    install_tags: Vec<String>,
+    #[serde(default)]
+    symlink_target: String,
+    #[serde(rename = "bIsReadOnly", default)]
+    is_read_only: bool,
+    #[serde(rename = "bIsCompressed", default)]
+    is_compressed: bool,
+    #[serde(rename = "bIsUnixExecutable", default)]
+    is_unix_executable: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct JsonChunkPart {
+    guid: String,
+    offset: String,
+    size: String,
+}
+
+pub(super) fn read(data: &[u8]) -> Result<Manifest> {
+    let json: JsonManifest = serde_json::from_slice(data)
+        .map_err(|e| invalid(&format!("neither binary nor JSON ({})", e)))?;
+
+    let version = blob_u32(&json.manifest_file_version)?;
+    let meta = Meta {
+        feature_level: version,
+        is_file_data: json.is_file_data,
+        app_id: json
+            .app_id
+            .as_deref()
+            .map(blob_u32)
+            .transpose()?
+            .unwrap_or(0),
+        app_name: json.app_name_string,
+        build_version: json.build_version_string,
+        launch_exe: json.launch_exe_string,
+        launch_command: json.launch_command,
+        prereq_ids: json.prereq_ids,
+        prereq_name: json.prereq_name,
+        prereq_path: json.prereq_path,
+        prereq_args: json.prereq_args,
+        ..Meta::default()
+    };
+
+    let mut chunks = Vec::with_capacity(json.chunk_hash_list.len());
+    for (guid, hash) in &json.chunk_hash_list {
+        let field = |list: &BTreeMap<String, String>, name: &str| {
+            list.get(guid)
+                .ok_or_else(|| invalid(&format!("chunk {} has no {}", guid, name)))
+                .cloned()
+        };
+        let sha = match json.chunk_sha_list.get(guid) {
+            Some(sha) => hex_sha(sha)?,
+            None => [0; 20],
+        };
+
+        chunks.push(ChunkInfo {
+            guid: guid.parse()?,
+            hash: blob_u64(hash)?,
+            sha,
+            group: blob_u64(&field(&json.data_group_list, "data group")?)? as u8,
+            window_size: WINDOW_SIZE,
+            file_size: blob_u64(&field(&json.chunk_filesize_list, "file size")?)?,
+        });
+    }
+
+    let files = json
+        .file_manifest_list
+        .into_iter()
+        .map(read_file)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Manifest {
+        version,
+        meta,
+        chunks,
+        files,
+        custom_fields: json.custom_fields,
+    })
+}
+
+fn read_file(file: JsonFile) -> Result<FileEntry> {
+    let sha = blob(&file.file_hash)?
+        .try_into()
+        .map_err(|_| invalid(&format!("hash of {} isn't 20 bytes", file.filename)))?;
+
+    let mut flags = 0;
+    for (set, flag) in [
+        (file.is_read_only, FLAG_READ_ONLY),
+        (file.is_compressed, FLAG_COMPRESSED),
+        (file.is_unix_executable, FLAG_UNIX_EXECUTABLE),
+    ] {
+        if set {
+            flags |= flag;
+        }
+    }
+
+    let parts = file
+        .file_chunk_parts
+        .iter()
+        .map(|part| {
+            Ok(ChunkPart {
+                guid: part.guid.parse::<Guid>()?,
+                offset: blob_u32(&part.offset)?,
+                size: blob_u32(&part.size)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(FileEntry {
+        filename: file.filename,
+        symlink_target: file.symlink_target,
+        sha,
+        flags,
+        install_tags: file.install_tags,
+        parts,
+        ..FileEntry::default()
+    })
+}
+
+/// The bytes of a blob
+fn blob(value: &str) -> Result<Vec<u8>> {
+    if !value.len().is_multiple_of(3) || !value.is_ascii() {
+        return Err(invalid(&format!("bad blob '{}'", value)));
+    }
+    value
+        .as_bytes()
+        .chunks(3)
+        .map(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .and_then(|digits| digits.parse::<u8>().ok())
+                .ok_or_else(|| invalid(&format!("bad blob '{}'", value)))
+        })
+        .collect()
+}
+
+/// A number stored as a blob, of at most 8 bytes
+fn blob_u64(value: &str) -> Result<u64> {
+    let bytes = blob(value)?;
+    if bytes.len() > 8 {
+        return Err(invalid(&format!(
+            "blob '{}' is too long for a number",
+            value
+        )));
+    }
+    let mut le = [0; 8];
+    le[..bytes.len()].copy_from_slice(&bytes);
+    Ok(u64::from_le_bytes(le))
+}
+
+fn blob_u32(value: &str) -> Result<u32> {
+    u32::try_from(blob_u64(value)?).map_err(|_| invalid(&format!("blob '{}' is too large", value)))
+}
+
+fn hex_sha(value: &str) -> Result<[u8; 20]> {
+    let bad = || invalid(&format!("bad SHA-1 '{}'", value));
+    if value.len() != 40 || !value.is_ascii() {
+        return Err(bad());
+    }
+    let mut sha = [0; 20];
+    for (i, byte) in sha.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[i * 2..i * 2 + 2], 16).map_err(|_| bad())?;
+    }
+    Ok(sha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha1::{Digest, Sha1};
+
+    const FIXTURE: &[u8] = include_bytes!("fixtures/kiwi.json");
+
+    #[test]
+    fn test_read_fixture() {
+        let manifest = read(FIXTURE).unwrap();
+        assert_eq!(manifest.version, 18);
+
+        let meta = &manifest.meta;
+        assert_eq!(meta.feature_level, 18);
+        assert_eq!(meta.app_name, "Kiwi");
+        assert_eq!(meta.build_version, "1.2.0-CL-1234+++Kiwi+Release");
+        assert_eq!(meta.launch_exe, "Binaries/Win64/Kiwi.exe");
+        assert_eq!(meta.prereq_ids, vec!["vcredist-2019".to_string()]);
+        assert_eq!(meta.prereq_args, "/quiet /norestart");
+        // Not part of the format
+        assert_eq!(meta.build_id, "");
+
+        assert_eq!(manifest.chunks.len(), 2);
+        let chunk = &manifest.chunks[1];
+        assert_eq!(chunk.guid.to_string(), "AABBCCDD010203040A0B0C0DDEADBEEF");
+        assert_eq!(chunk.hash, 0xFEDC_BA98_7654_3210);
+        assert_eq!(chunk.sha[..], Sha1::digest(b"chunk b")[..]);
+        assert_eq!(chunk.group, 40);
+        assert_eq!(chunk.window_size, WINDOW_SIZE);
+        assert_eq!(chunk.file_size, 1000);
+
+        let [exe, pak, redist] = &manifest.files[..] else {
+            panic!("expected three files, got {:?}", manifest.files);
+        };
+        assert_eq!(exe.sha[..], Sha1::digest(b"kiwi exe")[..]);
+        assert_eq!(exe.flags, FLAG_UNIX_EXECUTABLE);
+        assert_eq!(exe.size(), 301_200);
+        assert_eq!(pak.filename, "Content/Paks/Ünïcode ☕.pak");
+        assert_eq!(pak.flags, FLAG_READ_ONLY);
+        assert_eq!(pak.install_tags, vec!["content", "hd"]);
+        assert_eq!(pak.parts[0].offset, 300_000);
+        assert_eq!(redist.flags, 0);
+        assert_eq!(redist.md5, None);
+        assert_eq!(redist.sha256, None);
+
+        assert_eq!(manifest.custom_fields["BuildLabel"], "Live");
+    }
+
+    #[test]
+    fn test_blobs() {
+        assert_eq!(blob("").unwrap(), Vec::<u8>::new());
+        assert_eq!(blob("001255").unwrap(), vec![1, 255]);
+        assert_eq!(blob_u32("018000000000").unwrap(), 18);
+        assert_eq!(blob_u32("224147").unwrap(), 0x93E0);
+        assert_eq!(
+            blob_u64("239205171137103069035001").unwrap(),
+            0x0123_4567_89AB_CDEF
+        );
+
+        assert!(blob("12").is_err());
+        assert!(blob("256").is_err());
+        assert!(blob("0a1").is_err());
+        assert!(blob_u64("001002003004005006007008009").is_err());
+        assert!(blob_u32("000000000000001").is_err());
+    }
+
+    #[test]
+    fn test_rejects_incomplete_manifests() {
+        assert!(read(b"{}").is_err());
+        assert!(read(b"[1, 2]").is_err());
+
+        // A chunk without its data group
+        let json = String::from_utf8(FIXTURE.to_vec()).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["DataGroupList"]
+            .as_object_mut()
+            .unwrap()
+            .remove("11111111222222223333333344444444");
+        assert!(read(value.to_string().as_bytes()).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["FileManifestList"][0]["FileHash"] = "001002".into();
+        assert!(read(value.to_string().as_bytes()).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["FileManifestList"][0]["FileChunkParts"][0]["Guid"] = "1234".into();
+        assert!(read(value.to_string().as_bytes()).is_err());
+    }
+}
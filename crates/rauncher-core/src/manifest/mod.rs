@@ -0,0 +1,313 @@
+//! Epic's build manifests: the files a build is made of and the chunks they're assembled from.
+//! Older builds are described in JSON and newer ones in a packed binary format; both are read
+//! into [`Manifest`], which is written back in the binary format. The chunks themselves are
+//! read by [`chunk`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::api::{self, GameManifest, Prerequisite};
+use crate::{Error, Result};
+
+mod binary;
+pub mod chunk;
+mod json;
+
+/// Set in a file's flags when it must stay read-only
+pub const FLAG_READ_ONLY: u8 = 0x1;
+/// Set in a file's flags when it's stored compressed
+pub const FLAG_COMPRESSED: u8 = 0x2;
+/// Set in a file's flags when it's executable on Mac and Linux
+pub const FLAG_UNIX_EXECUTABLE: u8 = 0x4;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    /// Version of the format the manifest was written in
+    pub version: u32,
+    pub meta: Meta,
+    pub chunks: Vec<ChunkInfo>,
+    pub files: Vec<FileEntry>,
+    /// Settings of the build, e.g. where its cloud saves are kept
+    pub custom_fields: BTreeMap<String, String>,
+}
+
+/// What the build is and how it's started
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Meta {
+    pub feature_level: u32,
+    /// Files are stored whole rather than split into chunks
+    pub is_file_data: bool,
+    pub app_id: u32,
+    pub app_name: String,
+    pub build_version: String,
+    pub launch_exe: String,
+    pub launch_command: String,
+    pub prereq_ids: Vec<String>,
+    pub prereq_name: String,
+    pub prereq_path: String,
+    pub prereq_args: String,
+    /// Empty in JSON manifests and older binary ones
+    pub build_id: String,
+    pub uninstall_action_path: String,
+    pub uninstall_action_args: String,
+}
+
+/// A chunk files are assembled from, downloaded from the CDN as a whole
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkInfo {
+    pub guid: Guid,
+    /// Rolling hash of the chunk's data
+    pub hash: u64,
+    pub sha: [u8; 20],
+    /// Picks the CDN directory the chunk is stored in
+    pub group: u8,
+    /// Size of the chunk's data once uncompressed
+    pub window_size: u32,
+    /// Size of the chunk file downloaded
+    pub file_size: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileEntry {
+    pub filename: String,
+    /// Where the file links to, if it's a symlink
+    pub symlink_target: String,
+    pub sha: [u8; 20],
+    /// `FLAG_*` bits
+    pub flags: u8,
+    /// Optional components the file belongs to, e.g. a language pack; empty when always
+    /// installed
+    pub install_tags: Vec<String>,
+    pub parts: Vec<ChunkPart>,
+    pub md5: Option<[u8; 16]>,
+    pub mime_type: String,
+    pub sha256: Option<[u8; 32]>,
+}
+
+/// A range of a chunk's data, copied into a file in order
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkPart {
+    pub guid: Guid,
+    pub offset: u32,
+    pub size: u32,
+}
+
+/// A chunk's identity, written as 32 uppercase hex digits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guid(pub [u32; 4]);
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d] = self.0;
+        write!(f, "{:08X}{:08X}{:08X}{:08X}", a, b, c, d)
+    }
+}
+
+impl FromStr for Guid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 32 || !s.is_ascii() {
+            return Err(invalid(&format!("bad chunk GUID '{}'", s)));
+        }
+        let mut parts = [0; 4];
+        for (i, part) in parts.iter_mut().enumerate() {
+            *part = u32::from_str_radix(&s[i * 8..i * 8 + 8], 16)
+                .map_err(|_| invalid(&format!("bad chunk GUID '{}'", s)))?;
+        }
+        Ok(Self(parts))
+    }
+}
+
+impl FileEntry {
+    pub fn size(&self) -> u64 {
+        self.parts.iter().map(|part| u64::from(part.size)).sum()
+    }
+}
+
+/// Read a manifest in either format, told apart by the binary one's magic number
+pub fn parse(data: &[u8]) -> Result<Manifest> {
+    if binary::is_binary(data) {
+        binary::read(data)
+    } else {
+        json::read(data)
+    }
+}
+
+impl Manifest {
+    /// The manifest in the binary format, compressed
+    pub fn to_binary(&self) -> Vec<u8> {
+        binary::write(self)
+    }
+
+    pub fn build_size(&self) -> u64 {
+        self.files.iter().map(FileEntry::size).sum()
+    }
+}
+
+impl From<Manifest> for GameManifest {
+    fn from(manifest: Manifest) -> Self {
+        let build_size = manifest.build_size();
+        let meta = manifest.meta;
+
+        let mut data_group_list: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for chunk in &manifest.chunks {
+            data_group_list
+                .entry(format!("{:02}", chunk.group))
+                .or_default()
+                .push(chunk.guid.to_string());
+        }
+
+        let prerequisite = (!meta.prereq_path.is_empty()).then_some(Prerequisite {
+            ids: meta.prereq_ids,
+            name: meta.prereq_name,
+            path: meta.prereq_path,
+            args: meta.prereq_args,
+        });
+
+        GameManifest {
+            manifest_file_version: manifest.version.to_string(),
+            is_file_data: meta.is_file_data,
+            app_name: meta.app_name,
+            app_version: meta.build_version,
+            launch_exe: meta.launch_exe,
+            launch_command: meta.launch_command,
+            build_size,
+            file_list: manifest
+                .files
+                .into_iter()
+                .map(|file| api::FileManifest {
+                    filename: file.filename,
+                    file_hash: file.sha.to_vec(),
+                    file_chunk_parts: file
+                        .parts
+                        .iter()
+                        .map(|part| api::ChunkPart {
+                            guid: part.guid.to_string(),
+                            offset: part.offset.into(),
+                            size: part.size.into(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            chunk_hash_list: manifest
+                .chunks
+                .iter()
+                .map(|chunk| (chunk.guid.to_string(), format!("{:016X}", chunk.hash)))
+                .collect(),
+            chunk_sha_list: manifest
+                .chunks
+                .iter()
+                .map(|chunk| (chunk.guid.to_string(), chunk.sha.to_vec()))
+                .collect(),
+            data_group_list,
+            chunk_filesize_list: manifest
+                .chunks
+                .iter()
+                .map(|chunk| (chunk.guid.to_string(), chunk.file_size))
+                .collect(),
+            custom_fields: manifest.custom_fields,
+            prerequisite,
+        }
+    }
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::Api(format!("Invalid Epic manifest: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON_FIXTURE: &[u8] = include_bytes!("fixtures/kiwi.json");
+    const BINARY_FIXTURE: &[u8] = include_bytes!("fixtures/kiwi.manifest");
+
+    #[test]
+    fn test_guid() {
+        let guid: Guid = "AABBCCDD010203040A0B0C0DDEADBEEF".parse().unwrap();
+        assert_eq!(guid, Guid([0xAABBCCDD, 0x01020304, 0x0A0B0C0D, 0xDEADBEEF]));
+        assert_eq!(guid.to_string(), "AABBCCDD010203040A0B0C0DDEADBEEF");
+        assert_eq!(
+            "aabbccdd010203040a0b0c0ddeadbeef".parse::<Guid>().unwrap(),
+            guid
+        );
+
+        assert!("AABBCCDD".parse::<Guid>().is_err());
+        assert!("XABBCCDD010203040A0B0C0DDEADBEEF".parse::<Guid>().is_err());
+        assert!("ÄBBCCDD010203040A0B0C0DDEADBEEF".parse::<Guid>().is_err());
+    }
+
+    #[test]
+    fn test_parse_detects_format() {
+        let from_json = parse(JSON_FIXTURE).unwrap();
+        let from_binary = parse(BINARY_FIXTURE).unwrap();
+        assert_eq!(from_json.meta.app_name, "Kiwi");
+        assert_eq!(from_binary.meta.app_name, "Kiwi");
+
+        assert!(parse(b"").is_err());
+        assert!(parse(b"not a manifest").is_err());
+    }
+
+    #[test]
+    fn test_both_formats_describe_the_same_build() {
+        let from_json = GameManifest::from(parse(JSON_FIXTURE).unwrap());
+        let from_binary = GameManifest::from(parse(BINARY_FIXTURE).unwrap());
+
+        // GameManifest has no Eq; its JSON form compares every field
+        assert_eq!(
+            serde_json::to_value(&from_json).unwrap(),
+            serde_json::to_value(&from_binary).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_game_manifest_conversion() {
+        let game = GameManifest::from(parse(BINARY_FIXTURE).unwrap());
+
+        assert_eq!(game.manifest_file_version, "18");
+        assert_eq!(game.app_name, "Kiwi");
+        assert_eq!(game.app_version, "1.2.0-CL-1234+++Kiwi+Release");
+        assert_eq!(game.launch_exe, "Binaries/Win64/Kiwi.exe");
+        assert_eq!(game.launch_command, "-nosplash");
+        assert_eq!(game.build_size, 301_350);
+        // Both chunks, compressed, each counted once
+        assert_eq!(game.download_size(), 525_288);
+        assert_eq!(game.download_size_of(&game.file_list[2..]), 1000);
+        assert!(!game.is_file_data);
+
+        assert_eq!(game.file_list.len(), 3);
+        let exe = &game.file_list[0];
+        assert_eq!(exe.file_chunk_parts.len(), 2);
+        assert_eq!(
+            exe.file_chunk_parts[1].guid,
+            "AABBCCDD010203040A0B0C0DDEADBEEF"
+        );
+        assert_eq!(exe.file_chunk_parts[1].size, 1200);
+        assert_eq!(game.file_list[1].file_chunk_parts[0].offset, 300_000);
+
+        assert_eq!(
+            game.chunk_hash_list["11111111222222223333333344444444"],
+            "0123456789ABCDEF"
+        );
+        assert_eq!(game.chunk_sha_list.len(), 2);
+        assert_eq!(
+            game.data_group_list["40"],
+            vec!["AABBCCDD010203040A0B0C0DDEADBEEF".to_string()]
+        );
+
+        assert_eq!(
+            game.prerequisite,
+            Some(Prerequisite {
+                ids: vec!["vcredist-2019".to_string()],
+                name: "Visual C++ 2019".to_string(),
+                path: "Redist/vc_redist.x64.exe".to_string(),
+                args: "/quiet /norestart".to_string(),
+            })
+        );
+
+        let without_prerequisite = Manifest::default();
+        assert_eq!(GameManifest::from(without_prerequisite).prerequisite, None);
+    }
+}
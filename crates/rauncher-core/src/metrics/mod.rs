@@ -0,0 +1,161 @@
+//! Process-wide counters and gauges of downloads, store requests and logins, exported in
+//! Prometheus' text format by `rauncher daemon --listen` at `/metrics`.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+static API_REQUESTS: AtomicU64 = AtomicU64::new(0);
+static API_ERRORS: AtomicU64 = AtomicU64::new(0);
+static TOKEN_REFRESHES: AtomicU64 = AtomicU64::new(0);
+static TOKEN_REFRESH_FAILURES: AtomicU64 = AtomicU64::new(0);
+static DOWNLOADED_BYTES: AtomicU64 = AtomicU64::new(0);
+static QUEUED_UPDATES: AtomicU64 = AtomicU64::new(0);
+/// Download speed of each install running, by app name
+static ACTIVE_DOWNLOADS: Mutex<Option<HashMap<String, f64>>> = Mutex::new(None);
+
+/// An HTTP request got an answer with `status`, or none at all when `status` is `None`
+pub(crate) fn record_request(status: Option<u16>) {
+    API_REQUESTS.fetch_add(1, Ordering::Relaxed);
+    if status.is_none_or(|status| status >= 400) {
+        API_ERRORS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn record_token_refresh(succeeded: bool) {
+    TOKEN_REFRESHES.fetch_add(1, Ordering::Relaxed);
+    if !succeeded {
+        TOKEN_REFRESH_FAILURES.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn record_downloaded(bytes: u64) {
+    DOWNLOADED_BYTES.fetch_add(bytes, Ordering::Relaxed);
+}
+
+/// Games still waiting for their turn in an update run
+pub(crate) fn set_queued_updates(count: usize) {
+    QUEUED_UPDATES.store(count as u64, Ordering::Relaxed);
+}
+
+fn with_downloads<T>(f: impl FnOnce(&mut HashMap<String, f64>) -> T) -> T {
+    let mut downloads = ACTIVE_DOWNLOADS.lock().unwrap_or_else(|e| e.into_inner());
+    f(downloads.get_or_insert_with(HashMap::new))
+}
+
+/// Counts `app_name` as downloading until dropped
+pub(crate) struct ActiveDownload(String);
+
+impl ActiveDownload {
+    pub(crate) fn start(app_name: &str) -> Self {
+        with_downloads(|downloads| downloads.insert(app_name.to_string(), 0.0));
+        Self(app_name.to_string())
+    }
+}
+
+impl Drop for ActiveDownload {
+    fn drop(&mut self) {
+        with_downloads(|downloads| downloads.remove(&self.0));
+    }
+}
+
+/// The latest download speed of `app_name`, in bytes per second
+pub(crate) fn record_speed(app_name: &str, speed: f64) {
+    with_downloads(|downloads| {
+        if let Some(current) = downloads.get_mut(app_name) {
+            *current = speed;
+        }
+    });
+}
+
+/// Every metric, in Prometheus' text exposition format
+pub fn render() -> String {
+    let (active, speed) = with_downloads(|downloads| (downloads.len(), downloads.values().sum()));
+    let counters = [
+        (
+            "rauncher_api_requests_total",
+            "HTTP requests to the stores and other services",
+            API_REQUESTS.load(Ordering::Relaxed),
+        ),
+        (
+            "rauncher_api_errors_total",
+            "HTTP requests that failed or got an error status",
+            API_ERRORS.load(Ordering::Relaxed),
+        ),
+        (
+            "rauncher_token_refreshes_total",
+            "Login token refreshes attempted",
+            TOKEN_REFRESHES.load(Ordering::Relaxed),
+        ),
+        (
+            "rauncher_token_refresh_failures_total",
+            "Login token refreshes that failed",
+            TOKEN_REFRESH_FAILURES.load(Ordering::Relaxed),
+        ),
+        (
+            "rauncher_downloaded_bytes_total",
+            "Bytes downloaded by installs, updates and repairs",
+            DOWNLOADED_BYTES.load(Ordering::Relaxed),
+        ),
+    ];
+    let gauges = [
+        (
+            "rauncher_active_downloads",
+            "Installs and updates downloading",
+            active as f64,
+        ),
+        (
+            "rauncher_download_bytes_per_second",
+            "Combined speed of the running downloads",
+            speed,
+        ),
+        (
+            "rauncher_update_queue_depth",
+            "Games waiting for their turn in the running update run",
+            QUEUED_UPDATES.load(Ordering::Relaxed) as f64,
+        ),
+    ];
+
+    let mut out = String::new();
+    for (name, help, value) in counters {
+        let _ = writeln!(
+            out,
+            "# HELP {} {}\n# TYPE {} counter\n{} {}",
+            name, help, name, name, value
+        );
+    }
+    for (name, help, value) in gauges {
+        let _ = writeln!(
+            out,
+            "# HELP {} {}\n# TYPE {} gauge\n{} {}",
+            name, help, name, name, value
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let download = ActiveDownload::start("MetricsKiwi");
+        record_speed("MetricsKiwi", 1024.0);
+        record_request(Some(500));
+        let rendered = render();
+        assert!(rendered.contains("# TYPE rauncher_active_downloads gauge\n"));
+        assert!(rendered.contains("\nrauncher_download_bytes_per_second "));
+        let errors = rendered
+            .lines()
+            .find_map(|line| line.strip_prefix("rauncher_api_errors_total "))
+            .unwrap();
+        assert!(errors.parse::<u64>().unwrap() >= 1);
+
+        drop(download);
+        assert!(!with_downloads(
+            |downloads| downloads.contains_key("MetricsKiwi")
+        ));
+    }
+}
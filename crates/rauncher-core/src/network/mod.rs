@@ -0,0 +1,277 @@
+//! Behaving on metered and roaming connections, such as a phone's hotspot: downloads wait for
+//! another connection or slow down to `metered.limit_kbps`, as the config's `[metered]` table
+//! says, unless the user allows them for now.
+//!
+//! The connection is looked up with NetworkManager's metered flag on Linux and the connection
+//! cost API on Windows; elsewhere it's taken as unmetered.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+use crate::config::{MeteredAction, MeteredSettings};
+use crate::downloads::DownloadControl;
+use crate::Result;
+
+/// How long a look at the connection is trusted before looking again
+const RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often a download held back by a metered connection checks whether it may go on
+const WAIT_POLL: Duration = Duration::from_secs(1);
+
+/// Set by the user to download on a metered connection anyway
+static ALLOWED: AtomicBool = AtomicBool::new(false);
+
+/// Whether downloads go ahead on a metered connection anyway
+pub fn is_allowed() -> bool {
+    ALLOWED.load(Ordering::Relaxed)
+}
+
+/// Let this process download on a metered connection as on any other, until turned off again
+pub fn set_allowed(allowed: bool) {
+    ALLOWED.store(allowed, Ordering::Relaxed);
+}
+
+/// Whether the connection in use is metered or roaming. Unknown counts as unmetered, so a
+/// missing tool never stops downloads.
+pub async fn is_metered() -> bool {
+    if cfg!(target_os = "windows") {
+        let script = "$c = [Windows.Networking.Connectivity.NetworkInformation,\
+                      Windows.Networking.Connectivity,ContentType=WindowsRuntime]::\
+                      GetInternetConnectionProfile().GetConnectionCost(); \
+                      \"$($c.NetworkCostType) $($c.Roaming) $($c.OverDataLimit)\"";
+        output("powershell", &["-NoProfile", "-Command", script])
+            .await
+            .is_some_and(|out| windows_cost_is_metered(&out))
+    } else if cfg!(target_os = "linux") {
+        let args = [
+            "--system",
+            "get-property",
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ];
+        output("busctl", &args)
+            .await
+            .is_some_and(|out| network_manager_is_metered(&out))
+    } else {
+        false
+    }
+}
+
+async fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().await.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// NetworkManager's `Metered` property as busctl prints it, e.g. `u 1`: 1 is metered; 0 is
+/// unknown, 2 and 4 unmetered. 3 is only NetworkManager's guess (any tethered phone, say),
+/// so it counts as unmetered rather than pausing downloads on a hunch.
+fn network_manager_is_metered(out: &str) -> bool {
+    out.trim().strip_prefix("u ") == Some("1")
+}
+
+/// The cost type, roaming and over-the-limit flags of the Windows connection profile, e.g.
+/// `Variable False False`. Only an unrestricted connection not roaming is unmetered.
+fn windows_cost_is_metered(out: &str) -> bool {
+    let mut fields = out.split_whitespace();
+    let cost = fields.next().unwrap_or("Unknown");
+    let flagged = fields.any(|flag| flag.eq_ignore_ascii_case("true"));
+    matches!(cost, "Fixed" | "Variable") || flagged
+}
+
+/// Holds downloads back on metered connections; every clone of a `GameManager` shares one
+#[derive(Default)]
+pub struct NetworkGate {
+    state: Mutex<GateState>,
+    /// Held while the connection is being looked at, so chunks finding the last look stale
+    /// together wait for one look instead of each starting their own
+    probe: tokio::sync::Mutex<()>,
+}
+
+#[derive(Default)]
+struct GateState {
+    metered: bool,
+    checked: Option<Instant>,
+    /// When the bandwidth used so far at the metered limit runs out
+    next_free: Option<Instant>,
+    /// Whether waiting for another connection was logged, so it's logged once per wait
+    announced: bool,
+}
+
+impl NetworkGate {
+    /// Whether the connection was metered when last looked at
+    pub fn is_metered(&self) -> bool {
+        self.lock().metered
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, GateState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether the connection is metered, looking at it again when the last look is old
+    async fn metered(&self) -> bool {
+        if self.is_stale() {
+            let _probe = self.probe.lock().await;
+            // Another chunk may have looked while this one waited
+            if !self.is_stale() {
+                return self.lock().metered;
+            }
+            let metered = is_metered().await;
+            let mut state = self.lock();
+            if metered && !state.metered {
+                log::info!("The connection is metered");
+            } else if !metered && state.metered {
+                log::info!("The connection is no longer metered");
+            }
+            state.metered = metered;
+            state.checked = Some(Instant::now());
+        }
+        self.lock().metered
+    }
+
+    fn is_stale(&self) -> bool {
+        self.lock()
+            .checked
+            .is_none_or(|at| at.elapsed() >= RECHECK_INTERVAL)
+    }
+
+    /// What `settings` make downloads do right now
+    async fn action(&self, settings: &MeteredSettings) -> MeteredAction {
+        if settings.action == MeteredAction::Ignore || is_allowed() || !self.metered().await {
+            return MeteredAction::Ignore;
+        }
+        settings.action
+    }
+
+    /// Wait before downloading a chunk while the connection is metered and downloads pause
+    /// on one. The queued job's `control` is checked on every pass, so the wait ends with an
+    /// error once the job is cancelled or preempted.
+    pub(crate) async fn before_chunk(
+        &self,
+        settings: &MeteredSettings,
+        control: Option<&DownloadControl>,
+    ) -> Result<()> {
+        let result = loop {
+            if let Some(control) = control {
+                if let Err(e) = control.checkpoint().await {
+                    break Err(e);
+                }
+            }
+            if self.action(settings).await != MeteredAction::Pause {
+                break Ok(());
+            }
+            if !std::mem::replace(&mut self.lock().announced, true) {
+                log::info!(
+                    "Downloads wait for an unmetered connection; run with --allow-metered or \
+                     set metered.action to \"limit\" or \"ignore\" to download anyway"
+                );
+            }
+            tokio::time::sleep(WAIT_POLL).await;
+        };
+        self.lock().announced = false;
+        result
+    }
+
+    /// Slow down after downloading `bytes`, so downloads together stay within the metered
+    /// limit
+    pub(crate) async fn after_chunk(&self, settings: &MeteredSettings, bytes: usize) {
+        if self.action(settings).await != MeteredAction::Limit || settings.limit_kbps == 0 {
+            return;
+        }
+
+        let rate = settings.limit_kbps as f64 * 1024.0;
+        let until = {
+            let mut state = self.lock();
+            let now = Instant::now();
+            let start = state.next_free.filter(|at| *at > now).unwrap_or(now);
+            let until = start + Duration::from_secs_f64(bytes as f64 / rate);
+            state.next_free = Some(until);
+            until
+        };
+        tokio::time::sleep_until(until.into()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_connection_cost() {
+        assert!(network_manager_is_metered("u 1\n"));
+        assert!(!network_manager_is_metered("u 3"));
+        assert!(!network_manager_is_metered("u 4"));
+        assert!(!network_manager_is_metered("u 0"));
+
+        assert!(windows_cost_is_metered("Variable False False"));
+        assert!(windows_cost_is_metered("Unrestricted True False"));
+        assert!(!windows_cost_is_metered("Unrestricted False False\r\n"));
+        assert!(!windows_cost_is_metered(""));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_ends_metered_wait() {
+        let gate = NetworkGate::default();
+        {
+            let mut state = gate.lock();
+            state.metered = true;
+            state.checked = Some(Instant::now());
+        }
+        let settings = MeteredSettings::default();
+        assert_eq!(settings.action, MeteredAction::Pause);
+
+        let queue = crate::downloads::DownloadQueue::new();
+        let id = queue.enqueue("Fortnite");
+        let control = queue.control(id);
+        let cancel = {
+            let queue = queue.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                queue.cancel(id);
+            }
+        };
+
+        let (result, ()) = tokio::time::timeout(
+            Duration::from_secs(5),
+            futures_util::future::join(gate.before_chunk(&settings, Some(&control)), cancel),
+        )
+        .await
+        .expect("the wait should end once the download is cancelled");
+        assert!(result.is_err());
+        assert!(!gate.lock().announced);
+    }
+
+    #[tokio::test]
+    async fn test_limit_spaces_out_chunks() {
+        let gate = NetworkGate::default();
+        {
+            let mut state = gate.lock();
+            state.metered = true;
+            state.checked = Some(Instant::now());
+        }
+        let settings = MeteredSettings {
+            action: MeteredAction::Limit,
+            limit_kbps: 1000,
+        };
+
+        // 100 KiB at 1000 KiB/s takes 100 ms
+        let started = Instant::now();
+        gate.after_chunk(&settings, 50 * 1024).await;
+        gate.after_chunk(&settings, 50 * 1024).await;
+        assert!(started.elapsed() >= Duration::from_millis(95));
+
+        let ignored = MeteredSettings {
+            action: MeteredAction::Ignore,
+            ..settings
+        };
+        let started = Instant::now();
+        gate.after_chunk(&ignored, 1024 * 1024).await;
+        assert!(started.elapsed() < Duration::from_millis(50));
+    }
+}
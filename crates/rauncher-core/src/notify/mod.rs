@@ -0,0 +1,137 @@
+//! Native desktop notifications for events worth interrupting the user for.
+//!
+//! Built on notify-rust (D-Bus on Linux and the BSDs, Notification Center on macOS, toasts
+//! on Windows). Without the `notifications` feature, or when no notification service is
+//! running, notifications are only logged.
+
+pub mod webhook;
+
+use crate::config::NotificationSettings;
+use crate::tr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification {
+    InstallFinished {
+        title: String,
+    },
+    UpdateAvailable {
+        title: String,
+        version: String,
+    },
+    CloudSyncFailed {
+        title: String,
+        error: String,
+    },
+    /// A wishlisted game went on sale
+    WishlistSale {
+        title: String,
+        discount: u32,
+        price: String,
+    },
+    /// A wishlisted game is given away
+    WishlistFree {
+        title: String,
+    },
+}
+
+impl Notification {
+    /// Whether the user wants to be notified of this kind of event
+    pub fn enabled(&self, settings: &NotificationSettings) -> bool {
+        match self {
+            Self::InstallFinished { .. } => settings.install_finished,
+            Self::UpdateAvailable { .. } => settings.update_available,
+            Self::CloudSyncFailed { .. } => settings.cloud_sync_failed,
+            Self::WishlistSale { .. } | Self::WishlistFree { .. } => settings.wishlist,
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            Self::InstallFinished { .. } => tr!("notify-install-finished"),
+            Self::UpdateAvailable { .. } => tr!("notify-update-available"),
+            Self::CloudSyncFailed { .. } => tr!("notify-cloud-sync-failed"),
+            Self::WishlistSale { .. } => tr!("notify-wishlist-sale"),
+            Self::WishlistFree { .. } => tr!("notify-wishlist-free"),
+        }
+    }
+
+    fn body(&self) -> String {
+        match self {
+            Self::InstallFinished { title } => {
+                tr!("notify-install-finished-body", title = title.as_str())
+            }
+            Self::UpdateAvailable { title, version } => tr!(
+                "notify-update-available-body",
+                title = title.as_str(),
+                version = version.as_str()
+            ),
+            Self::CloudSyncFailed { title, error } => tr!(
+                "notify-cloud-sync-failed-body",
+                title = title.as_str(),
+                error = error.as_str()
+            ),
+            Self::WishlistSale {
+                title,
+                discount,
+                price,
+            } => tr!(
+                "notify-wishlist-sale-body",
+                title = title.as_str(),
+                discount = *discount,
+                price = price.as_str()
+            ),
+            Self::WishlistFree { title } => {
+                tr!("notify-wishlist-free-body", title = title.as_str())
+            }
+        }
+    }
+}
+
+/// Show `notification` unless its event type is turned off in `settings`
+pub fn send(settings: &NotificationSettings, notification: &Notification) {
+    if !notification.enabled(settings) {
+        return;
+    }
+
+    let summary = notification.summary();
+    let body = notification.body();
+    log::debug!("Notification: {}: {}", summary, body);
+
+    #[cfg(feature = "notifications")]
+    {
+        let shown = notify_rust::Notification::new()
+            .appname("rauncher")
+            .summary(&summary)
+            .body(&body)
+            .show();
+
+        if let Err(e) = shown {
+            log::debug!("Could not show a desktop notification: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_types_can_be_turned_off() {
+        let settings = NotificationSettings {
+            update_available: false,
+            ..Default::default()
+        };
+
+        let installed = Notification::InstallFinished {
+            title: "Game".to_string(),
+        };
+        let update = Notification::UpdateAvailable {
+            title: "Game".to_string(),
+            version: "1.1".to_string(),
+        };
+
+        assert!(installed.enabled(&settings));
+        assert!(!update.enabled(&settings));
+        assert!(installed.body().contains("Game"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Events POSTed to the URLs in the config's `[[webhooks]]` tables, so they can be routed into
+//! chat rooms or other notification systems. Each hook gets the event as JSON, or a message in
+//! Discord's format.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::config::{WebhookFormat, WebhookSettings};
+use crate::tr;
+
+/// Names of the events, as `[[webhooks]]` tables list them
+pub const EVENTS: [&str; 4] = [
+    "update_installed",
+    "download_failed",
+    "update_run_failed",
+    "free_game_claimed",
+];
+
+/// Shown as the sender of Discord messages
+const DISCORD_USERNAME: &str = "rauncher";
+
+/// How long a hook gets to answer, so one that hangs doesn't hold up the daemon's runs
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// An installed game was updated to a new build
+    UpdateInstalled {
+        app_name: String,
+        title: String,
+        from_version: String,
+        version: String,
+    },
+    /// A game's update couldn't be downloaded or installed
+    DownloadFailed {
+        app_name: String,
+        title: String,
+        error: String,
+    },
+    /// An update run failed before it got to the games, e.g. because the store was down
+    UpdateRunFailed { error: String },
+    /// A game given away right now showed up in the library
+    FreeGameClaimed { title: String, url: Option<String> },
+}
+
+impl WebhookEvent {
+    /// The event's name, one of `EVENTS`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::UpdateInstalled { .. } => EVENTS[0],
+            Self::DownloadFailed { .. } => EVENTS[1],
+            Self::UpdateRunFailed { .. } => EVENTS[2],
+            Self::FreeGameClaimed { .. } => EVENTS[3],
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::UpdateInstalled {
+                title,
+                from_version,
+                version,
+                ..
+            } => tr!(
+                "webhook-update-installed",
+                title = title.as_str(),
+                from = from_version.as_str(),
+                version = version.as_str()
+            ),
+            Self::DownloadFailed { title, error, .. } => tr!(
+                "webhook-download-failed",
+                title = title.as_str(),
+                error = error.as_str()
+            ),
+            Self::UpdateRunFailed { error } => {
+                tr!("webhook-update-run-failed", error = error.as_str())
+            }
+            Self::FreeGameClaimed { title, url } => {
+                let message = tr!("webhook-free-game-claimed", title = title.as_str());
+                match url {
+                    Some(url) => format!("{}\n{}", message, url),
+                    None => message,
+                }
+            }
+        }
+    }
+
+    /// Body of the request telling a hook in `format` about the event, which happened at `at`
+    pub fn payload(&self, format: WebhookFormat, at: DateTime<Utc>) -> serde_json::Value {
+        match format {
+            WebhookFormat::Json => {
+                let mut payload = serde_json::to_value(self).unwrap_or_default();
+                payload["timestamp"] = json!(at.to_rfc3339());
+                payload
+            }
+            WebhookFormat::Discord => json!({
+                "username": DISCORD_USERNAME,
+                "content": self.message(),
+            }),
+        }
+    }
+}
+
+/// A hook's URL as logged: only where it points, since chat services keep the hook's secret
+/// token in the path
+fn redacted(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(url) => format!(
+            "{}://{}/…",
+            url.scheme(),
+            url.host_str().unwrap_or_default()
+        ),
+        Err(_) => "(invalid URL)".to_string(),
+    }
+}
+
+/// Whether `hook` wants to hear about `event`
+fn wants(hook: &WebhookSettings, event: &WebhookEvent) -> bool {
+    hook.events.is_empty() || hook.events.iter().any(|name| name == event.name())
+}
+
+/// The configured webhooks, with a client to call them
+#[derive(Clone)]
+pub struct Webhooks {
+    hooks: Vec<WebhookSettings>,
+    client: reqwest::Client,
+}
+
+impl Webhooks {
+    pub fn new(hooks: Vec<WebhookSettings>) -> Self {
+        Self {
+            hooks,
+            client: reqwest::Client::builder()
+                .timeout(WEBHOOK_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// POST `event` to every hook that wants it. Failures are only logged: a hook being down
+    /// mustn't stop the daemon.
+    pub async fn send(&self, event: &WebhookEvent) {
+        let now = Utc::now();
+        for hook in self.hooks.iter().filter(|hook| wants(hook, event)) {
+            let sent = self
+                .client
+                .post(&hook.url)
+                .json(&event.payload(hook.format, now))
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+
+            let url = redacted(&hook.url);
+            match sent {
+                Ok(_) => log::debug!("Sent {} to {}", event.name(), url),
+                Err(e) => log::warn!("Webhook {} failed: {}", url, e.without_url()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn update() -> WebhookEvent {
+        WebhookEvent::UpdateInstalled {
+            app_name: "Kiwi".to_string(),
+            title: "Kiwi Quest".to_string(),
+            from_version: "1.0".to_string(),
+            version: "1.1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_payloads() {
+        let at = Utc.with_ymd_and_hms(2026, 10, 1, 20, 0, 0).unwrap();
+
+        assert_eq!(
+            update().payload(WebhookFormat::Json, at),
+            json!({
+                "event": "update_installed",
+                "app_name": "Kiwi",
+                "title": "Kiwi Quest",
+                "from_version": "1.0",
+                "version": "1.1",
+                "timestamp": "2026-10-01T20:00:00+00:00",
+            })
+        );
+
+        let discord = update().payload(WebhookFormat::Discord, at);
+        assert_eq!(discord["username"], DISCORD_USERNAME);
+        let content = discord["content"].as_str().unwrap();
+        assert!(content.contains("Kiwi Quest") && content.contains("1.1"));
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let mut hook = WebhookSettings {
+            url: "https://example.com/hook".to_string(),
+            format: WebhookFormat::Json,
+            events: Vec::new(),
+        };
+        let claimed = WebhookEvent::FreeGameClaimed {
+            title: "Kiwi Quest".to_string(),
+            url: None,
+        };
+        assert!(wants(&hook, &update()) && wants(&hook, &claimed));
+
+        hook.events = vec!["free_game_claimed".to_string()];
+        assert!(!wants(&hook, &update()));
+        assert!(wants(&hook, &claimed));
+    }
+
+    #[test]
+    fn test_logged_urls_leave_out_the_token() {
+        assert_eq!(
+            redacted("https://discord.com/api/webhooks/1234/s3cr3t-token"),
+            "https://discord.com/…"
+        );
+        assert_eq!(redacted("not a url"), "(invalid URL)");
+    }
+}
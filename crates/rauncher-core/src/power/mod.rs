@@ -0,0 +1,166 @@
+//! What to do once a run of downloads is over: run a command, then shut the computer down or
+//! suspend it, so overnight downloads don't keep it awake.
+
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::{Error, Result};
+
+/// Time given to cancel a shutdown or suspend before it happens
+pub const POWER_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerAction {
+    Shutdown,
+    Suspend,
+}
+
+impl PowerAction {
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Shutdown => "shut down",
+            Self::Suspend => "suspend",
+        }
+    }
+}
+
+/// Actions to take when downloads finish, successfully or not
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AfterDownloads {
+    /// Shell command to run first
+    pub exec: Option<String>,
+    pub power: Option<PowerAction>,
+}
+
+impl AfterDownloads {
+    pub fn is_empty(&self) -> bool {
+        self.exec.is_none() && self.power.is_none()
+    }
+
+    /// Run the command, if any, with `RAUNCHER_FAILED` set to how many of the downloads
+    /// failed. The power action is left to `power_off`, so callers can warn before it.
+    pub async fn run_command(&self, failed: usize) -> Result<()> {
+        let Some(command) = &self.exec else {
+            return Ok(());
+        };
+
+        log::info!("Running the after-download command: {}", command);
+        let status = shell(command)
+            .env("RAUNCHER_FAILED", failed.to_string())
+            .status()
+            .await
+            .map_err(|e| {
+                Error::Other(format!("Failed to run the after-download command: {}", e))
+            })?;
+        if !status.success() {
+            return Err(Error::Other(format!(
+                "The after-download command failed ({})",
+                status
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How many downloads of a batch failed, for `run_command`. A batch that failed before
+/// downloading anything counts as one failure, so the actions still run and report it.
+pub fn failures<T>(outcome: &Result<Vec<(T, Result<()>)>>) -> usize {
+    match outcome {
+        Ok(results) => results.iter().filter(|(_, r)| r.is_err()).count(),
+        Err(_) => 1,
+    }
+}
+
+fn shell(command: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    }
+}
+
+/// The system command that carries `action` out
+fn power_command(action: PowerAction) -> Command {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "windows") {
+        match action {
+            PowerAction::Shutdown => ("shutdown", &["/s", "/t", "0"]),
+            PowerAction::Suspend => ("rundll32.exe", &["powrprof.dll,SetSuspendState", "0,1,0"]),
+        }
+    } else if cfg!(target_os = "macos") {
+        match action {
+            PowerAction::Shutdown => (
+                "osascript",
+                &["-e", "tell application \"System Events\" to shut down"],
+            ),
+            PowerAction::Suspend => ("pmset", &["sleepnow"]),
+        }
+    } else {
+        match action {
+            PowerAction::Shutdown => ("systemctl", &["poweroff"]),
+            PowerAction::Suspend => ("systemctl", &["suspend"]),
+        }
+    };
+
+    let mut command = Command::new(program);
+    command.args(args);
+    command
+}
+
+/// Shut down or suspend the computer now
+pub async fn power_off(action: PowerAction) -> Result<()> {
+    let status = power_command(action)
+        .status()
+        .await
+        .map_err(|e| Error::Other(format!("Failed to {} the computer: {}", action.verb(), e)))?;
+    if !status.success() {
+        return Err(Error::Other(format!(
+            "Failed to {} the computer ({})",
+            action.verb(),
+            status
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_command_gets_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("failed");
+        let after = AfterDownloads {
+            exec: Some(format!("echo $RAUNCHER_FAILED > '{}'", out.display())),
+            power: None,
+        };
+
+        after.run_command(2).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap().trim(), "2");
+
+        let failing = AfterDownloads {
+            exec: Some("exit 3".to_string()),
+            power: None,
+        };
+        assert!(failing.run_command(0).await.is_err());
+        assert!(AfterDownloads::default().is_empty());
+    }
+
+    #[test]
+    fn test_failures_count_failed_batches() {
+        let results = vec![
+            ("Kiwi", Ok(())),
+            ("Fig", Err(Error::Other("no space".to_string()))),
+        ];
+        assert_eq!(failures(&Ok(results)), 1);
+        assert_eq!(failures::<&str>(&Ok(Vec::new())), 0);
+        assert_eq!(
+            failures::<&str>(&Err(Error::Other("offline".to_string()))),
+            1
+        );
+    }
+}
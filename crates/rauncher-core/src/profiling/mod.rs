@@ -0,0 +1,90 @@
+//! Lightweight startup and throughput timings, reported at debug level.
+//!
+//! Active in debug builds, or in release builds compiled with the `profiling` feature.
+//! Otherwise every function compiles to a no-op.
+
+use std::time::Duration;
+#[cfg(any(debug_assertions, feature = "profiling"))]
+use std::time::Instant;
+
+#[cfg(any(debug_assertions, feature = "profiling"))]
+static PROCESS_START: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+
+/// Record the reference point for `since_start`; call as early as possible in `main`
+pub fn mark_process_start() {
+    #[cfg(any(debug_assertions, feature = "profiling"))]
+    PROCESS_START.get_or_init(Instant::now);
+}
+
+/// Time elapsed since `mark_process_start`, if profiling is active
+pub fn since_start() -> Option<Duration> {
+    #[cfg(any(debug_assertions, feature = "profiling"))]
+    return PROCESS_START.get().map(|start| start.elapsed());
+
+    #[cfg(not(any(debug_assertions, feature = "profiling")))]
+    None
+}
+
+/// Log a one-off milestone relative to process start (e.g. first library paint)
+pub fn milestone(name: &str) {
+    if let Some(elapsed) = since_start() {
+        log::debug!(target: "rauncher::profiling", "{} at {:.1?}", name, elapsed);
+    }
+}
+
+/// Times a startup phase and logs its duration when dropped
+pub struct Phase {
+    #[cfg(any(debug_assertions, feature = "profiling"))]
+    name: &'static str,
+    #[cfg(any(debug_assertions, feature = "profiling"))]
+    start: Instant,
+}
+
+/// Start timing a named phase; the returned guard logs when it goes out of scope
+pub fn phase(name: &'static str) -> Phase {
+    #[cfg(not(any(debug_assertions, feature = "profiling")))]
+    let _ = name;
+
+    Phase {
+        #[cfg(any(debug_assertions, feature = "profiling"))]
+        name,
+        #[cfg(any(debug_assertions, feature = "profiling"))]
+        start: Instant::now(),
+    }
+}
+
+impl Drop for Phase {
+    fn drop(&mut self) {
+        #[cfg(any(debug_assertions, feature = "profiling"))]
+        log::debug!(
+            target: "rauncher::profiling",
+            "{} took {:.1?}",
+            self.name,
+            self.start.elapsed()
+        );
+    }
+}
+
+/// Log the throughput of a transfer, e.g. an install's downloaded bytes
+pub fn throughput(name: &str, bytes: u64, elapsed: Duration) {
+    #[cfg(any(debug_assertions, feature = "profiling"))]
+    {
+        let secs = elapsed.as_secs_f64();
+        let rate = if secs > 0.0 {
+            bytes as f64 / secs / (1024.0 * 1024.0)
+        } else {
+            0.0
+        };
+        log::debug!(
+            target: "rauncher::profiling",
+            "{}: {} bytes in {:.1?} ({:.2} MiB/s)",
+            name,
+            bytes,
+            elapsed,
+            rate
+        );
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "profiling")))]
+    let _ = (name, bytes, elapsed);
+}
@@ -0,0 +1,163 @@
+//! Questions that operations ask half-way through, such as what to do when a cloud save
+//! conflicts with a local one, answered by whichever frontend is running.
+//!
+//! The CLI asks on the terminal, the GUI in a dialog, and anything that can't ask anyone
+//! (the daemon, scripts embedding the core) answers from the `prompt_answers` policy in the
+//! config, falling back to the safest answer.
+
+use std::collections::BTreeMap;
+
+use crate::tr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionKind {
+    /// A downloaded cloud save differs from the local file it would replace
+    SaveConflict,
+    /// A game is about to be installed into a directory that already holds files
+    OverwriteInstall,
+    /// A yes/no confirmation before a destructive operation
+    Confirm,
+}
+
+impl QuestionKind {
+    pub const ALL: [QuestionKind; 3] = [Self::SaveConflict, Self::OverwriteInstall, Self::Confirm];
+
+    /// Name of the kind in the `prompt_answers` config table
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::SaveConflict => "save_conflict",
+            Self::OverwriteInstall => "overwrite_install",
+            Self::Confirm => "confirm",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.id() == id)
+    }
+
+    /// Ids of the possible answers; the first one is given when nobody can be asked
+    pub fn choices(self) -> &'static [&'static str] {
+        match self {
+            Self::SaveConflict => &["keep_local", "use_cloud"],
+            Self::OverwriteInstall => &["cancel", "overwrite"],
+            Self::Confirm => &["no", "yes"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Question {
+    pub kind: QuestionKind,
+    /// Localized question shown to the user
+    pub text: String,
+}
+
+impl Question {
+    pub fn new(kind: QuestionKind, text: String) -> Self {
+        Self { kind, text }
+    }
+
+    pub fn default_answer(&self) -> &'static str {
+        self.kind.choices()[0]
+    }
+
+    /// Localized label of each possible answer, paired with its id
+    pub fn choices(&self) -> Vec<(&'static str, String)> {
+        self.kind
+            .choices()
+            .iter()
+            .map(|id| (*id, choice_label(id)))
+            .collect()
+    }
+}
+
+fn choice_label(id: &str) -> String {
+    match id {
+        "keep_local" => tr!("prompt-answer-keep-local"),
+        "use_cloud" => tr!("prompt-answer-use-cloud"),
+        "cancel" => tr!("prompt-answer-cancel"),
+        "overwrite" => tr!("prompt-answer-overwrite"),
+        "no" => tr!("prompt-answer-no"),
+        "yes" => tr!("prompt-answer-yes"),
+        other => other.to_string(),
+    }
+}
+
+/// Something that can answer questions on the user's behalf, usually by asking them
+pub trait Prompter: Send + Sync {
+    /// Ask `question` and return the id of the chosen answer, one of `question.kind.choices()`
+    fn ask(&self, question: &Question) -> &'static str;
+}
+
+/// Answers without asking: the answer configured for the question's kind, else the default
+#[derive(Debug, Clone, Default)]
+pub struct PolicyPrompter {
+    answers: BTreeMap<String, String>,
+}
+
+impl PolicyPrompter {
+    pub fn new(answers: BTreeMap<String, String>) -> Self {
+        Self { answers }
+    }
+}
+
+impl Prompter for PolicyPrompter {
+    fn ask(&self, question: &Question) -> &'static str {
+        let configured = self.answers.get(question.kind.id());
+        let answer = question
+            .kind
+            .choices()
+            .iter()
+            .find(|choice| configured.is_some_and(|answer| answer == *choice))
+            .copied()
+            .unwrap_or_else(|| question.default_answer());
+
+        log::info!("{} -> {}", question.text, answer);
+        answer
+    }
+}
+
+/// Check a `prompt_answers` table: known question kinds mapped to one of their answers
+pub fn validate_answers(answers: &BTreeMap<String, String>) -> std::result::Result<(), String> {
+    for (kind, answer) in answers {
+        let Some(kind) = QuestionKind::from_id(kind) else {
+            let kinds: Vec<_> = QuestionKind::ALL.iter().map(|kind| kind.id()).collect();
+            return Err(format!(
+                "Unknown question '{}'. Must be one of: {}",
+                kind,
+                kinds.join(", ")
+            ));
+        };
+
+        if !kind.choices().contains(&answer.as_str()) {
+            return Err(format!(
+                "Invalid answer '{}' for {}. Must be one of: {}",
+                answer,
+                kind.id(),
+                kind.choices().join(", ")
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_answers_configured_kinds_only() {
+        let answers = BTreeMap::from([("save_conflict".to_string(), "use_cloud".to_string())]);
+        let prompter = PolicyPrompter::new(answers.clone());
+
+        let conflict = Question::new(QuestionKind::SaveConflict, "Conflict".to_string());
+        let overwrite = Question::new(QuestionKind::OverwriteInstall, "Overwrite".to_string());
+        assert_eq!(prompter.ask(&conflict), "use_cloud");
+        assert_eq!(prompter.ask(&overwrite), "cancel");
+
+        assert!(validate_answers(&answers).is_ok());
+        let unknown = BTreeMap::from([("confirm".to_string(), "maybe".to_string())]);
+        assert!(validate_answers(&unknown).is_err());
+    }
+}
@@ -0,0 +1,816 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::RngCore;
+use reqwest::{Client, Url};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::{code_from_input, LoginFlow, StoreProvider, AMAZON};
+use crate::api::{ChunkPart, FileManifest, Game, GameManifest};
+use crate::auth::AuthToken;
+use crate::config::Config;
+use crate::logs::transcript::RecordedSend;
+use crate::{Error, Result};
+
+// Request timeout configuration
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+// Amazon API endpoints
+const SIGNIN_URL: &str = "https://www.amazon.com/ap/signin";
+const AUTH_API_URL: &str = "https://api.amazon.com/auth";
+const DISTRIBUTION_API_URL: &str = "https://gaming.amazon.com/api/distribution";
+
+// Identity of the Amazon Games app, which rauncher registers as
+const DEVICE_TYPE: &str = "A2UMVHOX7UP4V7";
+const APP_NAME: &str = "AGSLauncher for Windows";
+const APP_VERSION: &str = "1.0.0";
+const USER_AGENT: &str = "com.amazon.agslauncher.win/3.0.9202.1";
+const MARKETPLACE_ID: &str = "ATVPDKIKX0DER";
+const ENTITLEMENTS_KEY_ID: &str = "d5dc8b8b-86c8-4fc4-ae93-18c0def5314d";
+
+const ENTITLEMENTS_TARGET: &str =
+    "com.amazon.animusdistributionservice.entitlement.AnimusEntitlementsService.GetEntitlements";
+const DOWNLOAD_TARGET: &str =
+    "com.amazon.animusdistributionservice.external.AnimusDistributionService.GetGameDownload";
+
+/// Query parameter carrying the code on the page Amazon redirects to after login
+const CODE_PARAM: &str = "openid.oa2.authorization_code";
+
+/// Device identity and the PKCE verifier of the login in progress. Amazon ties tokens
+/// to the registered device, so this outlives the login.
+#[derive(Debug, Serialize, Deserialize)]
+struct DeviceState {
+    serial: String,
+    code_verifier: String,
+}
+
+impl DeviceState {
+    fn generate() -> Self {
+        let mut serial = [0u8; 16];
+        let mut verifier = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut serial);
+        rand::thread_rng().fill_bytes(&mut verifier);
+
+        Self {
+            serial: hex(&serial).to_uppercase(),
+            code_verifier: URL_SAFE_NO_PAD.encode(verifier),
+        }
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?
+            .join("providers")
+            .join(AMAZON)
+            .join("device.json"))
+    }
+
+    fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Err(Error::Auth(
+                "No Amazon login in progress. Run 'rauncher auth --provider amazon' first"
+                    .to_string(),
+            ));
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn client_id(&self) -> String {
+        hex(format!("{}#{}", self.serial, DEVICE_TYPE).as_bytes())
+    }
+
+    fn code_challenge(&self) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(self.code_verifier.as_bytes()))
+    }
+
+    fn hardware_hash(&self) -> String {
+        hex(&Sha256::digest(self.serial.as_bytes())).to_uppercase()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterResponse {
+    response: RegisterResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterResult {
+    success: RegisterSuccess,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterSuccess {
+    tokens: RegisteredTokens,
+    #[serde(default)]
+    customer_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisteredTokens {
+    bearer: BearerToken,
+}
+
+#[derive(Debug, Deserialize)]
+struct BearerToken {
+    access_token: String,
+    refresh_token: String,
+    expires_in: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RefreshResponse {
+    access_token: String,
+    expires_in: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct EntitlementsResponse {
+    #[serde(default)]
+    entitlements: Vec<Entitlement>,
+    #[serde(rename = "nextToken")]
+    next_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Entitlement {
+    id: String,
+    product: EntitledProduct,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct EntitledProduct {
+    id: String,
+    #[serde(default)]
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GameDownloadResponse {
+    #[serde(rename = "downloadUrl")]
+    download_url: String,
+    #[serde(rename = "versionId", default)]
+    version_id: String,
+}
+
+/// Launch configuration Amazon games ship in their install directory
+#[derive(Debug, Deserialize)]
+struct FuelManifest {
+    #[serde(rename = "Main")]
+    main: FuelMain,
+}
+
+#[derive(Debug, Deserialize)]
+struct FuelMain {
+    #[serde(rename = "Command")]
+    command: String,
+}
+
+pub struct AmazonClient {
+    client: Client,
+    /// Download base URL per product, from the latest manifest request
+    download_urls: Mutex<HashMap<String, String>>,
+}
+
+impl AmazonClient {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            download_urls: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn login_url(device: &DeviceState) -> String {
+        let client_id = format!("device:{}", device.client_id());
+        let challenge = device.code_challenge();
+
+        Url::parse_with_params(
+            SIGNIN_URL,
+            &[
+                ("openid.ns", "http://specs.openid.net/auth/2.0"),
+                (
+                    "openid.claimed_id",
+                    "http://specs.openid.net/auth/2.0/identifier_select",
+                ),
+                (
+                    "openid.identity",
+                    "http://specs.openid.net/auth/2.0/identifier_select",
+                ),
+                ("openid.mode", "checkid_setup"),
+                ("openid.oa2.scope", "device_auth_access"),
+                ("openid.ns.oa2", "http://www.amazon.com/ap/ext/oauth/2"),
+                ("openid.oa2.response_type", "code"),
+                ("openid.oa2.code_challenge_method", "S256"),
+                ("openid.oa2.client_id", client_id.as_str()),
+                ("openid.oa2.code_challenge", challenge.as_str()),
+                ("language", "en_US"),
+                ("marketPlaceId", MARKETPLACE_ID),
+                ("openid.return_to", "https://www.amazon.com"),
+                ("openid.pape.max_auth_age", "0"),
+                ("openid.assoc_handle", "amzn_sonic_games_launcher"),
+                ("pageId", "amzn_sonic_games_launcher"),
+            ],
+        )
+        .map(String::from)
+        .unwrap_or_default()
+    }
+
+    async fn post_json<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        body: serde_json::Value,
+        context: &str,
+        error: fn(String) -> Error,
+    ) -> Result<T> {
+        let response = request.json(&body).send_recorded().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error(format!("{}: {} - {}", context, status, error_text)));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Call one of the Amazon Games distribution services
+    async fn distribution<T: DeserializeOwned>(
+        &self,
+        token: &AuthToken,
+        endpoint: &str,
+        target: &str,
+        body: serde_json::Value,
+        context: &str,
+    ) -> Result<T> {
+        let request = self
+            .client
+            .post(format!("{}/{}", DISTRIBUTION_API_URL, endpoint))
+            .header("X-Amz-Target", target)
+            .header("x-amzn-token", &token.access_token)
+            .header("Content-Encoding", "amz-1.0");
+
+        self.post_json(request, body, context, Error::Api).await
+    }
+
+    async fn get_entitlements(&self, token: &AuthToken) -> Result<Vec<Entitlement>> {
+        let device = DeviceState::load()?;
+        let mut entitlements: Vec<Entitlement> = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let body = json!({
+                "Operation": "GetEntitlements",
+                "clientId": "Sonic",
+                "syncPoint": null,
+                "nextToken": next_token,
+                "maxResults": 50,
+                "productIdFilter": null,
+                "keyId": ENTITLEMENTS_KEY_ID,
+                "hardwareHash": device.hardware_hash(),
+            });
+
+            let page: EntitlementsResponse = self
+                .distribution(
+                    token,
+                    "entitlements",
+                    ENTITLEMENTS_TARGET,
+                    body,
+                    "Failed to fetch entitlements",
+                )
+                .await?;
+
+            for entitlement in page.entitlements {
+                // The same game can be entitled more than once, e.g. claimed twice
+                if !entitlements
+                    .iter()
+                    .any(|e| e.product.id == entitlement.product.id)
+                {
+                    entitlements.push(entitlement);
+                }
+            }
+
+            match page.next_token {
+                Some(token) if !token.is_empty() => next_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(entitlements)
+    }
+}
+
+#[async_trait]
+impl StoreProvider for AmazonClient {
+    fn id(&self) -> &'static str {
+        AMAZON
+    }
+
+    fn login_flow(&self) -> Result<LoginFlow> {
+        let device = DeviceState::generate();
+        device.save()?;
+
+        Ok(LoginFlow::AuthorizationCode {
+            url: Self::login_url(&device),
+        })
+    }
+
+    async fn exchange_code(&self, code: &str) -> Result<AuthToken> {
+        let device = DeviceState::load()?;
+        let code = code_from_input(code, CODE_PARAM);
+
+        log::info!("Registering rauncher with Amazon");
+
+        let body = json!({
+            "auth_data": {
+                "authorization_code": code,
+                "client_domain": "DeviceLegacy",
+                "client_id": device.client_id(),
+                "code_algorithm": "SHA-256",
+                "code_verifier": device.code_verifier,
+                "use_global_authentication": false,
+            },
+            "registration_data": {
+                "app_name": APP_NAME,
+                "app_version": APP_VERSION,
+                "device_model": "Windows",
+                "device_serial": device.serial,
+                "device_type": DEVICE_TYPE,
+                "domain": "Device",
+                "os_version": "10.0.19044.0",
+            },
+            "requested_extensions": ["customer_info", "device_info"],
+            "requested_token_type": ["bearer", "mac_dms"],
+            "user_context_map": {},
+        });
+
+        let request = self.client.post(format!("{}/register", AUTH_API_URL));
+        let response: RegisterResponse = self
+            .post_json(request, body, "Amazon login failed", Error::Auth)
+            .await?;
+        let success = response.response.success;
+        let bearer = success.tokens.bearer;
+
+        Ok(AuthToken {
+            access_token: bearer.access_token,
+            refresh_token: bearer.refresh_token,
+            expires_at: expires_at(&bearer.expires_in),
+            account_id: success.customer_id,
+        })
+    }
+
+    async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken> {
+        log::info!("Refreshing Amazon access token");
+
+        let body = json!({
+            "source_token": refresh_token,
+            "source_token_type": "refresh_token",
+            "requested_token_type": "access_token",
+            "app_name": APP_NAME,
+            "app_version": APP_VERSION,
+        });
+
+        let request = self.client.post(format!("{}/token", AUTH_API_URL));
+        let response: RefreshResponse = self
+            .post_json(request, body, "Failed to refresh token", Error::Auth)
+            .await?;
+
+        // Amazon keeps the refresh token and doesn't repeat the account id
+        Ok(AuthToken {
+            access_token: response.access_token,
+            refresh_token: refresh_token.to_string(),
+            expires_at: expires_at(&response.expires_in),
+            account_id: String::new(),
+        })
+    }
+
+    async fn get_games(&self, token: &AuthToken) -> Result<Vec<Game>> {
+        log::info!("Fetching game library from Amazon Games");
+
+        let games: Vec<Game> = self
+            .get_entitlements(token)
+            .await?
+            .into_iter()
+            .map(|entitlement| Game {
+                app_title: if entitlement.product.title.is_empty() {
+                    entitlement.product.id.clone()
+                } else {
+                    entitlement.product.title
+                },
+                app_name: entitlement.product.id,
+                app_version: "unknown".to_string(),
+                install_path: None,
+                provider: AMAZON.to_string(),
+            })
+            .collect();
+
+        log::info!("Successfully fetched {} games from Amazon", games.len());
+
+        Ok(games)
+    }
+
+    async fn download_manifest(&self, token: &AuthToken, app_name: &str) -> Result<GameManifest> {
+        log::info!("Downloading manifest for game: {}", app_name);
+
+        let entitlement = self
+            .get_entitlements(token)
+            .await?
+            .into_iter()
+            .find(|e| e.product.id == app_name)
+            .ok_or_else(|| Error::GameNotFound(app_name.to_string()))?;
+
+        let download: GameDownloadResponse = self
+            .distribution(
+                token,
+                "v2/public",
+                DOWNLOAD_TARGET,
+                json!({
+                    "entitlementId": entitlement.id,
+                    "Operation": "GetGameDownloadV2",
+                }),
+                "Failed to get game download",
+            )
+            .await?;
+
+        let base_url = download.download_url.trim_end_matches('/').to_string();
+        let response = self
+            .client
+            .get(format!("{}/manifest.proto", base_url))
+            .send_recorded()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Error::Api(format!(
+                "Failed to download manifest: {}",
+                response.status()
+            )));
+        }
+
+        let files = manifest::parse(&response.bytes().await?)?;
+
+        self.download_urls
+            .lock()
+            .unwrap()
+            .insert(app_name.to_string(), base_url);
+
+        Ok(to_game_manifest(app_name, &download.version_id, files))
+    }
+
+    async fn download_chunk(
+        &self,
+        _token: &AuthToken,
+        app_name: &str,
+        chunk_guid: &str,
+    ) -> Result<Vec<u8>> {
+        log::debug!("Downloading file: {}", chunk_guid);
+
+        let base_url = self
+            .download_urls
+            .lock()
+            .unwrap()
+            .get(app_name)
+            .cloned()
+            .ok_or_else(|| Error::Other(format!("No download in progress for {}", app_name)))?;
+
+        let response = self
+            .client
+            .get(format!("{}/files/{}", base_url, chunk_guid))
+            .send_recorded()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Error::Api(format!(
+                "Failed to download file {}: {}",
+                chunk_guid,
+                response.status()
+            )));
+        }
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    fn launch_executable(&self, install_path: &Path) -> Option<String> {
+        let contents = fs::read_to_string(install_path.join("fuel.json")).ok()?;
+        let fuel: FuelManifest = serde_json::from_str(&contents).ok()?;
+
+        Some(fuel.main.command.replace('\\', "/"))
+    }
+}
+
+/// Amazon reports lifetimes as either numbers or numeric strings
+fn expires_at(expires_in: &serde_json::Value) -> chrono::DateTime<chrono::Utc> {
+    let seconds = match expires_in {
+        serde_json::Value::String(s) => s.parse().unwrap_or(0),
+        other => other.as_i64().unwrap_or(0),
+    };
+
+    chrono::Utc::now() + chrono::Duration::seconds(seconds)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Amazon downloads whole files addressed by their hash, so each file is a single chunk
+fn to_game_manifest(app_name: &str, version: &str, files: Vec<manifest::File>) -> GameManifest {
+    let file_list: Vec<FileManifest> = files
+        .into_iter()
+        .map(|file| FileManifest {
+            filename: file.path.replace('\\', "/"),
+            file_chunk_parts: vec![ChunkPart {
+                guid: hex(&file.hash),
+                offset: 0,
+                size: file.size,
+            }],
+            file_hash: file.hash,
+        })
+        .collect();
+
+    GameManifest {
+        manifest_file_version: "amazon".to_string(),
+        is_file_data: true,
+        app_name: app_name.to_string(),
+        app_version: version.to_string(),
+        launch_exe: String::new(),
+        launch_command: String::new(),
+        build_size: file_list
+            .iter()
+            .flat_map(|f| &f.file_chunk_parts)
+            .map(|p| p.size)
+            .sum(),
+        file_list,
+        chunk_hash_list: BTreeMap::new(),
+        chunk_sha_list: BTreeMap::new(),
+        data_group_list: BTreeMap::new(),
+        chunk_filesize_list: BTreeMap::new(),
+        custom_fields: BTreeMap::new(),
+        prerequisite: None,
+    }
+}
+
+/// Reader for Amazon's `manifest.proto`: a big-endian header length, a protobuf header
+/// naming the compression, then the (usually LZMA compressed) protobuf file list
+mod manifest {
+    use crate::{Error, Result};
+
+    const COMPRESSION_LZMA: u64 = 1;
+    const XZ_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0x00];
+
+    #[derive(Debug)]
+    pub struct File {
+        pub path: String,
+        pub size: u64,
+        pub hash: Vec<u8>,
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Vec<File>> {
+        let header_len = data
+            .get(..4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .ok_or_else(|| invalid("truncated header"))?;
+        let header = data
+            .get(4..4 + header_len)
+            .ok_or_else(|| invalid("truncated header"))?;
+        let body = &data[4 + header_len..];
+
+        // ManifestHeader.compression (1) -> CompressionSettings.algorithm (1)
+        let mut algorithm = 0;
+        for (field, value) in Fields::new(header) {
+            if let (1, Value::Bytes(settings)) = (field, value?) {
+                for (field, value) in Fields::new(settings) {
+                    if let (1, Value::Varint(v)) = (field, value?) {
+                        algorithm = v;
+                    }
+                }
+            }
+        }
+
+        let body = if algorithm == COMPRESSION_LZMA {
+            decompress(body)?
+        } else {
+            body.to_vec()
+        };
+
+        // Manifest.packages (1) -> Package.files (2)
+        let mut files = Vec::new();
+        for (field, value) in Fields::new(&body) {
+            if let (1, Value::Bytes(package)) = (field, value?) {
+                for (field, value) in Fields::new(package) {
+                    if let (2, Value::Bytes(file)) = (field, value?) {
+                        files.push(parse_file(file)?);
+                    }
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    fn parse_file(data: &[u8]) -> Result<File> {
+        let mut file = File {
+            path: String::new(),
+            size: 0,
+            hash: Vec::new(),
+        };
+
+        for (field, value) in Fields::new(data) {
+            match (field, value?) {
+                (1, Value::Bytes(path)) => file.path = String::from_utf8_lossy(path).into_owned(),
+                (3, Value::Varint(size)) => file.size = size,
+                // Hash.value (2)
+                (5, Value::Bytes(hash)) => {
+                    for (field, value) in Fields::new(hash) {
+                        if let (2, Value::Bytes(value)) = (field, value?) {
+                            file.hash = value.to_vec();
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(file)
+    }
+
+    fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+        let mut input = std::io::BufReader::new(data);
+        let mut out = Vec::new();
+
+        let result = if data.starts_with(&XZ_MAGIC) {
+            lzma_rs::xz_decompress(&mut input, &mut out)
+        } else {
+            lzma_rs::lzma_decompress(&mut input, &mut out)
+        };
+        result.map_err(|e| invalid(&e.to_string()))?;
+
+        Ok(out)
+    }
+
+    fn invalid(reason: &str) -> Error {
+        Error::Api(format!("Invalid Amazon manifest: {}", reason))
+    }
+
+    pub enum Value<'a> {
+        Varint(u64),
+        Bytes(&'a [u8]),
+        Fixed,
+    }
+
+    /// Iterator over the top-level fields of a protobuf message
+    pub struct Fields<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+
+    impl<'a> Fields<'a> {
+        pub fn new(data: &'a [u8]) -> Self {
+            Self { data, pos: 0 }
+        }
+
+        fn varint(&mut self) -> Result<u64> {
+            let mut value = 0u64;
+
+            for shift in (0..64).step_by(7) {
+                let byte = *self
+                    .data
+                    .get(self.pos)
+                    .ok_or_else(|| invalid("truncated varint"))?;
+                self.pos += 1;
+                value |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    return Ok(value);
+                }
+            }
+
+            Err(invalid("varint too long"))
+        }
+
+        fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+            let bytes = self
+                .data
+                .get(self.pos..self.pos + len)
+                .ok_or_else(|| invalid("truncated field"))?;
+            self.pos += len;
+            Ok(bytes)
+        }
+
+        fn field(&mut self) -> Result<(u32, Value<'a>)> {
+            let key = self.varint()?;
+            let value = match key & 0x7 {
+                0 => Value::Varint(self.varint()?),
+                1 => {
+                    self.take(8)?;
+                    Value::Fixed
+                }
+                2 => {
+                    let len = self.varint()? as usize;
+                    Value::Bytes(self.take(len)?)
+                }
+                5 => {
+                    self.take(4)?;
+                    Value::Fixed
+                }
+                wire_type => return Err(invalid(&format!("wire type {}", wire_type))),
+            };
+
+            Ok(((key >> 3) as u32, value))
+        }
+    }
+
+    impl<'a> Iterator for Fields<'a> {
+        type Item = (u32, Result<Value<'a>>);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.pos >= self.data.len() {
+                return None;
+            }
+
+            match self.field() {
+                Ok((field, value)) => Some((field, Ok(value))),
+                Err(e) => {
+                    // Stop after a malformed field; its length can't be trusted
+                    self.pos = self.data.len();
+                    Some((0, Err(e)))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn length_delimited(field: u8, bytes: &[u8]) -> Vec<u8> {
+        let mut out = vec![(field << 3) | 2, bytes.len() as u8];
+        out.extend_from_slice(bytes);
+        out
+    }
+
+    #[test]
+    fn test_parse_uncompressed_manifest() {
+        let hash = length_delimited(2, &[0xab, 0xcd]);
+        let mut file = length_delimited(1, b"bin\\game.exe");
+        file.extend_from_slice(&[3 << 3, 0x96, 0x01]); // size = 150
+        file.extend(length_delimited(5, &hash));
+        let package = length_delimited(2, &file);
+        let body = length_delimited(1, &package);
+
+        // Compression settings with algorithm 0 (none)
+        let header = length_delimited(1, &[1 << 3, 0]);
+        let mut data = (header.len() as u32).to_be_bytes().to_vec();
+        data.extend(header);
+        data.extend(body);
+
+        let files = manifest::parse(&data).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "bin\\game.exe");
+        assert_eq!(files[0].size, 150);
+
+        let game_manifest = to_game_manifest("amzn1.adg.product.test", "v1", files);
+        assert_eq!(game_manifest.file_list[0].filename, "bin/game.exe");
+        assert_eq!(game_manifest.file_list[0].file_chunk_parts[0].guid, "abcd");
+        assert_eq!(game_manifest.build_size, 150);
+    }
+
+    #[test]
+    fn test_truncated_manifest_is_rejected() {
+        assert!(manifest::parse(&[0, 0, 0, 9, 1]).is_err());
+    }
+
+    #[test]
+    fn test_pkce_challenge_matches_verifier() {
+        // Example from RFC 7636, appendix B
+        let device = DeviceState {
+            serial: "0123456789ABCDEF".to_string(),
+            code_verifier: "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string(),
+        };
+        assert_eq!(
+            device.code_challenge(),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+
+use super::{ChunkEncoding, LoginFlow, StoreProvider, EPIC};
+use crate::api::{CloudSave, EpicClient, Game, GameManifest, SaveFile};
+use crate::auth::AuthToken;
+use crate::Result;
+
+#[async_trait]
+impl StoreProvider for EpicClient {
+    fn id(&self) -> &'static str {
+        EPIC
+    }
+
+    fn login_flow(&self) -> Result<LoginFlow> {
+        Ok(LoginFlow::DeviceCode)
+    }
+
+    async fn exchange_code(&self, code: &str) -> Result<AuthToken> {
+        EpicClient::exchange_code(self, code).await
+    }
+
+    async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken> {
+        EpicClient::refresh_token(self, refresh_token).await
+    }
+
+    async fn get_games(&self, token: &AuthToken) -> Result<Vec<Game>> {
+        EpicClient::get_games(self, token).await
+    }
+
+    async fn download_manifest(&self, token: &AuthToken, app_name: &str) -> Result<GameManifest> {
+        EpicClient::download_manifest(self, token, app_name).await
+    }
+
+    async fn download_chunk(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        chunk_guid: &str,
+    ) -> Result<Vec<u8>> {
+        EpicClient::download_chunk(self, token, app_name, chunk_guid).await
+    }
+
+    fn chunk_encoding(&self) -> ChunkEncoding {
+        ChunkEncoding::Epic
+    }
+
+    async fn get_cloud_saves(&self, token: &AuthToken, app_name: &str) -> Result<Vec<CloudSave>> {
+        EpicClient::get_cloud_saves(self, token, app_name).await
+    }
+
+    async fn download_cloud_save(
+        &self,
+        token: &AuthToken,
+        save: &CloudSave,
+    ) -> Result<Vec<SaveFile>> {
+        EpicClient::download_cloud_save(self, token, save).await
+    }
+
+    async fn upload_cloud_save(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        save_data: &[u8],
+    ) -> Result<()> {
+        EpicClient::upload_cloud_save(self, token, app_name, save_data).await
+    }
+}
@@ -0,0 +1,588 @@
+use async_trait::async_trait;
+use flate2::read::ZlibDecoder;
+use reqwest::{Client, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::{code_from_input, ChunkEncoding, LoginFlow, StoreProvider, GOG};
+use crate::api::{ChunkPart, FileManifest, Game, GameManifest};
+use crate::auth::AuthToken;
+use crate::logs::transcript::RecordedSend;
+use crate::{Error, Result};
+
+// Request timeout configuration
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+// GOG API endpoints
+const AUTH_URL: &str = "https://auth.gog.com";
+const EMBED_URL: &str = "https://embed.gog.com";
+const CONTENT_SYSTEM_URL: &str = "https://content-system.gog.com";
+const CDN_URL: &str = "https://gog-cdn-fastly.gog.com";
+const REDIRECT_URI: &str = "https://embed.gog.com/on_login_success?origin=client";
+
+// GOG Galaxy client credentials
+const CLIENT_ID: &str = "46899977096215655";
+const CLIENT_SECRET: &str = "9d85c43b1482497dbbce61f6e4aa173a433796eeae2ca8c5f6129f2dc4de46d9";
+
+// Builds are fetched for Windows, the one platform every GOG game ships for
+const PLATFORM: &str = "windows";
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: i64,
+    user_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProductsPage {
+    #[serde(rename = "totalPages")]
+    total_pages: u32,
+    products: Vec<Product>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Product {
+    id: u64,
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuildsResponse {
+    items: Vec<Build>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Build {
+    #[serde(default)]
+    version_name: String,
+    link: String,
+    generation: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuildManifest {
+    #[serde(rename = "baseProductId")]
+    base_product_id: String,
+    depots: Vec<Depot>,
+    #[serde(default)]
+    products: Vec<BuildProduct>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Depot {
+    manifest: String,
+    #[serde(default)]
+    languages: Vec<String>,
+    #[serde(rename = "productId")]
+    product_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BuildProduct {
+    #[serde(rename = "productId")]
+    product_id: String,
+    #[serde(default)]
+    temp_executable: String,
+    #[serde(default)]
+    temp_arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepotManifest {
+    depot: DepotContents,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepotContents {
+    items: Vec<DepotItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepotItem {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    path: String,
+    #[serde(default)]
+    chunks: Vec<DepotChunk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepotChunk {
+    size: u64,
+    #[serde(rename = "compressedMd5")]
+    compressed_md5: String,
+    #[serde(rename = "compressedSize", default)]
+    compressed_size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SecureLinkResponse {
+    urls: Vec<SecureLink>,
+}
+
+/// A CDN URL template, filled in with its parameters and the chunk path
+#[derive(Debug, Clone, Deserialize)]
+struct SecureLink {
+    url_format: String,
+    parameters: HashMap<String, serde_json::Value>,
+}
+
+impl SecureLink {
+    fn chunk_url(&self, compressed_md5: &str) -> String {
+        let mut url = self.url_format.clone();
+
+        for (key, value) in &self.parameters {
+            let mut value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            if key == "path" {
+                value = format!(
+                    "{}/{}",
+                    value.trim_end_matches('/'),
+                    galaxy_path(compressed_md5)
+                );
+            }
+            url = url.replace(&format!("{{{}}}", key), &value);
+        }
+
+        url
+    }
+}
+
+pub struct GogClient {
+    client: Client,
+    /// CDN links per product, requested once per session
+    secure_links: Mutex<HashMap<String, SecureLink>>,
+}
+
+impl GogClient {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .user_agent("rauncher/0.1.0")
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            secure_links: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Page where the user logs in; GOG then redirects to a URL carrying the code
+    pub fn login_url() -> String {
+        Url::parse_with_params(
+            &format!("{}/auth", AUTH_URL),
+            &[
+                ("client_id", CLIENT_ID),
+                ("redirect_uri", REDIRECT_URI),
+                ("response_type", "code"),
+                ("layout", "client2"),
+            ],
+        )
+        .map(String::from)
+        .unwrap_or_default()
+    }
+
+    async fn request_token(&self, grant: &[(&str, &str)]) -> Result<AuthToken> {
+        let mut params = vec![("client_id", CLIENT_ID), ("client_secret", CLIENT_SECRET)];
+        params.extend_from_slice(grant);
+
+        let response = self
+            .client
+            .get(format!("{}/token", AUTH_URL))
+            .query(&params)
+            .send_recorded()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::Auth(format!(
+                "GOG login failed: {} - {}",
+                status, error_text
+            )));
+        }
+
+        let token: TokenResponse = response.json().await?;
+
+        Ok(AuthToken {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_at: chrono::Utc::now() + chrono::Duration::seconds(token.expires_in),
+            account_id: token.user_id,
+        })
+    }
+
+    async fn get_bytes(
+        &self,
+        url: &str,
+        token: Option<&AuthToken>,
+        context: &str,
+    ) -> Result<Vec<u8>> {
+        let mut request = self.client.get(url);
+        if let Some(token) = token {
+            request = request.bearer_auth(&token.access_token);
+        }
+
+        let response = request.send_recorded().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(error_from_response(status, &error_text, context));
+        }
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        token: Option<&AuthToken>,
+        context: &str,
+    ) -> Result<T> {
+        let body = self.get_bytes(url, token, context).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Fetch a content-system document, which is usually zlib compressed JSON
+    async fn get_manifest<T: DeserializeOwned>(&self, url: &str, context: &str) -> Result<T> {
+        let body = self.get_bytes(url, None, context).await?;
+
+        if body.first() == Some(&b'{') {
+            Ok(serde_json::from_slice(&body)?)
+        } else {
+            Ok(serde_json::from_slice(&inflate(&body)?)?)
+        }
+    }
+
+    async fn secure_link(&self, token: &AuthToken, product_id: &str) -> Result<SecureLink> {
+        if let Some(link) = self.secure_links.lock().unwrap().get(product_id) {
+            return Ok(link.clone());
+        }
+
+        let url = format!(
+            "{}/products/{}/secure_link?generation=2&path=/&_version=2",
+            CONTENT_SYSTEM_URL, product_id
+        );
+        let response: SecureLinkResponse = self
+            .get_json(&url, Some(token), "Failed to get download link")
+            .await?;
+
+        let link = response.urls.into_iter().next().ok_or_else(|| {
+            Error::Api(format!("No download servers available for {}", product_id))
+        })?;
+
+        self.secure_links
+            .lock()
+            .unwrap()
+            .insert(product_id.to_string(), link.clone());
+
+        Ok(link)
+    }
+}
+
+#[async_trait]
+impl StoreProvider for GogClient {
+    fn id(&self) -> &'static str {
+        GOG
+    }
+
+    fn login_flow(&self) -> Result<LoginFlow> {
+        Ok(LoginFlow::AuthorizationCode {
+            url: Self::login_url(),
+        })
+    }
+
+    async fn exchange_code(&self, code: &str) -> Result<AuthToken> {
+        let code = code_from_input(code, "code");
+
+        log::info!("Exchanging GOG authorization code");
+
+        self.request_token(&[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", REDIRECT_URI),
+        ])
+        .await
+    }
+
+    async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken> {
+        log::info!("Refreshing GOG access token");
+
+        self.request_token(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ])
+        .await
+    }
+
+    async fn get_games(&self, token: &AuthToken) -> Result<Vec<Game>> {
+        log::info!("Fetching game library from GOG");
+
+        let mut games = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let url = format!(
+                "{}/account/getFilteredProducts?mediaType=1&page={}",
+                EMBED_URL, page
+            );
+            let response: ProductsPage = self
+                .get_json(&url, Some(token), "Failed to fetch library")
+                .await?;
+
+            games.extend(response.products.into_iter().map(|product| Game {
+                app_name: product.id.to_string(),
+                app_title: product.title,
+                app_version: "unknown".to_string(),
+                install_path: None,
+                provider: GOG.to_string(),
+            }));
+
+            if page >= response.total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        log::info!("Successfully fetched {} games from GOG", games.len());
+
+        Ok(games)
+    }
+
+    async fn download_manifest(&self, token: &AuthToken, app_name: &str) -> Result<GameManifest> {
+        log::info!("Downloading manifest for game: {}", app_name);
+
+        let url = format!(
+            "{}/products/{}/os/{}/builds?generation=2",
+            CONTENT_SYSTEM_URL, app_name, PLATFORM
+        );
+        let builds: BuildsResponse = self
+            .get_json(&url, Some(token), "Failed to fetch builds")
+            .await?;
+
+        // Older installer-only releases aren't available through the content system
+        let build = builds
+            .items
+            .into_iter()
+            .find(|b| b.generation == 2)
+            .ok_or_else(|| Error::GameNotFound(app_name.to_string()))?;
+
+        let manifest: BuildManifest = self
+            .get_manifest(&build.link, "Failed to download build manifest")
+            .await?;
+
+        let mut files = Vec::new();
+        for depot in manifest.depots.iter().filter(|d| wanted_depot(d, app_name)) {
+            let url = format!(
+                "{}/content-system/v2/meta/{}",
+                CDN_URL,
+                galaxy_path(&depot.manifest)
+            );
+            let depot_manifest: DepotManifest = self
+                .get_manifest(&url, "Failed to download depot manifest")
+                .await?;
+            files.extend(depot_manifest.depot.items);
+        }
+
+        Ok(to_game_manifest(
+            app_name,
+            &build.version_name,
+            &manifest,
+            files,
+        ))
+    }
+
+    async fn download_chunk(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        chunk_guid: &str,
+    ) -> Result<Vec<u8>> {
+        log::debug!("Downloading chunk: {}", chunk_guid);
+
+        let link = self.secure_link(token, app_name).await?;
+        let chunk = self
+            .get_bytes(
+                &link.chunk_url(chunk_guid),
+                None,
+                "Failed to download chunk",
+            )
+            .await;
+        if let Err(Error::TokenRejected(_)) = &chunk {
+            // The link expired with the token it was made for
+            self.secure_links.lock().unwrap().remove(app_name);
+        }
+        chunk
+    }
+
+    fn chunk_encoding(&self) -> ChunkEncoding {
+        ChunkEncoding::Zlib
+    }
+}
+
+fn error_from_response(status: StatusCode, body: &str, context: &str) -> Error {
+    if status == StatusCode::UNAUTHORIZED {
+        Error::TokenRejected(format!(
+            "{}: GOG session expired. Run 'rauncher auth --provider gog' to log in again",
+            context
+        ))
+    } else {
+        Error::Api(format!("{}: {} - {}", context, status, body))
+    }
+}
+
+/// Content-system objects are sharded by the first two byte pairs of their hash
+fn galaxy_path(hash: &str) -> String {
+    match (hash.get(0..2), hash.get(2..4)) {
+        (Some(a), Some(b)) => format!("{}/{}/{}", a, b, hash),
+        _ => hash.to_string(),
+    }
+}
+
+/// Base game depots in a language-neutral or English variant; DLCs are separate products
+fn wanted_depot(depot: &Depot, product_id: &str) -> bool {
+    depot.product_id == product_id
+        && (depot.languages.is_empty()
+            || depot
+                .languages
+                .iter()
+                .any(|l| l == "*" || l.starts_with("en")))
+}
+
+fn inflate(data: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    ZlibDecoder::new(data).read_to_end(&mut out)?;
+    Ok(out)
+}
+
+/// Map GOG's depot files onto rauncher's manifest; each chunk is addressed by its
+/// compressed md5 and used whole
+fn to_game_manifest(
+    app_name: &str,
+    version: &str,
+    manifest: &BuildManifest,
+    items: Vec<DepotItem>,
+) -> GameManifest {
+    let base = manifest
+        .products
+        .iter()
+        .find(|p| p.product_id == manifest.base_product_id);
+
+    let mut chunk_filesize_list = BTreeMap::new();
+    let file_list: Vec<FileManifest> = items
+        .into_iter()
+        .filter(|item| item.kind == "DepotFile")
+        .map(|item| FileManifest {
+            filename: item.path.replace('\\', "/"),
+            file_hash: Vec::new(),
+            file_chunk_parts: item
+                .chunks
+                .into_iter()
+                .map(|chunk| {
+                    if chunk.compressed_size > 0 {
+                        chunk_filesize_list
+                            .insert(chunk.compressed_md5.clone(), chunk.compressed_size);
+                    }
+                    ChunkPart {
+                        guid: chunk.compressed_md5,
+                        offset: 0,
+                        size: chunk.size,
+                    }
+                })
+                .collect(),
+        })
+        .collect();
+
+    let build_size = file_list
+        .iter()
+        .flat_map(|f| &f.file_chunk_parts)
+        .map(|p| p.size)
+        .sum();
+
+    GameManifest {
+        manifest_file_version: "gog-v2".to_string(),
+        is_file_data: true,
+        app_name: app_name.to_string(),
+        app_version: version.to_string(),
+        launch_exe: base
+            .map(|p| p.temp_executable.replace('\\', "/"))
+            .unwrap_or_default(),
+        launch_command: base.map(|p| p.temp_arguments.clone()).unwrap_or_default(),
+        build_size,
+        file_list,
+        chunk_hash_list: BTreeMap::new(),
+        chunk_sha_list: BTreeMap::new(),
+        data_group_list: BTreeMap::new(),
+        chunk_filesize_list,
+        custom_fields: BTreeMap::new(),
+        prerequisite: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secure_link_chunk_url() {
+        let link: SecureLink = serde_json::from_str(
+            r#"{
+                "url_format": "{base_url}/token=nva={expires_at}~hmac={hmac}{path}",
+                "parameters": {
+                    "base_url": "https://cdn.example.com",
+                    "path": "/content-system/v2/store/1207658924/",
+                    "expires_at": 1700000000,
+                    "hmac": "deadbeef"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            link.chunk_url("0123456789abcdef"),
+            "https://cdn.example.com/token=nva=1700000000~hmac=deadbeef\
+             /content-system/v2/store/1207658924/01/23/0123456789abcdef"
+        );
+    }
+
+    #[test]
+    fn test_depot_files_map_to_manifest() {
+        let manifest: BuildManifest = serde_json::from_str(
+            r#"{
+                "baseProductId": "1207658924",
+                "depots": [],
+                "products": [{"productId": "1207658924", "temp_executable": "bin\\game.exe"}]
+            }"#,
+        )
+        .unwrap();
+        let contents: DepotManifest = serde_json::from_str(
+            r#"{"depot": {"items": [
+                {"type": "DepotFile", "path": "bin\\game.exe", "chunks": [
+                    {"md5": "a", "size": 100, "compressedMd5": "c1", "compressedSize": 40},
+                    {"md5": "b", "size": 50, "compressedMd5": "c2", "compressedSize": 20}
+                ]},
+                {"type": "DepotDirectory", "path": "saves"}
+            ]}}"#,
+        )
+        .unwrap();
+
+        let result = to_game_manifest("1207658924", "1.2", &manifest, contents.depot.items);
+
+        assert_eq!(result.launch_exe, "bin/game.exe");
+        assert_eq!(result.build_size, 150);
+        assert_eq!(result.download_size(), 60);
+        assert_eq!(result.file_list.len(), 1);
+        assert_eq!(result.file_list[0].filename, "bin/game.exe");
+        assert_eq!(result.file_list[0].file_chunk_parts[1].guid, "c2");
+    }
+}
@@ -0,0 +1,513 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::{LoginFlow, StoreProvider, ITCH};
+use crate::api::{ChunkPart, FileManifest, Game, GameManifest};
+use crate::auth::AuthToken;
+use crate::logs::transcript::RecordedSend;
+use crate::{Error, Result};
+
+// Request timeout configuration
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+// itch.io server-side API; the key is part of the path
+const API_URL: &str = "https://itch.io/api/1";
+const API_KEYS_URL: &str = "https://itch.io/user/settings/api-keys";
+
+// How deep to look for an executable when a game has no .itch.toml
+const EXECUTABLE_SEARCH_DEPTH: usize = 2;
+
+#[derive(Debug, Deserialize)]
+struct MeResponse {
+    user: ItchUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItchUser {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnedKeysPage {
+    #[serde(default)]
+    owned_keys: Vec<OwnedKey>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnedKey {
+    id: u64,
+    game: ItchGame,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItchGame {
+    id: u64,
+    title: String,
+    #[serde(default)]
+    classification: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadsResponse {
+    #[serde(default)]
+    uploads: Vec<Upload>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Upload {
+    id: u64,
+    filename: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    build_id: Option<u64>,
+    #[serde(default)]
+    updated_at: Option<String>,
+    #[serde(default)]
+    p_windows: bool,
+    #[serde(default)]
+    p_linux: bool,
+    #[serde(default)]
+    p_osx: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadResponse {
+    url: String,
+}
+
+/// The launch manifest itch.io games may ship at their root
+#[derive(Debug, Deserialize)]
+struct ItchToml {
+    #[serde(default)]
+    actions: Vec<ItchAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItchAction {
+    path: String,
+}
+
+pub struct ItchClient {
+    client: Client,
+    /// Download key per game, from the latest manifest request
+    download_keys: Mutex<HashMap<String, u64>>,
+}
+
+impl ItchClient {
+    pub fn new() -> Result<Self> {
+        let client = Client::builder()
+            .user_agent("rauncher/0.1.0")
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            download_keys: Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        token: &AuthToken,
+        path: &str,
+        context: &str,
+    ) -> Result<T> {
+        // The URL carries the API key, so it's never logged
+        let url = format!("{}/{}/{}", API_URL, token.access_token, path);
+        let response = self.client.get(&url).send_recorded().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(if status == reqwest::StatusCode::FORBIDDEN {
+                Error::Auth(format!(
+                    "{}: the itch.io API key was rejected. Run 'rauncher auth --provider itch' again",
+                    context
+                ))
+            } else {
+                Error::Api(format!("{}: {}", context, status))
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    async fn get_owned_keys(&self, token: &AuthToken) -> Result<Vec<OwnedKey>> {
+        let mut keys: Vec<OwnedKey> = Vec::new();
+
+        for page in 1.. {
+            let response: OwnedKeysPage = self
+                .get_json(
+                    token,
+                    &format!("my-owned-keys?page={}", page),
+                    "Failed to fetch library",
+                )
+                .await?;
+
+            if response.owned_keys.is_empty() {
+                break;
+            }
+
+            for key in response.owned_keys {
+                // Bundles can grant the same game more than once
+                if !keys.iter().any(|k| k.game.id == key.game.id) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        Ok(keys)
+    }
+}
+
+#[async_trait]
+impl StoreProvider for ItchClient {
+    fn id(&self) -> &'static str {
+        ITCH
+    }
+
+    fn login_flow(&self) -> Result<LoginFlow> {
+        Ok(LoginFlow::ApiKey {
+            url: API_KEYS_URL.to_string(),
+        })
+    }
+
+    async fn exchange_code(&self, code: &str) -> Result<AuthToken> {
+        let token = AuthToken {
+            access_token: code.trim().to_string(),
+            refresh_token: String::new(),
+            // API keys don't expire; they are revoked from the itch.io settings instead
+            expires_at: chrono::DateTime::<chrono::Utc>::MAX_UTC,
+            account_id: String::new(),
+        };
+
+        let me: MeResponse = self
+            .get_json(&token, "me", "Failed to verify API key")
+            .await?;
+
+        Ok(AuthToken {
+            account_id: me.user.id.to_string(),
+            ..token
+        })
+    }
+
+    async fn refresh_token(&self, _refresh_token: &str) -> Result<AuthToken> {
+        Err(Error::Auth(
+            "itch.io API keys can't be refreshed. Run 'rauncher auth --provider itch' again"
+                .to_string(),
+        ))
+    }
+
+    async fn get_games(&self, token: &AuthToken) -> Result<Vec<Game>> {
+        log::info!("Fetching game library from itch.io");
+
+        let games: Vec<Game> = self
+            .get_owned_keys(token)
+            .await?
+            .into_iter()
+            .filter(|key| key.game.classification.as_deref().unwrap_or("game") == "game")
+            .map(|key| Game {
+                app_name: key.game.id.to_string(),
+                app_title: key.game.title,
+                app_version: "unknown".to_string(),
+                install_path: None,
+                provider: ITCH.to_string(),
+            })
+            .collect();
+
+        log::info!("Successfully fetched {} games from itch.io", games.len());
+
+        Ok(games)
+    }
+
+    async fn download_manifest(&self, token: &AuthToken, app_name: &str) -> Result<GameManifest> {
+        log::info!("Fetching uploads for game: {}", app_name);
+
+        let key = self
+            .get_owned_keys(token)
+            .await?
+            .into_iter()
+            .find(|k| k.game.id.to_string() == app_name)
+            .ok_or_else(|| Error::GameNotFound(app_name.to_string()))?;
+
+        let response: UploadsResponse = self
+            .get_json(
+                token,
+                &format!("game/{}/uploads?download_key_id={}", app_name, key.id),
+                "Failed to fetch uploads",
+            )
+            .await?;
+
+        let upload = pick_upload(response.uploads).ok_or_else(|| {
+            Error::Api(format!(
+                "{} has no download for this platform or Windows",
+                key.game.title
+            ))
+        })?;
+
+        self.download_keys
+            .lock()
+            .unwrap()
+            .insert(app_name.to_string(), key.id);
+
+        Ok(to_game_manifest(app_name, upload))
+    }
+
+    async fn download_chunk(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        chunk_guid: &str,
+    ) -> Result<Vec<u8>> {
+        let key_id = self
+            .download_keys
+            .lock()
+            .unwrap()
+            .get(app_name)
+            .copied()
+            .ok_or_else(|| Error::Other(format!("No download in progress for {}", app_name)))?;
+
+        let download: DownloadResponse = self
+            .get_json(
+                token,
+                &format!("upload/{}/download?download_key_id={}", chunk_guid, key_id),
+                "Failed to get download link",
+            )
+            .await?;
+
+        log::debug!("Downloading upload: {}", chunk_guid);
+
+        let response = self.client.get(&download.url).send_recorded().await?;
+
+        if !response.status().is_success() {
+            return Err(Error::Api(format!(
+                "Failed to download upload {}: {}",
+                chunk_guid,
+                response.status()
+            )));
+        }
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    fn finalize_install(&self, manifest: &GameManifest, install_path: &Path) -> Result<()> {
+        for file in &manifest.file_list {
+            let path = install_path.join(&file.filename);
+            if crate::storage::extract_archive(&path, install_path)? {
+                fs::remove_file(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn launch_executable(&self, install_path: &Path) -> Option<String> {
+        if let Some(action) = fs::read_to_string(install_path.join(".itch.toml"))
+            .ok()
+            .and_then(|contents| toml::from_str::<ItchToml>(&contents).ok())
+            .and_then(|manifest| manifest.actions.into_iter().next())
+        {
+            return Some(action.path);
+        }
+
+        find_executable(install_path)
+    }
+}
+
+/// Prefer a native upload, falling back to Windows builds that run through Wine
+fn pick_upload(uploads: Vec<Upload>) -> Option<Upload> {
+    let native = |u: &Upload| {
+        if cfg!(target_os = "windows") {
+            u.p_windows
+        } else if cfg!(target_os = "macos") {
+            u.p_osx
+        } else {
+            u.p_linux
+        }
+    };
+
+    let (native_uploads, others): (Vec<_>, Vec<_>) = uploads.into_iter().partition(native);
+
+    native_uploads
+        .into_iter()
+        .next()
+        .or_else(|| others.into_iter().find(|u| u.p_windows))
+}
+
+/// An upload is downloaded whole, as a single chunk named by its id
+fn to_game_manifest(app_name: &str, upload: Upload) -> GameManifest {
+    let filename = Path::new(&upload.filename)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| upload.id.to_string());
+
+    let version = upload
+        .build_id
+        .map(|id| id.to_string())
+        .or(upload.updated_at)
+        .unwrap_or_else(|| upload.id.to_string());
+
+    GameManifest {
+        manifest_file_version: "itch".to_string(),
+        is_file_data: true,
+        app_name: app_name.to_string(),
+        app_version: version,
+        launch_exe: String::new(),
+        launch_command: String::new(),
+        build_size: upload.size,
+        file_list: vec![FileManifest {
+            filename,
+            file_hash: Vec::new(),
+            file_chunk_parts: vec![ChunkPart {
+                guid: upload.id.to_string(),
+                offset: 0,
+                size: upload.size,
+            }],
+        }],
+        chunk_hash_list: BTreeMap::new(),
+        chunk_sha_list: BTreeMap::new(),
+        data_group_list: BTreeMap::new(),
+        chunk_filesize_list: BTreeMap::new(),
+        custom_fields: BTreeMap::new(),
+        prerequisite: None,
+    }
+}
+
+/// Guess the game executable: the first platform-appropriate binary, shallowest first
+fn find_executable(install_path: &Path) -> Option<String> {
+    let mut candidates = Vec::new();
+    collect_files(install_path, EXECUTABLE_SEARCH_DEPTH, &mut candidates);
+    candidates.sort_by_key(|path| (path.components().count(), path.clone()));
+
+    let extensions: &[&str] = if cfg!(target_os = "windows") {
+        &["exe"]
+    } else {
+        &["x86_64", "sh", "AppImage", "exe"]
+    };
+
+    extensions.iter().find_map(|ext| {
+        candidates
+            .iter()
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+            })
+            .find(|path| !is_helper_binary(path))
+            .and_then(|path| path.strip_prefix(install_path).ok())
+            .map(|path| path.to_string_lossy().into_owned())
+    })
+}
+
+fn collect_files(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            if depth > 0 {
+                collect_files(&path, depth - 1, out);
+            }
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+/// Crash reporters and uninstallers that sit next to the real executable
+fn is_helper_binary(path: &Path) -> bool {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    ["unitycrashhandler", "unins", "crashpad", "vc_redist"]
+        .iter()
+        .any(|helper| stem.starts_with(helper))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn upload(id: u64, windows: bool, linux: bool) -> Upload {
+        Upload {
+            id,
+            filename: format!("game-{}.zip", id),
+            size: 10,
+            build_id: None,
+            updated_at: None,
+            p_windows: windows,
+            p_linux: linux,
+            p_osx: false,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_pick_upload_prefers_native_builds() {
+        let picked = pick_upload(vec![upload(1, true, false), upload(2, false, true)]).unwrap();
+        assert_eq!(picked.id, 2);
+
+        let picked = pick_upload(vec![upload(1, true, false)]).unwrap();
+        assert_eq!(picked.id, 1);
+
+        assert!(pick_upload(vec![upload(3, false, false)]).is_none());
+    }
+
+    #[test]
+    fn test_upload_maps_to_single_file_manifest() {
+        let manifest = to_game_manifest(
+            "42",
+            Upload {
+                filename: "../sneaky/game.zip".to_string(),
+                build_id: Some(7),
+                ..upload(9, true, true)
+            },
+        );
+
+        assert_eq!(manifest.app_version, "7");
+        assert_eq!(manifest.file_list[0].filename, "game.zip");
+        assert_eq!(manifest.file_list[0].file_chunk_parts[0].guid, "9");
+    }
+
+    #[test]
+    fn test_find_executable_skips_helpers() {
+        let temp_dir = TempDir::new().unwrap();
+        let game_dir = temp_dir.path().join("Game");
+        fs::create_dir_all(&game_dir).unwrap();
+        fs::write(game_dir.join("UnityCrashHandler64.exe"), b"").unwrap();
+        fs::write(game_dir.join("Game.exe"), b"").unwrap();
+        fs::write(game_dir.join("readme.txt"), b"").unwrap();
+
+        assert_eq!(
+            find_executable(temp_dir.path()).as_deref(),
+            Some(Path::new("Game").join("Game.exe").to_str().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_itch_toml_action_wins() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("Other.exe"), b"").unwrap();
+        fs::write(
+            temp_dir.path().join(".itch.toml"),
+            "[[actions]]\nname = \"play\"\npath = \"bin/launch.sh\"\n",
+        )
+        .unwrap();
+
+        let client = ItchClient::new().unwrap();
+        assert_eq!(
+            client.launch_executable(temp_dir.path()).as_deref(),
+            Some("bin/launch.sh")
+        );
+    }
+}
@@ -0,0 +1,273 @@
+mod amazon;
+mod epic;
+mod gog;
+mod itch;
+
+pub use amazon::AmazonClient;
+pub use gog::GogClient;
+pub use itch::ItchClient;
+
+use async_trait::async_trait;
+use flate2::read::ZlibDecoder;
+use std::io::Read;
+use std::path::Path;
+
+use crate::api::{CloudSave, EpicClient, Game, GameManifest, SaveFile};
+use crate::auth::{AuthToken, TokenRefresher};
+use crate::{Error, Result};
+
+pub const EPIC: &str = "epic";
+pub const GOG: &str = "gog";
+pub const AMAZON: &str = "amazon";
+pub const ITCH: &str = "itch";
+
+/// Identifiers of every supported store, as accepted by `--provider`
+pub const PROVIDER_IDS: [&str; 4] = [EPIC, GOG, AMAZON, ITCH];
+
+/// Compression of a store's chunks on its CDN
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkEncoding {
+    /// Stored as file data
+    Plain,
+    /// zlib streams, as on GOG's content system
+    Zlib,
+    /// Epic's chunk files, a header followed by the data
+    Epic,
+}
+
+impl ChunkEncoding {
+    /// Turn a downloaded chunk into file data
+    pub fn decode(self, data: Vec<u8>) -> Result<Vec<u8>> {
+        match self {
+            Self::Plain => Ok(data),
+            Self::Zlib => {
+                let mut out = Vec::new();
+                ZlibDecoder::new(data.as_slice()).read_to_end(&mut out)?;
+                Ok(out)
+            }
+            Self::Epic => crate::manifest::chunk::decode(&data),
+        }
+    }
+}
+
+/// How the user logs in to a store
+#[derive(Debug, Clone)]
+pub enum LoginFlow {
+    /// The user approves a code shown by rauncher (Epic's device flow)
+    DeviceCode,
+    /// The user logs in at `url` and passes back the code from the page they land on
+    AuthorizationCode { url: String },
+    /// The user creates an API key at `url` and passes it in place of a code
+    ApiKey { url: String },
+}
+
+/// A game store backend: authentication, library, manifests, downloads and cloud saves
+#[async_trait]
+pub trait StoreProvider: Send + Sync {
+    /// Short identifier used on the command line and in install records
+    fn id(&self) -> &'static str;
+
+    /// Human readable store name
+    fn name(&self) -> &'static str {
+        display_name(self.id())
+    }
+
+    /// Start a login; stores that keep state between the two login steps save it here
+    fn login_flow(&self) -> Result<LoginFlow>;
+
+    /// Complete an authorization code or API key login
+    async fn exchange_code(&self, _code: &str) -> Result<AuthToken> {
+        Err(Error::Auth(format!(
+            "{} does not use authorization code logins",
+            self.name()
+        )))
+    }
+
+    async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken>;
+
+    async fn get_games(&self, token: &AuthToken) -> Result<Vec<Game>>;
+
+    /// Download the manifest of the latest build of a game
+    async fn download_manifest(&self, token: &AuthToken, app_name: &str) -> Result<GameManifest>;
+
+    /// Download a chunk referenced by a manifest of `app_name`, as stored on the CDN; see
+    /// `chunk_encoding` for turning it into file data
+    async fn download_chunk(
+        &self,
+        token: &AuthToken,
+        app_name: &str,
+        chunk_guid: &str,
+    ) -> Result<Vec<u8>>;
+
+    /// How downloaded chunks are compressed. Decoding happens on worker threads, away from
+    /// the downloads.
+    fn chunk_encoding(&self) -> ChunkEncoding {
+        ChunkEncoding::Plain
+    }
+
+    /// Post-process downloaded files, e.g. unpack archives, before the install is recorded
+    fn finalize_install(&self, _manifest: &GameManifest, _install_path: &Path) -> Result<()> {
+        Ok(())
+    }
+
+    /// Executable to launch when the manifest doesn't name one, read from the installed files
+    fn launch_executable(&self, _install_path: &Path) -> Option<String> {
+        None
+    }
+
+    async fn get_cloud_saves(&self, _token: &AuthToken, _app_name: &str) -> Result<Vec<CloudSave>> {
+        Err(unsupported(self.name(), "Cloud saves"))
+    }
+
+    /// The files of a snapshot, as listed by `get_cloud_saves`
+    async fn download_cloud_save(
+        &self,
+        _token: &AuthToken,
+        _save: &CloudSave,
+    ) -> Result<Vec<SaveFile>> {
+        Err(unsupported(self.name(), "Cloud saves"))
+    }
+
+    async fn upload_cloud_save(
+        &self,
+        _token: &AuthToken,
+        _app_name: &str,
+        _save_data: &[u8],
+    ) -> Result<()> {
+        Err(unsupported(self.name(), "Cloud saves"))
+    }
+}
+
+/// Stores refresh their own tokens, for `AuthManager` and for downloads outliving a token
+#[async_trait]
+impl<T: StoreProvider + ?Sized> TokenRefresher for T {
+    async fn refresh_token(&self, refresh_token: &str) -> Result<AuthToken> {
+        StoreProvider::refresh_token(self, refresh_token).await
+    }
+}
+
+fn unsupported(provider: &str, feature: &str) -> Error {
+    Error::Other(format!("{} are not supported for {}", feature, provider))
+}
+
+/// Human readable name of a store by its identifier
+pub fn display_name(id: &str) -> &'static str {
+    match id {
+        EPIC => "Epic Games Store",
+        GOG => "GOG",
+        AMAZON => "Amazon Games",
+        ITCH => "itch.io",
+        _ => "Unknown store",
+    }
+}
+
+/// Create the client for a store by its identifier
+pub fn create(id: &str) -> Result<Box<dyn StoreProvider>> {
+    match id {
+        EPIC => Ok(Box::new(EpicClient::new()?)),
+        GOG => Ok(Box::new(GogClient::new()?)),
+        AMAZON => Ok(Box::new(AmazonClient::new()?)),
+        ITCH => Ok(Box::new(ItchClient::new()?)),
+        _ => Err(Error::Other(format!(
+            "Unknown provider '{}' (expected one of: {})",
+            id,
+            PROVIDER_IDS.join(", ")
+        ))),
+    }
+}
+
+/// Accept either a bare authorization code or the whole URL the login page redirected to,
+/// which carries the code in its `param` query parameter
+fn code_from_input(input: &str, param: &str) -> String {
+    let input = input.trim();
+
+    reqwest::Url::parse(input)
+        .ok()
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == param)
+                .map(|(_, value)| value.into_owned())
+        })
+        .unwrap_or_else(|| input.to_string())
+}
+
+/// Stable `provider:app_name` identifier of a game
+pub fn qualified_id(provider: &str, app_name: &str) -> String {
+    format!("{}:{}", provider, app_name)
+}
+
+/// Split a `provider:app_name` address; bare app names carry no provider.
+/// Only known provider prefixes are split off, so app names containing ':' still work.
+pub fn parse_address(address: &str) -> (Option<&str>, &str) {
+    match address.split_once(':') {
+        Some((provider, app_name)) if PROVIDER_IDS.contains(&provider) => {
+            (Some(provider), app_name)
+        }
+        _ => (None, address),
+    }
+}
+
+/// Provider of install records written before rauncher supported more than one store
+pub(crate) fn default_provider() -> String {
+    EPIC.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_from_redirect_url() {
+        assert_eq!(
+            code_from_input(
+                "https://embed.gog.com/on_login_success?origin=client&code=abc123",
+                "code"
+            ),
+            "abc123"
+        );
+        assert_eq!(
+            code_from_input(
+                "https://www.amazon.com/?openid.oa2.authorization_code=xyz",
+                "openid.oa2.authorization_code"
+            ),
+            "xyz"
+        );
+        assert_eq!(code_from_input("  abc123\n", "code"), "abc123");
+    }
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(parse_address("gog:1207658924"), (Some(GOG), "1207658924"));
+        assert_eq!(parse_address("Fortnite"), (None, "Fortnite"));
+        assert_eq!(parse_address("steam:123"), (None, "steam:123"));
+        assert_eq!(parse_address(&qualified_id(ITCH, "42")), (Some(ITCH), "42"));
+    }
+
+    #[test]
+    fn test_every_provider_can_be_created() {
+        for id in PROVIDER_IDS {
+            assert_eq!(create(id).unwrap().id(), id);
+        }
+        assert!(create("steam").is_err());
+    }
+
+    #[test]
+    fn test_zlib_chunks_are_decoded() {
+        use flate2::{write::ZlibEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"chunk data").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(
+            ChunkEncoding::Zlib.decode(compressed.clone()).unwrap(),
+            b"chunk data"
+        );
+        assert_eq!(
+            ChunkEncoding::Plain.decode(compressed.clone()).unwrap(),
+            compressed
+        );
+        assert!(ChunkEncoding::Zlib.decode(b"not zlib".to_vec()).is_err());
+    }
+}
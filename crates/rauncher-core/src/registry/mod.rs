@@ -0,0 +1,121 @@
+//! Windows registry integration. Every function is a no-op on other platforms.
+
+use std::path::PathBuf;
+
+use crate::games::InstalledGame;
+use crate::Result;
+
+#[cfg(windows)]
+const UNINSTALL_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Uninstall";
+
+#[cfg(windows)]
+const EGL_KEY: &str = r"SOFTWARE\WOW6432Node\Epic Games\EpicGamesLauncher";
+
+#[cfg(windows)]
+fn uninstall_key_path(app_name: &str) -> String {
+    format!(r"{}\rauncher-{}", UNINSTALL_KEY, app_name)
+}
+
+/// Command "Add/Remove Programs" runs to uninstall a game. Windows has asked already, and may
+/// have no console to answer rauncher's own question in, so it's skipped.
+#[cfg(any(windows, test))]
+fn uninstall_command(rauncher: &std::path::Path, app_name: &str) -> String {
+    format!("\"{}\" uninstall {} --yes", rauncher.display(), app_name)
+}
+
+/// Register an installed game in "Add/Remove Programs", uninstalling through rauncher
+pub fn register_install(game: &InstalledGame) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
+
+        let rauncher = std::env::current_exe()?;
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let (key, _) = hkcu.create_subkey(uninstall_key_path(&game.app_name))?;
+
+        key.set_value("DisplayName", &game.app_title)?;
+        key.set_value("DisplayVersion", &game.app_version)?;
+        key.set_value("Publisher", &"Rauncher")?;
+        key.set_value("InstallLocation", &game.install_path.display().to_string())?;
+        key.set_value(
+            "DisplayIcon",
+            &game
+                .install_path
+                .join(&game.executable)
+                .display()
+                .to_string(),
+        )?;
+        key.set_value(
+            "UninstallString",
+            &uninstall_command(&rauncher, &game.app_name),
+        )?;
+        key.set_value("NoModify", &1u32)?;
+        key.set_value("NoRepair", &1u32)?;
+
+        log::debug!("Registered {} in Add/Remove Programs", game.app_name);
+    }
+
+    #[cfg(not(windows))]
+    let _ = game;
+
+    Ok(())
+}
+
+/// Remove a game's "Add/Remove Programs" entry, if it has one
+pub fn unregister_install(app_name: &str) -> Result<()> {
+    #[cfg(windows)]
+    {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        match hkcu.delete_subkey_all(uninstall_key_path(app_name)) {
+            Ok(()) => log::debug!("Removed {} from Add/Remove Programs", app_name),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    #[cfg(not(windows))]
+    let _ = app_name;
+
+    Ok(())
+}
+
+/// Data directory of the official Epic Games Launcher, as recorded in the registry
+pub fn epic_launcher_data_path() -> Option<PathBuf> {
+    #[cfg(windows)]
+    {
+        use winreg::enums::HKEY_LOCAL_MACHINE;
+        use winreg::RegKey;
+
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let from_registry = hklm
+            .open_subkey(EGL_KEY)
+            .and_then(|key| key.get_value::<String, _>("AppDataPath"))
+            .map(PathBuf::from);
+
+        Some(
+            from_registry
+                .unwrap_or_else(|_| PathBuf::from(r"C:\ProgramData\Epic\EpicGamesLauncher\Data")),
+        )
+    }
+
+    #[cfg(not(windows))]
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uninstall_command_skips_confirmation() {
+        let rauncher = std::path::Path::new(r"C:\Program Files\rauncher\rauncher.exe");
+        assert_eq!(
+            uninstall_command(rauncher, "Fortnite"),
+            r#""C:\Program Files\rauncher\rauncher.exe" uninstall Fortnite --yes"#
+        );
+    }
+}
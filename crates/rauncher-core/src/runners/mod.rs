@@ -0,0 +1,580 @@
+//! Wine and Proton builds managed by rauncher. GE-Proton and Wine-GE releases are downloaded
+//! from GitHub into `<data dir>/runners`, one directory per build, and games pick one by name
+//! with their `runner` setting.
+
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::fmt;
+use std::fs;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::logs::transcript::RecordedSend;
+use crate::storage::Removal;
+use crate::{Error, Result};
+
+const GITHUB_API: &str = "https://api.github.com";
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Releases listed per kind; older builds can still be installed by hand
+const RELEASES_PER_PAGE: u32 = 20;
+
+/// Version and origin of a runner, written into its directory at install
+const RECORD_FILE: &str = ".rauncher-runner.json";
+
+/// Prefix of the directories runners are unpacked into before being moved in place
+const STAGING_PREFIX: &str = ".staging-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunnerKind {
+    /// GE-Proton, run through its `proton` script
+    Proton,
+    /// Wine-GE, run through `bin/wine`
+    Wine,
+}
+
+impl RunnerKind {
+    pub const ALL: [RunnerKind; 2] = [RunnerKind::Proton, RunnerKind::Wine];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RunnerKind::Proton => "proton",
+            RunnerKind::Wine => "wine",
+        }
+    }
+
+    /// GitHub repository publishing the builds
+    fn repository(self) -> &'static str {
+        match self {
+            RunnerKind::Proton => "GloriousEggroll/proton-ge-custom",
+            RunnerKind::Wine => "GloriousEggroll/wine-ge-custom",
+        }
+    }
+
+    /// The program games are run through, inside a runner's directory
+    fn executable(self, dir: &Path) -> PathBuf {
+        match self {
+            RunnerKind::Proton => dir.join("proton"),
+            RunnerKind::Wine => dir.join("bin").join("wine"),
+        }
+    }
+
+    /// Tell the kind of a runner directory from the program it ships
+    fn detect(dir: &Path) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.executable(dir).is_file())
+    }
+}
+
+impl fmt::Display for RunnerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RunnerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown runner kind '{}' (proton or wine)", s))
+    }
+}
+
+/// A runner installed in the runners directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct Runner {
+    /// Directory name, which games select the runner by
+    pub name: String,
+    pub kind: RunnerKind,
+    /// Release tag it was installed from, or the directory name for runners copied in by hand
+    pub version: String,
+    pub path: PathBuf,
+    pub installed_at: Option<DateTime<Utc>>,
+}
+
+impl Runner {
+    /// The Wine binary or Proton script to launch games with
+    pub fn executable(&self) -> PathBuf {
+        self.kind.executable(&self.path)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RunnerRecord {
+    kind: RunnerKind,
+    version: String,
+    installed_at: DateTime<Utc>,
+}
+
+/// A build published on GitHub
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunnerRelease {
+    pub kind: RunnerKind,
+    pub version: String,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Archive file name and download
+    pub asset: String,
+    pub url: String,
+    pub size: u64,
+    /// The release's SHA-512 checksum file, when it has one
+    checksum_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    prerelease: bool,
+    published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    size: u64,
+}
+
+/// Runners in the runners directory, by name
+pub fn installed() -> Result<Vec<Runner>> {
+    installed_in(&Config::runners_dir()?)
+}
+
+fn installed_in(dir: &Path) -> Result<Vec<Runner>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut runners: Vec<Runner> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| read_runner(&entry.path()))
+        .collect();
+    runners.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(runners)
+}
+
+fn read_runner(path: &Path) -> Option<Runner> {
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    let record: Option<RunnerRecord> = fs::read_to_string(path.join(RECORD_FILE))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok());
+
+    let kind = match &record {
+        Some(record) => record.kind,
+        None => RunnerKind::detect(path)?,
+    };
+
+    Some(Runner {
+        kind,
+        version: record
+            .as_ref()
+            .map_or_else(|| name.clone(), |r| r.version.clone()),
+        installed_at: record.map(|r| r.installed_at),
+        path: path.to_path_buf(),
+        name,
+    })
+}
+
+/// An installed runner by name
+pub fn find(name: &str) -> Result<Runner> {
+    installed()?
+        .into_iter()
+        .find(|runner| runner.name == name)
+        .ok_or_else(|| {
+            Error::Config(format!(
+                "Runner '{}' is not installed (see 'rauncher runners list')",
+                name
+            ))
+        })
+}
+
+/// Get rid of an installed runner. Refused while games are set to use it.
+pub fn remove(config: &Config, name: &str, removal: Removal) -> Result<()> {
+    let runner = find(name)?;
+
+    let users: Vec<&str> = config
+        .games
+        .iter()
+        .filter(|(_, settings)| settings.runner.as_deref() == Some(name))
+        .map(|(app_name, _)| app_name.as_str())
+        .collect();
+    if !users.is_empty() {
+        return Err(Error::Other(format!(
+            "{} is the runner of {} (change their runner first)",
+            name,
+            users.join(", ")
+        )));
+    }
+
+    crate::storage::remove_dir(&runner.path, removal)?;
+    log::debug!("Removed runner {}", name);
+
+    Ok(())
+}
+
+fn client() -> Result<Client> {
+    // No overall timeout: runner archives are hundreds of megabytes
+    Ok(Client::builder()
+        .user_agent("rauncher/0.1.0")
+        .connect_timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()?)
+}
+
+/// Recent releases of a kind, newest first. Prereleases are left out.
+pub async fn available(kind: RunnerKind) -> Result<Vec<RunnerRelease>> {
+    let url = format!(
+        "{}/repos/{}/releases?per_page={}",
+        GITHUB_API,
+        kind.repository(),
+        RELEASES_PER_PAGE
+    );
+    let response = client()?
+        .get(&url)
+        .header("Accept", "application/vnd.github+json")
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .send_recorded()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error::Api(format!(
+            "Failed to list {} releases: {}",
+            kind.repository(),
+            status
+        )));
+    }
+
+    let releases: Vec<GithubRelease> = response.json().await?;
+    Ok(releases
+        .into_iter()
+        .filter(|release| !release.prerelease)
+        .filter_map(|release| to_release(kind, release))
+        .collect())
+}
+
+/// The archive and checksum of a GitHub release; None for releases without a Linux archive
+fn to_release(kind: RunnerKind, release: GithubRelease) -> Option<RunnerRelease> {
+    let archive = release
+        .assets
+        .iter()
+        .find(|asset| asset.name.ends_with(".tar.gz") || asset.name.ends_with(".tar.xz"))?;
+    let checksum_url = release
+        .assets
+        .iter()
+        .find(|asset| asset.name.ends_with(".sha512sum"))
+        .map(|asset| asset.browser_download_url.clone());
+
+    Some(RunnerRelease {
+        kind,
+        version: release.tag_name,
+        published_at: release.published_at,
+        asset: archive.name.clone(),
+        url: archive.browser_download_url.clone(),
+        size: archive.size,
+        checksum_url,
+    })
+}
+
+/// A release of a kind by tag, or the newest one without a version
+pub async fn release(kind: RunnerKind, version: Option<&str>) -> Result<RunnerRelease> {
+    let releases = available(kind).await?;
+    let release = match version {
+        Some(version) => releases
+            .into_iter()
+            .find(|release| release.version.eq_ignore_ascii_case(version)),
+        None => releases.into_iter().next(),
+    };
+
+    release.ok_or_else(|| {
+        Error::Other(format!(
+            "No {} release {} found in {}",
+            kind,
+            version.unwrap_or("at all"),
+            kind.repository()
+        ))
+    })
+}
+
+/// Download, verify and unpack a release into the runners directory
+pub async fn install(release: &RunnerRelease) -> Result<Runner> {
+    let runners_dir = Config::runners_dir()?;
+    if let Some(existing) = installed_in(&runners_dir)?
+        .into_iter()
+        .find(|runner| runner.kind == release.kind && runner.version == release.version)
+    {
+        return Err(Error::Other(format!(
+            "{} is already installed as {}",
+            release.version, existing.name
+        )));
+    }
+
+    let staging = runners_dir.join(format!("{}{}", STAGING_PREFIX, release.version));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+
+    let result = install_staged(release, &runners_dir, &staging).await;
+    if let Err(e) = fs::remove_dir_all(&staging) {
+        log::debug!("Failed to remove {:?}: {}", staging, e);
+    }
+    result
+}
+
+async fn install_staged(
+    release: &RunnerRelease,
+    runners_dir: &Path,
+    staging: &Path,
+) -> Result<Runner> {
+    let client = client()?;
+    let archive = staging.join(&release.asset);
+    let digest = download(&client, &release.url, &archive).await?;
+
+    match &release.checksum_url {
+        Some(url) => {
+            let expected = client.get(url).send_recorded().await?.text().await?;
+            verify_checksum(&digest, &expected, &release.asset)?;
+        }
+        None => log::warn!("{} has no checksum to verify", release.version),
+    }
+
+    let unpacked = staging.join("unpacked");
+    unpack(&archive, &unpacked)?;
+    let top = single_dir(&unpacked)?;
+
+    let name = top
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| release.version.clone());
+    let dest = runners_dir.join(&name);
+    if dest.exists() {
+        return Err(Error::Other(format!("{:?} already exists", dest)));
+    }
+    fs::rename(&top, &dest)?;
+
+    let record = RunnerRecord {
+        kind: release.kind,
+        version: release.version.clone(),
+        installed_at: Utc::now(),
+    };
+    fs::write(dest.join(RECORD_FILE), serde_json::to_string(&record)?)?;
+    crate::logs::transcript::touched("wrote", &dest);
+    log::debug!("Installed runner {} into {:?}", release.version, dest);
+
+    read_runner(&dest).ok_or_else(|| {
+        Error::Other(format!(
+            "{:?} does not contain a Wine or Proton build",
+            dest
+        ))
+    })
+}
+
+/// Stream a download to disk, returning its SHA-512
+async fn download(client: &Client, url: &str, path: &Path) -> Result<Vec<u8>> {
+    let mut response = client.get(url).send_recorded().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error::Api(format!(
+            "Failed to download {}: {}",
+            url, status
+        )));
+    }
+
+    let mut file = BufWriter::new(fs::File::create(path)?);
+    let mut hasher = Sha512::new();
+    while let Some(chunk) = response.chunk().await? {
+        hasher.update(&chunk);
+        file.write_all(&chunk)?;
+    }
+    file.flush()?;
+
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Compare a digest with a `sha512sum` line: the hex digest, then the file name
+fn verify_checksum(digest: &[u8], checksum_file: &str, asset: &str) -> Result<()> {
+    let expected = checksum_file.split_whitespace().next().unwrap_or_default();
+    let actual: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(Error::Other(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            asset, expected, actual
+        )));
+    }
+    Ok(())
+}
+
+/// Unpack a `.tar.gz` or `.tar.xz` archive into `dest`
+fn unpack(archive: &Path, dest: &Path) -> Result<()> {
+    let name = archive.to_string_lossy().to_lowercase();
+
+    if name.ends_with(".tar.xz") {
+        // lzma-rs only decompresses into a writer, so the tarball goes through a file
+        let tarball = archive.with_extension("");
+        {
+            let mut input = BufReader::new(fs::File::open(archive)?);
+            let mut output = BufWriter::new(fs::File::create(&tarball)?);
+            lzma_rs::xz_decompress(&mut input, &mut output)
+                .map_err(|e| Error::Other(format!("Failed to decompress {:?}: {}", archive, e)))?;
+            output.flush()?;
+        }
+        fs::remove_file(archive)?;
+        tar::Archive::new(fs::File::open(&tarball)?).unpack(dest)?;
+        fs::remove_file(&tarball)?;
+    } else {
+        tar::Archive::new(GzDecoder::new(fs::File::open(archive)?)).unpack(dest)?;
+    }
+
+    Ok(())
+}
+
+/// The one directory a release archive unpacks to
+fn single_dir(dir: &Path) -> Result<PathBuf> {
+    let entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+
+    match entries.as_slice() {
+        [only] if only.is_dir() => Ok(only.clone()),
+        _ => Err(Error::Other(format!(
+            "Expected a single directory in the archive, found {} entries",
+            entries.len()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_installed_runners_are_detected_and_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let proton = dir.path().join("GE-Proton9-20");
+        fs::create_dir_all(&proton).unwrap();
+        fs::write(proton.join("proton"), "").unwrap();
+
+        let wine = dir.path().join("lutris-GE-Proton8-26-x86_64");
+        fs::create_dir_all(wine.join("bin")).unwrap();
+        fs::write(wine.join("bin").join("wine"), "").unwrap();
+        let record = RunnerRecord {
+            kind: RunnerKind::Wine,
+            version: "GE-Proton8-26".to_string(),
+            installed_at: Utc::now(),
+        };
+        fs::write(
+            wine.join(RECORD_FILE),
+            serde_json::to_string(&record).unwrap(),
+        )
+        .unwrap();
+
+        fs::create_dir_all(dir.path().join("not-a-runner")).unwrap();
+        fs::create_dir_all(dir.path().join(".staging-GE-Proton9-21")).unwrap();
+
+        let runners = installed_in(dir.path()).unwrap();
+        assert_eq!(runners.len(), 2);
+        assert_eq!(runners[0].name, "GE-Proton9-20");
+        assert_eq!(runners[0].kind, RunnerKind::Proton);
+        assert_eq!(runners[0].version, "GE-Proton9-20");
+        assert_eq!(runners[0].executable(), proton.join("proton"));
+        assert_eq!(runners[1].kind, RunnerKind::Wine);
+        assert_eq!(runners[1].version, "GE-Proton8-26");
+        assert!(runners[1].installed_at.is_some());
+    }
+
+    #[test]
+    fn test_release_picks_archive_and_checksum() {
+        let asset = |name: &str| GithubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://github.com/dl/{}", name),
+            size: 42,
+        };
+        let release = GithubRelease {
+            tag_name: "GE-Proton9-20".to_string(),
+            prerelease: false,
+            published_at: None,
+            assets: vec![
+                asset("GE-Proton9-20.sha512sum"),
+                asset("GE-Proton9-20.tar.gz"),
+            ],
+        };
+
+        let release = to_release(RunnerKind::Proton, release).unwrap();
+        assert_eq!(release.asset, "GE-Proton9-20.tar.gz");
+        assert_eq!(release.url, "https://github.com/dl/GE-Proton9-20.tar.gz");
+        assert_eq!(
+            release.checksum_url.as_deref(),
+            Some("https://github.com/dl/GE-Proton9-20.sha512sum")
+        );
+
+        let source_only = GithubRelease {
+            tag_name: "GE-Proton1-1".to_string(),
+            prerelease: false,
+            published_at: None,
+            assets: vec![asset("source.zip")],
+        };
+        assert!(to_release(RunnerKind::Proton, source_only).is_none());
+    }
+
+    #[test]
+    fn test_unpack_xz_release() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut tarball = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tarball
+            .append_data(
+                &mut header,
+                "lutris-GE-Proton8-26-x86_64/bin/wine",
+                &b"wine"[..],
+            )
+            .unwrap();
+        let tarball = tarball.into_inner().unwrap();
+
+        let archive = dir.path().join("wine-lutris-GE-Proton8-26-x86_64.tar.xz");
+        let mut compressed = Vec::new();
+        lzma_rs::xz_compress(&mut &tarball[..], &mut compressed).unwrap();
+        fs::write(&archive, compressed).unwrap();
+
+        let unpacked = dir.path().join("unpacked");
+        unpack(&archive, &unpacked).unwrap();
+
+        let top = single_dir(&unpacked).unwrap();
+        assert_eq!(top, unpacked.join("lutris-GE-Proton8-26-x86_64"));
+        assert_eq!(RunnerKind::detect(&top), Some(RunnerKind::Wine));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let digest = Sha512::digest(b"runner").to_vec();
+        let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+
+        assert!(verify_checksum(&digest, &format!("{}  GE-Proton.tar.gz\n", hex), "a").is_ok());
+        assert!(verify_checksum(&digest, "deadbeef  GE-Proton.tar.gz", "a").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Running as a Flatpak. The sandbox gives rauncher its own XDG directories and only the host
+//! directories its permissions name, so install directories elsewhere have to be granted with
+//! `flatpak override` before games can be installed there.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Written by Flatpak into every sandbox
+const FLATPAK_INFO: &str = "/.flatpak-info";
+
+/// A Flatpak sandbox rauncher runs in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Flatpak {
+    pub app_id: String,
+    /// Host paths from the `filesystems` permission, without their `:ro`/`:create` modes
+    filesystems: Vec<String>,
+}
+
+/// The sandbox rauncher runs in, if it is a Flatpak
+pub fn flatpak() -> Option<&'static Flatpak> {
+    static FLATPAK: OnceLock<Option<Flatpak>> = OnceLock::new();
+
+    FLATPAK
+        .get_or_init(|| {
+            let info = fs::read_to_string(FLATPAK_INFO).ok()?;
+            let flatpak = Flatpak::parse(&info);
+            log::debug!("Running in Flatpak: {:?}", flatpak);
+            flatpak
+        })
+        .as_ref()
+}
+
+impl Flatpak {
+    /// Read the app ID and filesystem permissions from `.flatpak-info`
+    fn parse(info: &str) -> Option<Self> {
+        let mut section = "";
+        let mut app_id = None;
+        let mut filesystems = Vec::new();
+
+        for line in info.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name;
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            match (section, key.trim()) {
+                ("Application", "name") => app_id = Some(value.trim().to_string()),
+                ("Context", "filesystems") => {
+                    filesystems = value
+                        .split(';')
+                        .filter(|entry| !entry.is_empty() && !entry.starts_with('!'))
+                        .map(|entry| without_mode(entry).to_string())
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            app_id: app_id?,
+            filesystems,
+        })
+    }
+
+    /// Whether the sandbox sees `path` as it is on the host: its own data directory, or a
+    /// directory its filesystem permissions grant
+    pub fn can_access(&self, path: &Path, home: &Path) -> bool {
+        if path.starts_with(home.join(".var").join("app").join(&self.app_id)) {
+            return true;
+        }
+
+        self.filesystems
+            .iter()
+            .filter_map(|entry| granted_path(entry, home))
+            .any(|granted| path.starts_with(granted))
+    }
+
+    /// The command granting the sandbox access to `dir`
+    pub fn permission_command(&self, dir: &str) -> String {
+        let dir = if dir.contains(char::is_whitespace) {
+            format!("\"{}\"", dir)
+        } else {
+            dir.to_string()
+        };
+        format!(
+            "flatpak override --user --filesystem={} {}",
+            dir, self.app_id
+        )
+    }
+}
+
+/// A `filesystems` entry without its access mode, e.g. `~/Games` for `~/Games:create`
+fn without_mode(entry: &str) -> &str {
+    match entry.rsplit_once(':') {
+        Some((path, "ro" | "rw" | "create")) => path,
+        _ => entry,
+    }
+}
+
+/// The host directory a `filesystems` entry grants. Entries naming XDG user directories other
+/// than the base ones aren't resolved, so paths under them are taken as not granted.
+fn granted_path(entry: &str, home: &Path) -> Option<PathBuf> {
+    let under = |base: PathBuf, rest: &str| {
+        let rest = rest.trim_start_matches('/');
+        if rest.is_empty() {
+            base
+        } else {
+            base.join(rest)
+        }
+    };
+
+    match entry {
+        "host" => Some(PathBuf::from("/")),
+        "home" => Some(home.to_path_buf()),
+        _ if entry.starts_with('/') => Some(PathBuf::from(entry)),
+        _ => {
+            let (base, rest) = entry.split_once('/').unwrap_or((entry, ""));
+            let base = match base {
+                "~" => home.to_path_buf(),
+                "xdg-data" => home.join(".local").join("share"),
+                "xdg-config" => home.join(".config"),
+                "xdg-cache" => home.join(".cache"),
+                _ => return None,
+            };
+            Some(under(base, rest))
+        }
+    }
+}
+
+/// The `flatpak override` command rauncher needs before it can use `dir`, when it runs in a
+/// Flatpak that can't reach it
+pub fn missing_permission(dir: &Path) -> Option<String> {
+    let flatpak = flatpak()?;
+    let home = home_dir()?;
+
+    (!flatpak.can_access(dir, &home)).then(|| flatpak.permission_command(&dir.to_string_lossy()))
+}
+
+/// Whether a path is a document portal mount, which is what a file chooser portal returns
+/// for directories outside the sandbox
+pub fn is_document_portal(path: &Path) -> bool {
+    let mut components = path.components().skip(1).map(|c| c.as_os_str());
+    components.next().is_some_and(|c| c == "run")
+        && components.next().is_some_and(|c| c == "user")
+        && components.next().is_some()
+        && components.next().is_some_and(|c| c == "doc")
+}
+
+/// Where games go by default in a Flatpak: `~/Games/rauncher`, which the Flathub manifest
+/// grants, rather than the sandbox's hidden data directory
+pub fn default_install_dir() -> Option<PathBuf> {
+    flatpak()?;
+    Some(home_dir()?.join("Games").join("rauncher"))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO: &str = "[Application]
+name=io.github.kairosci.rauncher
+runtime=runtime/org.freedesktop.Platform/x86_64/24.08/
+
+[Context]
+shared=network;ipc;
+filesystems=~/Games:create;/mnt/games:rw;xdg-data/Steam;xdg-download;!home;
+";
+
+    #[test]
+    fn test_parse_flatpak_info() {
+        let flatpak = Flatpak::parse(INFO).unwrap();
+        assert_eq!(flatpak.app_id, "io.github.kairosci.rauncher");
+        assert_eq!(
+            flatpak.filesystems,
+            vec!["~/Games", "/mnt/games", "xdg-data/Steam", "xdg-download"]
+        );
+        assert_eq!(Flatpak::parse("[Context]\nfilesystems=home;\n"), None);
+    }
+
+    #[test]
+    fn test_can_access_granted_paths_only() {
+        let flatpak = Flatpak::parse(INFO).unwrap();
+        let home = Path::new("/home/user");
+        let can = |path: &str| flatpak.can_access(Path::new(path), home);
+
+        assert!(can("/home/user/Games/rauncher"));
+        assert!(can("/mnt/games/Fortnite"));
+        assert!(can("/home/user/.local/share/Steam/steamapps"));
+        assert!(can(
+            "/home/user/.var/app/io.github.kairosci.rauncher/data/rauncher/games"
+        ));
+        assert!(!can("/home/user/Downloads/games"));
+        assert!(!can("/mnt/other"));
+        assert!(!can("/home/user/GamesElsewhere"));
+
+        assert_eq!(
+            flatpak.permission_command("/run/media/user/SSD Games"),
+            "flatpak override --user --filesystem=\"/run/media/user/SSD Games\" \
+             io.github.kairosci.rauncher"
+        );
+    }
+
+    #[test]
+    fn test_document_portal_paths() {
+        assert!(is_document_portal(Path::new(
+            "/run/user/1000/doc/a1b2c3/Games"
+        )));
+        assert!(!is_document_portal(Path::new("/run/media/user/Games")));
+        assert!(!is_document_portal(Path::new("/home/user/Games")));
+    }
+}
@@ -0,0 +1,327 @@
+//! Guessing where a game keeps its saves when the store doesn't say. Two hints are combined:
+//! folders in the usual save locations named after the game, as listed by PCGamingWiki for
+//! most titles, and folders whose files changed while the game was last played.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Where games usually save, relative to the user profile (the home directory on macOS), and
+/// how many levels below them a game's own folder is (`LocalLow` has a publisher folder in
+/// between)
+const SAVE_ROOTS: [(&str, usize); 7] = [
+    ("AppData/Local", 1),
+    ("AppData/LocalLow", 2),
+    ("AppData/Roaming", 1),
+    ("Documents/My Games", 1),
+    ("Documents", 1),
+    ("Saved Games", 1),
+    ("Library/Application Support", 1),
+];
+
+/// Folders that change while any game runs, or that belong to Windows or Wine
+const IGNORED_DIRS: [&str; 12] = [
+    "cache",
+    "crashdumps",
+    "crashes",
+    "crashreportclient",
+    "d3dscache",
+    "logs",
+    "microsoft",
+    "my games",
+    "nvidia",
+    "packages",
+    "temp",
+    "webcache",
+];
+
+/// How deep below a save root changed files are looked for
+const SCAN_DEPTH: usize = 8;
+
+/// Entries looked at per save root at most, so a huge `Documents` doesn't stall the scan
+const SCAN_LIMIT: usize = 50_000;
+
+/// Shortest name matched as part of a folder's name, so "Ori" doesn't match "Origin"
+const MIN_PARTIAL_MATCH: usize = 5;
+
+/// A folder that looks like it holds a game's saves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSuggestion {
+    pub path: PathBuf,
+    /// The folder is named after the game
+    pub name_match: bool,
+    /// Files in the folder changed since the game was last started
+    pub changed_files: usize,
+}
+
+/// Folders under `profile` that likely hold the saves of a game called any of `names`, most
+/// likely first. With `since` set, folders with files changed after it are suggested too.
+pub fn detect_save_dirs(
+    profile: &Path,
+    names: &[&str],
+    since: Option<SystemTime>,
+) -> Vec<SaveSuggestion> {
+    let names: Vec<Vec<String>> = names
+        .iter()
+        .map(|name| words(name))
+        .filter(|words| !words.is_empty())
+        .collect();
+
+    let mut found: BTreeMap<PathBuf, SaveSuggestion> = BTreeMap::new();
+    for (root, depth) in SAVE_ROOTS {
+        let root = profile.join(root);
+
+        for dir in game_dirs(&root, depth) {
+            if matches_name(&dir, &names) {
+                let path = save_games_dir(&dir);
+                found.entry(path.clone()).or_insert(SaveSuggestion {
+                    path,
+                    name_match: true,
+                    changed_files: 0,
+                });
+            }
+        }
+
+        if let Some(since) = since {
+            for (dir, changed_files) in changed_dirs(&root, depth, since) {
+                let name_match = matches_name(&dir, &names);
+                let path = save_games_dir(&dir);
+                found
+                    .entry(path.clone())
+                    .or_insert(SaveSuggestion {
+                        path,
+                        name_match,
+                        changed_files: 0,
+                    })
+                    .changed_files += changed_files;
+            }
+        }
+    }
+
+    let mut suggestions: Vec<_> = found.into_values().collect();
+    suggestions.sort_by(|a, b| {
+        let both = |s: &SaveSuggestion| s.name_match && s.changed_files > 0;
+        both(b)
+            .cmp(&both(a))
+            .then(b.changed_files.cmp(&a.changed_files))
+            .then(a.path.cmp(&b.path))
+    });
+    suggestions
+}
+
+/// The lowercased words of a game's name, split at anything but letters and digits
+fn words(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Lowercase letters and digits only, so "Hades II" matches a `HadesII` folder
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether a folder is named after a game: the whole name, a folder name holding it, or the
+/// name's leading words ("Hades" for "Hades II"). Partial matches need `MIN_PARTIAL_MATCH`
+/// letters, and a folder named after only some words elsewhere in the title, such as "Games",
+/// doesn't count.
+fn matches_name(dir: &Path, names: &[Vec<String>]) -> bool {
+    let Some(dir_name) = dir.file_name() else {
+        return false;
+    };
+    let dir_name = normalize(&dir_name.to_string_lossy());
+    if dir_name.is_empty() {
+        return false;
+    }
+
+    names.iter().any(|words| {
+        let name = words.concat();
+        if name.len() < dir_name.len() {
+            return name.len() >= MIN_PARTIAL_MATCH && dir_name.contains(name.as_str());
+        }
+        if dir_name.len() < MIN_PARTIAL_MATCH && dir_name != name {
+            return false;
+        }
+        let mut leading = String::new();
+        words.iter().any(|word| {
+            leading.push_str(word);
+            leading == dir_name
+        })
+    })
+}
+
+/// Unreal Engine games keep their saves in `Saved/SaveGames`, next to logs and configs
+fn save_games_dir(dir: &Path) -> PathBuf {
+    let save_games = dir.join("Saved").join("SaveGames");
+    if save_games.is_dir() {
+        save_games
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+fn is_ignored(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    IGNORED_DIRS.contains(&name.to_lowercase().as_str())
+}
+
+/// The folders `depth` levels below `root`, skipping ignored ones
+fn game_dirs(root: &Path, depth: usize) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    for _ in 0..depth {
+        dirs = dirs
+            .iter()
+            .filter_map(|dir| fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir() && !is_ignored(path))
+            .collect();
+    }
+    dirs
+}
+
+/// Game folders under `root` holding files modified after `since`, with how many. Log files
+/// and ignored folders don't count. Symlinks below the root aren't followed.
+fn changed_dirs(root: &Path, depth: usize, since: SystemTime) -> BTreeMap<PathBuf, usize> {
+    let mut changed = BTreeMap::new();
+    let mut seen = 0;
+    let mut pending = vec![(root.to_path_buf(), 0)];
+
+    while let Some((dir, level)) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            seen += 1;
+            if seen > SCAN_LIMIT {
+                return changed;
+            }
+
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if level < SCAN_DEPTH && !is_ignored(&path) {
+                    pending.push((path, level + 1));
+                }
+                continue;
+            }
+
+            let is_log = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("log"));
+            let modified = metadata.modified().is_ok_and(|modified| modified >= since);
+            // Files directly in the root, or in a publisher folder, belong to no game
+            if !metadata.is_file() || is_log || !modified || level < depth {
+                continue;
+            }
+
+            let below_root = path.strip_prefix(root).unwrap_or(&path);
+            let game_dir: PathBuf = root
+                .components()
+                .chain(below_root.components().take(depth))
+                .collect();
+            *changed.entry(game_dir).or_insert(0) += 1;
+        }
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"save").unwrap();
+    }
+
+    #[test]
+    fn test_detect_by_name() {
+        let profile = tempfile::tempdir().unwrap();
+        let profile = profile.path();
+        touch(&profile.join("AppData/Local/HadesII/Profile1.sav"));
+        touch(&profile.join("AppData/LocalLow/Supergiant/Pyre/save.dat"));
+        touch(&profile.join("AppData/Local/Kiwi/Saved/SaveGames/0.sav"));
+        touch(&profile.join("AppData/Local/Kiwi/Saved/Logs/Kiwi.log"));
+        touch(&profile.join("Documents/My Games/Unrelated/save.dat"));
+        touch(&profile.join("AppData/Local/Ori/save.dat"));
+
+        let suggestions = detect_save_dirs(profile, &["Hades II", "Kiwi"], None);
+        let paths: Vec<_> = suggestions.iter().map(|s| s.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                profile.join("AppData/Local/HadesII"),
+                profile.join("AppData/Local/Kiwi/Saved/SaveGames"),
+            ]
+        );
+        assert!(suggestions
+            .iter()
+            .all(|s| s.name_match && s.changed_files == 0));
+
+        let suggestions = detect_save_dirs(profile, &["Pyre"], None);
+        assert_eq!(
+            suggestions[0].path,
+            profile.join("AppData/LocalLow/Supergiant/Pyre")
+        );
+
+        // Named after the leading words of the title, but not after words further in
+        touch(&profile.join("Saved Games/Hades/Profile1.sav"));
+        touch(&profile.join("Saved Games/Games/settings.ini"));
+        let paths: Vec<_> = detect_save_dirs(profile, &["Hades Deluxe Games Edition"], None)
+            .into_iter()
+            .map(|s| s.path)
+            .collect();
+        assert_eq!(paths, vec![profile.join("Saved Games/Hades")]);
+
+        // Too short to match part of "Origin"
+        touch(&profile.join("AppData/Roaming/Origin/settings.xml"));
+        assert!(detect_save_dirs(profile, &["Ori"], None)
+            .iter()
+            .all(|s| s.path != profile.join("AppData/Roaming/Origin")));
+    }
+
+    #[test]
+    fn test_detect_by_changed_files() {
+        let profile = tempfile::tempdir().unwrap();
+        let profile = profile.path();
+        touch(&profile.join("Saved Games/Codename/old.sav"));
+        touch(&profile.join("AppData/Local/Kiwi/old.sav"));
+
+        let since = SystemTime::now();
+        std::thread::sleep(Duration::from_millis(20));
+        touch(&profile.join("Saved Games/Codename/slot1/a.sav"));
+        touch(&profile.join("Saved Games/Codename/slot1/b.sav"));
+        touch(&profile.join("AppData/Local/Kiwi/new.sav"));
+        touch(&profile.join("AppData/Local/Shared/output.log"));
+        touch(&profile.join("AppData/Local/Temp/scratch.tmp"));
+        touch(&profile.join("AppData/Local/stray.txt"));
+
+        let suggestions = detect_save_dirs(profile, &["Kiwi"], Some(since));
+        assert_eq!(
+            suggestions,
+            vec![
+                SaveSuggestion {
+                    path: profile.join("AppData/Local/Kiwi"),
+                    name_match: true,
+                    changed_files: 1,
+                },
+                SaveSuggestion {
+                    path: profile.join("Saved Games/Codename"),
+                    name_match: false,
+                    changed_files: 2,
+                },
+            ]
+        );
+    }
+}
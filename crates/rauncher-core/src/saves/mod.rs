@@ -0,0 +1,370 @@
+mod detect;
+mod paths;
+
+pub use detect::{detect_save_dirs, SaveSuggestion};
+pub use paths::{is_windows_build, manifest_template, prefix_profile, resolve_template, SaveRoots};
+
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::Config;
+use crate::games::InstalledGame;
+use crate::{Error, Result};
+
+const ARCHIVE_EXTENSION: &str = ".tar.gz";
+
+/// Name the game's save directory (`save_path`, the store's or a detected one) is archived as
+const SAVE_DIR_NAME: &str = "save_dir";
+
+// Profile directories inside a Wine prefix that games commonly save into
+const PREFIX_SAVE_DIRS: [&str; 3] = ["AppData", "Saved Games", "Documents"];
+
+/// A directory holding save data, and the name it is stored under in backup archives
+#[derive(Debug, Clone)]
+pub struct SaveLocation {
+    pub archive_name: String,
+    pub path: PathBuf,
+}
+
+/// A save backup archive on disk
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+    pub size: u64,
+}
+
+fn prefix_users_dir(app_name: &str) -> Result<PathBuf> {
+    Ok(Config::prefixes_dir()?
+        .join(app_name)
+        .join("drive_c")
+        .join("users"))
+}
+
+/// Collect the existing save directories of a game: `save_dir`, where the game is known to
+/// keep its saves, and the usual places. Prefix locations are only included when asked for,
+/// since they are shared with the game's other Wine data.
+pub fn save_locations(
+    game: &InstalledGame,
+    save_dir: Option<&Path>,
+    include_prefix: bool,
+) -> Result<Vec<SaveLocation>> {
+    let mut locations = Vec::new();
+
+    let saves_dir = game.install_path.join("saves");
+    if let Some(save_dir) = save_dir.filter(|dir| dir.is_dir() && *dir != saves_dir) {
+        locations.push(SaveLocation {
+            archive_name: SAVE_DIR_NAME.to_string(),
+            path: save_dir.to_path_buf(),
+        });
+    }
+
+    if saves_dir.is_dir() {
+        locations.push(SaveLocation {
+            archive_name: "saves".to_string(),
+            path: saves_dir,
+        });
+    }
+
+    if include_prefix {
+        let users_dir = prefix_users_dir(&game.app_name)?;
+
+        if users_dir.is_dir() {
+            for entry in fs::read_dir(&users_dir)? {
+                let user_dir = entry?.path();
+                let user = user_dir.file_name().unwrap_or_default().to_string_lossy();
+
+                if user == "Public" || !user_dir.is_dir() {
+                    continue;
+                }
+
+                for dir in PREFIX_SAVE_DIRS {
+                    let path = user_dir.join(dir);
+                    if path.is_dir() {
+                        locations.push(SaveLocation {
+                            archive_name: format!("prefix/{}/{}", user, dir),
+                            path,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(locations)
+}
+
+/// Write the given save locations into a gzipped tarball at `archive_path`
+pub fn create_archive(locations: &[SaveLocation], archive_path: &Path) -> Result<()> {
+    if let Some(parent) = archive_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = fs::File::create(archive_path)?;
+    crate::logs::transcript::touched("wrote", archive_path);
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    for location in locations {
+        builder.append_dir_all(&location.archive_name, &location.path)?;
+    }
+
+    builder.into_inner()?.finish()?;
+
+    Ok(())
+}
+
+/// Map an archive root name back to the directory it was backed up from
+fn location_for(
+    game: &InstalledGame,
+    save_dir: Option<&Path>,
+    archive_name: &str,
+) -> Result<PathBuf> {
+    let parts: Vec<&str> = archive_name.split('/').collect();
+
+    match parts.as_slice() {
+        [SAVE_DIR_NAME] => save_dir.map(Path::to_path_buf).ok_or_else(|| {
+            Error::Other(format!(
+                "The backup holds saves of {}'s save directory, which isn't known any more \
+                 (set it with 'rauncher config set games.{}.save_path')",
+                game.app_name, game.app_name
+            ))
+        }),
+        ["saves"] => Ok(game.install_path.join("saves")),
+        ["prefix", user, dir] if PREFIX_SAVE_DIRS.contains(dir) => {
+            Ok(prefix_users_dir(&game.app_name)?.join(user).join(dir))
+        }
+        _ => Err(Error::Other(format!(
+            "Unknown save location in backup: {}",
+            archive_name
+        ))),
+    }
+}
+
+/// Split an archive entry path into its location root and the path below it
+fn split_entry_path(path: &Path) -> Option<(String, PathBuf)> {
+    let parts: Vec<String> = path
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    let root_len = if parts.first().map(String::as_str) == Some("prefix") {
+        3
+    } else {
+        1
+    };
+
+    if parts.len() <= root_len {
+        return None;
+    }
+
+    let root = parts[..root_len].join("/");
+    let rest = parts[root_len..].iter().collect();
+
+    Some((root, rest))
+}
+
+/// Back up a game's saves into a timestamped archive under the backups directory,
+/// then prune old archives according to `backup_retention`.
+/// Returns None when the game has no save data to back up.
+pub fn backup_saves(
+    config: &Config,
+    game: &InstalledGame,
+    save_dir: Option<&Path>,
+    include_prefix: bool,
+) -> Result<Option<PathBuf>> {
+    let locations = save_locations(game, save_dir, include_prefix)?;
+
+    if locations.is_empty() {
+        return Ok(None);
+    }
+
+    let backup_dir = Config::backups_dir()?.join(&game.app_name);
+    let archive_path = backup_dir.join(format!(
+        "{}{}",
+        Utc::now().format("%Y%m%d-%H%M%S-%3f"),
+        ARCHIVE_EXTENSION
+    ));
+
+    create_archive(&locations, &archive_path)?;
+
+    log::info!(
+        "Backed up saves for {} to {:?}",
+        game.app_name,
+        archive_path
+    );
+
+    if config.backup_retention > 0 {
+        for removed in prune_snapshots_in(&backup_dir, config.backup_retention)? {
+            log::info!("Removed old backup {:?}", removed);
+        }
+    }
+
+    Ok(Some(archive_path))
+}
+
+/// List a game's save backups, newest first
+pub fn list_snapshots(app_name: &str) -> Result<Vec<Snapshot>> {
+    snapshots_in(&Config::backups_dir()?.join(app_name))
+}
+
+fn snapshots_in(dir: &Path) -> Result<Vec<Snapshot>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut snapshots = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if !path.to_string_lossy().ends_with(ARCHIVE_EXTENSION) {
+            continue;
+        }
+
+        let metadata = entry.metadata()?;
+        snapshots.push(Snapshot {
+            path,
+            created_at: metadata.modified()?.into(),
+            size: metadata.len(),
+        });
+    }
+
+    // Archive names are timestamps, so they sort chronologically
+    snapshots.sort_by(|a, b| b.path.cmp(&a.path));
+
+    Ok(snapshots)
+}
+
+fn prune_snapshots_in(dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+
+    for snapshot in snapshots_in(dir)?.into_iter().skip(keep) {
+        fs::remove_file(&snapshot.path)?;
+        crate::logs::transcript::touched("removed", &snapshot.path);
+        removed.push(snapshot.path);
+    }
+
+    Ok(removed)
+}
+
+/// Restore a save backup over the game's current save directories, `save_dir` being where
+/// the game keeps its saves now
+pub fn restore_snapshot(
+    game: &InstalledGame,
+    save_dir: Option<&Path>,
+    snapshot: &Snapshot,
+) -> Result<usize> {
+    let file = fs::File::open(&snapshot.path)?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut restored = 0;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.into_owned();
+
+        let Some((root, rest)) = split_entry_path(&entry_path) else {
+            continue;
+        };
+
+        // Directory entries are recreated as needed by the files below them
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+
+        let dest = location_for(game, save_dir, &root)?.join(rest);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        entry.unpack(&dest)?;
+        crate::logs::transcript::touched("wrote", &dest);
+        restored += 1;
+    }
+
+    log::info!(
+        "Restored {} file(s) for {} from {:?}",
+        restored,
+        game.app_name,
+        snapshot.path
+    );
+
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_archive_stores_locations_by_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let saves = temp_dir.path().join("saves");
+        fs::create_dir_all(saves.join("slot1")).unwrap();
+        fs::write(saves.join("slot1").join("save.dat"), b"progress").unwrap();
+
+        let archive_path = temp_dir.path().join("backup.tar.gz");
+        let locations = vec![SaveLocation {
+            archive_name: "saves".to_string(),
+            path: saves,
+        }];
+        create_archive(&locations, &archive_path).unwrap();
+
+        let mut archive = tar::Archive::new(GzDecoder::new(fs::File::open(&archive_path).unwrap()));
+        let names: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect();
+
+        assert!(names.iter().any(|n| n == "saves/slot1/save.dat"));
+    }
+
+    #[test]
+    fn test_split_entry_path() {
+        let (root, rest) = split_entry_path(Path::new("saves/slot1/save.dat")).unwrap();
+        assert_eq!(root, "saves");
+        assert_eq!(rest, PathBuf::from("slot1/save.dat"));
+
+        let (root, rest) =
+            split_entry_path(Path::new("prefix/steamuser/AppData/Game/save.sav")).unwrap();
+        assert_eq!(root, "prefix/steamuser/AppData");
+        assert_eq!(rest, PathBuf::from("Game/save.sav"));
+
+        assert!(split_entry_path(Path::new("saves")).is_none());
+        assert!(split_entry_path(Path::new("saves/../../etc/passwd")).is_none());
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in [
+            "20240101-000000-000",
+            "20240201-000000-000",
+            "20240301-000000-000",
+        ] {
+            fs::write(
+                temp_dir
+                    .path()
+                    .join(format!("{}{}", name, ARCHIVE_EXTENSION)),
+                b"",
+            )
+            .unwrap();
+        }
+
+        let removed = prune_snapshots_in(temp_dir.path(), 2).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(removed[0].ends_with("20240101-000000-000.tar.gz"));
+
+        let remaining = snapshots_in(temp_dir.path()).unwrap();
+        assert!(remaining[0].path.ends_with("20240301-000000-000.tar.gz"));
+    }
+}
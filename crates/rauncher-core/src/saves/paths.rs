@@ -0,0 +1,256 @@
+//! Where games keep their saves. Epic's manifests give the save folder as a template in
+//! Windows terms, e.g. `{AppData}/Kiwi/Saved`; it's resolved against the user profile on
+//! Windows, the home directory on macOS, and the profile inside the game's Wine prefix
+//! elsewhere.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Result};
+
+/// Custom field of Epic's manifests holding the save folder template
+const TEMPLATE_FIELD: &str = "CloudSaveFolder";
+
+/// The same for the macOS build
+const MAC_TEMPLATE_FIELD: &str = "CloudSaveFolder_MAC";
+
+/// User Proton runs games as, whatever the actual user's name
+const PROTON_USER: &str = "steamuser";
+
+/// The save folder template among a manifest's custom fields, for the build whose
+/// executable is `executable`: Windows builds and Mac ones have their own
+pub fn manifest_template(
+    custom_fields: &BTreeMap<String, String>,
+    executable: &str,
+) -> Option<String> {
+    let field = if is_windows_build(executable) {
+        TEMPLATE_FIELD
+    } else {
+        MAC_TEMPLATE_FIELD
+    };
+    custom_fields
+        .get(field)
+        .filter(|template| !template.trim().is_empty())
+        .cloned()
+}
+
+/// Whether a game with this executable is a Windows build, whose templates use the Windows
+/// variables
+pub fn is_windows_build(executable: &str) -> bool {
+    Path::new(executable)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+/// What the variables of a template stand for
+#[derive(Debug, Clone)]
+pub struct SaveRoots {
+    /// The Windows user profile (`C:\Users\<name>`), inside the prefix when running through
+    /// Wine; the home directory on macOS
+    pub profile: PathBuf,
+    pub install_dir: PathBuf,
+    /// Account id of the Epic user, for games keeping saves per account
+    pub epic_id: Option<String>,
+    /// Resolve the macOS variables rather than the Windows ones
+    pub mac: bool,
+}
+
+/// Fill in a template's variables, which are case-insensitive. Both `/` and `\` separate
+/// directories. Fails on unknown variables and on templates that don't resolve to an
+/// absolute path.
+pub fn resolve_template(template: &str, roots: &SaveRoots) -> Result<PathBuf> {
+    let bad = |reason: &str| {
+        Error::Other(format!(
+            "Can't resolve the save path '{}': {}",
+            template, reason
+        ))
+    };
+
+    let mut path = PathBuf::new();
+    for part in template.split(['/', '\\']).filter(|part| !part.is_empty()) {
+        let mut resolved = String::new();
+        let mut rest = part;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .map(|end| start + end)
+                .ok_or_else(|| bad("unclosed '{'"))?;
+            resolved.push_str(&rest[..start]);
+            let name = &rest[start + 1..end];
+            match variable(name, roots) {
+                Some(Variable::Dir(dir)) if resolved.is_empty() && path.as_os_str().is_empty() => {
+                    path = dir;
+                }
+                Some(Variable::Dir(_)) => {
+                    return Err(bad(&format!("{{{}}} must start the path", name)));
+                }
+                Some(Variable::Text(text)) => resolved.push_str(&text),
+                None if name.eq_ignore_ascii_case("EpicID") => {
+                    return Err(bad("it needs an Epic account, and none is logged in"));
+                }
+                None => return Err(bad(&format!("unknown variable {{{}}}", name))),
+            }
+            rest = &rest[end + 1..];
+        }
+        resolved.push_str(rest);
+
+        if resolved == ".." {
+            return Err(bad("it leaves the directory it starts in"));
+        }
+        if !resolved.is_empty() && resolved != "." {
+            path.push(resolved);
+        }
+    }
+
+    if !path.is_absolute() {
+        return Err(bad("it doesn't start with a known directory"));
+    }
+    Ok(path)
+}
+
+enum Variable {
+    /// A directory, which may only start the path
+    Dir(PathBuf),
+    Text(String),
+}
+
+fn variable(name: &str, roots: &SaveRoots) -> Option<Variable> {
+    let profile = &roots.profile;
+    let dir = match name.to_ascii_lowercase().as_str() {
+        "installdir" => roots.install_dir.clone(),
+        "epicid" => return roots.epic_id.clone().map(Variable::Text),
+        "appdata" if roots.mac => profile.join("Library").join("Application Support"),
+        "userdir" if roots.mac => profile.join("Documents"),
+        "userlibrary" if roots.mac => profile.join("Library"),
+        "home" if roots.mac => profile.clone(),
+        // Epic's {AppData} is the local one, not Roaming
+        "appdata" | "localappdata" => profile.join("AppData").join("Local"),
+        "userdir" => profile.join("Documents"),
+        "userprofile" => profile.clone(),
+        "usersavedgames" => profile.join("Saved Games"),
+        _ => return None,
+    };
+    Some(Variable::Dir(dir))
+}
+
+/// The Windows user profile inside a Wine prefix: the one Wine created there, or the one it
+/// will create on the game's first start. Proton keeps its Wine prefix in `pfx`.
+pub fn prefix_profile(prefix: &Path, proton: bool) -> PathBuf {
+    let wine_prefix = if proton {
+        prefix.join("pfx")
+    } else {
+        prefix.to_path_buf()
+    };
+    let users = wine_prefix.join("drive_c").join("users");
+
+    let existing = fs::read_dir(&users).ok().and_then(|entries| {
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .find(|path| path.file_name().is_some_and(|name| name != "Public"))
+    });
+    existing.unwrap_or_else(|| {
+        let user = if proton {
+            PROTON_USER.to_string()
+        } else {
+            std::env::var("USER").unwrap_or_else(|_| PROTON_USER.to_string())
+        };
+        users.join(user)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots(mac: bool) -> SaveRoots {
+        SaveRoots {
+            profile: PathBuf::from("/prefix/drive_c/users/kiwi"),
+            install_dir: PathBuf::from("/games/Kiwi"),
+            epic_id: Some("abc123".to_string()),
+            mac,
+        }
+    }
+
+    #[test]
+    fn test_resolve_template() {
+        let windows = roots(false);
+        assert_eq!(
+            resolve_template("{AppData}/Kiwi/Saved/SaveGames", &windows).unwrap(),
+            PathBuf::from("/prefix/drive_c/users/kiwi/AppData/Local/Kiwi/Saved/SaveGames")
+        );
+        assert_eq!(
+            resolve_template(r"{USERDIR}\My Games\Kiwi\{EpicID}", &windows).unwrap(),
+            PathBuf::from("/prefix/drive_c/users/kiwi/Documents/My Games/Kiwi/abc123")
+        );
+        assert_eq!(
+            resolve_template("{UserSavedGames}/Kiwi/Profile_{epicid}", &windows).unwrap(),
+            PathBuf::from("/prefix/drive_c/users/kiwi/Saved Games/Kiwi/Profile_abc123")
+        );
+        assert_eq!(
+            resolve_template("{InstallDir}/Saves/", &windows).unwrap(),
+            PathBuf::from("/games/Kiwi/Saves")
+        );
+
+        let mac = roots(true);
+        assert_eq!(
+            resolve_template("{AppData}/Kiwi", &mac).unwrap(),
+            PathBuf::from("/prefix/drive_c/users/kiwi/Library/Application Support/Kiwi")
+        );
+        assert_eq!(
+            resolve_template("{Home}/.kiwi", &mac).unwrap(),
+            PathBuf::from("/prefix/drive_c/users/kiwi/.kiwi")
+        );
+    }
+
+    #[test]
+    fn test_manifest_template() {
+        let fields = BTreeMap::from([
+            (TEMPLATE_FIELD.to_string(), "{AppData}/Kiwi".to_string()),
+            (MAC_TEMPLATE_FIELD.to_string(), " ".to_string()),
+        ]);
+        assert_eq!(
+            manifest_template(&fields, "Binaries/Kiwi.EXE").as_deref(),
+            Some("{AppData}/Kiwi")
+        );
+        assert_eq!(
+            manifest_template(&fields, "Kiwi.app/Contents/MacOS/Kiwi"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_template_rejects_bad_templates() {
+        let windows = roots(false);
+        assert!(resolve_template("{Nope}/Kiwi", &windows).is_err());
+        assert!(resolve_template("Kiwi/Saved", &windows).is_err());
+        assert!(resolve_template("{AppData}/../../Kiwi", &windows).is_err());
+        assert!(resolve_template("Saves/{AppData}", &windows).is_err());
+        assert!(resolve_template("{AppData/Kiwi", &windows).is_err());
+        // Only macOS has a {Home}
+        assert!(resolve_template("{Home}/Kiwi", &windows).is_err());
+
+        let logged_out = SaveRoots {
+            epic_id: None,
+            ..windows
+        };
+        let error = resolve_template("{AppData}/{EpicID}", &logged_out).unwrap_err();
+        assert!(error.to_string().contains("Epic account"));
+    }
+
+    #[test]
+    fn test_prefix_profile() {
+        let prefix = tempfile::tempdir().unwrap();
+        assert_eq!(
+            prefix_profile(prefix.path(), true),
+            prefix.path().join("pfx/drive_c/users/steamuser")
+        );
+
+        let users = prefix.path().join("drive_c/users");
+        fs::create_dir_all(users.join("Public")).unwrap();
+        fs::create_dir_all(users.join("kiwi")).unwrap();
+        assert_eq!(prefix_profile(prefix.path(), false), users.join("kiwi"));
+    }
+}
@@ -0,0 +1,264 @@
+//! Play sessions and the statistics built from them, the install records and the history.
+//! Sessions are appended one JSON line each to `<data dir>/sessions.jsonl` when rauncher sees
+//! a game exit: games waited on with `launch --wait` or from the GUI, and games stopped.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::games::InstalledGame;
+use crate::history::HistoryEntry;
+use crate::providers;
+use crate::Result;
+
+const SESSIONS_FILE: &str = "sessions.jsonl";
+
+/// Games listed as most played
+const MOST_PLAYED: usize = 5;
+
+/// A game played from start to exit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaySession {
+    pub app_name: String,
+    pub app_title: String,
+    pub provider: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl PlaySession {
+    /// A session of `game` that started at `started_at` and ended now
+    pub fn ended_now(game: &InstalledGame, started_at: DateTime<Utc>) -> Self {
+        Self {
+            app_name: game.app_name.clone(),
+            app_title: game.app_title.clone(),
+            provider: game.provider.clone(),
+            started_at,
+            ended_at: Utc::now(),
+        }
+    }
+
+    pub fn id(&self) -> String {
+        providers::qualified_id(&self.provider, &self.app_name)
+    }
+
+    /// Seconds played, never negative even if the clock went back meanwhile
+    pub fn seconds(&self) -> u64 {
+        (self.ended_at - self.started_at).num_seconds().max(0) as u64
+    }
+}
+
+fn sessions_path() -> Result<PathBuf> {
+    Ok(Config::data_dir()?.join(SESSIONS_FILE))
+}
+
+/// Append a session to the play log
+pub fn record_session(session: &PlaySession) -> Result<()> {
+    record_session_in(&sessions_path()?, session)
+}
+
+fn record_session_in(path: &Path, session: &PlaySession) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(session)?)?;
+    Ok(())
+}
+
+/// Every recorded session, oldest first. Lines that can't be read are skipped.
+pub fn sessions() -> Result<Vec<PlaySession>> {
+    sessions_from(&sessions_path()?)
+}
+
+fn sessions_from(path: &Path) -> Result<Vec<PlaySession>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// A game's total play time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GamePlaytime {
+    /// The game's address, as `InstalledGame::id` gives it
+    pub id: String,
+    pub title: String,
+    pub seconds: u64,
+    pub sessions: usize,
+}
+
+/// Bytes downloaded in a month, and in every month up to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MonthlyDownloads {
+    /// `YYYY-MM`
+    pub month: String,
+    pub bytes: u64,
+    pub cumulative_bytes: u64,
+}
+
+/// Summary of the library and how it's been used
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LibraryStats {
+    /// Games owned across the logged-in stores; `None` when the stores couldn't be asked
+    pub owned_games: Option<usize>,
+    pub installed_games: usize,
+    /// Bytes the installed games take on disk
+    pub installed_bytes: u64,
+    pub total_playtime_seconds: u64,
+    /// Games played the longest, longest first
+    pub most_played: Vec<GamePlaytime>,
+    /// Bytes downloaded by installs, updates and repairs, per month, oldest first
+    pub downloads: Vec<MonthlyDownloads>,
+}
+
+impl LibraryStats {
+    pub fn compute(
+        installed: &[InstalledGame],
+        owned_games: Option<usize>,
+        history: &[HistoryEntry],
+        sessions: &[PlaySession],
+    ) -> Self {
+        let mut playtime: HashMap<String, GamePlaytime> = HashMap::new();
+        for session in sessions {
+            let game = playtime
+                .entry(session.id())
+                .or_insert_with(|| GamePlaytime {
+                    id: session.id(),
+                    title: session.app_title.clone(),
+                    seconds: 0,
+                    sessions: 0,
+                });
+            game.seconds += session.seconds();
+            game.sessions += 1;
+            // Titles can change; the latest session has the current one
+            game.title = session.app_title.clone();
+        }
+        let total_playtime_seconds = playtime.values().map(|game| game.seconds).sum();
+        let mut most_played: Vec<GamePlaytime> = playtime.into_values().collect();
+        most_played.sort_by(|a, b| b.seconds.cmp(&a.seconds).then(a.id.cmp(&b.id)));
+        most_played.truncate(MOST_PLAYED);
+
+        let mut by_month: BTreeMap<String, u64> = BTreeMap::new();
+        for entry in history.iter().filter(|entry| entry.bytes > 0) {
+            *by_month
+                .entry(entry.started_at.format("%Y-%m").to_string())
+                .or_insert(0) += entry.bytes;
+        }
+        let mut cumulative_bytes = 0;
+        let downloads = by_month
+            .into_iter()
+            .map(|(month, bytes)| {
+                cumulative_bytes += bytes;
+                MonthlyDownloads {
+                    month,
+                    bytes,
+                    cumulative_bytes,
+                }
+            })
+            .collect();
+
+        Self {
+            owned_games,
+            installed_games: installed.len(),
+            installed_bytes: installed.iter().map(InstalledGame::disk_usage).sum(),
+            total_playtime_seconds,
+            most_played,
+            downloads,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::HistoryAction;
+    use chrono::{Duration, TimeZone};
+
+    fn session(app_name: &str, start: DateTime<Utc>, minutes: i64) -> PlaySession {
+        PlaySession {
+            app_name: app_name.to_string(),
+            app_title: app_name.to_string(),
+            provider: providers::EPIC.to_string(),
+            started_at: start,
+            ended_at: start + Duration::minutes(minutes),
+        }
+    }
+
+    fn download(month: u32, bytes: u64) -> HistoryEntry {
+        let started_at = Utc.with_ymd_and_hms(2026, month, 10, 12, 0, 0).unwrap();
+        HistoryEntry {
+            app_name: "Kiwi".to_string(),
+            provider: providers::EPIC.to_string(),
+            action: HistoryAction::Install,
+            from_version: None,
+            version: Some("1.0".to_string()),
+            bytes,
+            started_at,
+            finished_at: started_at,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let start = Utc.with_ymd_and_hms(2026, 10, 1, 20, 0, 0).unwrap();
+        let sessions = [
+            session("Kiwi", start, 30),
+            session("Pyre", start, 90),
+            session("Kiwi", start, 45),
+        ];
+        let history = [
+            download(9, 100),
+            download(10, 0),
+            download(10, 50),
+            download(9, 1),
+        ];
+
+        let stats = LibraryStats::compute(&[], Some(12), &history, &sessions);
+        assert_eq!(stats.owned_games, Some(12));
+        assert_eq!(stats.installed_games, 0);
+        assert_eq!(stats.total_playtime_seconds, 165 * 60);
+        let most_played: Vec<_> = stats
+            .most_played
+            .iter()
+            .map(|game| (game.title.as_str(), game.seconds / 60, game.sessions))
+            .collect();
+        assert_eq!(most_played, vec![("Pyre", 90, 1), ("Kiwi", 75, 2)]);
+        assert_eq!(
+            stats.downloads,
+            vec![
+                MonthlyDownloads {
+                    month: "2026-09".to_string(),
+                    bytes: 101,
+                    cumulative_bytes: 101,
+                },
+                MonthlyDownloads {
+                    month: "2026-10".to_string(),
+                    bytes: 50,
+                    cumulative_bytes: 151,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_record_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SESSIONS_FILE);
+        assert!(sessions_from(&path).unwrap().is_empty());
+
+        let kiwi = session("Kiwi", Utc::now(), 10);
+        record_session_in(&path, &kiwi).unwrap();
+        assert_eq!(sessions_from(&path).unwrap(), vec![kiwi]);
+    }
+}
@@ -0,0 +1,483 @@
+//! Disk usage of the installed games, their Wine prefixes and shader caches, grouped by the
+//! library root (drive or directory) holding them, and cleanup of what can be rebuilt.
+
+use flate2::read::GzDecoder;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::games::{dir_size, InstalledGame};
+use crate::{Error, Result};
+
+pub mod volume;
+
+/// File left in an install directory while its download is in progress
+pub const PARTIAL_MARKER: &str = ".rauncher-partial";
+
+/// Disk usage of one installed game
+#[derive(Debug, Clone)]
+pub struct GameUsage {
+    pub app_name: String,
+    pub app_title: String,
+    pub install: u64,
+    /// Bytes downloaded for the install, compressed; 0 when not known
+    pub download: u64,
+    pub prefix: u64,
+    pub shader_cache: u64,
+}
+
+impl GameUsage {
+    pub fn total(&self) -> u64 {
+        self.install + self.prefix + self.shader_cache
+    }
+}
+
+/// A directory games are installed into, with the space left on its filesystem
+#[derive(Debug, Clone)]
+pub struct LibraryRoot {
+    pub path: PathBuf,
+    pub games: Vec<GameUsage>,
+    /// Free and total bytes on the filesystem, when they can be read
+    pub free: Option<u64>,
+    pub capacity: Option<u64>,
+}
+
+impl LibraryRoot {
+    fn new(path: PathBuf) -> Self {
+        let free = fs4::available_space(existing_ancestor(&path)).ok();
+        let capacity = fs4::total_space(existing_ancestor(&path)).ok();
+        Self {
+            path,
+            games: Vec::new(),
+            free,
+            capacity,
+        }
+    }
+
+    /// Bytes used by the games in this root, prefixes and shader caches included
+    pub fn used(&self) -> u64 {
+        self.games.iter().map(GameUsage::total).sum()
+    }
+}
+
+/// An install directory left behind by a download that never finished
+#[derive(Debug, Clone)]
+pub struct PartialDownload {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageReport {
+    /// The configured install directory first, then any other root games live in
+    pub roots: Vec<LibraryRoot>,
+    /// Shader caches of every game, uninstalled ones included
+    pub shader_cache: u64,
+    pub partial_downloads: Vec<PartialDownload>,
+}
+
+impl StorageReport {
+    /// Measure every installed game. Walks the install directories, so it can take a while.
+    pub fn collect(config: &Config) -> Result<Self> {
+        let prefixes = Config::prefixes_dir()?;
+        let shader_caches = Config::shader_cache_dir()?;
+
+        let installed = InstalledGame::list_installed(config)?;
+        let mut roots: Vec<LibraryRoot> = library_roots(config, &installed)
+            .into_iter()
+            .map(LibraryRoot::new)
+            .collect();
+        for game in installed {
+            let root_path = root_of(&game);
+            let Some(root) = roots.iter_mut().find(|root| root.path == root_path) else {
+                continue;
+            };
+
+            root.games.push(GameUsage {
+                install: dir_size(&game.install_path),
+                download: game.download_size,
+                prefix: dir_size(&prefixes.join(&game.app_name)),
+                shader_cache: dir_size(&shader_caches.join(&game.app_name)),
+                app_name: game.app_name,
+                app_title: game.app_title,
+            });
+        }
+
+        for root in &mut roots {
+            root.games
+                .sort_by_key(|game| std::cmp::Reverse(game.total()));
+        }
+
+        Ok(Self {
+            roots,
+            shader_cache: dir_size(&shader_caches),
+            partial_downloads: partial_dirs(config),
+        })
+    }
+
+    /// Bytes that cleaning up shader caches and partial downloads would free
+    pub fn reclaimable(&self) -> u64 {
+        self.shader_cache + self.partial_downloads.iter().map(|p| p.size).sum::<u64>()
+    }
+}
+
+/// Directory holding a game's install directory
+fn root_of(game: &InstalledGame) -> PathBuf {
+    game.install_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| game.install_path.clone())
+}
+
+/// The configured install directory, then every other directory installed games live in
+pub(crate) fn library_roots(config: &Config, installed: &[InstalledGame]) -> Vec<PathBuf> {
+    let mut roots = vec![config.install_dir.clone()];
+    for game in installed {
+        let root = root_of(game);
+        if !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+    roots
+}
+
+/// Folders in the library roots that no install record points at, each with whether it is
+/// in the configured install directory. Partial downloads and hidden folders are left out.
+pub(crate) fn unregistered_dirs(
+    config: &Config,
+    installed: &[InstalledGame],
+) -> Vec<(PathBuf, bool)> {
+    let roots = library_roots(config, installed);
+    let mut found = Vec::new();
+
+    for root in &roots {
+        let Ok(entries) = fs::read_dir(root) else {
+            continue;
+        };
+
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            let registered = installed.iter().any(|game| game.install_path == path);
+            if hidden
+                || registered
+                || roots.contains(&path)
+                || !path.is_dir()
+                || path.join(PARTIAL_MARKER).exists()
+                || fs::read_dir(&path).map_or(true, |mut e| e.next().is_none())
+            {
+                continue;
+            }
+            found.push((path, *root == config.install_dir));
+        }
+    }
+
+    found.sort();
+    found
+}
+
+/// Install directories under `install_dir` still marked as downloading
+pub fn partial_downloads(install_dir: &Path) -> Vec<PartialDownload> {
+    let Ok(entries) = fs::read_dir(install_dir) else {
+        return Vec::new();
+    };
+
+    let mut partial: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(PARTIAL_MARKER).is_file())
+        .map(|path| PartialDownload {
+            size: dir_size(&path),
+            path,
+        })
+        .collect();
+    partial.sort_by(|a, b| a.path.cmp(&b.path));
+    partial
+}
+
+/// Partial downloads in the install directory and the staging directory
+fn partial_dirs(config: &Config) -> Vec<PartialDownload> {
+    let mut partial = partial_downloads(&config.install_dir);
+    if let Some(staging_dir) = &config.staging_dir {
+        partial.extend(partial_downloads(staging_dir));
+    }
+    partial
+}
+
+/// Move the files of a staged install into `dest`, replacing those already there, then
+/// delete `src`. Files are renamed where both are on the same filesystem and copied
+/// otherwise.
+pub(crate) fn move_tree(src: &Path, dest: &Path) -> Result<()> {
+    move_dir_contents(src, dest, true)?;
+    fs::remove_dir_all(src)?;
+    Ok(())
+}
+
+fn move_dir_contents(src: &Path, dest: &Path, top: bool) -> Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        if top && entry.file_name() == PARTIAL_MARKER {
+            continue;
+        }
+        let from = entry.path();
+        let to = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            move_dir_contents(&from, &to, false)?;
+        } else if fs::rename(&from, &to).is_err() {
+            fs::copy(&from, &to)?;
+            fs::remove_file(&from)?;
+        }
+        crate::logs::transcript::touched("wrote", &to);
+    }
+    Ok(())
+}
+
+/// How games, prefixes and caches are gotten rid of
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Removal {
+    /// Moved to the OS trash (the Recycle Bin on Windows), where they can be restored from
+    #[default]
+    Trash,
+    /// Deleted for good
+    Permanent,
+}
+
+/// Get rid of `dir` and everything in it. When it can't be moved to the trash, e.g. on a
+/// drive without one, nothing is deleted and the error says so.
+pub(crate) fn remove_dir(dir: &Path, removal: Removal) -> Result<()> {
+    match removal {
+        Removal::Trash => {
+            trash::delete(dir).map_err(|e| {
+                Error::Other(format!(
+                    "Failed to move {:?} to the trash, so it was kept: {}. Retry with \
+                     --permanent to delete it for good",
+                    dir, e
+                ))
+            })?;
+            crate::logs::transcript::touched("trashed", dir);
+        }
+        Removal::Permanent => {
+            fs::remove_dir_all(dir)?;
+            crate::logs::transcript::touched("removed", dir);
+        }
+    }
+    Ok(())
+}
+
+/// Get rid of the file at `path`, like `remove_dir`
+pub(crate) fn remove_file(path: &Path, removal: Removal) -> Result<()> {
+    match removal {
+        Removal::Trash => {
+            trash::delete(path).map_err(|e| {
+                Error::Other(format!(
+                    "Failed to move {:?} to the trash, so it was kept: {}. Retry with \
+                     --permanent to delete it for good",
+                    path, e
+                ))
+            })?;
+            crate::logs::transcript::touched("trashed", path);
+        }
+        Removal::Permanent => {
+            fs::remove_file(path)?;
+            crate::logs::transcript::touched("removed", path);
+        }
+    }
+    Ok(())
+}
+
+/// Unpack a zip or gzipped tarball into `dest`. Returns false for files that aren't archives.
+pub(crate) fn extract_archive(path: &Path, dest: &Path) -> Result<bool> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if name.ends_with(".zip") {
+        let mut archive = zip::ZipArchive::new(fs::File::open(path)?)
+            .map_err(|e| Error::Other(format!("Failed to open {:?}: {}", path, e)))?;
+        archive
+            .extract(dest)
+            .map_err(|e| Error::Other(format!("Failed to extract {:?}: {}", path, e)))?;
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        tar::Archive::new(GzDecoder::new(fs::File::open(path)?)).unpack(dest)?;
+    } else {
+        return Ok(false);
+    }
+
+    log::info!("Extracted {}", name);
+
+    Ok(true)
+}
+
+/// Get rid of the shader caches of every game; they are rebuilt as the games run. Returns the
+/// bytes removed.
+pub fn clean_shader_caches(removal: Removal) -> Result<u64> {
+    let dir = Config::shader_cache_dir()?;
+    let freed = dir_size(&dir);
+    if dir.exists() {
+        remove_dir(&dir, removal)?;
+    }
+    log::debug!("Removed the shader caches ({} bytes)", freed);
+    Ok(freed)
+}
+
+/// Get rid of partial downloads, skipping the games in `active` that are still downloading.
+/// Returns the bytes removed.
+pub fn clean_partial_downloads(
+    config: &Config,
+    active: &[String],
+    removal: Removal,
+) -> Result<u64> {
+    let mut freed = 0;
+    for partial in partial_dirs(config) {
+        let in_progress = partial
+            .path
+            .file_name()
+            .is_some_and(|name| active.iter().any(|app| name == app.as_str()));
+        if in_progress {
+            continue;
+        }
+
+        remove_dir(&partial.path, removal)?;
+        log::debug!("Removed partial download {:?}", partial.path);
+        freed += partial.size;
+    }
+    Ok(freed)
+}
+
+/// `path` or its closest ancestor that exists, for filesystem queries on directories not
+/// created yet
+pub(crate) fn existing_ancestor(path: &Path) -> &Path {
+    path.ancestors().find(|dir| dir.exists()).unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permanent_removal_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("shader_cache/Kiwi");
+        fs::create_dir_all(&cache).unwrap();
+        fs::write(cache.join("cache.bin"), b"shaders").unwrap();
+
+        remove_dir(&dir.path().join("shader_cache"), Removal::Permanent).unwrap();
+        assert!(!dir.path().join("shader_cache").exists());
+        assert_eq!(Removal::default(), Removal::Trash);
+    }
+
+    #[test]
+    fn test_failed_trash_keeps_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("gone");
+        let error = remove_dir(&missing, Removal::Trash).unwrap_err();
+        assert!(error.to_string().contains("to the trash, so it was kept"));
+        assert!(matches!(
+            remove_dir(&missing, Removal::Permanent),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_partial_downloads_are_marked_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let finished = dir.path().join("Celeste");
+        let partial = dir.path().join("Fortnite");
+        fs::create_dir_all(&finished).unwrap();
+        fs::create_dir_all(&partial).unwrap();
+        fs::write(finished.join("Celeste.exe"), [0u8; 10]).unwrap();
+        fs::write(partial.join("FortniteClient.pak"), [0u8; 20]).unwrap();
+        fs::write(partial.join(PARTIAL_MARKER), "").unwrap();
+
+        let found = partial_downloads(dir.path());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, partial);
+        assert_eq!(found[0].size, 20);
+    }
+
+    #[test]
+    fn test_unregistered_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            install_dir: dir.path().join("rauncher"),
+            ..Config::default()
+        };
+        for name in [
+            "rauncher/Celeste",
+            "rauncher/Fortnite",
+            "rauncher/Half",
+            "Hades",
+        ] {
+            fs::create_dir_all(dir.path().join(name)).unwrap();
+            fs::write(dir.path().join(name).join("game.bin"), "").unwrap();
+        }
+        fs::write(dir.path().join("rauncher/Half").join(PARTIAL_MARKER), "").unwrap();
+        fs::create_dir_all(dir.path().join("rauncher/Empty")).unwrap();
+
+        // A game installed outside the install directory makes its parent a root too
+        let installed: Vec<InstalledGame> = ["rauncher/Celeste", "Elsewhere"]
+            .iter()
+            .map(|path| InstalledGame {
+                app_name: path.to_string(),
+                app_title: path.to_string(),
+                app_version: "1.0".to_string(),
+                install_path: dir.path().join(path),
+                executable: "game.bin".to_string(),
+                pinned: false,
+                provider: "epic".to_string(),
+                install_size: 0,
+                download_size: 0,
+                installed_at: None,
+                last_played: None,
+                volume: None,
+                save_path_template: None,
+            })
+            .collect();
+
+        assert_eq!(
+            unregistered_dirs(&config, &installed),
+            vec![
+                (dir.path().join("Hades"), false),
+                (dir.path().join("rauncher/Fortnite"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_move_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let staged = dir.path().join("staging/Kiwi");
+        let install = dir.path().join("games/Kiwi");
+        fs::create_dir_all(staged.join("Content")).unwrap();
+        fs::create_dir_all(&install).unwrap();
+        fs::write(staged.join(PARTIAL_MARKER), "").unwrap();
+        fs::write(staged.join("Kiwi.exe"), "new").unwrap();
+        fs::write(staged.join("Content/data.pak"), "data").unwrap();
+        fs::write(install.join("Kiwi.exe"), "old").unwrap();
+        fs::write(install.join("settings.ini"), "mine").unwrap();
+
+        move_tree(&staged, &install).unwrap();
+        assert!(!staged.exists());
+        assert_eq!(fs::read_to_string(install.join("Kiwi.exe")).unwrap(), "new");
+        assert_eq!(
+            fs::read_to_string(install.join("Content/data.pak")).unwrap(),
+            "data"
+        );
+        assert_eq!(
+            fs::read_to_string(install.join("settings.ini")).unwrap(),
+            "mine"
+        );
+        assert!(!install.join(PARTIAL_MARKER).exists());
+    }
+
+    #[test]
+    fn test_existing_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("Games").join("Epic");
+        assert_eq!(existing_ancestor(&missing), dir.path());
+        assert_eq!(existing_ancestor(dir.path()), dir.path());
+    }
+}
@@ -0,0 +1,217 @@
+//! Libraries on removable and network drives. Installing to one leaves a marker file with a
+//! random id in the library, kept in the game's install record; while the drive is away, or
+//! another one is mounted in its place, the marker doesn't match and the game is reported as
+//! unavailable instead of broken. It's available again as soon as the drive is back.
+
+use rand::RngCore;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::existing_ancestor;
+use crate::Result;
+
+/// File identifying a removable or network library
+pub const VOLUME_MARKER: &str = ".rauncher-volume";
+
+/// Filesystems reached over the network
+const NETWORK_FILESYSTEMS: [&str; 11] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "davfs",
+];
+
+/// Directories desktop environments mount removable drives under
+const MOUNT_BASES: [&str; 4] = ["/media", "/run/media", "/mnt", "/Volumes"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeKind {
+    Local,
+    Removable,
+    Network,
+}
+
+impl VolumeKind {
+    /// Whether the drive may be missing when rauncher runs
+    pub fn is_detachable(self) -> bool {
+        self != Self::Local
+    }
+}
+
+/// What kind of drive `path` is on, or would be once created
+pub fn volume_kind(path: &Path) -> VolumeKind {
+    if cfg!(windows) {
+        // UNC paths, \\server\share
+        let path = path.to_string_lossy();
+        return if path.starts_with(r"\\") && !path.starts_with(r"\\?\") {
+            VolumeKind::Network
+        } else {
+            VolumeKind::Local
+        };
+    }
+    if cfg!(target_os = "macos") {
+        return if path.starts_with("/Volumes") {
+            VolumeKind::Removable
+        } else {
+            VolumeKind::Local
+        };
+    }
+
+    let Ok(mountinfo) = fs::read_to_string("/proc/self/mountinfo") else {
+        return VolumeKind::Local;
+    };
+    let path = fs::canonicalize(existing_ancestor(path)).unwrap_or_else(|_| path.to_path_buf());
+    match find_mount(&mountinfo, &path) {
+        Some(mount) if NETWORK_FILESYSTEMS.contains(&mount.fstype.as_str()) => VolumeKind::Network,
+        Some(mount) if is_removable_device(&mount.source) || under_mount_base(&mount.point) => {
+            VolumeKind::Removable
+        }
+        _ => VolumeKind::Local,
+    }
+}
+
+/// Whether `path` is where a removable drive would be mounted, but isn't: it doesn't exist,
+/// and all there is of it is the directory drives get mounted in
+pub fn drive_missing(path: &Path) -> bool {
+    if path.exists() {
+        return false;
+    }
+    let ancestor = existing_ancestor(path);
+    MOUNT_BASES.iter().any(|base| {
+        let base = Path::new(base);
+        // Per-user directories, as in /run/media/<user>
+        ancestor == base || ancestor.parent() == Some(base)
+    })
+}
+
+/// The id of the library at `root`, marking it first if it has none
+pub fn mark(root: &Path) -> Result<String> {
+    if let Some(id) = marker(root) {
+        return Ok(id);
+    }
+    let mut bytes = [0u8; 8];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let id: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    fs::create_dir_all(root)?;
+    fs::write(root.join(VOLUME_MARKER), &id)?;
+    Ok(id)
+}
+
+/// The id of the library at `root`, if it's marked and its drive is there
+pub fn marker(root: &Path) -> Option<String> {
+    let id = fs::read_to_string(root.join(VOLUME_MARKER)).ok()?;
+    Some(id.trim().to_string()).filter(|id| !id.is_empty())
+}
+
+#[derive(Debug, PartialEq)]
+struct Mount {
+    point: PathBuf,
+    fstype: String,
+    source: String,
+}
+
+/// The mount holding `path`, from the contents of /proc/self/mountinfo
+fn find_mount(mountinfo: &str, path: &Path) -> Option<Mount> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let (before, after) = line.split_once(" - ")?;
+            let point = unescape(before.split(' ').nth(4)?);
+            let mut after = after.split(' ');
+            let fstype = after.next()?.to_string();
+            let source = unescape(after.next()?);
+            Some(Mount {
+                point: PathBuf::from(point),
+                fstype,
+                source,
+            })
+        })
+        .filter(|mount| path.starts_with(&mount.point))
+        // The innermost one; later lines win ties, as they're mounted over earlier ones
+        .max_by_key(|mount| mount.point.components().count())
+}
+
+/// Undo the octal escapes mountinfo uses for spaces and other separators
+fn unescape(field: &str) -> String {
+    let mut out = String::new();
+    let mut rest = field;
+    while let Some(i) = rest.find('\\') {
+        out.push_str(&rest[..i]);
+        match rest
+            .get(i + 1..i + 4)
+            .and_then(|code| u8::from_str_radix(code, 8).ok())
+        {
+            Some(byte) => {
+                out.push(byte as char);
+                rest = &rest[i + 4..];
+            }
+            None => {
+                out.push('\\');
+                rest = &rest[i + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether the kernel flags the block device, or the disk its partition is on, as removable
+fn is_removable_device(source: &str) -> bool {
+    let Some(name) = source.strip_prefix("/dev/") else {
+        return false;
+    };
+    let device = Path::new("/sys/class/block").join(name);
+    [device.join("removable"), device.join("../removable")]
+        .iter()
+        .any(|flag| fs::read_to_string(flag).is_ok_and(|flag| flag.trim() == "1"))
+}
+
+fn under_mount_base(point: &Path) -> bool {
+    MOUNT_BASES
+        .iter()
+        .any(|base| point != Path::new(base) && point.starts_with(base))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTINFO: &str = "\
+22 1 259:2 / / rw,relatime - ext4 /dev/nvme0n1p2 rw
+40 22 8:17 / /run/media/me/Game\\040Drive rw,nosuid - exfat /dev/sdb1 rw
+41 22 0:50 / /srv/games rw,relatime - nfs4 nas:/export/games rw
+";
+
+    #[test]
+    fn test_find_mount() {
+        let mount = find_mount(MOUNTINFO, Path::new("/run/media/me/Game Drive/Games")).unwrap();
+        assert_eq!(mount.point, Path::new("/run/media/me/Game Drive"));
+        assert_eq!(mount.source, "/dev/sdb1");
+        assert!(under_mount_base(&mount.point));
+
+        let mount = find_mount(MOUNTINFO, Path::new("/srv/games/Kiwi")).unwrap();
+        assert_eq!(mount.fstype, "nfs4");
+        let mount = find_mount(MOUNTINFO, Path::new("/home/me/Games")).unwrap();
+        assert_eq!(mount.point, Path::new("/"));
+        assert!(!under_mount_base(&mount.point));
+    }
+
+    #[test]
+    fn test_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("Games");
+        assert_eq!(marker(&root), None);
+        let id = mark(&root).unwrap();
+        assert_eq!(id.len(), 16);
+        assert_eq!(mark(&root).unwrap(), id);
+        assert_eq!(marker(&root), Some(id));
+    }
+}
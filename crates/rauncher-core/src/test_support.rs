@@ -0,0 +1,73 @@
+//! Helpers shared by tests across modules
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Point the data directory at a temporary location so tests that persist
+/// tokens don't overwrite the developer's real credentials
+pub(crate) fn isolate_data_dir() {
+    static DATA_DIR: OnceLock<tempfile::TempDir> = OnceLock::new();
+    let dir = DATA_DIR.get_or_init(|| tempfile::TempDir::new().unwrap());
+    std::env::set_var("XDG_DATA_HOME", dir.path());
+}
+
+/// Answer one request per response, in order, on a free local port; returns its URL
+pub(crate) async fn serve_responses(responses: Vec<(u16, Vec<u8>)>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request).await;
+            let head = format!(
+                "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                status,
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).await.unwrap();
+            stream.write_all(&body).await.unwrap();
+        }
+    });
+    url
+}
+
+/// Serve `files` by path, e.g. `/Builds/kiwi.manifest`, on a free local port, answering 404
+/// for anything else; returns its URL
+pub(crate) async fn serve_files(files: HashMap<String, Vec<u8>>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let files = Arc::new(files);
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let files = Arc::clone(&files);
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(read) => request.extend_from_slice(&buf[..read]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let target = request.split_whitespace().nth(1).unwrap_or_default();
+                let path = target.split('?').next().unwrap_or_default();
+                let (status, body) = match files.get(path) {
+                    Some(body) => (200, body.as_slice()),
+                    None => (404, &b""[..]),
+                };
+                let head = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                let _ = stream.write_all(body).await;
+            });
+        }
+    });
+    url
+}
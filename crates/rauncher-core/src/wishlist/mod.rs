@@ -0,0 +1,285 @@
+//! Store offers the user wants to buy, with the price last seen for each. Kept in
+//! `<data dir>/wishlist.json` and keyed by `namespace:offer_id`; checks compare the store's
+//! current price against the one seen before, so each sale or giveaway is reported once.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::{StoreOffer, StorePrice};
+use crate::config::Config;
+use crate::Result;
+
+const WISHLIST_FILE: &str = "wishlist.json";
+
+/// A wishlisted offer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WishlistItem {
+    pub title: String,
+    pub namespace: String,
+    pub offer_id: String,
+    /// Store page, where the game is bought
+    pub url: Option<String>,
+    /// Price at the last check
+    pub price: Option<StorePrice>,
+    /// Given away at the last check
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub giveaway: bool,
+    /// In the library at the last check; owned games aren't reported anymore
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub owned: bool,
+    pub added_at: DateTime<Utc>,
+    pub checked_at: Option<DateTime<Utc>>,
+}
+
+impl WishlistItem {
+    pub fn key(&self) -> String {
+        key(&self.namespace, &self.offer_id)
+    }
+
+    pub fn on_sale(&self) -> bool {
+        self.price
+            .as_ref()
+            .is_some_and(|price| price.discount > 0 || price.free)
+    }
+}
+
+/// What a check found about a wishlisted offer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WishlistEvent {
+    /// A discount started, or got bigger
+    OnSale {
+        title: String,
+        discount: u32,
+        price: String,
+    },
+    /// The offer is among the games given away right now
+    Free { title: String },
+}
+
+/// Every wishlisted offer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Wishlist {
+    #[serde(default)]
+    items: BTreeMap<String, WishlistItem>,
+}
+
+impl Wishlist {
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        crate::logs::transcript::touched("wrote", &path);
+
+        Ok(())
+    }
+
+    fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join(WISHLIST_FILE))
+    }
+
+    /// Wishlisted offers, by title
+    pub fn items(&self) -> Vec<&WishlistItem> {
+        let mut items: Vec<&WishlistItem> = self.items.values().collect();
+        items.sort_by_key(|item| item.title.to_lowercase());
+        items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, offer: &StoreOffer) -> bool {
+        self.items.contains_key(&key(&offer.namespace, &offer.id))
+    }
+
+    /// Wishlist an offer at its current price. Returns false if it already was.
+    pub fn add(&mut self, offer: &StoreOffer) -> bool {
+        let key = key(&offer.namespace, &offer.id);
+        if self.items.contains_key(&key) {
+            return false;
+        }
+
+        self.items.insert(
+            key,
+            WishlistItem {
+                title: offer.title.clone(),
+                namespace: offer.namespace.clone(),
+                offer_id: offer.id.clone(),
+                url: offer.url.clone(),
+                price: offer.price.clone(),
+                giveaway: false,
+                owned: false,
+                added_at: Utc::now(),
+                checked_at: None,
+            },
+        );
+        true
+    }
+
+    /// Take an offer off the wishlist, by `namespace:offer_id` or title (case-insensitive)
+    pub fn remove(&mut self, name: &str) -> Option<WishlistItem> {
+        let name = name.trim();
+        let key = self
+            .items
+            .iter()
+            .find(|(key, item)| *key == name || item.title.eq_ignore_ascii_case(name))
+            .map(|(key, _)| key.clone())?;
+        self.items.remove(&key)
+    }
+
+    /// Record what a check found for the item under `key`: its offer in the store (None
+    /// when the store no longer lists it), whether it's given away and whether the user owns
+    /// it now. Returns what changed since the last check that's worth telling the user.
+    pub fn record(
+        &mut self,
+        key: &str,
+        offer: Option<&StoreOffer>,
+        giveaway: bool,
+        owned: bool,
+    ) -> Vec<WishlistEvent> {
+        let Some(item) = self.items.get_mut(key) else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+
+        let price = offer.and_then(|offer| offer.price.clone());
+        let discount = |price: &Option<StorePrice>| price.as_ref().map_or(0, |p| p.discount);
+        if !owned && !item.owned {
+            if let Some(current) = &price {
+                if current.discount > discount(&item.price) && !current.free {
+                    events.push(WishlistEvent::OnSale {
+                        title: item.title.clone(),
+                        discount: current.discount,
+                        price: current.current.clone(),
+                    });
+                }
+            }
+            if giveaway && !item.giveaway {
+                events.push(WishlistEvent::Free {
+                    title: item.title.clone(),
+                });
+            }
+        }
+
+        if let Some(offer) = offer {
+            item.url = offer.url.clone().or(item.url.take());
+        }
+        if price.is_some() {
+            item.price = price;
+        }
+        item.giveaway = giveaway;
+        item.owned = owned;
+        item.checked_at = Some(Utc::now());
+
+        events
+    }
+}
+
+fn key(namespace: &str, offer_id: &str) -> String {
+    format!("{}:{}", namespace, offer_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offer(discount: u32) -> StoreOffer {
+        StoreOffer {
+            title: "Kiwi Quest".to_string(),
+            namespace: "kiwi".to_string(),
+            id: "offer1".to_string(),
+            description: String::new(),
+            seller: None,
+            url: None,
+            image: None,
+            genres: Vec::new(),
+            price: Some(StorePrice {
+                original: "€20.00".to_string(),
+                current: format!("€{:.2}", 20.0 * (100 - discount) as f64 / 100.0),
+                currency: "EUR".to_string(),
+                discount,
+                free: false,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_each_sale_is_reported_once() {
+        let mut wishlist = Wishlist::default();
+        assert!(wishlist.add(&offer(0)));
+        assert!(!wishlist.add(&offer(0)));
+
+        assert!(wishlist
+            .record("kiwi:offer1", Some(&offer(0)), false, false)
+            .is_empty());
+        assert_eq!(
+            wishlist.record("kiwi:offer1", Some(&offer(50)), false, false),
+            vec![WishlistEvent::OnSale {
+                title: "Kiwi Quest".to_string(),
+                discount: 50,
+                price: "€10.00".to_string(),
+            }]
+        );
+        assert!(wishlist
+            .record("kiwi:offer1", Some(&offer(50)), false, false)
+            .is_empty());
+        assert!(wishlist.items()[0].on_sale());
+
+        // A deeper discount is news again, and so is a sale after the price went back up
+        assert_eq!(
+            wishlist
+                .record("kiwi:offer1", Some(&offer(75)), false, false)
+                .len(),
+            1
+        );
+        wishlist.record("kiwi:offer1", Some(&offer(0)), false, false);
+        assert_eq!(
+            wishlist
+                .record("kiwi:offer1", Some(&offer(25)), false, false)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_giveaways_are_reported_unless_owned() {
+        let mut wishlist = Wishlist::default();
+        wishlist.add(&offer(0));
+
+        assert_eq!(
+            wishlist.record("kiwi:offer1", None, true, false),
+            vec![WishlistEvent::Free {
+                title: "Kiwi Quest".to_string()
+            }]
+        );
+        assert!(wishlist.record("kiwi:offer1", None, true, false).is_empty());
+        // The price seen before is kept when the store stops listing the offer
+        assert!(wishlist.items()[0].price.is_some());
+
+        // Claimed: nothing to report anymore
+        assert!(wishlist
+            .record("kiwi:offer1", Some(&offer(90)), false, true)
+            .is_empty());
+        assert!(wishlist.items()[0].owned);
+
+        assert_eq!(
+            wishlist.remove("KIWI QUEST").map(|item| item.key()),
+            Some("kiwi:offer1".to_string())
+        );
+        assert!(wishlist.is_empty());
+    }
+}
@@ -0,0 +1,853 @@
+use eframe::egui;
+use poll_promise::Promise;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use rauncher_core::api::{BuildInfo, Game};
+use rauncher_core::auth::AuthManager;
+use rauncher_core::collections::Collections;
+use rauncher_core::config::Config;
+use rauncher_core::downloads::{DownloadQueue, JobState};
+use rauncher_core::games::{CrashReport, GameManager, InstalledGame, UninstallOptions};
+use rauncher_core::notify::Notification;
+use rauncher_core::providers;
+use rauncher_core::storage::Removal;
+use rauncher_core::tr;
+use rauncher_core::Result;
+
+use super::auth_view::AuthView;
+use super::big_picture::{BigPictureAction, BigPictureView};
+use super::components::{
+    CrashReportDialog, GameSettingsAction, GameSettingsDialog, Header, HeaderAction, Page,
+    PromptDialog, StatusBar, StatusBarAction, TagChange, Toasts,
+};
+use super::downloads_view::DownloadsView;
+use super::gamepad::{Gamepad, PadInput};
+use super::history_view::HistoryView;
+use super::library_view::{LibraryAction, LibraryView};
+use super::stats_view::StatsView;
+use super::storage_view::{StorageAction, StorageView};
+use super::store_view::StoreView;
+use super::tasks::{TaskEvent, Tasks};
+use super::theme::{Theme, ThemeWatcher};
+
+enum AppState {
+    Login,
+    Page(Page),
+    /// The launcher couldn't start with the configuration, for this reason
+    Unavailable(String),
+}
+
+/// How often games on removable and network drives are looked for
+const DRIVE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The library, and whether it came from the cache for lack of a connection
+type LibraryListing = (Vec<Game>, bool);
+
+pub struct LauncherApp {
+    state: AppState,
+    auth: AuthManager,
+    /// Shared with the background tasks and the download worker
+    manager: GameManager,
+    auth_view: AuthView,
+    library_view: LibraryView,
+    storage_view: StorageView,
+    history_view: HistoryView,
+    stats_view: StatsView,
+    store_view: StoreView,
+    library_games: Vec<Game>,
+    installed_games: Vec<InstalledGame>,
+    /// Installed games whose removable or network drive isn't mounted
+    unavailable_games: HashSet<String>,
+    /// When the drives of the installed games were last looked for
+    drives_checked: Instant,
+    /// Categories, favorites and hidden games
+    collections: Collections,
+    status_message: String,
+    loading_library: bool,
+    library_promise: Option<Promise<Result<LibraryListing>>>,
+    /// When the library was last loaded, for the refresh every `library_refresh_minutes`
+    library_refreshed: Instant,
+    /// Installed games with a newer build, by address, and that build
+    updates: HashMap<String, BuildInfo>,
+    /// The library shown is the last cached one
+    offline: bool,
+    /// Installs, run one at a time by the download worker
+    downloads: DownloadQueue,
+    /// Launches, uninstalls and installed-game listings running in the background
+    tasks: Tasks,
+    /// Games being launched or uninstalled, which ignore further clicks until done
+    busy_games: HashSet<String>,
+    toasts: Toasts,
+    /// Report of the last crash, offered from the status bar
+    crash_report: Option<CrashReport>,
+    crash_dialog: Option<CrashReportDialog>,
+    profiles: Vec<String>,
+    settings_dialog: Option<GameSettingsDialog>,
+    /// Questions asked by installs running in the background
+    prompt_dialog: PromptDialog,
+    gamepad: Gamepad,
+    /// Showing the fullscreen interface instead of the regular pages
+    big_picture: bool,
+    big_picture_view: BigPictureView,
+    /// The selected theme, reloaded when its file is edited
+    theme: ThemeWatcher,
+    /// Configured and system scale when the UI scale was last applied, so ctrl +/- zooming
+    /// isn't undone until one of them changes
+    applied_scale: Option<(Option<f32>, Option<f32>)>,
+    library_painted: bool,
+}
+
+impl LauncherApp {
+    pub fn new(cc: &eframe::CreationContext<'_>, big_picture: bool) -> Self {
+        let _phase = rauncher_core::profiling::phase("gui init");
+
+        let config = Config::load().unwrap_or_default();
+        rauncher_core::i18n::init(config.language.as_deref());
+
+        let (theme, loaded) = ThemeWatcher::load(config.theme.as_deref());
+        let mut status_message = String::new();
+        match loaded {
+            Ok(loaded) => loaded.apply(&cc.egui_ctx),
+            Err(error) => {
+                Theme::default().apply(&cc.egui_ctx);
+                status_message = tr!("gui-theme-failed", error = error);
+            }
+        }
+        let auth = AuthManager::new().unwrap_or_default();
+
+        // Check if already authenticated
+        let is_authenticated = auth.is_authenticated();
+        let (manager, state) = match GameManager::new(config, auth.clone()) {
+            Ok(manager) => {
+                let state = if is_authenticated {
+                    AppState::Page(Page::Library)
+                } else {
+                    AppState::Login
+                };
+                (manager, state)
+            }
+            Err(e) => {
+                // Explain what's wrong instead of closing; the defaults only stand in so the
+                // window can open, and nothing is saved over the configuration meanwhile
+                let manager = GameManager::new(Config::default(), auth.clone())
+                    .expect("The default configuration is valid");
+                (manager, AppState::Unavailable(e.to_string()))
+            }
+        };
+
+        let prompt_dialog = PromptDialog::default();
+        let downloads = DownloadQueue::new();
+        downloads.set_order(manager.config().download_order);
+        let prompter = Arc::new(prompt_dialog.prompter());
+        let worker_manager = manager.clone();
+        downloads.spawn_worker(move || {
+            let mut manager = worker_manager.clone();
+            manager.set_notifications(true);
+            manager.set_prompter(prompter.clone());
+            Ok(manager)
+        });
+
+        Self {
+            state,
+            auth,
+            manager,
+            auth_view: AuthView::default(),
+            library_view: LibraryView::default(),
+            storage_view: StorageView::default(),
+            history_view: HistoryView::default(),
+            stats_view: StatsView::default(),
+            store_view: StoreView::default(),
+            library_games: Vec::new(),
+            installed_games: Vec::new(),
+            unavailable_games: HashSet::new(),
+            drives_checked: Instant::now(),
+            collections: Collections::load().unwrap_or_default(),
+            status_message,
+            loading_library: false,
+            library_promise: None,
+            library_refreshed: Instant::now(),
+            updates: HashMap::new(),
+            offline: false,
+            downloads,
+            tasks: Tasks::new(cc.egui_ctx.clone()),
+            busy_games: HashSet::new(),
+            toasts: Toasts::default(),
+            crash_report: None,
+            crash_dialog: None,
+            profiles: AuthManager::list_profiles().unwrap_or_default(),
+            settings_dialog: None,
+            prompt_dialog,
+            gamepad: Gamepad::new(),
+            big_picture,
+            big_picture_view: BigPictureView::default(),
+            theme,
+            applied_scale: None,
+            library_painted: false,
+        }
+    }
+
+    fn handle_login(&mut self) {
+        self.manager.set_auth(self.auth.clone());
+        self.state = AppState::Page(Page::Library);
+        self.profiles = AuthManager::list_profiles().unwrap_or_default();
+        self.load_library();
+        self.load_installed_games();
+    }
+
+    fn handle_header_action(&mut self, action: HeaderAction, ctx: &egui::Context) {
+        if let HeaderAction::BigPicture = action {
+            self.big_picture = true;
+            return;
+        }
+
+        if let HeaderAction::ShowPage(page) = action {
+            match page {
+                Page::Storage => self.storage_view.refresh(&self.manager.config()),
+                Page::Store => self.store_view.open(&self.manager),
+                Page::History => self.history_view.refresh(),
+                Page::Stats => self.stats_view.refresh(&self.manager),
+                Page::Library | Page::Downloads => {}
+            }
+            self.state = AppState::Page(page);
+            return;
+        }
+
+        if let HeaderAction::SetTheme(name) = action {
+            self.set_theme(name, ctx);
+            return;
+        }
+
+        if let HeaderAction::SetUiScale(scale) = action {
+            if let Err(e) = self.manager.update_config(|config| config.ui_scale = scale) {
+                self.status_message = tr!("gui-settings-save-failed", error = e.to_string());
+            }
+            return;
+        }
+
+        if let HeaderAction::SetNotifications(settings) = action {
+            let saved = self
+                .manager
+                .update_config(|config| config.notifications = settings);
+            if let Err(e) = saved {
+                self.status_message = tr!("gui-settings-save-failed", error = e.to_string());
+            }
+            return;
+        }
+
+        let result = {
+            let auth = &mut self.auth;
+            match &action {
+                HeaderAction::Logout => auth.logout(),
+                HeaderAction::SwitchProfile(name) => auth.switch_profile(name),
+                HeaderAction::AddAccount => auth.prepare_new_profile(),
+                HeaderAction::SetNotifications(_)
+                | HeaderAction::ShowPage(_)
+                | HeaderAction::BigPicture
+                | HeaderAction::SetTheme(_)
+                | HeaderAction::SetUiScale(_) => unreachable!("handled above"),
+            }
+        };
+
+        if let Err(e) = result {
+            self.status_message = tr!("gui-error", error = e.to_string());
+            return;
+        }
+
+        self.manager.set_auth(self.auth.clone());
+        self.library_games.clear();
+        self.installed_games.clear();
+        self.updates.clear();
+        self.profiles = AuthManager::list_profiles().unwrap_or_default();
+
+        match action {
+            HeaderAction::SwitchProfile(name) => {
+                self.status_message = tr!("gui-profile-switched", name = name);
+                self.load_library();
+                self.load_installed_games();
+            }
+            HeaderAction::Logout | HeaderAction::AddAccount => {
+                self.state = AppState::Login;
+            }
+            HeaderAction::SetNotifications(_)
+            | HeaderAction::ShowPage(_)
+            | HeaderAction::BigPicture
+            | HeaderAction::SetTheme(_)
+            | HeaderAction::SetUiScale(_) => {}
+        }
+    }
+
+    fn load_library(&mut self) {
+        if self.loading_library {
+            return;
+        }
+
+        self.loading_library = true;
+        self.library_refreshed = Instant::now();
+        self.status_message = tr!("gui-library-loading");
+        self.tasks.check_updates(self.manager.clone());
+
+        // Usa GameManager per beneficiare dell'auto-refresh del token
+        let manager = self.manager.clone();
+
+        self.library_promise = Some(Promise::spawn_thread("load_library", move || {
+            let rt = tokio::runtime::Runtime::new()
+                .expect("Failed to create Tokio runtime for library load");
+            rt.block_on(async move {
+                let games = manager.list_merged_library().await?;
+                Ok((games, manager.is_offline()))
+            })
+        }));
+    }
+
+    fn load_installed_games(&mut self) {
+        self.tasks.load_installed(self.manager.clone());
+    }
+
+    /// Reload the library, the installed games and their updates once the configured time has
+    /// passed since the last load, so badges stay current without reloading by hand
+    fn refresh_if_due(&mut self) {
+        let minutes = self.manager.config().library_refresh_minutes;
+        if minutes == 0 || self.library_refreshed.elapsed() < Duration::from_secs(minutes * 60) {
+            return;
+        }
+
+        self.load_library();
+        self.load_installed_games();
+    }
+
+    /// Badge the games with a newer build, with a desktop notification for the ones that
+    /// weren't known yet
+    fn show_updates(&mut self, pending: Vec<(InstalledGame, BuildInfo)>) {
+        let mut notifier = self.manager.clone();
+        notifier.set_notifications(true);
+
+        let mut updates = HashMap::new();
+        for (game, build) in pending {
+            let address = game.id();
+            let known = self.updates.get(&address);
+            if known.is_none_or(|known| known.version != build.version) {
+                notifier.notify(Notification::UpdateAvailable {
+                    title: game.app_title,
+                    version: build.version.clone(),
+                });
+            }
+            updates.insert(address, build);
+        }
+        self.updates = updates;
+    }
+
+    /// Look for the drives of games installed on removable or network drives, so they turn
+    /// unavailable when unplugged and come back on their own
+    fn check_drives(&mut self) {
+        self.unavailable_games = self
+            .installed_games
+            .iter()
+            .filter(|game| game.is_unavailable())
+            .map(InstalledGame::id)
+            .collect();
+        self.drives_checked = Instant::now();
+    }
+
+    fn handle_install(&mut self, address: String) {
+        if self.downloads.contains(&address) {
+            return;
+        }
+
+        self.library_view.mark_installation_started(&address);
+        self.downloads.enqueue(&address);
+        self.status_message = tr!("gui-install-started", app = address.as_str());
+    }
+
+    fn handle_launch(&mut self, app_name: String) {
+        if self.library_view.is_running(&app_name) || !self.busy_games.insert(app_name.clone()) {
+            return;
+        }
+
+        self.status_message = tr!("gui-launching", app = app_name.as_str());
+        self.tasks.launch(self.manager.clone(), app_name);
+    }
+
+    fn handle_stop(&mut self, app_name: String) {
+        if !self.busy_games.insert(app_name.clone()) {
+            return;
+        }
+
+        self.status_message = tr!("gui-stopping", app = app_name.as_str());
+        self.tasks.stop(self.manager.clone(), app_name);
+    }
+
+    fn set_install_dir(&mut self, path: PathBuf) {
+        let saved = path.display().to_string();
+        match self
+            .manager
+            .update_config(|config| config.install_dir = path)
+        {
+            Ok(()) => {
+                self.status_message = tr!("gui-install-dir-saved", path = saved);
+                self.storage_view.refresh(&self.manager.config());
+            }
+            Err(e) => {
+                self.status_message = tr!("gui-settings-save-failed", error = e.to_string());
+            }
+        }
+    }
+
+    fn handle_settings_action(&mut self, action: GameSettingsAction) {
+        if let GameSettingsAction::Save(app_name, settings) = action {
+            let saved = self
+                .manager
+                .update_config(|config| config.games.insert(app_name.clone(), *settings));
+            match saved {
+                Ok(_) => {
+                    self.status_message = tr!("gui-settings-saved", app = app_name);
+                }
+                Err(e) => {
+                    self.status_message = tr!("gui-settings-save-failed", error = e.to_string());
+                }
+            }
+        }
+
+        self.settings_dialog = None;
+    }
+
+    /// B closes the open dialog, then returns to the library from other pages
+    fn handle_pad_input(&mut self, input: PadInput) {
+        if input == PadInput::Back
+            && (self.crash_dialog.take().is_some() || self.settings_dialog.take().is_some())
+        {
+            return;
+        }
+
+        match self.state {
+            AppState::Page(_) if self.big_picture => self.big_picture_view.navigate(input),
+            AppState::Page(Page::Library) => self.library_view.navigate(input),
+            AppState::Page(_) if input == PadInput::Back => {
+                self.state = AppState::Page(Page::Library);
+            }
+            AppState::Page(_) | AppState::Login | AppState::Unavailable(_) => {}
+        }
+    }
+
+    /// Open a game's settings dialog; settings are kept per app name
+    fn open_settings(&mut self, address: &str) {
+        let app_name = providers::parse_address(address).1.to_string();
+        let settings = self.manager.config().game_settings(&app_name);
+        self.settings_dialog = Some(GameSettingsDialog::new(app_name, settings));
+    }
+
+    fn handle_big_picture_action(&mut self, action: BigPictureAction) {
+        match action {
+            BigPictureAction::Launch(address) => self.handle_launch(address),
+            BigPictureAction::Settings(address) => self.open_settings(&address),
+            BigPictureAction::Exit => self.big_picture = false,
+        }
+    }
+
+    fn handle_tag(&mut self, address: String, change: TagChange) {
+        match change {
+            TagChange::Favorite(favorite) => self.collections.set_favorite(&address, favorite),
+            TagChange::Hidden(hidden) => self.collections.set_hidden(&address, hidden),
+            TagChange::AddCategory(category) => {
+                self.collections.add_category(&address, &category);
+            }
+            TagChange::RemoveCategory(category) => {
+                self.collections.remove_category(&address, &category);
+            }
+        }
+
+        if let Err(e) = self.collections.save() {
+            self.notify_error(tr!("gui-collections-save-failed", error = e.to_string()));
+        }
+    }
+
+    fn handle_uninstall(&mut self, app_name: String) {
+        if !self.busy_games.insert(app_name.clone()) {
+            return;
+        }
+
+        self.status_message = tr!("gui-uninstall-started", app = app_name.as_str());
+        self.tasks
+            .uninstall(self.manager.clone(), app_name, UninstallOptions::default());
+    }
+
+    /// Show the outcome of a background operation
+    fn handle_task_event(&mut self, event: TaskEvent) {
+        match event {
+            TaskEvent::InstalledGames {
+                result: Ok(games), ..
+            } => {
+                // Games updated since the last check have their new build now
+                self.updates.retain(|address, build| {
+                    games
+                        .iter()
+                        .any(|game| &game.id() == address && game.app_version != build.version)
+                });
+                self.installed_games = games;
+                self.check_drives();
+            }
+            TaskEvent::InstalledGames { result: Err(e), .. } => {
+                self.toasts.error(tr!("gui-error", error = e.to_string()));
+            }
+            TaskEvent::Launched { app, result } => {
+                self.busy_games.remove(&app);
+                match result {
+                    Ok(()) => {
+                        self.notify_success(tr!("gui-launched", app = app.as_str()));
+                        self.library_view.mark_running(&app, true);
+                    }
+                    Err(e) => self.notify_error(tr!(
+                        "gui-launch-failed",
+                        app = app,
+                        error = e.to_string()
+                    )),
+                }
+            }
+            TaskEvent::GameExited { app, result } => {
+                self.library_view.mark_running(&app, false);
+                match result {
+                    Ok(Some(report)) => {
+                        self.notify_error(tr!("gui-game-crashed", app = report.app_title.as_str()));
+                        self.crash_report = Some(report);
+                    }
+                    Ok(None) => {}
+                    Err(e) => self.notify_error(tr!("gui-error", error = e.to_string())),
+                }
+            }
+            TaskEvent::Stopped { app, result } => {
+                self.busy_games.remove(&app);
+                match result {
+                    Ok(_) => {
+                        self.status_message = tr!("gui-stopped", app = app.as_str());
+                        self.library_view.mark_running(&app, false);
+                    }
+                    Err(e) => {
+                        self.notify_error(tr!("gui-stop-failed", app = app, error = e.to_string()))
+                    }
+                }
+            }
+            TaskEvent::Uninstalled {
+                app,
+                removal,
+                result,
+            } => {
+                self.busy_games.remove(&app);
+                match result {
+                    Ok(()) => {
+                        let trash = if removal == Removal::Trash {
+                            "yes"
+                        } else {
+                            "no"
+                        };
+                        self.notify_success(tr!(
+                            "gui-uninstalled",
+                            app = app.as_str(),
+                            trash = trash
+                        ));
+                        self.load_installed_games();
+                    }
+                    Err(e) => self.notify_error(tr!(
+                        "gui-uninstall-failed",
+                        app = app,
+                        error = e.to_string()
+                    )),
+                }
+            }
+            TaskEvent::Updates(Ok(pending)) => self.show_updates(pending),
+            TaskEvent::Updates(Err(e)) => {
+                self.toasts.error(tr!("gui-error", error = e.to_string()));
+            }
+        }
+    }
+
+    /// Select a theme, remembering it in the configuration
+    fn set_theme(&mut self, name: String, ctx: &egui::Context) {
+        let (theme, loaded) = ThemeWatcher::load(Some(&name));
+        let loaded = match loaded {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                self.notify_error(tr!("gui-theme-failed", error = error));
+                return;
+            }
+        };
+        loaded.apply(ctx);
+        self.theme = theme;
+
+        let saved = self
+            .manager
+            .update_config(|config| config.theme = Some(name));
+        if let Err(e) = saved {
+            self.status_message = tr!("gui-settings-save-failed", error = e.to_string());
+        }
+    }
+
+    /// Scale the interface as configured, or by the system's display scaling when unset.
+    /// Applied again when the setting changes or the window moves to a monitor with another
+    /// scaling.
+    fn apply_ui_scale(&mut self, ctx: &egui::Context) {
+        let system = ctx.native_pixels_per_point();
+        let configured = self.manager.config().ui_scale;
+        let wanted = (configured, system);
+        if self.applied_scale == Some(wanted) {
+            return;
+        }
+
+        self.applied_scale = Some(wanted);
+        if let Some(scale) = configured.or(system) {
+            ctx.set_pixels_per_point(scale);
+        }
+    }
+
+    /// Report a finished operation in a toast, and in the status bar until the next one
+    fn notify_success(&mut self, text: String) {
+        self.status_message = text.clone();
+        self.toasts.success(text);
+    }
+
+    fn notify_error(&mut self, text: String) {
+        self.status_message = text.clone();
+        self.toasts.error(text);
+    }
+}
+
+impl eframe::App for LauncherApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        match self.theme.poll() {
+            Some(Ok(theme)) => theme.apply(ctx),
+            Some(Err(error)) => self.notify_error(tr!("gui-theme-failed", error = error)),
+            None => {}
+        }
+        let theme = Theme::current(ctx);
+        self.apply_ui_scale(ctx);
+
+        for input in self.gamepad.poll() {
+            self.handle_pad_input(input);
+        }
+
+        // Check for library loading completion
+        if let Some(promise) = &self.library_promise {
+            if let Some(result) = promise.ready() {
+                match result {
+                    Ok((games, offline)) => {
+                        self.library_games = games.clone();
+                        self.offline = *offline;
+                        self.status_message = if *offline {
+                            tr!("gui-library-loaded-offline")
+                        } else {
+                            tr!("gui-library-loaded")
+                        };
+                        if !self.library_painted {
+                            self.library_painted = true;
+                            rauncher_core::profiling::milestone("first library paint");
+                        }
+                    }
+                    Err(e) => {
+                        self.status_message = tr!("gui-library-failed", error = e.to_string());
+                    }
+                }
+                self.loading_library = false;
+                self.library_promise = None;
+            }
+        }
+
+        // Big Picture replaces the header and pages once logged in
+        let big_picture = self.big_picture && matches!(self.state, AppState::Page(_));
+
+        if let AppState::Unavailable(error) = &self.state {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(60.0);
+                    ui.heading(tr!("gui-unavailable-title"));
+                    ui.add_space(10.0);
+                    ui.colored_label(theme.error, error);
+                    ui.add_space(10.0);
+                    let path = Config::config_path()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_default();
+                    ui.label(tr!("gui-unavailable-hint", path = path));
+                });
+            });
+            return;
+        }
+
+        if !big_picture {
+            egui::TopBottomPanel::top("top_panel")
+                .frame(
+                    egui::Frame::none()
+                        .fill(theme.panel)
+                        .inner_margin(egui::Margin::symmetric(20.0, 15.0)),
+                )
+                .show(ctx, |ui| {
+                    let (is_authenticated, page) = match self.state {
+                        AppState::Login | AppState::Unavailable(_) => (false, Page::Library),
+                        AppState::Page(page) => (true, page),
+                    };
+                    let active_profile = self.auth.active_profile();
+
+                    if let Some(action) = Header::show(
+                        ui,
+                        is_authenticated,
+                        active_profile.as_deref(),
+                        &self.profiles,
+                        &self.manager.config(),
+                        page,
+                        self.offline,
+                    ) {
+                        self.handle_header_action(action, ui.ctx());
+                    }
+                });
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if big_picture {
+                if let Some(action) = self.big_picture_view.ui(ui, &self.installed_games) {
+                    self.handle_big_picture_action(action);
+                }
+                return;
+            }
+
+            match self.state {
+                AppState::Unavailable(_) => {}
+                AppState::Login => {
+                    let config = self.manager.config();
+                    if self.auth_view.ui(ui, &mut self.auth, &config.epic) {
+                        self.handle_login();
+                    }
+                }
+                AppState::Page(Page::Downloads) => {
+                    if let Some(order) = DownloadsView::ui(ui, &self.downloads, &self.manager) {
+                        let saved = self
+                            .manager
+                            .update_config(|config| config.download_order = order);
+                        if let Err(e) = saved {
+                            self.status_message =
+                                tr!("gui-settings-save-failed", error = e.to_string());
+                        }
+                    }
+                }
+                AppState::Page(Page::History) => self.history_view.ui(ui),
+                AppState::Page(Page::Stats) => self.stats_view.ui(ui, &self.manager),
+                AppState::Page(Page::Store) => {
+                    self.store_view.ui(ui, &self.manager, &self.library_games);
+                }
+                AppState::Page(Page::Storage) => {
+                    // Directories of installs still downloading aren't partial leftovers
+                    let active = self
+                        .downloads
+                        .jobs()
+                        .into_iter()
+                        .filter(|job| !job.state.is_finished())
+                        .map(|job| providers::parse_address(&job.address).1.to_string())
+                        .collect();
+                    match self.storage_view.ui(ui, &self.manager, active) {
+                        Some(StorageAction::Adopted(game)) => {
+                            self.status_message = tr!(
+                                "gui-orphan-adopted",
+                                path = game.install_path.display().to_string(),
+                                app = game.id()
+                            );
+                            self.load_installed_games();
+                        }
+                        Some(StorageAction::InstallDirChanged(path)) => {
+                            self.set_install_dir(path);
+                        }
+                        None => {}
+                    }
+                }
+                AppState::Page(Page::Library) => {
+                    if let Some(action) = self.library_view.ui(
+                        ui,
+                        &self.library_games,
+                        &self.installed_games,
+                        &self.unavailable_games,
+                        &self.updates,
+                        &self.collections,
+                    ) {
+                        match action {
+                            LibraryAction::Install(app_name) => {
+                                self.handle_install(app_name);
+                            }
+                            LibraryAction::Launch(app_name) => {
+                                self.handle_launch(app_name);
+                            }
+                            LibraryAction::Stop(app_name) => {
+                                self.handle_stop(app_name);
+                            }
+                            LibraryAction::Uninstall(app_name) => {
+                                self.handle_uninstall(app_name);
+                            }
+                            LibraryAction::Settings(address) => {
+                                self.open_settings(&address);
+                            }
+                            LibraryAction::Tag(address, change) => {
+                                self.handle_tag(address, change);
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Status bar at bottom using StatusBar component
+            match StatusBar::show(ui, &self.status_message, self.crash_report.is_some()) {
+                Some(StatusBarAction::Clear) => {
+                    self.status_message.clear();
+                    self.crash_report = None;
+                }
+                Some(StatusBarAction::ViewReport) => {
+                    self.crash_dialog = self.crash_report.clone().map(CrashReportDialog::new);
+                }
+                None => {}
+            }
+        });
+
+        if let Some(dialog) = &mut self.settings_dialog {
+            if let Some(action) = dialog.show(ctx) {
+                self.handle_settings_action(action);
+            }
+        }
+        self.prompt_dialog.show(ctx);
+
+        if self
+            .crash_dialog
+            .as_ref()
+            .is_some_and(|dialog| dialog.show(ctx))
+        {
+            self.crash_dialog = None;
+        }
+
+        let mut need_reload_installed = false;
+        for job in self.downloads.take_finished() {
+            let app = job.address.as_str();
+            self.library_view.mark_installation_complete(app);
+            match &job.state {
+                JobState::Completed => {
+                    self.notify_success(tr!("gui-install-done", app = app));
+                    need_reload_installed = true;
+                }
+                JobState::Failed(error) => {
+                    self.notify_error(tr!("gui-install-failed", app = app, error = error.as_str()));
+                }
+                _ => self.status_message = tr!("gui-install-cancelled", app = app),
+            }
+        }
+        if need_reload_installed {
+            self.load_installed_games();
+        }
+
+        for event in self.tasks.poll() {
+            self.handle_task_event(event);
+        }
+        if self.drives_checked.elapsed() >= DRIVE_CHECK_INTERVAL {
+            self.check_drives();
+        }
+        if matches!(self.state, AppState::Page(_)) {
+            self.refresh_if_due();
+        }
+        self.toasts.show(ctx);
+
+        // Request repaint for animations/updates
+        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+    }
+}
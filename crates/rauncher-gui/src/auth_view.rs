@@ -2,9 +2,13 @@ use egui::{Align, Layout, RichText};
 use poll_promise::Promise;
 use std::time::{Duration, Instant};
 
-use crate::api::{DeviceAuthResponse, EpicClient};
-use crate::auth::{AuthManager, AuthToken};
-use crate::Result;
+use rauncher_core::api::{DeviceAuthResponse, EpicClient};
+use rauncher_core::auth::{AuthManager, AuthToken, WebLogin};
+use rauncher_core::config::EpicSettings;
+use rauncher_core::tr;
+use rauncher_core::Result;
+
+use super::theme::Theme;
 
 enum AuthState {
     Idle,
@@ -14,6 +18,10 @@ enum AuthState {
         last_poll: Instant,
         attempts: u32,
     },
+    /// Waiting for the code from the browser login page served at `url`
+    WebLogin {
+        url: String,
+    },
 }
 
 pub struct AuthView {
@@ -23,6 +31,7 @@ pub struct AuthView {
     user_code: Option<String>,
     device_auth_promise: Option<Promise<Result<DeviceAuthResponse>>>,
     poll_promise: Option<Promise<Result<Option<AuthToken>>>>,
+    web_login_promise: Option<Promise<Result<AuthToken>>>,
 }
 
 impl Default for AuthView {
@@ -34,12 +43,19 @@ impl Default for AuthView {
             user_code: None,
             device_auth_promise: None,
             poll_promise: None,
+            web_login_promise: None,
         }
     }
 }
 
 impl AuthView {
-    pub fn ui(&mut self, ui: &mut egui::Ui, auth: &mut AuthManager) -> bool {
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        auth: &mut AuthManager,
+        settings: &EpicSettings,
+    ) -> bool {
+        let theme = Theme::current(ui.ctx());
         // Handle device auth promise
         if let Some(promise) = &self.device_auth_promise {
             if let Some(result) = promise.ready() {
@@ -54,7 +70,7 @@ impl AuthView {
                         };
                     }
                     Err(e) => {
-                        self.auth_status = format!("Failed to start authentication: {}", e);
+                        self.auth_status = tr!("gui-auth-start-failed", error = e.to_string());
                         self.state = AuthState::Idle;
                         self.verification_url = None;
                         self.user_code = None;
@@ -64,6 +80,24 @@ impl AuthView {
             }
         }
 
+        if let Some(result) = self.web_login_promise.as_ref().and_then(Promise::ready) {
+            let result = match result {
+                Ok(token) => auth
+                    .set_token(token.clone())
+                    .map_err(|e| tr!("gui-auth-save-failed", error = e.to_string())),
+                Err(e) => Err(tr!("gui-auth-failed", error = e.to_string())),
+            };
+            self.state = AuthState::Idle;
+            self.web_login_promise = None;
+            match result {
+                Ok(()) => {
+                    self.auth_status = tr!("gui-auth-success");
+                    return true;
+                }
+                Err(status) => self.auth_status = status,
+            }
+        }
+
         // Handle polling state - extract values first to avoid borrow checker issues
         let polling_info = if let AuthState::Polling {
             device_code,
@@ -84,11 +118,11 @@ impl AuthView {
                         Ok(Some(token)) => {
                             // Successfully got token, save it
                             if let Err(e) = auth.set_token(token.clone()) {
-                                self.auth_status = format!("Error saving token: {}", e);
+                                self.auth_status =
+                                    tr!("gui-auth-save-failed", error = e.to_string());
                                 self.state = AuthState::Idle;
                             } else {
-                                self.auth_status =
-                                    "✓ Successfully authenticated with Epic Games!".to_string();
+                                self.auth_status = tr!("gui-auth-success");
                                 self.state = AuthState::Idle;
                                 self.poll_promise = None;
                                 return true; // Signal successful login
@@ -99,8 +133,7 @@ impl AuthView {
                             let new_attempts = attempts + 1;
                             if new_attempts >= 120 {
                                 // Timeout after 10 minutes (120 * 5 seconds)
-                                self.auth_status =
-                                    "Authentication timed out. Please try again.".to_string();
+                                self.auth_status = tr!("gui-auth-timed-out");
                                 self.state = AuthState::Idle;
                                 self.verification_url = None;
                                 self.user_code = None;
@@ -113,7 +146,7 @@ impl AuthView {
                             }
                         }
                         Err(e) => {
-                            self.auth_status = format!("Authentication failed: {}", e);
+                            self.auth_status = tr!("gui-auth-failed", error = e.to_string());
                             self.state = AuthState::Idle;
                             self.verification_url = None;
                             self.user_code = None;
@@ -126,11 +159,12 @@ impl AuthView {
             // Start new poll if needed
             if self.poll_promise.is_none() && last_poll.elapsed() >= Duration::from_secs(5) {
                 let device_code_clone = device_code.clone();
+                let settings = settings.clone();
                 let promise = Promise::spawn_thread("poll_auth", move || {
                     tokio::runtime::Runtime::new()
                         .unwrap()
                         .block_on(async move {
-                            let client = EpicClient::new()?;
+                            let client = EpicClient::with_settings(&settings)?;
                             client.poll_for_token(&device_code_clone).await
                         })
                 });
@@ -143,16 +177,16 @@ impl AuthView {
 
             // Enhanced Epic Games branding
             ui.heading(
-                RichText::new("EPIC GAMES STORE")
+                RichText::new(tr!("gui-store-banner"))
                     .size(36.0)
                     .strong()
-                    .color(egui::Color32::WHITE),
+                    .color(theme.text_strong),
             );
             ui.add_space(15.0);
             ui.label(
-                RichText::new("Sign in to your account")
+                RichText::new(tr!("gui-sign-in-subtitle"))
                     .size(18.0)
-                    .color(egui::Color32::from_rgb(180, 180, 190)),
+                    .color(theme.text_weak),
             );
 
             ui.add_space(50.0);
@@ -165,75 +199,118 @@ impl AuthView {
                     AuthState::Idle => {
                         // Show enhanced login button with Epic blue
                         let button = egui::Button::new(
-                            RichText::new("Sign In with Epic Games")
+                            RichText::new(tr!("gui-sign-in"))
                                 .size(18.0)
                                 .strong()
-                                .color(egui::Color32::WHITE),
+                                .color(theme.on_accent),
                         )
-                        .fill(egui::Color32::from_rgb(0, 121, 214))
+                        .fill(theme.accent)
                         .min_size(egui::Vec2::new(280.0, 50.0));
-                        
+
                         if ui.add(button).clicked() {
-                            self.start_authentication();
+                            self.start_authentication(settings);
                         }
 
                         ui.add_space(30.0);
 
                         // Enhanced instructions
                         ui.label(
-                            RichText::new("Click the button above to authenticate with Epic Games")
+                            RichText::new(tr!("gui-sign-in-hint"))
                                 .size(15.0)
-                                .color(egui::Color32::from_rgb(160, 160, 170)),
+                                .color(theme.text_weak),
                         );
                         ui.add_space(8.0);
                         ui.label(
-                            RichText::new("You'll receive a code to enter in your browser")
+                            RichText::new(tr!("gui-sign-in-code-hint"))
                                 .size(15.0)
-                                .color(egui::Color32::from_rgb(160, 160, 170)),
+                                .color(theme.text_weak),
+                        );
+
+                        ui.add_space(25.0);
+                        if ui.button(tr!("gui-sign-in-web")).clicked() {
+                            self.start_web_login(settings);
+                        }
+                        ui.label(
+                            RichText::new(tr!("gui-sign-in-web-hint"))
+                                .size(13.0)
+                                .color(theme.text_weak),
+                        );
+                    }
+                    AuthState::WebLogin { url } => {
+                        ui.heading(
+                            RichText::new(tr!("gui-auth-in-progress"))
+                                .size(24.0)
+                                .strong()
+                                .color(theme.text_strong),
+                        );
+                        ui.add_space(25.0);
+                        ui.label(
+                            RichText::new(tr!("gui-auth-web-steps"))
+                                .size(16.0)
+                                .color(theme.text),
+                        );
+                        ui.add_space(15.0);
+                        let _ = ui.selectable_label(
+                            false,
+                            RichText::new(url).monospace().size(14.0).color(theme.link),
                         );
+                        ui.add_space(15.0);
+                        if ui.button(tr!("gui-open-in-browser")).clicked() {
+                            let _ = webbrowser::open(url);
+                        }
+                        ui.add_space(15.0);
+                        ui.spinner();
+
+                        ui.add_space(25.0);
+                        if ui
+                            .button(RichText::new(tr!("gui-cancel")).size(14.0))
+                            .clicked()
+                        {
+                            self.cancel_authentication();
+                        }
                     }
                     AuthState::RequestingDeviceAuth => {
                         ui.spinner();
                         ui.add_space(10.0);
-                        ui.label("Initializing authentication...");
+                        ui.label(tr!("gui-auth-initializing"));
 
                         ui.add_space(20.0);
 
-                        if ui.button("Cancel").clicked() {
+                        if ui.button(tr!("gui-cancel")).clicked() {
                             self.cancel_authentication();
                         }
                     }
                     AuthState::Polling { attempts, .. } => {
                         // Show authentication in progress with enhanced styling
                         ui.heading(
-                            RichText::new("⏳ Authentication in Progress")
+                            RichText::new(tr!("gui-auth-in-progress"))
                                 .size(24.0)
                                 .strong()
-                                .color(egui::Color32::WHITE),
+                                .color(theme.text_strong),
                         );
                         ui.add_space(25.0);
 
                         if let (Some(url), Some(code)) = (&self.verification_url, &self.user_code) {
                             ui.label(
-                                RichText::new("Please complete authentication in your browser:")
+                                RichText::new(tr!("gui-auth-complete-in-browser"))
                                     .size(17.0)
-                                    .color(egui::Color32::from_rgb(220, 220, 230)),
+                                    .color(theme.text),
                             );
                             ui.add_space(20.0);
 
                             // Display verification URL in an enhanced frame
                             egui::Frame::none()
-                                .fill(egui::Color32::from_rgb(32, 34, 40))
-                                .stroke(egui::Stroke::new(2.0, egui::Color32::from_rgb(0, 121, 214)))
+                                .fill(theme.surface)
+                                .stroke(egui::Stroke::new(2.0, theme.accent))
                                 .rounding(egui::Rounding::same(6.0))
                                 .inner_margin(20.0)
                                 .show(ui, |ui| {
                                     ui.horizontal(|ui| {
                                         ui.label(
-                                            RichText::new("URL:")
+                                            RichText::new(tr!("gui-auth-url"))
                                                 .strong()
                                                 .size(15.0)
-                                                .color(egui::Color32::from_rgb(180, 180, 190)),
+                                                .color(theme.text_weak),
                                         );
                                         ui.add_space(8.0);
                                         let _ = ui.selectable_label(
@@ -241,7 +318,7 @@ impl AuthView {
                                             RichText::new(url)
                                                 .monospace()
                                                 .size(14.0)
-                                                .color(egui::Color32::from_rgb(100, 170, 230)),
+                                                .color(theme.link),
                                         );
                                     });
 
@@ -249,10 +326,10 @@ impl AuthView {
 
                                     ui.horizontal(|ui| {
                                         ui.label(
-                                            RichText::new("Code:")
+                                            RichText::new(tr!("gui-auth-code"))
                                                 .strong()
                                                 .size(15.0)
-                                                .color(egui::Color32::from_rgb(180, 180, 190)),
+                                                .color(theme.text_weak),
                                         );
                                         ui.add_space(8.0);
                                         let _ = ui.selectable_label(
@@ -261,7 +338,7 @@ impl AuthView {
                                                 .monospace()
                                                 .size(22.0)
                                                 .strong()
-                                                .color(egui::Color32::WHITE),
+                                                .color(theme.text_strong),
                                         );
                                     });
                                 });
@@ -270,32 +347,36 @@ impl AuthView {
 
                             // Enhanced "Open in Browser" button
                             let browser_button = egui::Button::new(
-                                RichText::new("🌐 Open in Browser")
+                                RichText::new(tr!("gui-open-in-browser"))
                                     .size(16.0)
                                     .strong()
-                                    .color(egui::Color32::WHITE),
+                                    .color(theme.on_accent),
                             )
-                            .fill(egui::Color32::from_rgb(0, 121, 214))
+                            .fill(theme.accent)
                             .min_size(egui::Vec2::new(200.0, 42.0));
-                            
+
                             if ui.add(browser_button).clicked() {
                                 let _ = webbrowser::open(url);
                             }
 
                             ui.add_space(15.0);
                             ui.label(
-                                RichText::new(format!(
-                                    "Waiting for you to complete authentication... (attempt {}/120)",
-                                    attempts + 1
+                                RichText::new(tr!(
+                                    "gui-auth-waiting",
+                                    attempt = attempts + 1,
+                                    max = 120
                                 ))
                                 .size(15.0)
-                                .color(egui::Color32::from_rgb(100, 170, 230)),
+                                .color(theme.link),
                             );
                         }
 
                         ui.add_space(25.0);
 
-                        if ui.button(RichText::new("Cancel").size(14.0)).clicked() {
+                        if ui
+                            .button(RichText::new(tr!("gui-cancel")).size(14.0))
+                            .clicked()
+                        {
                             self.cancel_authentication();
                         }
                     }
@@ -306,10 +387,10 @@ impl AuthView {
                 // Status message
                 if !self.auth_status.is_empty() {
                     ui.colored_label(
-                        if self.auth_status.starts_with("✓") {
-                            egui::Color32::GREEN
+                        if self.auth_status.starts_with('✓') {
+                            theme.success
                         } else {
-                            egui::Color32::RED
+                            theme.error
                         },
                         &self.auth_status,
                     );
@@ -320,18 +401,19 @@ impl AuthView {
         false
     }
 
-    fn start_authentication(&mut self) {
+    fn start_authentication(&mut self, settings: &EpicSettings) {
         self.state = AuthState::RequestingDeviceAuth;
         self.auth_status = String::new();
         self.verification_url = None;
         self.user_code = None;
 
         // Spawn thread to run async device auth request
-        let promise = Promise::spawn_thread("device_auth", || {
+        let settings = settings.clone();
+        let promise = Promise::spawn_thread("device_auth", move || {
             tokio::runtime::Runtime::new()
                 .unwrap()
                 .block_on(async move {
-                    let client = EpicClient::new()?;
+                    let client = EpicClient::with_settings(&settings)?;
                     client.request_device_auth().await
                 })
         });
@@ -339,12 +421,46 @@ impl AuthView {
         self.device_auth_promise = Some(promise);
     }
 
+    /// Serve the browser login page locally and wait for the code it sends back
+    fn start_web_login(&mut self, settings: &EpicSettings) {
+        self.auth_status = String::new();
+
+        let client = match EpicClient::with_settings(settings) {
+            Ok(client) => client,
+            Err(e) => {
+                self.auth_status = tr!("gui-auth-start-failed", error = e.to_string());
+                return;
+            }
+        };
+        let login = match WebLogin::bind(&client) {
+            Ok(login) => login,
+            Err(e) => {
+                self.auth_status = tr!("gui-auth-start-failed", error = e.to_string());
+                return;
+            }
+        };
+        let url = login.url();
+        let _ = webbrowser::open(&url);
+        self.state = AuthState::WebLogin { url };
+
+        // The listener outlives a cancel until its timeout, but the result is then ignored
+        self.web_login_promise = Some(Promise::spawn_thread("web_login", move || {
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(async move {
+                    let code = login.wait_for_code().await?;
+                    client.exchange_code(&code).await
+                })
+        }));
+    }
+
     fn cancel_authentication(&mut self) {
         self.state = AuthState::Idle;
         self.device_auth_promise = None;
         self.poll_promise = None;
+        self.web_login_promise = None;
         self.verification_url = None;
         self.user_code = None;
-        self.auth_status = "Authentication cancelled".to_string();
+        self.auth_status = tr!("gui-auth-cancelled");
     }
 }
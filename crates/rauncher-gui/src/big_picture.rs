@@ -0,0 +1,205 @@
+use egui::{RichText, ScrollArea, Sense, Vec2};
+
+use rauncher_core::games::InstalledGame;
+use rauncher_core::tr;
+
+use super::gamepad::{PadInput, LONG_PRESS};
+use super::theme::Theme;
+
+const TILE_SIZE: Vec2 = Vec2::new(360.0, 220.0);
+const TILE_SPACING: f32 = 24.0;
+
+/// Fullscreen interface for TVs and handhelds: large tiles of the installed games, most
+/// recently played first. Settings stay out of the way behind a long press.
+#[derive(Default)]
+pub struct BigPictureView {
+    /// Tile picked with a controller
+    selected: usize,
+    /// Controller moves not applied yet, since they need the current layout
+    pad_inputs: Vec<PadInput>,
+    /// A long press already fired for the pointer held down now, so its release isn't a click
+    long_press_fired: bool,
+}
+
+pub enum BigPictureAction {
+    Launch(String),
+    Settings(String),
+    /// Leave for the regular interface
+    Exit,
+}
+
+impl BigPictureView {
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        installed_games: &[InstalledGame],
+    ) -> Option<BigPictureAction> {
+        let theme = Theme::current(ui.ctx());
+        let mut action = None;
+
+        let mut games: Vec<&InstalledGame> = installed_games.iter().collect();
+        games.sort_by(|a, b| {
+            b.last_played
+                .cmp(&a.last_played)
+                .then_with(|| a.app_title.cmp(&b.app_title))
+        });
+
+        ui.horizontal(|ui| {
+            ui.heading(
+                RichText::new(tr!("gui-title"))
+                    .size(34.0)
+                    .strong()
+                    .color(theme.text_strong),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let settings = ui
+                    .add(
+                        egui::Button::new(RichText::new("⚙").size(26.0))
+                            .fill(theme.widget)
+                            .min_size(Vec2::new(56.0, 56.0)),
+                    )
+                    .on_hover_text(tr!("gui-big-picture-exit"));
+                if self.long_pressed(ui, &settings) {
+                    action = Some(BigPictureAction::Exit);
+                }
+            });
+        });
+        ui.add_space(24.0);
+
+        let cards_per_row = ((ui.available_width() + TILE_SPACING) / (TILE_SIZE.x + TILE_SPACING))
+            .floor()
+            .max(1.0) as usize;
+        let scroll_to_selected = !self.pad_inputs.is_empty();
+        for input in std::mem::take(&mut self.pad_inputs) {
+            let Some(last) = games.len().checked_sub(1) else {
+                break;
+            };
+            let current = self.selected.min(last);
+            self.selected = match input {
+                PadInput::Left => current.saturating_sub(1),
+                PadInput::Right => (current + 1).min(last),
+                PadInput::Up => current.checked_sub(cards_per_row).unwrap_or(current),
+                PadInput::Down => (current + cards_per_row).min(last),
+                PadInput::Confirm => {
+                    action = Some(BigPictureAction::Launch(games[current].id()));
+                    current
+                }
+                PadInput::Hold => {
+                    action = Some(BigPictureAction::Settings(games[current].id()));
+                    current
+                }
+                PadInput::Back => current,
+            };
+        }
+
+        if games.is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(120.0);
+                ui.label(
+                    RichText::new(tr!("gui-big-picture-empty"))
+                        .size(26.0)
+                        .color(theme.text_weak),
+                );
+            });
+        } else {
+            let selected = self.selected.min(games.len() - 1);
+            ScrollArea::vertical()
+                .max_height(ui.available_height() - 48.0)
+                .show(ui, |ui| {
+                    for (row, row_games) in games.chunks(cards_per_row).enumerate() {
+                        ui.horizontal(|ui| {
+                            for (column, game) in row_games.iter().enumerate() {
+                                let index = row * cards_per_row + column;
+                                let tile = self.tile(ui, game, index == selected);
+                                if index == selected && scroll_to_selected {
+                                    tile.scroll_to_me(None);
+                                }
+
+                                if self.long_pressed(ui, &tile) {
+                                    action = Some(BigPictureAction::Settings(game.id()));
+                                } else if tile.clicked() && !self.long_press_fired {
+                                    self.selected = index;
+                                    action = Some(BigPictureAction::Launch(game.id()));
+                                }
+                                ui.add_space(TILE_SPACING);
+                            }
+                        });
+                        ui.add_space(TILE_SPACING);
+                    }
+                });
+        }
+
+        ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+            ui.label(
+                RichText::new(tr!("gui-big-picture-hint"))
+                    .size(16.0)
+                    .color(theme.text_weak),
+            );
+        });
+
+        if !ui.input(|i| i.pointer.any_down()) {
+            self.long_press_fired = false;
+        }
+
+        action
+    }
+
+    /// Move the controller selection
+    pub fn navigate(&mut self, input: PadInput) {
+        self.pad_inputs.push(input);
+    }
+
+    fn tile(&self, ui: &mut egui::Ui, game: &InstalledGame, selected: bool) -> egui::Response {
+        let theme = Theme::current(ui.ctx());
+        let (rect, response) = ui.allocate_exact_size(TILE_SIZE, Sense::click());
+        let painter = ui.painter();
+
+        let fill = if response.hovered() || selected {
+            theme.surface_hover
+        } else {
+            theme.surface
+        };
+        painter.rect_filled(rect, 10.0, fill);
+        if selected {
+            painter.rect_stroke(rect, 10.0, egui::Stroke::new(4.0, theme.accent));
+        }
+
+        painter.text(
+            rect.center() - Vec2::new(0.0, 14.0),
+            egui::Align2::CENTER_CENTER,
+            &game.app_title,
+            egui::FontId::proportional(28.0),
+            theme.text_strong,
+        );
+        let played = match game.last_played {
+            Some(time) => tr!(
+                "gui-last-played",
+                date = time.format("%Y-%m-%d").to_string()
+            ),
+            None => tr!("gui-never-played"),
+        };
+        painter.text(
+            rect.center() + Vec2::new(0.0, 30.0),
+            egui::Align2::CENTER_CENTER,
+            played,
+            egui::FontId::proportional(16.0),
+            theme.text_weak,
+        );
+
+        response
+    }
+
+    /// Whether the pointer has been held on `response` for a long press, firing once per press
+    fn long_pressed(&mut self, ui: &egui::Ui, response: &egui::Response) -> bool {
+        if self.long_press_fired || !response.is_pointer_button_down_on() {
+            return false;
+        }
+
+        let held = ui.input(|i| i.pointer.press_start_time().map(|start| i.time - start));
+        if held.is_some_and(|held| held >= LONG_PRESS.as_secs_f64()) {
+            self.long_press_fired = true;
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,62 @@
+use egui::RichText;
+
+use rauncher_core::games::CrashReport;
+use rauncher_core::tr;
+
+/// Lines of game output shown below the report summary
+const OUTPUT_LINES: usize = 40;
+
+/// A crash report read once when opened, shown in a modal-style window
+pub struct CrashReportDialog {
+    report: CrashReport,
+    summary: String,
+    output: String,
+}
+
+impl CrashReportDialog {
+    pub fn new(report: CrashReport) -> Self {
+        let summary = report.summary().unwrap_or_else(|e| e.to_string());
+        let output = report
+            .output_tail(OUTPUT_LINES)
+            .unwrap_or_else(|e| e.to_string());
+
+        Self {
+            report,
+            summary,
+            output,
+        }
+    }
+
+    /// Returns true once the dialog is closed
+    pub fn show(&self, ctx: &egui::Context) -> bool {
+        let mut closed = false;
+
+        egui::Window::new(tr!("gui-crash-title", app = self.report.app_title.as_str()))
+            .collapsible(false)
+            .resizable(true)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(&self.summary).monospace());
+                ui.add_space(10.0);
+
+                ui.label(RichText::new(tr!("gui-crash-output")).size(16.0).strong());
+                egui::ScrollArea::vertical()
+                    .max_height(250.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new(&self.output).monospace().size(11.0));
+                    });
+                ui.add_space(10.0);
+
+                ui.label(tr!(
+                    "gui-crash-saved",
+                    path = self.report.dir.display().to_string()
+                ));
+                if ui.button(tr!("gui-close")).clicked() {
+                    closed = true;
+                }
+            });
+
+        closed
+    }
+}
@@ -0,0 +1,361 @@
+use egui::{RichText, Vec2};
+
+use rauncher_core::api::{BuildInfo, Game};
+use rauncher_core::collections::GameTags;
+use rauncher_core::providers;
+use rauncher_core::tr;
+
+use crate::theme::Theme;
+
+pub struct GameCard;
+
+/// Where a card's game stands, which decides its buttons and highlight
+pub struct CardState {
+    pub installed: bool,
+    /// Installed on a drive that isn't mounted
+    pub unavailable: bool,
+    pub installing: bool,
+    /// Launched from this window and still running
+    pub running: bool,
+    /// A newer build of the installed game
+    pub update: Option<BuildInfo>,
+    /// Picked with a controller
+    pub selected: bool,
+}
+
+impl GameCard {
+    pub fn show(
+        ui: &mut egui::Ui,
+        game: &Game,
+        state: CardState,
+        tags: &GameTags,
+        categories: &[String],
+        new_category: &mut String,
+    ) -> Option<GameCardAction> {
+        let theme = Theme::current(ui.ctx());
+        let mut action = None;
+
+        egui::Frame::none()
+            .fill(theme.surface)
+            .stroke(if state.selected {
+                egui::Stroke::new(2.0, theme.accent)
+            } else {
+                egui::Stroke::new(1.0, theme.widget)
+            })
+            .rounding(egui::Rounding::same(6.0))
+            .inner_margin(egui::Margin::same(0.0))
+            .show(ui, |ui| {
+                ui.set_min_size(Vec2::new(280.0, 340.0));
+                ui.set_max_size(Vec2::new(280.0, 340.0));
+
+                ui.vertical(|ui| {
+                    // Game image placeholder with gradient effect
+                    let (rect, response) =
+                        ui.allocate_exact_size(Vec2::new(280.0, 200.0), egui::Sense::hover());
+
+                    // Create a gradient background for the image placeholder
+                    let painter = ui.painter();
+                    let image_rounding = egui::Rounding {
+                        nw: 6.0,
+                        ne: 6.0,
+                        sw: 0.0,
+                        se: 0.0,
+                    };
+
+                    painter.rect_filled(rect, image_rounding, theme.surface_hover);
+
+                    // Add a subtle overlay gradient
+                    if response.hovered() {
+                        painter.rect_filled(
+                            rect,
+                            image_rounding,
+                            theme.accent.linear_multiply(0.08),
+                        );
+                    }
+
+                    // Game title on image
+                    painter.text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        &game.app_title,
+                        egui::FontId::proportional(16.0),
+                        theme.text_strong,
+                    );
+
+                    ui.add_space(15.0);
+
+                    // Content area with padding
+                    ui.add_space(0.0);
+                    ui.horizontal(|ui| {
+                        ui.add_space(15.0);
+                        ui.vertical(|ui| {
+                            // Game title
+                            ui.label(
+                                RichText::new(&game.app_title)
+                                    .size(16.0)
+                                    .strong()
+                                    .color(theme.text_strong),
+                            );
+
+                            ui.add_space(5.0);
+
+                            // Store badge and version info
+                            ui.horizontal(|ui| {
+                                egui::Frame::none()
+                                    .fill(theme.widget)
+                                    .rounding(egui::Rounding::same(3.0))
+                                    .inner_margin(egui::Margin::symmetric(6.0, 2.0))
+                                    .show(ui, |ui| {
+                                        ui.label(
+                                            RichText::new(providers::display_name(&game.provider))
+                                                .size(11.0)
+                                                .color(theme.text_weak),
+                                        );
+                                    });
+
+                                ui.label(
+                                    RichText::new(format!("v{}", &game.app_version))
+                                        .size(12.0)
+                                        .color(theme.text_weak),
+                                );
+
+                                if let Some(build) = &state.update {
+                                    egui::Frame::none()
+                                        .fill(theme.warning)
+                                        .rounding(egui::Rounding::same(3.0))
+                                        .inner_margin(egui::Margin::symmetric(6.0, 2.0))
+                                        .show(ui, |ui| {
+                                            ui.label(
+                                                RichText::new(tr!("gui-update-available"))
+                                                    .size(11.0)
+                                                    .strong()
+                                                    .color(theme.on_accent),
+                                            );
+                                        })
+                                        .response
+                                        .on_hover_text(Self::update_hint(build));
+                                }
+
+                                let (star, color, hint) = if tags.favorite {
+                                    ("★", theme.warning, tr!("gui-unfavorite"))
+                                } else {
+                                    ("☆", theme.text_weak, tr!("gui-favorite"))
+                                };
+                                let star_button =
+                                    egui::Button::new(RichText::new(star).size(15.0).color(color))
+                                        .frame(false);
+                                if ui.add(star_button).on_hover_text(hint).clicked() {
+                                    action = Some(GameCardAction::Tag(
+                                        game.id(),
+                                        TagChange::Favorite(!tags.favorite),
+                                    ));
+                                }
+
+                                ui.menu_button("⋯", |ui| {
+                                    let change = Self::tag_menu(ui, tags, categories, new_category);
+                                    if let Some(change) = change {
+                                        action = Some(GameCardAction::Tag(game.id(), change));
+                                    }
+                                })
+                                .response
+                                .on_hover_text(tr!("gui-game-menu"));
+                            });
+
+                            ui.add_space(15.0);
+
+                            // Action buttons
+                            ui.horizontal(|ui| {
+                                if state.installed {
+                                    if state.unavailable {
+                                        ui.add_enabled_ui(false, |ui| {
+                                            let unavailable_button = egui::Button::new(
+                                                RichText::new(tr!("gui-drive-not-mounted"))
+                                                    .size(13.0)
+                                                    .color(theme.text_weak),
+                                            )
+                                            .fill(theme.widget)
+                                            .min_size(Vec2::new(90.0, 36.0));
+
+                                            ui.add(unavailable_button).on_disabled_hover_text(tr!(
+                                                "gui-drive-not-mounted-hint"
+                                            ));
+                                        });
+                                    } else if state.running {
+                                        let stop_button = egui::Button::new(
+                                            RichText::new(tr!("gui-stop"))
+                                                .size(15.0)
+                                                .strong()
+                                                .color(theme.on_accent),
+                                        )
+                                        .fill(theme.error)
+                                        .min_size(Vec2::new(90.0, 36.0));
+
+                                        if ui.add(stop_button).clicked() {
+                                            action = Some(GameCardAction::Stop(game.id()));
+                                        }
+                                    } else {
+                                        // Play button - Epic blue
+                                        let play_button = egui::Button::new(
+                                            RichText::new(tr!("gui-play"))
+                                                .size(15.0)
+                                                .strong()
+                                                .color(theme.on_accent),
+                                        )
+                                        .fill(theme.accent)
+                                        .min_size(Vec2::new(90.0, 36.0));
+
+                                        if ui.add(play_button).clicked() {
+                                            action = Some(GameCardAction::Launch(game.id()));
+                                        }
+                                    }
+
+                                    ui.add_space(5.0);
+
+                                    // Uninstall button
+                                    let uninstall_button = egui::Button::new(
+                                        RichText::new(tr!("gui-uninstall")).size(13.0),
+                                    )
+                                    .fill(theme.widget)
+                                    .min_size(Vec2::new(90.0, 36.0));
+
+                                    if ui.add(uninstall_button).clicked() {
+                                        action = Some(GameCardAction::Uninstall(game.id()));
+                                    }
+
+                                    // Per-game settings
+                                    let settings_button =
+                                        egui::Button::new(RichText::new("⚙").size(15.0))
+                                            .fill(theme.widget)
+                                            .min_size(Vec2::new(36.0, 36.0));
+
+                                    if ui
+                                        .add(settings_button)
+                                        .on_hover_text(tr!("gui-settings"))
+                                        .clicked()
+                                    {
+                                        action = Some(GameCardAction::Settings(game.id()));
+                                    }
+                                } else if state.installing {
+                                    ui.add_enabled_ui(false, |ui| {
+                                        let installing_button = egui::Button::new(
+                                            RichText::new(tr!("gui-installing"))
+                                                .size(15.0)
+                                                .color(theme.text_weak),
+                                        )
+                                        .fill(theme.widget)
+                                        .min_size(Vec2::new(200.0, 36.0));
+
+                                        let _ = ui.add(installing_button);
+                                    });
+                                } else {
+                                    // Install button - Epic blue
+                                    let install_button = egui::Button::new(
+                                        RichText::new(tr!("gui-get"))
+                                            .size(15.0)
+                                            .strong()
+                                            .color(theme.on_accent),
+                                    )
+                                    .fill(theme.accent)
+                                    .min_size(Vec2::new(200.0, 36.0));
+
+                                    if ui.add(install_button).clicked() {
+                                        action = Some(GameCardAction::Install(game.id()));
+                                    }
+                                }
+                            });
+                        });
+                    });
+                });
+            });
+
+        action
+    }
+
+    /// The new build's version, release date and notes, for the update badge
+    fn update_hint(build: &BuildInfo) -> String {
+        let mut hint = tr!(
+            "gui-update-available-hint",
+            version = build.version.as_str()
+        );
+        if let Some(date) = build.date {
+            hint.push('\n');
+            hint.push_str(&tr!(
+                "gui-update-released",
+                date = date.format("%Y-%m-%d").to_string()
+            ));
+        }
+        if let Some(notes) = &build.release_notes {
+            hint.push_str("\n\n");
+            hint.push_str(notes);
+        }
+        hint
+    }
+
+    /// Hiding the game and picking its categories, from the card's ⋯ menu
+    fn tag_menu(
+        ui: &mut egui::Ui,
+        tags: &GameTags,
+        categories: &[String],
+        new_category: &mut String,
+    ) -> Option<TagChange> {
+        let mut change = None;
+
+        let hide_label = if tags.hidden {
+            tr!("gui-unhide")
+        } else {
+            tr!("gui-hide")
+        };
+        if ui.button(hide_label).clicked() {
+            change = Some(TagChange::Hidden(!tags.hidden));
+            ui.close_menu();
+        }
+
+        ui.separator();
+        ui.label(RichText::new(tr!("gui-categories")).size(12.0).strong());
+        for category in categories {
+            let mut member = tags.has_category(category);
+            if ui.checkbox(&mut member, category).changed() {
+                change = Some(if member {
+                    TagChange::AddCategory(category.clone())
+                } else {
+                    TagChange::RemoveCategory(category.clone())
+                });
+            }
+        }
+
+        ui.horizontal(|ui| {
+            let edit = ui.add(
+                egui::TextEdit::singleline(new_category)
+                    .hint_text(tr!("gui-new-category"))
+                    .desired_width(120.0),
+            );
+            let entered = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let add = ui.add_enabled(
+                !new_category.trim().is_empty(),
+                egui::Button::new(tr!("gui-add-category")),
+            );
+            if (add.clicked() || entered) && !new_category.trim().is_empty() {
+                change = Some(TagChange::AddCategory(std::mem::take(new_category)));
+            }
+        });
+
+        change
+    }
+}
+
+pub enum GameCardAction {
+    Install(String),
+    Launch(String),
+    Stop(String),
+    Uninstall(String),
+    Settings(String),
+    /// Change how the user organized the game
+    Tag(String, TagChange),
+}
+
+pub enum TagChange {
+    Favorite(bool),
+    Hidden(bool),
+    AddCategory(String),
+    RemoveCategory(String),
+}
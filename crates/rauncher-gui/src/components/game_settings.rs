@@ -0,0 +1,248 @@
+use egui::RichText;
+use std::path::PathBuf;
+
+use rauncher_core::config::{GameSettings, SandboxTool};
+use rauncher_core::runners;
+use rauncher_core::tr;
+
+use crate::theme::Theme;
+
+/// Editable copy of a game's settings shown in a modal-style window
+pub struct GameSettingsDialog {
+    pub app_name: String,
+    pub settings: GameSettings,
+    wine_path: String,
+    executable: String,
+    working_dir: String,
+    shader_cache_url: String,
+    /// Names of the installed runners offered in the picker
+    runners: Vec<String>,
+    /// Environment variables as editable key/value rows
+    env: Vec<(String, String)>,
+}
+
+pub enum GameSettingsAction {
+    Save(String, Box<GameSettings>),
+    Cancel,
+}
+
+impl GameSettingsDialog {
+    pub fn new(app_name: String, settings: GameSettings) -> Self {
+        let path_text = |path: &Option<PathBuf>| {
+            path.as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default()
+        };
+
+        Self {
+            app_name,
+            wine_path: path_text(&settings.wine),
+            executable: path_text(&settings.executable),
+            working_dir: path_text(&settings.working_dir),
+            shader_cache_url: settings.shader_cache_url.clone().unwrap_or_default(),
+            runners: runners::installed()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|runner| runner.name)
+                .collect(),
+            env: settings.env.clone().into_iter().collect(),
+            settings,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<GameSettingsAction> {
+        let theme = Theme::current(ctx);
+        let mut action = None;
+
+        egui::Window::new(tr!("gui-settings-title", app = self.app_name.as_str()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(tr!("gui-launch-wrappers"))
+                        .size(16.0)
+                        .strong(),
+                );
+                ui.add_space(5.0);
+
+                ui.add_enabled_ui(cfg!(target_os = "linux"), |ui| {
+                    ui.checkbox(&mut self.settings.gamemode, tr!("gui-gamemode"));
+                    ui.checkbox(&mut self.settings.mangohud, tr!("gui-mangohud"));
+                    ui.checkbox(&mut self.settings.gamescope, tr!("gui-gamescope"));
+
+                    ui.add_enabled_ui(self.settings.gamescope, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("gui-gamescope-options"));
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.settings.gamescope_args)
+                                    .hint_text("-W 2560 -H 1440 -f")
+                                    .desired_width(200.0),
+                            );
+                        });
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("gui-sandbox"));
+                        let name = |tool: Option<SandboxTool>| {
+                            tool.map(|tool| tool.as_str().to_string())
+                                .unwrap_or_else(|| tr!("gui-sandbox-none"))
+                        };
+                        egui::ComboBox::from_id_salt("sandbox")
+                            .selected_text(name(self.settings.sandbox))
+                            .show_ui(ui, |ui| {
+                                let tools = [SandboxTool::Bwrap, SandboxTool::Firejail];
+                                for tool in [None].into_iter().chain(tools.map(Some)) {
+                                    let sandbox = &mut self.settings.sandbox;
+                                    ui.selectable_value(sandbox, tool, name(tool));
+                                }
+                            });
+                    });
+                    ui.add_enabled_ui(self.settings.sandbox.is_some(), |ui| {
+                        let network = &mut self.settings.sandbox_network;
+                        ui.checkbox(network, tr!("gui-sandbox-network"));
+                    });
+                });
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label(tr!("gui-launch-args"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.settings.launch_args)
+                            .hint_text("-windowed -dx11")
+                            .desired_width(200.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("gui-launch-executable"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.executable)
+                            .hint_text(tr!("gui-launch-executable-hint"))
+                            .desired_width(200.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("gui-launch-working-dir"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.working_dir)
+                            .hint_text(tr!("gui-launch-working-dir-hint"))
+                            .desired_width(200.0),
+                    );
+                });
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr!("gui-compatibility")).size(16.0).strong());
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(tr!("gui-runner"));
+                    let selected = self
+                        .settings
+                        .runner
+                        .clone()
+                        .unwrap_or_else(|| tr!("gui-runner-none"));
+                    egui::ComboBox::from_id_salt("runner")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.settings.runner,
+                                None,
+                                tr!("gui-runner-none"),
+                            );
+                            for runner in &self.runners {
+                                ui.selectable_value(
+                                    &mut self.settings.runner,
+                                    Some(runner.clone()),
+                                    runner,
+                                );
+                            }
+                        });
+                });
+                if self.runners.is_empty() {
+                    ui.label(RichText::new(tr!("gui-runner-hint")).color(theme.text_weak));
+                }
+
+                ui.add_enabled_ui(self.settings.runner.is_none(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("gui-wine"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.wine_path)
+                                .hint_text(tr!("gui-wine-hint"))
+                                .desired_width(250.0),
+                        );
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("gui-shader-cache-url"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.shader_cache_url)
+                            .hint_text(tr!("gui-shader-cache-url-hint"))
+                            .desired_width(250.0),
+                    );
+                });
+
+                ui.add_space(10.0);
+                ui.label(RichText::new(tr!("gui-environment")).size(16.0).strong());
+                ui.add_space(5.0);
+
+                let mut remove = None;
+                for (index, (key, value)) in self.env.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(key)
+                                .hint_text("DXVK_HUD")
+                                .desired_width(140.0),
+                        );
+                        ui.label("=");
+                        ui.add(
+                            egui::TextEdit::singleline(value)
+                                .hint_text("fps")
+                                .desired_width(160.0),
+                        );
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                }
+                if let Some(index) = remove {
+                    self.env.remove(index);
+                }
+                if ui.button(tr!("gui-env-add")).clicked() {
+                    self.env.push((String::new(), String::new()));
+                }
+
+                ui.add_space(15.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("gui-save")).clicked() {
+                        let mut settings = self.settings.clone();
+                        settings.wine = optional_path(&self.wine_path);
+                        settings.executable = optional_path(&self.executable);
+                        settings.working_dir = optional_path(&self.working_dir);
+                        let url = self.shader_cache_url.trim();
+                        settings.shader_cache_url = (!url.is_empty()).then(|| url.to_string());
+                        settings.env = self
+                            .env
+                            .iter()
+                            .filter(|(key, _)| !key.trim().is_empty())
+                            .map(|(key, value)| (key.trim().to_string(), value.clone()))
+                            .collect();
+                        action = Some(GameSettingsAction::Save(
+                            self.app_name.clone(),
+                            Box::new(settings),
+                        ));
+                    }
+                    if ui.button(tr!("gui-cancel")).clicked() {
+                        action = Some(GameSettingsAction::Cancel);
+                    }
+                });
+            });
+
+        action
+    }
+}
+
+fn optional_path(text: &str) -> Option<PathBuf> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| PathBuf::from(text))
+}
@@ -0,0 +1,215 @@
+use egui::RichText;
+
+use rauncher_core::config::{Config, NotificationSettings};
+use rauncher_core::tr;
+
+use crate::theme::{self, Theme};
+
+pub struct Header;
+
+impl Header {
+    pub fn show(
+        ui: &mut egui::Ui,
+        is_authenticated: bool,
+        active_profile: Option<&str>,
+        profiles: &[String],
+        config: &Config,
+        page: Page,
+        offline: bool,
+    ) -> Option<HeaderAction> {
+        let theme = Theme::current(ui.ctx());
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            // Logo/Title with Epic Games-inspired styling
+            ui.heading(
+                RichText::new(tr!("gui-title"))
+                    .size(22.0)
+                    .strong()
+                    .color(theme.text_strong),
+            );
+
+            if offline {
+                ui.add_space(10.0);
+                ui.label(
+                    RichText::new(tr!("gui-offline"))
+                        .size(14.0)
+                        .strong()
+                        .color(theme.warning),
+                )
+                .on_hover_text(tr!("gui-offline-hint"));
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if is_authenticated {
+                    if ui
+                        .button(RichText::new(tr!("gui-logout")).size(14.0))
+                        .clicked()
+                    {
+                        action = Some(HeaderAction::Logout);
+                    }
+
+                    ui.add_space(5.0);
+
+                    if ui
+                        .button(RichText::new("📺").size(14.0))
+                        .on_hover_text(tr!("gui-big-picture"))
+                        .clicked()
+                    {
+                        action = Some(HeaderAction::BigPicture);
+                    }
+
+                    // Right to left, so the pages are listed last first
+                    let pages = [
+                        (Page::Stats, format!("📊 {}", tr!("gui-stats"))),
+                        (Page::History, format!("📜 {}", tr!("gui-history"))),
+                        (Page::Storage, format!("💾 {}", tr!("gui-storage"))),
+                        (Page::Downloads, format!("⬇ {}", tr!("gui-downloads"))),
+                        (Page::Store, format!("🛒 {}", tr!("gui-store"))),
+                        (Page::Library, tr!("gui-library")),
+                    ];
+                    for (target, label) in pages {
+                        let selected = page == target;
+                        if ui
+                            .selectable_label(selected, RichText::new(label).size(14.0))
+                            .clicked()
+                            && !selected
+                        {
+                            action = Some(HeaderAction::ShowPage(target));
+                        }
+                    }
+
+                    ui.add_space(5.0);
+
+                    // Which events show a desktop notification
+                    let notifications = &config.notifications;
+                    let mut settings = notifications.clone();
+                    ui.menu_button(RichText::new("🔔").size(14.0), |ui| {
+                        ui.label(RichText::new(tr!("gui-notifications")).strong());
+                        ui.checkbox(
+                            &mut settings.install_finished,
+                            tr!("gui-notify-install-finished"),
+                        );
+                        ui.checkbox(
+                            &mut settings.update_available,
+                            tr!("gui-notify-update-available"),
+                        );
+                        ui.checkbox(
+                            &mut settings.cloud_sync_failed,
+                            tr!("gui-notify-cloud-sync-failed"),
+                        );
+                        ui.checkbox(&mut settings.wishlist, tr!("gui-notify-wishlist"));
+                    });
+                    if settings != *notifications {
+                        action = Some(HeaderAction::SetNotifications(settings));
+                    }
+
+                    ui.add_space(5.0);
+
+                    ui.menu_button(RichText::new("🎨").size(14.0), |ui| {
+                        if let Some(appearance) = Self::appearance_menu(ui, config) {
+                            action = Some(appearance);
+                            ui.close_menu();
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("gui-appearance"));
+
+                    ui.add_space(5.0);
+
+                    // Account menu for switching between stored profiles
+                    let account = active_profile.map_or_else(|| tr!("gui-account"), str::to_string);
+                    let label = format!("👤 {}", account);
+                    ui.menu_button(RichText::new(label).size(14.0), |ui| {
+                        for name in profiles {
+                            let is_active = active_profile == Some(name.as_str());
+                            if ui.selectable_label(is_active, name).clicked() {
+                                if !is_active {
+                                    action = Some(HeaderAction::SwitchProfile(name.clone()));
+                                }
+                                ui.close_menu();
+                            }
+                        }
+
+                        ui.separator();
+
+                        if ui.button(tr!("gui-add-account")).clicked() {
+                            action = Some(HeaderAction::AddAccount);
+                            ui.close_menu();
+                        }
+                    });
+                }
+            });
+        });
+
+        action
+    }
+
+    /// Theme and UI scale pickers
+    fn appearance_menu(ui: &mut egui::Ui, config: &Config) -> Option<HeaderAction> {
+        let mut action = None;
+
+        ui.label(RichText::new(tr!("gui-theme")).strong());
+        let current = config.theme.as_deref().unwrap_or("dark");
+        for name in theme::available() {
+            let selected = name == current;
+            if ui.selectable_label(selected, &name).clicked() && !selected {
+                action = Some(HeaderAction::SetTheme(name));
+            }
+        }
+
+        ui.separator();
+        ui.label(RichText::new(tr!("gui-ui-scale")).strong());
+        let system = ui.ctx().native_pixels_per_point().unwrap_or(1.0);
+        let automatic = tr!("gui-ui-scale-auto", percent = percent(system));
+        let mut scales = vec![(None, automatic)];
+        scales.extend(
+            UI_SCALES
+                .iter()
+                .map(|&scale| (Some(scale), format!("{}%", percent(scale)))),
+        );
+        for (scale, label) in scales {
+            let selected = config.ui_scale == scale;
+            if ui.selectable_label(selected, label).clicked() && !selected {
+                action = Some(HeaderAction::SetUiScale(scale));
+            }
+        }
+
+        action
+    }
+}
+
+/// Scales offered in the appearance menu, from handheld to 4K screens
+const UI_SCALES: [f32; 8] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0];
+
+fn percent(scale: f32) -> u32 {
+    (scale * 100.0).round() as u32
+}
+
+pub enum HeaderAction {
+    Logout,
+    SwitchProfile(String),
+    AddAccount,
+    SetNotifications(NotificationSettings),
+    ShowPage(Page),
+    /// Switch to the fullscreen interface for TVs and handhelds
+    BigPicture,
+    /// Draw with the theme of this name from now on
+    SetTheme(String),
+    /// Scale the interface by this factor, or follow the system's display scaling
+    SetUiScale(Option<f32>),
+}
+
+/// The views reachable from the header once logged in
+#[derive(Clone, Copy, PartialEq)]
+pub enum Page {
+    Library,
+    Downloads,
+    Storage,
+    /// Past installs, updates and uninstalls
+    History,
+    /// Library size, play time and downloads
+    Stats,
+    /// Epic Games Store listings
+    Store,
+}
@@ -0,0 +1,18 @@
+// GUI Components module
+mod crash_report;
+mod game_card;
+mod game_settings;
+mod header;
+mod prompt_dialog;
+mod search_bar;
+mod status_bar;
+mod toasts;
+
+pub use crash_report::CrashReportDialog;
+pub use game_card::{CardState, GameCard, GameCardAction, TagChange};
+pub use game_settings::{GameSettingsAction, GameSettingsDialog};
+pub use header::{Header, HeaderAction, Page};
+pub use prompt_dialog::PromptDialog;
+pub use search_bar::{GameFilter, SearchBar};
+pub use status_bar::{StatusBar, StatusBarAction};
+pub use toasts::Toasts;
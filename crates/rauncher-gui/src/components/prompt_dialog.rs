@@ -0,0 +1,90 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+
+use rauncher_core::prompt::{Prompter, Question};
+use rauncher_core::tr;
+
+type Request = (Question, Sender<&'static str>);
+
+/// Prompter for background operations: sends each question to the UI thread and blocks
+/// until it has been answered in a `PromptDialog`
+pub struct DialogPrompter {
+    requests: Mutex<Sender<Request>>,
+}
+
+impl Prompter for DialogPrompter {
+    fn ask(&self, question: &Question) -> &'static str {
+        let (reply, answer) = mpsc::channel();
+        let sent = self
+            .requests
+            .lock()
+            .unwrap()
+            .send((question.clone(), reply));
+
+        // The window closing drops the dialog; fall back to the safe answer
+        match sent {
+            Ok(()) => answer.recv().unwrap_or_else(|_| question.default_answer()),
+            Err(_) => question.default_answer(),
+        }
+    }
+}
+
+/// Shows the questions asked through its `DialogPrompter`s, one at a time
+pub struct PromptDialog {
+    requests: Sender<Request>,
+    pending: Receiver<Request>,
+    current: Option<Request>,
+}
+
+impl Default for PromptDialog {
+    fn default() -> Self {
+        let (requests, pending) = mpsc::channel();
+        Self {
+            requests,
+            pending,
+            current: None,
+        }
+    }
+}
+
+impl PromptDialog {
+    pub fn prompter(&self) -> DialogPrompter {
+        DialogPrompter {
+            requests: Mutex::new(self.requests.clone()),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if self.current.is_none() {
+            self.current = self.pending.try_recv().ok();
+        }
+        let Some((question, _)) = &self.current else {
+            return;
+        };
+
+        let mut chosen = None;
+        egui::Window::new(tr!("gui-question"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(&question.text);
+                ui.add_space(15.0);
+
+                ui.horizontal(|ui| {
+                    for (id, label) in question.choices() {
+                        if ui.button(label).clicked() {
+                            chosen = Some(id);
+                        }
+                    }
+                });
+            });
+
+        if let Some(answer) = chosen {
+            if let Some((_, reply)) = self.current.take() {
+                // The operation may have given up waiting; nothing left to answer then
+                let _ = reply.send(answer);
+            }
+        }
+    }
+}
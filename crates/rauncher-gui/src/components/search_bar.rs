@@ -0,0 +1,112 @@
+use egui::RichText;
+
+use rauncher_core::collections::Filter;
+use rauncher_core::providers;
+use rauncher_core::tr;
+
+#[derive(Clone, PartialEq)]
+pub enum GameFilter {
+    All,
+    Installed,
+}
+
+pub struct SearchBar;
+
+impl SearchBar {
+    pub fn show(
+        ui: &mut egui::Ui,
+        search_query: &mut String,
+        filter: &mut GameFilter,
+        provider: &mut Option<String>,
+        stores: &[String],
+        tag_filter: &mut Filter,
+        categories: &[String],
+    ) {
+        ui.horizontal(|ui| {
+            ui.heading(RichText::new(tr!("gui-library")).size(20.0).strong());
+            ui.add_space(20.0);
+
+            // Search box with enhanced styling
+            ui.label(RichText::new("🔍").size(16.0));
+            ui.add_space(5.0);
+            let search_edit = egui::TextEdit::singleline(search_query)
+                .hint_text(tr!("gui-search-hint"))
+                .desired_width(250.0);
+            ui.add(search_edit);
+
+            ui.add_space(20.0);
+
+            // Filters with Epic-style buttons
+            let all_selected = *filter == GameFilter::All;
+            if ui
+                .selectable_label(
+                    all_selected,
+                    RichText::new(tr!("gui-filter-all")).size(14.0),
+                )
+                .clicked()
+            {
+                *filter = GameFilter::All;
+            }
+
+            ui.add_space(5.0);
+
+            let installed_selected = *filter == GameFilter::Installed;
+            if ui
+                .selectable_label(
+                    installed_selected,
+                    RichText::new(tr!("gui-filter-installed")).size(14.0),
+                )
+                .clicked()
+            {
+                *filter = GameFilter::Installed;
+            }
+
+            // Store filter, only useful once more than one store is in the library
+            if stores.len() > 1 {
+                ui.add_space(20.0);
+                let selected = provider.as_deref().map_or_else(
+                    || tr!("gui-all-stores"),
+                    |store| providers::display_name(store).to_string(),
+                );
+                egui::ComboBox::from_id_salt("store_filter")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(provider, None, tr!("gui-all-stores"));
+                        for store in stores {
+                            ui.selectable_value(
+                                provider,
+                                Some(store.clone()),
+                                providers::display_name(store),
+                            );
+                        }
+                    });
+            }
+        });
+
+        // Chips for favorites, the user's categories and hidden games
+        ui.add_space(8.0);
+        ui.horizontal_wrapped(|ui| {
+            ui.toggle_value(
+                &mut tag_filter.favorites,
+                RichText::new(tr!("gui-filter-favorites")).size(13.0),
+            );
+            for category in categories {
+                let selected = tag_filter.category.as_ref() == Some(category);
+                if ui
+                    .selectable_label(selected, RichText::new(category).size(13.0))
+                    .clicked()
+                {
+                    tag_filter.category = if selected {
+                        None
+                    } else {
+                        Some(category.clone())
+                    };
+                }
+            }
+            ui.toggle_value(
+                &mut tag_filter.hidden,
+                RichText::new(tr!("gui-filter-hidden")).size(13.0),
+            );
+        });
+    }
+}
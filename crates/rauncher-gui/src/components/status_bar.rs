@@ -0,0 +1,52 @@
+use egui::RichText;
+
+use rauncher_core::tr;
+
+use crate::theme::Theme;
+
+pub struct StatusBar;
+
+pub enum StatusBarAction {
+    Clear,
+    ViewReport,
+}
+
+impl StatusBar {
+    /// Show `message`, with a button opening the crash report when `has_report` is set
+    pub fn show(ui: &mut egui::Ui, message: &str, has_report: bool) -> Option<StatusBarAction> {
+        let theme = Theme::current(ui.ctx());
+        let mut action = None;
+
+        if !message.is_empty() {
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(message)
+                        .size(13.0)
+                        .color(if message.starts_with('✓') {
+                            theme.success
+                        } else if message.starts_with('✗') {
+                            theme.error
+                        } else {
+                            theme.text_weak
+                        }),
+                );
+                if has_report
+                    && ui
+                        .button(RichText::new(tr!("gui-view-report")).size(12.0))
+                        .clicked()
+                {
+                    action = Some(StatusBarAction::ViewReport);
+                }
+                if ui
+                    .button(RichText::new(tr!("gui-clear")).size(12.0))
+                    .clicked()
+                {
+                    action = Some(StatusBarAction::Clear);
+                }
+            });
+        }
+
+        action
+    }
+}
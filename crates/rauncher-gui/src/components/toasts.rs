@@ -0,0 +1,96 @@
+use egui::{Align2, RichText};
+use std::time::{Duration, Instant};
+
+use rauncher_core::tr;
+
+use crate::theme::Theme;
+
+/// How long a toast stays up; failures stay longer so they can be read
+const SUCCESS_DURATION: Duration = Duration::from_secs(5);
+const ERROR_DURATION: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, PartialEq)]
+enum ToastKind {
+    Success,
+    Error,
+}
+
+struct Toast {
+    kind: ToastKind,
+    text: String,
+    shown_at: Instant,
+}
+
+/// Short-lived notifications stacked in the bottom-right corner of the window
+#[derive(Default)]
+pub struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn success(&mut self, text: String) {
+        self.push(ToastKind::Success, text);
+    }
+
+    pub fn error(&mut self, text: String) {
+        self.push(ToastKind::Error, text);
+    }
+
+    fn push(&mut self, kind: ToastKind, text: String) {
+        self.toasts.push(Toast {
+            kind,
+            text,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Draw the toasts still up, dropping expired and dismissed ones
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let theme = Theme::current(ctx);
+        self.toasts.retain(|toast| {
+            let duration = match toast.kind {
+                ToastKind::Success => SUCCESS_DURATION,
+                ToastKind::Error => ERROR_DURATION,
+            };
+            toast.shown_at.elapsed() < duration
+        });
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -48.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (index, toast) in self.toasts.iter().enumerate() {
+                    let accent = match toast.kind {
+                        ToastKind::Success => theme.success,
+                        ToastKind::Error => theme.error,
+                    };
+                    egui::Frame::popup(ui.style())
+                        .stroke(egui::Stroke::new(1.0, accent))
+                        .show(ui, |ui| {
+                            ui.set_max_width(360.0);
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(&toast.text).size(13.0).color(accent));
+                                if ui
+                                    .small_button("✖")
+                                    .on_hover_text(tr!("gui-toast-dismiss"))
+                                    .clicked()
+                                {
+                                    dismissed = Some(index);
+                                }
+                            });
+                        });
+                    ui.add_space(6.0);
+                }
+            });
+
+        if let Some(index) = dismissed {
+            self.toasts.remove(index);
+        }
+        // Repaint to expire the toasts on time
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+}
@@ -0,0 +1,281 @@
+use egui::{Color32, RichText, ScrollArea, Stroke};
+
+use rauncher_core::config::{MeteredAction, QueueOrder};
+use rauncher_core::downloads::{DownloadJob, DownloadQueue, JobId, JobPriority, JobState};
+use rauncher_core::games::{format_duration, format_size, GameManager};
+use rauncher_core::{network, tr};
+
+use super::theme::Theme;
+
+/// Queued, active, paused and finished downloads, with controls acting on the shared queue
+pub struct DownloadsView;
+
+impl DownloadsView {
+    /// Returns the queue order when the user changed it, to be saved in the config
+    pub fn ui(
+        ui: &mut egui::Ui,
+        queue: &DownloadQueue,
+        manager: &GameManager,
+    ) -> Option<QueueOrder> {
+        let theme = Theme::current(ui.ctx());
+        let jobs = queue.jobs();
+        let order = queue.order();
+        let mut new_order = order;
+
+        ui.horizontal(|ui| {
+            ui.heading(RichText::new(tr!("gui-downloads")).size(20.0).strong());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let any_finished = jobs.iter().any(|job| job.state.is_finished());
+                if ui
+                    .add_enabled(any_finished, egui::Button::new(tr!("gui-clear-finished")))
+                    .clicked()
+                {
+                    queue.clear_finished();
+                }
+
+                egui::ComboBox::from_id_salt("download_order")
+                    .selected_text(order_label(order))
+                    .show_ui(ui, |ui| {
+                        for choice in [
+                            QueueOrder::Manual,
+                            QueueOrder::SmallestFirst,
+                            QueueOrder::Priority,
+                        ] {
+                            ui.selectable_value(&mut new_order, choice, order_label(choice));
+                        }
+                    });
+                ui.label(tr!("gui-download-order"));
+            });
+        });
+
+        ui.separator();
+        Self::metered_banner(ui, manager);
+        ui.add_space(10.0);
+
+        if jobs.is_empty() {
+            ui.label(RichText::new(tr!("gui-downloads-empty")).color(theme.text_weak));
+        } else {
+            ScrollArea::vertical().show(ui, |ui| {
+                for job in &jobs {
+                    let row = Self::job_row(ui, queue, job, order);
+                    // A job dragged by its handle takes the place of the one it's dropped on
+                    if let Some(dragged) = row.dnd_release_payload::<JobId>() {
+                        queue.move_to(*dragged, job.id);
+                    }
+                    ui.add_space(8.0);
+                }
+            });
+        }
+
+        if new_order == order {
+            return None;
+        }
+        queue.set_order(new_order);
+        Some(new_order)
+    }
+
+    /// Says what a metered connection does to downloads, with a toggle to download anyway
+    fn metered_banner(ui: &mut egui::Ui, manager: &GameManager) {
+        let settings = manager.config().metered.clone();
+        if !manager.is_metered() || settings.action == MeteredAction::Ignore {
+            return;
+        }
+
+        let theme = Theme::current(ui.ctx());
+        let mut allowed = network::is_allowed();
+        ui.horizontal(|ui| {
+            let message = if allowed {
+                tr!("gui-metered-allowed")
+            } else if settings.action == MeteredAction::Limit {
+                tr!(
+                    "gui-metered-limited",
+                    speed = format_size(settings.limit_kbps * 1024)
+                )
+            } else {
+                tr!("gui-metered-paused")
+            };
+            ui.label(RichText::new(message).color(theme.warning));
+            if ui
+                .checkbox(&mut allowed, tr!("gui-metered-allow"))
+                .changed()
+            {
+                network::set_allowed(allowed);
+            }
+        });
+    }
+
+    fn job_row(
+        ui: &mut egui::Ui,
+        queue: &DownloadQueue,
+        job: &DownloadJob,
+        order: QueueOrder,
+    ) -> egui::Response {
+        let theme = Theme::current(ui.ctx());
+        egui::Frame::none()
+            .fill(theme.surface)
+            .rounding(6.0)
+            .inner_margin(egui::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.dnd_drag_source(egui::Id::new(("download_job", job.id)), job.id, |ui| {
+                        ui.label(RichText::new("☰").color(theme.text_weak));
+                    })
+                    .response
+                    .on_hover_text(tr!("gui-drag-to-reorder"));
+                    ui.vertical(|ui| {
+                        let title = match &job.version {
+                            Some(version) => format!("{} ({})", job.address, version),
+                            None => job.address.clone(),
+                        };
+                        ui.label(RichText::new(title).size(15.0).strong());
+                        ui.label(
+                            RichText::new(state_label(&job.state))
+                                .color(state_color(&theme, &job.state)),
+                        );
+                        if let (JobState::Queued, Some(size)) = (&job.state, job.size) {
+                            ui.label(
+                                RichText::new(tr!("gui-download-size", size = format_size(size)))
+                                    .size(12.0)
+                                    .color(theme.text_weak),
+                            );
+                        }
+                    });
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if !job.state.is_finished() {
+                            if ui.button(tr!("gui-cancel")).clicked() {
+                                queue.cancel(job.id);
+                            }
+                            if job.state == JobState::Paused {
+                                if ui.button(tr!("gui-resume")).clicked() {
+                                    queue.resume(job.id);
+                                }
+                            } else if ui.button(tr!("gui-pause")).clicked() {
+                                queue.pause(job.id);
+                            }
+                            if job.state != JobState::Active
+                                && ui
+                                    .button("⏫")
+                                    .on_hover_text(tr!("gui-prioritize"))
+                                    .clicked()
+                            {
+                                queue.prioritize(job.id);
+                            }
+                        }
+                        if order == QueueOrder::Priority {
+                            let mut priority = job.priority;
+                            egui::ComboBox::from_id_salt(("job_priority", job.id))
+                                .selected_text(priority_label(priority))
+                                .show_ui(ui, |ui| {
+                                    for choice in
+                                        [JobPriority::High, JobPriority::Normal, JobPriority::Low]
+                                    {
+                                        ui.selectable_value(
+                                            &mut priority,
+                                            choice,
+                                            priority_label(choice),
+                                        );
+                                    }
+                                });
+                            if priority != job.priority {
+                                queue.set_priority(job.id, priority);
+                            }
+                        } else {
+                            if ui.button("▼").on_hover_text(tr!("gui-move-down")).clicked() {
+                                queue.move_down(job.id);
+                            }
+                            if ui.button("▲").on_hover_text(tr!("gui-move-up")).clicked() {
+                                queue.move_up(job.id);
+                            }
+                        }
+                    });
+                });
+
+                if matches!(job.state, JobState::Active | JobState::Paused) || job.bytes_done > 0 {
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::ProgressBar::new(job.progress())
+                                .desired_width(ui.available_width() - 140.0)
+                                .show_percentage(),
+                        );
+                        speed_graph(ui, &job.speed_history);
+                    });
+                    let mut details = tr!(
+                        "gui-download-progress",
+                        files_done = job.files_done,
+                        files_total = job.files_total,
+                        done = format_size(job.bytes_done),
+                        total = format_size(job.bytes_total),
+                        speed = format_size(job.speed() as u64)
+                    );
+                    if let (JobState::Active, Some(eta)) = (&job.state, job.eta()) {
+                        details.push_str(", ");
+                        details.push_str(&tr!("gui-download-eta", eta = format_duration(eta)));
+                    }
+                    ui.label(RichText::new(details).size(12.0).color(theme.text_weak));
+                }
+            })
+            .response
+    }
+}
+
+fn state_label(state: &JobState) -> String {
+    match state {
+        JobState::Queued => tr!("gui-download-queued"),
+        JobState::Active => tr!("gui-download-active"),
+        JobState::Paused => tr!("gui-download-paused"),
+        JobState::Completed => tr!("gui-download-completed"),
+        JobState::Failed(error) => tr!("gui-download-failed", error = error.as_str()),
+        JobState::Cancelled => tr!("gui-download-cancelled"),
+    }
+}
+
+fn order_label(order: QueueOrder) -> String {
+    match order {
+        QueueOrder::Manual => tr!("gui-order-manual"),
+        QueueOrder::SmallestFirst => tr!("gui-order-smallest-first"),
+        QueueOrder::Priority => tr!("gui-order-priority"),
+    }
+}
+
+fn priority_label(priority: JobPriority) -> String {
+    match priority {
+        JobPriority::Low => tr!("gui-priority-low"),
+        JobPriority::Normal => tr!("gui-priority-normal"),
+        JobPriority::High => tr!("gui-priority-high"),
+    }
+}
+
+fn state_color(theme: &Theme, state: &JobState) -> Color32 {
+    match state {
+        JobState::Active => theme.accent,
+        JobState::Completed => theme.success,
+        JobState::Failed(_) => theme.error,
+        _ => theme.text_weak,
+    }
+}
+
+/// Sparkline of the recent download speed, scaled to its peak
+fn speed_graph(ui: &mut egui::Ui, samples: &[f64]) {
+    let theme = Theme::current(ui.ctx());
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(120.0, 24.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 3.0, theme.sunken);
+
+    let peak = samples.iter().copied().fold(0.0, f64::max);
+    if samples.len() < 2 || peak <= 0.0 {
+        return;
+    }
+
+    let step = rect.width() / (samples.len() - 1) as f32;
+    let points = samples
+        .iter()
+        .enumerate()
+        .map(|(i, speed)| {
+            let height = (speed / peak) as f32 * (rect.height() - 4.0);
+            egui::pos2(rect.left() + i as f32 * step, rect.bottom() - 2.0 - height)
+        })
+        .collect();
+    painter.add(egui::Shape::line(points, Stroke::new(1.5, theme.accent)));
+}
@@ -0,0 +1,133 @@
+//! Game controller input, so the library can be browsed from the couch: the d-pad or left
+//! stick moves between games, A launches (or installs) the selected one, holding A opens its
+//! settings and B goes back.
+//! Controllers are only read when built with the `gamepad` feature.
+
+/// A navigation step from the controller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+pub enum PadInput {
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+    /// A held down rather than pressed
+    Hold,
+    Back,
+}
+
+/// How long a button or tile has to be held to count as a long press
+pub const LONG_PRESS: std::time::Duration = std::time::Duration::from_millis(700);
+
+#[cfg(feature = "gamepad")]
+pub use self::gilrs_pad::Gamepad;
+
+#[cfg(feature = "gamepad")]
+mod gilrs_pad {
+    use gilrs::{Axis, Button, EventType, Gilrs};
+    use std::time::SystemTime;
+
+    use super::{PadInput, LONG_PRESS};
+
+    /// How far the stick has to be pushed to move, and let go before it moves again
+    const STICK_PUSHED: f32 = 0.6;
+    const STICK_RELEASED: f32 = 0.3;
+
+    pub struct Gamepad {
+        /// None when the platform's controller backend couldn't be opened
+        gilrs: Option<Gilrs>,
+        /// Direction the stick is held in, which moved once already
+        stick: Option<PadInput>,
+        /// When A went down, to tell a press from a hold once it's released
+        confirm_pressed: Option<SystemTime>,
+    }
+
+    impl Gamepad {
+        pub fn new() -> Self {
+            Self {
+                gilrs: Gilrs::new().ok(),
+                stick: None,
+                confirm_pressed: None,
+            }
+        }
+
+        /// Inputs since the last call, from every connected controller
+        pub fn poll(&mut self) -> Vec<PadInput> {
+            let mut inputs = Vec::new();
+            let Some(gilrs) = &mut self.gilrs else {
+                return inputs;
+            };
+
+            while let Some(event) = gilrs.next_event() {
+                match event.event {
+                    EventType::ButtonPressed(Button::South, _) => {
+                        self.confirm_pressed = Some(event.time);
+                    }
+                    EventType::ButtonReleased(Button::South, _) => {
+                        let held = self
+                            .confirm_pressed
+                            .take()
+                            .and_then(|pressed| event.time.duration_since(pressed).ok());
+                        inputs.push(if held.is_some_and(|held| held >= LONG_PRESS) {
+                            PadInput::Hold
+                        } else {
+                            PadInput::Confirm
+                        });
+                    }
+                    EventType::ButtonPressed(button, _) => {
+                        let input = match button {
+                            Button::DPadUp => PadInput::Up,
+                            Button::DPadDown => PadInput::Down,
+                            Button::DPadLeft => PadInput::Left,
+                            Button::DPadRight => PadInput::Right,
+                            Button::Start => PadInput::Confirm,
+                            Button::East | Button::Select => PadInput::Back,
+                            _ => continue,
+                        };
+                        inputs.push(input);
+                    }
+                    EventType::AxisChanged(axis, value, _) => {
+                        let direction = match axis {
+                            Axis::LeftStickX if value >= STICK_PUSHED => PadInput::Right,
+                            Axis::LeftStickX if value <= -STICK_PUSHED => PadInput::Left,
+                            // Up is positive on the Y axis
+                            Axis::LeftStickY if value >= STICK_PUSHED => PadInput::Up,
+                            Axis::LeftStickY if value <= -STICK_PUSHED => PadInput::Down,
+                            Axis::LeftStickX | Axis::LeftStickY => {
+                                if value.abs() < STICK_RELEASED {
+                                    self.stick = None;
+                                }
+                                continue;
+                            }
+                            _ => continue,
+                        };
+                        // Holding the stick moves once, not on every reading
+                        if self.stick != Some(direction) {
+                            self.stick = Some(direction);
+                            inputs.push(direction);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            inputs
+        }
+    }
+}
+
+/// Stand-in when built without controller support
+#[cfg(not(feature = "gamepad"))]
+pub struct Gamepad;
+
+#[cfg(not(feature = "gamepad"))]
+impl Gamepad {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn poll(&mut self) -> Vec<PadInput> {
+        Vec::new()
+    }
+}
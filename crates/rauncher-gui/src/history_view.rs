@@ -0,0 +1,134 @@
+use egui::{RichText, ScrollArea};
+
+use rauncher_core::games::{format_duration, format_size};
+use rauncher_core::history::{self, HistoryAction, HistoryEntry};
+use rauncher_core::tr;
+
+use super::theme::Theme;
+
+/// Past installs, updates, repairs and uninstalls, newest first, read again each time the page
+/// opens
+#[derive(Default)]
+pub struct HistoryView {
+    entries: Vec<HistoryEntry>,
+    /// Only show games whose address contains this
+    search: String,
+    error: Option<String>,
+}
+
+impl HistoryView {
+    pub fn refresh(&mut self) {
+        match history::load(None) {
+            Ok(entries) => {
+                self.entries = entries;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(tr!("gui-error", error = e.to_string())),
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        let theme = Theme::current(ui.ctx());
+
+        ui.horizontal(|ui| {
+            ui.heading(RichText::new(tr!("gui-history")).size(20.0).strong());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(tr!("gui-refresh")).clicked() {
+                    self.refresh();
+                }
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.search)
+                        .hint_text(tr!("gui-history-search"))
+                        .desired_width(200.0),
+                );
+            });
+        });
+
+        ui.separator();
+        ui.add_space(10.0);
+
+        if let Some(error) = &self.error {
+            ui.label(RichText::new(error).color(theme.error));
+            return;
+        }
+
+        let search = self.search.trim().to_lowercase();
+        let entries: Vec<&HistoryEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.id().to_lowercase().contains(&search))
+            .collect();
+        if entries.is_empty() {
+            ui.label(RichText::new(tr!("gui-history-empty")).color(theme.text_weak));
+            return;
+        }
+
+        ScrollArea::vertical().show(ui, |ui| {
+            for entry in entries {
+                Self::entry_row(ui, entry);
+                ui.add_space(8.0);
+            }
+        });
+    }
+
+    fn entry_row(ui: &mut egui::Ui, entry: &HistoryEntry) {
+        let theme = Theme::current(ui.ctx());
+        egui::Frame::none()
+            .fill(theme.surface)
+            .rounding(6.0)
+            .inner_margin(egui::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(action_label(entry.action))
+                            .size(15.0)
+                            .strong(),
+                    );
+                    ui.label(RichText::new(entry.id()).size(15.0));
+                    ui.label(RichText::new(version_label(entry)).color(theme.text_weak));
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let started_at = entry.started_at.format("%Y-%m-%d %H:%M UTC");
+                        ui.label(RichText::new(started_at.to_string()).color(theme.text_weak));
+                    });
+                });
+
+                let mut details = format_duration(entry.duration().to_std().unwrap_or_default());
+                if entry.bytes > 0 {
+                    details.push_str(", ");
+                    details.push_str(&tr!(
+                        "gui-history-downloaded",
+                        size = format_size(entry.bytes)
+                    ));
+                }
+                ui.label(RichText::new(details).size(12.0).color(theme.text_weak));
+
+                if let Some(error) = &entry.error {
+                    ui.label(
+                        RichText::new(tr!("gui-history-failed", error = error.as_str()))
+                            .size(12.0)
+                            .color(theme.error),
+                    );
+                }
+            });
+    }
+}
+
+fn action_label(action: HistoryAction) -> String {
+    match action {
+        HistoryAction::Install => tr!("gui-history-install"),
+        HistoryAction::Update => tr!("gui-history-update"),
+        HistoryAction::Repair => tr!("gui-history-repair"),
+        HistoryAction::Uninstall => tr!("gui-history-uninstall"),
+    }
+}
+
+/// The build before and after, for updates; the one installed or removed otherwise
+fn version_label(entry: &HistoryEntry) -> String {
+    match (&entry.from_version, &entry.version) {
+        (Some(from), Some(to)) if from != to => format!("{} → {}", from, to),
+        (_, Some(version)) | (Some(version), None) => version.clone(),
+        (None, None) => String::new(),
+    }
+}
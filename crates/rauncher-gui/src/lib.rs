@@ -0,0 +1,41 @@
+mod app;
+mod auth_view;
+mod big_picture;
+mod components;
+mod downloads_view;
+mod gamepad;
+mod history_view;
+mod library_view;
+mod stats_view;
+mod storage_view;
+mod store_view;
+mod styles;
+mod tasks;
+mod theme;
+
+pub use app::LauncherApp;
+
+/// How the launcher window opens
+#[derive(Debug, Clone, Default)]
+pub struct GuiOptions {
+    /// Open fullscreen in Big Picture mode, for TVs and handhelds
+    pub fullscreen: bool,
+}
+
+/// Open the launcher window and block until it is closed
+pub fn run(options: GuiOptions) -> eframe::Result<()> {
+    let native_options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_inner_size([1200.0, 800.0])
+            .with_min_inner_size([800.0, 600.0])
+            .with_fullscreen(options.fullscreen)
+            .with_title("R Games Launcher"),
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "Rauncher",
+        native_options,
+        Box::new(move |cc| Ok(Box::new(LauncherApp::new(cc, options.fullscreen)))),
+    )
+}
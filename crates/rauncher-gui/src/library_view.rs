@@ -0,0 +1,291 @@
+use egui::{RichText, ScrollArea};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use super::components::{CardState, GameCard, GameCardAction, GameFilter, SearchBar, TagChange};
+use super::gamepad::PadInput;
+use super::theme::Theme;
+use rauncher_core::api::{BuildInfo, Game};
+use rauncher_core::collections::{Collections, Filter};
+use rauncher_core::games::InstalledGame;
+use rauncher_core::tr;
+
+#[derive(Clone)]
+pub struct LibraryView {
+    filter: GameFilter,
+    search_query: String,
+    /// Only show games from this store
+    provider_filter: Option<String>,
+    /// Favorites, category and hidden-games chips
+    tag_filter: Filter,
+    /// Name typed for a new category in a card's menu
+    new_category: String,
+    /// Game picked with a controller, as an index into the games shown
+    selected: Option<usize>,
+    /// Controller moves not applied yet, since they need the current layout
+    pad_inputs: Vec<PadInput>,
+    installing_games: Arc<Mutex<Vec<String>>>,
+    /// Games launched from this window that haven't exited yet
+    running_games: HashSet<String>,
+}
+
+impl Default for LibraryView {
+    fn default() -> Self {
+        Self {
+            filter: GameFilter::All,
+            search_query: String::new(),
+            provider_filter: None,
+            tag_filter: Filter::default(),
+            new_category: String::new(),
+            selected: None,
+            pad_inputs: Vec::new(),
+            installing_games: Arc::new(Mutex::new(Vec::new())),
+            running_games: HashSet::new(),
+        }
+    }
+}
+
+impl LibraryView {
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        library_games: &[Game],
+        installed_games: &[InstalledGame],
+        unavailable: &HashSet<String>,
+        updates: &HashMap<String, BuildInfo>,
+        collections: &Collections,
+    ) -> Option<LibraryAction> {
+        let theme = Theme::current(ui.ctx());
+        let mut action = None;
+
+        let categories = collections.categories();
+        // The selected category is gone once its last game left it
+        if self
+            .tag_filter
+            .category
+            .as_ref()
+            .is_some_and(|category| !categories.contains(category))
+        {
+            self.tag_filter.category = None;
+        }
+
+        // Top bar with search and filters using the SearchBar component
+        let mut stores: Vec<String> = library_games.iter().map(|g| g.provider.clone()).collect();
+        stores.sort();
+        stores.dedup();
+        SearchBar::show(
+            ui,
+            &mut self.search_query,
+            &mut self.filter,
+            &mut self.provider_filter,
+            &stores,
+            &mut self.tag_filter,
+            &categories,
+        );
+
+        ui.separator();
+        ui.add_space(15.0);
+
+        // Game grid with enhanced layout
+        ScrollArea::vertical().show(ui, |ui| {
+            let available_width = ui.available_width();
+            let card_width = 280.0; // Slightly larger cards
+            let card_spacing = 15.0; // More spacing between cards
+            let cards_per_row = (available_width / (card_width + card_spacing))
+                .floor()
+                .max(1.0) as usize;
+
+            let is_installed = |g: &Game| {
+                installed_games
+                    .iter()
+                    .any(|ig| ig.app_name == g.app_name && ig.provider == g.provider)
+            };
+            let in_store = |g: &&Game| {
+                self.provider_filter
+                    .as_ref()
+                    .is_none_or(|p| &g.provider == p)
+                    && collections.shows(&g.id(), &self.tag_filter)
+            };
+
+            let games_to_show: Vec<_> = match self.filter {
+                GameFilter::All => library_games
+                    .iter()
+                    .filter(in_store)
+                    .filter(|g| {
+                        self.search_query.is_empty()
+                            || g.app_title
+                                .to_lowercase()
+                                .contains(&self.search_query.to_lowercase())
+                    })
+                    .collect(),
+                GameFilter::Installed => library_games
+                    .iter()
+                    .filter(in_store)
+                    .filter(|g| {
+                        is_installed(g)
+                            && (self.search_query.is_empty()
+                                || g.app_title
+                                    .to_lowercase()
+                                    .contains(&self.search_query.to_lowercase()))
+                    })
+                    .collect(),
+            };
+
+            let scroll_to_selected = !self.pad_inputs.is_empty();
+            for input in std::mem::take(&mut self.pad_inputs) {
+                let Some(last) = games_to_show.len().checked_sub(1) else {
+                    break;
+                };
+                // The first move only picks the first game
+                let Some(current) = self.selected.map(|i| i.min(last)) else {
+                    self.selected = Some(0);
+                    continue;
+                };
+
+                self.selected = Some(match input {
+                    PadInput::Left => current.saturating_sub(1),
+                    PadInput::Right => (current + 1).min(last),
+                    PadInput::Up => current.checked_sub(cards_per_row).unwrap_or(current),
+                    PadInput::Down => (current + cards_per_row).min(last),
+                    PadInput::Confirm => {
+                        let game = games_to_show[current];
+                        let address = game.id();
+                        if is_installed(game) {
+                            if !unavailable.contains(&address) {
+                                action = Some(LibraryAction::Launch(address));
+                            }
+                        } else if !self.installing_games.lock().unwrap().contains(&address) {
+                            action = Some(LibraryAction::Install(address));
+                        }
+                        current
+                    }
+                    PadInput::Hold => {
+                        let game = games_to_show[current];
+                        if is_installed(game) {
+                            action = Some(LibraryAction::Settings(game.id()));
+                        }
+                        current
+                    }
+                    PadInput::Back => current,
+                });
+            }
+            let selected = self
+                .selected
+                .zip(games_to_show.len().checked_sub(1))
+                .map(|(i, last)| i.min(last));
+
+            if games_to_show.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(100.0);
+                    ui.label(
+                        RichText::new(tr!("gui-no-games"))
+                            .size(18.0)
+                            .color(theme.text_weak),
+                    );
+                    if self.search_query.is_empty() {
+                        ui.label(RichText::new(tr!("gui-library-empty")).color(theme.text_weak));
+                    }
+                });
+            } else {
+                // Display games in a grid with enhanced spacing
+                for (row, row_games) in games_to_show.chunks(cards_per_row).enumerate() {
+                    ui.horizontal(|ui| {
+                        for (column, game) in row_games.iter().enumerate() {
+                            let is_selected = selected == Some(row * cards_per_row + column);
+                            let card_origin = ui.next_widget_position();
+                            let is_installed = is_installed(game);
+                            let is_installing =
+                                self.installing_games.lock().unwrap().contains(&game.id());
+
+                            let tags = collections.tags(&game.id());
+
+                            if let Some(game_action) = GameCard::show(
+                                ui,
+                                game,
+                                CardState {
+                                    installed: is_installed,
+                                    unavailable: unavailable.contains(&game.id()),
+                                    update: updates.get(&game.id()).cloned(),
+                                    installing: is_installing,
+                                    running: self.running_games.contains(&game.id()),
+                                    selected: is_selected,
+                                },
+                                &tags,
+                                &categories,
+                                &mut self.new_category,
+                            ) {
+                                action = Some(match game_action {
+                                    GameCardAction::Install(name) => LibraryAction::Install(name),
+                                    GameCardAction::Launch(name) => LibraryAction::Launch(name),
+                                    GameCardAction::Stop(name) => LibraryAction::Stop(name),
+                                    GameCardAction::Uninstall(name) => {
+                                        LibraryAction::Uninstall(name)
+                                    }
+                                    GameCardAction::Settings(name) => LibraryAction::Settings(name),
+                                    GameCardAction::Tag(name, change) => {
+                                        LibraryAction::Tag(name, change)
+                                    }
+                                });
+                            }
+                            if is_selected && scroll_to_selected {
+                                let card = egui::Rect::from_min_size(
+                                    card_origin,
+                                    egui::vec2(card_width, 340.0),
+                                );
+                                ui.scroll_to_rect(card, None);
+                            }
+                            ui.add_space(card_spacing);
+                        }
+                    });
+                    ui.add_space(15.0);
+                }
+            }
+        });
+
+        action
+    }
+
+    /// Move the controller selection; B drops it
+    pub fn navigate(&mut self, input: PadInput) {
+        if input == PadInput::Back {
+            self.selected = None;
+        } else {
+            self.pad_inputs.push(input);
+        }
+    }
+
+    pub fn mark_installation_started(&mut self, address: &str) {
+        let mut list = self.installing_games.lock().unwrap();
+        if !list.iter().any(|n| n == address) {
+            list.push(address.to_string());
+        }
+    }
+
+    pub fn mark_installation_complete(&mut self, address: &str) {
+        self.installing_games
+            .lock()
+            .unwrap()
+            .retain(|name| name != address);
+    }
+
+    pub fn mark_running(&mut self, address: &str, running: bool) {
+        if running {
+            self.running_games.insert(address.to_string());
+        } else {
+            self.running_games.remove(address);
+        }
+    }
+
+    pub fn is_running(&self, address: &str) -> bool {
+        self.running_games.contains(address)
+    }
+}
+
+pub enum LibraryAction {
+    Install(String),
+    Launch(String),
+    Stop(String),
+    Uninstall(String),
+    Settings(String),
+    Tag(String, TagChange),
+}
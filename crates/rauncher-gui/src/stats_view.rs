@@ -0,0 +1,205 @@
+use egui::{RichText, ScrollArea, Stroke};
+use poll_promise::Promise;
+use std::time::Duration;
+
+use rauncher_core::games::{format_duration, format_size, GameManager};
+use rauncher_core::stats::{LibraryStats, MonthlyDownloads};
+use rauncher_core::tr;
+use rauncher_core::Result;
+
+use super::theme::Theme;
+
+/// Library size, play time and downloads, computed in the background since counting the
+/// owned games asks the stores
+#[derive(Default)]
+pub struct StatsView {
+    stats: Option<LibraryStats>,
+    loading: Option<Promise<Result<LibraryStats>>>,
+    error: Option<String>,
+}
+
+impl StatsView {
+    /// Compute the statistics again, unless that's already running
+    pub fn refresh(&mut self, manager: &GameManager) {
+        if self.loading.is_some() {
+            return;
+        }
+
+        let manager = manager.clone();
+        self.loading = Some(Promise::spawn_thread("load_stats", move || {
+            let rt = tokio::runtime::Runtime::new()
+                .expect("Failed to create Tokio runtime for the statistics");
+            rt.block_on(async move { manager.stats().await })
+        }));
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, manager: &GameManager) {
+        let theme = Theme::current(ui.ctx());
+
+        if let Some(result) = self.loading.as_ref().and_then(Promise::ready) {
+            match result {
+                Ok(stats) => {
+                    self.stats = Some(stats.clone());
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(tr!("gui-error", error = e.to_string())),
+            }
+            self.loading = None;
+        }
+
+        ui.horizontal(|ui| {
+            ui.heading(RichText::new(tr!("gui-stats")).size(20.0).strong());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let refresh = egui::Button::new(tr!("gui-refresh"));
+                if ui.add_enabled(self.loading.is_none(), refresh).clicked() {
+                    self.refresh(manager);
+                }
+                if self.loading.is_some() {
+                    ui.spinner();
+                }
+            });
+        });
+
+        ui.separator();
+        ui.add_space(10.0);
+
+        if let Some(error) = &self.error {
+            ui.label(RichText::new(error).color(theme.error));
+        }
+        let Some(stats) = &self.stats else {
+            return;
+        };
+        let time = |seconds: u64| format_duration(Duration::from_secs(seconds));
+
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                let owned = match stats.owned_games {
+                    Some(count) => count.to_string(),
+                    None => "–".to_string(),
+                };
+                summary_card(ui, tr!("gui-stats-owned"), owned);
+                summary_card(
+                    ui,
+                    tr!("gui-stats-installed"),
+                    stats.installed_games.to_string(),
+                );
+                summary_card(
+                    ui,
+                    tr!("gui-stats-installed-size"),
+                    format_size(stats.installed_bytes),
+                );
+                summary_card(
+                    ui,
+                    tr!("gui-stats-playtime"),
+                    time(stats.total_playtime_seconds),
+                );
+            });
+
+            ui.add_space(15.0);
+            ui.label(
+                RichText::new(tr!("gui-stats-most-played"))
+                    .size(16.0)
+                    .strong(),
+            );
+            if stats.most_played.is_empty() {
+                ui.label(RichText::new(tr!("gui-stats-no-sessions")).color(theme.text_weak));
+            }
+            for game in &stats.most_played {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&game.title).strong());
+                    ui.label(
+                        RichText::new(tr!(
+                            "gui-stats-played",
+                            time = time(game.seconds),
+                            sessions = game.sessions
+                        ))
+                        .color(theme.text_weak),
+                    );
+                });
+            }
+
+            ui.add_space(15.0);
+            ui.label(
+                RichText::new(tr!("gui-stats-downloads"))
+                    .size(16.0)
+                    .strong(),
+            );
+            match stats.downloads.last() {
+                Some(last) => {
+                    ui.label(
+                        RichText::new(tr!(
+                            "gui-stats-downloads-total",
+                            size = format_size(last.cumulative_bytes)
+                        ))
+                        .color(theme.text_weak),
+                    );
+                    downloads_graph(ui, &stats.downloads);
+                }
+                None => {
+                    ui.label(RichText::new(tr!("gui-stats-no-downloads")).color(theme.text_weak));
+                }
+            }
+        });
+    }
+}
+
+fn summary_card(ui: &mut egui::Ui, label: String, value: String) {
+    let theme = Theme::current(ui.ctx());
+    egui::Frame::none()
+        .fill(theme.surface)
+        .rounding(6.0)
+        .inner_margin(egui::Margin::same(12.0))
+        .show(ui, |ui| {
+            ui.set_min_width(160.0);
+            ui.vertical(|ui| {
+                ui.label(RichText::new(label).size(12.0).color(theme.text_weak));
+                ui.label(
+                    RichText::new(value)
+                        .size(22.0)
+                        .strong()
+                        .color(theme.text_strong),
+                );
+            });
+        });
+}
+
+/// Bars of the bytes downloaded up to each month, scaled to the total
+fn downloads_graph(ui: &mut egui::Ui, months: &[MonthlyDownloads]) {
+    let theme = Theme::current(ui.ctx());
+    let width = ui.available_width().min(600.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, 120.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 3.0, theme.sunken);
+
+    let peak = months.last().map_or(0, |month| month.cumulative_bytes);
+    if peak == 0 {
+        return;
+    }
+
+    // Room at the bottom for the month labels
+    let chart_height = rect.height() - 18.0;
+    let slot = rect.width() / months.len() as f32;
+    for (i, month) in months.iter().enumerate() {
+        let height = (month.cumulative_bytes as f64 / peak as f64) as f32 * (chart_height - 4.0);
+        let left = rect.left() + i as f32 * slot + slot * 0.15;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(left, rect.top() + chart_height - height),
+            egui::pos2(left + slot * 0.7, rect.top() + chart_height),
+        );
+        painter.rect_filled(bar, 2.0, theme.accent);
+        painter.text(
+            egui::pos2(bar.center().x, rect.bottom() - 2.0),
+            egui::Align2::CENTER_BOTTOM,
+            &month.month,
+            egui::FontId::proportional(10.0),
+            theme.text_weak,
+        );
+    }
+    painter.line_segment(
+        [
+            egui::pos2(rect.left(), rect.top() + chart_height),
+            egui::pos2(rect.right(), rect.top() + chart_height),
+        ],
+        Stroke::new(1.0, theme.widget),
+    );
+}
@@ -0,0 +1,439 @@
+use egui::{RichText, ScrollArea};
+use poll_promise::Promise;
+use std::path::PathBuf;
+
+use rauncher_core::config::Config;
+use rauncher_core::games::{format_size, GameManager, InstalledGame, OrphanedInstall};
+use rauncher_core::sandbox;
+use rauncher_core::storage::{self, LibraryRoot, Removal, StorageReport};
+use rauncher_core::tr;
+use rauncher_core::Result;
+
+use super::theme::Theme;
+
+/// Disk usage per library root, measured in the background, with cleanup of rebuildable data
+#[derive(Default)]
+pub struct StorageView {
+    report: Option<StorageReport>,
+    measuring: Option<Promise<Result<StorageReport>>>,
+    /// Running cleanup, resolving to the bytes freed
+    cleaning: Option<Promise<Result<u64>>>,
+    /// Unregistered game folders from the last scan, once one has run
+    orphans: Option<Vec<OrphanedInstall>>,
+    scanning: Option<Promise<Result<Vec<OrphanedInstall>>>>,
+    /// Orphan being recorded as an installed game, resolving to the record
+    adopting: Option<(OrphanedInstall, Promise<Result<InstalledGame>>)>,
+    /// Open directory picker for the install directory, resolving to the chosen folder
+    picking: Option<Promise<Option<PathBuf>>>,
+    message: String,
+}
+
+pub enum StorageAction {
+    /// An orphaned folder was recorded as an installed game
+    Adopted(Box<InstalledGame>),
+    /// A new install directory was picked
+    InstallDirChanged(PathBuf),
+}
+
+impl StorageView {
+    /// Measure disk usage again, unless a measurement is already running
+    pub fn refresh(&mut self, config: &Config) {
+        if self.measuring.is_some() {
+            return;
+        }
+
+        let config = config.clone();
+        self.measuring = Some(Promise::spawn_thread("measure_storage", move || {
+            StorageReport::collect(&config)
+        }));
+    }
+
+    /// Look for orphaned game folders and identify them against the library
+    fn scan_orphans(&mut self, manager: &GameManager) {
+        let manager = manager.clone();
+        self.scanning = Some(Promise::spawn_thread("scan_orphans", move || {
+            let rt = tokio::runtime::Runtime::new()
+                .expect("Failed to create Tokio runtime for the orphan scan");
+            rt.block_on(async move { manager.find_orphans().await })
+        }));
+    }
+
+    /// Record an orphaned folder as an installed game, which reads through its files
+    fn adopt_orphan(&mut self, manager: &GameManager, orphan: OrphanedInstall) {
+        let manager = manager.clone();
+        let adopted = orphan.clone();
+        let promise = Promise::spawn_thread("adopt_orphan", move || manager.adopt_orphan(&adopted));
+        self.adopting = Some((orphan, promise));
+    }
+
+    /// Ask for a new install directory through the desktop's file chooser, which is the XDG
+    /// portal on Linux so it also works inside Flatpak
+    fn pick_install_dir(&mut self, current: PathBuf) {
+        self.picking = Some(Promise::spawn_thread("pick_install_dir", move || {
+            let rt = tokio::runtime::Runtime::new()
+                .expect("Failed to create Tokio runtime for the directory picker");
+            rt.block_on(async move {
+                rfd::AsyncFileDialog::new()
+                    .set_directory(current)
+                    .pick_folder()
+                    .await
+                    .map(|folder| folder.path().to_path_buf())
+            })
+        }));
+    }
+
+    /// The picked install directory, unless a Flatpak sandbox can't use it
+    fn accept_install_dir(&mut self, path: PathBuf) -> Option<PathBuf> {
+        let flatpak = sandbox::flatpak();
+
+        if let Some(flatpak) = flatpak.filter(|_| sandbox::is_document_portal(&path)) {
+            self.message = tr!(
+                "gui-flatpak-portal-path",
+                command = flatpak.permission_command("/path/to/folder")
+            );
+            return None;
+        }
+        if let Some(command) = sandbox::missing_permission(&path) {
+            self.message = tr!(
+                "gui-flatpak-permission",
+                path = path.display().to_string(),
+                command = command
+            );
+            return None;
+        }
+
+        Some(path)
+    }
+
+    /// `active` lists the app names still downloading, whose directories are left alone
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        manager: &GameManager,
+        active: Vec<String>,
+    ) -> Option<StorageAction> {
+        let theme = Theme::current(ui.ctx());
+        let config = manager.config();
+        let mut action = None;
+
+        if let Some(result) = self.scanning.as_ref().and_then(Promise::ready) {
+            match result {
+                Ok(orphans) => self.orphans = Some(orphans.clone()),
+                Err(e) => self.message = tr!("gui-orphans-failed", error = e.to_string()),
+            }
+            self.scanning = None;
+        }
+        if let Some(result) = self.measuring.as_ref().and_then(Promise::ready) {
+            match result {
+                Ok(report) => self.report = Some(report.clone()),
+                Err(e) => self.message = tr!("gui-storage-failed", error = e.to_string()),
+            }
+            self.measuring = None;
+        }
+        if let Some(result) = self.cleaning.as_ref().and_then(Promise::ready) {
+            self.message = match result {
+                Ok(freed) => tr!("gui-storage-freed", size = format_size(*freed)),
+                Err(e) => tr!("gui-error", error = e.to_string()),
+            };
+            self.cleaning = None;
+            self.refresh(&config);
+        }
+
+        if self
+            .adopting
+            .as_ref()
+            .is_some_and(|(_, promise)| promise.ready().is_some())
+        {
+            let (orphan, promise) = self.adopting.take().unwrap();
+            match promise.block_and_take() {
+                Ok(game) => {
+                    self.forget_orphan(&orphan);
+                    self.refresh(&config);
+                    action = Some(StorageAction::Adopted(Box::new(game)));
+                }
+                Err(e) => self.message = tr!("gui-error", error = e.to_string()),
+            }
+        }
+
+        if let Some(picked) = self.picking.as_ref().and_then(Promise::ready) {
+            let picked = picked.clone();
+            self.picking = None;
+            if let Some(path) = picked.and_then(|path| self.accept_install_dir(path)) {
+                action = Some(StorageAction::InstallDirChanged(path));
+            }
+        }
+
+        let busy = self.measuring.is_some()
+            || self.cleaning.is_some()
+            || self.scanning.is_some()
+            || self.adopting.is_some();
+
+        ui.horizontal(|ui| {
+            ui.heading(RichText::new(tr!("gui-storage")).size(20.0).strong());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .add_enabled(!busy, egui::Button::new(tr!("gui-refresh")))
+                    .clicked()
+                {
+                    self.refresh(&config);
+                }
+                if busy {
+                    ui.spinner();
+                }
+            });
+        });
+
+        ui.separator();
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            ui.label(tr!(
+                "gui-install-dir",
+                path = config.install_dir.display().to_string()
+            ));
+            if ui
+                .add_enabled(
+                    self.picking.is_none(),
+                    egui::Button::new(tr!("gui-install-dir-change")),
+                )
+                .clicked()
+            {
+                self.pick_install_dir(config.install_dir.clone());
+            }
+        });
+        ui.add_space(5.0);
+
+        if !self.message.is_empty() {
+            ui.label(&self.message);
+            ui.add_space(5.0);
+        }
+
+        let Some(report) = &self.report else {
+            if busy {
+                ui.label(RichText::new(tr!("gui-storage-loading")).color(theme.text_weak));
+            }
+            return action;
+        };
+
+        let mut clean = None;
+        let mut adopt = None;
+        let mut scan = false;
+        ScrollArea::vertical().show(ui, |ui| {
+            for root in &report.roots {
+                root_section(ui, root);
+                ui.add_space(12.0);
+            }
+
+            ui.label(
+                RichText::new(tr!("gui-storage-cleanup"))
+                    .size(16.0)
+                    .strong(),
+            );
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label(tr!(
+                    "gui-storage-shader-cache",
+                    size = format_size(report.shader_cache)
+                ));
+                let enabled = !busy && report.shader_cache > 0;
+                if ui
+                    .add_enabled(enabled, egui::Button::new(tr!("gui-clean-shader-cache")))
+                    .clicked()
+                {
+                    clean = Some(Cleanup::ShaderCaches);
+                }
+            });
+
+            let partial_size: u64 = report.partial_downloads.iter().map(|p| p.size).sum();
+            ui.horizontal(|ui| {
+                ui.label(tr!(
+                    "gui-storage-partial",
+                    count = report.partial_downloads.len(),
+                    size = format_size(partial_size)
+                ));
+                let enabled = !busy && !report.partial_downloads.is_empty();
+                if ui
+                    .add_enabled(enabled, egui::Button::new(tr!("gui-clean-partial")))
+                    .clicked()
+                {
+                    clean = Some(Cleanup::PartialDownloads);
+                }
+            });
+            for partial in &report.partial_downloads {
+                ui.label(
+                    RichText::new(format!(
+                        "{} ({})",
+                        partial.path.display(),
+                        format_size(partial.size)
+                    ))
+                    .size(12.0)
+                    .color(theme.text_weak),
+                );
+            }
+
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(tr!("gui-orphans")).size(16.0).strong());
+                if ui
+                    .add_enabled(!busy, egui::Button::new(tr!("gui-orphans-scan")))
+                    .clicked()
+                {
+                    scan = true;
+                }
+            });
+            ui.label(
+                RichText::new(tr!("gui-orphans-hint"))
+                    .size(12.0)
+                    .color(theme.text_weak),
+            );
+            ui.add_space(5.0);
+
+            match &self.orphans {
+                Some(orphans) if orphans.is_empty() => {
+                    ui.label(tr!("gui-orphans-none"));
+                }
+                Some(orphans) => {
+                    for orphan in orphans {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(orphan.path.display().to_string()).strong());
+                            ui.label(format_size(orphan.size));
+                            let identity = match &orphan.matched {
+                                Some(matched) => tr!(
+                                    "gui-orphan-identified",
+                                    title = matched.app_title.as_str(),
+                                    version = matched.app_version.as_str(),
+                                    found = matched.files_found,
+                                    total = matched.files_total
+                                ),
+                                None => tr!("gui-orphan-unidentified"),
+                            };
+                            ui.label(RichText::new(identity).color(theme.text_weak));
+
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    if ui
+                                        .add_enabled(!busy, egui::Button::new(tr!("gui-delete")))
+                                        .clicked()
+                                    {
+                                        clean = Some(Cleanup::Orphan(orphan.clone()));
+                                    }
+                                    if orphan.matched.is_some()
+                                        && ui
+                                            .add_enabled(!busy, egui::Button::new(tr!("gui-adopt")))
+                                            .clicked()
+                                    {
+                                        adopt = Some(orphan.clone());
+                                    }
+                                },
+                            );
+                        });
+                    }
+                }
+                None => {}
+            }
+        });
+
+        if scan {
+            self.message.clear();
+            self.scan_orphans(manager);
+        }
+
+        if let Some(orphan) = adopt {
+            self.message.clear();
+            self.adopt_orphan(manager, orphan);
+        }
+
+        if let Some(Cleanup::Orphan(orphan)) = &clean {
+            self.forget_orphan(orphan);
+        }
+
+        if let Some(cleanup) = clean {
+            self.message.clear();
+            self.cleaning = Some(Promise::spawn_thread(
+                "clean_storage",
+                move || match cleanup {
+                    Cleanup::ShaderCaches => storage::clean_shader_caches(Removal::Trash),
+                    Cleanup::PartialDownloads => {
+                        storage::clean_partial_downloads(&config, &active, Removal::Trash)
+                    }
+                    Cleanup::Orphan(orphan) => orphan.delete(Removal::Trash),
+                },
+            ));
+        }
+
+        action
+    }
+
+    fn forget_orphan(&mut self, orphan: &OrphanedInstall) {
+        if let Some(orphans) = &mut self.orphans {
+            orphans.retain(|o| o.path != orphan.path);
+        }
+    }
+}
+
+enum Cleanup {
+    ShaderCaches,
+    PartialDownloads,
+    Orphan(OrphanedInstall),
+}
+
+fn root_section(ui: &mut egui::Ui, root: &LibraryRoot) {
+    let theme = Theme::current(ui.ctx());
+    egui::Frame::none()
+        .fill(theme.surface)
+        .rounding(6.0)
+        .inner_margin(egui::Margin::same(10.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(root.path.display().to_string())
+                        .size(15.0)
+                        .strong(),
+                );
+                ui.label(RichText::new(format_size(root.used())).color(theme.text_weak));
+            });
+
+            match (root.free, root.capacity) {
+                (Some(free), Some(capacity)) => {
+                    ui.label(tr!(
+                        "gui-storage-root",
+                        free = format_size(free),
+                        capacity = format_size(capacity)
+                    ));
+                    let used = 1.0 - free as f32 / capacity.max(1) as f32;
+                    ui.add(egui::ProgressBar::new(used).desired_width(300.0));
+                }
+                _ => {
+                    ui.label(tr!("gui-storage-root-unknown"));
+                }
+            }
+            ui.add_space(5.0);
+
+            if root.games.is_empty() {
+                ui.label(RichText::new(tr!("gui-storage-root-empty")).color(theme.text_weak));
+            }
+            for game in &root.games {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&game.app_title).strong());
+                    ui.label(format_size(game.total()));
+                    let mut detail = tr!(
+                        "gui-storage-game-detail",
+                        install = format_size(game.install),
+                        prefix = format_size(game.prefix),
+                        shaders = format_size(game.shader_cache)
+                    );
+                    if game.download > 0 {
+                        detail.push_str(" · ");
+                        detail.push_str(&tr!(
+                            "gui-storage-game-download",
+                            size = format_size(game.download)
+                        ));
+                    }
+                    ui.label(RichText::new(detail).size(12.0).color(theme.text_weak));
+                });
+            }
+        });
+}
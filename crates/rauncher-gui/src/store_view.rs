@@ -0,0 +1,319 @@
+use egui::{RichText, ScrollArea};
+use poll_promise::Promise;
+
+use rauncher_core::api::{FreeGame, Game, StoreOffer, StorePage, StoreQuery, STORE_GENRES};
+use rauncher_core::games::GameManager;
+use rauncher_core::tr;
+use rauncher_core::wishlist::Wishlist;
+use rauncher_core::Result;
+
+use super::theme::Theme;
+
+/// Epic Games Store listings, searched in the background. Games are bought on the website.
+#[derive(Default)]
+pub struct StoreView {
+    /// Query of the results shown or being fetched
+    query: StoreQuery,
+    /// Search box contents, applied on Enter
+    keywords: String,
+    page: Option<StorePage>,
+    searching: Option<Promise<Result<StorePage>>>,
+    /// Loaded when the store is first opened
+    wishlist: Option<Wishlist>,
+    message: String,
+}
+
+impl StoreView {
+    /// Load the first results, unless some are shown or coming already
+    pub fn open(&mut self, manager: &GameManager) {
+        if self.wishlist.is_none() {
+            match Wishlist::load() {
+                Ok(wishlist) => self.wishlist = Some(wishlist),
+                Err(e) => self.message = tr!("gui-store-failed", error = e.to_string()),
+            }
+        }
+        if self.page.is_none() && self.searching.is_none() {
+            self.search(manager);
+        }
+    }
+
+    fn search(&mut self, manager: &GameManager) {
+        let manager = manager.clone();
+        let query = self.query.clone();
+        self.searching = Some(Promise::spawn_thread("browse_store", move || {
+            let rt = tokio::runtime::Runtime::new()
+                .expect("Failed to create Tokio runtime for the store");
+            rt.block_on(async move { manager.browse_store(&query).await })
+        }));
+    }
+
+    /// `library` marks the games already owned
+    pub fn ui(&mut self, ui: &mut egui::Ui, manager: &GameManager, library: &[Game]) {
+        let theme = Theme::current(ui.ctx());
+
+        if let Some(result) = self.searching.as_ref().and_then(Promise::ready) {
+            match result {
+                Ok(page) => {
+                    self.message.clear();
+                    self.page = Some(page.clone());
+                }
+                Err(e) => self.message = tr!("gui-store-failed", error = e.to_string()),
+            }
+            self.searching = None;
+        }
+
+        let mut search = false;
+        ui.horizontal(|ui| {
+            ui.heading(RichText::new(tr!("gui-store")).size(20.0).strong());
+            ui.add_space(10.0);
+
+            let edit = ui.add(
+                egui::TextEdit::singleline(&mut self.keywords)
+                    .hint_text(tr!("gui-store-search"))
+                    .desired_width(260.0),
+            );
+            if edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                search = true;
+            }
+
+            let all_genres = tr!("gui-store-all-genres");
+            egui::ComboBox::from_id_salt("store_genre")
+                .selected_text(
+                    self.query
+                        .genre
+                        .clone()
+                        .unwrap_or_else(|| all_genres.clone()),
+                )
+                .show_ui(ui, |ui| {
+                    search |= ui
+                        .selectable_value(&mut self.query.genre, None, all_genres)
+                        .clicked();
+                    for (genre, _) in STORE_GENRES {
+                        search |= ui
+                            .selectable_value(&mut self.query.genre, Some(genre.to_string()), genre)
+                            .clicked();
+                    }
+                });
+
+            search |= ui
+                .checkbox(&mut self.query.free, tr!("gui-store-free"))
+                .changed();
+
+            if self.searching.is_some() {
+                ui.spinner();
+            }
+        });
+
+        if search {
+            self.query.keywords = self.keywords.trim().to_string();
+            self.query.page = 0;
+            self.search(manager);
+        }
+
+        ui.separator();
+        ui.add_space(10.0);
+
+        if !self.message.is_empty() {
+            ui.label(RichText::new(&self.message).color(theme.error));
+            ui.add_space(5.0);
+        }
+
+        let Some(page) = &self.page else {
+            if self.searching.is_some() {
+                ui.label(RichText::new(tr!("gui-store-loading")).color(theme.text_weak));
+            }
+            return;
+        };
+
+        let mut go_to = None;
+        let mut toggled = None;
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(tr!("gui-store-prices", country = page.country.as_str()))
+                    .color(theme.text_weak),
+            );
+            if page.stale {
+                ui.label(RichText::new(tr!("gui-store-stale")).color(theme.warning));
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let current = self.query.page;
+                let last = page.pages() - 1;
+                if ui
+                    .add_enabled(current < last, egui::Button::new(tr!("gui-store-next")))
+                    .clicked()
+                {
+                    go_to = Some(current + 1);
+                }
+                ui.label(tr!(
+                    "gui-store-page",
+                    page = current + 1,
+                    pages = page.pages()
+                ));
+                if ui
+                    .add_enabled(current > 0, egui::Button::new(tr!("gui-store-previous")))
+                    .clicked()
+                {
+                    go_to = Some(current - 1);
+                }
+            });
+        });
+        ui.add_space(5.0);
+
+        ScrollArea::vertical().show(ui, |ui| {
+            if !page.giveaways.is_empty() {
+                ui.label(
+                    RichText::new(tr!("gui-store-giveaways"))
+                        .size(16.0)
+                        .strong(),
+                );
+                for game in &page.giveaways {
+                    giveaway_row(ui, game);
+                    ui.add_space(6.0);
+                }
+                ui.add_space(10.0);
+            }
+
+            if page.offers.is_empty() {
+                ui.label(RichText::new(tr!("gui-store-empty")).color(theme.text_weak));
+            }
+            for offer in &page.offers {
+                let owned = library
+                    .iter()
+                    .any(|game| game.app_title.eq_ignore_ascii_case(&offer.title));
+                let wishlisted = self.wishlist.as_ref().map(|w| w.contains(offer));
+                if offer_row(ui, offer, owned, wishlisted) {
+                    toggled = Some(offer.clone());
+                }
+                ui.add_space(6.0);
+            }
+        });
+
+        if let (Some(offer), Some(wishlist)) = (toggled, &mut self.wishlist) {
+            if wishlist.contains(&offer) {
+                wishlist.remove(&format!("{}:{}", offer.namespace, offer.id));
+            } else {
+                wishlist.add(&offer);
+            }
+            if let Err(e) = wishlist.save() {
+                self.message = tr!("gui-store-failed", error = e.to_string());
+            }
+        }
+
+        if let Some(number) = go_to {
+            self.query.page = number;
+            self.search(manager);
+        }
+    }
+}
+
+/// `wishlisted` is None when the wishlist couldn't be loaded. Returns whether the wishlist
+/// button was clicked.
+fn offer_row(ui: &mut egui::Ui, offer: &StoreOffer, owned: bool, wishlisted: Option<bool>) -> bool {
+    let theme = Theme::current(ui.ctx());
+    let mut toggled = false;
+    egui::Frame::none()
+        .fill(theme.surface)
+        .rounding(6.0)
+        .inner_margin(egui::Margin::same(10.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.label(
+                        RichText::new(&offer.title)
+                            .size(15.0)
+                            .strong()
+                            .color(theme.text_strong),
+                    );
+                    let mut details: Vec<&str> = offer.seller.iter().map(String::as_str).collect();
+                    details.extend(offer.genres.iter().map(String::as_str));
+                    if !details.is_empty() {
+                        ui.label(RichText::new(details.join(" · ")).color(theme.text_weak));
+                    }
+                });
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if let Some(url) = &offer.url {
+                        if ui.button(tr!("gui-store-open")).clicked() {
+                            let _ = webbrowser::open(url);
+                        }
+                    }
+                    if owned {
+                        ui.label(RichText::new(tr!("gui-store-owned")).color(theme.success));
+                    } else if let Some(wishlisted) = wishlisted {
+                        let label = if wishlisted {
+                            RichText::new(tr!("gui-store-wishlisted")).color(theme.accent)
+                        } else {
+                            RichText::new(tr!("gui-store-wishlist"))
+                        };
+                        toggled = ui.button(label).clicked();
+                    }
+                    price_label(ui, offer, &theme);
+                });
+            });
+        });
+    toggled
+}
+
+fn price_label(ui: &mut egui::Ui, offer: &StoreOffer, theme: &Theme) {
+    let Some(price) = &offer.price else {
+        ui.label(RichText::new(tr!("gui-store-no-price")).color(theme.text_weak));
+        return;
+    };
+
+    if price.free {
+        ui.label(
+            RichText::new(tr!("gui-store-price-free"))
+                .strong()
+                .color(theme.success),
+        );
+        return;
+    }
+
+    // Right to left: the current price ends up last
+    ui.label(RichText::new(&price.current).size(15.0).strong());
+    if price.discount > 0 {
+        ui.label(
+            RichText::new(&price.original)
+                .strikethrough()
+                .color(theme.text_weak),
+        );
+        egui::Frame::none()
+            .fill(theme.accent)
+            .rounding(3.0)
+            .inner_margin(egui::Margin::symmetric(6.0, 2.0))
+            .show(ui, |ui| {
+                ui.label(RichText::new(format!("-{}%", price.discount)).color(theme.on_accent));
+            });
+    }
+}
+
+fn giveaway_row(ui: &mut egui::Ui, game: &FreeGame) {
+    let theme = Theme::current(ui.ctx());
+    egui::Frame::none()
+        .fill(theme.surface)
+        .stroke(egui::Stroke::new(1.0, theme.success))
+        .rounding(6.0)
+        .inner_margin(egui::Margin::same(10.0))
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&game.title).size(15.0).strong());
+                ui.label(
+                    RichText::new(tr!(
+                        "gui-store-giveaway-until",
+                        date = game.ends_at.format("%Y-%m-%d").to_string()
+                    ))
+                    .color(theme.success),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if let Some(url) = &game.url {
+                        if ui.button(tr!("gui-store-claim")).clicked() {
+                            let _ = webbrowser::open(url);
+                        }
+                    }
+                });
+            });
+        });
+}
@@ -0,0 +1,51 @@
+use egui::{Rounding, Stroke, Style, Visuals};
+
+use super::theme::Theme;
+
+/// egui's style with the colors of `theme`
+pub fn style(theme: &Theme) -> Style {
+    let mut style = Style {
+        visuals: if theme.dark {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        },
+        ..Default::default()
+    };
+
+    style.visuals.window_fill = theme.background;
+    style.visuals.panel_fill = theme.panel;
+    style.visuals.faint_bg_color = theme.surface;
+    style.visuals.extreme_bg_color = theme.sunken;
+
+    style.visuals.override_text_color = Some(theme.text);
+    style.visuals.hyperlink_color = theme.link;
+    style.visuals.warn_fg_color = theme.warning;
+    style.visuals.error_fg_color = theme.error;
+
+    // Button styling - Enhanced Epic Games style
+    style.visuals.widgets.inactive.bg_fill = theme.widget;
+    style.visuals.widgets.inactive.weak_bg_fill = theme.widget;
+    style.visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, theme.text);
+    style.visuals.widgets.inactive.rounding = Rounding::same(5.0);
+
+    style.visuals.widgets.hovered.bg_fill = theme.widget_hover;
+    style.visuals.widgets.hovered.weak_bg_fill = theme.widget_hover;
+    style.visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, theme.text_strong);
+    style.visuals.widgets.hovered.rounding = Rounding::same(5.0);
+
+    style.visuals.widgets.active.bg_fill = theme.accent;
+    style.visuals.widgets.active.weak_bg_fill = theme.accent;
+    style.visuals.widgets.active.fg_stroke = Stroke::new(1.0, theme.on_accent);
+    style.visuals.widgets.active.rounding = Rounding::same(5.0);
+
+    // Selection color (Epic Games blue in the dark theme)
+    style.visuals.selection.bg_fill = theme.accent;
+    style.visuals.selection.stroke = Stroke::new(1.5, theme.accent);
+
+    // Enhance spacing
+    style.spacing.item_spacing = egui::vec2(8.0, 8.0);
+    style.spacing.button_padding = egui::vec2(12.0, 6.0);
+
+    style
+}
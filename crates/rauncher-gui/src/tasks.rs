@@ -0,0 +1,168 @@
+//! Game operations run off the UI thread. Each one gets its own thread and a clone of the
+//! app's `GameManager`, and reports back over a channel the app drains every frame, so a slow
+//! uninstall or launch never freezes the window.
+
+use std::cell::Cell;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use rauncher_core::api::BuildInfo;
+use rauncher_core::games::{CrashReport, GameManager, InstalledGame, UninstallOptions};
+use rauncher_core::storage::Removal;
+use rauncher_core::{Error, Result};
+
+/// Outcome of a background operation
+pub enum TaskEvent {
+    /// The installed games as listed by the `generation`th request for them
+    InstalledGames {
+        generation: u64,
+        result: Result<Vec<InstalledGame>>,
+    },
+    Launched {
+        app: String,
+        result: Result<()>,
+    },
+    /// A launched game exited, with a crash report if it exited with an error
+    GameExited {
+        app: String,
+        result: Result<Option<CrashReport>>,
+    },
+    /// A running game was stopped; false when it had already exited
+    Stopped {
+        app: String,
+        result: Result<bool>,
+    },
+    Uninstalled {
+        app: String,
+        /// Where the game's files went
+        removal: Removal,
+        result: Result<()>,
+    },
+    /// Installed games with a newer build, and that build
+    Updates(Result<Vec<(InstalledGame, BuildInfo)>>),
+}
+
+pub struct Tasks {
+    sender: Sender<TaskEvent>,
+    receiver: Receiver<TaskEvent>,
+    ctx: egui::Context,
+    /// Requests for the installed games so far; only the answer to the last one is kept
+    installed_generation: Cell<u64>,
+}
+
+impl Tasks {
+    pub fn new(ctx: egui::Context) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver,
+            ctx,
+            installed_generation: Cell::new(0),
+        }
+    }
+
+    /// Events sent since the last call. Installed games listed before a later request for
+    /// them are dropped, so a slow listing can't overwrite a newer one.
+    pub fn poll(&self) -> Vec<TaskEvent> {
+        let latest = self.installed_generation.get();
+        self.receiver
+            .try_iter()
+            .filter(|event| {
+                !matches!(event, TaskEvent::InstalledGames { generation, .. } if *generation != latest)
+            })
+            .collect()
+    }
+
+    pub fn load_installed(&self, manager: GameManager) {
+        let generation = self.installed_generation.get() + 1;
+        self.installed_generation.set(generation);
+        self.spawn("load_installed", move |events| {
+            events.send(TaskEvent::InstalledGames {
+                generation,
+                result: manager.list_installed(),
+            });
+        });
+    }
+
+    /// Launch a game, then wait on it in the same thread to run its post-exit steps and
+    /// report how it exited
+    pub fn launch(&self, manager: GameManager, app: String) {
+        self.spawn("launch", move |events| match manager.launch_game(&app) {
+            Ok(running) => {
+                events.send(TaskEvent::Launched {
+                    app: app.clone(),
+                    result: Ok(()),
+                });
+                let game = running.game.clone();
+                let result = running.wait_for_exit().and_then(|exit| {
+                    let rt = tokio::runtime::Runtime::new().map_err(|e| {
+                        Error::Other(format!("Failed to run post-exit steps: {}", e))
+                    })?;
+                    rt.block_on(manager.after_exit(&game, &exit));
+                    Ok(exit.crash_report)
+                });
+                events.send(TaskEvent::GameExited { app, result });
+            }
+            Err(e) => events.send(TaskEvent::Launched {
+                app,
+                result: Err(e),
+            }),
+        });
+    }
+
+    /// Stop a running game and wait until it's gone
+    pub fn stop(&self, manager: GameManager, app: String) {
+        self.spawn("stop", move |events| {
+            let result = tokio::runtime::Runtime::new()
+                .map_err(|e| Error::Other(format!("Failed to stop game: {}", e)))
+                .and_then(|rt| rt.block_on(manager.stop_game(&app)));
+            events.send(TaskEvent::Stopped { app, result });
+        });
+    }
+
+    pub fn uninstall(&self, manager: GameManager, app: String, options: UninstallOptions) {
+        self.spawn("uninstall", move |events| {
+            let result = manager.uninstall_game_with_options(&app, &options);
+            events.send(TaskEvent::Uninstalled {
+                app,
+                removal: options.removal,
+                result,
+            });
+        });
+    }
+
+    /// Ask every installed game's store for a newer build
+    pub fn check_updates(&self, manager: GameManager) {
+        self.spawn("check_updates", move |events| {
+            let result = tokio::runtime::Runtime::new()
+                .map_err(|e| Error::Other(format!("Failed to start update check: {}", e)))
+                .and_then(|rt| rt.block_on(manager.pending_updates()));
+            events.send(TaskEvent::Updates(result));
+        });
+    }
+
+    fn spawn(&self, name: &str, task: impl FnOnce(&EventSender) + Send + 'static) {
+        let events = EventSender {
+            sender: self.sender.clone(),
+            ctx: self.ctx.clone(),
+        };
+        thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || task(&events))
+            .expect("Failed to spawn background task");
+    }
+}
+
+struct EventSender {
+    sender: Sender<TaskEvent>,
+    ctx: egui::Context,
+}
+
+impl EventSender {
+    /// Send an event and wake the UI up to handle it
+    fn send(&self, event: TaskEvent) {
+        if self.sender.send(event).is_ok() {
+            self.ctx.request_repaint();
+        }
+    }
+}
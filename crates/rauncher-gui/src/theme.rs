@@ -0,0 +1,311 @@
+//! Colors and font scaling of the GUI. Besides the built-in dark, light and high-contrast
+//! themes, users can drop TOML files into `<config dir>/themes`; a file only needs the colors
+//! it changes, the rest come from the built-in theme named by its `base` key:
+//!
+//! ```toml
+//! base = "dark"
+//! accent = "#e91e63"
+//! font_scale = 1.2
+//! ```
+//!
+//! The selected theme's file is watched, so edits show up without restarting.
+
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+use rauncher_core::config::Config;
+
+pub const BUILT_IN: [&str; 3] = ["dark", "light", "high-contrast"];
+
+/// How often the selected theme's file is checked for changes
+const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where the current theme is kept in egui's memory
+const THEME_ID: &str = "rauncher_theme";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    /// Start from egui's dark widget visuals rather than its light ones
+    pub dark: bool,
+    /// Primary buttons, selections and progress
+    #[serde(with = "hex")]
+    pub accent: Color32,
+    /// Text on accent-colored buttons
+    #[serde(with = "hex")]
+    pub on_accent: Color32,
+    /// Windows and dialogs
+    #[serde(with = "hex")]
+    pub background: Color32,
+    #[serde(with = "hex")]
+    pub panel: Color32,
+    /// Game cards, download and storage rows
+    #[serde(with = "hex")]
+    pub surface: Color32,
+    #[serde(with = "hex")]
+    pub surface_hover: Color32,
+    /// Text fields and progress bar tracks
+    #[serde(with = "hex")]
+    pub sunken: Color32,
+    #[serde(with = "hex")]
+    pub widget: Color32,
+    #[serde(with = "hex")]
+    pub widget_hover: Color32,
+    #[serde(with = "hex")]
+    pub text: Color32,
+    /// Titles
+    #[serde(with = "hex")]
+    pub text_strong: Color32,
+    /// Hints and secondary details
+    #[serde(with = "hex")]
+    pub text_weak: Color32,
+    #[serde(with = "hex")]
+    pub link: Color32,
+    #[serde(with = "hex")]
+    pub success: Color32,
+    #[serde(with = "hex")]
+    pub warning: Color32,
+    #[serde(with = "hex")]
+    pub error: Color32,
+    /// Multiplies every font size, e.g. 1.25 for a quarter larger text
+    pub font_scale: f32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Epic Games Store-inspired dark theme
+    pub fn dark() -> Self {
+        Self {
+            dark: true,
+            accent: Color32::from_rgb(0, 121, 214),
+            on_accent: Color32::WHITE,
+            background: Color32::from_rgb(16, 18, 22),
+            panel: Color32::from_rgb(22, 24, 28),
+            surface: Color32::from_rgb(28, 30, 34),
+            surface_hover: Color32::from_rgb(45, 50, 65),
+            sunken: Color32::from_rgb(12, 14, 18),
+            widget: Color32::from_rgb(50, 52, 58),
+            widget_hover: Color32::from_rgb(65, 68, 75),
+            text: Color32::from_rgb(245, 245, 245),
+            text_strong: Color32::WHITE,
+            text_weak: Color32::from_rgb(170, 170, 175),
+            link: Color32::from_rgb(100, 170, 230),
+            success: Color32::from_rgb(76, 175, 80),
+            warning: Color32::from_rgb(255, 183, 77),
+            error: Color32::from_rgb(244, 67, 54),
+            font_scale: 1.0,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            dark: false,
+            accent: Color32::from_rgb(0, 103, 192),
+            on_accent: Color32::WHITE,
+            background: Color32::from_rgb(250, 250, 252),
+            panel: Color32::from_rgb(238, 240, 243),
+            surface: Color32::WHITE,
+            surface_hover: Color32::from_rgb(222, 230, 244),
+            sunken: Color32::WHITE,
+            widget: Color32::from_rgb(222, 224, 228),
+            widget_hover: Color32::from_rgb(205, 208, 214),
+            text: Color32::from_rgb(30, 30, 34),
+            text_strong: Color32::BLACK,
+            text_weak: Color32::from_rgb(95, 98, 108),
+            link: Color32::from_rgb(0, 90, 170),
+            success: Color32::from_rgb(46, 125, 50),
+            warning: Color32::from_rgb(200, 110, 0),
+            error: Color32::from_rgb(198, 40, 40),
+            font_scale: 1.0,
+        }
+    }
+
+    /// Black and white with a yellow accent and slightly larger text
+    pub fn high_contrast() -> Self {
+        Self {
+            dark: true,
+            accent: Color32::from_rgb(255, 214, 0),
+            on_accent: Color32::BLACK,
+            background: Color32::BLACK,
+            panel: Color32::BLACK,
+            surface: Color32::from_rgb(18, 18, 18),
+            surface_hover: Color32::from_rgb(50, 50, 50),
+            sunken: Color32::BLACK,
+            widget: Color32::from_rgb(45, 45, 45),
+            widget_hover: Color32::from_rgb(85, 85, 85),
+            text: Color32::WHITE,
+            text_strong: Color32::WHITE,
+            text_weak: Color32::from_rgb(225, 225, 225),
+            link: Color32::from_rgb(0, 230, 255),
+            success: Color32::from_rgb(90, 255, 90),
+            warning: Color32::from_rgb(255, 170, 0),
+            error: Color32::from_rgb(255, 90, 90),
+            font_scale: 1.1,
+        }
+    }
+
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Read a theme file, filling in what it leaves out from its `base` theme
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let overrides: toml::Table = contents.parse().map_err(|e| format!("{}", e))?;
+        let base = match overrides.get("base") {
+            Some(toml::Value::String(name)) => {
+                Self::built_in(name).ok_or_else(|| format!("unknown base theme '{}'", name))?
+            }
+            Some(_) => return Err("base must be the name of a built-in theme".to_string()),
+            None => Self::dark(),
+        };
+
+        let mut table: toml::Table = toml::to_string(&base)
+            .map_err(|e| e.to_string())?
+            .parse()
+            .map_err(|e| format!("{}", e))?;
+        table.extend(overrides);
+        table.remove("base");
+        let theme: Self = toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("{}", e))?;
+
+        if !(0.5..=3.0).contains(&theme.font_scale) {
+            return Err(format!(
+                "font_scale must be between 0.5 and 3.0, not {}",
+                theme.font_scale
+            ));
+        }
+        Ok(theme)
+    }
+
+    /// The theme the views draw with
+    pub fn current(ctx: &egui::Context) -> Self {
+        ctx.data(|data| data.get_temp(egui::Id::new(THEME_ID)))
+            .unwrap_or_default()
+    }
+
+    /// Draw with this theme from the next frame on
+    pub fn apply(&self, ctx: &egui::Context) {
+        let previous = ctx.data(|data| data.get_temp::<Self>(egui::Id::new(THEME_ID)));
+        if previous.as_ref().map(|theme| theme.font_scale) != Some(self.font_scale) {
+            let mut fonts = egui::FontDefinitions::default();
+            for font in fonts.font_data.values_mut() {
+                font.tweak.scale *= self.font_scale;
+            }
+            ctx.set_fonts(fonts);
+        }
+
+        ctx.set_style(super::styles::style(self));
+        ctx.data_mut(|data| data.insert_temp(egui::Id::new(THEME_ID), self.clone()));
+    }
+}
+
+/// Directory holding the user's theme files
+pub fn themes_dir() -> Option<PathBuf> {
+    Config::config_dir().ok().map(|dir| dir.join("themes"))
+}
+
+/// The built-in themes, then the user's by file name
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILT_IN.iter().map(|name| name.to_string()).collect();
+
+    let mut custom: Vec<String> = themes_dir()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        })
+        .filter(|name| !BUILT_IN.contains(&name.as_str()))
+        .collect();
+    custom.sort();
+    names.extend(custom);
+
+    names
+}
+
+/// The theme selected in the configuration, reloaded when its file changes
+pub struct ThemeWatcher {
+    name: String,
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    checked_at: Instant,
+}
+
+impl ThemeWatcher {
+    /// Load the theme called `name`; the dark theme when unset
+    pub fn load(name: Option<&str>) -> (Self, Result<Theme, String>) {
+        let name = name.unwrap_or("dark").to_string();
+        let path = match Theme::built_in(&name) {
+            Some(_) => None,
+            None => themes_dir().map(|dir| dir.join(format!("{}.toml", name))),
+        };
+
+        let mut watcher = Self {
+            name,
+            path,
+            modified: None,
+            checked_at: Instant::now(),
+        };
+        let theme = watcher.read();
+        (watcher, theme)
+    }
+
+    /// The theme again if its file changed since it was read; looks at most once a second
+    pub fn poll(&mut self) -> Option<Result<Theme, String>> {
+        let path = self.path.as_ref()?;
+        if self.checked_at.elapsed() < RELOAD_INTERVAL {
+            return None;
+        }
+        self.checked_at = Instant::now();
+
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified == self.modified {
+            return None;
+        }
+        Some(self.read())
+    }
+
+    fn read(&mut self) -> Result<Theme, String> {
+        let Some(path) = &self.path else {
+            return Ok(Theme::built_in(&self.name).unwrap_or_default());
+        };
+
+        self.modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Theme::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Colors as `#rrggbb` or `#rrggbbaa` strings
+mod hex {
+    use egui::Color32;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&color.to_hex())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color32, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Color32::from_hex(&hex)
+            .map_err(|_| serde::de::Error::custom(format!("'{}' is not a #rrggbb color", hex)))
+    }
+}
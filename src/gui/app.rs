@@ -1,276 +0,0 @@
-use eframe::egui;
-use poll_promise::Promise;
-use std::sync::{Arc, Mutex};
-
-use crate::api::Game;
-use crate::auth::AuthManager;
-use crate::config::Config;
-use crate::games::{GameManager, InstalledGame};
-use crate::Result;
-
-use super::auth_view::AuthView;
-use super::library_view::{LibraryAction, LibraryView};
-use super::styles;
-use super::components::{Header, StatusBar};
-
-enum AppState {
-    Login,
-    Library,
-}
-
-pub struct LauncherApp {
-    state: AppState,
-    auth: Arc<Mutex<AuthManager>>,
-    config: Arc<Config>,
-    auth_view: AuthView,
-    library_view: LibraryView,
-    library_games: Vec<Game>,
-    installed_games: Vec<InstalledGame>,
-    status_message: String,
-    loading_library: bool,
-    library_promise: Option<Promise<Result<Vec<Game>>>>,
-    install_promises: Vec<(String, Promise<Result<()>>)>,
-}
-
-impl LauncherApp {
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
-        styles::setup_custom_style(&cc.egui_ctx);
-
-        let config = Config::load().unwrap_or_default();
-        let auth = AuthManager::new().unwrap_or_default();
-
-        // Check if already authenticated
-        let is_authenticated = auth.is_authenticated();
-
-        Self {
-            state: if is_authenticated {
-                AppState::Library
-            } else {
-                AppState::Login
-            },
-            auth: Arc::new(Mutex::new(auth)),
-            config: Arc::new(config),
-            auth_view: AuthView::default(),
-            library_view: LibraryView::default(),
-            library_games: Vec::new(),
-            installed_games: Vec::new(),
-            status_message: String::new(),
-            loading_library: false,
-            library_promise: None,
-            install_promises: Vec::new(),
-        }
-    }
-
-    fn handle_login(&mut self) {
-        self.state = AppState::Library;
-        self.load_library();
-        self.load_installed_games();
-    }
-
-    fn load_library(&mut self) {
-        if self.loading_library {
-            return;
-        }
-
-        self.loading_library = true;
-        self.status_message = "Loading library...".to_string();
-
-        // Usa GameManager per beneficiare dell'auto-refresh del token
-        let config = (*self.config).clone();
-        let auth = (*self.auth.lock().unwrap()).clone();
-
-        self.library_promise = Some(Promise::spawn_thread("load_library", move || {
-            let rt = tokio::runtime::Runtime::new()
-                .expect("Failed to create Tokio runtime for library load");
-            rt.block_on(async move {
-                match GameManager::new(config, auth) {
-                    Ok(mut manager) => manager.list_library().await,
-                    Err(e) => Err(e),
-                }
-            })
-        }));
-    }
-
-    fn load_installed_games(&mut self) {
-        if let Ok(manager) =
-            GameManager::new((*self.config).clone(), (*self.auth.lock().unwrap()).clone())
-        {
-            if let Ok(games) = manager.list_installed() {
-                self.installed_games = games;
-            }
-        }
-    }
-
-    fn handle_install(&mut self, app_name: String) {
-        // Esegue l'installazione in background e aggiorna la UI
-        let config = Arc::clone(&self.config);
-        let auth = Arc::clone(&self.auth);
-        let mut library_view = self.library_view.clone();
-        library_view.mark_installation_started(&app_name);
-        self.library_view = library_view.clone();
-        self.status_message = format!("Installazione avviata per {}...", app_name);
-
-        let app_name_clone = app_name.clone();
-        let promise = Promise::spawn_thread("install_game", move || {
-            let rt = tokio::runtime::Runtime::new()
-                .expect("Failed to create Tokio runtime for install");
-            let config = (*config).clone();
-            let auth = (*auth.lock().unwrap()).clone();
-            rt.block_on(async move {
-                match GameManager::new(config, auth) {
-                    Ok(mut manager) => manager.install_game(&app_name_clone).await,
-                    Err(e) => Err(e),
-                }
-            })
-        });
-
-        self.install_promises.push((app_name, promise));
-    }
-
-    fn handle_launch(&mut self, app_name: String) {
-        let config = (*self.config).clone();
-        let auth = (*self.auth.lock().unwrap()).clone();
-
-        match GameManager::new(config, auth) {
-            Ok(manager) => match manager.launch_game(&app_name) {
-                Ok(()) => {
-                    self.status_message = format!("Launched {}", app_name);
-                }
-                Err(e) => {
-                    self.status_message = format!("Failed to launch {}: {}", app_name, e);
-                }
-            },
-            Err(e) => {
-                self.status_message = format!("Error: {}", e);
-            }
-        }
-    }
-
-    fn handle_uninstall(&mut self, app_name: String) {
-        let config = (*self.config).clone();
-        let auth = (*self.auth.lock().unwrap()).clone();
-
-        match GameManager::new(config, auth) {
-            Ok(manager) => match manager.uninstall_game(&app_name) {
-                Ok(()) => {
-                    self.status_message = format!("Uninstalled {}", app_name);
-                    self.load_installed_games();
-                }
-                Err(e) => {
-                    self.status_message = format!("Failed to uninstall {}: {}", app_name, e);
-                }
-            },
-            Err(e) => {
-                self.status_message = format!("Error: {}", e);
-            }
-        }
-    }
-}
-
-impl eframe::App for LauncherApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Check for library loading completion
-        if let Some(promise) = &self.library_promise {
-            if let Some(result) = promise.ready() {
-                match result {
-                    Ok(games) => {
-                        self.library_games = games.clone();
-                        self.status_message = "Library loaded successfully".to_string();
-                    }
-                    Err(e) => {
-                        self.status_message = format!("Failed to load library: {}", e);
-                    }
-                }
-                self.loading_library = false;
-                self.library_promise = None;
-            }
-        }
-
-        egui::TopBottomPanel::top("top_panel")
-            .frame(egui::Frame::none()
-                .fill(egui::Color32::from_rgb(22, 24, 28))
-                .inner_margin(egui::Margin::symmetric(20.0, 15.0)))
-            .show(ctx, |ui| {
-                let mut logout_requested = false;
-                let is_authenticated = matches!(self.state, AppState::Library);
-                Header::show(ui, is_authenticated, &mut logout_requested);
-
-                if logout_requested {
-                    if let Ok(mut auth) = self.auth.lock() {
-                        let _ = auth.logout();
-                    }
-                    self.state = AppState::Login;
-                    self.library_games.clear();
-                    self.installed_games.clear();
-                }
-            });
-
-        egui::CentralPanel::default().show(ctx, |ui| {
-            match self.state {
-                AppState::Login => {
-                    if self.auth_view.ui(ui, &mut self.auth.lock().unwrap()) {
-                        self.handle_login();
-                    }
-                }
-                AppState::Library => {
-                    if let Some(action) =
-                        self.library_view
-                            .ui(ui, &self.library_games, &self.installed_games)
-                    {
-                        match action {
-                            LibraryAction::Install(app_name) => {
-                                self.handle_install(app_name);
-                            }
-                            LibraryAction::Launch(app_name) => {
-                                self.handle_launch(app_name);
-                            }
-                            LibraryAction::Uninstall(app_name) => {
-                                self.handle_uninstall(app_name);
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Status bar at bottom using StatusBar component
-            let mut clear_status = false;
-            StatusBar::show(ui, &self.status_message, &mut clear_status);
-            if clear_status {
-                self.status_message.clear();
-            }
-        });
-
-        // Gestisci completamento installazioni senza mutare self durante l'iterazione
-        let mut completed: Vec<(usize, String, Option<String>)> = Vec::new();
-        for (idx, (app_name, p)) in self.install_promises.iter().enumerate() {
-            if let Some(result) = p.ready() {
-                match result {
-                    Ok(()) => completed.push((idx, app_name.clone(), None)),
-                    Err(e) => completed.push((idx, app_name.clone(), Some(format!("{}", e)))),
-                }
-            }
-        }
-        let mut need_reload_installed = false;
-        for (idx, app_name, maybe_err) in completed.into_iter().rev() {
-            // rimuovi promise completata
-            let _ = self.install_promises.remove(idx);
-            match maybe_err {
-                None => {
-                    self.status_message = format!("Installazione completata per {}", app_name);
-                    self.library_view.mark_installation_complete(&app_name);
-                    need_reload_installed = true;
-                }
-                Some(err) => {
-                    self.status_message = format!("Installazione fallita per {}: {}", app_name, err);
-                    self.library_view.mark_installation_complete(&app_name);
-                }
-            }
-        }
-        if need_reload_installed {
-            self.load_installed_games();
-        }
-
-        // Request repaint for animations/updates
-        ctx.request_repaint_after(std::time::Duration::from_millis(100));
-    }
-}
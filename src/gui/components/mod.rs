@@ -1,10 +0,0 @@
-// GUI Components module
-mod header;
-mod game_card;
-mod status_bar;
-mod search_bar;
-
-pub use header::Header;
-pub use game_card::{GameCard, GameCardAction};
-pub use status_bar::StatusBar;
-pub use search_bar::{SearchBar, GameFilter};
@@ -1,7 +0,0 @@
-mod app;
-mod auth_view;
-mod library_view;
-mod styles;
-mod components;
-
-pub use app::LauncherApp;